@@ -4,6 +4,7 @@
 //! for audit log entries.
 
 use serde_json::Value;
+use std::fmt;
 
 /// Generate a human-readable diff between two JSON values
 ///
@@ -81,10 +82,44 @@ fn format_value(value: &Value) -> String {
     }
 }
 
+/// A single field-level change between two JSON values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FieldChange {
+    /// Dotted path to the field (e.g., `splits[0].amount`)
+    pub path: String,
+    /// Formatted value before the change (None if the field was added)
+    pub before: Option<String>,
+    /// Formatted value after the change (None if the field was removed)
+    pub after: Option<String>,
+}
+
+impl fmt::Display for FieldChange {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} -> {}",
+            self.path,
+            self.before.as_deref().unwrap_or("(added)"),
+            self.after.as_deref().unwrap_or("(removed)")
+        )
+    }
+}
+
 /// Generate a detailed diff that includes nested changes
 ///
 /// More verbose than `generate_diff`, useful for detailed auditing.
 pub fn generate_detailed_diff(before: &Value, after: &Value, prefix: &str) -> Vec<String> {
+    detailed_changes(before, after, prefix)
+        .iter()
+        .map(|change| change.to_string())
+        .collect()
+}
+
+/// Generate structured field changes, recursing into nested values
+///
+/// The structured form of `generate_detailed_diff`, for callers that render
+/// the before and after sides separately.
+pub fn detailed_changes(before: &Value, after: &Value, prefix: &str) -> Vec<FieldChange> {
     let mut changes = Vec::new();
 
     match (before, after) {
@@ -101,26 +136,21 @@ pub fn generate_detailed_diff(before: &Value, after: &Value, prefix: &str) -> Ve
                     if before_val != after_val {
                         // Recurse for nested objects
                         if before_val.is_object() && after_val.is_object() {
-                            changes.extend(generate_detailed_diff(
-                                before_val,
-                                after_val,
-                                &field_prefix,
-                            ));
+                            changes.extend(detailed_changes(before_val, after_val, &field_prefix));
                         } else {
-                            changes.push(format!(
-                                "{}: {} -> {}",
-                                field_prefix,
-                                format_value(before_val),
-                                format_value(after_val)
-                            ));
+                            changes.push(FieldChange {
+                                path: field_prefix,
+                                before: Some(format_value(before_val)),
+                                after: Some(format_value(after_val)),
+                            });
                         }
                     }
                 } else {
-                    changes.push(format!(
-                        "{}: {} -> (removed)",
-                        field_prefix,
-                        format_value(before_val)
-                    ));
+                    changes.push(FieldChange {
+                        path: field_prefix,
+                        before: Some(format_value(before_val)),
+                        after: None,
+                    });
                 }
             }
 
@@ -132,39 +162,37 @@ pub fn generate_detailed_diff(before: &Value, after: &Value, prefix: &str) -> Ve
                     } else {
                         format!("{}.{}", prefix, key)
                     };
-                    changes.push(format!(
-                        "{}: (added) -> {}",
-                        field_prefix,
-                        format_value(after_val)
-                    ));
+                    changes.push(FieldChange {
+                        path: field_prefix,
+                        before: None,
+                        after: Some(format_value(after_val)),
+                    });
                 }
             }
         }
         (Value::Array(before_arr), Value::Array(after_arr)) => {
             if before_arr.len() != after_arr.len() {
-                changes.push(format!(
-                    "{}: [{} items] -> [{} items]",
-                    prefix,
-                    before_arr.len(),
-                    after_arr.len()
-                ));
+                changes.push(FieldChange {
+                    path: prefix.to_string(),
+                    before: Some(format_value(before)),
+                    after: Some(format_value(after)),
+                });
             } else {
                 for (i, (b, a)) in before_arr.iter().zip(after_arr.iter()).enumerate() {
                     if b != a {
                         let item_prefix = format!("{}[{}]", prefix, i);
-                        changes.extend(generate_detailed_diff(b, a, &item_prefix));
+                        changes.extend(detailed_changes(b, a, &item_prefix));
                     }
                 }
             }
         }
         _ => {
             if before != after {
-                changes.push(format!(
-                    "{}: {} -> {}",
-                    prefix,
-                    format_value(before),
-                    format_value(after)
-                ));
+                changes.push(FieldChange {
+                    path: prefix.to_string(),
+                    before: Some(format_value(before)),
+                    after: Some(format_value(after)),
+                });
             }
         }
     }
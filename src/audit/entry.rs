@@ -6,6 +6,8 @@
 use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 
+use crate::config::clock;
+
 /// Types of operations that can be audited
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 #[serde(rename_all = "lowercase")]
@@ -40,6 +42,7 @@ pub enum EntityType {
     BudgetTarget,
     Payee,
     IncomeExpectation,
+    ScheduledTransaction,
 }
 
 impl std::fmt::Display for EntityType {
@@ -53,6 +56,7 @@ impl std::fmt::Display for EntityType {
             EntityType::BudgetTarget => write!(f, "BudgetTarget"),
             EntityType::Payee => write!(f, "Payee"),
             EntityType::IncomeExpectation => write!(f, "IncomeExpectation"),
+            EntityType::ScheduledTransaction => write!(f, "ScheduledTransaction"),
         }
     }
 }
@@ -101,7 +105,7 @@ impl AuditEntry {
         entity: &T,
     ) -> Self {
         Self {
-            timestamp: Utc::now(),
+            timestamp: clock::now(),
             operation: Operation::Create,
             entity_type,
             entity_id: entity_id.into(),
@@ -122,7 +126,7 @@ impl AuditEntry {
         diff_summary: Option<String>,
     ) -> Self {
         Self {
-            timestamp: Utc::now(),
+            timestamp: clock::now(),
             operation: Operation::Update,
             entity_type,
             entity_id: entity_id.into(),
@@ -141,7 +145,7 @@ impl AuditEntry {
         entity: &T,
     ) -> Self {
         Self {
-            timestamp: Utc::now(),
+            timestamp: clock::now(),
             operation: Operation::Delete,
             entity_type,
             entity_id: entity_id.into(),
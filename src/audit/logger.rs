@@ -2,45 +2,198 @@
 //!
 //! Provides the AuditLogger struct that writes audit entries to a log file.
 //! Each entry is written as a single JSON line and flushed immediately.
+//! With an encryption key set, each line is encrypted on its own so the log
+//! stays append-only.
 
-use std::fs::{File, OpenOptions};
+use std::fs::{self, File, OpenOptions};
 use std::io::{BufRead, BufReader, Write};
 use std::path::PathBuf;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Mutex, RwLock};
 
+use zeroize::Zeroizing;
+
+use crate::crypto::{decrypt_string, encrypt_string, DerivedKey, EncryptedData};
 use crate::error::{EnvelopeError, EnvelopeResult};
 
 use super::entry::AuditEntry;
 
+/// Prefix marking a line that holds an encrypted entry
+const ENCRYPTED_PREFIX: &str = "enc:";
+
+/// Supplies the key the first time the log needs one, e.g. by prompting
+pub type KeySource = Box<dyn Fn() -> EnvelopeResult<DerivedKey> + Send + Sync>;
+
 /// Handles writing audit entries to the audit log file
 ///
 /// The log file uses a line-delimited JSON format (JSONL) where each line
-/// is a complete JSON object representing one audit entry.
+/// is a complete JSON object representing one audit entry. When a key is
+/// set, new lines are `enc:` followed by the entry's encrypted JSON; lines
+/// written before encryption was turned on are still read as plain JSON.
 pub struct AuditLogger {
     /// Path to the audit log file
     log_path: PathBuf,
+    /// Key for encrypting new entries and decrypting existing ones
+    key: RwLock<Option<DerivedKey>>,
+    /// Set once the key has been dropped by [`AuditLogger::lock`]
+    locked: AtomicBool,
+    /// Where to get the key when it's first needed, until it has been got
+    key_source: Mutex<Option<KeySource>>,
 }
 
 impl AuditLogger {
     /// Create a new AuditLogger that writes to the specified path
     pub fn new(log_path: PathBuf) -> Self {
-        Self { log_path }
+        Self {
+            log_path,
+            key: RwLock::new(None),
+            locked: AtomicBool::new(false),
+            key_source: Mutex::new(None),
+        }
+    }
+
+    /// Get the key from `source` only once an entry is written or an
+    /// encrypted line is read
+    ///
+    /// Lets a command that never touches the log skip the passphrase. Until
+    /// the source succeeds, new entries are refused rather than written in
+    /// plaintext, and a failed attempt is retried on the next access.
+    pub fn set_key_source(&self, source: KeySource) {
+        *self.key_source.lock().unwrap_or_else(|e| e.into_inner()) = Some(source);
+    }
+
+    /// Fetch the key from the key source if one is waiting to be asked
+    fn ensure_key(&self) -> EnvelopeResult<()> {
+        let mut source = self.key_source.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(get_key) = source.as_ref() {
+            let key = get_key()?;
+            *source = None;
+            *self.key.write().unwrap_or_else(|e| e.into_inner()) = Some(key);
+            self.locked.store(false, Ordering::SeqCst);
+        }
+        Ok(())
+    }
+
+    /// Set the key used to encrypt new entries, or `None` for plaintext
+    pub fn set_key(&self, key: Option<DerivedKey>) {
+        *self.key_source.lock().unwrap_or_else(|e| e.into_inner()) = None;
+        *self.key.write().unwrap_or_else(|e| e.into_inner()) = key;
+        self.locked.store(false, Ordering::SeqCst);
+    }
+
+    /// Drop the key so it doesn't stay in memory after it's needed
+    ///
+    /// The key is zeroized as it's dropped. Until a key is set again, new
+    /// entries are refused rather than written in plaintext. Does nothing
+    /// when no key is set.
+    pub fn lock(&self) {
+        let mut key = self.key.write().unwrap_or_else(|e| e.into_inner());
+        if key.take().is_some() {
+            self.locked.store(true, Ordering::SeqCst);
+        }
+    }
+
+    /// Check whether new entries are encrypted
+    pub fn is_encrypted(&self) -> bool {
+        self.key.read().unwrap_or_else(|e| e.into_inner()).is_some()
+    }
+
+    /// Rewrite the whole log under a new key, or in plaintext with `None`
+    ///
+    /// Existing entries are read with the current key, written to a
+    /// temporary file, and swapped in with a rename, so a failure leaves the
+    /// old log intact. Used when encryption is turned on or off or the key
+    /// changes; the new key is used for every entry after.
+    pub fn rekey(&self, new_key: Option<DerivedKey>) -> EnvelopeResult<()> {
+        let entries = self.read_all()?;
+        self.set_key(new_key);
+        if entries.is_empty() {
+            return Ok(());
+        }
+
+        let temp_path = self.log_path.with_extension("log.tmp");
+        let mut file = File::create(&temp_path)
+            .map_err(|e| EnvelopeError::Io(format!("Failed to create audit log: {}", e)))?;
+        for entry in &entries {
+            writeln!(file, "{}", self.encode_line(entry)?)
+                .map_err(|e| EnvelopeError::Io(format!("Failed to write audit entry: {}", e)))?;
+        }
+        file.sync_all()
+            .map_err(|e| EnvelopeError::Io(format!("Failed to flush audit log: {}", e)))?;
+
+        fs::rename(&temp_path, &self.log_path)
+            .map_err(|e| EnvelopeError::Io(format!("Failed to replace audit log: {}", e)))
+    }
+
+    /// Serialize an entry as one log line, encrypted if a key is set
+    fn encode_line(&self, entry: &AuditEntry) -> EnvelopeResult<String> {
+        self.ensure_key()?;
+        let json = serde_json::to_string(entry)
+            .map_err(|e| EnvelopeError::Json(format!("Failed to serialize audit entry: {}", e)))?;
+
+        let key = self.key.read().unwrap_or_else(|e| e.into_inner());
+        match key.as_ref() {
+            None if self.locked.load(Ordering::SeqCst) => Err(EnvelopeError::Encryption(
+                "Audit log is locked; a passphrase is needed to write to it".to_string(),
+            )),
+            Some(key) => {
+                // The plaintext doesn't outlive its encryption
+                let json = Zeroizing::new(json);
+                let encrypted =
+                    serde_json::to_string(&encrypt_string(&json, key)?).map_err(|e| {
+                        EnvelopeError::Json(format!("Failed to serialize audit entry: {}", e))
+                    })?;
+                Ok(format!("{}{}", ENCRYPTED_PREFIX, encrypted))
+            }
+            None => Ok(json),
+        }
+    }
+
+    /// Parse one log line, decrypting it if needed
+    fn decode_line(&self, line: &str, line_num: usize) -> EnvelopeResult<AuditEntry> {
+        let json = match line.strip_prefix(ENCRYPTED_PREFIX) {
+            Some(encrypted) => {
+                self.ensure_key()?;
+                let key = self.key.read().unwrap_or_else(|e| e.into_inner());
+                let key = key.as_ref().ok_or_else(|| {
+                    EnvelopeError::Encryption(format!(
+                        "Audit log line {} is encrypted; a passphrase is needed to read it",
+                        line_num
+                    ))
+                })?;
+                let encrypted: EncryptedData = serde_json::from_str(encrypted).map_err(|e| {
+                    EnvelopeError::Json(format!(
+                        "Failed to parse audit entry at line {}: {}",
+                        line_num, e
+                    ))
+                })?;
+                Zeroizing::new(decrypt_string(&encrypted, key)?)
+            }
+            None => Zeroizing::new(line.to_string()),
+        };
+
+        serde_json::from_str(&json).map_err(|e| {
+            EnvelopeError::Json(format!(
+                "Failed to parse audit entry at line {}: {}",
+                line_num, e
+            ))
+        })
     }
 
     /// Log an audit entry
     ///
-    /// Appends the entry as a JSON line to the audit log file.
+    /// Appends the entry as a JSON line to the audit log file, encrypted
+    /// when a key is set.
     /// Each write is flushed immediately to ensure durability.
     pub fn log(&self, entry: &AuditEntry) -> EnvelopeResult<()> {
+        let line = self.encode_line(entry)?;
         let mut file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&self.log_path)
             .map_err(|e| EnvelopeError::Io(format!("Failed to open audit log: {}", e)))?;
 
-        let json = serde_json::to_string(entry)
-            .map_err(|e| EnvelopeError::Json(format!("Failed to serialize audit entry: {}", e)))?;
-
-        writeln!(file, "{}", json)
+        writeln!(file, "{}", line)
             .map_err(|e| EnvelopeError::Io(format!("Failed to write audit entry: {}", e)))?;
 
         file.flush()
@@ -57,18 +210,19 @@ impl AuditLogger {
             return Ok(());
         }
 
+        let lines = entries
+            .iter()
+            .map(|entry| self.encode_line(entry))
+            .collect::<EnvelopeResult<Vec<_>>>()?;
+
         let mut file = OpenOptions::new()
             .create(true)
             .append(true)
             .open(&self.log_path)
             .map_err(|e| EnvelopeError::Io(format!("Failed to open audit log: {}", e)))?;
 
-        for entry in entries {
-            let json = serde_json::to_string(entry).map_err(|e| {
-                EnvelopeError::Json(format!("Failed to serialize audit entry: {}", e))
-            })?;
-
-            writeln!(file, "{}", json)
+        for line in lines {
+            writeln!(file, "{}", line)
                 .map_err(|e| EnvelopeError::Io(format!("Failed to write audit entry: {}", e)))?;
         }
 
@@ -106,15 +260,7 @@ impl AuditLogger {
                 continue;
             }
 
-            let entry: AuditEntry = serde_json::from_str(&line).map_err(|e| {
-                EnvelopeError::Json(format!(
-                    "Failed to parse audit entry at line {}: {}",
-                    line_num + 1,
-                    e
-                ))
-            })?;
-
-            entries.push(entry);
+            entries.push(self.decode_line(&line, line_num + 1)?);
         }
 
         Ok(entries)
@@ -127,6 +273,19 @@ impl AuditLogger {
         Ok(all_entries[start..].to_vec())
     }
 
+    /// Read all entries for a single entity, oldest first
+    ///
+    /// Matches the logged entity ID exactly, or by its hex part so that
+    /// `1a2b3c4d` finds `txn-1a2b3c4d`.
+    pub fn read_for_entity(&self, entity_id: &str) -> EnvelopeResult<Vec<AuditEntry>> {
+        let suffix = format!("-{}", entity_id);
+        Ok(self
+            .read_all()?
+            .into_iter()
+            .filter(|e| e.entity_id == entity_id || e.entity_id.ends_with(&suffix))
+            .collect())
+    }
+
     /// Get the number of entries in the audit log
     pub fn entry_count(&self) -> EnvelopeResult<usize> {
         if !self.log_path.exists() {
@@ -157,6 +316,7 @@ impl AuditLogger {
 mod tests {
     use super::*;
     use crate::audit::entry::{EntityType, Operation};
+    use crate::crypto::{derive_key, KdfCosts, KeyDerivationParams};
     use serde_json::json;
     use tempfile::TempDir;
 
@@ -258,6 +418,20 @@ mod tests {
         assert_eq!(recent[2].entity_id, "acc-9");
     }
 
+    #[test]
+    fn test_read_for_entity() {
+        let (logger, _temp) = create_test_logger();
+
+        for id in ["acc-12345678", "acc-87654321", "acc-12345678"] {
+            let entry = AuditEntry::create(EntityType::Account, id, None, &json!({"id": id}));
+            logger.log(&entry).unwrap();
+        }
+
+        assert_eq!(logger.read_for_entity("acc-12345678").unwrap().len(), 2);
+        assert_eq!(logger.read_for_entity("87654321").unwrap().len(), 1);
+        assert!(logger.read_for_entity("2345678").unwrap().is_empty());
+    }
+
     #[test]
     fn test_empty_log() {
         let (logger, _temp) = create_test_logger();
@@ -328,4 +502,106 @@ mod tests {
         let entries = logger2.read_all().unwrap();
         assert_eq!(entries.len(), 1);
     }
+
+    fn test_key() -> DerivedKey {
+        let params = KeyDerivationParams::with_costs(&KdfCosts {
+            memory_cost: 8192,
+            time_cost: 1,
+            parallelism: 1,
+        });
+        derive_key("test_passphrase", &params).unwrap()
+    }
+
+    #[test]
+    fn test_encrypted_entries() {
+        let (logger, temp) = create_test_logger();
+
+        // Written before encryption was turned on
+        logger.log(&create_test_entry()).unwrap();
+
+        logger.set_key(Some(test_key()));
+        logger.log(&create_test_entry()).unwrap();
+
+        let contents = fs::read_to_string(temp.path().join("audit.log")).unwrap();
+        let lines: Vec<_> = contents.lines().collect();
+        assert!(lines[0].contains("Test Account"));
+        assert!(lines[1].starts_with(ENCRYPTED_PREFIX));
+        assert!(!lines[1].contains("Test Account"));
+
+        // Both read back transparently
+        let entries = logger.read_recent(10).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].entity_name.as_deref(), Some("Test Account"));
+
+        // Without the key, encrypted lines can't be read
+        let locked = AuditLogger::new(temp.path().join("audit.log"));
+        assert!(matches!(
+            locked.read_all(),
+            Err(EnvelopeError::Encryption(_))
+        ));
+    }
+
+    #[test]
+    fn test_lock_refuses_plaintext() {
+        let (logger, temp) = create_test_logger();
+        logger.set_key(Some(test_key()));
+        logger.log(&create_test_entry()).unwrap();
+
+        logger.lock();
+        assert!(matches!(
+            logger.log(&create_test_entry()),
+            Err(EnvelopeError::Encryption(_))
+        ));
+        let contents = fs::read_to_string(temp.path().join("audit.log")).unwrap();
+        assert_eq!(contents.lines().count(), 1);
+        assert!(logger.read_all().is_err());
+    }
+
+    #[test]
+    fn test_key_source_asked_on_first_use() {
+        use std::sync::atomic::AtomicUsize;
+        use std::sync::Arc;
+
+        let (logger, temp) = create_test_logger();
+        let asked = Arc::new(AtomicUsize::new(0));
+        let counter = Arc::clone(&asked);
+        logger.set_key_source(Box::new(move || {
+            // The first attempt fails, like a wrong passphrase
+            if counter.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(EnvelopeError::Encryption("Invalid passphrase".into()))
+            } else {
+                Ok(test_key())
+            }
+        }));
+        assert_eq!(asked.load(Ordering::SeqCst), 0);
+
+        // Nothing is written in plaintext while the key is missing
+        assert!(logger.log(&create_test_entry()).is_err());
+        assert!(!temp.path().join("audit.log").exists());
+
+        logger.log(&create_test_entry()).unwrap();
+        logger.log(&create_test_entry()).unwrap();
+        assert_eq!(asked.load(Ordering::SeqCst), 2);
+        assert_eq!(logger.read_all().unwrap().len(), 2);
+        let contents = fs::read_to_string(temp.path().join("audit.log")).unwrap();
+        assert!(contents.lines().all(|l| l.starts_with(ENCRYPTED_PREFIX)));
+    }
+
+    #[test]
+    fn test_rekey() {
+        let (logger, temp) = create_test_logger();
+        logger.log(&create_test_entry()).unwrap();
+        logger.log(&create_test_entry()).unwrap();
+
+        logger.rekey(Some(test_key())).unwrap();
+        assert!(logger.is_encrypted());
+        let contents = fs::read_to_string(temp.path().join("audit.log")).unwrap();
+        assert!(contents.lines().all(|l| l.starts_with(ENCRYPTED_PREFIX)));
+        assert_eq!(logger.read_all().unwrap().len(), 2);
+
+        logger.rekey(None).unwrap();
+        let contents = fs::read_to_string(temp.path().join("audit.log")).unwrap();
+        assert!(contents.lines().all(|l| l.starts_with('{')));
+        assert_eq!(logger.read_all().unwrap().len(), 2);
+    }
 }
@@ -48,6 +48,6 @@ mod diff;
 mod entry;
 mod logger;
 
-pub use diff::{generate_detailed_diff, generate_diff};
+pub use diff::{detailed_changes, generate_detailed_diff, generate_diff, FieldChange};
 pub use entry::{AuditEntry, EntityType, Operation};
-pub use logger::AuditLogger;
+pub use logger::{AuditLogger, KeySource};
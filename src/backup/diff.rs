@@ -0,0 +1,385 @@
+//! Comparing backups against live data
+//!
+//! Loads a backup (internal or export format) and the current data files,
+//! and reports which entities were added, removed, or modified since the
+//! backup was taken, or which restoring it would add, remove, or modify.
+//! Nothing is written.
+
+use std::collections::BTreeMap;
+use std::path::Path;
+
+use chrono::{DateTime, Utc};
+use serde_json::Value;
+
+use crate::audit::generate_diff;
+use crate::config::paths::EnvelopePaths;
+use crate::error::{EnvelopeError, EnvelopeResult};
+
+use super::manager::{is_missing_file, read_json_value};
+use super::restore::{read_backup_file, BackupFileFormat};
+
+/// An entity that differs between a backup and the live data
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct EntityChange {
+    /// Entity ID
+    pub id: String,
+    /// Human-readable label (name, or date/payee/amount for transactions)
+    pub label: String,
+    /// Field-level changes, for modified entities
+    pub details: Option<String>,
+}
+
+/// Changes to one entity type
+///
+/// Going from the older state to the newer one: the backup to the live data
+/// for [`BackupDiff::generate`], and the reverse for
+/// [`BackupDiff::restore_plan`].
+#[derive(Debug, Clone)]
+pub struct EntityDiff {
+    /// Entity type name (e.g., "Accounts")
+    pub entity_type: &'static str,
+    /// Entities only in the newer state
+    pub added: Vec<EntityChange>,
+    /// Entities only in the older state
+    pub removed: Vec<EntityChange>,
+    /// Entities whose fields differ between the two
+    pub modified: Vec<EntityChange>,
+}
+
+impl EntityDiff {
+    /// Check if nothing changed for this entity type
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.modified.is_empty()
+    }
+}
+
+/// Differences between a backup and the live data
+#[derive(Debug, Clone)]
+pub struct BackupDiff {
+    /// When the backup was created
+    pub backup_date: DateTime<Utc>,
+    /// Changes grouped by entity type
+    pub entities: Vec<EntityDiff>,
+}
+
+impl BackupDiff {
+    /// Compare a backup file against the live data
+    pub fn generate(paths: &EnvelopePaths, backup_path: &Path) -> EnvelopeResult<Self> {
+        Self::compare(paths, backup_path, false)
+    }
+
+    /// What restoring a backup file would change in the live data
+    ///
+    /// The reverse of [`Self::generate`]: `added` entities are only in the
+    /// backup and would come back, `removed` ones exist now and would be
+    /// lost, and field changes read from the live value to the backup's.
+    pub fn restore_plan(paths: &EnvelopePaths, backup_path: &Path) -> EnvelopeResult<Self> {
+        Self::compare(paths, backup_path, true)
+    }
+
+    fn compare(paths: &EnvelopePaths, backup_path: &Path, restoring: bool) -> EnvelopeResult<Self> {
+        let accounts = read_json_value(&paths.accounts_file())?;
+        let transactions = read_json_value(&paths.transactions_file())?;
+        let budget = read_json_value(&paths.budget_file())?;
+        let payees = read_json_value(&paths.payees_file())?;
+        let current = Collections::from_files(&accounts, &transactions, &budget, &payees);
+
+        // A restore leaves alone the files the backup has no copy of
+        let file = |backed_up: &Value, live: &Value| {
+            if restoring && is_missing_file(backed_up) {
+                live.clone()
+            } else {
+                backed_up.clone()
+            }
+        };
+
+        let (backup_date, backup) = match read_backup_file(backup_path)? {
+            BackupFileFormat::Backup(archive) => (
+                archive.created_at,
+                Collections::from_files(
+                    &file(&archive.accounts, &accounts),
+                    &file(&archive.transactions, &transactions),
+                    &file(&archive.budget, &budget),
+                    &file(&archive.payees, &payees),
+                ),
+            ),
+            BackupFileFormat::Export(export) => {
+                let to_value = |value: Result<Value, serde_json::Error>| {
+                    value.map_err(|e| {
+                        EnvelopeError::Json(format!("Failed to read export data: {}", e))
+                    })
+                };
+                (
+                    export.exported_at,
+                    Collections {
+                        accounts: to_value(serde_json::to_value(&export.accounts))?,
+                        groups: to_value(serde_json::to_value(&export.category_groups))?,
+                        categories: to_value(serde_json::to_value(&export.categories))?,
+                        transactions: to_value(serde_json::to_value(&export.transactions))?,
+                        payees: to_value(serde_json::to_value(&export.payees))?,
+                    },
+                )
+            }
+        };
+
+        let (before, after) = if restoring {
+            (&current, &backup)
+        } else {
+            (&backup, &current)
+        };
+        let entities = vec![
+            diff_entities("Accounts", &before.accounts, &after.accounts),
+            diff_entities("Category groups", &before.groups, &after.groups),
+            diff_entities("Categories", &before.categories, &after.categories),
+            diff_entities("Transactions", &before.transactions, &after.transactions),
+            diff_entities("Payees", &before.payees, &after.payees),
+        ];
+
+        Ok(Self {
+            backup_date,
+            entities,
+        })
+    }
+
+    /// Check if the live data matches the backup
+    pub fn is_empty(&self) -> bool {
+        self.entities.iter().all(EntityDiff::is_empty)
+    }
+}
+
+/// Entity lists pulled out of the data files
+struct Collections {
+    accounts: Value,
+    groups: Value,
+    categories: Value,
+    transactions: Value,
+    payees: Value,
+}
+
+impl Collections {
+    fn from_files(accounts: &Value, transactions: &Value, budget: &Value, payees: &Value) -> Self {
+        let field = |file: &Value, key: &str| file.get(key).cloned().unwrap_or(Value::Null);
+        Self {
+            accounts: field(accounts, "accounts"),
+            groups: field(budget, "groups"),
+            categories: field(budget, "categories"),
+            transactions: field(transactions, "transactions"),
+            payees: field(payees, "payees"),
+        }
+    }
+}
+
+/// Compare two entity lists by ID
+fn diff_entities(entity_type: &'static str, before: &Value, after: &Value) -> EntityDiff {
+    let before = index_by_id(before);
+    let after = index_by_id(after);
+
+    let mut diff = EntityDiff {
+        entity_type,
+        added: Vec::new(),
+        removed: Vec::new(),
+        modified: Vec::new(),
+    };
+
+    for (id, old) in &before {
+        match after.get(id) {
+            Some(new) => {
+                if let Some(details) = generate_diff(old, new) {
+                    diff.modified.push(EntityChange {
+                        id: id.clone(),
+                        label: entity_label(new),
+                        details: Some(details),
+                    });
+                }
+            }
+            None => diff.removed.push(EntityChange {
+                id: id.clone(),
+                label: entity_label(old),
+                details: None,
+            }),
+        }
+    }
+
+    for (id, new) in &after {
+        if !before.contains_key(id) {
+            diff.added.push(EntityChange {
+                id: id.clone(),
+                label: entity_label(new),
+                details: None,
+            });
+        }
+    }
+
+    diff
+}
+
+/// Index an array of entities by their `id` field
+fn index_by_id(entities: &Value) -> BTreeMap<String, &Value> {
+    entities
+        .as_array()
+        .map(|items| {
+            items
+                .iter()
+                .filter_map(|item| {
+                    let id = item.get("id")?.as_str()?;
+                    Some((id.to_string(), item))
+                })
+                .collect()
+        })
+        .unwrap_or_default()
+}
+
+/// A short label for an entity
+fn entity_label(entity: &Value) -> String {
+    if let Some(name) = entity.get("name").and_then(Value::as_str) {
+        return name.to_string();
+    }
+
+    // Transactions have no name; describe them by date, payee, and amount
+    let amount = entity
+        .get("amount")
+        .and_then(Value::as_i64)
+        .map(|cents| crate::models::Money::from_cents(cents).to_string())
+        .unwrap_or_default();
+    [
+        text_field(entity, "date"),
+        text_field(entity, "payee_name"),
+        &amount,
+    ]
+    .iter()
+    .filter(|part| !part.is_empty())
+    .copied()
+    .collect::<Vec<_>>()
+    .join(" ")
+}
+
+/// A string field of an entity, or empty if missing
+fn text_field<'a>(entity: &'a Value, key: &str) -> &'a str {
+    entity.get(key).and_then(Value::as_str).unwrap_or("")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::backup::BackupManager;
+    use crate::config::settings::BackupRetention;
+    use crate::models::{Account, AccountType, Money, Transaction};
+    use crate::storage::Storage;
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_backup_diff() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let mut storage = Storage::new(paths.clone()).unwrap();
+        storage.load_all().unwrap();
+
+        let mut checking = Account::new("Checking", AccountType::Checking);
+        let savings = Account::new("Savings", AccountType::Savings);
+        storage.accounts.upsert(checking.clone()).unwrap();
+        storage.accounts.upsert(savings.clone()).unwrap();
+        storage.accounts.save().unwrap();
+
+        let backup_path = BackupManager::new(paths.clone(), BackupRetention::default())
+            .create_backup()
+            .unwrap();
+        assert!(BackupDiff::generate(&paths, &backup_path)
+            .unwrap()
+            .is_empty());
+
+        // Rename one account, delete another, and add a transaction
+        checking.name = "Main Checking".to_string();
+        storage.accounts.upsert(checking.clone()).unwrap();
+        storage.accounts.delete(savings.id).unwrap();
+        storage.accounts.save().unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let mut txn = Transaction::new(checking.id, date, Money::from_cents(-4250));
+        txn.payee_name = "Corner Store".to_string();
+        storage.transactions.upsert(txn).unwrap();
+        storage.transactions.save().unwrap();
+
+        let diff = BackupDiff::generate(&paths, &backup_path).unwrap();
+        let accounts = &diff.entities[0];
+        assert_eq!(accounts.modified.len(), 1);
+        assert_eq!(accounts.modified[0].label, "Main Checking");
+        assert!(accounts.modified[0]
+            .details
+            .as_deref()
+            .unwrap()
+            .contains("\"Checking\" -> \"Main Checking\""));
+        assert_eq!(accounts.removed[0].label, "Savings");
+
+        let transactions = &diff.entities[3];
+        assert_eq!(transactions.added.len(), 1);
+        assert_eq!(
+            transactions.added[0].label,
+            "2025-01-15 Corner Store -$42.50"
+        );
+
+        // Restoring would undo each of those
+        let plan = BackupDiff::restore_plan(&paths, &backup_path).unwrap();
+        let accounts = &plan.entities[0];
+        assert_eq!(accounts.added[0].label, "Savings");
+        assert!(accounts.modified[0]
+            .details
+            .as_deref()
+            .unwrap()
+            .contains("\"Main Checking\" -> \"Checking\""));
+        assert!(accounts.removed.is_empty());
+
+        // No transactions file was backed up, so a restore keeps the new one
+        assert!(plan.entities[3].is_empty());
+    }
+
+    #[test]
+    fn test_restore_plan_matches_restore() {
+        use crate::backup::{BackupArchive, RestoreManager};
+
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let mut storage = Storage::new(paths.clone()).unwrap();
+        storage.load_all().unwrap();
+
+        let mut checking = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(checking.clone()).unwrap();
+        storage.accounts.save().unwrap();
+
+        // An older backup, which stored the missing transactions file as `{}`
+        let backup_path = BackupManager::new(paths.clone(), BackupRetention::default())
+            .create_backup()
+            .unwrap();
+        let mut archive: BackupArchive =
+            serde_json::from_str(&std::fs::read_to_string(&backup_path).unwrap()).unwrap();
+        archive.transactions = serde_json::json!({});
+        std::fs::write(&backup_path, serde_json::to_string(&archive).unwrap()).unwrap();
+
+        checking.name = "Main Checking".to_string();
+        storage.accounts.upsert(checking.clone()).unwrap();
+        storage.accounts.save().unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let txn = Transaction::new(checking.id, date, Money::from_cents(-4250));
+        storage.transactions.upsert(txn.clone()).unwrap();
+        storage.transactions.save().unwrap();
+
+        let plan = BackupDiff::restore_plan(&paths, &backup_path).unwrap();
+        assert_eq!(plan.entities[0].modified.len(), 1);
+        assert!(plan.entities[3].is_empty());
+
+        // The restore does what the plan said, and nothing else
+        RestoreManager::new(paths.clone())
+            .restore_from_file(&backup_path)
+            .unwrap();
+        assert!(BackupDiff::restore_plan(&paths, &backup_path)
+            .unwrap()
+            .is_empty());
+
+        storage.load_all().unwrap();
+        assert_eq!(
+            storage.accounts.get(checking.id).unwrap().unwrap().name,
+            "Checking"
+        );
+        assert!(storage.transactions.get(txn.id).unwrap().is_some());
+    }
+}
@@ -1,14 +1,18 @@
 //! Backup manager for EnvelopeCLI
 //!
 //! Handles automatic rolling backups with configurable retention policies.
-//! Backups are stored as dated JSON archives.
+//! Backups are stored as dated JSON archives, optionally gzip-compressed.
 
-use std::fs;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 
 use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use flate2::write::GzEncoder;
+use flate2::Compression;
 use serde::{Deserialize, Serialize};
 
+use crate::config::clock;
 use crate::config::paths::EnvelopePaths;
 use crate::config::settings::BackupRetention;
 use crate::error::{EnvelopeError, EnvelopeResult};
@@ -22,12 +26,20 @@ pub struct BackupInfo {
     pub path: PathBuf,
     /// When the backup was created
     pub created_at: DateTime<Utc>,
-    /// Size in bytes
+    /// Size in bytes of the JSON archive, before any compression
     pub size_bytes: u64,
     /// Whether this is a monthly backup (kept longer)
     pub is_monthly: bool,
+    /// Whether this backup was taken just before a destructive operation
+    pub is_pre_destructive: bool,
 }
 
+/// Filename suffix marking a backup taken before a destructive operation
+const PRE_DESTRUCTIVE_MARKER: &str = "-pre";
+
+/// Extension of gzip-compressed backups, after the `.json`
+pub(super) const COMPRESSED_EXTENSION: &str = "gz";
+
 /// Backup archive format
 #[derive(Debug, Serialize, Deserialize)]
 pub struct BackupArchive {
@@ -70,16 +82,32 @@ impl BackupManager {
     ///
     /// Returns the path to the created backup file.
     pub fn create_backup(&self) -> EnvelopeResult<PathBuf> {
+        self.write_backup("")
+    }
+
+    /// Create a backup marked as taken before a destructive operation
+    ///
+    /// These are the backups `Storage::undo_last` rolls back to.
+    pub fn create_pre_destructive_backup(&self) -> EnvelopeResult<PathBuf> {
+        self.write_backup(PRE_DESTRUCTIVE_MARKER)
+    }
+
+    /// Write a backup file whose name ends with `marker`
+    fn write_backup(&self, marker: &str) -> EnvelopeResult<PathBuf> {
         // Ensure backup directory exists
         fs::create_dir_all(&self.backup_dir)
             .map_err(|e| EnvelopeError::Io(format!("Failed to create backup directory: {}", e)))?;
 
-        let now = Utc::now();
-        let filename = format!(
-            "backup-{}-{:03}.json",
+        let now = clock::now();
+        let mut filename = format!(
+            "backup-{}-{:03}{}.json",
             now.format("%Y%m%d-%H%M%S"),
-            now.timestamp_subsec_millis()
+            now.timestamp_subsec_millis(),
+            marker
         );
+        if self.retention.compress {
+            filename = format!("{}.{}", filename, COMPRESSED_EXTENSION);
+        }
         let backup_path = self.backup_dir.join(&filename);
 
         // Read all data files
@@ -89,8 +117,12 @@ impl BackupManager {
         let json = serde_json::to_string_pretty(&archive)
             .map_err(|e| EnvelopeError::Json(format!("Failed to serialize backup: {}", e)))?;
 
-        fs::write(&backup_path, json)
-            .map_err(|e| EnvelopeError::Io(format!("Failed to write backup file: {}", e)))?;
+        if self.retention.compress {
+            write_compressed(&backup_path, json.as_bytes())
+        } else {
+            fs::write(&backup_path, json)
+        }
+        .map_err(|e| EnvelopeError::Io(format!("Failed to write backup file: {}", e)))?;
 
         Ok(backup_path)
     }
@@ -122,15 +154,22 @@ impl BackupManager {
                 .map_err(|e| EnvelopeError::Io(format!("Failed to read directory entry: {}", e)))?;
 
             let path = entry.path();
-            if path.extension().is_some_and(|ext| ext == "json") {
+            if path
+                .extension()
+                .is_some_and(|ext| ext == "json" || ext == COMPRESSED_EXTENSION)
+            {
                 if let Some(info) = self.parse_backup_info(&path) {
                     backups.push(info);
                 }
             }
         }
 
-        // Sort by date, newest first
-        backups.sort_by(|a, b| b.created_at.cmp(&a.created_at));
+        // Sort by date, newest first; on a tie the routine backup counts as newer
+        backups.sort_by(|a, b| {
+            b.created_at
+                .cmp(&a.created_at)
+                .then(a.is_pre_destructive.cmp(&b.is_pre_destructive))
+        });
 
         Ok(backups)
     }
@@ -139,16 +178,27 @@ impl BackupManager {
     fn parse_backup_info(&self, path: &Path) -> Option<BackupInfo> {
         let filename = path.file_name()?.to_string_lossy().to_string();
 
-        // Parse date from filename: backup-YYYYMMDD-HHMMSS.json
+        // Parse date from filename: backup-YYYYMMDD-HHMMSS[-mmm][-pre].json[.gz]
         if !filename.starts_with("backup-") {
             return None;
         }
 
-        let date_part = filename.strip_prefix("backup-")?.strip_suffix(".json")?;
+        let (name, compressed) = match filename.strip_suffix(".gz") {
+            Some(stripped) => (stripped, true),
+            None => (filename.as_str(), false),
+        };
+        let date_part = name.strip_prefix("backup-")?.strip_suffix(".json")?;
+        let (date_part, is_pre_destructive) = match date_part.strip_suffix(PRE_DESTRUCTIVE_MARKER) {
+            Some(stripped) => (stripped, true),
+            None => (date_part, false),
+        };
         let created_at = parse_backup_timestamp(date_part)?;
 
-        let metadata = fs::metadata(path).ok()?;
-        let size_bytes = metadata.len();
+        let size_bytes = if compressed {
+            uncompressed_size(path).ok()?
+        } else {
+            fs::metadata(path).ok()?.len()
+        };
 
         // A backup is "monthly" if it's the first backup of the month
         let is_monthly = self.is_first_of_month(&created_at);
@@ -159,6 +209,7 @@ impl BackupManager {
             created_at,
             size_bytes,
             is_monthly,
+            is_pre_destructive,
         })
     }
 
@@ -226,9 +277,9 @@ impl BackupManager {
 }
 
 /// Read a JSON file as a generic Value, returning empty object if file doesn't exist
-fn read_json_value(path: &Path) -> EnvelopeResult<serde_json::Value> {
+pub(super) fn read_json_value(path: &Path) -> EnvelopeResult<serde_json::Value> {
     if !path.exists() {
-        return Ok(serde_json::Value::Object(serde_json::Map::new()));
+        return Ok(serde_json::Value::Null);
     }
 
     let contents = fs::read_to_string(path)
@@ -238,6 +289,33 @@ fn read_json_value(path: &Path) -> EnvelopeResult<serde_json::Value> {
         .map_err(|e| EnvelopeError::Json(format!("Failed to parse JSON for backup: {}", e)))
 }
 
+/// Whether a backed-up file stands for a data file that didn't exist
+///
+/// Missing files are stored as `null`, or as `{}` by older backups. A restore
+/// leaves such files alone rather than writing an empty one.
+pub(super) fn is_missing_file(value: &serde_json::Value) -> bool {
+    value.is_null() || value.as_object().is_some_and(|map| map.is_empty())
+}
+
+/// Write `data` gzip-compressed to `path`
+fn write_compressed(path: &Path, data: &[u8]) -> std::io::Result<()> {
+    let mut encoder = GzEncoder::new(File::create(path)?, Compression::default());
+    encoder.write_all(data)?;
+    encoder.finish()?.sync_all()
+}
+
+/// Uncompressed size of a gzip file, read from its trailer
+///
+/// The trailer holds the size modulo 2^32, which is exact for any backup
+/// this small.
+fn uncompressed_size(path: &Path) -> std::io::Result<u64> {
+    let mut file = File::open(path)?;
+    file.seek(SeekFrom::End(-4))?;
+    let mut trailer = [0u8; 4];
+    file.read_exact(&mut trailer)?;
+    Ok(u64::from(u32::from_le_bytes(trailer)))
+}
+
 /// Parse a backup timestamp from the filename date part
 fn parse_backup_timestamp(date_str: &str) -> Option<DateTime<Utc>> {
     // Expected format: YYYYMMDD-HHMMSS or YYYYMMDD-HHMMSS-mmm (with milliseconds)
@@ -285,12 +363,23 @@ mod tests {
         let retention = BackupRetention {
             daily_count: 3,
             monthly_count: 2,
+            compress: false,
         };
 
         let manager = BackupManager::new(paths, retention);
         (manager, temp_dir)
     }
 
+    fn create_compressed_manager(temp_dir: &TempDir) -> BackupManager {
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let retention = BackupRetention {
+            daily_count: 3,
+            monthly_count: 2,
+            compress: true,
+        };
+        BackupManager::new(paths, retention)
+    }
+
     #[test]
     fn test_create_backup() {
         let (manager, _temp) = create_test_manager();
@@ -347,6 +436,20 @@ mod tests {
         assert_eq!(latest.path, path);
     }
 
+    #[test]
+    fn test_pre_destructive_marker() {
+        let (manager, _temp) = create_test_manager();
+
+        manager.create_backup().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+        let path = manager.create_pre_destructive_backup().unwrap();
+
+        let latest = manager.get_latest_backup().unwrap().unwrap();
+        assert_eq!(latest.path, path);
+        assert!(latest.is_pre_destructive);
+        assert!(!manager.list_backups().unwrap()[1].is_pre_destructive);
+    }
+
     #[test]
     fn test_parse_backup_timestamp() {
         // Test old format without milliseconds
@@ -365,6 +468,7 @@ mod tests {
     #[test]
     fn test_backup_archive_structure() {
         let (manager, _temp) = create_test_manager();
+        fs::write(manager.paths.accounts_file(), r#"{"accounts": []}"#).unwrap();
 
         let backup_path = manager.create_backup().unwrap();
 
@@ -374,7 +478,45 @@ mod tests {
 
         assert_eq!(archive.schema_version, 1);
         assert!(archive.accounts.is_object());
-        assert!(archive.transactions.is_object());
+        // A data file that doesn't exist yet is stored as null
+        assert!(archive.transactions.is_null());
+    }
+
+    #[test]
+    fn test_compressed_backup() {
+        let (plain, temp) = create_test_manager();
+        let plain_path = plain.create_backup().unwrap();
+        std::thread::sleep(std::time::Duration::from_millis(10));
+
+        let manager = create_compressed_manager(&temp);
+        let path = manager.create_backup().unwrap();
+        assert!(path.to_string_lossy().ends_with(".json.gz"));
+
+        // Listed alongside plain backups, reporting the uncompressed size
+        let backups = manager.list_backups().unwrap();
+        assert_eq!(backups.len(), 2);
+        assert_eq!(backups[0].path, path);
+        assert_eq!(backups[0].size_bytes, backups[1].size_bytes);
+        assert_eq!(
+            backups[1].size_bytes,
+            fs::metadata(&plain_path).unwrap().len()
+        );
+        assert!(fs::metadata(&path).unwrap().len() < backups[0].size_bytes);
+    }
+
+    #[test]
+    fn test_retention_prunes_compressed_backups() {
+        let temp = TempDir::new().unwrap();
+        let manager = create_compressed_manager(&temp);
+
+        for _ in 0..5 {
+            manager.create_backup().unwrap();
+            std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+
+        let deleted = manager.enforce_retention().unwrap();
+        assert_eq!(deleted.len(), 2);
+        assert_eq!(manager.list_backups().unwrap().len(), 3);
     }
 
     #[test]
@@ -9,6 +9,7 @@
 //!
 //! - `BackupManager`: Creates and manages backups with retention policies
 //! - `RestoreManager`: Validates and restores backups
+//! - `BackupDiff`: Compares a backup against the live data without restoring
 //!
 //! # Backup Format
 //!
@@ -20,6 +21,9 @@
 //! - `budget`: Categories, groups, and allocations
 //! - `payees`: Payee data
 //!
+//! With `BackupRetention::compress` set, backups are written gzip-compressed
+//! as `.json.gz`; restore and retention handle both kinds.
+//!
 //! # Retention Policy
 //!
 //! By default, the system keeps:
@@ -46,8 +50,12 @@
 //! println!("{}", result.summary());
 //! ```
 
+mod diff;
 mod manager;
 mod restore;
 
+pub use diff::{BackupDiff, EntityChange, EntityDiff};
 pub use manager::{BackupArchive, BackupInfo, BackupManager};
-pub use restore::{ExportRestoreCounts, RestoreManager, RestoreResult, ValidationResult};
+pub use restore::{
+    ExportRestoreCounts, RestoreManager, RestorePlan, RestoreResult, ValidationResult,
+};
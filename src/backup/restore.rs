@@ -3,14 +3,18 @@
 //! Handles restoring data from backup archives.
 //! Supports both internal backup format and export format files.
 
-use std::fs;
+use std::fs::{self, File};
+use std::io::Read;
 use std::path::Path;
 
+use flate2::read::GzDecoder;
+
 use crate::config::paths::EnvelopePaths;
 use crate::error::{EnvelopeError, EnvelopeResult};
 use crate::export::FullExport;
 
-use super::manager::BackupArchive;
+use super::diff::BackupDiff;
+use super::manager::{is_missing_file, BackupArchive, COMPRESSED_EXTENSION};
 
 /// File format type detected during parsing
 #[derive(Debug)]
@@ -58,6 +62,28 @@ fn parse_backup_contents(path: &Path, contents: &str) -> EnvelopeResult<BackupFi
     }
 }
 
+/// Read and parse a backup file, auto-detecting format
+///
+/// A `.gz` file is decompressed first and parsed by the extension before it.
+pub(super) fn read_backup_file(path: &Path) -> EnvelopeResult<BackupFileFormat> {
+    if path
+        .extension()
+        .is_some_and(|ext| ext == COMPRESSED_EXTENSION)
+    {
+        let mut contents = String::new();
+        File::open(path)
+            .and_then(|file| GzDecoder::new(file).read_to_string(&mut contents))
+            .map_err(|e| {
+                EnvelopeError::Io(format!("Failed to read compressed backup file: {}", e))
+            })?;
+        return parse_backup_contents(&path.with_extension(""), &contents);
+    }
+
+    let contents = fs::read_to_string(path)
+        .map_err(|e| EnvelopeError::Io(format!("Failed to read backup file: {}", e)))?;
+    parse_backup_contents(path, &contents)
+}
+
 /// Handles restoring from backups
 pub struct RestoreManager {
     paths: EnvelopePaths,
@@ -74,13 +100,11 @@ impl RestoreManager {
     /// This will overwrite all current data with the backup contents.
     /// It's recommended to create a backup before restoring.
     /// Supports both internal backup format and export format files.
-    /// Supports both JSON and YAML formats (detected by file extension).
+    /// Supports both JSON and YAML formats (detected by file extension),
+    /// and gzip-compressed backups ending in `.gz`.
     pub fn restore_from_file(&self, backup_path: &Path) -> EnvelopeResult<RestoreResult> {
         // Read and parse the backup
-        let contents = fs::read_to_string(backup_path)
-            .map_err(|e| EnvelopeError::Io(format!("Failed to read backup file: {}", e)))?;
-
-        let parsed = parse_backup_contents(backup_path, &contents)?;
+        let parsed = read_backup_file(backup_path)?;
 
         match parsed {
             BackupFileFormat::Backup(archive) => self.restore_from_archive(&archive),
@@ -89,6 +113,8 @@ impl RestoreManager {
     }
 
     /// Restore data from a parsed backup archive (internal format)
+    ///
+    /// Files the backup has no copy of are left as they are.
     pub fn restore_from_archive(&self, archive: &BackupArchive) -> EnvelopeResult<RestoreResult> {
         // Ensure directories exist
         self.paths.ensure_directories()?;
@@ -96,7 +122,7 @@ impl RestoreManager {
         let mut result = RestoreResult::default();
 
         // Restore accounts
-        if !archive.accounts.is_null() {
+        if !is_missing_file(&archive.accounts) {
             let json = serde_json::to_string_pretty(&archive.accounts)
                 .map_err(|e| EnvelopeError::Json(format!("Failed to serialize accounts: {}", e)))?;
             fs::write(self.paths.accounts_file(), json)
@@ -105,7 +131,7 @@ impl RestoreManager {
         }
 
         // Restore transactions
-        if !archive.transactions.is_null() {
+        if !is_missing_file(&archive.transactions) {
             let json = serde_json::to_string_pretty(&archive.transactions).map_err(|e| {
                 EnvelopeError::Json(format!("Failed to serialize transactions: {}", e))
             })?;
@@ -115,7 +141,7 @@ impl RestoreManager {
         }
 
         // Restore budget (categories, groups, allocations)
-        if !archive.budget.is_null() {
+        if !is_missing_file(&archive.budget) {
             let json = serde_json::to_string_pretty(&archive.budget)
                 .map_err(|e| EnvelopeError::Json(format!("Failed to serialize budget: {}", e)))?;
             fs::write(self.paths.budget_file(), json)
@@ -124,7 +150,7 @@ impl RestoreManager {
         }
 
         // Restore payees
-        if !archive.payees.is_null() {
+        if !is_missing_file(&archive.payees) {
             let json = serde_json::to_string_pretty(&archive.payees)
                 .map_err(|e| EnvelopeError::Json(format!("Failed to serialize payees: {}", e)))?;
             fs::write(self.paths.payees_file(), json)
@@ -139,6 +165,55 @@ impl RestoreManager {
         Ok(result)
     }
 
+    /// Restore a single data file from a backup file
+    ///
+    /// Only files included in backups (accounts, transactions, budget, and
+    /// payees) can be restored; the rest of the data is left untouched. The
+    /// current file is kept alongside as `<name>.corrupt`. Returns `false`
+    /// if the backup has no copy of the file.
+    pub fn restore_single_file(&self, backup_path: &Path, file: &Path) -> EnvelopeResult<bool> {
+        let archive = match read_backup_file(backup_path)? {
+            BackupFileFormat::Backup(archive) => archive,
+            BackupFileFormat::Export(_) => {
+                return Err(EnvelopeError::Validation(
+                    "Single files can only be restored from internal backups".into(),
+                ))
+            }
+        };
+
+        let value = if file == self.paths.accounts_file() {
+            &archive.accounts
+        } else if file == self.paths.transactions_file() {
+            &archive.transactions
+        } else if file == self.paths.budget_file() {
+            &archive.budget
+        } else if file == self.paths.payees_file() {
+            &archive.payees
+        } else {
+            return Ok(false);
+        };
+
+        if is_missing_file(value) {
+            return Ok(false);
+        }
+
+        let json = serde_json::to_string_pretty(value)
+            .map_err(|e| EnvelopeError::Json(format!("Failed to serialize backup data: {}", e)))?;
+
+        if file.exists() {
+            let mut corrupt = file.as_os_str().to_owned();
+            corrupt.push(".corrupt");
+            fs::rename(file, &corrupt)
+                .map_err(|e| EnvelopeError::Io(format!("Failed to move corrupted file: {}", e)))?;
+        }
+
+        fs::write(file, json).map_err(|e| {
+            EnvelopeError::Io(format!("Failed to restore {}: {}", file.display(), e))
+        })?;
+
+        Ok(true)
+    }
+
     /// Restore data from an export file
     fn restore_from_export(&self, export: &FullExport) -> EnvelopeResult<RestoreResult> {
         // Ensure directories exist
@@ -173,25 +248,33 @@ impl RestoreManager {
         })
     }
 
+    /// Show what restoring a backup file would change, without writing
+    ///
+    /// Validates the backup first, then compares it against the live data.
+    pub fn plan(&self, backup_path: &Path) -> EnvelopeResult<RestorePlan> {
+        let validation = self.validate_backup(backup_path)?;
+        let changes = BackupDiff::restore_plan(&self.paths, backup_path)?;
+        Ok(RestorePlan {
+            validation,
+            changes,
+        })
+    }
+
     /// Validate a backup file without restoring it
     /// Supports both internal backup format and export format files.
     /// Supports both JSON and YAML formats (detected by file extension).
     pub fn validate_backup(&self, backup_path: &Path) -> EnvelopeResult<ValidationResult> {
-        let contents = fs::read_to_string(backup_path)
-            .map_err(|e| EnvelopeError::Io(format!("Failed to read backup file: {}", e)))?;
-
-        let parsed = parse_backup_contents(backup_path, &contents)?;
+        let parsed = read_backup_file(backup_path)?;
 
         match parsed {
             BackupFileFormat::Backup(archive) => Ok(ValidationResult {
                 is_valid: true,
                 schema_version: archive.schema_version,
                 backup_date: archive.created_at,
-                has_accounts: !archive.accounts.is_null() && archive.accounts.is_object(),
-                has_transactions: !archive.transactions.is_null()
-                    && archive.transactions.is_object(),
-                has_budget: !archive.budget.is_null() && archive.budget.is_object(),
-                has_payees: !archive.payees.is_null() && archive.payees.is_object(),
+                has_accounts: !is_missing_file(&archive.accounts),
+                has_transactions: !is_missing_file(&archive.transactions),
+                has_budget: !is_missing_file(&archive.budget),
+                has_payees: !is_missing_file(&archive.payees),
                 is_export_format: false,
                 export_schema_version: None,
             }),
@@ -290,6 +373,15 @@ impl RestoreResult {
     }
 }
 
+/// What restoring a backup would do, from [`RestoreManager::plan`]
+#[derive(Debug)]
+pub struct RestorePlan {
+    /// The backup's structure check
+    pub validation: ValidationResult,
+    /// Entities the restore would add, remove, or change
+    pub changes: BackupDiff,
+}
+
 /// Result of validating a backup
 #[derive(Debug)]
 pub struct ValidationResult {
@@ -382,6 +474,10 @@ mod tests {
         let temp_dir = TempDir::new().unwrap();
         let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
         paths.ensure_directories().unwrap();
+        fs::write(paths.accounts_file(), r#"{"accounts": []}"#).unwrap();
+        fs::write(paths.transactions_file(), r#"{"transactions": []}"#).unwrap();
+        fs::write(paths.budget_file(), r#"{"groups": [], "categories": []}"#).unwrap();
+        fs::write(paths.payees_file(), r#"{"payees": []}"#).unwrap();
 
         let retention = BackupRetention::default();
         let backup_manager = BackupManager::new(paths.clone(), retention);
@@ -406,6 +502,73 @@ mod tests {
         assert!(result.payees_restored);
     }
 
+    #[test]
+    fn test_restore_from_compressed_backup() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        paths.ensure_directories().unwrap();
+        fs::write(paths.accounts_file(), r#"{"accounts":[]}"#).unwrap();
+
+        let retention = BackupRetention {
+            compress: true,
+            ..BackupRetention::default()
+        };
+        let backup_path = BackupManager::new(paths.clone(), retention)
+            .create_backup()
+            .unwrap();
+        fs::write(paths.accounts_file(), "{}").unwrap();
+
+        let restore_manager = RestoreManager::new(paths.clone());
+        assert!(
+            restore_manager
+                .validate_backup(&backup_path)
+                .unwrap()
+                .is_valid
+        );
+        let result = restore_manager.restore_from_file(&backup_path).unwrap();
+
+        assert!(result.accounts_restored);
+        let accounts: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(paths.accounts_file()).unwrap()).unwrap();
+        assert_eq!(accounts, serde_json::json!({ "accounts": [] }));
+    }
+
+    #[test]
+    fn test_restore_leaves_files_missing_from_backup() {
+        let (restore_manager, backup_manager, _temp) = create_test_env();
+        let paths = &restore_manager.paths;
+        fs::remove_file(paths.transactions_file()).unwrap();
+
+        let backup_path = backup_manager.create_backup().unwrap();
+        assert!(
+            !restore_manager
+                .validate_backup(&backup_path)
+                .unwrap()
+                .has_transactions
+        );
+
+        // Created after the backup; restoring must not replace it with nothing
+        fs::write(paths.transactions_file(), r#"{"transactions": []}"#).unwrap();
+        let result = restore_manager.restore_from_file(&backup_path).unwrap();
+
+        assert!(result.accounts_restored);
+        assert!(!result.transactions_restored);
+        let transactions = fs::read_to_string(paths.transactions_file()).unwrap();
+        assert_eq!(transactions, r#"{"transactions": []}"#);
+        assert!(!restore_manager
+            .restore_single_file(&backup_path, &paths.transactions_file())
+            .unwrap());
+
+        // Older backups stored a missing file as an empty object
+        let mut archive: BackupArchive =
+            serde_json::from_str(&fs::read_to_string(&backup_path).unwrap()).unwrap();
+        archive.transactions = serde_json::json!({});
+        let result = restore_manager.restore_from_archive(&archive).unwrap();
+        assert!(!result.transactions_restored);
+        let transactions = fs::read_to_string(paths.transactions_file()).unwrap();
+        assert_eq!(transactions, r#"{"transactions": []}"#);
+    }
+
     #[test]
     fn test_validate_backup() {
         let (restore_manager, backup_manager, _temp) = create_test_env();
@@ -480,4 +643,35 @@ mod tests {
         assert!(restore_manager.paths.budget_file().exists());
         assert!(restore_manager.paths.payees_file().exists());
     }
+
+    #[test]
+    fn test_restore_single_file() {
+        let (restore_manager, backup_manager, _temp) = create_test_env();
+        let paths = &restore_manager.paths;
+        fs::write(paths.accounts_file(), r#"{"accounts": []}"#).unwrap();
+        fs::write(paths.payees_file(), r#"{"payees": []}"#).unwrap();
+
+        let backup_path = backup_manager.create_backup().unwrap();
+
+        // Corrupt accounts and change payees after the backup
+        fs::write(paths.accounts_file(), r#"{"accounts": ["#).unwrap();
+        fs::write(paths.payees_file(), r#"{"payees": [], "new": true}"#).unwrap();
+
+        assert!(restore_manager
+            .restore_single_file(&backup_path, &paths.accounts_file())
+            .unwrap());
+
+        let accounts = fs::read_to_string(paths.accounts_file()).unwrap();
+        assert!(serde_json::from_str::<serde_json::Value>(&accounts).is_ok());
+        assert!(paths.data_dir().join("accounts.json.corrupt").exists());
+
+        // Other files are untouched
+        let payees = fs::read_to_string(paths.payees_file()).unwrap();
+        assert!(payees.contains("new"));
+
+        // Files outside the backup can't be restored
+        assert!(!restore_manager
+            .restore_single_file(&backup_path, &paths.targets_file())
+            .unwrap());
+    }
 }
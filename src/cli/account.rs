@@ -2,12 +2,19 @@
 //!
 //! Implements CLI commands for account management.
 
+use chrono::{Duration, NaiveDate};
 use clap::Subcommand;
+use serde::Serialize;
 
+use crate::cli::confirm::confirm_with_backup;
+use crate::cli::output::{print_json, OutputFormat};
+use crate::config::clock;
+use crate::config::settings::Settings;
 use crate::display::account::{format_account_details, format_account_list};
 use crate::error::EnvelopeResult;
 use crate::models::{AccountType, Money};
-use crate::services::AccountService;
+use crate::services::account::{AccountSummary, BALANCE_HISTORY_DAYS};
+use crate::services::{AccountService, CategoryService};
 use crate::storage::Storage;
 
 /// Account subcommands
@@ -20,12 +27,22 @@ pub enum AccountCommands {
         /// Account type (checking, savings, credit, cash, investment)
         #[arg(short = 't', long, default_value = "checking")]
         account_type: String,
-        /// Starting balance (e.g., "1000.00" or "1000")
-        #[arg(short, long, default_value = "0")]
+        /// Starting balance (e.g., "1000.00" or "-500"); debt may be given as
+        /// a positive amount for credit accounts
+        #[arg(
+            short,
+            long,
+            visible_alias = "starting-balance",
+            default_value = "0",
+            allow_negative_numbers = true
+        )]
         balance: String,
         /// Mark as off-budget
         #[arg(long)]
         off_budget: bool,
+        /// Currency code (ISO 4217, e.g., EUR); defaults to the base currency
+        #[arg(long)]
+        currency: Option<String>,
     },
     /// List all accounts
     List {
@@ -48,6 +65,18 @@ pub enum AccountCommands {
         /// New name
         #[arg(short, long)]
         name: Option<String>,
+        /// New starting balance (recorded as a "Starting Balance" transaction)
+        #[arg(long, allow_negative_numbers = true)]
+        starting_balance: Option<String>,
+        /// Date for the starting balance (YYYY-MM-DD)
+        #[arg(long, requires = "starting_balance")]
+        date: Option<String>,
+        /// Category prefilled on new transactions ("none" to clear)
+        #[arg(long)]
+        default_category: Option<String>,
+        /// Currency code (ISO 4217), or "base" for the base currency
+        #[arg(long)]
+        currency: Option<String>,
     },
     /// Archive an account
     Archive {
@@ -59,10 +88,34 @@ pub enum AccountCommands {
         /// Account name or ID
         account: String,
     },
+    /// Move every transaction from one account into another and delete it
+    Merge {
+        /// Account to merge away (name or ID)
+        from: String,
+        /// Account that keeps the transactions (name or ID)
+        to: String,
+        /// Skip confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+}
+
+/// An account as printed by `account show --json`
+#[derive(Debug, Serialize)]
+struct AccountDetails<'a> {
+    #[serde(flatten)]
+    summary: &'a AccountSummary,
+    /// Daily balances, oldest first, ending today
+    balance_history: &'a [Money],
 }
 
 /// Handle an account command
-pub fn handle_account_command(storage: &Storage, cmd: AccountCommands) -> EnvelopeResult<()> {
+pub fn handle_account_command(
+    storage: &Storage,
+    settings: &Settings,
+    cmd: AccountCommands,
+    output: OutputFormat,
+) -> EnvelopeResult<()> {
     let service = AccountService::new(storage);
 
     match cmd {
@@ -71,6 +124,7 @@ pub fn handle_account_command(storage: &Storage, cmd: AccountCommands) -> Envelo
             account_type,
             balance,
             off_budget,
+            currency,
         } => {
             let account_type = AccountType::parse(&account_type).ok_or_else(|| {
                 crate::error::EnvelopeError::Validation(format!(
@@ -93,11 +147,25 @@ pub fn handle_account_command(storage: &Storage, cmd: AccountCommands) -> Envelo
                 starting_balance = Money::from_cents(-starting_balance.cents());
             }
 
-            let account = service.create(&name, account_type, starting_balance, !off_budget)?;
+            if let Some(warning) = account_type.starting_balance_warning(starting_balance) {
+                println!("Warning: {}", warning);
+            }
+
+            let currency = account_currency(currency.as_deref(), settings);
+            let mut account = service.create(&name, account_type, starting_balance, !off_budget)?;
+            if currency.is_some() {
+                account = service.set_currency(account.id, currency.as_deref())?;
+            }
 
             println!("Created account: {}", account.name);
             println!("  Type: {}", account.account_type);
-            println!("  Starting Balance: {}", account.starting_balance);
+            if let Some(code) = &account.currency {
+                println!("  Currency: {}", code);
+            }
+            println!(
+                "  Starting Balance: {}",
+                account.format_amount(account.starting_balance)
+            );
             println!(
                 "  On Budget: {}",
                 if account.on_budget { "Yes" } else { "No" }
@@ -107,7 +175,11 @@ pub fn handle_account_command(storage: &Storage, cmd: AccountCommands) -> Envelo
 
         AccountCommands::List { all, pretty } => {
             let summaries = service.list_with_balances(all)?;
-            print!("{}", format_account_list(&summaries, pretty));
+            if output.is_json() {
+                print_json(&summaries)?;
+            } else {
+                print!("{}", format_account_list(&summaries, pretty));
+            }
         }
 
         AccountCommands::Show { account } => {
@@ -116,21 +188,119 @@ pub fn handle_account_command(storage: &Storage, cmd: AccountCommands) -> Envelo
                 .ok_or_else(|| crate::error::EnvelopeError::account_not_found(&account))?;
 
             let summary = service.get_summary(&found)?;
-            print!("{}", format_account_details(&summary));
+            let today = clock::today();
+            let history: Vec<Money> = service
+                .daily_balances(
+                    found.id,
+                    today - Duration::days(BALANCE_HISTORY_DAYS - 1),
+                    today,
+                )?
+                .into_iter()
+                .map(|(_, balance)| balance)
+                .collect();
+            if output.is_json() {
+                print_json(&AccountDetails {
+                    summary: &summary,
+                    balance_history: &history,
+                })?;
+            } else {
+                print!("{}", format_account_details(&summary, &history));
+            }
         }
 
-        AccountCommands::Edit { account, name } => {
+        AccountCommands::Edit {
+            account,
+            name,
+            starting_balance,
+            date,
+            default_category,
+            currency,
+        } => {
             let found = service
                 .find(&account)?
                 .ok_or_else(|| crate::error::EnvelopeError::account_not_found(&account))?;
 
-            if name.is_none() {
-                println!("No changes specified. Use --name to change the account name.");
+            if name.is_none()
+                && starting_balance.is_none()
+                && default_category.is_none()
+                && currency.is_none()
+            {
+                println!(
+                    "No changes specified. Use --name, --starting-balance, --default-category, or --currency."
+                );
                 return Ok(());
             }
 
-            let updated = service.update(found.id, name.as_deref())?;
+            let mut updated = found.clone();
+            if name.is_some() {
+                updated = service.update(found.id, name.as_deref())?;
+            }
+
+            if let Some(balance) = starting_balance {
+                let mut amount = Money::parse(&balance).map_err(|e| {
+                    crate::error::EnvelopeError::Validation(format!(
+                        "Invalid balance format: '{}'. Use format like '1000.00' or '1000'. Error: {}",
+                        balance, e
+                    ))
+                })?;
+                if found.account_type.is_liability() && amount.cents() > 0 {
+                    amount = Money::from_cents(-amount.cents());
+                }
+
+                let date = date
+                    .map(|d| {
+                        NaiveDate::parse_from_str(&d, "%Y-%m-%d").map_err(|_| {
+                            crate::error::EnvelopeError::Validation(format!(
+                                "Invalid date format: '{}'. Use YYYY-MM-DD",
+                                d
+                            ))
+                        })
+                    })
+                    .transpose()?;
+
+                if let Some(warning) = found.account_type.starting_balance_warning(amount) {
+                    println!("Warning: {}", warning);
+                }
+                updated = service.set_starting_balance(found.id, amount, date)?;
+            }
+
+            if let Some(cat_name) = default_category {
+                let category_id = if cat_name.eq_ignore_ascii_case("none") {
+                    None
+                } else {
+                    let category = CategoryService::new(storage)
+                        .find_category(&cat_name)?
+                        .ok_or_else(|| {
+                            crate::error::EnvelopeError::category_not_found(&cat_name)
+                        })?;
+                    Some(category.id)
+                };
+                updated = service.set_default_category(found.id, category_id)?;
+            }
+
+            if let Some(code) = currency {
+                let code = if code.eq_ignore_ascii_case("base") {
+                    None
+                } else {
+                    account_currency(Some(&code), settings)
+                };
+                updated = service.set_currency(found.id, code.as_deref())?;
+            }
+
             println!("Updated account: {}", updated.name);
+            println!(
+                "  Currency: {}",
+                updated.currency_or(&settings.base_currency)
+            );
+            println!(
+                "  Starting Balance: {}",
+                updated.format_amount(updated.starting_balance)
+            );
+            if let Some(cat_id) = updated.default_category_id {
+                if let Some(category) = CategoryService::new(storage).get_category(cat_id)? {
+                    println!("  Default Category: {}", category.name);
+                }
+            }
         }
 
         AccountCommands::Archive { account } => {
@@ -150,7 +320,49 @@ pub fn handle_account_command(storage: &Storage, cmd: AccountCommands) -> Envelo
             let unarchived = service.unarchive(found.id)?;
             println!("Unarchived account: {}", unarchived.name);
         }
+
+        AccountCommands::Merge { from, to, yes } => {
+            let source = service
+                .find(&from)?
+                .ok_or_else(|| crate::error::EnvelopeError::account_not_found(&from))?;
+            let target = service
+                .find(&to)?
+                .ok_or_else(|| crate::error::EnvelopeError::account_not_found(&to))?;
+
+            let preview = service.merge_preview(source.id, target.id)?;
+            println!("Merging '{}' into '{}':", source.name, target.name);
+            println!(
+                "  Transactions to move: {} ({} reconciled)",
+                preview.transactions_moved, preview.reconciled_moved
+            );
+            if preview.transfers_unlinked > 0 {
+                println!(
+                    "  Transfers between the two to unlink: {}",
+                    preview.transfers_unlinked
+                );
+            }
+            println!("  Combined starting balance: {}", preview.starting_balance);
+
+            let prompt = format!("Merge and delete account '{}'?", source.name);
+            if !confirm_with_backup(storage, settings, yes, &prompt)? {
+                return Ok(());
+            }
+
+            let summary = service.merge(source.id, target.id)?;
+            println!(
+                "Merged '{}' into '{}': moved {} transaction(s)",
+                source.name, target.name, summary.transactions_moved
+            );
+        }
     }
 
     Ok(())
 }
+
+/// The currency to store for an account: `None` when it's the base currency
+///
+/// Codes are validated when saved by [`AccountService::set_currency`].
+fn account_currency(code: Option<&str>, settings: &Settings) -> Option<String> {
+    code.filter(|c| !c.trim().eq_ignore_ascii_case(&settings.base_currency))
+        .map(|c| c.to_string())
+}
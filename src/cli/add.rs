@@ -0,0 +1,109 @@
+//! CLI command handler for quick transaction capture
+//!
+//! `envelope add` records a transaction in one line without going through
+//! the `transaction` subcommands, for fast entry from the terminal.
+
+use chrono::NaiveDate;
+
+use crate::config::clock;
+use crate::error::{EnvelopeError, EnvelopeResult};
+use crate::models::{CategoryId, Money};
+use crate::services::{
+    AccountService, CategoryService, CreateTransactionInput, TransactionService,
+};
+use crate::storage::Storage;
+
+/// How many close matches to offer for an unknown category name
+const CATEGORY_SUGGESTIONS: usize = 3;
+
+/// Handle the add command
+///
+/// A negative amount is an outflow. An unknown category is an error that
+/// lists the closest category names instead of creating anything.
+pub fn handle_add_command(
+    storage: &Storage,
+    account: &str,
+    amount: &str,
+    payee: Option<String>,
+    category: Option<&str>,
+    memo: Option<String>,
+    date: Option<&str>,
+) -> EnvelopeResult<()> {
+    let account_service = AccountService::new(storage);
+    let category_service = CategoryService::new(storage);
+    let service = TransactionService::new(storage);
+
+    let account = account_service
+        .find(account)?
+        .ok_or_else(|| EnvelopeError::account_not_found(account))?;
+
+    let amount = Money::parse(amount).map_err(|e| {
+        EnvelopeError::Validation(format!(
+            "Invalid amount format: '{}'. Use format like '-50.00' or '100'. Error: {}",
+            amount, e
+        ))
+    })?;
+
+    // Parse date (default to today)
+    let date = if let Some(date_str) = date {
+        NaiveDate::parse_from_str(date_str, "%Y-%m-%d").map_err(|_| {
+            EnvelopeError::Validation(format!(
+                "Invalid date format: '{}'. Use YYYY-MM-DD",
+                date_str
+            ))
+        })?
+    } else {
+        clock::today()
+    };
+
+    let category_id = category
+        .map(|name| resolve_category(&category_service, name))
+        .transpose()?;
+
+    let input = CreateTransactionInput {
+        account_id: account.id,
+        date,
+        amount,
+        payee_name: payee,
+        category_id,
+        memo,
+        status: None,
+    };
+
+    let txn = service.create(input)?;
+    service.learn_from_transaction(&txn)?;
+
+    println!("Added transaction:");
+    println!("  Account:  {}", account.name);
+    println!("  Date:     {}", txn.date);
+    println!("  Amount:   {}", account.format_amount(txn.amount));
+    if !txn.payee_name.is_empty() {
+        println!("  Payee:    {}", txn.payee_name);
+    }
+    if let Some(cat_id) = txn.category_id {
+        if let Some(cat) = category_service.get_category(cat_id)? {
+            println!("  Category: {}", cat.name);
+        }
+    }
+
+    Ok(())
+}
+
+/// Find a category by name, suggesting close names when there's no match
+fn resolve_category(service: &CategoryService, name: &str) -> EnvelopeResult<CategoryId> {
+    if let Some(category) = service.find_category(name)? {
+        return Ok(category.id);
+    }
+
+    let similar = service.similar_categories(name, CATEGORY_SUGGESTIONS)?;
+    if similar.is_empty() {
+        return Err(EnvelopeError::category_not_found(name));
+    }
+
+    let names: Vec<&str> = similar.iter().map(|c| c.name.as_str()).collect();
+    Err(EnvelopeError::Validation(format!(
+        "Category '{}' not found. Did you mean: {}?",
+        name,
+        names.join(", ")
+    )))
+}
@@ -0,0 +1,86 @@
+//! Audit CLI commands
+//!
+//! Implements CLI commands for browsing the audit log.
+
+use std::io::IsTerminal;
+
+use clap::Subcommand;
+
+use crate::display::audit::format_audit_diff;
+use crate::error::{EnvelopeError, EnvelopeResult};
+use crate::storage::Storage;
+
+/// Audit subcommands
+#[derive(Subcommand)]
+pub enum AuditCommands {
+    /// List recent audit log entries
+    List {
+        /// Number of entries to show
+        #[arg(short = 'n', long, default_value = "20")]
+        count: usize,
+    },
+    /// Show the full before/after diff of changes to an entity
+    Show {
+        /// Entity ID (e.g., "txn-1a2b3c4d" or "1a2b3c4d")
+        entity_id: String,
+        /// Number of most recent changes to show, newest first
+        #[arg(long, default_value = "1")]
+        last: usize,
+        /// Disable colored output
+        #[arg(long)]
+        no_color: bool,
+    },
+}
+
+/// Handle audit commands
+pub fn handle_audit_command(storage: &Storage, cmd: AuditCommands) -> EnvelopeResult<()> {
+    match cmd {
+        AuditCommands::List { count } => {
+            let entries = storage.read_audit_log(count)?;
+            if entries.is_empty() {
+                println!("Audit log is empty.");
+                return Ok(());
+            }
+
+            for entry in entries.iter().rev() {
+                println!("{}", entry.format_human_readable());
+            }
+        }
+
+        AuditCommands::Show {
+            entity_id,
+            last,
+            no_color,
+        } => {
+            let history = storage.read_audit_history(&entity_id)?;
+            if history.is_empty() {
+                return Err(EnvelopeError::NotFound {
+                    entity_type: "Audit history",
+                    identifier: entity_id,
+                });
+            }
+
+            let color = !no_color && std::io::stdout().is_terminal();
+            let shown = last.min(history.len());
+
+            for (i, entry) in history.iter().rev().take(shown).enumerate() {
+                if i > 0 {
+                    println!();
+                }
+                print!("{}", format_audit_diff(entry, color));
+            }
+
+            if shown < history.len() {
+                println!();
+                println!(
+                    "Showing {} of {} changes. Use --last {} to see all.",
+                    shown,
+                    history.len(),
+                    history.len()
+                );
+            }
+        }
+    }
+
+    Ok(())
+}
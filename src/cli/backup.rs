@@ -1,14 +1,18 @@
 //! Backup CLI commands
 //!
-//! Implements CLI commands for backup management.
+//! Implements CLI commands for backup management, and `undo` for rolling
+//! back the last destructive change.
 
 use clap::Subcommand;
 use std::path::PathBuf;
 
-use crate::backup::{BackupManager, RestoreManager};
+use crate::backup::{BackupDiff, BackupManager, EntityChange, EntityDiff, RestoreManager};
+use crate::cli::confirm::confirm_destructive;
+use crate::config::clock;
 use crate::config::paths::EnvelopePaths;
 use crate::config::settings::Settings;
 use crate::error::EnvelopeResult;
+use crate::storage::Storage;
 
 /// Backup subcommands
 #[derive(Subcommand)]
@@ -29,8 +33,12 @@ pub enum BackupCommands {
         backup: String,
 
         /// Skip confirmation prompt
-        #[arg(short, long)]
-        force: bool,
+        #[arg(short, long, alias = "force", short_alias = 'f')]
+        yes: bool,
+
+        /// Show what the restore would change without writing anything
+        #[arg(long)]
+        dry_run: bool,
     },
 
     /// Show information about a specific backup
@@ -39,11 +47,21 @@ pub enum BackupCommands {
         backup: String,
     },
 
+    /// Show what changed in the live data since a backup
+    Diff {
+        /// Backup filename or path (use 'latest' for most recent)
+        backup: String,
+
+        /// List each changed entity and its field changes
+        #[arg(short, long)]
+        verbose: bool,
+    },
+
     /// Delete old backups according to retention policy
     Prune {
         /// Skip confirmation prompt
-        #[arg(short, long)]
-        force: bool,
+        #[arg(short, long, alias = "force", short_alias = 'f')]
+        yes: bool,
     },
 }
 
@@ -82,7 +100,7 @@ pub fn handle_backup_command(
             println!();
 
             for (i, backup) in backups.iter().enumerate() {
-                let age = chrono::Utc::now().signed_duration_since(backup.created_at);
+                let age = clock::now().signed_duration_since(backup.created_at);
                 let age_str = format_duration(age);
 
                 let monthly_marker = if backup.is_monthly { " [monthly]" } else { "" };
@@ -113,7 +131,11 @@ pub fn handle_backup_command(
             println!("Total: {} backup(s)", backups.len());
         }
 
-        BackupCommands::Restore { backup, force } => {
+        BackupCommands::Restore {
+            backup,
+            yes,
+            dry_run,
+        } => {
             let backup_path = resolve_backup_path(&manager, paths, &backup)?;
 
             // Validate the backup first
@@ -135,14 +157,28 @@ pub fn handle_backup_command(
             println!("Status: {}", validation.summary());
             println!();
 
-            if !force {
+            if dry_run {
+                let plan = restore_manager.plan(&backup_path)?;
+                if plan.changes.is_empty() {
+                    println!("No changes. Current data already matches the backup.");
+                } else {
+                    println!("Restoring would change:");
+                    print_entity_counts(&plan.changes);
+                }
+                println!();
+                println!("Dry run: nothing was changed.");
+                return Ok(());
+            }
+
+            if settings.requires_confirmation(yes) {
                 println!("WARNING: This will overwrite ALL current data!");
-                println!("To proceed, run again with --force flag:");
-                println!("  envelope backup restore {} --force", backup);
+            }
+            if !confirm_destructive(settings, yes, "Restore from this backup?")? {
                 return Ok(());
             }
 
-            // Create a backup of current data before restoring
+            // Create a backup of current data before restoring, even when
+            // confirmation was skipped
             println!("Creating backup of current data before restore...");
             let pre_restore_backup = manager.create_backup()?;
             println!(
@@ -218,7 +254,42 @@ pub fn handle_backup_command(
             );
         }
 
-        BackupCommands::Prune { force } => {
+        BackupCommands::Diff { backup, verbose } => {
+            let backup_path = resolve_backup_path(&manager, paths, &backup)?;
+            let diff = BackupDiff::generate(paths, &backup_path)?;
+
+            println!("Changes Since Backup");
+            println!("====================");
+            println!("File: {}", backup_path.display());
+            println!(
+                "Created: {}",
+                diff.backup_date.format("%Y-%m-%d %H:%M:%S UTC")
+            );
+            println!();
+
+            if diff.is_empty() {
+                println!("No changes. Current data matches the backup.");
+                return Ok(());
+            }
+
+            for entity in &diff.entities {
+                print_entity_count(entity);
+
+                if verbose && !entity.is_empty() {
+                    print_changes("+", &entity.added);
+                    print_changes("-", &entity.removed);
+                    print_changes("~", &entity.modified);
+                    println!();
+                }
+            }
+
+            if !verbose {
+                println!();
+                println!("Run with --verbose to list each change.");
+            }
+        }
+
+        BackupCommands::Prune { yes } => {
             let backups = manager.list_backups()?;
             let retention = settings.backup_retention.clone();
 
@@ -262,9 +333,7 @@ pub fn handle_backup_command(
             );
             println!();
 
-            if !force {
-                println!("To delete old backups, run again with --force flag:");
-                println!("  envelope backup prune --force");
+            if !confirm_destructive(settings, yes, "Delete old backups?")? {
                 return Ok(());
             }
 
@@ -276,6 +345,30 @@ pub fn handle_backup_command(
     Ok(())
 }
 
+/// Handle the undo command
+///
+/// Rolls back to the backup taken before the latest destructive change.
+pub fn handle_undo_command(
+    storage: &Storage,
+    settings: &Settings,
+    yes: bool,
+) -> EnvelopeResult<()> {
+    if !confirm_destructive(settings, yes, "Undo the last destructive change?")? {
+        return Ok(());
+    }
+
+    let result = storage.undo_last()?;
+
+    println!(
+        "Undone: data is back to {}",
+        result.backup_date.format("%Y-%m-%d %H:%M:%S UTC")
+    );
+    println!("{}", result.summary());
+    println!("Run 'envelope backup restore latest' to redo.");
+
+    Ok(())
+}
+
 /// Resolve a backup identifier to a full path
 fn resolve_backup_path(
     manager: &BackupManager,
@@ -305,7 +398,7 @@ fn resolve_backup_path(
     }
 
     // Try adding common backup extensions
-    for ext in &["json", "yaml", "yml"] {
+    for ext in &["json", "json.gz", "yaml", "yml"] {
         let with_ext = paths.backup_dir().join(format!("{}.{}", backup, ext));
         if with_ext.exists() {
             return Ok(with_ext);
@@ -318,6 +411,34 @@ fn resolve_backup_path(
     })
 }
 
+/// Print the change counts for every entity type
+fn print_entity_counts(diff: &BackupDiff) {
+    for entity in &diff.entities {
+        print_entity_count(entity);
+    }
+}
+
+/// Print one entity type's added, removed, and modified counts
+fn print_entity_count(entity: &EntityDiff) {
+    println!(
+        "{:<16} {} added, {} removed, {} modified",
+        format!("{}:", entity.entity_type),
+        entity.added.len(),
+        entity.removed.len(),
+        entity.modified.len()
+    );
+}
+
+/// Print one line per changed entity, with field changes indented below
+fn print_changes(marker: &str, changes: &[EntityChange]) {
+    for change in changes {
+        println!("  {} {} ({})", marker, change.label, change.id);
+        if let Some(details) = &change.details {
+            println!("      {}", details);
+        }
+    }
+}
+
 /// Format a duration in human-readable form
 fn format_duration(duration: chrono::Duration) -> String {
     let total_seconds = duration.num_seconds();
@@ -3,11 +3,17 @@
 //! Implements CLI commands for budget management including period navigation,
 //! allocation, and overview.
 
+use std::collections::HashMap;
+
 use clap::Subcommand;
+use serde::Serialize;
 
+use crate::cli::confirm::confirm_with_backup;
+use crate::cli::output::{print_json, OutputFormat};
 use crate::config::settings::Settings;
 use crate::error::EnvelopeResult;
-use crate::services::{BudgetService, CategoryService, PeriodService};
+use crate::models::{BudgetPeriod, CategoryBudgetSummary, Money};
+use crate::services::{BudgetService, CategoryService, IncomeService, PeriodService};
 use crate::storage::Storage;
 
 /// Budget subcommands
@@ -26,6 +32,13 @@ pub enum BudgetCommands {
         period: Option<String>,
     },
 
+    /// Show Available to Budget (exits nonzero if overbudgeted)
+    Status {
+        /// Budget period (e.g., "2025-01", "January", "current", "last")
+        #[arg(short, long)]
+        period: Option<String>,
+    },
+
     /// List recent budget periods
     Periods {
         /// Number of periods to show
@@ -71,6 +84,16 @@ pub enum BudgetCommands {
         period: Option<String>,
     },
 
+    /// Zero every category's budgeted amount for a period
+    Clear {
+        /// Budget period (defaults to current)
+        #[arg(short, long)]
+        period: Option<String>,
+        /// Skip confirmation
+        #[arg(short, long)]
+        yes: bool,
+    },
+
     /// Show overspent categories
     Overspent {
         /// Budget period
@@ -79,11 +102,26 @@ pub enum BudgetCommands {
     },
 }
 
+/// Available to Budget as printed by `budget status --json`
+#[derive(Debug, Serialize)]
+struct BudgetStatus {
+    period: BudgetPeriod,
+    available_to_budget: Money,
+    overbudgeted: bool,
+    /// Available to Budget counting expected income, when that's shown
+    #[serde(skip_serializing_if = "Option::is_none")]
+    projected: Option<Money>,
+    /// The expected income included in `projected`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    expected_income: Option<Money>,
+}
+
 /// Handle a budget command
 pub fn handle_budget_command(
     storage: &Storage,
     settings: &Settings,
     cmd: BudgetCommands,
+    output: OutputFormat,
 ) -> EnvelopeResult<()> {
     let period_service = PeriodService::new(settings);
 
@@ -99,8 +137,19 @@ pub fn handle_budget_command(
             let category_service = CategoryService::new(storage);
             let groups = category_service.list_groups_with_categories()?;
 
-            // Get budget service for summaries
+            // Get every category's summary in one pass
             let budget_service = BudgetService::new(storage);
+            let summaries: HashMap<_, _> = budget_service
+                .get_category_summaries(&period)?
+                .into_iter()
+                .map(|s| (s.category_id, s))
+                .collect();
+            let summary_for = |id| {
+                summaries
+                    .get(&id)
+                    .cloned()
+                    .unwrap_or_else(|| CategoryBudgetSummary::empty(id))
+            };
 
             // Calculate totals
             let mut total_budgeted = crate::models::Money::zero();
@@ -112,7 +161,7 @@ pub fn handle_budget_command(
             // First pass: check if any categories have carryover
             for gwc in &groups {
                 for category in &gwc.categories {
-                    let summary = budget_service.get_category_summary(category.id, &period)?;
+                    let summary = summary_for(category.id);
                     if !summary.carryover.is_zero() {
                         has_any_carryover = true;
                         break;
@@ -143,7 +192,7 @@ pub fn handle_budget_command(
                 println!("{}", "-".repeat(72));
 
                 for category in &gwc.categories {
-                    let summary = budget_service.get_category_summary(category.id, &period)?;
+                    let summary = summary_for(category.id);
 
                     total_budgeted += summary.budgeted;
                     total_carryover += summary.carryover;
@@ -244,6 +293,57 @@ pub fn handle_budget_command(
             }
         }
 
+        BudgetCommands::Status { period } => {
+            let period = period_service.parse_or_current(period.as_deref())?;
+            let friendly = period_service.format_period_friendly(&period);
+
+            let budget_service = BudgetService::new(storage);
+            let atb = budget_service.get_available_to_budget(&period)?;
+
+            // Projected figure is informational; the exit status stays cash-based
+            let mut projected = None;
+            if settings.show_projected_available {
+                let income_service = IncomeService::new(storage);
+                let upcoming = income_service.get_upcoming_income(&period)?;
+                if !upcoming.is_zero() {
+                    projected = Some((
+                        income_service.project_available_to_budget(&period)?,
+                        upcoming,
+                    ));
+                }
+            }
+
+            if output.is_json() {
+                print_json(&BudgetStatus {
+                    period: period.clone(),
+                    available_to_budget: atb,
+                    overbudgeted: atb.is_negative(),
+                    projected: projected.map(|(amount, _)| amount),
+                    expected_income: projected.map(|(_, upcoming)| upcoming),
+                })?;
+            } else {
+                println!(
+                    "{:30} {:>10}",
+                    format!("Available to Budget ({}):", friendly),
+                    atb
+                );
+                if let Some((amount, upcoming)) = projected {
+                    println!(
+                        "{:30} {:>10}  (includes {} expected income)",
+                        "Projected:", amount, upcoming
+                    );
+                }
+            }
+
+            if atb.is_negative() {
+                return Err(crate::error::EnvelopeError::Budget(format!(
+                    "Overbudgeted by {} for {}",
+                    atb.abs(),
+                    friendly
+                )));
+            }
+        }
+
         BudgetCommands::Periods { count } => {
             println!("Recent Budget Periods:");
             println!();
@@ -339,6 +439,46 @@ pub fn handle_budget_command(
             );
         }
 
+        BudgetCommands::Clear { period, yes } => {
+            let period = period_service.parse_or_current(period.as_deref())?;
+            let friendly = period_service.format_period_friendly(&period);
+
+            let budget_service = BudgetService::new(storage);
+            let allocations = budget_service.budgeted_in_period(&period)?;
+            if allocations.is_empty() {
+                println!("Nothing is budgeted in {}.", friendly);
+                return Ok(());
+            }
+
+            let total: crate::models::Money = allocations.iter().map(|a| a.budgeted).sum();
+            let prompt = format!(
+                "Clear {} budgeted across {} categories in {}? Carryover is kept.",
+                total,
+                allocations.len(),
+                friendly
+            );
+            if !confirm_with_backup(storage, settings, yes, &prompt)? {
+                return Ok(());
+            }
+
+            let cleared = budget_service.clear_period(&period)?;
+
+            let category_service = CategoryService::new(storage);
+            for alloc in &allocations {
+                let name = category_service
+                    .get_category(alloc.category_id)?
+                    .map(|c| c.name)
+                    .unwrap_or_else(|| "Unknown".to_string());
+                println!("  {} {} -> $0.00", name, alloc.budgeted);
+            }
+            println!();
+            println!(
+                "Cleared {} categories in {}. Carryover from earlier periods is unchanged.",
+                cleared.len(),
+                friendly
+            );
+        }
+
         BudgetCommands::Rollover { period } => {
             let period = period_service.parse_or_current(period.as_deref())?;
             let friendly = period_service.format_period_friendly(&period);
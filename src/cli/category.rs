@@ -2,8 +2,12 @@
 //!
 //! Implements CLI commands for category and category group management.
 
+use std::io::IsTerminal;
+
 use clap::Subcommand;
 
+use crate::cli::confirm::confirm_with_backup;
+use crate::config::settings::Settings;
 use crate::display::category::{
     format_category_details, format_category_tree, format_group_details, format_group_list,
 };
@@ -15,7 +19,11 @@ use crate::storage::Storage;
 #[derive(Subcommand)]
 pub enum CategoryCommands {
     /// List all categories (organized by group)
-    List,
+    List {
+        /// Include archived categories
+        #[arg(short, long)]
+        all: bool,
+    },
 
     /// Create a new category
     Create {
@@ -48,6 +56,9 @@ pub enum CategoryCommands {
         /// Clear the goal
         #[arg(long)]
         clear_goal: bool,
+        /// Notes ("" to clear)
+        #[arg(long)]
+        notes: Option<String>,
     },
 
     /// Move a category to a different group
@@ -59,10 +70,25 @@ pub enum CategoryCommands {
         to: String,
     },
 
+    /// Archive a category, keeping its history
+    Archive {
+        /// Category name or ID
+        category: String,
+    },
+
+    /// Unarchive a category
+    Unarchive {
+        /// Category name or ID
+        category: String,
+    },
+
     /// Delete a category
     Delete {
         /// Category name or ID
         category: String,
+        /// Skip confirmation
+        #[arg(short, long)]
+        yes: bool,
     },
 
     /// Create a new category group
@@ -101,17 +127,30 @@ pub enum CategoryCommands {
         /// Force delete (also deletes all categories in the group)
         #[arg(long)]
         force: bool,
+        /// Skip confirmation
+        #[arg(short, long)]
+        yes: bool,
     },
 }
 
 /// Handle a category command
-pub fn handle_category_command(storage: &Storage, cmd: CategoryCommands) -> EnvelopeResult<()> {
+pub fn handle_category_command(
+    storage: &Storage,
+    settings: &Settings,
+    cmd: CategoryCommands,
+) -> EnvelopeResult<()> {
     let service = CategoryService::new(storage);
 
     match cmd {
-        CategoryCommands::List => {
-            let groups = service.list_groups_with_categories()?;
-            print!("{}", format_category_tree(&groups));
+        CategoryCommands::List { all } => {
+            let mut groups = service.list_groups_with_categories()?;
+            if !all {
+                for gwc in &mut groups {
+                    gwc.categories.retain(|c| !c.archived);
+                }
+            }
+            let color = std::io::stdout().is_terminal();
+            print!("{}", format_category_tree(&groups, color));
         }
 
         CategoryCommands::Create { name, group, goal } => {
@@ -151,13 +190,14 @@ pub fn handle_category_command(storage: &Storage, cmd: CategoryCommands) -> Enve
             name,
             goal,
             clear_goal,
+            notes,
         } => {
             let cat = service
                 .find_category(&category)?
                 .ok_or_else(|| EnvelopeError::category_not_found(&category))?;
 
-            if name.is_none() && goal.is_none() && !clear_goal {
-                println!("No changes specified. Use --name, --goal, or --clear-goal.");
+            if name.is_none() && goal.is_none() && !clear_goal && notes.is_none() {
+                println!("No changes specified. Use --name, --goal, --clear-goal, or --notes.");
                 return Ok(());
             }
 
@@ -170,8 +210,14 @@ pub fn handle_category_command(storage: &Storage, cmd: CategoryCommands) -> Enve
                 None
             };
 
-            let updated =
-                service.update_category(cat.id, name.as_deref(), goal_cents, clear_goal)?;
+            let mut updated = cat;
+            if let Some(notes) = &notes {
+                updated = service.set_notes(updated.id, notes)?;
+            }
+            if name.is_some() || goal_cents.is_some() || clear_goal {
+                updated =
+                    service.update_category(updated.id, name.as_deref(), goal_cents, clear_goal)?;
+            }
             println!("Updated category: {}", updated.name);
         }
 
@@ -191,11 +237,34 @@ pub fn handle_category_command(storage: &Storage, cmd: CategoryCommands) -> Enve
             println!("Moved '{}' to group '{}'", moved.name, target_group.name);
         }
 
-        CategoryCommands::Delete { category } => {
+        CategoryCommands::Archive { category } => {
             let cat = service
                 .find_category(&category)?
                 .ok_or_else(|| EnvelopeError::category_not_found(&category))?;
 
+            let archived = service.archive_category(cat.id)?;
+            println!("Archived category: {}", archived.name);
+        }
+
+        CategoryCommands::Unarchive { category } => {
+            let cat = service
+                .find_category(&category)?
+                .ok_or_else(|| EnvelopeError::category_not_found(&category))?;
+
+            let unarchived = service.unarchive_category(cat.id)?;
+            println!("Unarchived category: {}", unarchived.name);
+        }
+
+        CategoryCommands::Delete { category, yes } => {
+            let cat = service
+                .find_category(&category)?
+                .ok_or_else(|| EnvelopeError::category_not_found(&category))?;
+
+            let prompt = format!("Delete category '{}'?", cat.name);
+            if !confirm_with_backup(storage, settings, yes, &prompt)? {
+                return Ok(());
+            }
+
             service.delete_category(cat.id)?;
             println!("Deleted category: {}", cat.name);
         }
@@ -240,7 +309,7 @@ pub fn handle_category_command(storage: &Storage, cmd: CategoryCommands) -> Enve
             println!("Updated category group: {}", updated.name);
         }
 
-        CategoryCommands::DeleteGroup { group, force } => {
+        CategoryCommands::DeleteGroup { group, force, yes } => {
             let g = service
                 .find_group(&group)?
                 .ok_or_else(|| EnvelopeError::NotFound {
@@ -248,6 +317,11 @@ pub fn handle_category_command(storage: &Storage, cmd: CategoryCommands) -> Enve
                     identifier: group.clone(),
                 })?;
 
+            let prompt = format!("Delete category group '{}'?", g.name);
+            if !confirm_with_backup(storage, settings, yes, &prompt)? {
+                return Ok(());
+            }
+
             service.delete_group(g.id, force)?;
             println!("Deleted category group: {}", g.name);
         }
@@ -0,0 +1,56 @@
+//! Command reference CLI
+//!
+//! Dumps the TUI command palette as a cheat-sheet, generated from the same
+//! `COMMANDS` table the palette uses.
+
+use serde::Serialize;
+
+use crate::cli::output::{print_json, OutputFormat};
+use crate::error::EnvelopeResult;
+use crate::tui::commands::commands_by_section;
+
+/// A palette command as exposed in JSON output
+#[derive(Debug, Serialize)]
+struct CommandEntry {
+    section: &'static str,
+    name: &'static str,
+    description: &'static str,
+    shortcut: Option<&'static str>,
+}
+
+/// Print every palette command, optionally as JSON
+pub fn handle_commands_command(output: OutputFormat) -> EnvelopeResult<()> {
+    let sections = commands_by_section();
+
+    if output.is_json() {
+        let entries: Vec<CommandEntry> = sections
+            .iter()
+            .flat_map(|(section, commands)| {
+                commands.iter().map(move |cmd| CommandEntry {
+                    section,
+                    name: cmd.name,
+                    description: cmd.description,
+                    shortcut: cmd.shortcut,
+                })
+            })
+            .collect();
+
+        return print_json(&entries);
+    }
+
+    for (section, commands) in sections {
+        println!("{}", section);
+        for cmd in commands {
+            println!(
+                "  {:<20} {:<8} {}",
+                cmd.name,
+                cmd.shortcut.unwrap_or("-"),
+                cmd.description
+            );
+        }
+        println!();
+    }
+    println!("Run these from the TUI command palette (press ':').");
+
+    Ok(())
+}
@@ -0,0 +1,240 @@
+//! Config CLI command
+//!
+//! Shows paths and settings, and updates the settings that can be changed
+//! from the command line.
+
+use crate::config::{clock, paths::EnvelopePaths, settings::Settings};
+use crate::display::NegativeStyle;
+use crate::error::{EnvelopeError, EnvelopeResult};
+use crate::models::{Money, TargetRounding};
+use crate::rates::{normalize_currency, ExchangeRate};
+use crate::reports::MonthlyReportFormat;
+use crate::services::CategoryService;
+use crate::storage::Storage;
+
+/// Settings changes requested on the command line
+#[derive(Debug, Clone, Default)]
+pub struct ConfigOptions {
+    /// Default category name, or "none" to clear it
+    pub default_category: Option<String>,
+    /// Base currency code for converted reports
+    pub base_currency: Option<String>,
+    /// Exchange rates to add or replace, as `FROM/TO=RATE`
+    pub rates: Vec<String>,
+    /// Remove all saved exchange rates before adding `rates`
+    pub clear_rates: bool,
+    /// Largest difference reconciliation may absorb into one transaction
+    pub max_reconcile_adjustment: Option<String>,
+    /// Show projected Available to Budget alongside the cash figure
+    pub show_projected: Option<bool>,
+    /// Ask before destructive actions
+    pub confirm_destructive: Option<bool>,
+    /// Rounding policy for target suggestions: nearest, up, or down
+    pub target_rounding: Option<String>,
+    /// How negative amounts are written in tables: minus or parentheses
+    pub negative_style: Option<String>,
+    /// IANA timezone for today's date, or "local" for the system timezone
+    pub timezone: Option<String>,
+    /// File format for written monthly summaries: md, html, or json
+    pub monthly_report_format: Option<String>,
+    /// Write the just-closed period's summary on launch
+    pub auto_monthly_report: Option<bool>,
+    /// Write backups gzip-compressed
+    pub compress_backups: Option<bool>,
+}
+
+/// Handle the config command
+///
+/// Applies any requested settings changes and saves them, then prints the
+/// current configuration.
+pub fn handle_config_command(
+    paths: &EnvelopePaths,
+    settings: &mut Settings,
+    storage: &Storage,
+    options: ConfigOptions,
+) -> EnvelopeResult<()> {
+    let category_service = CategoryService::new(storage);
+    let mut changed = false;
+
+    if let Some(name) = options.default_category {
+        settings.default_category_id = if name.eq_ignore_ascii_case("none") {
+            None
+        } else {
+            let category = category_service
+                .find_category(&name)?
+                .ok_or_else(|| EnvelopeError::category_not_found(&name))?;
+            Some(category.id)
+        };
+        changed = true;
+    }
+
+    if let Some(code) = options.base_currency {
+        settings.base_currency = normalize_currency(&code).ok_or_else(|| {
+            EnvelopeError::Validation(format!(
+                "Invalid currency code '{}'. Use a 3-letter code like USD",
+                code
+            ))
+        })?;
+        changed = true;
+    }
+
+    if options.clear_rates {
+        settings.exchange_rates.clear();
+        changed = true;
+    }
+
+    for rate in &options.rates {
+        let rate = ExchangeRate::parse(rate)?;
+        // Replace any existing rate for the pair, in either direction
+        settings.exchange_rates.retain(|r| {
+            !((r.from == rate.from && r.to == rate.to) || (r.from == rate.to && r.to == rate.from))
+        });
+        settings.exchange_rates.push(rate);
+        changed = true;
+    }
+
+    if let Some(amount) = options.max_reconcile_adjustment {
+        let amount = Money::parse(&amount).map_err(|e| {
+            EnvelopeError::Validation(format!("Invalid amount '{}': {}", amount, e))
+        })?;
+        if amount.is_negative() {
+            return Err(EnvelopeError::Validation(
+                "Maximum reconcile adjustment can't be negative".into(),
+            ));
+        }
+        settings.reconcile_max_adjustment = amount;
+        changed = true;
+    }
+
+    if let Some(show) = options.show_projected {
+        settings.show_projected_available = show;
+        changed = true;
+    }
+
+    if let Some(confirm) = options.confirm_destructive {
+        settings.confirm_destructive = confirm;
+        changed = true;
+    }
+
+    if let Some(policy) = options.target_rounding {
+        settings.target_rounding = TargetRounding::parse(&policy).ok_or_else(|| {
+            EnvelopeError::Validation(format!(
+                "Invalid rounding '{}'. Use nearest, up, or down",
+                policy
+            ))
+        })?;
+        changed = true;
+    }
+
+    if let Some(style) = options.negative_style {
+        settings.negative_style = NegativeStyle::parse(&style).ok_or_else(|| {
+            EnvelopeError::Validation(format!(
+                "Invalid negative style '{}'. Use minus or parentheses",
+                style
+            ))
+        })?;
+        changed = true;
+    }
+
+    if let Some(name) = options.timezone {
+        settings.timezone = if name.eq_ignore_ascii_case("local") {
+            None
+        } else {
+            let timezone = clock::parse_timezone(&name).ok_or_else(|| {
+                EnvelopeError::Validation(format!(
+                    "Unknown timezone '{}'. Use a name like America/New_York, or local",
+                    name
+                ))
+            })?;
+            Some(timezone.name().to_string())
+        };
+        changed = true;
+    }
+
+    if let Some(format) = options.monthly_report_format {
+        settings.monthly_report_format = MonthlyReportFormat::parse(&format).ok_or_else(|| {
+            EnvelopeError::Validation(format!(
+                "Invalid report format '{}'. Use md, html, or json",
+                format
+            ))
+        })?;
+        changed = true;
+    }
+
+    if let Some(auto) = options.auto_monthly_report {
+        settings.auto_monthly_report = auto;
+        changed = true;
+    }
+
+    if let Some(compress) = options.compress_backups {
+        settings.backup_retention.compress = compress;
+        changed = true;
+    }
+
+    if changed {
+        storage.check_writable()?;
+        settings.save(paths)?;
+        println!("Settings updated.");
+        println!();
+    }
+
+    println!("EnvelopeCLI Configuration");
+    println!("========================");
+    println!("Profile:          {}", paths.profile());
+    println!("Config directory: {}", paths.config_dir().display());
+    println!("Data directory:   {}", paths.data_dir().display());
+    println!("Backup directory: {}", paths.backup_dir().display());
+    println!("Reports directory: {}", paths.reports_dir().display());
+    println!();
+    println!("Settings:");
+    println!("  Budget period type: {:?}", settings.budget_period_type);
+    println!("  Encryption enabled: {}", settings.is_encryption_enabled());
+
+    // A deleted category is shown as such; new transactions ignore it
+    let default_name = match settings.default_category_id {
+        Some(id) => category_service
+            .get_category(id)?
+            .map(|c| c.name)
+            .unwrap_or_else(|| "(deleted category, ignored)".to_string()),
+        None => "(none)".to_string(),
+    };
+    println!("  Default category:   {}", default_name);
+    println!("  Base currency:      {}", settings.base_currency);
+    println!(
+        "  Max reconcile adjustment: {}",
+        settings.reconcile_max_adjustment
+    );
+    println!(
+        "  Show projected ATB: {}",
+        settings.show_projected_available
+    );
+    println!(
+        "  Confirm destructive actions: {}",
+        settings.confirm_destructive
+    );
+    println!("  Target rounding:    {}", settings.target_rounding);
+    println!("  Negative amounts:   {}", settings.negative_style);
+    println!(
+        "  Timezone:           {}",
+        settings.timezone.as_deref().unwrap_or("(system)")
+    );
+    println!(
+        "  Monthly report format: {}",
+        settings.monthly_report_format
+    );
+    println!("  Auto monthly report: {}", settings.auto_monthly_report);
+    println!(
+        "  Compress backups:   {}",
+        settings.backup_retention.compress
+    );
+
+    if !settings.exchange_rates.is_empty() {
+        println!();
+        println!("Exchange rates:");
+        for rate in &settings.exchange_rates {
+            println!("  1 {} = {} {}", rate.from, rate.rate, rate.to);
+        }
+    }
+
+    Ok(())
+}
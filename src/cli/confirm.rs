@@ -0,0 +1,57 @@
+//! Confirmation for destructive commands
+//!
+//! Deleting and restoring all go through the same policy: `--yes` or the
+//! `confirm_destructive` setting skip the prompt, and nothing is destroyed
+//! without an answer when no one is at the terminal.
+
+use std::io::{IsTerminal, Write};
+
+use crate::config::settings::Settings;
+use crate::error::EnvelopeResult;
+use crate::storage::Storage;
+
+/// Decide whether a destructive command should go ahead
+///
+/// Proceeds without asking when `yes` is set or confirmation is turned off.
+/// Otherwise asks on an interactive terminal, and declines when stdin is not
+/// one.
+pub fn confirm_destructive(settings: &Settings, yes: bool, prompt: &str) -> EnvelopeResult<bool> {
+    if !settings.requires_confirmation(yes) {
+        return Ok(true);
+    }
+
+    if !std::io::stdin().is_terminal() {
+        println!("Use --yes to confirm.");
+        return Ok(false);
+    }
+
+    print!("{} (yes/no): ", prompt);
+    std::io::stdout().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    if matches!(answer.trim().to_lowercase().as_str(), "y" | "yes") {
+        Ok(true)
+    } else {
+        println!("Cancelled.");
+        Ok(false)
+    }
+}
+
+/// Confirm a destructive change to the data, backing it up first
+///
+/// The backup runs whether or not a prompt was shown, so skipping
+/// confirmation never leaves the change without a way back.
+pub fn confirm_with_backup(
+    storage: &Storage,
+    settings: &Settings,
+    yes: bool,
+    prompt: &str,
+) -> EnvelopeResult<bool> {
+    if !confirm_destructive(settings, yes, prompt)? {
+        return Ok(false);
+    }
+
+    storage.backup_before_destructive()?;
+    Ok(true)
+}
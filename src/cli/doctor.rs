@@ -0,0 +1,155 @@
+//! Doctor CLI command
+//!
+//! Runs the data integrity checks and optionally applies safe fixes, and
+//! recovers corrupted data files from backup at startup.
+
+use std::io::{IsTerminal, Write};
+use std::path::PathBuf;
+
+use crate::backup::{BackupManager, RestoreManager};
+use crate::config::paths::EnvelopePaths;
+use crate::config::settings::BackupRetention;
+use crate::error::{EnvelopeError, EnvelopeResult};
+use crate::services::{DoctorReport, DoctorService, Severity};
+use crate::storage::Storage;
+
+/// Load storage, offering to restore corrupted data files from backup
+///
+/// When a data file can't be parsed, the file is restored from the most
+/// recent backup: without asking if `repair` is set, otherwise after a
+/// prompt on an interactive terminal. If the file can't be restored, the
+/// corruption error naming the file is returned.
+pub fn load_storage(
+    paths: &EnvelopePaths,
+    retention: &BackupRetention,
+    repair: bool,
+    read_only: bool,
+) -> EnvelopeResult<Storage> {
+    let mut restored: Vec<PathBuf> = Vec::new();
+
+    loop {
+        let mut storage = Storage::new(paths.clone())?.with_backup_retention(retention.clone());
+        storage.set_read_only(read_only);
+        let err = match storage.load_all() {
+            Ok(()) => return Ok(storage),
+            Err(err) => err,
+        };
+
+        // Give up on anything but a newly corrupted file, and never restore
+        // over it in read-only mode
+        let file = match err.corrupted_path() {
+            Some(file) if !read_only && !restored.iter().any(|r| r == file) => file.to_path_buf(),
+            _ => return Err(err),
+        };
+
+        eprintln!("{}", err.user_message());
+
+        let manager = BackupManager::new(paths.clone(), retention.clone());
+        let Some(backup) = manager.get_latest_backup()? else {
+            eprintln!("No backup is available to restore it from.");
+            return Err(err);
+        };
+
+        if !repair && !confirm_restore(&backup.filename)? {
+            return Err(err);
+        }
+
+        let restore_manager = RestoreManager::new(paths.clone());
+        if !restore_manager.restore_single_file(&backup.path, &file)? {
+            eprintln!("The backup has no copy of {}.", file.display());
+            return Err(err);
+        }
+
+        eprintln!(
+            "Restored {} from {} (previous contents kept as .corrupt).",
+            file.display(),
+            backup.filename
+        );
+        restored.push(file);
+    }
+}
+
+/// Ask whether to restore a corrupted file, declining when not interactive
+fn confirm_restore(backup_name: &str) -> EnvelopeResult<bool> {
+    if !std::io::stdin().is_terminal() {
+        eprintln!(
+            "Run 'envelope doctor --repair' to restore it from {}.",
+            backup_name
+        );
+        return Ok(false);
+    }
+
+    eprint!("Restore this file from backup {}? (yes/no): ", backup_name);
+    std::io::stderr().flush()?;
+
+    let mut answer = String::new();
+    std::io::stdin().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
+
+/// Check data integrity, exiting with an error if problems remain
+pub fn handle_doctor_command(storage: &Storage, fix: bool) -> EnvelopeResult<()> {
+    let service = DoctorService::new(storage);
+    let mut report = service.check()?;
+
+    if report.is_healthy() {
+        println!("No problems found.");
+        return Ok(());
+    }
+
+    print_report(&report);
+
+    if fix && report.fixable_count() > 0 {
+        let applied = service.fix(&report)?;
+        println!();
+        println!("Applied {} fix(es).", applied);
+        report = service.check()?;
+        if report.is_healthy() {
+            println!("No problems remain.");
+            return Ok(());
+        }
+        println!();
+        println!("Remaining problems:");
+        print_report(&report);
+    } else if report.fixable_count() > 0 {
+        println!();
+        println!(
+            "{} problem(s) can be fixed automatically. Run 'envelope doctor --fix'.",
+            report.fixable_count()
+        );
+    }
+
+    if report.error_count() > 0 {
+        return Err(EnvelopeError::Validation(format!(
+            "{} error(s) found",
+            report.error_count()
+        )));
+    }
+
+    Ok(())
+}
+
+/// Print findings grouped by severity
+fn print_report(report: &DoctorReport) {
+    for severity in [Severity::Error, Severity::Warning] {
+        let findings: Vec<_> = report
+            .findings
+            .iter()
+            .filter(|f| f.severity == severity)
+            .collect();
+        if findings.is_empty() {
+            continue;
+        }
+
+        println!();
+        println!("{}s ({}):", severity, findings.len());
+        for finding in findings {
+            let marker = if finding.fix.is_some() {
+                " [fixable]"
+            } else {
+                ""
+            };
+            println!("  - {}{}", finding.message, marker);
+        }
+    }
+}
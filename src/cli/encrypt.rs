@@ -2,11 +2,14 @@
 //!
 //! Provides commands for enabling, disabling, and managing encryption.
 
+use std::time::Duration;
+
 use clap::Subcommand;
 
 use crate::config::{paths::EnvelopePaths, settings::Settings};
 use crate::crypto::{
-    decrypt_string, derive_key, encrypt_string, EncryptedData, KeyDerivationParams,
+    benchmark, decrypt_string, derive_key, encrypt_string, DerivedKey, EncryptedData, KdfCosts,
+    KeyDerivationParams, SecureString,
 };
 use crate::error::{EnvelopeError, EnvelopeResult};
 use crate::storage::Storage;
@@ -29,6 +32,22 @@ pub enum EncryptCommands {
 
     /// Verify your passphrase is correct
     Verify,
+
+    /// Set the Argon2 costs used to derive the encryption key
+    SetParams {
+        /// Memory cost in KiB
+        #[arg(long, value_name = "KIB")]
+        memory: Option<u32>,
+        /// Number of iterations
+        #[arg(long)]
+        iterations: Option<u32>,
+        /// Degree of parallelism
+        #[arg(long)]
+        parallelism: Option<u32>,
+    },
+
+    /// Time key derivation and suggest costs for this machine
+    Benchmark,
 }
 
 /// Handle encryption commands
@@ -38,12 +57,26 @@ pub fn handle_encrypt_command(
     storage: &Storage,
     cmd: EncryptCommands,
 ) -> EnvelopeResult<()> {
+    // Everything but the inspection commands rewrites settings or data
+    if !matches!(
+        cmd,
+        EncryptCommands::Status | EncryptCommands::Verify | EncryptCommands::Benchmark
+    ) {
+        storage.check_writable()?;
+    }
+
     match cmd {
         EncryptCommands::Enable => enable_encryption(paths, settings, storage),
         EncryptCommands::Disable => disable_encryption(paths, settings, storage),
-        EncryptCommands::ChangePassphrase => change_passphrase(paths, settings),
+        EncryptCommands::ChangePassphrase => change_passphrase(paths, settings, storage),
         EncryptCommands::Status => show_status(settings),
         EncryptCommands::Verify => verify_passphrase(settings),
+        EncryptCommands::SetParams {
+            memory,
+            iterations,
+            parallelism,
+        } => set_params(paths, settings, storage, memory, iterations, parallelism),
+        EncryptCommands::Benchmark => run_benchmark(settings),
     }
 }
 
@@ -51,7 +84,7 @@ pub fn handle_encrypt_command(
 fn enable_encryption(
     paths: &EnvelopePaths,
     settings: &mut Settings,
-    _storage: &Storage,
+    storage: &Storage,
 ) -> EnvelopeResult<()> {
     if settings.is_encryption_enabled() {
         println!("Encryption is already enabled.");
@@ -72,7 +105,7 @@ fn enable_encryption(
     let passphrase = prompt_new_passphrase()?;
 
     // Generate key derivation params
-    let key_params = KeyDerivationParams::new();
+    let key_params = KeyDerivationParams::with_costs(&settings.encryption.kdf_costs);
 
     // Derive key
     println!("Deriving encryption key...");
@@ -93,6 +126,10 @@ fn enable_encryption(
     // Save settings
     settings.save(paths)?;
 
+    // The audit log holds before/after copies of everything, so it's
+    // encrypted right away rather than on the next save
+    storage.audit().rekey(Some(key))?;
+
     println!();
     println!("Encryption enabled successfully!");
     println!();
@@ -106,7 +143,7 @@ fn enable_encryption(
 fn disable_encryption(
     paths: &EnvelopePaths,
     settings: &mut Settings,
-    _storage: &Storage,
+    storage: &Storage,
 ) -> EnvelopeResult<()> {
     if !settings.is_encryption_enabled() {
         println!("Encryption is not enabled.");
@@ -119,7 +156,7 @@ fn disable_encryption(
 
     // Verify current passphrase
     let passphrase = prompt_passphrase("Enter current passphrase: ")?;
-    verify_passphrase_internal(settings, &passphrase)?;
+    let key = verify_passphrase_internal(settings, &passphrase)?;
 
     println!("Passphrase verified.");
     println!();
@@ -136,6 +173,10 @@ fn disable_encryption(
         return Ok(());
     }
 
+    // Decrypt the audit log while the key is still known
+    storage.audit().set_key(Some(key));
+    storage.audit().rekey(None)?;
+
     // Update settings
     settings.encryption.enabled = false;
     settings.encryption.key_params = None;
@@ -153,7 +194,11 @@ fn disable_encryption(
 }
 
 /// Change the encryption passphrase
-fn change_passphrase(paths: &EnvelopePaths, settings: &mut Settings) -> EnvelopeResult<()> {
+fn change_passphrase(
+    paths: &EnvelopePaths,
+    settings: &mut Settings,
+    storage: &Storage,
+) -> EnvelopeResult<()> {
     if !settings.is_encryption_enabled() {
         println!("Encryption is not enabled.");
         println!("Use 'envelope encrypt enable' to enable encryption first.");
@@ -166,7 +211,7 @@ fn change_passphrase(paths: &EnvelopePaths, settings: &mut Settings) -> Envelope
 
     // Verify current passphrase
     let current = prompt_passphrase("Enter current passphrase: ")?;
-    verify_passphrase_internal(settings, &current)?;
+    let current_key = verify_passphrase_internal(settings, &current)?;
 
     println!("Current passphrase verified.");
     println!();
@@ -175,7 +220,7 @@ fn change_passphrase(paths: &EnvelopePaths, settings: &mut Settings) -> Envelope
     let new_passphrase = prompt_new_passphrase()?;
 
     // Generate new key derivation params
-    let new_key_params = KeyDerivationParams::new();
+    let new_key_params = KeyDerivationParams::with_costs(&settings.encryption.kdf_costs);
 
     // Derive new key
     println!("Deriving new encryption key...");
@@ -187,6 +232,10 @@ fn change_passphrase(paths: &EnvelopePaths, settings: &mut Settings) -> Envelope
         EnvelopeError::Encryption(format!("Failed to serialize verification: {}", e))
     })?;
 
+    // Re-encrypt the audit log under the new key
+    storage.audit().set_key(Some(current_key));
+    storage.audit().rekey(Some(new_key))?;
+
     // Update settings
     settings.encryption.key_params = Some(new_key_params);
     settings.encryption.verification_hash = Some(verification_json);
@@ -201,6 +250,91 @@ fn change_passphrase(paths: &EnvelopePaths, settings: &mut Settings) -> Envelope
     Ok(())
 }
 
+/// Change the Argon2 costs
+///
+/// With encryption enabled the key is derived again from the current
+/// passphrase with the new costs and a fresh salt, so the data is
+/// re-encrypted under the new key on the next save.
+fn set_params(
+    paths: &EnvelopePaths,
+    settings: &mut Settings,
+    storage: &Storage,
+    memory: Option<u32>,
+    iterations: Option<u32>,
+    parallelism: Option<u32>,
+) -> EnvelopeResult<()> {
+    let current = settings.encryption.kdf_costs;
+    let costs = KdfCosts {
+        memory_cost: memory.unwrap_or(current.memory_cost),
+        time_cost: iterations.unwrap_or(current.time_cost),
+        parallelism: parallelism.unwrap_or(current.parallelism),
+    };
+    costs.validate()?;
+
+    if costs == current {
+        println!("Key derivation parameters unchanged.");
+        return Ok(());
+    }
+
+    if settings.is_encryption_enabled() {
+        let passphrase = prompt_passphrase("Enter current passphrase: ")?;
+        let current_key = verify_passphrase_internal(settings, &passphrase)?;
+
+        let key_params = KeyDerivationParams::with_costs(&costs);
+        println!("Deriving encryption key with the new parameters...");
+        let key = derive_key(&passphrase, &key_params)?;
+
+        let verification = encrypt_string("envelope_verify", &key)?;
+        let verification_json = serde_json::to_string(&verification).map_err(|e| {
+            EnvelopeError::Encryption(format!("Failed to serialize verification: {}", e))
+        })?;
+
+        storage.audit().set_key(Some(current_key));
+        storage.audit().rekey(Some(key))?;
+
+        settings.encryption.key_params = Some(key_params);
+        settings.encryption.verification_hash = Some(verification_json);
+    }
+
+    settings.encryption.kdf_costs = costs;
+    settings.save(paths)?;
+
+    println!("Key derivation parameters updated:");
+    print_costs(&costs);
+    if settings.is_encryption_enabled() {
+        println!("Your data will be re-encrypted with the new key on the next save.");
+    }
+
+    Ok(())
+}
+
+/// Suggest costs that take about a second to derive on this machine
+fn run_benchmark(settings: &Settings) -> EnvelopeResult<()> {
+    println!("Timing key derivation (this takes a few seconds)...");
+    let costs = benchmark(Duration::from_secs(1))?;
+
+    println!();
+    println!("Suggested parameters for ~1s derivation:");
+    print_costs(&costs);
+    println!();
+    println!("Current parameters:");
+    print_costs(&settings.encryption.kdf_costs);
+    println!();
+    println!(
+        "Apply with: envelope encrypt set-params --memory {} --iterations {} --parallelism {}",
+        costs.memory_cost, costs.time_cost, costs.parallelism
+    );
+
+    Ok(())
+}
+
+/// Print Argon2 costs, one per line
+fn print_costs(costs: &KdfCosts) {
+    println!("  Memory Cost: {} KiB", costs.memory_cost);
+    println!("  Time Cost: {} iterations", costs.time_cost);
+    println!("  Parallelism: {} threads", costs.parallelism);
+}
+
 /// Show encryption status
 fn show_status(settings: &Settings) -> EnvelopeResult<()> {
     println!("Encryption Status");
@@ -237,7 +371,7 @@ fn verify_passphrase(settings: &Settings) -> EnvelopeResult<()> {
     let passphrase = prompt_passphrase("Enter passphrase: ")?;
 
     match verify_passphrase_internal(settings, &passphrase) {
-        Ok(()) => {
+        Ok(_) => {
             println!("Passphrase is correct!");
             Ok(())
         }
@@ -248,8 +382,8 @@ fn verify_passphrase(settings: &Settings) -> EnvelopeResult<()> {
     }
 }
 
-/// Internal passphrase verification
-fn verify_passphrase_internal(settings: &Settings, passphrase: &str) -> EnvelopeResult<()> {
+/// Internal passphrase verification, returning the key it derives
+fn verify_passphrase_internal(settings: &Settings, passphrase: &str) -> EnvelopeResult<DerivedKey> {
     let key_params = settings
         .encryption
         .key_params
@@ -273,11 +407,52 @@ fn verify_passphrase_internal(settings: &Settings, passphrase: &str) -> Envelope
         return Err(EnvelopeError::Encryption("Invalid passphrase".to_string()));
     }
 
+    Ok(key)
+}
+
+/// Environment variable that supplies the passphrase instead of a prompt
+pub const PASSPHRASE_ENV: &str = "ENVELOPE_PASSPHRASE";
+
+/// Ask for the passphrase and unlock the encrypted audit log
+///
+/// Does nothing when encryption is off. Otherwise the passphrase is checked
+/// and its key is handed to the audit logger, which encrypts new entries
+/// and decrypts existing ones with it.
+pub fn unlock_storage(settings: &Settings, storage: &Storage) -> EnvelopeResult<()> {
+    if !settings.is_encryption_enabled() {
+        return Ok(());
+    }
+
+    storage.audit().set_key(Some(unlock_key(settings)?));
     Ok(())
 }
 
+/// Unlock the encrypted audit log only once a command uses it
+///
+/// Like [`unlock_storage`], but the passphrase is asked for on the first
+/// audit write or read, so listing and reporting never prompt.
+pub fn unlock_storage_on_demand(settings: &Settings, storage: &Storage) {
+    if !settings.is_encryption_enabled() {
+        return;
+    }
+
+    let settings = settings.clone();
+    storage
+        .audit()
+        .set_key_source(Box::new(move || unlock_key(&settings)));
+}
+
+/// Get the audit log key from `ENVELOPE_PASSPHRASE`, or else by prompting
+fn unlock_key(settings: &Settings) -> EnvelopeResult<DerivedKey> {
+    let passphrase = match std::env::var(PASSPHRASE_ENV) {
+        Ok(passphrase) => SecureString::from(passphrase),
+        Err(_) => prompt_passphrase("Passphrase: ")?,
+    };
+    verify_passphrase_internal(settings, &passphrase)
+}
+
 /// Prompt for a new passphrase with confirmation
-fn prompt_new_passphrase() -> EnvelopeResult<String> {
+pub(crate) fn prompt_new_passphrase() -> EnvelopeResult<SecureString> {
     loop {
         let pass1 = prompt_passphrase("Enter new passphrase: ")?;
 
@@ -288,7 +463,7 @@ fn prompt_new_passphrase() -> EnvelopeResult<String> {
 
         let pass2 = prompt_passphrase("Confirm passphrase: ")?;
 
-        if pass1 != pass2 {
+        if pass1.as_str() != pass2.as_str() {
             println!("Passphrases do not match. Please try again.");
             continue;
         }
@@ -298,8 +473,9 @@ fn prompt_new_passphrase() -> EnvelopeResult<String> {
 }
 
 /// Prompt for a passphrase (hidden input)
-fn prompt_passphrase(prompt: &str) -> EnvelopeResult<String> {
+pub(crate) fn prompt_passphrase(prompt: &str) -> EnvelopeResult<SecureString> {
     rpassword::prompt_password(prompt)
+        .map(SecureString::from)
         .map_err(|e| EnvelopeError::Encryption(format!("Failed to read passphrase: {}", e)))
 }
 
@@ -2,8 +2,10 @@
 //!
 //! Provides commands for exporting data in various formats.
 
+use crate::cli::encrypt::prompt_new_passphrase;
+use crate::config::settings::Settings;
 use crate::error::EnvelopeResult;
-use crate::export::{csv, json, yaml};
+use crate::export::{csv, export_encrypted, json, yaml, ExportContentFormat, SplitRows};
 use crate::storage::Storage;
 use clap::{Subcommand, ValueEnum};
 use std::fs::File;
@@ -19,6 +21,29 @@ pub enum ExportFormat {
     Json,
     /// YAML format (full database, human-readable)
     Yaml,
+    /// JSON Lines (transactions only, one per line, streamed)
+    Jsonl,
+}
+
+/// How split transactions appear in a transactions CSV
+#[derive(Debug, Clone, Copy, ValueEnum)]
+pub enum SplitsOption {
+    /// One row per split, with no parent row
+    Flatten,
+    /// Parent row followed by split rows that reference it
+    Child,
+    /// Parent row only
+    ParentOnly,
+}
+
+impl From<SplitsOption> for SplitRows {
+    fn from(option: SplitsOption) -> Self {
+        match option {
+            SplitsOption::Flatten => SplitRows::Flatten,
+            SplitsOption::Child => SplitRows::Child,
+            SplitsOption::ParentOnly => SplitRows::ParentOnly,
+        }
+    }
 }
 
 /// Export subcommands
@@ -36,12 +61,21 @@ pub enum ExportCommands {
         /// Pretty-print JSON output
         #[arg(long)]
         pretty: bool,
+
+        /// Encrypt the export with a passphrase (JSON and YAML only)
+        #[arg(long)]
+        encrypt: bool,
     },
 
     /// Export transactions to CSV
     Transactions {
         /// Output file path
         output: PathBuf,
+
+        /// How to write split transactions, adding a `Split Of` column
+        /// (default: parent and split rows, without `Split Of`)
+        #[arg(long, value_enum)]
+        splits: Option<SplitsOption>,
     },
 
     /// Export budget allocations to CSV
@@ -65,14 +99,27 @@ pub enum ExportCommands {
 }
 
 /// Handle export commands
-pub fn handle_export_command(storage: &Storage, cmd: ExportCommands) -> EnvelopeResult<()> {
+pub fn handle_export_command(
+    storage: &Storage,
+    settings: &Settings,
+    cmd: ExportCommands,
+) -> EnvelopeResult<()> {
     match cmd {
         ExportCommands::All {
             output,
             format,
             pretty,
-        } => handle_export_all(storage, output, format, pretty),
-        ExportCommands::Transactions { output } => handle_export_transactions(storage, output),
+            encrypt,
+        } => {
+            if encrypt {
+                handle_export_encrypted(storage, settings, output, format, pretty)
+            } else {
+                handle_export_all(storage, output, format, pretty)
+            }
+        }
+        ExportCommands::Transactions { output, splits } => {
+            handle_export_transactions(storage, output, splits)
+        }
         ExportCommands::Allocations { output, months } => {
             handle_export_allocations(storage, output, months)
         }
@@ -112,13 +159,71 @@ fn handle_export_all(
             yaml::export_full_yaml(storage, &mut writer)?;
             println!("Full database exported to: {}", output.display());
         }
+        ExportFormat::Jsonl => {
+            json::export_transactions_jsonl(storage, &mut writer)?;
+            println!("Transactions exported to: {}", output.display());
+        }
     }
 
     Ok(())
 }
 
+/// Handle encrypted full export
+fn handle_export_encrypted(
+    storage: &Storage,
+    settings: &Settings,
+    output: PathBuf,
+    format: ExportFormat,
+    pretty: bool,
+) -> EnvelopeResult<()> {
+    let content_format = match format {
+        ExportFormat::Json => ExportContentFormat::Json,
+        ExportFormat::Yaml => ExportContentFormat::Yaml,
+        ExportFormat::Csv | ExportFormat::Jsonl => {
+            return Err(crate::error::EnvelopeError::Export(
+                "Encryption is only supported for JSON and YAML exports".to_string(),
+            ))
+        }
+    };
+
+    println!("Choose a passphrase for this export.");
+    println!("You will need it to import the file again.");
+    let passphrase = prompt_new_passphrase()?;
+
+    let file = File::create(&output).map_err(|e| {
+        crate::error::EnvelopeError::Export(format!(
+            "Failed to create file {}: {}",
+            output.display(),
+            e
+        ))
+    })?;
+    let mut writer = BufWriter::new(file);
+
+    println!("Encrypting export...");
+    export_encrypted(
+        storage,
+        &mut writer,
+        content_format,
+        pretty,
+        passphrase.as_str(),
+        &settings.encryption.kdf_costs,
+    )?;
+
+    println!("Encrypted database exported to: {}", output.display());
+    println!(
+        "Import it with: envelope import {} --decrypt",
+        output.display()
+    );
+
+    Ok(())
+}
+
 /// Handle transactions export
-fn handle_export_transactions(storage: &Storage, output: PathBuf) -> EnvelopeResult<()> {
+fn handle_export_transactions(
+    storage: &Storage,
+    output: PathBuf,
+    splits: Option<SplitsOption>,
+) -> EnvelopeResult<()> {
     let file = File::create(&output).map_err(|e| {
         crate::error::EnvelopeError::Export(format!(
             "Failed to create file {}: {}",
@@ -128,7 +233,12 @@ fn handle_export_transactions(storage: &Storage, output: PathBuf) -> EnvelopeRes
     })?;
     let mut writer = BufWriter::new(file);
 
-    csv::export_transactions_csv(storage, &mut writer)?;
+    match splits {
+        Some(splits) => {
+            csv::export_transactions_csv_with_splits(storage, &mut writer, splits.into())?
+        }
+        None => csv::export_transactions_csv(storage, &mut writer)?,
+    }
 
     let count = storage.transactions.get_all()?.len();
     println!("Exported {} transactions to: {}", count, output.display());
@@ -223,12 +333,14 @@ fn handle_export_info(storage: &Storage) -> EnvelopeResult<()> {
     }
 
     println!("\nAvailable Export Formats:");
-    println!("  csv  - CSV format (transactions, allocations, or accounts)");
-    println!("  json - JSON format (full database, machine-readable)");
-    println!("  yaml - YAML format (full database, human-readable)");
+    println!("  csv   - CSV format (transactions, allocations, or accounts)");
+    println!("  json  - JSON format (full database, machine-readable)");
+    println!("  yaml  - YAML format (full database, human-readable)");
+    println!("  jsonl - JSON Lines (transactions only, streamed one per line)");
 
     println!("\nExamples:");
     println!("  envelope export all backup.json --format json --pretty");
+    println!("  envelope export all backup.enc.json --format json --encrypt");
     println!("  envelope export transactions txns.csv");
     println!("  envelope export accounts accounts.csv");
 
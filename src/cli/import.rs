@@ -1,90 +1,311 @@
-//! CLI command handler for CSV import
+//! CLI command handler for CSV and OFX import
 //!
 //! Handles importing transactions from CSV files with automatic
-//! column mapping detection and duplicate checking.
+//! column mapping detection, and from OFX/QFX bank downloads, with
+//! duplicate checking.
 
+use std::io::{IsTerminal, Write};
 use std::path::Path;
 
+use clap::ValueEnum;
+
+use crate::cli::confirm::confirm_with_backup;
+use crate::cli::encrypt::prompt_passphrase;
+use crate::config::settings::Settings;
 use crate::error::{EnvelopeError, EnvelopeResult};
+use crate::export::{restore_from_export, EncryptedExport};
 use crate::models::{Account, AccountId};
 use crate::services::{
-    AccountService, ImportPreviewEntry, ImportService, ImportStatus, ParsedTransaction,
+    decode_csv_bytes, is_ofx, AccountService, ImportPreviewEntry, ImportService, ImportStatus,
+    ParsedTransaction,
 };
 use crate::storage::Storage;
 
+/// File format of an import
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum ImportFormat {
+    /// Comma- or otherwise-delimited text with a detected column mapping
+    Csv,
+    /// OFX or QFX bank download
+    #[value(alias = "qfx")]
+    Ofx,
+}
+
+/// Row-skipping and decoding options for the import command
+#[derive(Debug, Clone, Default)]
+pub struct ImportOptions {
+    /// File format (detected from the extension and contents if `None`)
+    pub format: Option<ImportFormat>,
+    /// Preamble lines to skip before the header (auto-detected if `None`)
+    pub skip_rows: Option<usize>,
+    /// Trailing footer rows to skip
+    pub skip_footer: usize,
+    /// Skip rows whose date fails to parse instead of reporting them as errors
+    pub skip_invalid_dates: bool,
+    /// Character encoding of the file (UTF-8 if `None`)
+    pub encoding: Option<String>,
+    /// Days either side of a row's date to look for likely duplicates
+    pub duplicate_window: Option<u32>,
+    /// Import likely duplicates instead of skipping them
+    pub import_likely: bool,
+    /// Show the preview counts without importing
+    pub dry_run: bool,
+}
+
 /// Handle the import command
-pub fn handle_import_command(storage: &Storage, file: &str, account: &str) -> EnvelopeResult<()> {
+///
+/// Without an unambiguous `account`, the user picks the target account from
+/// a list when running interactively.
+pub fn handle_import_command(
+    storage: &Storage,
+    file: &str,
+    account: Option<&str>,
+    options: &ImportOptions,
+) -> EnvelopeResult<()> {
     let account_service = AccountService::new(storage);
-    let import_service = ImportService::new(storage);
+    let mut import_service = ImportService::new(storage);
+    if let Some(days) = options.duplicate_window {
+        import_service = import_service.with_duplicate_window(days);
+    }
 
     let (parsed, target_account) =
-        read_and_parse_csv(&import_service, &account_service, file, account)?;
+        read_and_parse_file(&import_service, &account_service, file, account, options)?;
 
     if parsed.is_empty() {
-        println!("No transactions found in CSV file.");
+        println!("No transactions found in {}.", file);
         return Ok(());
     }
 
-    let preview = generate_and_display_preview(&import_service, &parsed, &target_account)?;
+    let preview = generate_and_display_preview(
+        &import_service,
+        &parsed,
+        &target_account,
+        options.import_likely,
+    )?;
 
     let new_count = preview
         .iter()
         .filter(|e| e.status == ImportStatus::New)
         .count();
 
-    if new_count > 0 {
+    if options.dry_run {
+        println!("Dry run: {} transaction(s) would be imported.", new_count);
+    } else if new_count > 0 {
         execute_import(&import_service, &preview, target_account.id)?;
     }
 
     Ok(())
 }
 
-/// Read and parse CSV file, returning parsed transactions and target account
-fn read_and_parse_csv(
+/// Handle `import --decrypt`: restore an encrypted full export
+///
+/// The file is decrypted in memory; plaintext never touches disk. The
+/// restore overwrites matching records, so it is confirmed and the current
+/// data backed up first.
+pub fn handle_encrypted_import(
+    storage: &Storage,
+    settings: &Settings,
+    file: &str,
+    yes: bool,
+) -> EnvelopeResult<()> {
+    let path = Path::new(file);
+    if !path.exists() {
+        return Err(EnvelopeError::Import(format!("File not found: {}", file)));
+    }
+
+    let contents = std::fs::read_to_string(path)
+        .map_err(|e| EnvelopeError::Import(format!("Failed to read file: {}", e)))?;
+    let sealed = EncryptedExport::from_json(&contents)?;
+
+    let passphrase = prompt_passphrase("Enter export passphrase: ")?;
+    println!("Decrypting export...");
+    let export = sealed.open(passphrase.as_str())?;
+
+    let prompt = format!(
+        "Restore this export from {} over the current data?",
+        export.exported_at.format("%Y-%m-%d %H:%M")
+    );
+    if !confirm_with_backup(storage, settings, yes, &prompt)? {
+        return Ok(());
+    }
+
+    let result = restore_from_export(storage, &export)?;
+
+    println!("Import Complete!");
+    println!("  Exported at: {}", result.exported_at);
+    println!("  {}", result.summary());
+
+    Ok(())
+}
+
+/// Read and parse a CSV or OFX file, returning parsed transactions and target account
+///
+/// Row skipping and mapping options only apply to CSV.
+fn read_and_parse_file(
     import_service: &ImportService,
     account_service: &AccountService,
     file: &str,
-    account: &str,
+    account: Option<&str>,
+    options: &ImportOptions,
 ) -> EnvelopeResult<(Vec<Result<ParsedTransaction, String>>, Account)> {
-    let target_account = account_service
-        .find(account)?
-        .ok_or_else(|| EnvelopeError::account_not_found(account))?;
-
     let path = Path::new(file);
     if !path.exists() {
         return Err(EnvelopeError::Import(format!("File not found: {}", file)));
     }
 
-    // First, peek at the file to detect the format
-    let mut reader = csv::Reader::from_path(path)
-        .map_err(|e| EnvelopeError::Import(format!("Failed to open CSV file: {}", e)))?;
-    let headers = reader
-        .headers()
-        .map_err(|e| EnvelopeError::Import(format!("Failed to read CSV headers: {}", e)))?
-        .clone();
-    let mapping = import_service.detect_mapping_from_headers(&headers);
-
-    // If no header detected, re-read without treating first row as header
-    let parsed = if !mapping.has_header {
-        let mut reader = csv::ReaderBuilder::new()
-            .has_headers(false)
-            .from_path(path)
-            .map_err(|e| EnvelopeError::Import(format!("Failed to open CSV file: {}", e)))?;
-        import_service.parse_csv_from_reader(&mut reader, &mapping)?
-    } else {
-        import_service.parse_csv_from_reader(&mut reader, &mapping)?
+    let target_account = resolve_account(account_service, account)?;
+
+    let bytes = std::fs::read(path)
+        .map_err(|e| EnvelopeError::Import(format!("Failed to open file: {}", e)))?;
+    let data = decode_csv_bytes(&bytes, options.encoding.as_deref())?;
+
+    let ofx = match options.format {
+        Some(format) => format == ImportFormat::Ofx,
+        None => is_ofx(path, &data),
     };
+    if ofx {
+        return Ok((import_service.parse_ofx_str(&data)?, target_account));
+    }
+
+    // Detect the format, skipping any bank preamble ahead of the header
+    let mut mapping = import_service.detect_mapping(&data)?;
+    if let Some(skip_rows) = options.skip_rows {
+        mapping.skip_header_rows = skip_rows;
+    }
+    mapping.skip_footer_rows = options.skip_footer;
+    mapping.skip_invalid_dates = options.skip_invalid_dates;
+
+    // Catch a wrong mapping once here rather than as an error on every row
+    import_service.validate_mapping(&data, &mapping)?;
+
+    let parsed = import_service.parse_csv_str(&data, &mapping)?;
 
     Ok((parsed, target_account))
 }
 
+/// Resolve the target account, prompting when it isn't given exactly
+///
+/// An exact name or ID is used as-is. Otherwise the accounts whose names
+/// contain `account` (or all accounts, if none do or none was given) are
+/// listed to pick from. When stdin isn't a terminal an exact `account` is
+/// required.
+fn resolve_account(
+    account_service: &AccountService,
+    account: Option<&str>,
+) -> EnvelopeResult<Account> {
+    if let Some(identifier) = account {
+        if let Some(found) = account_service.find(identifier)? {
+            return Ok(found);
+        }
+    }
+
+    let matches = match account {
+        Some(identifier) => account_service.search(identifier)?,
+        None => Vec::new(),
+    };
+
+    if !std::io::stdin().is_terminal() {
+        return Err(match account {
+            None => EnvelopeError::Validation(
+                "--account is required when input is not interactive".into(),
+            ),
+            Some(identifier) if matches.is_empty() => EnvelopeError::account_not_found(identifier),
+            Some(identifier) => {
+                let names: Vec<&str> = matches.iter().map(|a| a.name.as_str()).collect();
+                EnvelopeError::Validation(format!(
+                    "Account '{}' is ambiguous; matches: {}",
+                    identifier,
+                    names.join(", ")
+                ))
+            }
+        });
+    }
+
+    let candidates = if matches.is_empty() {
+        if let Some(identifier) = account {
+            println!("No account matches '{}'.", identifier);
+        }
+        account_service.list(false)?
+    } else {
+        matches
+    };
+
+    if candidates.is_empty() {
+        return Err(EnvelopeError::Validation(
+            "No accounts to import into. Create one with 'envelope account create'".into(),
+        ));
+    }
+
+    prompt_account_choice(candidates)
+}
+
+/// List accounts and read the user's choice by number
+fn prompt_account_choice(mut candidates: Vec<Account>) -> EnvelopeResult<Account> {
+    println!("Select the account to import into:");
+    for (i, account) in candidates.iter().enumerate() {
+        println!("  {}. {} ({})", i + 1, account.name, account.account_type);
+    }
+
+    loop {
+        print!("Account number (blank to cancel): ");
+        std::io::stdout().flush()?;
+
+        let mut input = String::new();
+        std::io::stdin().read_line(&mut input)?;
+        let input = input.trim();
+
+        if input.is_empty() {
+            return Err(EnvelopeError::Import("No account selected".into()));
+        }
+
+        match input.parse::<usize>() {
+            Ok(n) if (1..=candidates.len()).contains(&n) => {
+                println!();
+                return Ok(candidates.swap_remove(n - 1));
+            }
+            _ => println!("Enter a number from 1 to {}.", candidates.len()),
+        }
+    }
+}
+
 /// Generate import preview and display summary to user
+///
+/// Likely duplicates are listed with the transaction they matched; with
+/// `import_likely` they are imported instead of skipped.
 fn generate_and_display_preview(
     import_service: &ImportService,
     parsed: &[Result<ParsedTransaction, String>],
     target_account: &Account,
+    import_likely: bool,
 ) -> EnvelopeResult<Vec<ImportPreviewEntry>> {
-    let preview = import_service.generate_preview(parsed, target_account.id)?;
+    let mut preview = import_service.generate_preview(parsed, target_account.id)?;
+
+    let likely: Vec<&ImportPreviewEntry> = preview.iter().filter(|e| e.likely).collect();
+    if !likely.is_empty() {
+        println!("Likely duplicates (same amount, nearby date):");
+        for entry in &likely {
+            println!(
+                "  {} {} {} ~ {}",
+                entry.transaction.date,
+                entry.transaction.payee,
+                entry.transaction.amount,
+                entry.existing_id.as_deref().unwrap_or("?")
+            );
+        }
+        if import_likely {
+            println!("These will be imported (--import-likely).");
+        } else {
+            println!("These will be skipped; use --import-likely to import them.");
+        }
+        println!();
+    }
+
+    if import_likely {
+        for entry in preview.iter_mut().filter(|e| e.likely) {
+            entry.status = ImportStatus::New;
+            entry.likely = false;
+        }
+    }
 
     let new_count = preview
         .iter()
@@ -115,9 +336,13 @@ fn generate_and_display_preview(
             .filter(|e| e.status == ImportStatus::New)
             .take(5)
         {
+            let splits = match entry.transaction.splits.len() {
+                0 => String::new(),
+                n => format!(" ({} splits)", n),
+            };
             println!(
-                "  {} {} {}",
-                entry.transaction.date, entry.transaction.payee, entry.transaction.amount
+                "  {} {} {}{}",
+                entry.transaction.date, entry.transaction.payee, entry.transaction.amount, splits
             );
         }
         if new_count > 5 {
@@ -2,11 +2,12 @@
 //!
 //! Implements CLI commands for managing expected income per budget period.
 
+use chrono::NaiveDate;
 use clap::Subcommand;
 
 use crate::config::settings::Settings;
 use crate::error::{EnvelopeError, EnvelopeResult};
-use crate::models::Money;
+use crate::models::{BudgetPeriod, Money};
 use crate::services::{BudgetService, IncomeService, PeriodService};
 use crate::storage::Storage;
 
@@ -47,6 +48,17 @@ pub enum IncomeCommands {
         #[arg(short, long)]
         period: Option<String>,
     },
+
+    /// Show income received by source
+    Breakdown {
+        /// Budget period (defaults to current month)
+        #[arg(short, long, conflicts_with = "year")]
+        period: Option<String>,
+
+        /// Whole calendar year instead of a period (e.g., 2024)
+        #[arg(short, long)]
+        year: Option<i32>,
+    },
 }
 
 /// Handle an income command
@@ -185,6 +197,46 @@ pub fn handle_income_command(
                 overview.available_to_budget
             );
         }
+
+        IncomeCommands::Breakdown { period, year } => {
+            let (period, label) = match year {
+                Some(year) => {
+                    let start = NaiveDate::from_ymd_opt(year, 1, 1).ok_or_else(|| {
+                        EnvelopeError::Validation(format!("Invalid year: {}", year))
+                    })?;
+                    let end = NaiveDate::from_ymd_opt(year, 12, 31).ok_or_else(|| {
+                        EnvelopeError::Validation(format!("Invalid year: {}", year))
+                    })?;
+                    (BudgetPeriod::custom(start, end), year.to_string())
+                }
+                None => {
+                    let period = period_service.parse_or_current(period.as_deref())?;
+                    let label = period_service.format_period_friendly(&period);
+                    (period, label)
+                }
+            };
+
+            let totals = income_service.by_source(&period)?;
+            println!("Income by Source for {}", label);
+            println!("{}", "=".repeat(50));
+
+            if totals.is_empty() {
+                println!("No income received.");
+                return Ok(());
+            }
+
+            for total in &totals {
+                println!(
+                    "{:<28} {:>4} txn  {:>12}",
+                    total.source.as_deref().unwrap_or("(no source)"),
+                    total.count,
+                    total.amount
+                );
+            }
+            let sum: Money = totals.iter().map(|t| t.amount).sum();
+            println!("{}", "-".repeat(50));
+            println!("{:<38} {:>12}", "TOTAL", sum);
+        }
     }
 
     Ok(())
@@ -4,31 +4,51 @@
 //! bridging the clap argument parsing with the service layer.
 
 pub mod account;
+pub mod add;
+pub mod audit;
 pub mod backup;
 pub mod budget;
 pub mod category;
+pub mod commands;
+pub mod config;
+pub mod confirm;
+pub mod doctor;
 pub mod encrypt;
 pub mod export;
 pub mod import;
 pub mod income;
+pub mod output;
+pub mod pager;
 pub mod payee;
+pub mod profile;
 pub mod reconcile;
 pub mod report;
+pub mod schedule;
 pub mod target;
 pub mod transaction;
 pub mod transfer;
 
 pub use account::{handle_account_command, AccountCommands};
-pub use backup::{handle_backup_command, BackupCommands};
+pub use add::handle_add_command;
+pub use audit::{handle_audit_command, AuditCommands};
+pub use backup::{handle_backup_command, handle_undo_command, BackupCommands};
 pub use budget::{handle_budget_command, BudgetCommands};
 pub use category::{handle_category_command, CategoryCommands};
-pub use encrypt::{handle_encrypt_command, EncryptCommands};
+pub use commands::handle_commands_command;
+pub use config::{handle_config_command, ConfigOptions};
+pub use doctor::{handle_doctor_command, load_storage};
+pub use encrypt::{
+    handle_encrypt_command, unlock_storage, unlock_storage_on_demand, EncryptCommands,
+};
 pub use export::{handle_export_command, ExportCommands};
-pub use import::handle_import_command;
+pub use import::{handle_encrypted_import, handle_import_command, ImportFormat, ImportOptions};
 pub use income::{handle_income_command, IncomeCommands};
+pub use output::OutputFormat;
 pub use payee::{handle_payee_command, PayeeCommands};
+pub use profile::{handle_profile_command, ProfileCommands};
 pub use reconcile::{handle_reconcile_command, ReconcileCommands};
-pub use report::{handle_report_command, ReportCommands};
+pub use report::{handle_report_command, write_missing_monthly_summary, ReportCommands};
+pub use schedule::{handle_schedule_command, ScheduleCommands};
 pub use target::{handle_target_command, TargetCommands};
 pub use transaction::{handle_transaction_command, TransactionCommands};
 pub use transfer::handle_transfer_command;
@@ -0,0 +1,43 @@
+//! Output format for CLI commands
+//!
+//! The global `--json` flag switches the commands that support it from their
+//! human-readable tables to pretty-printed JSON, for use in scripts. Amounts
+//! are serialized as integer cents.
+
+use serde::Serialize;
+
+use crate::error::{EnvelopeError, EnvelopeResult};
+
+/// How a command prints its results
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Tables and messages for people
+    #[default]
+    Text,
+    /// JSON for scripts
+    Json,
+}
+
+impl OutputFormat {
+    /// The format chosen by the `--json` flag
+    pub fn from_json_flag(json: bool) -> Self {
+        if json {
+            Self::Json
+        } else {
+            Self::Text
+        }
+    }
+
+    /// Whether to print JSON
+    pub fn is_json(self) -> bool {
+        self == Self::Json
+    }
+}
+
+/// Print a value as pretty-printed JSON
+pub fn print_json<T: Serialize + ?Sized>(value: &T) -> EnvelopeResult<()> {
+    let output =
+        serde_json::to_string_pretty(value).map_err(|e| EnvelopeError::Json(e.to_string()))?;
+    println!("{}", output);
+    Ok(())
+}
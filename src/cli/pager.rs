@@ -0,0 +1,62 @@
+//! Paging for long command output
+//!
+//! Output taller than the terminal goes through `$PAGER` (or `less`) when
+//! stdout is a terminal. Piped output is always printed as is, so scripts
+//! see the same text with or without paging.
+
+use std::io::{IsTerminal, Write};
+use std::process::{Command, Stdio};
+
+use crate::error::EnvelopeResult;
+
+/// Pager used when `$PAGER` is not set
+const DEFAULT_PAGER: &str = "less";
+
+/// Print output, through a pager if it won't fit on the screen
+///
+/// `no_pager` prints directly, as does an empty `$PAGER`. If the pager
+/// can't be started the output is printed instead.
+pub fn print_paged(output: &str, no_pager: bool) -> EnvelopeResult<()> {
+    if no_pager || !std::io::stdout().is_terminal() || fits_on_screen(output) {
+        print!("{}", output);
+        return Ok(());
+    }
+
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| DEFAULT_PAGER.to_string());
+    let mut parts = pager.split_whitespace();
+    let Some(program) = parts.next() else {
+        print!("{}", output);
+        return Ok(());
+    };
+
+    let child = Command::new(program)
+        .args(parts)
+        .stdin(Stdio::piped())
+        .spawn();
+    let mut child = match child {
+        Ok(child) => child,
+        Err(_) => {
+            print!("{}", output);
+            return Ok(());
+        }
+    };
+
+    if let Some(mut stdin) = child.stdin.take() {
+        // Quitting the pager early closes the pipe; that isn't an error
+        match stdin.write_all(output.as_bytes()) {
+            Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => return Err(e.into()),
+            _ => {}
+        }
+    }
+    child.wait()?;
+
+    Ok(())
+}
+
+/// Check whether output fits in the terminal without scrolling
+fn fits_on_screen(output: &str) -> bool {
+    match crossterm::terminal::size() {
+        Ok((_, rows)) => output.lines().count() < rows as usize,
+        Err(_) => true,
+    }
+}
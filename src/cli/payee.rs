@@ -4,7 +4,10 @@
 
 use clap::Subcommand;
 
+use crate::cli::confirm::confirm_with_backup;
+use crate::config::settings::Settings;
 use crate::error::{EnvelopeError, EnvelopeResult};
+use crate::models::Payee;
 use crate::services::{CategoryService, PayeeService};
 use crate::storage::Storage;
 
@@ -39,20 +42,41 @@ pub enum PayeeCommands {
         /// Payee name or ID
         payee: String,
         /// Skip confirmation
-        #[arg(short, long)]
-        force: bool,
+        #[arg(short, long, alias = "force", short_alias = 'f')]
+        yes: bool,
     },
-    /// Rename a payee
+    /// Rename a payee and its transactions
     Rename {
         /// Payee name or ID
         payee: String,
         /// New name
         name: String,
+        /// If another payee already has the new name, merge into it
+        #[arg(long)]
+        merge: bool,
+        /// Skip confirmation when merging
+        #[arg(short, long, alias = "force", short_alias = 'f')]
+        yes: bool,
+    },
+    /// Merge duplicate payees into one, moving their transactions
+    Merge {
+        /// Payee to keep (name or ID)
+        target: String,
+        /// Payees to merge away (names or IDs)
+        #[arg(required = true)]
+        sources: Vec<String>,
+        /// Skip confirmation
+        #[arg(short, long, alias = "force", short_alias = 'f')]
+        yes: bool,
     },
 }
 
 /// Handle a payee command
-pub fn handle_payee_command(storage: &Storage, cmd: PayeeCommands) -> EnvelopeResult<()> {
+pub fn handle_payee_command(
+    storage: &Storage,
+    settings: &Settings,
+    cmd: PayeeCommands,
+) -> EnvelopeResult<()> {
     let service = PayeeService::new(storage);
     let category_service = CategoryService::new(storage);
 
@@ -163,14 +187,13 @@ pub fn handle_payee_command(storage: &Storage, cmd: PayeeCommands) -> EnvelopeRe
             );
         }
 
-        PayeeCommands::Delete { payee, force } => {
+        PayeeCommands::Delete { payee, yes } => {
             let p = service
                 .find(&payee)?
                 .ok_or_else(|| EnvelopeError::payee_not_found(&payee))?;
 
-            if !force {
-                println!("About to delete payee: {}", p.name);
-                println!("Use --force to confirm deletion");
+            let prompt = format!("Delete payee '{}'?", p.name);
+            if !confirm_with_backup(storage, settings, yes, &prompt)? {
                 return Ok(());
             }
 
@@ -178,14 +201,87 @@ pub fn handle_payee_command(storage: &Storage, cmd: PayeeCommands) -> EnvelopeRe
             println!("Deleted payee: {}", deleted.name);
         }
 
-        PayeeCommands::Rename { payee, name } => {
+        PayeeCommands::Rename {
+            payee,
+            name,
+            merge,
+            yes,
+        } => {
             let p = service
                 .find(&payee)?
                 .ok_or_else(|| EnvelopeError::payee_not_found(&payee))?;
 
+            if let Some(existing) = service.get_by_name(&name)?.filter(|e| e.id != p.id) {
+                if !merge {
+                    return Err(EnvelopeError::Validation(format!(
+                        "Payee '{}' already exists. Use --merge to combine '{}' into it",
+                        existing.name, p.name
+                    )));
+                }
+
+                let prompt = format!("Merge and delete payee '{}'?", p.name);
+                if !confirm_with_backup(storage, settings, yes, &prompt)? {
+                    return Ok(());
+                }
+
+                let moved = service.merge(vec![p.id], existing.id)?;
+                println!(
+                    "Merged '{}' into '{}': moved {} transaction(s)",
+                    p.name, existing.name, moved
+                );
+                return Ok(());
+            }
+
             let old_name = p.name.clone();
+            let count = service.transaction_count(p.id)?;
             let renamed = service.rename(p.id, &name)?;
             println!("Renamed payee: '{}' -> '{}'", old_name, renamed.name);
+            if count > 0 {
+                println!("Updated {} transaction(s)", count);
+            }
+        }
+
+        PayeeCommands::Merge {
+            target,
+            sources,
+            yes,
+        } => {
+            let target = service
+                .find(&target)?
+                .ok_or_else(|| EnvelopeError::payee_not_found(&target))?;
+            println!("Merging into '{}':", target.name);
+            let mut merging: Vec<Payee> = Vec::with_capacity(sources.len());
+            for source in &sources {
+                let p = service
+                    .find(source)?
+                    .ok_or_else(|| EnvelopeError::payee_not_found(source))?;
+                if merging.iter().any(|m| m.id == p.id) {
+                    continue;
+                }
+                println!(
+                    "  {} ({} transaction(s))",
+                    p.name,
+                    service.transaction_count(p.id)?
+                );
+                merging.push(p);
+            }
+
+            let prompt = format!(
+                "Merge {} payee(s) into '{}' and delete them?",
+                merging.len(),
+                target.name
+            );
+            if !confirm_with_backup(storage, settings, yes, &prompt)? {
+                return Ok(());
+            }
+
+            let moved = service.merge(merging.iter().map(|p| p.id).collect(), target.id)?;
+            println!(
+                "Merged {} payee(s) into '{}': moved {} transaction(s)",
+                merging.len(),
+                target.name,
+                moved
+            );
         }
     }
 
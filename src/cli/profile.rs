@@ -0,0 +1,82 @@
+//! Profile CLI commands
+//!
+//! Manages separate budgets, each with its own settings, data, backups, and
+//! encryption.
+
+use clap::Subcommand;
+
+use crate::config::paths::{EnvelopePaths, DEFAULT_PROFILE};
+use crate::config::settings::Settings;
+use crate::error::{EnvelopeError, EnvelopeResult};
+
+/// Profile subcommands
+#[derive(Subcommand)]
+pub enum ProfileCommands {
+    /// List all profiles
+    List,
+
+    /// Create a new profile with the default categories
+    New {
+        /// Profile name (letters, digits, '-' and '_')
+        name: String,
+    },
+
+    /// Make a profile the one used when no --profile is given
+    Switch {
+        /// Profile name
+        name: String,
+    },
+}
+
+/// Handle a profile command
+pub fn handle_profile_command(paths: &EnvelopePaths, cmd: ProfileCommands) -> EnvelopeResult<()> {
+    match cmd {
+        ProfileCommands::List => {
+            for name in paths.list_profiles()? {
+                let marker = if name == paths.profile() { "*" } else { " " };
+                println!("{} {}", marker, name);
+            }
+        }
+
+        ProfileCommands::New { name } => {
+            let profile_paths = EnvelopePaths::with_root_dir(paths.root_dir().clone(), &name)?;
+            if name == DEFAULT_PROFILE || profile_paths.base_dir().exists() {
+                return Err(EnvelopeError::Duplicate {
+                    entity_type: "Profile",
+                    identifier: name,
+                });
+            }
+
+            crate::storage::init::initialize_storage(&profile_paths)?;
+            Settings::default().save(&profile_paths)?;
+
+            println!("Created profile '{}'", name);
+            println!("Location: {}", profile_paths.base_dir().display());
+            println!();
+            println!("Use it with: envelope --profile {} <command>", name);
+            println!("Or make it the default: envelope profile switch {}", name);
+        }
+
+        ProfileCommands::Switch { name } => {
+            if !paths.list_profiles()?.contains(&name) {
+                return Err(EnvelopeError::NotFound {
+                    entity_type: "Profile",
+                    identifier: name,
+                });
+            }
+
+            std::fs::create_dir_all(paths.root_dir()).map_err(|e| {
+                EnvelopeError::Io(format!("Failed to create base directory: {}", e))
+            })?;
+            std::fs::write(paths.active_profile_file(), &name)
+                .map_err(|e| EnvelopeError::Io(format!("Failed to save active profile: {}", e)))?;
+
+            println!("Switched to profile '{}'", name);
+            if std::env::var("ENVELOPE_PROFILE").is_ok_and(|p| !p.is_empty() && p != name) {
+                println!("Note: ENVELOPE_PROFILE is set and takes precedence.");
+            }
+        }
+    }
+
+    Ok(())
+}
@@ -5,6 +5,7 @@
 use chrono::NaiveDate;
 use clap::Subcommand;
 
+use crate::config::clock;
 use crate::error::{EnvelopeError, EnvelopeResult};
 use crate::models::Money;
 use crate::services::{AccountService, CategoryService, ReconciliationService};
@@ -66,6 +67,11 @@ pub enum ReconcileCommands {
         #[arg(short, long)]
         category: Option<String>,
     },
+    /// Show past reconciliations of an account
+    History {
+        /// Account name or ID
+        account: String,
+    },
 }
 
 /// Handle a reconcile command
@@ -289,6 +295,30 @@ pub fn handle_reconcile_command(storage: &Storage, cmd: ReconcileCommands) -> En
                 );
             }
         }
+
+        ReconcileCommands::History { account } => {
+            let account = account_service
+                .find(&account)?
+                .ok_or_else(|| EnvelopeError::account_not_found(&account))?;
+
+            let history = service.history(account.id)?;
+            if history.is_empty() {
+                println!("{} has never been reconciled.", account.name);
+                return Ok(());
+            }
+
+            println!("Reconciliation History: {}", account.name);
+            println!("{}", "=".repeat(40));
+            println!("{:<12} {:>14}  Completed", "Statement", "Balance");
+            for record in &history {
+                println!(
+                    "{:<12} {:>14}  {}",
+                    record.statement_date.to_string(),
+                    record.statement_balance.to_string(),
+                    record.completed_at.format("%Y-%m-%d %H:%M UTC")
+                );
+            }
+        }
     }
 
     Ok(())
@@ -304,6 +334,6 @@ fn parse_date_or_today(date_str: Option<&str>) -> EnvelopeResult<NaiveDate> {
             ))
         })
     } else {
-        Ok(chrono::Local::now().date_naive())
+        Ok(clock::today())
     }
 }
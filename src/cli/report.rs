@@ -2,19 +2,49 @@
 //!
 //! Provides commands for generating and exporting various financial reports.
 
+use crate::cli::output::{print_json, OutputFormat};
+use crate::config::clock;
+use crate::config::settings::Settings;
 use crate::error::EnvelopeResult;
 use crate::models::BudgetPeriod;
+use crate::rates::StaticRates;
 use crate::reports::{
-    AccountRegisterReport, BudgetOverviewReport, NetWorthReport, RegisterFilter, SpendingReport,
+    AccountRegisterReport, BudgetOverviewReport, CashflowReport, MonthlyReportFormat,
+    MonthlySummary, NetWorthReport, NetWorthSummary, PeriodComparisonReport, RegisterFilter,
+    SpendingReport, TagReport,
 };
-use crate::services::AccountService;
+use crate::services::{AccountService, PeriodService};
 use crate::storage::Storage;
 use chrono::NaiveDate;
-use clap::Subcommand;
+use clap::{Subcommand, ValueEnum};
+use serde::Serialize;
 use std::fs::File;
-use std::io::BufWriter;
+use std::io::{BufWriter, IsTerminal};
 use std::path::PathBuf;
 
+/// How a report is printed when it isn't exported to a file
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum ReportFormat {
+    /// Aligned plain-text columns
+    #[default]
+    Text,
+    /// GitHub-flavored Markdown tables
+    #[value(name = "md", alias = "markdown")]
+    Markdown,
+    /// Pretty-printed JSON, with amounts in cents
+    Json,
+}
+
+/// How the spending report breaks down outflows
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, ValueEnum)]
+pub enum SpendingGrouping {
+    /// By category group and category
+    #[default]
+    Category,
+    /// By payee, with transaction counts and averages
+    Payee,
+}
+
 /// Report subcommands
 #[derive(Subcommand, Debug)]
 pub enum ReportCommands {
@@ -28,6 +58,52 @@ pub enum ReportCommands {
         /// Export to CSV file
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Output format (--output always writes CSV)
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+
+    /// Compare budgeted and spent amounts per category
+    Variance {
+        /// Budget period (e.g., "2025-01" for January 2025)
+        #[arg(short, long)]
+        period: Option<String>,
+
+        /// Export to CSV file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Output format (--output always writes CSV)
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+
+        /// Disable colored output
+        #[arg(long)]
+        no_color: bool,
+    },
+
+    /// Compare budgeted and activity per category across two periods
+    Compare {
+        /// First period (defaults to the one before --b)
+        #[arg(long)]
+        a: Option<String>,
+
+        /// Second period (defaults to the current month)
+        #[arg(long)]
+        b: Option<String>,
+
+        /// Export to CSV file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Output format (--output always writes CSV)
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+
+        /// Disable colored output
+        #[arg(long)]
+        no_color: bool,
     },
 
     /// Generate a spending report by category
@@ -48,9 +124,32 @@ pub enum ReportCommands {
         #[arg(short, long)]
         output: Option<PathBuf>,
 
-        /// Show top N categories only
+        /// Output format (--output always writes CSV)
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+
+        /// Show top N categories (or payees) only
         #[arg(long)]
         top: Option<usize>,
+
+        /// Break spending down by category or payee
+        #[arg(long, value_enum, default_value_t = SpendingGrouping::Category)]
+        by: SpendingGrouping,
+    },
+
+    /// Show income, expenses, and net for a period, excluding transfers
+    Cashflow {
+        /// Budget period (e.g., "2025-01" for January 2025)
+        #[arg(short, long)]
+        period: Option<String>,
+
+        /// Export to CSV file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Output format (--output always writes CSV)
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
     },
 
     /// Generate an account register report
@@ -75,9 +174,63 @@ pub enum ReportCommands {
         #[arg(long)]
         uncategorized: bool,
 
+        /// Show only flagged (review or starred) transactions
+        #[arg(long)]
+        flagged: bool,
+
         /// Export to CSV file
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Output format (--output always writes CSV)
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+
+    /// Total tagged transactions, per tag or for a single tag
+    #[command(alias = "tags", alias = "tagged")]
+    Tag {
+        /// Only report this tag and list its transactions
+        tag: Option<String>,
+
+        /// Same as the positional tag, for `report tagged --tag <TAG>`
+        #[arg(long = "tag", value_name = "TAG", conflicts_with = "tag")]
+        tag_option: Option<String>,
+
+        /// Calendar year to report on (alternative to start/end)
+        #[arg(short, long, conflicts_with_all = ["start", "end"])]
+        year: Option<i32>,
+
+        /// Start date (YYYY-MM-DD)
+        #[arg(short, long)]
+        start: Option<String>,
+
+        /// End date (YYYY-MM-DD)
+        #[arg(short, long)]
+        end: Option<String>,
+
+        /// Export to CSV file
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Output format (--output always writes CSV)
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
+    },
+
+    /// Summarize a period's budget and spending, e.g. for a monthly archive
+    Monthly {
+        /// Budget period (defaults to the one that just closed)
+        #[arg(short, long)]
+        period: Option<String>,
+
+        /// Write to a dated file in the reports directory instead of printing
+        #[arg(short, long)]
+        write: bool,
+
+        /// Output format: md, html, or json (defaults to the configured format)
+        #[arg(long, value_name = "FORMAT")]
+        format: Option<String>,
     },
 
     /// Generate a net worth report
@@ -87,40 +240,217 @@ pub enum ReportCommands {
         #[arg(short, long)]
         all: bool,
 
+        /// Show net worth at the end of each of the last N periods instead
+        #[arg(long, value_name = "N")]
+        trend: Option<usize>,
+
         /// Export to CSV file
         #[arg(short, long)]
         output: Option<PathBuf>,
+
+        /// Output format (--output always writes CSV)
+        #[arg(long, value_enum, default_value_t = ReportFormat::Text)]
+        format: ReportFormat,
     },
 }
 
+impl ReportCommands {
+    /// The report's `--format`; `monthly` has its own formats instead
+    fn format_mut(&mut self) -> Option<&mut ReportFormat> {
+        match self {
+            Self::Budget { format, .. }
+            | Self::Variance { format, .. }
+            | Self::Compare { format, .. }
+            | Self::Spending { format, .. }
+            | Self::Cashflow { format, .. }
+            | Self::Register { format, .. }
+            | Self::Tag { format, .. }
+            | Self::NetWorth { format, .. } => Some(format),
+            Self::Monthly { .. } => None,
+        }
+    }
+
+    /// Switch the report to JSON when the global `--json` flag is set
+    fn apply_output_format(&mut self, output_format: OutputFormat) {
+        if !output_format.is_json() {
+            return;
+        }
+        if let Self::Monthly { format, .. } = self {
+            format.get_or_insert_with(|| "json".to_string());
+        } else if let Some(format) = self.format_mut() {
+            *format = ReportFormat::Json;
+        }
+    }
+}
+
 /// Handle report commands
-pub fn handle_report_command(storage: &Storage, cmd: ReportCommands) -> EnvelopeResult<()> {
+///
+/// `--json` overrides each report's `--format`; `--output` still writes CSV.
+pub fn handle_report_command(
+    storage: &Storage,
+    settings: &Settings,
+    mut cmd: ReportCommands,
+    output_format: OutputFormat,
+) -> EnvelopeResult<()> {
+    cmd.apply_output_format(output_format);
+
     match cmd {
-        ReportCommands::Budget { period, output } => handle_budget_report(storage, period, output),
+        ReportCommands::Budget {
+            period,
+            output,
+            format,
+        } => handle_budget_report(storage, settings, period, output, format),
+        ReportCommands::Variance {
+            period,
+            output,
+            format,
+            no_color,
+        } => handle_variance_report(storage, period, output, format, no_color),
+        ReportCommands::Compare {
+            a,
+            b,
+            output,
+            format,
+            no_color,
+        } => handle_compare_report(storage, a, b, output, format, no_color),
         ReportCommands::Spending {
             start,
             end,
             period,
             output,
+            format,
             top,
-        } => handle_spending_report(storage, start, end, period, output, top),
+            by,
+        } => handle_spending_report(storage, start, end, period, output, format, top, by),
+        ReportCommands::Cashflow {
+            period,
+            output,
+            format,
+        } => handle_cashflow_report(storage, settings, period, output, format),
         ReportCommands::Register {
             account,
             start,
             end,
             payee,
             uncategorized,
+            flagged,
             output,
-        } => handle_register_report(storage, account, start, end, payee, uncategorized, output),
-        ReportCommands::NetWorth { all, output } => handle_net_worth_report(storage, all, output),
+            format,
+        } => handle_register_report(
+            storage,
+            account,
+            start,
+            end,
+            payee,
+            uncategorized,
+            flagged,
+            output,
+            format,
+        ),
+        ReportCommands::Tag {
+            tag,
+            tag_option,
+            year,
+            start,
+            end,
+            output,
+            format,
+        } => handle_tag_report(
+            storage,
+            tag.or(tag_option),
+            year,
+            start,
+            end,
+            output,
+            format,
+        ),
+        ReportCommands::Monthly {
+            period,
+            write,
+            format,
+        } => handle_monthly_report(storage, settings, period, write, format),
+        ReportCommands::NetWorth {
+            all,
+            trend: Some(count),
+            output,
+            format,
+        } => handle_net_worth_trend(storage, settings, all, count, output, format),
+        ReportCommands::NetWorth {
+            all,
+            trend: None,
+            output,
+            format,
+        } => handle_net_worth_report(storage, settings, all, output, format),
     }
 }
 
+/// Handle monthly summary report
+fn handle_monthly_report(
+    storage: &Storage,
+    settings: &Settings,
+    period: Option<String>,
+    write: bool,
+    format: Option<String>,
+) -> EnvelopeResult<()> {
+    let period_service = PeriodService::new(settings);
+    let budget_period = match period {
+        Some(period_str) => period_service.parse(&period_str)?,
+        None => period_service.previous_period(&period_service.current_period()),
+    };
+
+    let format = match format {
+        Some(name) => MonthlyReportFormat::parse(&name).ok_or_else(|| {
+            crate::error::EnvelopeError::Validation(format!(
+                "Invalid report format '{}'. Use md, html, or json",
+                name
+            ))
+        })?,
+        None => settings.monthly_report_format,
+    };
+
+    let summary = MonthlySummary::generate(storage, &budget_period)?;
+
+    if write {
+        let path = summary.write_to(&storage.paths().reports_dir(), format)?;
+        println!("Monthly summary written to: {}", path.display());
+    } else {
+        print!("{}", summary.render(format));
+    }
+
+    Ok(())
+}
+
+/// Write the summary for the period that just closed, unless it's already on disk
+///
+/// Used by the `auto_monthly_report` setting. Returns the path when a file
+/// was written.
+pub fn write_missing_monthly_summary(
+    storage: &Storage,
+    settings: &Settings,
+) -> EnvelopeResult<Option<PathBuf>> {
+    let period_service = PeriodService::new(settings);
+    let closed = period_service.previous_period(&period_service.current_period());
+
+    let dir = storage.paths().reports_dir();
+    let format = settings.monthly_report_format;
+    if dir
+        .join(MonthlySummary::file_name(&closed, format))
+        .exists()
+    {
+        return Ok(None);
+    }
+    MonthlySummary::generate(storage, &closed)?
+        .write_to(&dir, format)
+        .map(Some)
+}
+
 /// Handle budget overview report
 fn handle_budget_report(
     storage: &Storage,
+    settings: &Settings,
     period: Option<String>,
     output: Option<PathBuf>,
+    format: ReportFormat,
 ) -> EnvelopeResult<()> {
     // Parse period or use current
     let budget_period = if let Some(period_str) = period {
@@ -149,21 +479,123 @@ fn handle_budget_report(
         let mut writer = BufWriter::new(file);
         report.export_csv(&mut writer)?;
         println!("Budget report exported to: {}", path.display());
+    } else if format == ReportFormat::Json {
+        print_json(&report)?;
+    } else if format == ReportFormat::Markdown {
+        println!("{}", report.format_markdown());
     } else {
-        println!("{}", report.format_terminal());
+        println!("{}", report.format_terminal(settings.negative_style));
+    }
+
+    Ok(())
+}
+
+/// Handle budget vs. actual variance report
+fn handle_variance_report(
+    storage: &Storage,
+    period: Option<String>,
+    output: Option<PathBuf>,
+    format: ReportFormat,
+    no_color: bool,
+) -> EnvelopeResult<()> {
+    let budget_period = match period {
+        Some(period_str) => BudgetPeriod::parse(&period_str).map_err(|e| {
+            crate::error::EnvelopeError::Validation(format!(
+                "Invalid period format: {}. Use YYYY-MM (e.g., 2025-01)",
+                e
+            ))
+        })?,
+        None => BudgetPeriod::current_month(),
+    };
+
+    let report = BudgetOverviewReport::generate(storage, &budget_period)?;
+
+    if let Some(path) = output {
+        let file = File::create(&path).map_err(|e| {
+            crate::error::EnvelopeError::Export(format!(
+                "Failed to create file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let mut writer = BufWriter::new(file);
+        report.export_variance_csv(&mut writer)?;
+        println!("Variance report exported to: {}", path.display());
+    } else if format == ReportFormat::Json {
+        print_json(&report)?;
+    } else if format == ReportFormat::Markdown {
+        println!("{}", report.format_variance_markdown());
+    } else {
+        let color = !no_color && std::io::stdout().is_terminal();
+        println!("{}", report.format_variance_terminal(color));
+    }
+
+    Ok(())
+}
+
+/// Handle period comparison report
+fn handle_compare_report(
+    storage: &Storage,
+    a: Option<String>,
+    b: Option<String>,
+    output: Option<PathBuf>,
+    format: ReportFormat,
+    no_color: bool,
+) -> EnvelopeResult<()> {
+    let parse = |period_str: String| {
+        BudgetPeriod::parse(&period_str).map_err(|e| {
+            crate::error::EnvelopeError::Validation(format!(
+                "Invalid period format: {}. Use YYYY-MM (e.g., 2025-01)",
+                e
+            ))
+        })
+    };
+
+    let period_b = match b {
+        Some(period_str) => parse(period_str)?,
+        None => BudgetPeriod::current_month(),
+    };
+    let period_a = match a {
+        Some(period_str) => parse(period_str)?,
+        None => period_b.prev(),
+    };
+
+    let report = PeriodComparisonReport::generate(storage, &period_a, &period_b)?;
+
+    if let Some(path) = output {
+        let file = File::create(&path).map_err(|e| {
+            crate::error::EnvelopeError::Export(format!(
+                "Failed to create file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let mut writer = BufWriter::new(file);
+        report.export_csv(&mut writer)?;
+        println!("Comparison report exported to: {}", path.display());
+    } else if format == ReportFormat::Json {
+        print_json(&report)?;
+    } else if format == ReportFormat::Markdown {
+        println!("{}", report.format_markdown());
+    } else {
+        let color = !no_color && std::io::stdout().is_terminal();
+        println!("{}", report.format_terminal(color));
     }
 
     Ok(())
 }
 
 /// Handle spending report
+#[allow(clippy::too_many_arguments)]
 fn handle_spending_report(
     storage: &Storage,
     start: Option<String>,
     end: Option<String>,
     period: Option<String>,
     output: Option<PathBuf>,
+    format: ReportFormat,
     top: Option<usize>,
+    by: SpendingGrouping,
 ) -> EnvelopeResult<()> {
     // Determine date range
     let (start_date, end_date) = if let Some(period_str) = period {
@@ -184,7 +616,7 @@ fn handle_spending_report(
             })?
         } else {
             // Default to start of current month
-            let today = chrono::Local::now().date_naive();
+            let today = clock::today();
             NaiveDate::from_ymd_opt(today.year(), today.month(), 1).unwrap_or(today)
         };
 
@@ -197,7 +629,7 @@ fn handle_spending_report(
             })?
         } else {
             // Default to today
-            chrono::Local::now().date_naive()
+            clock::today()
         };
 
         (start_date, end_date)
@@ -206,6 +638,10 @@ fn handle_spending_report(
     // Generate report
     let report = SpendingReport::generate(storage, start_date, end_date)?;
 
+    if by == SpendingGrouping::Payee {
+        return print_spending_by_payee(&report, output, format, top);
+    }
+
     // Output
     if let Some(path) = output {
         let file = File::create(&path).map_err(|e| {
@@ -218,6 +654,11 @@ fn handle_spending_report(
         let mut writer = BufWriter::new(file);
         report.export_csv(&mut writer)?;
         println!("Spending report exported to: {}", path.display());
+    } else if format == ReportFormat::Json {
+        match top {
+            Some(n) => print_json(&report.top_categories(n))?,
+            None => print_json(&report)?,
+        }
     } else if let Some(n) = top {
         // Show top N categories only
         println!(
@@ -236,6 +677,8 @@ fn handle_spending_report(
             );
         }
         println!("\nTotal Spending: {}", report.total_spending.abs());
+    } else if format == ReportFormat::Markdown {
+        println!("{}", report.format_markdown());
     } else {
         println!("{}", report.format_terminal());
     }
@@ -243,7 +686,58 @@ fn handle_spending_report(
     Ok(())
 }
 
+/// Print or export the payee breakdown of a spending report
+fn print_spending_by_payee(
+    report: &SpendingReport,
+    output: Option<PathBuf>,
+    format: ReportFormat,
+    top: Option<usize>,
+) -> EnvelopeResult<()> {
+    if let Some(path) = output {
+        let file = File::create(&path).map_err(|e| {
+            crate::error::EnvelopeError::Export(format!(
+                "Failed to create file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let mut writer = BufWriter::new(file);
+        report.export_payees_csv(&mut writer)?;
+        println!("Spending by payee exported to: {}", path.display());
+    } else if format == ReportFormat::Json {
+        match top {
+            Some(n) => print_json(&report.top_payees(n))?,
+            None => print_json(&report.payees)?,
+        }
+    } else if let Some(n) = top {
+        println!(
+            "Top {} Payees: {} to {}\n",
+            n, report.start_date, report.end_date
+        );
+        println!("{:<35} {:>12} {:>8} {:>8}", "Payee", "Amount", "Count", "%");
+        println!("{}", "-".repeat(66));
+
+        for payee in report.top_payees(n) {
+            println!(
+                "{:<35} {:>12} {:>8} {:>7.1}%",
+                payee.payee_name,
+                payee.total_spending.abs(),
+                payee.transaction_count,
+                payee.percentage
+            );
+        }
+        println!("\nTotal Spending: {}", report.total_spending.abs());
+    } else if format == ReportFormat::Markdown {
+        println!("{}", report.format_payees_markdown());
+    } else {
+        println!("{}", report.format_payees_terminal());
+    }
+
+    Ok(())
+}
+
 /// Handle account register report
+#[allow(clippy::too_many_arguments)]
 fn handle_register_report(
     storage: &Storage,
     account: String,
@@ -251,7 +745,9 @@ fn handle_register_report(
     end: Option<String>,
     payee: Option<String>,
     uncategorized: bool,
+    flagged: bool,
     output: Option<PathBuf>,
+    format: ReportFormat,
 ) -> EnvelopeResult<()> {
     let account_service = AccountService::new(storage);
 
@@ -284,6 +780,7 @@ fn handle_register_report(
             .transpose()?,
         payee_contains: payee,
         uncategorized_only: uncategorized,
+        flagged_only: flagged,
         ..Default::default()
     };
 
@@ -302,6 +799,72 @@ fn handle_register_report(
         let mut writer = BufWriter::new(file);
         report.export_csv(&mut writer)?;
         println!("Register report exported to: {}", path.display());
+    } else if format == ReportFormat::Json {
+        print_json(&report)?;
+    } else if format == ReportFormat::Markdown {
+        println!("{}", report.format_markdown());
+    } else {
+        println!("{}", report.format_terminal());
+    }
+
+    Ok(())
+}
+
+/// Handle tag report
+fn handle_tag_report(
+    storage: &Storage,
+    tag: Option<String>,
+    year: Option<i32>,
+    start: Option<String>,
+    end: Option<String>,
+    output: Option<PathBuf>,
+    format: ReportFormat,
+) -> EnvelopeResult<()> {
+    // Determine date range: a year, explicit dates, or the current year
+    let year = year.unwrap_or_else(|| clock::today().year());
+    let year_start = NaiveDate::from_ymd_opt(year, 1, 1).ok_or_else(|| {
+        crate::error::EnvelopeError::Validation(format!("Invalid year: {}", year))
+    })?;
+    let year_end = NaiveDate::from_ymd_opt(year, 12, 31).unwrap_or(year_start);
+
+    let start_date = match start {
+        Some(s) => NaiveDate::parse_from_str(&s, "%Y-%m-%d").map_err(|_| {
+            crate::error::EnvelopeError::Validation(format!(
+                "Invalid start date format: {}. Use YYYY-MM-DD",
+                s
+            ))
+        })?,
+        None => year_start,
+    };
+    let end_date = match end {
+        Some(e) => NaiveDate::parse_from_str(&e, "%Y-%m-%d").map_err(|_| {
+            crate::error::EnvelopeError::Validation(format!(
+                "Invalid end date format: {}. Use YYYY-MM-DD",
+                e
+            ))
+        })?,
+        None => year_end,
+    };
+
+    // Generate report
+    let report = TagReport::generate(storage, tag.as_deref(), start_date, end_date)?;
+
+    // Output
+    if let Some(path) = output {
+        let file = File::create(&path).map_err(|e| {
+            crate::error::EnvelopeError::Export(format!(
+                "Failed to create file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let mut writer = BufWriter::new(file);
+        report.export_csv(&mut writer)?;
+        println!("Tag report exported to: {}", path.display());
+    } else if format == ReportFormat::Json {
+        print_json(&report)?;
+    } else if format == ReportFormat::Markdown {
+        println!("{}", report.format_markdown());
     } else {
         println!("{}", report.format_terminal());
     }
@@ -312,11 +875,15 @@ fn handle_register_report(
 /// Handle net worth report
 fn handle_net_worth_report(
     storage: &Storage,
+    settings: &Settings,
     include_archived: bool,
     output: Option<PathBuf>,
+    format: ReportFormat,
 ) -> EnvelopeResult<()> {
-    // Generate report
-    let report = NetWorthReport::generate(storage, include_archived)?;
+    // Generate report, converting to the base currency with saved rates
+    let rates = StaticRates::from_rates(&settings.exchange_rates);
+    let report =
+        NetWorthReport::generate(storage, include_archived, &settings.base_currency, &rates)?;
 
     // Output
     if let Some(path) = output {
@@ -330,6 +897,10 @@ fn handle_net_worth_report(
         let mut writer = BufWriter::new(file);
         report.export_csv(&mut writer)?;
         println!("Net worth report exported to: {}", path.display());
+    } else if format == ReportFormat::Json {
+        print_json(&report)?;
+    } else if format == ReportFormat::Markdown {
+        println!("{}", report.format_markdown());
     } else {
         println!("{}", report.format_terminal());
     }
@@ -337,4 +908,170 @@ fn handle_net_worth_report(
     Ok(())
 }
 
+/// Handle income vs. expense cashflow report
+fn handle_cashflow_report(
+    storage: &Storage,
+    settings: &Settings,
+    period: Option<String>,
+    output: Option<PathBuf>,
+    format: ReportFormat,
+) -> EnvelopeResult<()> {
+    let budget_period = PeriodService::new(settings).parse_or_current(period.as_deref())?;
+
+    let report = CashflowReport::generate(storage, &budget_period)?;
+
+    // Output
+    if let Some(path) = output {
+        let file = File::create(&path).map_err(|e| {
+            crate::error::EnvelopeError::Export(format!(
+                "Failed to create file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let mut writer = BufWriter::new(file);
+        report.export_csv(&mut writer)?;
+        println!("Cashflow report exported to: {}", path.display());
+    } else if format == ReportFormat::Json {
+        print_json(&report)?;
+    } else if format == ReportFormat::Markdown {
+        println!("{}", report.format_markdown());
+    } else {
+        println!("{}", report.format_terminal());
+    }
+
+    Ok(())
+}
+
+/// One period of `report net-worth --trend` in JSON output
+#[derive(Debug, Serialize)]
+struct TrendPoint<'a> {
+    period: &'a BudgetPeriod,
+    #[serde(flatten)]
+    summary: &'a NetWorthSummary,
+}
+
+/// Handle net worth trend report
+fn handle_net_worth_trend(
+    storage: &Storage,
+    settings: &Settings,
+    include_archived: bool,
+    count: usize,
+    output: Option<PathBuf>,
+    format: ReportFormat,
+) -> EnvelopeResult<()> {
+    if count == 0 {
+        return Err(crate::error::EnvelopeError::Validation(
+            "--trend needs at least one period".to_string(),
+        ));
+    }
+
+    // The last `count` periods, oldest first, ending with the current one
+    let period_service = PeriodService::new(settings);
+    let mut periods = vec![period_service.current_period()];
+    while periods.len() < count {
+        let earliest = period_service.previous_period(&periods[0]);
+        periods.insert(0, earliest);
+    }
+
+    let rates = StaticRates::from_rates(&settings.exchange_rates);
+    let trend = NetWorthReport::trend(
+        storage,
+        &periods,
+        include_archived,
+        &settings.base_currency,
+        &rates,
+    )?;
+
+    if let Some(path) = output {
+        let file = File::create(&path).map_err(|e| {
+            crate::error::EnvelopeError::Export(format!(
+                "Failed to create file {}: {}",
+                path.display(),
+                e
+            ))
+        })?;
+        let mut writer = BufWriter::new(file);
+        NetWorthReport::export_trend_csv(&trend, &mut writer)?;
+        println!("Net worth trend exported to: {}", path.display());
+    } else if format == ReportFormat::Json {
+        let points: Vec<TrendPoint> = trend
+            .iter()
+            .map(|(period, summary)| TrendPoint { period, summary })
+            .collect();
+        print_json(&points)?;
+    } else if format == ReportFormat::Markdown {
+        println!("{}", NetWorthReport::format_trend_markdown(&trend));
+    } else {
+        println!("{}", NetWorthReport::format_trend_terminal(&trend));
+    }
+
+    Ok(())
+}
+
 use chrono::Datelike;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::Parser;
+
+    #[derive(Parser)]
+    struct TestCli {
+        #[command(subcommand)]
+        report: ReportCommands,
+    }
+
+    fn parse(args: &[&str]) -> ReportCommands {
+        TestCli::parse_from(std::iter::once("report").chain(args.iter().copied())).report
+    }
+
+    #[test]
+    fn test_json_flag_applies_to_every_report() {
+        let reports: [&[&str]; 9] = [
+            &["budget"],
+            &["variance"],
+            &["compare"],
+            &["spending", "--format", "md"],
+            &["cashflow"],
+            &["register", "Checking"],
+            &["tag", "vacation"],
+            &["net-worth"],
+            &["net-worth", "--trend", "3"],
+        ];
+        for args in reports {
+            let mut cmd = parse(args);
+            cmd.apply_output_format(OutputFormat::Text);
+            assert_ne!(cmd.format_mut().copied(), Some(ReportFormat::Json));
+
+            cmd.apply_output_format(OutputFormat::Json);
+            assert_eq!(
+                cmd.format_mut().copied(),
+                Some(ReportFormat::Json),
+                "{:?}",
+                args
+            );
+        }
+
+        // The tag given as an option is kept alongside the JSON format
+        let mut tagged = parse(&["tagged", "--tag", "vacation", "--year", "2025"]);
+        tagged.apply_output_format(OutputFormat::Json);
+        assert!(matches!(
+            tagged,
+            ReportCommands::Tag {
+                tag: None,
+                tag_option: Some(ref t),
+                year: Some(2025),
+                format: ReportFormat::Json,
+                ..
+            } if t == "vacation"
+        ));
+
+        let mut monthly = parse(&["monthly"]);
+        monthly.apply_output_format(OutputFormat::Json);
+        assert!(matches!(
+            monthly,
+            ReportCommands::Monthly { format: Some(ref f), .. } if f == "json"
+        ));
+    }
+}
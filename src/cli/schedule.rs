@@ -0,0 +1,217 @@
+//! Scheduled transaction CLI commands
+//!
+//! Implements CLI commands for recurring transactions like rent or
+//! subscriptions, and for posting the ones that have come due.
+
+use chrono::NaiveDate;
+use clap::Subcommand;
+
+use crate::cli::target::parse_cadence;
+use crate::config::clock;
+use crate::error::{EnvelopeError, EnvelopeResult};
+use crate::models::Money;
+use crate::services::{AccountService, CategoryService, CreateScheduledInput, ScheduledService};
+use crate::storage::Storage;
+
+/// Schedule subcommands
+#[derive(Subcommand)]
+pub enum ScheduleCommands {
+    /// Schedule a recurring transaction
+    Add {
+        /// Account name or ID
+        account: String,
+        /// Amount (negative for outflow, e.g., "-1500.00")
+        #[arg(allow_negative_numbers = true)]
+        amount: String,
+        /// Payee name
+        #[arg(short, long)]
+        payee: Option<String>,
+        /// Category name or ID
+        #[arg(short, long)]
+        category: Option<String>,
+        /// Memo
+        #[arg(short, long)]
+        memo: Option<String>,
+        /// Cadence: weekly, monthly, yearly, custom, or by-date
+        #[arg(long, default_value = "monthly")]
+        cadence: String,
+        /// Number of days for custom cadence (required when cadence is "custom")
+        #[arg(long)]
+        days: Option<u32>,
+        /// First occurrence (YYYY-MM-DD), defaults to today; the only one for by-date
+        #[arg(short, long)]
+        start: Option<String>,
+        /// Last day an occurrence may fall on (YYYY-MM-DD)
+        #[arg(short, long)]
+        end: Option<String>,
+    },
+
+    /// List scheduled transactions
+    List,
+
+    /// Post every scheduled transaction that is due
+    Run {
+        /// Post occurrences on or before this date (YYYY-MM-DD), defaults to today
+        #[arg(long)]
+        as_of: Option<String>,
+    },
+
+    /// Delete a scheduled transaction (posted transactions are kept)
+    Delete {
+        /// Schedule ID or payee name
+        schedule: String,
+    },
+}
+
+/// Handle a schedule command
+pub fn handle_schedule_command(storage: &Storage, cmd: ScheduleCommands) -> EnvelopeResult<()> {
+    let service = ScheduledService::new(storage);
+
+    match cmd {
+        ScheduleCommands::Add {
+            account,
+            amount,
+            payee,
+            category,
+            memo,
+            cadence,
+            days,
+            start,
+            end,
+        } => {
+            let account = AccountService::new(storage)
+                .find(&account)?
+                .ok_or_else(|| EnvelopeError::account_not_found(&account))?;
+
+            let amount = Money::parse(&amount)
+                .map_err(|e| EnvelopeError::Validation(format!("Invalid amount: {}", e)))?;
+
+            let category_id = match category {
+                Some(name) => Some(
+                    CategoryService::new(storage)
+                        .find_category(&name)?
+                        .ok_or_else(|| EnvelopeError::category_not_found(&name))?
+                        .id,
+                ),
+                None => None,
+            };
+
+            let start_date = match start.as_deref() {
+                Some(s) => parse_date(s)?,
+                None => clock::today(),
+            };
+            let end_date = end.as_deref().map(parse_date).transpose()?;
+
+            let start_str = start_date.format("%Y-%m-%d").to_string();
+            let cadence = parse_cadence(&cadence, days, Some(start_str.as_str()))?;
+
+            let scheduled = service.create(CreateScheduledInput {
+                account_id: account.id,
+                amount,
+                payee_name: payee,
+                category_id,
+                memo,
+                cadence,
+                start_date,
+                end_date,
+            })?;
+
+            println!("Scheduled: {}", scheduled);
+            println!("  ID:      {}", scheduled.id);
+            println!("  Account: {}", account.name);
+            println!("  Next:    {}", scheduled.next_date);
+            if let Some(end) = scheduled.end_date {
+                println!("  Ends:    {}", end);
+            }
+        }
+
+        ScheduleCommands::List => {
+            let schedules = service.list()?;
+
+            if schedules.is_empty() {
+                println!("No scheduled transactions.");
+                println!();
+                println!(
+                    "Use 'envelope schedule add <account> <amount> --payee <name>' to create one."
+                );
+                return Ok(());
+            }
+
+            let account_service = AccountService::new(storage);
+            println!(
+                "{:12} {:20} {:15} {:>12} {:15} {:12}",
+                "ID", "Payee", "Account", "Amount", "Cadence", "Next"
+            );
+            println!("{}", "-".repeat(91));
+
+            for scheduled in &schedules {
+                let account_name = account_service
+                    .get(scheduled.account_id)?
+                    .map(|a| a.name)
+                    .unwrap_or_else(|| "Unknown".to_string());
+                let next = if scheduled.active {
+                    scheduled.next_date.to_string()
+                } else {
+                    "finished".to_string()
+                };
+
+                println!(
+                    "{:12} {:20} {:15} {:>12} {:15} {:12}",
+                    scheduled.id.to_string(),
+                    scheduled.payee_name,
+                    account_name,
+                    scheduled.amount.to_string(),
+                    scheduled.cadence.to_string(),
+                    next
+                );
+            }
+
+            println!("{}", "-".repeat(91));
+            println!("{} schedule(s) total", schedules.len());
+        }
+
+        ScheduleCommands::Run { as_of } => {
+            let as_of = match as_of.as_deref() {
+                Some(s) => parse_date(s)?,
+                None => clock::today(),
+            };
+
+            let result = service.materialize_due(as_of)?;
+            if result.created.is_empty() && result.failed.is_empty() {
+                println!("Nothing due on or before {}.", as_of);
+            } else {
+                for txn in &result.created {
+                    println!("  {} {:20} {:>12}", txn.date, txn.payee_name, txn.amount);
+                }
+                println!("Posted {} scheduled transaction(s).", result.created.len());
+            }
+
+            for (scheduled, e) in &result.failed {
+                eprintln!("{}: {}", scheduled, e);
+            }
+            if !result.failed.is_empty() {
+                return Err(EnvelopeError::Validation(format!(
+                    "{} schedule(s) could not be posted",
+                    result.failed.len()
+                )));
+            }
+        }
+
+        ScheduleCommands::Delete { schedule } => {
+            let scheduled = service.find(&schedule)?.ok_or(EnvelopeError::NotFound {
+                entity_type: "Scheduled transaction",
+                identifier: schedule,
+            })?;
+            service.delete(scheduled.id)?;
+            println!("Deleted schedule: {}", scheduled);
+        }
+    }
+
+    Ok(())
+}
+
+fn parse_date(s: &str) -> EnvelopeResult<NaiveDate> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|_| {
+        EnvelopeError::Validation(format!("Invalid date format: '{}'. Use YYYY-MM-DD", s))
+    })
+}
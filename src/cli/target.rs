@@ -40,6 +40,14 @@ pub enum TargetCommands {
         category: String,
     },
 
+    /// Set the notes on a category's target ("" clears them)
+    Note {
+        /// Category name or ID
+        category: String,
+        /// Why this target exists
+        notes: String,
+    },
+
     /// Delete the target for a category
     Delete {
         /// Category name or ID
@@ -81,7 +89,8 @@ pub fn handle_target_command(
 
             let cadence = parse_cadence(&cadence, days, date.as_deref())?;
 
-            let budget_service = BudgetService::new(storage);
+            let budget_service =
+                BudgetService::new(storage).with_target_rounding(settings.target_rounding);
             let target = budget_service.set_target(cat.id, amount, cadence.clone())?;
 
             println!(
@@ -91,7 +100,8 @@ pub fn handle_target_command(
 
             // Show what the suggested amount would be for the current period
             let current_period = period_service.current_period();
-            let suggested = target.calculate_for_period(&current_period);
+            let suggested =
+                target.calculate_for_period_rounded(&current_period, settings.target_rounding);
             println!(
                 "  Suggested for {}: {}",
                 period_service.format_period_friendly(&current_period),
@@ -100,7 +110,8 @@ pub fn handle_target_command(
         }
 
         TargetCommands::List => {
-            let budget_service = BudgetService::new(storage);
+            let budget_service =
+                BudgetService::new(storage).with_target_rounding(settings.target_rounding);
             let category_service = CategoryService::new(storage);
             let targets = budget_service.get_all_targets()?;
 
@@ -122,7 +133,8 @@ pub fn handle_target_command(
                         .map(|c| c.name)
                         .unwrap_or_else(|| "Unknown".to_string());
 
-                    let suggested = target.calculate_for_period(&current_period);
+                    let suggested = target
+                        .calculate_for_period_rounded(&current_period, settings.target_rounding);
 
                     println!(
                         "{:25} {:>12} {:>15}",
@@ -138,6 +150,10 @@ pub fn handle_target_command(
                             period_service.format_period_friendly(&current_period)
                         );
                     }
+
+                    if !target.notes.is_empty() {
+                        println!("{:25} {}", "", target.notes);
+                    }
                 }
 
                 println!("{}", "-".repeat(60));
@@ -151,7 +167,8 @@ pub fn handle_target_command(
                 .find_category(&category)?
                 .ok_or_else(|| EnvelopeError::category_not_found(&category))?;
 
-            let budget_service = BudgetService::new(storage);
+            let budget_service =
+                BudgetService::new(storage).with_target_rounding(settings.target_rounding);
             let target = budget_service.get_target(cat.id)?;
 
             match target {
@@ -179,7 +196,8 @@ pub fn handle_target_command(
                             }
                             p
                         };
-                        let suggested = t.calculate_for_period(&period);
+                        let suggested =
+                            t.calculate_for_period_rounded(&period, settings.target_rounding);
                         let label = if i == 0 { " (current)" } else { "" };
                         println!(
                             "  {}{}: {}",
@@ -200,13 +218,31 @@ pub fn handle_target_command(
             }
         }
 
+        TargetCommands::Note { category, notes } => {
+            let category_service = CategoryService::new(storage);
+            let cat = category_service
+                .find_category(&category)?
+                .ok_or_else(|| EnvelopeError::category_not_found(&category))?;
+
+            let budget_service =
+                BudgetService::new(storage).with_target_rounding(settings.target_rounding);
+            let target = budget_service.set_target_notes(cat.id, &notes)?;
+
+            if target.notes.is_empty() {
+                println!("Cleared notes on the target for '{}'.", cat.name);
+            } else {
+                println!("Notes for '{}': {}", cat.name, target.notes);
+            }
+        }
+
         TargetCommands::Delete { category } => {
             let category_service = CategoryService::new(storage);
             let cat = category_service
                 .find_category(&category)?
                 .ok_or_else(|| EnvelopeError::category_not_found(&category))?;
 
-            let budget_service = BudgetService::new(storage);
+            let budget_service =
+                BudgetService::new(storage).with_target_rounding(settings.target_rounding);
             let deleted = budget_service.remove_target(cat.id)?;
 
             if deleted {
@@ -220,7 +256,8 @@ pub fn handle_target_command(
             let period = period_service.parse_or_current(period.as_deref())?;
             let friendly = period_service.format_period_friendly(&period);
 
-            let budget_service = BudgetService::new(storage);
+            let budget_service =
+                BudgetService::new(storage).with_target_rounding(settings.target_rounding);
             let category_service = CategoryService::new(storage);
             let allocations = budget_service.auto_fill_all_targets(&period)?;
 
@@ -265,7 +302,7 @@ pub fn handle_target_command(
 }
 
 /// Parse the cadence string and optional parameters into a TargetCadence
-fn parse_cadence(
+pub(crate) fn parse_cadence(
     cadence: &str,
     days: Option<u32>,
     date: Option<&str>,
@@ -2,14 +2,26 @@
 //!
 //! Implements CLI commands for transaction management.
 
+use std::collections::{HashMap, HashSet};
+
 use chrono::NaiveDate;
 use clap::Subcommand;
 
+use crate::cli::confirm::confirm_with_backup;
+use crate::cli::output::{print_json, OutputFormat};
+use crate::cli::pager::print_paged;
+use crate::config::clock;
+use crate::config::settings::Settings;
 use crate::display::transaction::{
     format_transaction_details, format_transaction_list_by_account, format_transaction_register,
+    format_transaction_register_with_accounts,
 };
 use crate::error::{EnvelopeError, EnvelopeResult};
-use crate::models::{Money, TransactionStatus};
+use crate::models::{
+    parse_tags, Account, AccountId, Money, Transaction, TransactionFlag, TransactionId,
+    TransactionStatus,
+};
+use crate::reports::{AccountRegisterReport, RegisterFilter};
 use crate::services::{
     AccountService, CategoryService, CreateTransactionInput, PayeeService, TransactionFilter,
     TransactionService,
@@ -21,14 +33,19 @@ use crate::storage::Storage;
 pub enum TransactionCommands {
     /// Add a new transaction
     Add {
-        /// Account name or ID
-        account: String,
+        /// Account name or ID, or the amount when the account is left out
+        #[arg(value_name = "ACCOUNT", allow_negative_numbers = true)]
+        account_or_amount: String,
         /// Amount (e.g., "-50.00" for outflow, "100.00" for inflow)
-        amount: String,
+        #[arg(allow_negative_numbers = true)]
+        amount: Option<String>,
+        /// Account name or ID, instead of giving it before the amount
+        #[arg(short, long)]
+        account: Option<String>,
         /// Payee name
         #[arg(short, long)]
         payee: Option<String>,
-        /// Category name
+        /// Category name ("none" to skip the default category)
         #[arg(short, long)]
         category: Option<String>,
         /// Transaction date (YYYY-MM-DD), defaults to today
@@ -43,6 +60,9 @@ pub enum TransactionCommands {
         /// Auto-categorize based on payee history
         #[arg(long)]
         auto_categorize: bool,
+        /// Where an inflow came from (e.g., salary, freelance, interest)
+        #[arg(long)]
+        income_source: Option<String>,
     },
     /// List transactions
     List {
@@ -64,10 +84,46 @@ pub enum TransactionCommands {
         /// Filter by status (pending, cleared, reconciled)
         #[arg(long)]
         status: Option<String>,
+        /// Filter by tag
+        #[arg(long)]
+        tag: Option<String>,
+        /// Filter by flag (none, review, starred)
+        #[arg(long)]
+        flag: Option<String>,
+        /// Show transactions from every non-archived account with an account column
+        #[arg(long, conflicts_with = "account")]
+        all_accounts: bool,
+        /// Print everything without a pager, even when it won't fit on screen
+        #[arg(long)]
+        no_pager: bool,
+    },
+    /// Search every account by payee, memo, and amount
+    Search {
+        /// Payee contains this text (case-insensitive)
+        #[arg(short, long)]
+        payee: Option<String>,
+        /// Memo or a split's memo contains this text (case-insensitive)
+        #[arg(short, long)]
+        memo: Option<String>,
+        /// Smallest amount, ignoring sign (e.g., "40")
+        #[arg(long)]
+        min: Option<String>,
+        /// Largest amount, ignoring sign (e.g., "45")
+        #[arg(long)]
+        max: Option<String>,
+        /// Start date (YYYY-MM-DD)
+        #[arg(long)]
+        from: Option<String>,
+        /// End date (YYYY-MM-DD)
+        #[arg(long)]
+        to: Option<String>,
+        /// Print everything without a pager, even when it won't fit on screen
+        #[arg(long)]
+        no_pager: bool,
     },
     /// Show transaction details
     Show {
-        /// Transaction ID
+        /// Transaction ID (full or the short txn-xxxxxxxx form)
         id: String,
     },
     /// Edit a transaction
@@ -90,35 +146,77 @@ pub enum TransactionCommands {
         #[arg(short, long)]
         memo: Option<String>,
     },
-    /// Delete a transaction
+    /// Delete one or more transactions
     Delete {
-        /// Transaction ID
-        id: String,
+        /// Transaction IDs
+        #[arg(required = true)]
+        ids: Vec<String>,
         /// Skip confirmation
-        #[arg(short, long)]
-        force: bool,
+        #[arg(short, long, alias = "force", short_alias = 'f')]
+        yes: bool,
     },
-    /// Clear a transaction (mark as cleared)
+    /// Clear transactions (mark as cleared)
     Clear {
+        /// Transaction IDs
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+    /// Unclear transactions (mark as pending)
+    Unclear {
+        /// Transaction IDs
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+    /// Reconcile transactions (mark as reconciled and lock)
+    Reconcile {
+        /// Transaction IDs
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+    /// Unlock reconciled transactions for editing
+    Unlock {
+        /// Transaction IDs
+        #[arg(required = true)]
+        ids: Vec<String>,
+    },
+    /// Add tags to a transaction
+    Tag {
         /// Transaction ID
         id: String,
+        /// Tags to add (e.g., "vacation2025" or "#tax-deductible")
+        #[arg(required = true)]
+        tags: Vec<String>,
     },
-    /// Unclear a transaction (mark as pending)
-    Unclear {
+    /// Remove tags from a transaction
+    Untag {
         /// Transaction ID
         id: String,
+        /// Tags to remove
+        #[arg(required = true)]
+        tags: Vec<String>,
     },
-    /// Unlock a reconciled transaction for editing
-    Unlock {
+    /// Set where an inflow came from, for income reports
+    Source {
         /// Transaction ID
         id: String,
+        /// Income source (e.g., salary, freelance, interest), or "none" to clear
+        source: String,
+    },
+    /// Flag a transaction for review or star it
+    Flag {
+        /// Transaction ID
+        id: String,
+        /// Flag (none, review, starred)
+        flag: String,
     },
 }
 
 /// Handle a transaction command
 pub fn handle_transaction_command(
     storage: &Storage,
+    settings: &Settings,
     cmd: TransactionCommands,
+    output: OutputFormat,
 ) -> EnvelopeResult<()> {
     let service = TransactionService::new(storage);
     let account_service = AccountService::new(storage);
@@ -127,19 +225,40 @@ pub fn handle_transaction_command(
 
     match cmd {
         TransactionCommands::Add {
-            account,
+            account_or_amount,
             amount,
+            account,
             payee,
             category,
             date,
             memo,
             cleared,
             auto_categorize,
+            income_source,
         } => {
+            // With one positional it's the amount, and the account comes from
+            // --account or the last one used
+            let (account, amount) = match (amount, account) {
+                (Some(amount), None) => (Some(account_or_amount), amount),
+                (None, account) => (account, account_or_amount),
+                (Some(_), Some(_)) => {
+                    return Err(EnvelopeError::Validation(
+                        "Give the account before the amount or with --account, not both".into(),
+                    ))
+                }
+            };
+
             // Find account
-            let account = account_service
-                .find(&account)?
-                .ok_or_else(|| EnvelopeError::account_not_found(&account))?;
+            let account = match account {
+                Some(account) => account_service
+                    .find(&account)?
+                    .ok_or_else(|| EnvelopeError::account_not_found(&account))?,
+                None => {
+                    let account = last_used_account(&account_service, settings)?;
+                    println!("Using last account: {}", account.name);
+                    account
+                }
+            };
 
             // Parse amount
             let amount = Money::parse(&amount).map_err(|e| {
@@ -148,6 +267,11 @@ pub fn handle_transaction_command(
                     amount, e
                 ))
             })?;
+            if income_source.is_some() && !amount.is_positive() {
+                return Err(EnvelopeError::Validation(
+                    "Only inflows can have an income source".into(),
+                ));
+            }
 
             // Parse date (default to today)
             let date = if let Some(date_str) = date {
@@ -158,21 +282,25 @@ pub fn handle_transaction_command(
                     ))
                 })?
             } else {
-                chrono::Local::now().date_naive()
+                clock::today()
             };
 
-            // Find category
-            let mut category_id = if let Some(cat_name) = &category {
-                let cat = category_service
-                    .find_category(cat_name)?
-                    .ok_or_else(|| EnvelopeError::category_not_found(cat_name))?;
-                Some(cat.id)
-            } else {
-                None
+            // Find category ("none" explicitly leaves it uncategorized)
+            let skip_default = category
+                .as_deref()
+                .is_some_and(|c| c.eq_ignore_ascii_case("none"));
+            let mut category_id = match &category {
+                Some(cat_name) if !skip_default => {
+                    let cat = category_service
+                        .find_category(cat_name)?
+                        .ok_or_else(|| EnvelopeError::category_not_found(cat_name))?;
+                    Some(cat.id)
+                }
+                _ => None,
             };
 
             // Auto-categorize from payee if requested
-            if auto_categorize && category_id.is_none() {
+            if auto_categorize && category_id.is_none() && !skip_default {
                 if let Some(payee_name) = &payee {
                     category_id = payee_service.get_suggested_category(payee_name)?;
                     if category_id.is_some() {
@@ -181,6 +309,11 @@ pub fn handle_transaction_command(
                 }
             }
 
+            // Fall back to the account or global default category
+            if category_id.is_none() && !skip_default {
+                category_id = service.default_category(account.id, settings.default_category_id)?;
+            }
+
             let status = if cleared {
                 Some(TransactionStatus::Cleared)
             } else {
@@ -197,11 +330,21 @@ pub fn handle_transaction_command(
                 status,
             };
 
-            let txn = service.create(input)?;
+            let mut txn = service.create(input)?;
+            if let Some(source) = &income_source {
+                txn = service.set_income_source(txn.id, Some(source))?;
+            }
 
             // Learn from transaction (update payee category frequency)
             service.learn_from_transaction(&txn)?;
 
+            // Remember the account for the next add without one
+            if settings.last_account_id != Some(account.id) {
+                let mut settings = settings.clone();
+                settings.last_account_id = Some(account.id);
+                settings.save(storage.paths())?;
+            }
+
             println!("Created transaction:");
             println!("  ID:       {}", txn.id);
             println!("  Date:     {}", txn.date);
@@ -214,6 +357,9 @@ pub fn handle_transaction_command(
                     println!("  Category: {}", cat.name);
                 }
             }
+            if let Some(source) = &txn.income_source {
+                println!("  Income:   {}", source);
+            }
             println!("  Status:   {}", txn.status);
         }
 
@@ -224,8 +370,15 @@ pub fn handle_transaction_command(
             from,
             to,
             status,
+            tag,
+            flag,
+            all_accounts,
+            no_pager,
         } => {
             let mut filter = TransactionFilter::new().limit(limit);
+            if all_accounts {
+                filter = filter.active_accounts_only();
+            }
 
             // Apply account filter
             if let Some(acc_name) = &account {
@@ -280,22 +433,110 @@ pub fn handle_transaction_command(
                 filter = filter.status(status);
             }
 
+            if let Some(tag) = tag {
+                filter = filter.tag(tag);
+            }
+
+            if let Some(flag) = flag {
+                filter = filter.flag(parse_flag(&flag)?);
+            }
+
             let transactions = service.list(filter)?;
+            if output.is_json() {
+                return print_json(&transactions);
+            }
 
-            if let Some(acc_name) = &account {
+            let mut output = if all_accounts {
+                let account_names = account_service
+                    .list(true)?
+                    .into_iter()
+                    .map(|a| (a.id, a.name))
+                    .collect();
+                format_transaction_register_with_accounts(
+                    &transactions,
+                    &account_names,
+                    settings.negative_style,
+                )
+            } else if let Some(acc_name) = &account {
                 if let Some(acc) = account_service.find(acc_name)? {
-                    print!(
-                        "{}",
-                        format_transaction_list_by_account(&transactions, &acc.name)
-                    );
+                    format_transaction_list_by_account(
+                        &transactions,
+                        &acc.name,
+                        settings.negative_style,
+                    )
                 } else {
-                    print!("{}", format_transaction_register(&transactions));
+                    format_transaction_register(&transactions, None, settings.negative_style)
                 }
             } else {
-                print!("{}", format_transaction_register(&transactions));
+                let balances = running_balances(storage, &transactions)?;
+                format_transaction_register(&transactions, Some(&balances), settings.negative_style)
+            };
+
+            output.push_str(&format!("\nShowing {} transactions\n", transactions.len()));
+            print_paged(&output, no_pager)?;
+        }
+
+        TransactionCommands::Search {
+            payee,
+            memo,
+            min,
+            max,
+            from,
+            to,
+            no_pager,
+        } => {
+            if payee.is_none() && memo.is_none() && min.is_none() && max.is_none() {
+                return Err(EnvelopeError::Validation(
+                    "Give at least one of --payee, --memo, --min, or --max".into(),
+                ));
             }
 
-            println!("\nShowing {} transactions", transactions.len());
+            let mut filter = TransactionFilter::new();
+            if let Some(payee) = payee {
+                filter = filter.payee_contains(payee);
+            }
+            if let Some(memo) = memo {
+                filter = filter.memo_contains(memo);
+            }
+            if let Some(min) = min {
+                filter = filter.amount_min(parse_search_amount(&min)?);
+            }
+            if let Some(max) = max {
+                filter = filter.amount_max(parse_search_amount(&max)?);
+            }
+            filter.start_date = from.as_deref().map(parse_search_date).transpose()?;
+            filter.end_date = to.as_deref().map(parse_search_date).transpose()?;
+
+            let transactions = service.list(filter)?;
+            if transactions.is_empty() {
+                println!("No matching transactions.");
+                return Ok(());
+            }
+
+            // One section per account, in account list order
+            let mut output = String::new();
+            for account in account_service.list(true)? {
+                let matches: Vec<_> = transactions
+                    .iter()
+                    .filter(|t| t.account_id == account.id)
+                    .cloned()
+                    .collect();
+                if matches.is_empty() {
+                    continue;
+                }
+                output.push_str(&format_transaction_list_by_account(
+                    &matches,
+                    &account.name,
+                    settings.negative_style,
+                ));
+                output.push('\n');
+            }
+
+            output.push_str(&format!(
+                "Found {} matching transactions\n",
+                transactions.len()
+            ));
+            print_paged(&output, no_pager)?;
         }
 
         TransactionCommands::Show { id } => {
@@ -309,15 +550,42 @@ pub fn handle_transaction_command(
                 None
             };
 
+            let mut split_categories = HashMap::new();
+            for cat_id in txn.splits.iter().filter_map(|s| s.category_id) {
+                if let Some(category) = category_service.get_category(cat_id)? {
+                    split_categories.insert(cat_id, category.name);
+                }
+            }
+
             print!(
                 "{}",
-                format_transaction_details(&txn, category_name.as_deref())
+                format_transaction_details(&txn, category_name.as_deref(), &split_categories)
             );
 
             // Show account name
             if let Some(account) = account_service.get(txn.account_id)? {
                 println!("Account:     {}", account.name);
             }
+
+            // Show the transfer's other side by account
+            if let Some(linked_id) = txn.transfer_transaction_id {
+                if let Some(linked) = service.get(linked_id)? {
+                    if let Some(account) = account_service.get(linked.account_id)? {
+                        println!("Transfer:    {} in {}", linked.amount, account.name);
+                    }
+                }
+            }
+
+            if let Some(entry) = storage.read_audit_history(&txn.id.to_string())?.last() {
+                println!(
+                    "Last change: {} {}",
+                    entry.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+                    entry.operation
+                );
+                if let Some(diff) = &entry.diff_summary {
+                    println!("             {}", diff);
+                }
+            }
         }
 
         TransactionCommands::Edit {
@@ -380,66 +648,259 @@ pub fn handle_transaction_command(
             }
         }
 
-        TransactionCommands::Delete { id, force } => {
-            let txn = service
-                .find(&id)?
-                .ok_or_else(|| EnvelopeError::transaction_not_found(&id))?;
+        TransactionCommands::Delete { ids, yes } => {
+            let mut txns = Vec::with_capacity(ids.len());
+            for id in &ids {
+                let txn = service
+                    .find(id)?
+                    .ok_or_else(|| EnvelopeError::transaction_not_found(id))?;
+                txns.push(txn);
+            }
 
-            if !force {
-                println!("About to delete transaction:");
-                println!("  Date:   {}", txn.date);
-                println!("  Amount: {}", txn.amount);
-                println!("  Payee:  {}", txn.payee_name);
+            if settings.requires_confirmation(yes) {
+                println!("About to delete:");
+                for txn in &txns {
+                    println!("  {}  {:>12}  {}", txn.date, txn.amount, txn.payee_name);
+                }
                 println!();
-                println!("Use --force to confirm deletion");
-                return Ok(());
             }
 
-            let deleted = service.delete(txn.id)?;
-            println!(
-                "Deleted transaction: {} ({} {})",
-                deleted.id, deleted.date, deleted.payee_name
+            let prompt = format!(
+                "Delete {} transaction{}?",
+                txns.len(),
+                if txns.len() == 1 { "" } else { "s" }
             );
+            if !confirm_with_backup(storage, settings, yes, &prompt)? {
+                return Ok(());
+            }
+
+            for txn in txns {
+                let deleted = service.delete(txn.id)?;
+                println!(
+                    "Deleted transaction: {} ({} {})",
+                    deleted.id, deleted.date, deleted.payee_name
+                );
+            }
+        }
+
+        TransactionCommands::Clear { ids } => {
+            update_each(&service, &ids, |id| {
+                let cleared = service.clear(id)?;
+                println!(
+                    "Cleared transaction: {} ({})",
+                    cleared.id, cleared.payee_name
+                );
+                Ok(())
+            })?;
+        }
+
+        TransactionCommands::Unclear { ids } => {
+            update_each(&service, &ids, |id| {
+                let uncleared = service.unclear(id)?;
+                println!(
+                    "Uncleared transaction: {} ({})",
+                    uncleared.id, uncleared.payee_name
+                );
+                Ok(())
+            })?;
+        }
+
+        TransactionCommands::Reconcile { ids } => {
+            update_each(&service, &ids, |id| {
+                let reconciled = service.set_status(id, TransactionStatus::Reconciled)?;
+                println!(
+                    "Reconciled transaction: {} ({}) - now locked",
+                    reconciled.id, reconciled.payee_name
+                );
+                Ok(())
+            })?;
         }
 
-        TransactionCommands::Clear { id } => {
+        TransactionCommands::Unlock { ids } => {
+            let mut unlocked_any = false;
+            let result = update_each(&service, &ids, |id| {
+                let unlocked = service.unlock(id)?;
+                println!(
+                    "Unlocked transaction: {} ({}) - now marked as Cleared",
+                    unlocked.id, unlocked.payee_name
+                );
+                unlocked_any = true;
+                Ok(())
+            });
+            if unlocked_any {
+                println!("WARNING: Unlocked transactions were previously reconciled.");
+                println!("         Editing them may cause discrepancies with your bank statement.");
+            }
+            result?;
+        }
+
+        TransactionCommands::Tag { id, tags } => {
             let txn = service
                 .find(&id)?
                 .ok_or_else(|| EnvelopeError::transaction_not_found(&id))?;
+            let tags = tags.iter().flat_map(|t| parse_tags(t)).collect::<Vec<_>>();
+            if tags.is_empty() {
+                return Err(EnvelopeError::Validation("No valid tags given".into()));
+            }
 
-            let cleared = service.clear(txn.id)?;
-            println!(
-                "Cleared transaction: {} ({})",
-                cleared.id, cleared.payee_name
-            );
+            let tagged = service.add_tags(txn.id, &tags)?;
+            println!("Tagged transaction: {} ({})", tagged.id, tagged.payee_name);
+            println!("  Tags: {}", format_tags(&tagged.tags));
         }
 
-        TransactionCommands::Unclear { id } => {
+        TransactionCommands::Untag { id, tags } => {
             let txn = service
                 .find(&id)?
                 .ok_or_else(|| EnvelopeError::transaction_not_found(&id))?;
+            let tags = tags.iter().flat_map(|t| parse_tags(t)).collect::<Vec<_>>();
 
-            let uncleared = service.unclear(txn.id)?;
+            let untagged = service.remove_tags(txn.id, &tags)?;
             println!(
-                "Uncleared transaction: {} ({})",
-                uncleared.id, uncleared.payee_name
+                "Untagged transaction: {} ({})",
+                untagged.id, untagged.payee_name
             );
+            println!("  Tags: {}", format_tags(&untagged.tags));
+        }
+
+        TransactionCommands::Source { id, source } => {
+            let txn = service
+                .find(&id)?
+                .ok_or_else(|| EnvelopeError::transaction_not_found(&id))?;
+            let source = Some(source.as_str()).filter(|s| !s.eq_ignore_ascii_case("none"));
+
+            let updated = service.set_income_source(txn.id, source)?;
+            match &updated.income_source {
+                Some(source) => println!(
+                    "Set income source of {} ({}) to {}",
+                    updated.id, updated.payee_name, source
+                ),
+                None => println!(
+                    "Cleared income source of {} ({})",
+                    updated.id, updated.payee_name
+                ),
+            }
         }
 
-        TransactionCommands::Unlock { id } => {
+        TransactionCommands::Flag { id, flag } => {
             let txn = service
                 .find(&id)?
                 .ok_or_else(|| EnvelopeError::transaction_not_found(&id))?;
 
-            let unlocked = service.unlock(txn.id)?;
+            let updated = service.set_flag(txn.id, parse_flag(&flag)?)?;
             println!(
-                "Unlocked transaction: {} ({}) - now marked as Cleared",
-                unlocked.id, unlocked.payee_name
+                "Flagged {} ({}) as {}",
+                updated.id, updated.payee_name, updated.flag
             );
-            println!("WARNING: This transaction was previously reconciled.");
-            println!("         Editing it may cause discrepancies with your bank statement.");
         }
     }
 
     Ok(())
 }
+
+/// Format tags for display, or "(none)"
+/// Each listed transaction's account balance after it
+///
+/// Balances are worked out per account over its full history in date order,
+/// so they're right however the list is sorted or filtered.
+fn running_balances(
+    storage: &Storage,
+    transactions: &[Transaction],
+) -> EnvelopeResult<HashMap<TransactionId, Money>> {
+    let accounts: HashSet<AccountId> = transactions.iter().map(|t| t.account_id).collect();
+
+    let mut balances = HashMap::new();
+    for account_id in accounts {
+        let report =
+            AccountRegisterReport::generate(storage, account_id, RegisterFilter::default())?;
+        balances.extend(report.running_balances());
+    }
+    Ok(balances)
+}
+
+fn parse_search_amount(s: &str) -> EnvelopeResult<Money> {
+    Money::parse(s).map_err(|e| {
+        EnvelopeError::Validation(format!("Invalid amount format: '{}'. Error: {}", s, e))
+    })
+}
+
+fn parse_flag(s: &str) -> EnvelopeResult<TransactionFlag> {
+    TransactionFlag::parse(s).ok_or_else(|| {
+        EnvelopeError::Validation(format!(
+            "Invalid flag: '{}'. Use none, review, or starred",
+            s
+        ))
+    })
+}
+
+fn parse_search_date(s: &str) -> EnvelopeResult<NaiveDate> {
+    NaiveDate::parse_from_str(s, "%Y-%m-%d").map_err(|_| {
+        EnvelopeError::Validation(format!("Invalid date format: '{}'. Use YYYY-MM-DD", s))
+    })
+}
+
+fn format_tags(tags: &[String]) -> String {
+    if tags.is_empty() {
+        "(none)".to_string()
+    } else {
+        tags.iter()
+            .map(|t| format!("#{}", t))
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+}
+
+/// The account `transaction add` last used, for an add without one
+///
+/// Archived or deleted accounts aren't assumed.
+fn last_used_account(service: &AccountService, settings: &Settings) -> EnvelopeResult<Account> {
+    let account = match settings.last_account_id {
+        Some(id) => service.get(id)?.filter(|a| !a.archived),
+        None => None,
+    };
+    account.ok_or_else(|| {
+        EnvelopeError::Validation(
+            "No account given and no recent account to use. Name one before the amount or with --account"
+                .into(),
+        )
+    })
+}
+
+/// Apply a status change to each transaction ID
+///
+/// Every ID is attempted; failures (unknown IDs, locked transactions) are
+/// reported individually and the command fails if any occurred.
+fn update_each<F>(service: &TransactionService, ids: &[String], mut update: F) -> EnvelopeResult<()>
+where
+    F: FnMut(TransactionId) -> EnvelopeResult<()>,
+{
+    // A single ID surfaces its error directly
+    if let [id] = ids {
+        let txn = service
+            .find(id)?
+            .ok_or_else(|| EnvelopeError::transaction_not_found(id))?;
+        return update(txn.id);
+    }
+
+    let mut failures = 0;
+    for id in ids {
+        let result = service
+            .find(id)?
+            .ok_or_else(|| EnvelopeError::transaction_not_found(id))
+            .and_then(|txn| update(txn.id));
+
+        if let Err(e) = result {
+            eprintln!("{}: {}", id, e);
+            failures += 1;
+        }
+    }
+
+    if failures > 0 {
+        return Err(EnvelopeError::Validation(format!(
+            "{} of {} transactions could not be updated",
+            failures,
+            ids.len()
+        )));
+    }
+
+    Ok(())
+}
@@ -5,12 +5,16 @@
 
 use chrono::NaiveDate;
 
+use crate::config::clock;
 use crate::error::{EnvelopeError, EnvelopeResult};
 use crate::models::Money;
 use crate::services::{AccountService, TransferService};
 use crate::storage::Storage;
 
 /// Handle the transfer command
+///
+/// `rate` converts the amount into the destination account's currency and
+/// is required when the two accounts' currencies differ.
 pub fn handle_transfer_command(
     storage: &Storage,
     from: &str,
@@ -18,9 +22,13 @@ pub fn handle_transfer_command(
     amount: &str,
     date: Option<&str>,
     memo: Option<String>,
+    rate: Option<f64>,
 ) -> EnvelopeResult<()> {
     let account_service = AccountService::new(storage);
-    let transfer_service = TransferService::new(storage);
+    let mut transfer_service = TransferService::new(storage);
+    if let Some(rate) = rate {
+        transfer_service = transfer_service.with_exchange_rate(rate);
+    }
 
     // Find source account
     let from_account = account_service
@@ -56,7 +64,7 @@ pub fn handle_transfer_command(
             ))
         })?
     } else {
-        chrono::Local::now().date_naive()
+        clock::today()
     };
 
     let result =
@@ -65,11 +73,13 @@ pub fn handle_transfer_command(
     println!("Transfer created:");
     println!(
         "  From: {} ({})",
-        from_account.name, result.from_transaction.amount
+        from_account.name,
+        from_account.format_amount(result.from_transaction.amount)
     );
     println!(
         "  To:   {} ({})",
-        to_account.name, result.to_transaction.amount
+        to_account.name,
+        to_account.format_amount(result.to_transaction.amount)
     );
     println!("  Date: {}", date);
 
@@ -0,0 +1,175 @@
+//! Application clock
+//!
+//! All code asks this module for "now" and "today" instead of calling
+//! `Utc::now()` or `Local::now()` directly, so tests can pin the date and a
+//! session can be backdated with the hidden `--date` option.
+//!
+//! Dates are local calendar days: the configured timezone if one is set,
+//! otherwise the system's. Timestamps stay in UTC.
+//!
+//! The clock is installed per thread. The CLI and TUI do all their work on
+//! the main thread, and each test runs on its own thread, so a test's fixed
+//! clock never leaks into another test.
+
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use chrono::{DateTime, Local, NaiveDate, Utc};
+use chrono_tz::Tz;
+
+/// Source of the current time and date
+pub trait Clock {
+    /// Current instant, used for timestamps
+    fn now(&self) -> DateTime<Utc>;
+
+    /// Today's date, used for default transaction dates, periods, and reports
+    fn today(&self) -> NaiveDate;
+}
+
+/// The real system clock
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock {
+    timezone: Option<Tz>,
+}
+
+impl SystemClock {
+    /// A system clock whose dates follow `timezone` instead of the system's
+    pub fn in_timezone(timezone: Tz) -> Self {
+        Self {
+            timezone: Some(timezone),
+        }
+    }
+}
+
+impl Clock for SystemClock {
+    fn now(&self) -> DateTime<Utc> {
+        Utc::now()
+    }
+
+    fn today(&self) -> NaiveDate {
+        local_date(Utc::now(), self.timezone)
+    }
+}
+
+/// A clock pinned to a fixed date
+#[derive(Debug, Clone, Copy)]
+pub struct FixedClock {
+    today: NaiveDate,
+    now: Option<DateTime<Utc>>,
+}
+
+impl FixedClock {
+    /// A clock whose date is fixed while timestamps keep the real time
+    ///
+    /// Used for backdated sessions, so audit entries and backups still
+    /// record when changes were actually made.
+    pub fn on(today: NaiveDate) -> Self {
+        Self { today, now: None }
+    }
+
+    /// A clock frozen at an instant, for deterministic tests
+    pub fn at(now: DateTime<Utc>) -> Self {
+        Self {
+            today: now.date_naive(),
+            now: Some(now),
+        }
+    }
+
+    /// Take the date of a frozen instant in `timezone` rather than UTC
+    pub fn in_timezone(mut self, timezone: Tz) -> Self {
+        if let Some(now) = self.now {
+            self.today = local_date(now, Some(timezone));
+        }
+        self
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> DateTime<Utc> {
+        self.now.unwrap_or_else(Utc::now)
+    }
+
+    fn today(&self) -> NaiveDate {
+        self.today
+    }
+}
+
+thread_local! {
+    static CLOCK: RefCell<Rc<dyn Clock>> = RefCell::new(Rc::new(SystemClock::default()));
+}
+
+/// Install the clock used by the current thread
+pub fn set_clock(clock: impl Clock + 'static) {
+    CLOCK.with(|c| *c.borrow_mut() = Rc::new(clock));
+}
+
+/// Go back to the system clock on the current thread
+pub fn reset_clock() {
+    set_clock(SystemClock::default());
+}
+
+/// Current instant from the installed clock
+pub fn now() -> DateTime<Utc> {
+    CLOCK.with(|c| c.borrow().clone()).now()
+}
+
+/// Today's date from the installed clock
+pub fn today() -> NaiveDate {
+    CLOCK.with(|c| c.borrow().clone()).today()
+}
+
+/// Parse an IANA timezone name such as `America/New_York`
+pub fn parse_timezone(name: &str) -> Option<Tz> {
+    name.trim().parse().ok()
+}
+
+/// The calendar day of `now` in `timezone`, or in the system timezone
+fn local_date(now: DateTime<Utc>, timezone: Option<Tz>) -> NaiveDate {
+    match timezone {
+        Some(tz) => now.with_timezone(&tz).date_naive(),
+        None => now.with_timezone(&Local).date_naive(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+
+    #[test]
+    fn test_fixed_clock() {
+        let date = NaiveDate::from_ymd_opt(2024, 2, 29).unwrap();
+        set_clock(FixedClock::on(date));
+        assert_eq!(today(), date);
+
+        let instant = Utc.with_ymd_and_hms(2023, 7, 4, 12, 0, 0).unwrap();
+        set_clock(FixedClock::at(instant));
+        assert_eq!(now(), instant);
+        assert_eq!(today(), instant.date_naive());
+
+        reset_clock();
+        assert_eq!(today(), Local::now().date_naive());
+    }
+
+    #[test]
+    fn test_late_evening_uses_local_day() {
+        // 10:30 PM on Jan 15 in New York is already Jan 16 in UTC
+        let instant = Utc.with_ymd_and_hms(2025, 1, 16, 3, 30, 0).unwrap();
+        let new_york = parse_timezone("America/New_York").unwrap();
+        set_clock(FixedClock::at(instant).in_timezone(new_york));
+
+        assert_eq!(today(), NaiveDate::from_ymd_opt(2025, 1, 15).unwrap());
+        assert_eq!(now(), instant);
+
+        let txn = crate::models::Transaction::new(
+            crate::models::AccountId::new(),
+            today(),
+            crate::models::Money::from_cents(-500),
+        );
+        assert_eq!(txn.date, NaiveDate::from_ymd_opt(2025, 1, 15).unwrap());
+        assert_eq!(txn.created_at, instant);
+
+        reset_clock();
+        assert!(parse_timezone("Mars/Olympus_Mons").is_none());
+    }
+}
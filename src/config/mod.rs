@@ -4,7 +4,9 @@
 //! - XDG-compliant path resolution
 //! - User settings persistence
 //! - Application preferences
+//! - The application clock
 
+pub mod clock;
 pub mod paths;
 pub mod settings;
 
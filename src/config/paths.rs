@@ -7,16 +7,33 @@
 //! 1. `ENVELOPE_CLI_DATA_DIR` environment variable (if set)
 //! 2. Unix (Linux/macOS): `$XDG_CONFIG_HOME/envelope-cli` or `~/.config/envelope-cli`
 //! 3. Windows: `%APPDATA%\envelope-cli`
+//!
+//! ## Profiles
+//!
+//! Each profile is a separate budget with its own settings, data, and
+//! backups. The default profile uses the root directory above, so existing
+//! data stays where it is. Other profiles live under `profiles/<name>/`.
+//!
+//! The active profile is, in order: the `--profile` flag, the
+//! `ENVELOPE_PROFILE` environment variable, the profile saved by
+//! `envelope profile switch`, or the default profile.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 
 use crate::error::EnvelopeError;
 
+/// Name of the profile that uses the root directory
+pub const DEFAULT_PROFILE: &str = "default";
+
 /// Manages all paths used by EnvelopeCLI
 #[derive(Debug, Clone)]
 pub struct EnvelopePaths {
-    /// Base directory for all EnvelopeCLI data
+    /// Base directory for the active profile's data
     base_dir: PathBuf,
+    /// Root directory shared by all profiles
+    root_dir: PathBuf,
+    /// Name of the active profile
+    profile: String,
 }
 
 impl EnvelopePaths {
@@ -31,18 +48,97 @@ impl EnvelopePaths {
     ///
     /// Returns an error if the home directory cannot be determined.
     pub fn new() -> Result<Self, EnvelopeError> {
-        let base_dir = if let Ok(custom) = std::env::var("ENVELOPE_CLI_DATA_DIR") {
+        Self::for_profile(None)
+    }
+
+    /// Create an EnvelopePaths instance for a profile
+    ///
+    /// Without an explicit profile, uses `ENVELOPE_PROFILE` or the profile
+    /// saved by `envelope profile switch`, falling back to the default.
+    pub fn for_profile(profile: Option<&str>) -> Result<Self, EnvelopeError> {
+        let root_dir = if let Ok(custom) = std::env::var("ENVELOPE_CLI_DATA_DIR") {
             PathBuf::from(custom)
         } else {
             resolve_default_path()?
         };
 
-        Ok(Self { base_dir })
+        let profile = match profile {
+            Some(name) => name.to_string(),
+            None => std::env::var("ENVELOPE_PROFILE")
+                .ok()
+                .filter(|name| !name.is_empty())
+                .or_else(|| read_active_profile(&root_dir))
+                .unwrap_or_else(|| DEFAULT_PROFILE.to_string()),
+        };
+
+        Self::with_root_dir(root_dir, &profile)
+    }
+
+    /// Create EnvelopePaths for a profile under a custom root directory
+    pub fn with_root_dir(root_dir: PathBuf, profile: &str) -> Result<Self, EnvelopeError> {
+        validate_profile_name(profile)?;
+
+        let base_dir = if profile == DEFAULT_PROFILE {
+            root_dir.clone()
+        } else {
+            root_dir.join("profiles").join(profile)
+        };
+
+        Ok(Self {
+            base_dir,
+            root_dir,
+            profile: profile.to_string(),
+        })
     }
 
     /// Create EnvelopePaths with a custom base directory (useful for testing)
     pub fn with_base_dir(base_dir: PathBuf) -> Self {
-        Self { base_dir }
+        Self {
+            root_dir: base_dir.clone(),
+            base_dir,
+            profile: DEFAULT_PROFILE.to_string(),
+        }
+    }
+
+    /// Get the name of the active profile
+    pub fn profile(&self) -> &str {
+        &self.profile
+    }
+
+    /// Get the root directory shared by all profiles
+    pub fn root_dir(&self) -> &PathBuf {
+        &self.root_dir
+    }
+
+    /// Get the file recording the profile chosen with `envelope profile switch`
+    pub fn active_profile_file(&self) -> PathBuf {
+        self.root_dir.join("active_profile")
+    }
+
+    /// List all profiles, starting with the default profile
+    pub fn list_profiles(&self) -> Result<Vec<String>, EnvelopeError> {
+        let mut profiles = Vec::new();
+
+        let profiles_dir = self.root_dir.join("profiles");
+        if profiles_dir.exists() {
+            let entries = std::fs::read_dir(&profiles_dir)
+                .map_err(|e| EnvelopeError::Io(format!("Failed to read profiles: {}", e)))?;
+            for entry in entries {
+                let entry = entry
+                    .map_err(|e| EnvelopeError::Io(format!("Failed to read profiles: {}", e)))?;
+                if entry.path().is_dir() {
+                    if let Some(name) = entry.file_name().to_str() {
+                        if validate_profile_name(name).is_ok() {
+                            profiles.push(name.to_string());
+                        }
+                    }
+                }
+            }
+        }
+
+        profiles.sort();
+        profiles.insert(0, DEFAULT_PROFILE.to_string());
+        Ok(profiles)
     }
 
     /// Get the base directory (~/.config/envelope-cli/ or equivalent)
@@ -65,6 +161,11 @@ impl EnvelopePaths {
         self.base_dir.join("backups")
     }
 
+    /// Get the reports directory (~/.config/envelope-cli/reports/)
+    pub fn reports_dir(&self) -> PathBuf {
+        self.base_dir.join("reports")
+    }
+
     /// Get the path to the settings file
     pub fn settings_file(&self) -> PathBuf {
         self.base_dir.join("config.json")
@@ -110,6 +211,11 @@ impl EnvelopePaths {
         self.data_dir().join("income.json")
     }
 
+    /// Get the path to scheduled.json (scheduled transactions)
+    pub fn scheduled_file(&self) -> PathBuf {
+        self.data_dir().join("scheduled.json")
+    }
+
     /// Ensure all required directories exist
     ///
     /// Creates:
@@ -135,6 +241,32 @@ impl EnvelopePaths {
     }
 }
 
+/// Check that a profile name is safe to use as a directory name
+pub fn validate_profile_name(name: &str) -> Result<(), EnvelopeError> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+
+    if valid {
+        Ok(())
+    } else {
+        Err(EnvelopeError::Validation(format!(
+            "Invalid profile name '{}'. Use letters, digits, '-' and '_'",
+            name
+        )))
+    }
+}
+
+/// Read the profile saved by `envelope profile switch`, if any
+fn read_active_profile(root_dir: &Path) -> Option<String> {
+    std::fs::read_to_string(root_dir.join("active_profile"))
+        .ok()
+        .map(|name| name.trim().to_string())
+        .filter(|name| !name.is_empty())
+}
+
 /// Resolve the default data directory path based on platform
 #[cfg(not(windows))]
 fn resolve_default_path() -> Result<PathBuf, EnvelopeError> {
@@ -188,6 +320,27 @@ mod tests {
         env::remove_var("ENVELOPE_CLI_DATA_DIR");
     }
 
+    #[test]
+    fn test_profiles() {
+        let temp_dir = TempDir::new().unwrap();
+        let root = temp_dir.path().to_path_buf();
+
+        // The default profile keeps the original layout
+        let default = EnvelopePaths::with_root_dir(root.clone(), DEFAULT_PROFILE).unwrap();
+        assert_eq!(default.base_dir(), &root);
+
+        let business = EnvelopePaths::with_root_dir(root.clone(), "business").unwrap();
+        assert_eq!(business.profile(), "business");
+        assert_eq!(business.data_dir(), root.join("profiles/business/data"));
+        business.ensure_directories().unwrap();
+
+        assert_eq!(
+            default.list_profiles().unwrap(),
+            vec!["default", "business"]
+        );
+        assert!(EnvelopePaths::with_root_dir(root, "../escape").is_err());
+    }
+
     #[test]
     fn test_ensure_directories() {
         let temp_dir = TempDir::new().unwrap();
@@ -6,8 +6,12 @@
 use serde::{Deserialize, Serialize};
 
 use super::paths::EnvelopePaths;
-use crate::crypto::key_derivation::KeyDerivationParams;
+use crate::crypto::key_derivation::{KdfCosts, KeyDerivationParams};
+use crate::display::NegativeStyle;
 use crate::error::EnvelopeError;
+use crate::models::{AccountId, CategoryGroupId, CategoryId, Money, TargetRounding};
+use crate::rates::ExchangeRate;
+use crate::reports::MonthlyReportFormat;
 
 /// Budget period type preference
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
@@ -29,6 +33,9 @@ pub struct BackupRetention {
     pub daily_count: u32,
     /// Number of monthly backups to keep
     pub monthly_count: u32,
+    /// Write backups gzip-compressed (`.json.gz`)
+    #[serde(default)]
+    pub compress: bool,
 }
 
 impl Default for BackupRetention {
@@ -36,6 +43,55 @@ impl Default for BackupRetention {
         Self {
             daily_count: 30,
             monthly_count: 12,
+            compress: false,
+        }
+    }
+}
+
+/// TUI view state restored on the next launch
+///
+/// The sort key, view, and period are kept as text so a value this version
+/// doesn't know falls back to the default instead of failing to load the
+/// settings.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TuiState {
+    /// Register sort key: `date`, `payee`, or `amount`
+    #[serde(default = "default_register_sort")]
+    pub register_sort: String,
+    /// Whether the register sorts latest or largest first
+    #[serde(default = "default_register_sort_descending")]
+    pub register_sort_descending: bool,
+    /// Category groups collapsed in the budget view
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub collapsed_groups: Vec<CategoryGroupId>,
+    /// Last active view: `accounts`, `register`, `budget`, or `reports`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub active_view: Option<String>,
+    /// Account selected in the sidebar
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub selected_account: Option<AccountId>,
+    /// Budget period being viewed, such as `2025-01`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub period: Option<String>,
+}
+
+fn default_register_sort() -> String {
+    "date".to_string()
+}
+
+fn default_register_sort_descending() -> bool {
+    true
+}
+
+impl Default for TuiState {
+    fn default() -> Self {
+        Self {
+            register_sort: default_register_sort(),
+            register_sort_descending: default_register_sort_descending(),
+            collapsed_groups: Vec::new(),
+            active_view: None,
+            selected_account: None,
+            period: None,
         }
     }
 }
@@ -55,6 +111,10 @@ pub struct EncryptionSettings {
     /// (This is a hash of "envelope_verify" encrypted with the key)
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub verification_hash: Option<String>,
+
+    /// Argon2 costs used the next time a key is derived
+    #[serde(default)]
+    pub kdf_costs: KdfCosts,
 }
 
 /// User settings for EnvelopeCLI
@@ -95,6 +155,58 @@ pub struct Settings {
     /// Whether initial setup has been completed
     #[serde(default)]
     pub setup_completed: bool,
+
+    /// Category prefilled on new manual transactions
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_category_id: Option<CategoryId>,
+
+    /// Currency (ISO 4217) that reports convert totals into
+    #[serde(default = "default_base_currency")]
+    pub base_currency: String,
+
+    /// Exchange rates used to convert other currencies
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exchange_rates: Vec<ExchangeRate>,
+
+    /// Largest difference reconciliation may absorb into one transaction
+    #[serde(default = "default_reconcile_max_adjustment")]
+    pub reconcile_max_adjustment: Money,
+
+    /// Also show Available to Budget projected with expected income
+    #[serde(default)]
+    pub show_projected_available: bool,
+
+    /// Ask before deleting, restoring, or other destructive actions
+    #[serde(default = "default_confirm_destructive")]
+    pub confirm_destructive: bool,
+
+    /// How target suggestions round to whole cents
+    #[serde(default)]
+    pub target_rounding: TargetRounding,
+
+    /// Account `transaction add` last used, assumed when none is given
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub last_account_id: Option<AccountId>,
+
+    /// How negative amounts are written in money columns
+    #[serde(default)]
+    pub negative_style: NegativeStyle,
+
+    /// Register sort and collapsed budget groups from the last TUI session
+    #[serde(default)]
+    pub tui_state: TuiState,
+
+    /// IANA timezone that decides today's date, overriding the system's
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timezone: Option<String>,
+
+    /// File format for written monthly summaries
+    #[serde(default)]
+    pub monthly_report_format: MonthlyReportFormat,
+
+    /// Write the just-closed period's summary on launch if it's missing
+    #[serde(default)]
+    pub auto_monthly_report: bool,
 }
 
 fn default_schema_version() -> u32 {
@@ -105,6 +217,18 @@ fn default_currency() -> String {
     "$".to_string()
 }
 
+fn default_base_currency() -> String {
+    "USD".to_string()
+}
+
+fn default_reconcile_max_adjustment() -> Money {
+    Money::from_cents(100)
+}
+
+fn default_confirm_destructive() -> bool {
+    true
+}
+
 fn default_date_format() -> String {
     "%Y-%m-%d".to_string()
 }
@@ -125,6 +249,19 @@ impl Default for Settings {
             date_format: default_date_format(),
             first_day_of_week: default_first_day_of_week(),
             setup_completed: false,
+            default_category_id: None,
+            base_currency: default_base_currency(),
+            exchange_rates: Vec::new(),
+            reconcile_max_adjustment: default_reconcile_max_adjustment(),
+            show_projected_available: false,
+            confirm_destructive: default_confirm_destructive(),
+            target_rounding: TargetRounding::default(),
+            last_account_id: None,
+            negative_style: NegativeStyle::default(),
+            tui_state: TuiState::default(),
+            timezone: None,
+            monthly_report_format: MonthlyReportFormat::default(),
+            auto_monthly_report: false,
         }
     }
 }
@@ -135,6 +272,13 @@ impl Settings {
         self.encryption.enabled || self.encryption_enabled
     }
 
+    /// Check if a destructive action must be confirmed before running
+    ///
+    /// `skip` is an explicit request to proceed, such as a `--yes` flag.
+    pub fn requires_confirmation(&self, skip: bool) -> bool {
+        self.confirm_destructive && !skip
+    }
+
     /// Load settings from disk, or create default settings if file doesn't exist
     pub fn load_or_create(paths: &EnvelopePaths) -> Result<Self, EnvelopeError> {
         let settings_path = paths.settings_file();
@@ -184,6 +328,11 @@ mod tests {
         assert!(!settings.encryption_enabled);
         assert_eq!(settings.backup_retention.daily_count, 30);
         assert_eq!(settings.backup_retention.monthly_count, 12);
+        assert_eq!(
+            settings.monthly_report_format,
+            MonthlyReportFormat::Markdown
+        );
+        assert!(!settings.auto_monthly_report);
     }
 
     #[test]
@@ -194,6 +343,7 @@ mod tests {
         let settings = Settings {
             budget_period_type: BudgetPeriodType::Weekly,
             encryption_enabled: true,
+            monthly_report_format: MonthlyReportFormat::Html,
             ..Default::default()
         };
 
@@ -202,6 +352,7 @@ mod tests {
         let loaded = Settings::load_or_create(&paths).unwrap();
         assert_eq!(loaded.budget_period_type, BudgetPeriodType::Weekly);
         assert!(loaded.encryption_enabled);
+        assert_eq!(loaded.monthly_report_format, MonthlyReportFormat::Html);
     }
 
     #[test]
@@ -211,4 +362,35 @@ mod tests {
         let deserialized: Settings = serde_json::from_str(&json).unwrap();
         assert_eq!(settings.budget_period_type, deserialized.budget_period_type);
     }
+
+    #[test]
+    fn test_requires_confirmation() {
+        let mut settings = Settings::default();
+        assert!(settings.requires_confirmation(false));
+        assert!(!settings.requires_confirmation(true));
+
+        settings.confirm_destructive = false;
+        assert!(!settings.requires_confirmation(false));
+
+        // Settings saved before the option existed keep confirming
+        let legacy: Settings = serde_json::from_str("{}").unwrap();
+        assert!(legacy.confirm_destructive);
+    }
+
+    #[test]
+    fn test_tui_state_defaults() {
+        let legacy: Settings = serde_json::from_str("{}").unwrap();
+        assert_eq!(legacy.tui_state, TuiState::default());
+        assert_eq!(legacy.tui_state.register_sort, "date");
+        assert!(legacy.tui_state.register_sort_descending);
+
+        // Missing fields inside the state fall back individually
+        let partial: Settings =
+            serde_json::from_str(r#"{"tui_state": {"register_sort": "payee"}}"#).unwrap();
+        assert_eq!(partial.tui_state.register_sort, "payee");
+        assert!(partial.tui_state.register_sort_descending);
+        assert!(partial.tui_state.active_view.is_none());
+        assert!(partial.tui_state.selected_account.is_none());
+        assert!(partial.tui_state.period.is_none());
+    }
 }
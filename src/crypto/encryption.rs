@@ -9,6 +9,7 @@ use aes_gcm::{
     Aes256Gcm, Nonce,
 };
 use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
 
 use crate::error::{EnvelopeError, EnvelopeResult};
 
@@ -125,8 +126,11 @@ pub fn encrypt_string(plaintext: &str, key: &DerivedKey) -> EnvelopeResult<Encry
 /// Decrypt to a string
 pub fn decrypt_string(encrypted: &EncryptedData, key: &DerivedKey) -> EnvelopeResult<String> {
     let plaintext = decrypt(encrypted, key)?;
-    String::from_utf8(plaintext)
-        .map_err(|e| EnvelopeError::Encryption(format!("Invalid UTF-8 in decrypted data: {}", e)))
+    String::from_utf8(plaintext).map_err(|e| {
+        let message = format!("Invalid UTF-8 in decrypted data: {}", e.utf8_error());
+        e.into_bytes().zeroize();
+        EnvelopeError::Encryption(message)
+    })
 }
 
 #[cfg(test)]
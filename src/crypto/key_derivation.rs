@@ -8,9 +8,51 @@ use argon2::{
     Argon2, Params,
 };
 use serde::{Deserialize, Serialize};
+use std::time::{Duration, Instant};
+use zeroize::{Zeroize, ZeroizeOnDrop};
 
 use crate::error::{EnvelopeError, EnvelopeResult};
 
+/// Argon2 cost settings chosen by the user
+///
+/// Copied into [`KeyDerivationParams`] with a fresh salt whenever a key is
+/// derived for new data. Existing data keeps the costs stored with its salt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct KdfCosts {
+    /// Memory cost in KiB
+    pub memory_cost: u32,
+    /// Time cost (iterations)
+    pub time_cost: u32,
+    /// Parallelism degree
+    pub parallelism: u32,
+}
+
+impl Default for KdfCosts {
+    fn default() -> Self {
+        Self {
+            memory_cost: 65536, // 64 MiB
+            time_cost: 3,
+            parallelism: 4,
+        }
+    }
+}
+
+impl KdfCosts {
+    /// Smallest memory cost accepted, to keep derivation memory-hard
+    pub const MIN_MEMORY_COST: u32 = 8192; // 8 MiB
+
+    /// Check the costs are ones Argon2 accepts and not uselessly weak
+    pub fn validate(&self) -> EnvelopeResult<()> {
+        if self.memory_cost < Self::MIN_MEMORY_COST {
+            return Err(EnvelopeError::Validation(format!(
+                "Memory cost must be at least {} KiB",
+                Self::MIN_MEMORY_COST
+            )));
+        }
+        argon2_params(self.memory_cost, self.time_cost, self.parallelism).map(|_| ())
+    }
+}
+
 /// Parameters for key derivation
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KeyDerivationParams {
@@ -26,11 +68,12 @@ pub struct KeyDerivationParams {
 
 impl Default for KeyDerivationParams {
     fn default() -> Self {
+        let costs = KdfCosts::default();
         Self {
             salt: String::new(), // Will be generated on first use
-            memory_cost: 65536,  // 64 MiB
-            time_cost: 3,
-            parallelism: 4,
+            memory_cost: costs.memory_cost,
+            time_cost: costs.time_cost,
+            parallelism: costs.parallelism,
         }
     }
 }
@@ -38,10 +81,26 @@ impl Default for KeyDerivationParams {
 impl KeyDerivationParams {
     /// Create new params with a random salt
     pub fn new() -> Self {
+        Self::with_costs(&KdfCosts::default())
+    }
+
+    /// Create params with a random salt and the given costs
+    pub fn with_costs(costs: &KdfCosts) -> Self {
         let salt = SaltString::generate(&mut OsRng);
         Self {
             salt: salt.to_string(),
-            ..Default::default()
+            memory_cost: costs.memory_cost,
+            time_cost: costs.time_cost,
+            parallelism: costs.parallelism,
+        }
+    }
+
+    /// The Argon2 costs these params were created with
+    pub fn costs(&self) -> KdfCosts {
+        KdfCosts {
+            memory_cost: self.memory_cost,
+            time_cost: self.time_cost,
+            parallelism: self.parallelism,
         }
     }
 
@@ -57,6 +116,9 @@ impl KeyDerivationParams {
 }
 
 /// A derived encryption key
+///
+/// The key bytes are zeroized when it is dropped.
+#[derive(Zeroize, ZeroizeOnDrop)]
 pub struct DerivedKey {
     /// The 32-byte key for AES-256
     key: [u8; 32],
@@ -69,13 +131,6 @@ impl DerivedKey {
     }
 }
 
-impl Drop for DerivedKey {
-    fn drop(&mut self) {
-        // Zero out the key when dropped
-        self.key.iter_mut().for_each(|b| *b = 0);
-    }
-}
-
 /// Derive an encryption key from a passphrase
 pub fn derive_key(passphrase: &str, params: &KeyDerivationParams) -> EnvelopeResult<DerivedKey> {
     // Parse the salt
@@ -83,13 +138,7 @@ pub fn derive_key(passphrase: &str, params: &KeyDerivationParams) -> EnvelopeRes
         .map_err(|e| EnvelopeError::Encryption(format!("Invalid salt: {}", e)))?;
 
     // Configure Argon2id with custom params
-    let argon2_params = Params::new(
-        params.memory_cost,
-        params.time_cost,
-        params.parallelism,
-        Some(32), // Output length for AES-256
-    )
-    .map_err(|e| EnvelopeError::Encryption(format!("Invalid Argon2 parameters: {}", e)))?;
+    let argon2_params = argon2_params(params.memory_cost, params.time_cost, params.parallelism)?;
 
     let argon2 = Argon2::new(
         argon2::Algorithm::Argon2id,
@@ -115,10 +164,49 @@ pub fn derive_key(passphrase: &str, params: &KeyDerivationParams) -> EnvelopeRes
         ));
     }
 
-    let mut key = [0u8; 32];
-    key.copy_from_slice(&hash_bytes[..32]);
+    let mut key = DerivedKey { key: [0u8; 32] };
+    key.key.copy_from_slice(&hash_bytes[..32]);
 
-    Ok(DerivedKey { key })
+    Ok(key)
+}
+
+/// Build Argon2 params producing a 32-byte key
+fn argon2_params(memory_cost: u32, time_cost: u32, parallelism: u32) -> EnvelopeResult<Params> {
+    Params::new(
+        memory_cost,
+        time_cost,
+        parallelism,
+        Some(32), // Output length for AES-256
+    )
+    .map_err(|e| EnvelopeError::Encryption(format!("Invalid Argon2 parameters: {}", e)))
+}
+
+/// Suggest costs that take about `target` to derive a key on this machine
+///
+/// Times one pass at the default memory cost, then scales the iterations to
+/// fill `target`. If a single pass is already too slow, memory is halved
+/// (down to [`KdfCosts::MIN_MEMORY_COST`]) until it fits.
+pub fn benchmark(target: Duration) -> EnvelopeResult<KdfCosts> {
+    let mut costs = KdfCosts {
+        time_cost: 1,
+        ..KdfCosts::default()
+    };
+
+    loop {
+        let params = KeyDerivationParams::with_costs(&costs);
+        let start = Instant::now();
+        derive_key("envelope_benchmark", &params)?;
+        let elapsed = start.elapsed().max(Duration::from_millis(1));
+
+        if elapsed > target && costs.memory_cost / 2 >= KdfCosts::MIN_MEMORY_COST {
+            costs.memory_cost /= 2;
+            continue;
+        }
+
+        let passes = target.as_secs_f64() / elapsed.as_secs_f64();
+        costs.time_cost = (passes.floor() as u32).max(1);
+        return Ok(costs);
+    }
 }
 
 #[cfg(test)]
@@ -171,4 +259,41 @@ mod tests {
         // but we've at least attempted to clear it
         let _ = key_ptr; // Suppress unused warning
     }
+
+    #[test]
+    fn test_custom_costs() {
+        let costs = KdfCosts {
+            memory_cost: KdfCosts::MIN_MEMORY_COST,
+            time_cost: 1,
+            parallelism: 1,
+        };
+        assert!(costs.validate().is_ok());
+
+        let params = KeyDerivationParams::with_costs(&costs);
+        assert_eq!(params.costs(), costs);
+
+        // The costs stored with the salt reproduce the same key
+        let stored: KeyDerivationParams =
+            serde_json::from_str(&serde_json::to_string(&params).unwrap()).unwrap();
+        let key1 = derive_key("test_passphrase", &params).unwrap();
+        let key2 = derive_key("test_passphrase", &stored).unwrap();
+        assert_eq!(key1.as_bytes(), key2.as_bytes());
+
+        // Same salt, different costs, different key
+        let mut heavier = params.clone();
+        heavier.time_cost = 2;
+        let key3 = derive_key("test_passphrase", &heavier).unwrap();
+        assert_ne!(key1.as_bytes(), key3.as_bytes());
+
+        let weak = KdfCosts {
+            memory_cost: 1024,
+            ..costs
+        };
+        assert!(weak.validate().is_err());
+        let zero = KdfCosts {
+            time_cost: 0,
+            ..costs
+        };
+        assert!(zero.validate().is_err());
+    }
 }
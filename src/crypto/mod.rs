@@ -8,5 +8,5 @@ pub mod key_derivation;
 pub mod secure_memory;
 
 pub use encryption::{decrypt, decrypt_string, encrypt, encrypt_string, EncryptedData};
-pub use key_derivation::{derive_key, DerivedKey, KeyDerivationParams};
+pub use key_derivation::{benchmark, derive_key, DerivedKey, KdfCosts, KeyDerivationParams};
 pub use secure_memory::SecureString;
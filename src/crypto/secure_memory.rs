@@ -6,6 +6,8 @@
 use std::fmt;
 use std::ops::Deref;
 
+use zeroize::Zeroize;
+
 /// A string type that zeros its contents on drop
 ///
 /// Use this for passphrases and other sensitive string data.
@@ -37,18 +39,8 @@ impl SecureString {
 
 impl Drop for SecureString {
     fn drop(&mut self) {
-        // Zero out the string's memory
-        // SAFETY: We're modifying the bytes in place before the String is dropped
-        // The string might be on the heap, so we need to zero those bytes
-        unsafe {
-            let bytes = self.inner.as_bytes_mut();
-            for byte in bytes.iter_mut() {
-                std::ptr::write_volatile(byte, 0);
-            }
-        }
-        // Clear the string (this might reallocate, but the important thing
-        // is we've zeroed the original memory)
-        self.inner.clear();
+        // Zeroes the whole allocation, including spare capacity
+        self.inner.zeroize();
     }
 }
 
@@ -139,13 +131,8 @@ impl SecureBytes {
 
 impl Drop for SecureBytes {
     fn drop(&mut self) {
-        // Zero out the memory
-        for byte in self.inner.iter_mut() {
-            unsafe {
-                std::ptr::write_volatile(byte, 0);
-            }
-        }
-        self.inner.clear();
+        // Zeroes the whole allocation, including spare capacity
+        self.inner.zeroize();
     }
 }
 
@@ -2,13 +2,25 @@
 //!
 //! Formats accounts for terminal output in table and detail views.
 
-use crate::models::Account;
+use chrono::NaiveDate;
+
+use crate::config::clock;
+use crate::models::{Account, Money};
 use crate::services::account::AccountSummary;
 use tabled::{
     settings::{object::Columns, Alignment, Modify, Style},
     Table, Tabled,
 };
 
+/// How long ago an account was reconciled, e.g. "45 days ago"
+fn reconciled_age(date: NaiveDate, today: NaiveDate) -> String {
+    match (today - date).num_days() {
+        i64::MIN..=0 => "today".to_string(),
+        1 => "1 day ago".to_string(),
+        days => format!("{} days ago", days),
+    }
+}
+
 /// Row for account table display (used in pretty mode)
 #[derive(Tabled)]
 struct AccountRow {
@@ -49,19 +61,18 @@ fn format_account_list_pretty(summaries: &[AccountSummary]) -> String {
             AccountRow {
                 name: summary.account.name.clone(),
                 account_type: summary.account.account_type.to_string(),
-                balance: summary.balance.to_string(),
-                cleared: summary.cleared_balance.to_string(),
+                balance: summary.account.format_amount(summary.balance),
+                cleared: summary.account.format_amount(summary.cleared_balance),
                 status,
             }
         })
         .collect();
 
     // Add total row
-    let total_balance: crate::models::Money = summaries.iter().map(|s| s.balance).sum();
-    let total_cleared: crate::models::Money = summaries.iter().map(|s| s.cleared_balance).sum();
+    let (total_balance, total_cleared) = base_currency_totals(summaries);
 
     rows.push(AccountRow {
-        name: "TOTAL".to_string(),
+        name: total_label(summaries, "TOTAL"),
         account_type: String::new(),
         balance: total_balance.to_string(),
         cleared: total_cleared.to_string(),
@@ -102,27 +113,51 @@ fn format_account_list_plain(summaries: &[AccountSummary]) -> String {
             "{:<26} {:>14} {:>12} {:>12} {:>12}\n",
             truncate_str(&summary.account.name, 26),
             summary.account.account_type.to_string(),
-            summary.balance,
-            summary.cleared_balance,
+            summary.account.format_amount(summary.balance),
+            summary.account.format_amount(summary.cleared_balance),
             status,
         ));
     }
 
     // Total row
-    let total_balance: crate::models::Money = summaries.iter().map(|s| s.balance).sum();
-    let total_cleared: crate::models::Money = summaries.iter().map(|s| s.cleared_balance).sum();
+    let (total_balance, total_cleared) = base_currency_totals(summaries);
 
     output.push('\n');
     output.push_str(&"=".repeat(80));
     output.push('\n');
     output.push_str(&format!(
         "{:<26} {:>14} {:>12} {:>12}\n",
-        "TOTALS:", "", total_balance, total_cleared
+        total_label(summaries, "TOTALS:"),
+        "",
+        total_balance,
+        total_cleared
     ));
 
     output
 }
 
+/// Balance and cleared totals over the accounts in the base currency
+///
+/// Other currencies can't be added without a rate; `report networth`
+/// converts them.
+fn base_currency_totals(summaries: &[AccountSummary]) -> (Money, Money) {
+    summaries
+        .iter()
+        .filter(|s| s.account.currency.is_none())
+        .fold((Money::zero(), Money::zero()), |(balance, cleared), s| {
+            (balance + s.balance, cleared + s.cleared_balance)
+        })
+}
+
+/// Label for the total row, noting when other currencies are left out
+fn total_label(summaries: &[AccountSummary], label: &str) -> String {
+    if summaries.iter().any(|s| s.account.currency.is_some()) {
+        format!("{} (base currency)", label.trim_end_matches(':'))
+    } else {
+        label.to_string()
+    }
+}
+
 /// Truncate a string to a maximum length, adding "..." if truncated
 fn truncate_str(s: &str, max_len: usize) -> String {
     if s.len() <= max_len {
@@ -148,7 +183,10 @@ fn get_account_status(summary: &AccountSummary) -> String {
 }
 
 /// Format a single account's details
-pub fn format_account_details(summary: &AccountSummary) -> String {
+///
+/// `history` is the account's recent daily balances, oldest first, shown as
+/// a sparkline when not empty.
+pub fn format_account_details(summary: &AccountSummary, history: &[Money]) -> String {
     let account = &summary.account;
 
     let mut output = String::new();
@@ -156,6 +194,9 @@ pub fn format_account_details(summary: &AccountSummary) -> String {
     output.push_str(&format!("Account: {}\n", account.name));
     output.push_str(&format!("  Type:           {}\n", account.account_type));
     output.push_str(&format!("  ID:             {}\n", account.id));
+    if let Some(currency) = &account.currency {
+        output.push_str(&format!("  Currency:       {}\n", currency));
+    }
     output.push_str(&format!(
         "  On Budget:      {}\n",
         if account.on_budget { "Yes" } else { "No" }
@@ -167,24 +208,41 @@ pub fn format_account_details(summary: &AccountSummary) -> String {
     output.push('\n');
     output.push_str(&format!(
         "  Starting Balance: {}\n",
-        account.starting_balance
+        account.format_amount(account.starting_balance)
+    ));
+    output.push_str(&format!(
+        "  Current Balance:  {}\n",
+        account.format_amount(summary.balance)
     ));
-    output.push_str(&format!("  Current Balance:  {}\n", summary.balance));
     output.push_str(&format!(
         "  Cleared Balance:  {}\n",
-        summary.cleared_balance
+        account.format_amount(summary.cleared_balance)
     ));
     output.push_str(&format!(
         "  Uncleared Count:  {}\n",
         summary.uncleared_count
     ));
 
-    if let Some(date) = account.last_reconciled_date {
-        output.push('\n');
-        output.push_str(&format!("  Last Reconciled:  {}\n", date));
-        if let Some(balance) = account.last_reconciled_balance {
-            output.push_str(&format!("  Reconciled Balance: {}\n", balance));
+    if let (Some(low), Some(high)) = (history.iter().min(), history.iter().max()) {
+        output.push_str(&format!(
+            "  Last {} Days:     {} ({} to {})\n",
+            history.len(),
+            sparkline(history),
+            low,
+            high
+        ));
+    }
+
+    output.push('\n');
+    match account.last_reconciled_date {
+        Some(date) => {
+            let age = reconciled_age(date, clock::today());
+            output.push_str(&format!("  Last Reconciled:  {} ({})\n", date, age));
+            if let Some(balance) = account.last_reconciled_balance {
+                output.push_str(&format!("  Reconciled Balance: {}\n", balance));
+            }
         }
+        None => output.push_str("  Last Reconciled:  Never\n"),
     }
 
     if !account.notes.is_empty() {
@@ -222,10 +280,35 @@ pub fn format_account_list_simple(accounts: &[Account]) -> String {
     output
 }
 
+/// Render balances as a line of Unicode block characters
+///
+/// Each value is scaled between the lowest and highest; a flat series draws
+/// a flat line through the middle.
+fn sparkline(values: &[Money]) -> String {
+    const BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+    let (Some(low), Some(high)) = (values.iter().min(), values.iter().max()) else {
+        return String::new();
+    };
+    let range = (high.cents() - low.cents()) as f64;
+
+    values
+        .iter()
+        .map(|value| {
+            if range == 0.0 {
+                BLOCKS[3]
+            } else {
+                let scaled = (value.cents() - low.cents()) as f64 / range;
+                BLOCKS[(scaled * (BLOCKS.len() - 1) as f64).round() as usize]
+            }
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{AccountType, Money};
+    use crate::models::AccountType;
 
     fn create_test_summary(name: &str, balance: i64, cleared: i64) -> AccountSummary {
         let account =
@@ -235,6 +318,7 @@ mod tests {
             balance: Money::from_cents(balance),
             cleared_balance: Money::from_cents(cleared),
             uncleared_count: if balance != cleared { 1 } else { 0 },
+            transaction_count: 1,
         }
     }
 
@@ -254,6 +338,20 @@ mod tests {
         assert!(output.contains("TOTALS:")); // Matches budget overview style
     }
 
+    #[test]
+    fn test_format_account_list_foreign_currency() {
+        let mut euro = create_test_summary("Euro Savings", 20000, 20000);
+        euro.account.currency = Some("EUR".to_string());
+        let summaries = vec![create_test_summary("Checking", 100000, 95000), euro];
+
+        let output = format_account_list(&summaries, false);
+        assert!(output.contains("€200.00"));
+        // The EUR balance isn't added into the dollar total
+        assert!(output.contains("TOTALS (base currency)"));
+        assert!(output.contains("$1000.00"));
+        assert!(!output.contains("$1200.00"));
+    }
+
     #[test]
     fn test_format_account_list_pretty() {
         let summaries = vec![
@@ -277,11 +375,44 @@ mod tests {
     #[test]
     fn test_format_account_details() {
         let summary = create_test_summary("My Account", 100000, 90000);
-        let output = format_account_details(&summary);
+        let output = format_account_details(&summary, &[]);
 
         assert!(output.contains("My Account"));
         assert!(output.contains("Checking"));
         assert!(output.contains("Current Balance"));
         assert!(output.contains("Cleared Balance"));
+        assert!(!output.contains("Days:"));
+
+        let history = [100, 300, 200].map(Money::from_cents);
+        let output = format_account_details(&summary, &history);
+        assert!(output.contains("Last 3 Days:     ▁█▅ ($1.00 to $3.00)"));
+    }
+
+    #[test]
+    fn test_format_last_reconciled() {
+        clock::set_clock(clock::FixedClock::on(
+            NaiveDate::from_ymd_opt(2025, 3, 17).unwrap(),
+        ));
+
+        let mut summary = create_test_summary("My Account", 100000, 90000);
+        let output = format_account_details(&summary, &[]);
+        assert!(output.contains("Last Reconciled:  Never"));
+
+        summary.account.reconcile(
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+            Money::from_cents(90000),
+        );
+        let output = format_account_details(&summary, &[]);
+        assert!(output.contains("Last Reconciled:  2025-01-31 (45 days ago)"));
+        assert!(output.contains("Reconciled Balance: $900.00"));
+
+        clock::reset_clock();
+    }
+
+    #[test]
+    fn test_sparkline_flat() {
+        let flat = [Money::from_cents(500); 4];
+        assert_eq!(sparkline(&flat), "▄▄▄▄");
+        assert_eq!(sparkline(&[]), "");
     }
 }
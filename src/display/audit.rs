@@ -0,0 +1,102 @@
+//! Audit log formatting for terminal display
+//!
+//! Renders audit entries as before/after diffs with `-`/`+` lines.
+
+use serde_json::{json, Value};
+
+use crate::audit::{detailed_changes, AuditEntry};
+
+/// Format an audit entry with its full field-level diff
+///
+/// Removed or previous values are shown as `-` lines and new values as `+`
+/// lines, colored red and green when `color` is set. Creates show every
+/// field as added and deletes show every field as removed.
+pub fn format_audit_diff(entry: &AuditEntry, color: bool) -> String {
+    let mut output = String::new();
+
+    output.push_str(&format!(
+        "{} {} {} {}",
+        entry.timestamp.format("%Y-%m-%d %H:%M:%S UTC"),
+        entry.operation,
+        entry.entity_type,
+        entry.entity_id
+    ));
+    if let Some(name) = &entry.entity_name {
+        output.push_str(&format!(" ({})", name));
+    }
+    output.push('\n');
+
+    let empty = json!({});
+    let before: &Value = entry.before.as_ref().unwrap_or(&empty);
+    let after: &Value = entry.after.as_ref().unwrap_or(&empty);
+    let changes = detailed_changes(before, after, "");
+
+    if changes.is_empty() {
+        output.push_str("  (no field changes recorded)\n");
+        return output;
+    }
+
+    for change in changes {
+        if let Some(old) = &change.before {
+            output.push_str(&diff_line('-', &change.path, old, color));
+        }
+        if let Some(new) = &change.after {
+            output.push_str(&diff_line('+', &change.path, new, color));
+        }
+    }
+
+    output
+}
+
+/// Format one side of a field change
+fn diff_line(sign: char, path: &str, value: &str, color: bool) -> String {
+    let line = format!("  {} {}: {}", sign, path, value);
+    if !color {
+        return format!("{}\n", line);
+    }
+
+    let code = if sign == '-' { 31 } else { 32 };
+    format!("\x1b[{}m{}\x1b[0m\n", code, line)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audit::EntityType;
+
+    #[test]
+    fn test_format_update_diff() {
+        let entry = AuditEntry::update(
+            EntityType::Account,
+            "acc-12345678",
+            Some("Checking".to_string()),
+            &json!({"name": "Checking", "on_budget": true}),
+            &json!({"name": "Main Checking", "on_budget": true}),
+            None,
+        );
+
+        let output = format_audit_diff(&entry, false);
+        assert!(output.contains("UPDATE Account acc-12345678 (Checking)"));
+        assert!(output.contains("  - name: \"Checking\"\n"));
+        assert!(output.contains("  + name: \"Main Checking\"\n"));
+        assert!(!output.contains("on_budget"));
+
+        let colored = format_audit_diff(&entry, true);
+        assert!(colored.contains("\x1b[31m  - name"));
+        assert!(colored.contains("\x1b[32m  + name"));
+    }
+
+    #[test]
+    fn test_format_create_diff() {
+        let entry = AuditEntry::create(
+            EntityType::Category,
+            "cat-12345678",
+            None,
+            &json!({"name": "Groceries"}),
+        );
+
+        let output = format_audit_diff(&entry, false);
+        assert!(output.contains("  + name: \"Groceries\""));
+        assert!(!output.contains("  - "));
+    }
+}
@@ -6,7 +6,12 @@ use crate::models::{Category, CategoryGroup};
 use crate::services::category::CategoryGroupWithCategories;
 
 /// Format categories as a tree structure grouped by category group
-pub fn format_category_tree(groups_with_categories: &[CategoryGroupWithCategories]) -> String {
+///
+/// Archived categories are marked, and dimmed when `color` is on.
+pub fn format_category_tree(
+    groups_with_categories: &[CategoryGroupWithCategories],
+    color: bool,
+) -> String {
     if groups_with_categories.is_empty() {
         return "No categories found.\n\nRun 'envelope init' to create default categories."
             .to_string();
@@ -32,7 +37,14 @@ pub fn format_category_tree(groups_with_categories: &[CategoryGroupWithCategorie
                     String::new()
                 };
 
-                output.push_str(&format!("  {}{}{}\n", prefix, category.name, goal_str));
+                let line = format!("{}{}", category.name, goal_str);
+                if !category.archived {
+                    output.push_str(&format!("  {}{}\n", prefix, line));
+                } else if color {
+                    output.push_str(&format!("  {}\x1b[2m{} (archived)\x1b[0m\n", prefix, line));
+                } else {
+                    output.push_str(&format!("  {}{} (archived)\n", prefix, line));
+                }
             }
         }
 
@@ -192,7 +204,7 @@ mod tests {
 
     #[test]
     fn test_format_empty_tree() {
-        let output = format_category_tree(&[]);
+        let output = format_category_tree(&[], false);
         assert!(output.contains("No categories found"));
     }
 
@@ -207,7 +219,7 @@ mod tests {
             categories: vec![cat1, cat2],
         };
 
-        let output = format_category_tree(&[gwc]);
+        let output = format_category_tree(&[gwc], false);
         assert!(output.contains("Bills"));
         assert!(output.contains("Rent"));
         assert!(output.contains("Electric"));
@@ -226,9 +238,26 @@ mod tests {
             categories: vec![cat],
         };
 
-        let output = format_category_tree(&[gwc]);
+        let output = format_category_tree(&[gwc], false);
         assert!(output.contains("Emergency Fund"));
         assert!(output.contains("goal:"));
         assert!(output.contains("$1000.00"));
     }
+
+    #[test]
+    fn test_format_archived_category() {
+        let group = CategoryGroup::new("Wants");
+        let mut cat = Category::new("Old Gym", group.id);
+        cat.archive();
+
+        let gwc = CategoryGroupWithCategories {
+            group,
+            categories: vec![cat],
+        };
+
+        let output = format_category_tree(std::slice::from_ref(&gwc), false);
+        assert!(output.contains("Old Gym (archived)"));
+        let colored = format_category_tree(&[gwc], true);
+        assert!(colored.contains("\x1b[2mOld Gym (archived)\x1b[0m"));
+    }
 }
@@ -4,17 +4,19 @@
 //! including tables, colors, and status indicators.
 
 pub mod account;
+pub mod audit;
 pub mod category;
 pub mod report;
 pub mod transaction;
 
 pub use account::{format_account_details, format_account_list};
+pub use audit::format_audit_diff;
 pub use category::{
     format_category_details, format_category_tree, format_group_details, format_group_list,
 };
 pub use report::{
     double_separator, format_bar, format_header, format_money_colored, format_percentage,
-    left_align, right_align, separator, truncate,
+    left_align, right_align, separator, truncate, MoneyColumn, NegativeStyle,
 };
 pub use transaction::{
     format_transaction_details, format_transaction_list_by_account, format_transaction_register,
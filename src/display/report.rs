@@ -2,8 +2,96 @@
 //!
 //! Provides formatting helpers for various report types.
 
+use std::fmt;
+
+use serde::{Deserialize, Serialize};
+
 use crate::models::Money;
 
+/// How negative amounts are written in money columns
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum NegativeStyle {
+    /// A leading minus: `-$5.00`
+    #[default]
+    Minus,
+    /// Accounting style: `($5.00)`
+    Parentheses,
+}
+
+impl NegativeStyle {
+    /// Parse a style name: minus or parentheses
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "minus" => Some(Self::Minus),
+            "parentheses" | "parens" => Some(Self::Parentheses),
+            _ => None,
+        }
+    }
+
+    /// Write an amount in this style
+    ///
+    /// With parentheses, other amounts get a trailing space so decimal
+    /// points still line up with the closing `)`.
+    pub fn format(self, amount: Money) -> String {
+        match self {
+            Self::Minus => amount.to_string(),
+            Self::Parentheses if amount.is_negative() => format!("({})", -amount),
+            Self::Parentheses => format!("{} ", amount),
+        }
+    }
+}
+
+impl fmt::Display for NegativeStyle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Minus => write!(f, "minus"),
+            Self::Parentheses => write!(f, "parentheses"),
+        }
+    }
+}
+
+/// A column of money amounts, right-aligned to the widest one
+///
+/// Measure the whole set first with [`MoneyColumn::new`], then format each
+/// row. Amounts always have two decimals, so right alignment also lines up
+/// the decimal points.
+#[derive(Debug, Clone, Copy)]
+pub struct MoneyColumn {
+    width: usize,
+    style: NegativeStyle,
+}
+
+impl MoneyColumn {
+    /// Measure `amounts`, keeping the column at least `min_width` wide
+    pub fn new(
+        amounts: impl IntoIterator<Item = Money>,
+        min_width: usize,
+        style: NegativeStyle,
+    ) -> Self {
+        let width = amounts
+            .into_iter()
+            .map(|amount| style.format(amount).chars().count())
+            .fold(min_width, usize::max);
+        Self { width, style }
+    }
+
+    /// Width of the column in characters
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Format an amount padded to the column width
+    pub fn format(&self, amount: Money) -> String {
+        self.pad(&self.style.format(amount))
+    }
+
+    /// Right-align other text, such as a header or a blank cell
+    pub fn pad(&self, text: &str) -> String {
+        format!("{:>width$}", text, width = self.width)
+    }
+}
+
 /// Format a money amount with color hints for terminal display
 pub fn format_money_colored(amount: Money) -> String {
     if amount.is_negative() {
@@ -116,4 +204,27 @@ mod tests {
         assert_eq!(right_align("abc", 5), "  abc");
         assert_eq!(left_align("abc", 5), "abc  ");
     }
+
+    #[test]
+    fn test_money_column() {
+        let amounts = [
+            Money::from_cents(500),
+            Money::from_cents(-123456789),
+            Money::zero(),
+        ];
+
+        let column = MoneyColumn::new(amounts, 8, NegativeStyle::Minus);
+        assert_eq!(column.width(), 12);
+        assert_eq!(column.format(amounts[0]), "       $5.00");
+        assert_eq!(column.format(amounts[1]), "-$1234567.89");
+        assert_eq!(column.pad("Amount"), "      Amount");
+
+        // Short amounts keep the minimum width
+        let column = MoneyColumn::new([Money::from_cents(500)], 8, NegativeStyle::Minus);
+        assert_eq!(column.format(Money::from_cents(500)), "   $5.00");
+
+        let column = MoneyColumn::new(amounts, 0, NegativeStyle::Parentheses);
+        assert_eq!(column.format(amounts[1]), "($1234567.89)");
+        assert_eq!(column.format(amounts[0]), "       $5.00 ");
+    }
 }
@@ -3,10 +3,24 @@
 //! Provides utilities for formatting transactions for terminal display,
 //! including register views and status indicators.
 
-use crate::models::{Transaction, TransactionStatus};
+use std::collections::HashMap;
+
+use super::report::{MoneyColumn, NegativeStyle};
+use crate::models::{AccountId, CategoryId, Money, Transaction, TransactionId, TransactionStatus};
+
+/// Narrowest an amount column gets, so short amounts keep the usual layout
+const AMOUNT_WIDTH: usize = 12;
 
 /// Format a single transaction for display (register row)
 pub fn format_transaction_row(txn: &Transaction) -> String {
+    let column = MoneyColumn::new([txn.amount], AMOUNT_WIDTH, NegativeStyle::Minus);
+    register_row(txn, &column, None)
+}
+
+/// Format a register row with its amount in `column`
+///
+/// `balance` is an already padded balance cell placed after the amount.
+fn register_row(txn: &Transaction, column: &MoneyColumn, balance: Option<&str>) -> String {
     let status_icon = match txn.status {
         TransactionStatus::Pending => " ",
         TransactionStatus::Cleared => "✓",
@@ -26,40 +40,142 @@ pub fn format_transaction_row(txn: &Transaction) -> String {
         format!("{}{}", transfer_indicator, txn.payee_name)
     };
 
+    let balance = balance.map(|b| format!(" {}", b)).unwrap_or_default();
+
     format!(
-        "{} {} {:20} {:>12}{}",
+        "{}{} {} {:20} {}{}{}",
         status_icon,
+        txn.flag.marker(),
         txn.date.format("%Y-%m-%d"),
         truncate(&payee_display, 20),
-        txn.amount,
+        column.format(txn.amount),
+        balance,
         split_indicator
     )
 }
 
 /// Format a list of transactions as a register
-pub fn format_transaction_register(transactions: &[Transaction]) -> String {
+///
+/// The amount column widens to fit the largest amount in the list. With
+/// `balances`, a Balance column shows each transaction's account balance
+/// after it; those are computed in date order beforehand (see
+/// `AccountRegisterReport::running_balances`), so the rows can be listed in
+/// any order.
+pub fn format_transaction_register(
+    transactions: &[Transaction],
+    balances: Option<&HashMap<TransactionId, Money>>,
+    style: NegativeStyle,
+) -> String {
     if transactions.is_empty() {
         return "No transactions found.\n".to_string();
     }
 
+    let column = MoneyColumn::new(transactions.iter().map(|t| t.amount), AMOUNT_WIDTH, style);
+    let balance_column = balances.map(|balances| {
+        let shown = transactions
+            .iter()
+            .filter_map(|t| balances.get(&t.id).copied());
+        (balances, MoneyColumn::new(shown, AMOUNT_WIDTH, style))
+    });
+
+    let mut output = String::new();
+    let mut header = format!(
+        "{:3} {:10} {:20} {}",
+        "St",
+        "Date",
+        "Payee",
+        column.pad("Amount")
+    );
+    let mut rule_width = 38 + column.width();
+    if let Some((_, balance_column)) = &balance_column {
+        header.push_str(&format!(" {}", balance_column.pad("Balance")));
+        rule_width += 1 + balance_column.width();
+    }
+    output.push_str(&header);
+    output.push('\n');
+    output.push_str(&"-".repeat(rule_width));
+    output.push('\n');
+
+    for txn in transactions {
+        let balance =
+            balance_column
+                .as_ref()
+                .map(|(balances, balance_column)| match balances.get(&txn.id) {
+                    Some(balance) => balance_column.format(*balance),
+                    None => balance_column.pad(""),
+                });
+        output.push_str(&register_row(txn, &column, balance.as_deref()));
+        output.push('\n');
+    }
+
+    output
+}
+
+/// Format a register spanning several accounts, with an account column
+pub fn format_transaction_register_with_accounts(
+    transactions: &[Transaction],
+    account_names: &HashMap<AccountId, String>,
+    style: NegativeStyle,
+) -> String {
+    if transactions.is_empty() {
+        return "No transactions found.\n".to_string();
+    }
+
+    let column = MoneyColumn::new(transactions.iter().map(|t| t.amount), AMOUNT_WIDTH, style);
+
     let mut output = String::new();
     output.push_str(&format!(
-        "{:3} {:10} {:20} {:>12}\n",
-        "St", "Date", "Payee", "Amount"
+        "{:3} {:10} {:15} {:20} {}\n",
+        "St",
+        "Date",
+        "Account",
+        "Payee",
+        column.pad("Amount")
     ));
-    output.push_str(&"-".repeat(50));
+    output.push_str(&"-".repeat(54 + column.width()));
     output.push('\n');
 
     for txn in transactions {
-        output.push_str(&format_transaction_row(txn));
-        output.push('\n');
+        let status_icon = match txn.status {
+            TransactionStatus::Pending => " ",
+            TransactionStatus::Cleared => "✓",
+            TransactionStatus::Reconciled => "🔒",
+        };
+        let markers = format!("{}{}", status_icon, txn.flag.marker());
+
+        let account_name = account_names
+            .get(&txn.account_id)
+            .map(|s| s.as_str())
+            .unwrap_or("Unknown");
+
+        let payee_display = if txn.payee_name.is_empty() {
+            "(no payee)"
+        } else {
+            &txn.payee_name
+        };
+
+        output.push_str(&format!(
+            "{:3} {} {} {} {}\n",
+            markers,
+            txn.date.format("%Y-%m-%d"),
+            truncate(account_name, 15),
+            truncate(payee_display, 20),
+            column.format(txn.amount)
+        ));
     }
 
     output
 }
 
 /// Format transaction details for display
-pub fn format_transaction_details(txn: &Transaction, category_name: Option<&str>) -> String {
+///
+/// Splits show their category name from `split_categories`, falling back to
+/// the category ID when it isn't listed.
+pub fn format_transaction_details(
+    txn: &Transaction,
+    category_name: Option<&str>,
+    split_categories: &HashMap<CategoryId, String>,
+) -> String {
     let mut output = String::new();
 
     output.push_str(&format!("Transaction: {}\n", txn.id));
@@ -77,6 +193,8 @@ pub fn format_transaction_details(txn: &Transaction, category_name: Option<&str>
             "Category:    Split ({} categories)\n",
             txn.splits.len()
         ));
+    } else if txn.is_starting_balance {
+        output.push_str("Category:    Available to Budget (starting balance)\n");
     } else {
         output.push_str("Category:    (uncategorized)\n");
     }
@@ -85,10 +203,27 @@ pub fn format_transaction_details(txn: &Transaction, category_name: Option<&str>
         output.push_str(&format!("Memo:        {}\n", txn.memo));
     }
 
+    if !txn.tags.is_empty() {
+        let tags: Vec<String> = txn.tags.iter().map(|t| format!("#{}", t)).collect();
+        output.push_str(&format!("Tags:        {}\n", tags.join(" ")));
+    }
+
+    if let Some(source) = &txn.income_source {
+        output.push_str(&format!("Income:      {}\n", source));
+    }
+
     output.push_str(&format!("Status:      {}\n", txn.status));
 
-    if txn.is_transfer() {
-        output.push_str("Type:        Transfer\n");
+    if !txn.flag.is_none() {
+        output.push_str(&format!("Flag:        {}\n", txn.flag));
+    }
+
+    if let Some(linked) = txn.transfer_transaction_id {
+        output.push_str(&format!("Type:        Transfer (linked to {})\n", linked));
+    }
+
+    if let Some(import_id) = &txn.import_id {
+        output.push_str(&format!("Import ID:   {}\n", import_id));
     }
 
     if txn.is_split() {
@@ -99,11 +234,19 @@ pub fn format_transaction_details(txn: &Transaction, category_name: Option<&str>
             } else {
                 format!(" - {}", split.memo)
             };
+            let target = match (split.category_id, split.transfer_account_id) {
+                (Some(category_id), _) => split_categories
+                    .get(&category_id)
+                    .cloned()
+                    .unwrap_or_else(|| category_id.to_string()),
+                (None, Some(account_id)) => format!("transfer to account {}", account_id),
+                (None, None) => "nothing".to_string(),
+            };
             output.push_str(&format!(
                 "  {}. {} to {}{}\n",
                 i + 1,
                 split.amount,
-                split.category_id,
+                target,
                 memo_part
             ));
         }
@@ -116,27 +259,52 @@ pub fn format_transaction_details(txn: &Transaction, category_name: Option<&str>
 pub fn format_transaction_list_by_account(
     transactions: &[Transaction],
     account_name: &str,
+    style: NegativeStyle,
 ) -> String {
     let mut output = String::new();
 
     output.push_str(&format!("Account: {}\n", account_name));
     output.push_str(&format!("Transactions: {}\n\n", transactions.len()));
 
+    // Measure both columns first; the balance goes under the inflows
+    let outflows = MoneyColumn::new(
+        transactions
+            .iter()
+            .filter(|t| t.amount.is_negative())
+            .map(|t| -t.amount),
+        AMOUNT_WIDTH,
+        style,
+    );
+    let balance: Money = transactions.iter().map(|t| t.amount).sum();
+    let inflows = MoneyColumn::new(
+        transactions
+            .iter()
+            .filter(|t| !t.amount.is_negative())
+            .map(|t| t.amount)
+            .chain([balance]),
+        AMOUNT_WIDTH,
+        style,
+    );
+    let rule_width = 37 + outflows.width() + inflows.width();
+
     output.push_str(&format!(
-        "{:3} {:10} {:20} {:>12} {:>12}\n",
-        "St", "Date", "Payee", "Outflow", "Inflow"
+        "{:3} {:10} {:20} {} {}\n",
+        "St",
+        "Date",
+        "Payee",
+        outflows.pad("Outflow"),
+        inflows.pad("Inflow")
     ));
-    output.push_str(&"-".repeat(62));
+    output.push_str(&"-".repeat(rule_width));
     output.push('\n');
 
-    let mut running_balance = crate::models::Money::zero();
-
     for txn in transactions {
         let status_icon = match txn.status {
             TransactionStatus::Pending => " ",
             TransactionStatus::Cleared => "✓",
             TransactionStatus::Reconciled => "🔒",
         };
+        let markers = format!("{}{}", status_icon, txn.flag.marker());
 
         let payee_display = if txn.payee_name.is_empty() {
             "(no payee)".to_string()
@@ -145,16 +313,14 @@ pub fn format_transaction_list_by_account(
         };
 
         let (outflow, inflow) = if txn.amount.is_negative() {
-            (format!("{}", -txn.amount), String::new())
+            (outflows.format(-txn.amount), inflows.pad(""))
         } else {
-            (String::new(), format!("{}", txn.amount))
+            (outflows.pad(""), inflows.format(txn.amount))
         };
 
-        running_balance += txn.amount;
-
         output.push_str(&format!(
-            "{:3} {} {:20} {:>12} {:>12}\n",
-            status_icon,
+            "{:3} {} {:20} {} {}\n",
+            markers,
             txn.date.format("%Y-%m-%d"),
             truncate(&payee_display, 20),
             outflow,
@@ -162,9 +328,14 @@ pub fn format_transaction_list_by_account(
         ));
     }
 
-    output.push_str(&"-".repeat(62));
+    output.push_str(&"-".repeat(rule_width));
     output.push('\n');
-    output.push_str(&format!("{:>50} {:>12}\n", "Balance:", running_balance));
+    output.push_str(&format!(
+        "{:>width$} {}\n",
+        "Balance:",
+        inflows.format(balance),
+        width = rule_width - inflows.width() - 1
+    ));
 
     output
 }
@@ -204,7 +375,7 @@ fn truncate(s: &str, max_len: usize) -> String {
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::models::{AccountId, Money};
+    use crate::models::{Money, Split, TransactionFlag};
     use chrono::NaiveDate;
 
     #[test]
@@ -224,12 +395,58 @@ mod tests {
         assert!(formatted.contains("-$50.00"));
     }
 
+    #[test]
+    fn test_format_flagged_row() {
+        let mut txn = Transaction::new(
+            AccountId::new(),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            Money::from_cents(-5000),
+        );
+        assert!(format_transaction_row(&txn).starts_with("   2025-01-15"));
+
+        txn.flag = TransactionFlag::Review;
+        assert!(format_transaction_row(&txn).starts_with(" ⚑ 2025-01-15"));
+    }
+
     #[test]
     fn test_format_empty_register() {
-        let formatted = format_transaction_register(&[]);
+        let formatted = format_transaction_register(&[], None, NegativeStyle::Minus);
         assert!(formatted.contains("No transactions found"));
     }
 
+    #[test]
+    fn test_format_register_with_balances() {
+        let account_id = AccountId::new();
+        let older = Transaction::new(
+            account_id,
+            NaiveDate::from_ymd_opt(2025, 1, 10).unwrap(),
+            Money::from_cents(10000),
+        );
+        let newer = Transaction::new(
+            account_id,
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            Money::from_cents(-2500),
+        );
+
+        let mut balances = HashMap::new();
+        balances.insert(older.id, Money::from_cents(10000));
+        balances.insert(newer.id, Money::from_cents(7500));
+
+        // Newest first, as lists are shown
+        let formatted = format_transaction_register(
+            &[newer, older.clone()],
+            Some(&balances),
+            NegativeStyle::Minus,
+        );
+        let lines: Vec<&str> = formatted.lines().collect();
+        assert!(lines[0].ends_with("Balance"));
+        assert!(lines[2].ends_with("-$25.00       $75.00"));
+        assert!(lines[3].ends_with("$100.00      $100.00"));
+
+        let plain = format_transaction_register(&[older], None, NegativeStyle::Minus);
+        assert!(!plain.contains("Balance"));
+    }
+
     #[test]
     fn test_format_transaction_details() {
         let txn = Transaction::with_details(
@@ -241,12 +458,94 @@ mod tests {
             "Test memo",
         );
 
-        let formatted = format_transaction_details(&txn, Some("Groceries"));
+        let formatted = format_transaction_details(&txn, Some("Groceries"), &HashMap::new());
         assert!(formatted.contains("Test Store"));
         assert!(formatted.contains("Groceries"));
         assert!(formatted.contains("Test memo"));
     }
 
+    #[test]
+    fn test_format_split_details() {
+        let mut txn = Transaction::new(
+            AccountId::new(),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            Money::from_cents(-8000),
+        );
+        let groceries = CategoryId::new();
+        txn.add_split(Split::new(groceries, Money::from_cents(-5000)));
+        txn.add_split(Split::new(CategoryId::new(), Money::from_cents(-3000)));
+        txn.import_id = Some("bank-123".to_string());
+
+        let mut names = HashMap::new();
+        names.insert(groceries, "Groceries".to_string());
+
+        let formatted = format_transaction_details(&txn, None, &names);
+        assert!(formatted.contains("1. -$50.00 to Groceries"));
+        assert!(formatted.contains("2. -$30.00 to cat-"));
+        assert!(formatted.contains("Import ID:   bank-123"));
+    }
+
+    #[test]
+    fn test_format_register_with_accounts() {
+        let account_id = AccountId::new();
+        let txn = Transaction::with_details(
+            account_id,
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            Money::from_cents(-5000),
+            "Test Store",
+            None,
+            "",
+        );
+
+        let mut names = HashMap::new();
+        names.insert(account_id, "Checking".to_string());
+
+        let formatted =
+            format_transaction_register_with_accounts(&[txn], &names, NegativeStyle::Minus);
+        assert!(formatted.contains("Account"));
+        assert!(formatted.contains("Checking"));
+        assert!(formatted.contains("Test Store"));
+    }
+
+    #[test]
+    fn test_list_by_account_alignment() {
+        let account_id = AccountId::new();
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let transactions = vec![
+            Transaction::with_details(
+                account_id,
+                date,
+                Money::from_cents(123456789),
+                "Windfall",
+                None,
+                "",
+            ),
+            Transaction::with_details(
+                account_id,
+                date,
+                Money::from_cents(-500),
+                "Coffee",
+                None,
+                "",
+            ),
+        ];
+
+        let formatted =
+            format_transaction_list_by_account(&transactions, "Checking", NegativeStyle::Minus);
+        let lines: Vec<&str> = formatted.lines().collect();
+        let rows: Vec<&&str> = lines
+            .iter()
+            .filter(|l| l.contains("Windfall") || l.contains("Coffee") || l.contains("Balance:"))
+            .collect();
+
+        // Every amount ends in the same column, however long it is
+        let widths: Vec<usize> = rows.iter().map(|l| l.trim_end().chars().count()).collect();
+        assert_eq!(rows.len(), 3);
+        assert!(rows[0].ends_with("$1234567.89"));
+        assert!(rows[2].ends_with("$1234562.89"));
+        assert_eq!(widths[0], widths[2]);
+    }
+
     #[test]
     fn test_truncate() {
         assert_eq!(truncate("Short", 10).trim(), "Short");
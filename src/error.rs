@@ -3,6 +3,7 @@
 //! This module defines the error hierarchy for the application using thiserror
 //! for ergonomic error definitions.
 
+use std::path::{Path, PathBuf};
 use thiserror::Error;
 
 /// The main error type for EnvelopeCLI operations
@@ -81,6 +82,22 @@ pub enum EnvelopeError {
     /// Income expectation errors
     #[error("Income error: {0}")]
     Income(String),
+
+    /// No exchange rate is known for a currency pair
+    #[error("No exchange rate from {from} to {to}")]
+    MissingExchangeRate { from: String, to: String },
+
+    /// A write was attempted while the budget is open read-only
+    #[error("Read-only mode: nothing was changed")]
+    ReadOnly,
+
+    /// A data file exists but could not be parsed
+    #[error("Corrupted data file {} at byte {offset}: {message}", .path.display())]
+    Corrupted {
+        path: PathBuf,
+        offset: usize,
+        message: String,
+    },
 }
 
 impl EnvelopeError {
@@ -116,6 +133,14 @@ impl EnvelopeError {
         }
     }
 
+    /// Get the corrupted data file, if this is a corruption error
+    pub fn corrupted_path(&self) -> Option<&Path> {
+        match self {
+            Self::Corrupted { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+
     /// Check if this is a "not found" error
     pub fn is_not_found(&self) -> bool {
         matches!(self, Self::NotFound { .. })
@@ -179,6 +204,20 @@ impl EnvelopeError {
             Self::Storage(msg) => format!("Storage error: {}", msg),
             Self::Tui(msg) => format!("Display error: {}", msg),
             Self::Income(msg) => msg.clone(),
+            Self::MissingExchangeRate { from, to } => {
+                format!("No exchange rate from {} to {} is configured", from, to)
+            }
+            Self::Corrupted {
+                path,
+                offset,
+                message,
+            } => format!(
+                "Data file {} is corrupted at byte {}: {}",
+                path.display(),
+                offset,
+                message
+            ),
+            Self::ReadOnly => "Opened read-only, so nothing was changed".to_string(),
         }
     }
 
@@ -245,6 +284,15 @@ impl EnvelopeError {
                 "Check the expected income amount is positive",
                 "Run 'envelope income show' to see current income expectations",
             ],
+            Self::MissingExchangeRate { .. } => vec![
+                "Add a rate with 'envelope config --rate FROM/TO=RATE'",
+                "Run 'envelope config' to see the configured rates",
+            ],
+            Self::Corrupted { .. } => vec![
+                "Restore the file from the latest backup: 'envelope doctor --repair'",
+                "Restore everything from a backup: 'envelope backup restore'",
+            ],
+            Self::ReadOnly => vec!["Run the command again without --read-only"],
         }
     }
 
@@ -267,6 +315,9 @@ impl EnvelopeError {
             Self::Storage(_) => 14,
             Self::Tui(_) => 15,
             Self::Income(_) => 16,
+            Self::Corrupted { .. } => 17,
+            Self::MissingExchangeRate { .. } => 18,
+            Self::ReadOnly => 19,
         }
     }
 }
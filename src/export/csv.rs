@@ -3,13 +3,49 @@
 //! Exports transactions, budget allocations, and account data to CSV format.
 
 use crate::error::EnvelopeResult;
-use crate::models::{BudgetPeriod, TransactionStatus};
+use crate::models::{BudgetPeriod, CategoryBudgetSummary, TransactionStatus};
 use crate::services::{AccountService, BudgetService, CategoryService};
 use crate::storage::Storage;
 use std::io::Write;
 
+/// How split transactions are written to CSV
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SplitRows {
+    /// One row per split with its own category and amount, and no parent row
+    Flatten,
+    /// The transaction row followed by one row per split
+    Child,
+    /// The transaction row only
+    ParentOnly,
+}
+
 /// Export all transactions to CSV
+///
+/// Split transactions get their transaction row followed by one row per
+/// split. Use [`export_transactions_csv_with_splits`] for a file that can be
+/// imported again without double counting.
 pub fn export_transactions_csv<W: Write>(storage: &Storage, writer: &mut W) -> EnvelopeResult<()> {
+    write_transactions_csv(storage, writer, None)
+}
+
+/// Export all transactions to CSV, choosing how splits are written
+///
+/// Adds a `Split Of` column holding the parent transaction ID on split rows,
+/// which import uses to rebuild the splits.
+pub fn export_transactions_csv_with_splits<W: Write>(
+    storage: &Storage,
+    writer: &mut W,
+    splits: SplitRows,
+) -> EnvelopeResult<()> {
+    write_transactions_csv(storage, writer, Some(splits))
+}
+
+/// Write the transactions CSV; `None` is the original format without `Split Of`
+fn write_transactions_csv<W: Write>(
+    storage: &Storage,
+    writer: &mut W,
+    splits: Option<SplitRows>,
+) -> EnvelopeResult<()> {
     let category_service = CategoryService::new(storage);
     let account_service = AccountService::new(storage);
 
@@ -23,9 +59,11 @@ pub fn export_transactions_csv<W: Write>(storage: &Storage, writer: &mut W) -> E
         accounts.iter().map(|a| (a.id, a.name.clone())).collect();
 
     // Write header
+    let split_of_header = if splits.is_some() { ",Split Of" } else { "" };
     writeln!(
         writer,
-        "ID,Date,Account,Payee,Category,Memo,Amount,Status,Is Split,Is Transfer"
+        "ID,Date,Account,Payee,Category,Memo,Amount,Status,Is Split,Is Transfer{}",
+        split_of_header
     )
     .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
 
@@ -57,41 +95,60 @@ pub fn export_transactions_csv<W: Write>(storage: &Storage, writer: &mut W) -> E
             TransactionStatus::Reconciled => "Reconciled",
         };
 
-        writeln!(
-            writer,
-            "{},{},{},{},{},{},{:.2},{},{},{}",
-            txn.id,
-            txn.date,
-            escape_csv(&account_name),
-            escape_csv(&txn.payee_name),
-            escape_csv(&category_name),
-            escape_csv(&txn.memo),
-            txn.amount.cents() as f64 / 100.0,
-            status,
-            txn.is_split(),
-            txn.is_transfer()
-        )
-        .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
+        // Flattened splits stand in for the transaction row
+        let write_parent = !(txn.is_split() && splits == Some(SplitRows::Flatten));
+        if write_parent {
+            writeln!(
+                writer,
+                "{},{},{},{},{},{},{:.2},{},{},{}{}",
+                txn.id,
+                txn.date,
+                escape_csv(&account_name),
+                escape_csv(&txn.payee_name),
+                escape_csv(&category_name),
+                escape_csv(&txn.memo),
+                txn.amount.cents() as f64 / 100.0,
+                status,
+                txn.is_split(),
+                txn.is_transfer(),
+                if splits.is_some() { "," } else { "" }
+            )
+            .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
+        }
 
         // If split transaction, also export split details
-        if txn.is_split() {
-            for split in &txn.splits {
-                let split_cat_name = category_names
-                    .get(&split.category_id)
-                    .cloned()
-                    .unwrap_or_else(|| "Unknown".to_string());
+        if txn.is_split() && splits != Some(SplitRows::ParentOnly) {
+            for (idx, split) in txn.splits.iter().enumerate() {
+                let split_cat_name = match split.category_id {
+                    Some(cat_id) => category_names
+                        .get(&cat_id)
+                        .cloned()
+                        .unwrap_or_else(|| "Unknown".to_string()),
+                    None => "Transfer".to_string(),
+                };
+
+                // The original format reuses one ID for every split
+                let (split_id, split_of) = match splits {
+                    Some(_) => (
+                        format!("{}-split-{}", txn.id, idx + 1),
+                        format!(",{}", txn.id),
+                    ),
+                    None => (format!("{}-split", txn.id), String::new()),
+                };
 
                 writeln!(
                     writer,
-                    "{}-split,{},{},{},{},{},{:.2},{},true,false",
-                    txn.id,
+                    "{},{},{},{},{},{},{:.2},{},true,{}{}",
+                    split_id,
                     txn.date,
                     escape_csv(&account_name),
                     escape_csv(&txn.payee_name),
                     escape_csv(&split_cat_name),
                     escape_csv(&split.memo),
                     split.amount.cents() as f64 / 100.0,
-                    status
+                    status,
+                    split.is_transfer(),
+                    split_of
                 )
                 .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
             }
@@ -142,8 +199,16 @@ pub fn export_allocations_csv<W: Write>(
     };
 
     for period in periods_to_export {
+        let mut summaries: std::collections::HashMap<_, _> = budget_service
+            .get_category_summaries(&period)?
+            .into_iter()
+            .map(|s| (s.category_id, s))
+            .collect();
+
         for category in &categories {
-            let summary = budget_service.get_category_summary(category.id, &period)?;
+            let summary = summaries
+                .remove(&category.id)
+                .unwrap_or_else(|| CategoryBudgetSummary::empty(category.id));
             let group_name = group_names
                 .get(&category.group_id)
                 .cloned()
@@ -257,6 +322,74 @@ mod tests {
         assert!(csv_string.contains("Groceries"));
     }
 
+    #[test]
+    fn test_export_transactions_csv_split_rows() {
+        use crate::models::Split;
+
+        let (_temp_dir, storage) = create_test_storage();
+
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+
+        let group = CategoryGroup::new("Test");
+        storage.categories.upsert_group(group.clone()).unwrap();
+        let groceries = Category::new("Groceries", group.id);
+        let household = Category::new("Household", group.id);
+        storage
+            .categories
+            .upsert_category(groceries.clone())
+            .unwrap();
+        storage
+            .categories
+            .upsert_category(household.clone())
+            .unwrap();
+
+        let mut txn = Transaction::new(
+            account.id,
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            Money::from_cents(-8000),
+        );
+        txn.payee_name = "Market".to_string();
+        txn.add_split(Split::new(groceries.id, Money::from_cents(-5000)));
+        txn.add_split(Split::new(household.id, Money::from_cents(-3000)));
+        let txn_id = txn.id;
+        storage.transactions.upsert(txn).unwrap();
+
+        let export = |mode| {
+            let mut output = Vec::new();
+            export_transactions_csv_with_splits(&storage, &mut output, mode).unwrap();
+            String::from_utf8(output).unwrap()
+        };
+
+        // Flatten: no parent row, splits carry their own amounts
+        let flat = export(SplitRows::Flatten);
+        let lines: Vec<_> = flat.lines().collect();
+        assert!(lines[0].ends_with(",Split Of"));
+        assert_eq!(lines.len(), 3);
+        assert!(lines[1].contains("Groceries,,-50.00"));
+        assert!(lines[1].ends_with(&format!(",{}", txn_id)));
+        assert!(!flat.contains(",Split,"));
+
+        // Child: parent row plus one row per split
+        let child = export(SplitRows::Child);
+        let lines: Vec<_> = child.lines().collect();
+        assert_eq!(lines.len(), 4);
+        assert!(lines[1].contains(",Split,"));
+        assert!(lines[1].ends_with(','));
+        assert!(lines[3].starts_with(&format!("{}-split-2,", txn_id)));
+
+        // Parent only
+        let parent = export(SplitRows::ParentOnly);
+        assert_eq!(parent.lines().count(), 2);
+
+        // The original format is unchanged
+        let mut output = Vec::new();
+        export_transactions_csv(&storage, &mut output).unwrap();
+        let legacy = String::from_utf8(output).unwrap();
+        assert!(!legacy.contains("Split Of"));
+        assert_eq!(legacy.matches(&format!("{}-split,", txn_id)).count(), 2);
+    }
+
     #[test]
     fn test_export_accounts_csv() {
         let (_temp_dir, storage) = create_test_storage();
@@ -0,0 +1,173 @@
+//! Encrypted export functionality
+//!
+//! Wraps a full JSON or YAML export in an AES-256-GCM envelope so it can be
+//! shared or stored without exposing plaintext. The export is serialized and
+//! encrypted entirely in memory; only ciphertext is ever written out.
+
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use zeroize::Zeroizing;
+
+use crate::crypto::{
+    decrypt_string, derive_key, encrypt, EncryptedData, KdfCosts, KeyDerivationParams,
+};
+use crate::error::{EnvelopeError, EnvelopeResult};
+use crate::export::json::{export_full_json, import_from_json, FullExport};
+use crate::export::yaml::{export_full_yaml, import_from_yaml};
+use crate::storage::Storage;
+
+/// Marker identifying an encrypted export file
+pub const ENCRYPTED_EXPORT_MAGIC: &str = "envelope-encrypted-export";
+
+/// Format of the plaintext inside an encrypted export
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportContentFormat {
+    Json,
+    Yaml,
+}
+
+/// An encrypted full-database export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EncryptedExport {
+    /// Always [`ENCRYPTED_EXPORT_MAGIC`]
+    pub magic: String,
+    /// Format of the decrypted payload
+    pub content_format: ExportContentFormat,
+    /// Key derivation parameters (salt and Argon2 costs)
+    pub key_params: KeyDerivationParams,
+    /// The encrypted export
+    pub data: EncryptedData,
+}
+
+impl EncryptedExport {
+    /// Encrypt plaintext export contents with a passphrase
+    ///
+    /// The key is derived with `costs` and a fresh salt; both are stored in
+    /// the export so it opens regardless of later settings.
+    pub fn seal(
+        plaintext: &[u8],
+        content_format: ExportContentFormat,
+        passphrase: &str,
+        costs: &KdfCosts,
+    ) -> EnvelopeResult<Self> {
+        let key_params = KeyDerivationParams::with_costs(costs);
+        let key = derive_key(passphrase, &key_params)?;
+        let data = encrypt(plaintext, &key)?;
+
+        Ok(Self {
+            magic: ENCRYPTED_EXPORT_MAGIC.to_string(),
+            content_format,
+            key_params,
+            data,
+        })
+    }
+
+    /// Decrypt and parse the export
+    pub fn open(&self, passphrase: &str) -> EnvelopeResult<FullExport> {
+        let key = derive_key(passphrase, &self.key_params)?;
+        let plaintext = Zeroizing::new(decrypt_string(&self.data, &key)?);
+
+        match self.content_format {
+            ExportContentFormat::Json => import_from_json(&plaintext),
+            ExportContentFormat::Yaml => import_from_yaml(&plaintext),
+        }
+    }
+
+    /// Parse an encrypted export file's contents
+    pub fn from_json(contents: &str) -> EnvelopeResult<Self> {
+        let export: Self = serde_json::from_str(contents)
+            .map_err(|e| EnvelopeError::Import(format!("Not a valid encrypted export: {}", e)))?;
+
+        if export.magic != ENCRYPTED_EXPORT_MAGIC {
+            return Err(EnvelopeError::Import(
+                "Not a valid encrypted export: missing marker".to_string(),
+            ));
+        }
+
+        Ok(export)
+    }
+}
+
+/// Export the full database encrypted with a passphrase
+///
+/// `costs` are the Argon2 costs for the export's key, normally the
+/// configured `encryption.kdf_costs`.
+pub fn export_encrypted<W: Write>(
+    storage: &Storage,
+    writer: &mut W,
+    content_format: ExportContentFormat,
+    pretty: bool,
+    passphrase: &str,
+    costs: &KdfCosts,
+) -> EnvelopeResult<()> {
+    // Serialize to memory only; the buffer is wiped when dropped
+    let mut plaintext = Zeroizing::new(Vec::new());
+    match content_format {
+        ExportContentFormat::Json => export_full_json(storage, &mut *plaintext, pretty)?,
+        ExportContentFormat::Yaml => export_full_yaml(storage, &mut *plaintext)?,
+    }
+
+    let sealed = EncryptedExport::seal(&plaintext, content_format, passphrase, costs)?;
+
+    serde_json::to_writer_pretty(writer, &sealed)
+        .map_err(|e| EnvelopeError::Export(e.to_string()))?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::paths::EnvelopePaths;
+    use crate::models::{Account, AccountType};
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (TempDir, Storage) {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let mut storage = Storage::new(paths).unwrap();
+        storage.load_all().unwrap();
+        (temp_dir, storage)
+    }
+
+    #[test]
+    fn test_encrypted_roundtrip() {
+        let (_temp_dir, storage) = create_test_storage();
+
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account).unwrap();
+
+        let costs = KdfCosts {
+            memory_cost: KdfCosts::MIN_MEMORY_COST,
+            time_cost: 1,
+            parallelism: 1,
+        };
+        let mut output = Vec::new();
+        export_encrypted(
+            &storage,
+            &mut output,
+            ExportContentFormat::Json,
+            false,
+            "correct horse",
+            &costs,
+        )
+        .unwrap();
+
+        let contents = String::from_utf8(output).unwrap();
+        assert!(!contents.contains("Checking"));
+
+        let sealed = EncryptedExport::from_json(&contents).unwrap();
+        assert_eq!(sealed.key_params.costs(), costs);
+        let export = sealed.open("correct horse").unwrap();
+        assert_eq!(export.accounts.len(), 1);
+        assert_eq!(export.accounts[0].name, "Checking");
+
+        assert!(sealed.open("wrong passphrase").is_err());
+    }
+
+    #[test]
+    fn test_rejects_plain_json() {
+        assert!(EncryptedExport::from_json("{\"accounts\": []}").is_err());
+    }
+}
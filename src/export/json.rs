@@ -2,6 +2,7 @@
 //!
 //! Exports the complete database to JSON format with schema versioning.
 
+use crate::config::clock;
 use crate::error::EnvelopeResult;
 use crate::models::{Account, BudgetAllocation, Category, CategoryGroup, Payee, Transaction};
 use crate::storage::Storage;
@@ -106,7 +107,7 @@ impl FullExport {
 
         Ok(Self {
             schema_version: EXPORT_SCHEMA_VERSION.to_string(),
-            exported_at: Utc::now(),
+            exported_at: clock::now(),
             app_version: env!("CARGO_PKG_VERSION").to_string(),
             accounts,
             category_groups,
@@ -196,6 +197,52 @@ pub fn export_full_json<W: Write>(
     Ok(())
 }
 
+/// First line of a JSON Lines transaction export
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonlHeader {
+    /// Schema version for compatibility checking
+    pub schema_version: String,
+
+    /// Export timestamp
+    pub exported_at: DateTime<Utc>,
+
+    /// Application version that created the export
+    pub app_version: String,
+
+    /// Number of transaction lines that follow
+    pub transaction_count: usize,
+}
+
+/// Export transactions as JSON Lines
+///
+/// Writes a [`JsonlHeader`] line followed by one transaction per line,
+/// oldest first. Transactions are serialized straight from storage, so
+/// memory use doesn't grow with the size of the export.
+pub fn export_transactions_jsonl<W: Write>(
+    storage: &Storage,
+    writer: &mut W,
+) -> EnvelopeResult<()> {
+    let header = JsonlHeader {
+        schema_version: EXPORT_SCHEMA_VERSION.to_string(),
+        exported_at: clock::now(),
+        app_version: env!("CARGO_PKG_VERSION").to_string(),
+        transaction_count: storage.transactions.count()?,
+    };
+    serde_json::to_writer(&mut *writer, &header).map_err(export_error)?;
+    writer.write_all(b"\n").map_err(export_error)?;
+
+    storage.transactions.for_each(|txn| {
+        serde_json::to_writer(&mut *writer, txn).map_err(export_error)?;
+        writer.write_all(b"\n").map_err(export_error)
+    })?;
+
+    writer.flush().map_err(export_error)
+}
+
+fn export_error(e: impl std::fmt::Display) -> crate::error::EnvelopeError {
+    crate::error::EnvelopeError::Export(e.to_string())
+}
+
 /// Import from a JSON export (for verification/restore)
 pub fn import_from_json(json_str: &str) -> EnvelopeResult<FullExport> {
     let export: FullExport = serde_json::from_str(json_str)
@@ -362,7 +409,8 @@ mod tests {
 
         let group = CategoryGroup::new("Test");
         storage.categories.upsert_group(group.clone()).unwrap();
-        let cat = Category::new("Groceries", group.id);
+        let mut cat = Category::new("Groceries", group.id);
+        cat.notes = "Bump this in December".to_string();
         storage.categories.upsert_category(cat.clone()).unwrap();
         storage.categories.save().unwrap();
 
@@ -377,6 +425,7 @@ mod tests {
 
         assert_eq!(imported.accounts.len(), 1);
         assert_eq!(imported.accounts[0].name, "Checking");
+        assert_eq!(imported.categories[0].notes, "Bump this in December");
     }
 
     #[test]
@@ -395,4 +444,36 @@ mod tests {
         assert_eq!(export.metadata.account_count, 3);
         assert_eq!(export.metadata.transaction_count, 0);
     }
+
+    #[test]
+    fn test_transactions_jsonl() {
+        let (_temp_dir, storage) = create_test_storage();
+
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+        for day in [20, 5] {
+            let txn = Transaction::new(
+                account.id,
+                NaiveDate::from_ymd_opt(2025, 1, day).unwrap(),
+                Money::from_cents(-1000),
+            );
+            storage.transactions.upsert(txn).unwrap();
+        }
+
+        let mut output = Vec::new();
+        export_transactions_jsonl(&storage, &mut output).unwrap();
+        let text = String::from_utf8(output).unwrap();
+        let lines: Vec<_> = text.lines().collect();
+        assert_eq!(lines.len(), 3);
+
+        let header: JsonlHeader = serde_json::from_str(lines[0]).unwrap();
+        assert_eq!(header.schema_version, EXPORT_SCHEMA_VERSION);
+        assert_eq!(header.transaction_count, 2);
+
+        // Oldest first
+        let first: Transaction = serde_json::from_str(lines[1]).unwrap();
+        let second: Transaction = serde_json::from_str(lines[2]).unwrap();
+        assert_eq!(first.date, NaiveDate::from_ymd_opt(2025, 1, 5).unwrap());
+        assert_eq!(second.date, NaiveDate::from_ymd_opt(2025, 1, 20).unwrap());
+    }
 }
@@ -3,14 +3,22 @@
 //! Provides complete data export functionality in multiple formats:
 //! - CSV: For transaction and budget data (spreadsheet-compatible)
 //! - JSON: For machine-readable full database export
+//! - JSON Lines: For streaming very large transaction sets
 //! - YAML: For human-readable full database export
+//! - Encrypted: JSON or YAML wrapped in an AES-256-GCM envelope
 
 pub mod csv;
+pub mod encrypted;
 pub mod json;
 pub mod yaml;
 
-pub use csv::{export_accounts_csv, export_allocations_csv, export_transactions_csv};
+pub use csv::{
+    export_accounts_csv, export_allocations_csv, export_transactions_csv,
+    export_transactions_csv_with_splits, SplitRows,
+};
+pub use encrypted::{export_encrypted, EncryptedExport, ExportContentFormat};
 pub use json::{
-    export_full_json, restore_from_export, ExportRestoreResult, FullExport, EXPORT_SCHEMA_VERSION,
+    export_full_json, export_transactions_jsonl, restore_from_export, ExportRestoreResult,
+    FullExport, JsonlHeader, EXPORT_SCHEMA_VERSION,
 };
 pub use yaml::{export_full_yaml, import_from_yaml};
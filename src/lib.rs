@@ -15,6 +15,7 @@
 //! - `services`: Business logic layer
 //! - `audit`: Audit logging system
 //! - `backup`: Automatic backup management
+//! - `rates`: Exchange rates for multi-currency reports
 //!
 //! # Example
 //!
@@ -34,6 +35,7 @@ pub mod display;
 pub mod error;
 pub mod export; // Step 30: Full Data Export
 pub mod models;
+pub mod rates;
 pub mod reports; // Steps 28-29: Reports
 pub mod services;
 pub mod setup; // Step 32: First-Run Setup Wizard
@@ -2,13 +2,18 @@ use anyhow::Result;
 use clap::{Parser, Subcommand};
 
 use envelope_cli::cli::{
-    handle_account_command, handle_backup_command, handle_budget_command, handle_category_command,
-    handle_encrypt_command, handle_export_command, handle_import_command, handle_income_command,
-    handle_payee_command, handle_reconcile_command, handle_report_command, handle_target_command,
-    handle_transaction_command, handle_transfer_command,
+    handle_account_command, handle_add_command, handle_audit_command, handle_backup_command,
+    handle_budget_command, handle_category_command, handle_commands_command, handle_config_command,
+    handle_doctor_command, handle_encrypt_command, handle_encrypted_import, handle_export_command,
+    handle_import_command, handle_income_command, handle_payee_command, handle_profile_command,
+    handle_reconcile_command, handle_report_command, handle_schedule_command,
+    handle_target_command, handle_transaction_command, handle_transfer_command,
+    handle_undo_command, load_storage, unlock_storage, unlock_storage_on_demand,
+    write_missing_monthly_summary, BackupCommands, ConfigOptions, ImportFormat, ImportOptions,
+    OutputFormat,
 };
+use envelope_cli::config::clock::{self, FixedClock, SystemClock};
 use envelope_cli::config::{paths::EnvelopePaths, settings::Settings};
-use envelope_cli::storage::Storage;
 
 #[derive(Parser)]
 #[command(
@@ -21,6 +26,22 @@ use envelope_cli::storage::Storage;
                   control of your finances from the command line."
 )]
 struct Cli {
+    /// Treat this date (YYYY-MM-DD) as today, for backdated sessions
+    #[arg(long, hide = true)]
+    date: Option<chrono::NaiveDate>,
+
+    /// Budget profile to use (defaults to the one chosen with `profile switch`)
+    #[arg(long, global = true, env = "ENVELOPE_PROFILE")]
+    profile: Option<String>,
+
+    /// Open the budget for inspection only; any change is refused
+    #[arg(long, global = true)]
+    read_only: bool,
+
+    /// Print JSON instead of tables, for commands that support it
+    #[arg(long, global = true)]
+    json: bool,
+
     #[command(subcommand)]
     command: Option<Commands>,
 }
@@ -51,10 +72,18 @@ enum Commands {
     #[command(subcommand)]
     Income(envelope_cli::cli::IncomeCommands),
 
+    /// Scheduled (recurring) transaction commands
+    #[command(subcommand)]
+    Schedule(envelope_cli::cli::ScheduleCommands),
+
     /// Backup management commands
     #[command(subcommand)]
     Backup(envelope_cli::cli::BackupCommands),
 
+    /// Profile (separate budget) management commands
+    #[command(subcommand)]
+    Profile(envelope_cli::cli::ProfileCommands),
+
     /// Transaction management commands
     #[command(subcommand, alias = "txn")]
     Transaction(envelope_cli::cli::TransactionCommands),
@@ -75,10 +104,34 @@ enum Commands {
     #[command(subcommand)]
     Export(envelope_cli::cli::ExportCommands),
 
+    /// Audit log commands
+    #[command(subcommand)]
+    Audit(envelope_cli::cli::AuditCommands),
+
     /// Encryption management commands
     #[command(subcommand)]
     Encrypt(envelope_cli::cli::EncryptCommands),
 
+    /// Quickly add a transaction
+    Add {
+        /// Account name or ID
+        account: String,
+        /// Amount (e.g., "-50.00" for outflow, "100.00" for inflow)
+        #[arg(allow_negative_numbers = true)]
+        amount: String,
+        /// Payee name
+        payee: Option<String>,
+        /// Category name
+        #[arg(short, long)]
+        category: Option<String>,
+        /// Memo
+        #[arg(short, long)]
+        memo: Option<String>,
+        /// Transaction date (YYYY-MM-DD), defaults to today
+        #[arg(short, long)]
+        date: Option<String>,
+    },
+
     /// Transfer between accounts
     Transfer {
         /// Source account name
@@ -93,48 +146,183 @@ enum Commands {
         /// Memo
         #[arg(short, long)]
         memo: Option<String>,
+        /// Units of the destination currency per unit of the source currency
+        /// (required between accounts in different currencies)
+        #[arg(long)]
+        rate: Option<f64>,
     },
 
-    /// Import transactions from CSV
+    /// Import transactions from CSV or OFX/QFX (or an encrypted export with --decrypt)
     Import {
-        /// Path to CSV file
+        /// Path to CSV, OFX, or QFX file
         file: String,
-        /// Target account name or ID
+        /// File format (detected from the extension and contents by default)
+        #[arg(long, value_enum, conflicts_with = "decrypt")]
+        format: Option<ImportFormat>,
+        /// Target account name or ID (prompts if omitted or ambiguous)
         #[arg(short, long)]
-        account: String,
+        account: Option<String>,
+        /// Restore an encrypted export created with `export all --encrypt`
+        #[arg(long, conflicts_with = "account")]
+        decrypt: bool,
+        /// Preamble rows to skip before the header, not counting blank lines
+        /// (auto-detected by default)
+        #[arg(long)]
+        skip_rows: Option<usize>,
+        /// Trailing footer rows to skip (e.g., a "Total" line)
+        #[arg(long, default_value = "0")]
+        skip_footer: usize,
+        /// Skip rows whose date cannot be parsed instead of reporting errors
+        #[arg(long)]
+        skip_invalid_dates: bool,
+        /// Character encoding of the CSV file (e.g., latin1, windows-1252)
+        #[arg(long)]
+        encoding: Option<String>,
+        /// Days either side of a row's date to flag same-amount transactions
+        /// as likely duplicates (default 1)
+        #[arg(long, value_name = "DAYS")]
+        duplicate_window: Option<u32>,
+        /// Import likely duplicates instead of skipping them
+        #[arg(long)]
+        import_likely: bool,
+        /// Show what would be imported without importing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Skip the confirmation prompt before restoring with --decrypt
+        #[arg(short, long, alias = "force", short_alias = 'f', requires = "decrypt")]
+        yes: bool,
     },
 
     /// Initialize a new budget
     Init,
 
     /// Show current configuration and paths
-    Config,
+    Config {
+        /// Category prefilled on new transactions ("none" to clear)
+        #[arg(long)]
+        default_category: Option<String>,
+
+        /// Currency that reports convert totals into (e.g., USD)
+        #[arg(long)]
+        base_currency: Option<String>,
+
+        /// Add or replace an exchange rate, as FROM/TO=RATE (repeatable)
+        #[arg(long = "rate", value_name = "FROM/TO=RATE")]
+        rates: Vec<String>,
+
+        /// Remove all saved exchange rates
+        #[arg(long)]
+        clear_rates: bool,
+
+        /// Largest difference reconciliation may absorb into one transaction
+        #[arg(long, value_name = "AMOUNT")]
+        max_reconcile_adjustment: Option<String>,
+
+        /// Also show Available to Budget projected with expected income
+        #[arg(long, value_name = "BOOL")]
+        show_projected: Option<bool>,
+
+        /// Ask before deleting or restoring (false skips prompts; a backup is still made)
+        #[arg(long, value_name = "BOOL")]
+        confirm_destructive: Option<bool>,
+
+        /// Round target suggestions to the nearest cent, up, or down
+        #[arg(long, value_name = "POLICY")]
+        target_rounding: Option<String>,
+
+        /// Write negative amounts in tables with a minus or in parentheses
+        #[arg(long, value_name = "STYLE")]
+        negative_style: Option<String>,
+
+        /// Timezone that decides today's date (e.g., America/New_York, or "local")
+        #[arg(long, value_name = "ZONE")]
+        timezone: Option<String>,
+
+        /// File format for `report monthly --write`: md, html, or json
+        #[arg(long, value_name = "FORMAT")]
+        monthly_report_format: Option<String>,
+
+        /// Write last period's summary automatically on launch if it's missing
+        #[arg(long, value_name = "BOOL")]
+        auto_monthly_report: Option<bool>,
+
+        /// Write backups gzip-compressed (.json.gz) to save space
+        #[arg(long, value_name = "BOOL")]
+        compress_backups: Option<bool>,
+    },
+
+    /// Undo the last destructive change, such as a delete
+    Undo {
+        /// Skip confirmation prompt
+        #[arg(short, long, alias = "force", short_alias = 'f')]
+        yes: bool,
+    },
+
+    /// Check data for integrity problems
+    Doctor {
+        /// Apply safe, unambiguous fixes
+        #[arg(long)]
+        fix: bool,
+
+        /// Restore corrupted data files from the latest backup
+        #[arg(long)]
+        repair: bool,
+    },
+
+    /// List every TUI palette command with its shortcut
+    #[command(name = "commands")]
+    Cheatsheet,
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
 
     // Initialize paths and settings
-    let paths = EnvelopePaths::new()?;
+    let paths = EnvelopePaths::for_profile(cli.profile.as_deref())?;
     let mut settings = Settings::load_or_create(&paths)?;
 
-    // Initialize storage
-    let mut storage = Storage::new(paths.clone())?;
-    storage.load_all()?;
+    if let Some(date) = cli.date {
+        clock::set_clock(FixedClock::on(date));
+    } else if let Some(timezone) = settings.timezone.as_deref().and_then(clock::parse_timezone) {
+        clock::set_clock(SystemClock::in_timezone(timezone));
+    }
+
+    // Initialize storage, recovering corrupted files from backup
+    let repair = matches!(cli.command, Some(Commands::Doctor { repair: true, .. }));
+    let storage = load_storage(&paths, &settings.backup_retention, repair, cli.read_only)?;
 
+    // Encrypt commands ask for the passphrase themselves. The TUI asks up
+    // front, since a prompt can't be shown once it has the screen; everything
+    // else asks only when it touches the audit log.
+    match cli.command {
+        Some(Commands::Encrypt(_)) => {}
+        Some(Commands::Tui) => unlock_storage(&settings, &storage)?,
+        _ => unlock_storage_on_demand(&settings, &storage),
+    }
+
+    // Archive the period that just closed, once; a failure shouldn't block the command
+    if settings.auto_monthly_report && !cli.read_only && paths.is_initialized() {
+        match write_missing_monthly_summary(&storage, &settings) {
+            Ok(Some(path)) => eprintln!("Monthly summary written to: {}", path.display()),
+            Ok(None) => {}
+            Err(e) => eprintln!("Warning: could not write monthly summary: {}", e),
+        }
+    }
+
+    let output = OutputFormat::from_json_flag(cli.json);
     match cli.command {
         Some(Commands::Tui) => {
             // Launch the TUI
             envelope_cli::tui::run_tui(&storage, &settings, &paths)?;
         }
         Some(Commands::Account(cmd)) => {
-            handle_account_command(&storage, cmd)?;
+            handle_account_command(&storage, &settings, cmd, output)?;
         }
         Some(Commands::Category(cmd)) => {
-            handle_category_command(&storage, cmd)?;
+            handle_category_command(&storage, &settings, cmd)?;
         }
         Some(Commands::Budget(cmd)) => {
-            handle_budget_command(&storage, &settings, cmd)?;
+            handle_budget_command(&storage, &settings, cmd, output)?;
         }
         Some(Commands::Target(cmd)) => {
             handle_target_command(&storage, &settings, cmd)?;
@@ -142,40 +330,99 @@ fn main() -> Result<()> {
         Some(Commands::Income(cmd)) => {
             handle_income_command(&storage, &settings, cmd)?;
         }
+        Some(Commands::Schedule(cmd)) => {
+            handle_schedule_command(&storage, cmd)?;
+        }
         Some(Commands::Backup(cmd)) => {
+            if matches!(cmd, BackupCommands::Restore { .. }) {
+                storage.check_writable()?;
+            }
             handle_backup_command(&paths, &settings, cmd)?;
         }
+        Some(Commands::Profile(cmd)) => {
+            handle_profile_command(&paths, cmd)?;
+        }
         Some(Commands::Transaction(cmd)) => {
-            handle_transaction_command(&storage, cmd)?;
+            handle_transaction_command(&storage, &settings, cmd, output)?;
         }
         Some(Commands::Payee(cmd)) => {
-            handle_payee_command(&storage, cmd)?;
+            handle_payee_command(&storage, &settings, cmd)?;
         }
         Some(Commands::Reconcile(cmd)) => {
             handle_reconcile_command(&storage, cmd)?;
         }
         Some(Commands::Report(cmd)) => {
-            handle_report_command(&storage, cmd)?;
+            handle_report_command(&storage, &settings, cmd, output)?;
         }
         Some(Commands::Export(cmd)) => {
-            handle_export_command(&storage, cmd)?;
+            handle_export_command(&storage, &settings, cmd)?;
+        }
+        Some(Commands::Audit(cmd)) => {
+            handle_audit_command(&storage, cmd)?;
         }
         Some(Commands::Encrypt(cmd)) => {
             handle_encrypt_command(&paths, &mut settings, &storage, cmd)?;
         }
+        Some(Commands::Add {
+            account,
+            amount,
+            payee,
+            category,
+            memo,
+            date,
+        }) => {
+            handle_add_command(
+                &storage,
+                &account,
+                &amount,
+                payee,
+                category.as_deref(),
+                memo,
+                date.as_deref(),
+            )?;
+        }
         Some(Commands::Transfer {
             from,
             to,
             amount,
             date,
             memo,
+            rate,
         }) => {
-            handle_transfer_command(&storage, &from, &to, &amount, date.as_deref(), memo)?;
+            handle_transfer_command(&storage, &from, &to, &amount, date.as_deref(), memo, rate)?;
         }
-        Some(Commands::Import { file, account }) => {
-            handle_import_command(&storage, &file, &account)?;
+        Some(Commands::Import {
+            file,
+            format,
+            account,
+            decrypt,
+            skip_rows,
+            skip_footer,
+            skip_invalid_dates,
+            encoding,
+            duplicate_window,
+            import_likely,
+            dry_run,
+            yes,
+        }) => {
+            if decrypt {
+                handle_encrypted_import(&storage, &settings, &file, yes)?;
+            } else {
+                let options = ImportOptions {
+                    format,
+                    skip_rows,
+                    skip_footer,
+                    skip_invalid_dates,
+                    encoding,
+                    duplicate_window,
+                    import_likely,
+                    dry_run,
+                };
+                handle_import_command(&storage, &file, account.as_deref(), &options)?;
+            }
         }
         Some(Commands::Init) => {
+            storage.check_writable()?;
             println!(
                 "Initializing EnvelopeCLI at: {}",
                 paths.data_dir().display()
@@ -192,16 +439,46 @@ fn main() -> Result<()> {
             println!();
             println!("Run 'envelope category list' to see all categories.");
         }
-        Some(Commands::Config) => {
-            println!("EnvelopeCLI Configuration");
-            println!("========================");
-            println!("Config directory: {}", paths.config_dir().display());
-            println!("Data directory:   {}", paths.data_dir().display());
-            println!("Backup directory: {}", paths.backup_dir().display());
-            println!();
-            println!("Settings:");
-            println!("  Budget period type: {:?}", settings.budget_period_type);
-            println!("  Encryption enabled: {}", settings.is_encryption_enabled());
+        Some(Commands::Config {
+            default_category,
+            base_currency,
+            rates,
+            clear_rates,
+            max_reconcile_adjustment,
+            show_projected,
+            confirm_destructive,
+            target_rounding,
+            negative_style,
+            timezone,
+            monthly_report_format,
+            auto_monthly_report,
+            compress_backups,
+        }) => {
+            let options = ConfigOptions {
+                default_category,
+                base_currency,
+                rates,
+                clear_rates,
+                max_reconcile_adjustment,
+                show_projected,
+                confirm_destructive,
+                target_rounding,
+                negative_style,
+                timezone,
+                monthly_report_format,
+                auto_monthly_report,
+                compress_backups,
+            };
+            handle_config_command(&paths, &mut settings, &storage, options)?;
+        }
+        Some(Commands::Undo { yes }) => {
+            handle_undo_command(&storage, &settings, yes)?;
+        }
+        Some(Commands::Doctor { fix, .. }) => {
+            handle_doctor_command(&storage, fix)?;
+        }
+        Some(Commands::Cheatsheet) => {
+            handle_commands_command(output)?;
         }
         None => {
             println!("EnvelopeCLI - Terminal-based zero-based budgeting");
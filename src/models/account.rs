@@ -6,8 +6,12 @@ use chrono::{DateTime, NaiveDate, Utc};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
-use super::ids::AccountId;
+use super::ids::{AccountId, CategoryId};
 use super::money::Money;
+use super::transaction::Transaction;
+
+use crate::config::clock;
+use crate::rates::currency_symbol;
 
 /// Type of financial account
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, Default)]
@@ -30,6 +34,9 @@ pub enum AccountType {
     Other,
 }
 
+/// Asset accounts starting below this (in cents) get a warning
+const OVERDRAWN_WARNING_CENTS: i64 = -100_000;
+
 impl AccountType {
     /// Returns true if this account type typically has a negative balance as normal
     /// (e.g., credit cards show debt as positive spending)
@@ -37,6 +44,20 @@ impl AccountType {
         matches!(self, Self::Credit | Self::LineOfCredit)
     }
 
+    /// Warning for a starting balance that looks wrong for this type
+    ///
+    /// Liabilities may start anywhere. An asset account starting more than
+    /// $1,000 overdrawn usually means a card or loan got the wrong type.
+    pub fn starting_balance_warning(&self, balance: Money) -> Option<String> {
+        if self.is_liability() || balance.cents() >= OVERDRAWN_WARNING_CENTS {
+            return None;
+        }
+        Some(format!(
+            "{} account starting at {} is deeply negative; use type 'credit' or 'line_of_credit' for debt",
+            self, balance
+        ))
+    }
+
     /// Parse account type from string
     pub fn parse(s: &str) -> Option<Self> {
         match s.to_lowercase().as_str() {
@@ -108,12 +129,20 @@ pub struct Account {
     /// Sort order for display
     #[serde(default)]
     pub sort_order: i32,
+
+    /// Category prefilled on new manual transactions (overrides the global default)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_category_id: Option<CategoryId>,
+
+    /// Currency code (ISO 4217); `None` means the base currency
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub currency: Option<String>,
 }
 
 impl Account {
     /// Create a new account with default values
     pub fn new(name: impl Into<String>, account_type: AccountType) -> Self {
-        let now = Utc::now();
+        let now = clock::now();
         Self {
             id: AccountId::new(),
             name: name.into(),
@@ -127,6 +156,8 @@ impl Account {
             created_at: now,
             updated_at: now,
             sort_order: 0,
+            default_category_id: None,
+            currency: None,
         }
     }
 
@@ -144,26 +175,55 @@ impl Account {
     /// Mark this account as archived
     pub fn archive(&mut self) {
         self.archived = true;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Unarchive this account
     pub fn unarchive(&mut self) {
         self.archived = false;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Set whether this account is on-budget
     pub fn set_on_budget(&mut self, on_budget: bool) {
         self.on_budget = on_budget;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Record a reconciliation
     pub fn reconcile(&mut self, date: NaiveDate, balance: Money) {
         self.last_reconciled_date = Some(date);
         self.last_reconciled_balance = Some(balance);
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
+    }
+
+    /// Portion of the starting balance not already carried by a transaction
+    ///
+    /// New accounts record their starting balance as a "Starting Balance"
+    /// transaction; accounts created before that only have this field, so
+    /// balance calculations add it back for them.
+    pub fn untracked_starting_balance(&self, transactions: &[Transaction]) -> Money {
+        if transactions.iter().any(|t| t.is_starting_balance) {
+            Money::zero()
+        } else {
+            self.starting_balance
+        }
+    }
+
+    /// Format an amount in this account's currency
+    ///
+    /// Base-currency accounts use the usual `$` formatting; others use their
+    /// currency's symbol, e.g. `€12.50`.
+    pub fn format_amount(&self, amount: Money) -> String {
+        match &self.currency {
+            Some(code) => amount.format_with_symbol(&currency_symbol(code)),
+            None => amount.to_string(),
+        }
+    }
+
+    /// The account's currency code, falling back to `base` if unset
+    pub fn currency_or<'a>(&'a self, base: &'a str) -> &'a str {
+        self.currency.as_deref().unwrap_or(base)
     }
 
     /// Validate the account
@@ -265,6 +325,29 @@ mod tests {
         assert_eq!(AccountType::parse("invalid"), None);
     }
 
+    #[test]
+    fn test_starting_balance_warning() {
+        let overdrawn = Money::from_cents(-150_000);
+        assert!(AccountType::Checking
+            .starting_balance_warning(overdrawn)
+            .is_some());
+        assert!(AccountType::Credit
+            .starting_balance_warning(overdrawn)
+            .is_none());
+        assert!(AccountType::Checking
+            .starting_balance_warning(Money::from_cents(-5_000))
+            .is_none());
+    }
+
+    #[test]
+    fn test_format_amount_in_currency() {
+        let mut account = Account::new("Savings", AccountType::Savings);
+        assert_eq!(account.format_amount(Money::from_cents(1250)), "$12.50");
+
+        account.currency = Some("EUR".to_string());
+        assert_eq!(account.format_amount(Money::from_cents(-1250)), "-€12.50");
+    }
+
     #[test]
     fn test_is_liability() {
         assert!(AccountType::Credit.is_liability());
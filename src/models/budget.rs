@@ -10,6 +10,8 @@ use super::ids::CategoryId;
 use super::money::Money;
 use super::period::BudgetPeriod;
 
+use crate::config::clock;
+
 /// A budget allocation for a specific category in a specific period
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BudgetAllocation {
@@ -39,7 +41,7 @@ pub struct BudgetAllocation {
 impl BudgetAllocation {
     /// Create a new budget allocation
     pub fn new(category_id: CategoryId, period: BudgetPeriod) -> Self {
-        let now = Utc::now();
+        let now = clock::now();
         Self {
             category_id,
             period,
@@ -61,19 +63,19 @@ impl BudgetAllocation {
     /// Set the budgeted amount
     pub fn set_budgeted(&mut self, amount: Money) {
         self.budgeted = amount;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Add to the budgeted amount
     pub fn add_budgeted(&mut self, amount: Money) {
         self.budgeted += amount;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Set the carryover amount
     pub fn set_carryover(&mut self, amount: Money) {
         self.carryover = amount;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Get the total available in this category (budgeted + carryover)
@@ -9,6 +9,8 @@ use std::fmt;
 
 use super::ids::{CategoryGroupId, CategoryId};
 
+use crate::config::clock;
+
 /// A group of related categories (e.g., "Bills", "Needs", "Wants")
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CategoryGroup {
@@ -35,7 +37,7 @@ pub struct CategoryGroup {
 impl CategoryGroup {
     /// Create a new category group
     pub fn new(name: impl Into<String>) -> Self {
-        let now = Utc::now();
+        let now = clock::now();
         Self {
             id: CategoryGroupId::new(),
             name: name.into(),
@@ -92,6 +94,10 @@ pub struct Category {
     #[serde(default)]
     pub hidden: bool,
 
+    /// Whether this category is archived (kept for history, out of the budget)
+    #[serde(default)]
+    pub archived: bool,
+
     /// Goal amount per period (optional)
     pub goal_amount: Option<i64>,
 
@@ -109,13 +115,14 @@ pub struct Category {
 impl Category {
     /// Create a new category
     pub fn new(name: impl Into<String>, group_id: CategoryGroupId) -> Self {
-        let now = Utc::now();
+        let now = clock::now();
         Self {
             id: CategoryId::new(),
             name: name.into(),
             group_id,
             sort_order: 0,
             hidden: false,
+            archived: false,
             goal_amount: None,
             notes: String::new(),
             created_at: now,
@@ -137,19 +144,31 @@ impl Category {
     /// Set a goal amount
     pub fn set_goal(&mut self, amount: i64) {
         self.goal_amount = Some(amount);
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Clear the goal
     pub fn clear_goal(&mut self) {
         self.goal_amount = None;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
+    }
+
+    /// Archive this category
+    pub fn archive(&mut self) {
+        self.archived = true;
+        self.updated_at = clock::now();
+    }
+
+    /// Unarchive this category
+    pub fn unarchive(&mut self) {
+        self.archived = false;
+        self.updated_at = clock::now();
     }
 
     /// Move to a different group
     pub fn move_to_group(&mut self, group_id: CategoryGroupId) {
         self.group_id = group_id;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Validate the category
@@ -77,6 +77,7 @@ define_id!(CategoryId, "cat-");
 define_id!(CategoryGroupId, "grp-");
 define_id!(PayeeId, "pay-");
 define_id!(IncomeId, "inc-");
+define_id!(ScheduledId, "sch-");
 
 #[cfg(test)]
 mod tests {
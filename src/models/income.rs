@@ -10,6 +10,8 @@ use super::ids::IncomeId;
 use super::money::Money;
 use super::period::BudgetPeriod;
 
+use crate::config::clock;
+
 /// Validation errors for income expectations
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum IncomeValidationError {
@@ -41,7 +43,7 @@ pub struct IncomeExpectation {
 impl IncomeExpectation {
     /// Create a new income expectation
     pub fn new(period: BudgetPeriod, expected_amount: Money) -> Self {
-        let now = Utc::now();
+        let now = clock::now();
         Self {
             id: IncomeId::new(),
             period,
@@ -55,13 +57,13 @@ impl IncomeExpectation {
     /// Set the expected amount
     pub fn set_expected_amount(&mut self, amount: Money) {
         self.expected_amount = amount;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Set notes
     pub fn set_notes(&mut self, notes: impl Into<String>) {
         self.notes = notes.into();
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Validate the income expectation
@@ -11,16 +11,22 @@ pub mod income;
 pub mod money;
 pub mod payee;
 pub mod period;
+pub mod scheduled;
 pub mod target;
 pub mod transaction;
 
 pub use account::{Account, AccountType};
 pub use budget::{BudgetAllocation, CategoryBudgetSummary};
 pub use category::{Category, CategoryGroup, DefaultCategoryGroup};
-pub use ids::{AccountId, CategoryGroupId, CategoryId, IncomeId, PayeeId, TransactionId};
+pub use ids::{
+    AccountId, CategoryGroupId, CategoryId, IncomeId, PayeeId, ScheduledId, TransactionId,
+};
 pub use income::IncomeExpectation;
 pub use money::Money;
 pub use payee::Payee;
 pub use period::BudgetPeriod;
-pub use target::{BudgetTarget, BudgetTargetId, TargetCadence};
-pub use transaction::{Split, Transaction, TransactionStatus};
+pub use scheduled::ScheduledTransaction;
+pub use target::{BudgetTarget, BudgetTargetId, TargetCadence, TargetRounding};
+pub use transaction::{
+    normalize_tag, parse_tags, Split, Transaction, TransactionFlag, TransactionStatus,
+};
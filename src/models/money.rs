@@ -132,6 +132,61 @@ impl Money {
         Ok(Self(if negative { -cents } else { cents }))
     }
 
+    /// Parse an amount that may be a running sum, like "12.50+3.25-8"
+    ///
+    /// Only `+` and `-` between amounts are allowed, and only digits, dots,
+    /// and spaces may appear otherwise. Input that isn't a sum is parsed
+    /// with [`Money::parse`].
+    pub fn parse_expression(s: &str) -> Result<Self, MoneyParseError> {
+        if !Self::is_expression(s) {
+            return Self::parse(s);
+        }
+
+        let invalid = || MoneyParseError::InvalidFormat(s.trim().to_string());
+        if !s
+            .chars()
+            .all(|c| c.is_ascii_digit() || matches!(c, '.' | ' ' | '+' | '-'))
+        {
+            return Err(invalid());
+        }
+
+        let mut total = Self::zero();
+        let mut term = String::new();
+        let mut negative = false;
+        let mut first = true;
+
+        for c in s.trim().chars().chain(std::iter::once('+')) {
+            if c != '+' && c != '-' {
+                term.push(c);
+                continue;
+            }
+
+            if term.trim().is_empty() {
+                // Only a leading minus may appear without an amount before it
+                if c == '-' && first && !negative {
+                    negative = true;
+                    continue;
+                }
+                return Err(invalid());
+            }
+
+            let amount = Self::parse(term.trim()).map_err(|_| invalid())?;
+            total += if negative { -amount } else { amount };
+            term.clear();
+            negative = c == '-';
+            first = false;
+        }
+
+        Ok(total)
+    }
+
+    /// Check if a string is a sum or difference rather than a single amount
+    pub fn is_expression(s: &str) -> bool {
+        let s = s.trim();
+        let s = s.strip_prefix('-').unwrap_or(s);
+        s.contains(['+', '-'])
+    }
+
     /// Format with a currency symbol
     pub fn format_with_symbol(&self, symbol: &str) -> String {
         if self.is_negative() {
@@ -279,6 +334,23 @@ mod tests {
         assert_eq!(Money::parse("0.05").unwrap().cents(), 5);
     }
 
+    #[test]
+    fn test_parse_expression() {
+        assert_eq!(
+            Money::parse_expression("12.50+3.25+8").unwrap().cents(),
+            2375
+        );
+        assert_eq!(Money::parse_expression(" 20 - 4.5 ").unwrap().cents(), 1550);
+        assert_eq!(Money::parse_expression("-5+2").unwrap().cents(), -300);
+        assert_eq!(Money::parse_expression("$10.50").unwrap().cents(), 1050);
+
+        assert!(Money::parse_expression("2*3").is_err());
+        assert!(Money::parse_expression("1++2").is_err());
+        assert!(Money::parse_expression("0+-3").is_err());
+        assert!(Money::parse_expression("5+").is_err());
+        assert!(Money::parse_expression("$1+2").is_err());
+    }
+
     #[test]
     fn test_comparison() {
         let a = Money::from_cents(1000);
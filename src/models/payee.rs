@@ -9,6 +9,8 @@ use std::fmt;
 
 use super::ids::{CategoryId, PayeeId};
 
+use crate::config::clock;
+
 /// A payee with auto-categorization rules
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Payee {
@@ -39,7 +41,7 @@ pub struct Payee {
 impl Payee {
     /// Create a new payee
     pub fn new(name: impl Into<String>) -> Self {
-        let now = Utc::now();
+        let now = clock::now();
         Self {
             id: PayeeId::new(),
             name: name.into(),
@@ -62,7 +64,7 @@ impl Payee {
     /// Record a category usage for learning
     pub fn record_category_usage(&mut self, category_id: CategoryId) {
         *self.category_frequency.entry(category_id).or_insert(0) += 1;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
 
         // Auto-update default category if not manually set
         if !self.manual {
@@ -95,14 +97,14 @@ impl Payee {
     pub fn set_default_category(&mut self, category_id: CategoryId) {
         self.default_category_id = Some(category_id);
         self.manual = true;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Clear the default category
     pub fn clear_default_category(&mut self) {
         self.default_category_id = None;
         self.manual = false;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Validate the payee
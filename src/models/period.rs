@@ -6,6 +6,8 @@ use chrono::{Datelike, Duration, NaiveDate, Weekday};
 use serde::{Deserialize, Serialize};
 use std::fmt;
 
+use crate::config::clock;
+
 /// Represents a budget period
 #[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(tag = "type", content = "value")]
@@ -46,7 +48,7 @@ impl BudgetPeriod {
 
     /// Get the current monthly period
     pub fn current_month() -> Self {
-        let today = chrono::Local::now().date_naive();
+        let today = clock::today();
         Self::Monthly {
             year: today.year(),
             month: today.month(),
@@ -55,7 +57,7 @@ impl BudgetPeriod {
 
     /// Get the current weekly period
     pub fn current_week() -> Self {
-        let today = chrono::Local::now().date_naive();
+        let today = clock::today();
         Self::Weekly {
             year: today.iso_week().year(),
             week: today.iso_week().week(),
@@ -0,0 +1,245 @@
+//! Scheduled transaction model
+//!
+//! Describes a transaction that repeats on a cadence, like rent on the 1st
+//! of every month. Due occurrences are turned into real transactions by the
+//! scheduled service.
+
+use chrono::{DateTime, Datelike, Duration, Months, NaiveDate, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::ids::{AccountId, CategoryId, ScheduledId};
+use super::money::Money;
+use super::target::TargetCadence;
+
+use crate::config::clock;
+
+/// Validation errors for scheduled transactions
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduledValidationError {
+    ZeroAmount,
+    InvalidCustomInterval,
+    EndBeforeStart,
+}
+
+impl std::fmt::Display for ScheduledValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ZeroAmount => write!(f, "Scheduled amount cannot be zero"),
+            Self::InvalidCustomInterval => {
+                write!(f, "Custom interval must be at least 1 day")
+            }
+            Self::EndBeforeStart => write!(f, "End date cannot be before the next date"),
+        }
+    }
+}
+
+impl std::error::Error for ScheduledValidationError {}
+
+/// A transaction that repeats on a cadence
+///
+/// `start_date` anchors monthly and yearly schedules, so one on the 31st
+/// lands on the last day of shorter months and returns to the 31st after.
+/// A `ByDate` cadence happens once.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScheduledTransaction {
+    pub id: ScheduledId,
+    pub account_id: AccountId,
+    pub amount: Money,
+    #[serde(default)]
+    pub payee_name: String,
+    #[serde(default)]
+    pub category_id: Option<CategoryId>,
+    #[serde(default)]
+    pub memo: String,
+    pub cadence: TargetCadence,
+    pub start_date: NaiveDate,
+    pub next_date: NaiveDate,
+    #[serde(default)]
+    pub end_date: Option<NaiveDate>,
+    #[serde(default = "default_active")]
+    pub active: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+fn default_active() -> bool {
+    true
+}
+
+impl ScheduledTransaction {
+    /// Create a schedule whose first occurrence is `start_date`
+    pub fn new(
+        account_id: AccountId,
+        amount: Money,
+        cadence: TargetCadence,
+        start_date: NaiveDate,
+    ) -> Self {
+        let now = clock::now();
+        Self {
+            id: ScheduledId::new(),
+            account_id,
+            amount,
+            payee_name: String::new(),
+            category_id: None,
+            memo: String::new(),
+            cadence,
+            start_date,
+            next_date: start_date,
+            end_date: None,
+            active: true,
+            created_at: now,
+            updated_at: now,
+        }
+    }
+
+    /// Whether an occurrence is due on or before `as_of`
+    pub fn is_due(&self, as_of: NaiveDate) -> bool {
+        self.active && self.next_date <= as_of && !self.is_past_end(self.next_date)
+    }
+
+    /// The occurrence after `next_date`, or None if the cadence doesn't repeat
+    pub fn following_date(&self) -> Option<NaiveDate> {
+        match &self.cadence {
+            TargetCadence::Weekly => self.next_date.checked_add_signed(Duration::days(7)),
+            TargetCadence::Custom { days } => self
+                .next_date
+                .checked_add_signed(Duration::days(i64::from(*days))),
+            TargetCadence::Monthly => self.anchored_after(1),
+            TargetCadence::Yearly => self.anchored_after(12),
+            TargetCadence::ByDate { .. } => None,
+        }
+    }
+
+    /// Move to the next occurrence, deactivating once the schedule runs out
+    pub fn advance(&mut self) {
+        match self.following_date() {
+            Some(next) if !self.is_past_end(next) => self.next_date = next,
+            Some(next) => {
+                self.next_date = next;
+                self.active = false;
+            }
+            None => self.active = false,
+        }
+        self.updated_at = clock::now();
+    }
+
+    /// Validate the schedule
+    pub fn validate(&self) -> Result<(), ScheduledValidationError> {
+        if self.amount.is_zero() {
+            return Err(ScheduledValidationError::ZeroAmount);
+        }
+
+        if let TargetCadence::Custom { days } = self.cadence {
+            if days == 0 {
+                return Err(ScheduledValidationError::InvalidCustomInterval);
+            }
+        }
+
+        if self.is_past_end(self.next_date) {
+            return Err(ScheduledValidationError::EndBeforeStart);
+        }
+
+        Ok(())
+    }
+
+    fn is_past_end(&self, date: NaiveDate) -> bool {
+        self.end_date.is_some_and(|end| date > end)
+    }
+
+    /// The first `start_date + k * step` months strictly after `next_date`
+    fn anchored_after(&self, step: u32) -> Option<NaiveDate> {
+        let elapsed = (self.next_date.year() - self.start_date.year()) * 12
+            + self.next_date.month() as i32
+            - self.start_date.month() as i32;
+        let steps = elapsed.max(0) as u32 / step + 1;
+        self.start_date
+            .checked_add_months(Months::new(steps * step))
+    }
+}
+
+impl std::fmt::Display for ScheduledTransaction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let label = if self.payee_name.is_empty() {
+            "Scheduled transaction"
+        } else {
+            self.payee_name.as_str()
+        };
+        write!(f, "{} ({} {})", label, self.amount, self.cadence)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_monthly_keeps_day_of_month() {
+        let mut scheduled = ScheduledTransaction::new(
+            AccountId::new(),
+            Money::from_cents(-150000),
+            TargetCadence::Monthly,
+            date(2025, 1, 31),
+        );
+
+        scheduled.advance();
+        assert_eq!(scheduled.next_date, date(2025, 2, 28));
+        scheduled.advance();
+        assert_eq!(scheduled.next_date, date(2025, 3, 31));
+    }
+
+    #[test]
+    fn test_end_date_deactivates() {
+        let mut scheduled = ScheduledTransaction::new(
+            AccountId::new(),
+            Money::from_cents(-2000),
+            TargetCadence::Weekly,
+            date(2025, 1, 1),
+        );
+        scheduled.end_date = Some(date(2025, 1, 10));
+
+        scheduled.advance();
+        assert!(scheduled.is_due(date(2025, 1, 8)));
+        scheduled.advance();
+        assert!(!scheduled.active);
+        assert!(!scheduled.is_due(date(2025, 2, 1)));
+    }
+
+    #[test]
+    fn test_by_date_happens_once() {
+        let mut scheduled = ScheduledTransaction::new(
+            AccountId::new(),
+            Money::from_cents(-50000),
+            TargetCadence::by_date(date(2025, 6, 1)),
+            date(2025, 6, 1),
+        );
+
+        assert!(scheduled.is_due(date(2025, 6, 1)));
+        scheduled.advance();
+        assert!(!scheduled.active);
+    }
+
+    #[test]
+    fn test_validation() {
+        let mut scheduled = ScheduledTransaction::new(
+            AccountId::new(),
+            Money::zero(),
+            TargetCadence::Monthly,
+            date(2025, 1, 1),
+        );
+        assert_eq!(
+            scheduled.validate(),
+            Err(ScheduledValidationError::ZeroAmount)
+        );
+
+        scheduled.amount = Money::from_cents(-100);
+        scheduled.end_date = Some(date(2024, 12, 31));
+        assert_eq!(
+            scheduled.validate(),
+            Err(ScheduledValidationError::EndBeforeStart)
+        );
+    }
+}
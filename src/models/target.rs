@@ -11,6 +11,8 @@ use super::ids::CategoryId;
 use super::money::Money;
 use super::period::BudgetPeriod;
 
+use crate::config::clock;
+
 /// Unique identifier for a budget target
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
 #[serde(transparent)]
@@ -91,6 +93,58 @@ impl fmt::Display for TargetCadence {
     }
 }
 
+/// How a target's per-period suggestion is rounded to whole cents
+///
+/// Spreading a target over a period rarely divides evenly. The same policy
+/// applies to every cadence; the default rounds up so a suggestion never
+/// falls short of the goal.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TargetRounding {
+    /// Round half a cent or more up, anything less down
+    Nearest,
+    /// Round any fraction of a cent up
+    #[default]
+    Up,
+    /// Drop any fraction of a cent
+    Down,
+}
+
+impl TargetRounding {
+    /// Parse a policy name: nearest, up, or down
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "nearest" => Some(Self::Nearest),
+            "up" => Some(Self::Up),
+            "down" => Some(Self::Down),
+            _ => None,
+        }
+    }
+
+    /// Divide `numerator` cents by a positive `denominator` under this policy
+    ///
+    /// Integer arithmetic, so an exact division never picks up a stray cent.
+    pub fn divide(self, numerator: i64, denominator: i64) -> i64 {
+        let (numerator, denominator) = (numerator as i128, denominator as i128);
+        let result = match self {
+            Self::Nearest => (2 * numerator + denominator).div_euclid(2 * denominator),
+            Self::Up => -(-numerator).div_euclid(denominator),
+            Self::Down => numerator.div_euclid(denominator),
+        };
+        result as i64
+    }
+}
+
+impl fmt::Display for TargetRounding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Nearest => write!(f, "nearest"),
+            Self::Up => write!(f, "up"),
+            Self::Down => write!(f, "down"),
+        }
+    }
+}
+
 /// A budget target for a category
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BudgetTarget {
@@ -112,7 +166,7 @@ fn default_active() -> bool {
 
 impl BudgetTarget {
     pub fn new(category_id: CategoryId, amount: Money, cadence: TargetCadence) -> Self {
-        let now = Utc::now();
+        let now = clock::now();
         Self {
             id: BudgetTargetId::new(),
             category_id,
@@ -137,99 +191,102 @@ impl BudgetTarget {
         Self::new(category_id, amount, TargetCadence::Yearly)
     }
 
+    /// Suggested budget for a period, rounded with the default policy
     pub fn calculate_for_period(&self, period: &BudgetPeriod) -> Money {
+        self.calculate_for_period_rounded(period, TargetRounding::default())
+    }
+
+    /// Suggested budget for a period, rounded with `rounding`
+    pub fn calculate_for_period_rounded(
+        &self,
+        period: &BudgetPeriod,
+        rounding: TargetRounding,
+    ) -> Money {
         if !self.active {
             return Money::zero();
         }
 
-        match &self.cadence {
-            TargetCadence::Weekly => self.calculate_weekly_for_period(period),
-            TargetCadence::Monthly => self.calculate_monthly_for_period(period),
-            TargetCadence::Yearly => self.calculate_yearly_for_period(period),
-            TargetCadence::Custom { days } => self.calculate_custom_for_period(period, *days),
+        let cents = match &self.cadence {
+            TargetCadence::Weekly => self.calculate_weekly_for_period(period, rounding),
+            TargetCadence::Monthly => self.calculate_monthly_for_period(period, rounding),
+            TargetCadence::Yearly => self.calculate_yearly_for_period(period, rounding),
+            TargetCadence::Custom { days } => {
+                self.calculate_custom_for_period(period, *days, rounding)
+            }
             TargetCadence::ByDate { target_date } => {
-                self.calculate_by_date_for_period(period, *target_date)
+                self.calculate_by_date_for_period(period, *target_date, rounding)
             }
-        }
+        };
+        Money::from_cents(cents)
     }
 
-    fn calculate_weekly_for_period(&self, period: &BudgetPeriod) -> Money {
+    fn calculate_weekly_for_period(&self, period: &BudgetPeriod, rounding: TargetRounding) -> i64 {
+        let amount = self.amount.cents();
         match period {
-            BudgetPeriod::Weekly { .. } => self.amount,
-            BudgetPeriod::Monthly { year, month } => {
-                let start = NaiveDate::from_ymd_opt(*year, *month, 1).unwrap();
-                let end = if *month == 12 {
-                    NaiveDate::from_ymd_opt(*year + 1, 1, 1).unwrap()
-                } else {
-                    NaiveDate::from_ymd_opt(*year, *month + 1, 1).unwrap()
-                };
-                let days = (end - start).num_days() as f64;
-                let weeks = days / 7.0;
-                Money::from_cents((self.amount.cents() as f64 * weeks).round() as i64)
-            }
-            BudgetPeriod::BiWeekly { .. } => Money::from_cents(self.amount.cents() * 2),
-            BudgetPeriod::Custom { start, end } => {
-                let days = (*end - *start).num_days() as f64 + 1.0;
-                let weeks = days / 7.0;
-                Money::from_cents((self.amount.cents() as f64 * weeks).round() as i64)
+            BudgetPeriod::Weekly { .. } => amount,
+            BudgetPeriod::BiWeekly { .. } => amount * 2,
+            BudgetPeriod::Monthly { .. } | BudgetPeriod::Custom { .. } => {
+                rounding.divide(amount * period_days(period), 7)
             }
         }
     }
 
-    fn calculate_monthly_for_period(&self, period: &BudgetPeriod) -> Money {
+    fn calculate_monthly_for_period(&self, period: &BudgetPeriod, rounding: TargetRounding) -> i64 {
+        let amount = self.amount.cents();
         match period {
-            BudgetPeriod::Monthly { .. } => self.amount,
-            BudgetPeriod::Weekly { .. } => {
-                Money::from_cents((self.amount.cents() as f64 / 4.33).round() as i64)
-            }
-            BudgetPeriod::BiWeekly { .. } => Money::from_cents(self.amount.cents() / 2),
-            BudgetPeriod::Custom { start, end } => {
-                let days = (*end - *start).num_days() as f64 + 1.0;
-                Money::from_cents((self.amount.cents() as f64 * days / 30.0).round() as i64)
-            }
+            BudgetPeriod::Monthly { .. } => amount,
+            // 4.33 weeks to a month
+            BudgetPeriod::Weekly { .. } => rounding.divide(amount * 100, 433),
+            BudgetPeriod::BiWeekly { .. } => rounding.divide(amount, 2),
+            BudgetPeriod::Custom { .. } => rounding.divide(amount * period_days(period), 30),
         }
     }
 
-    fn calculate_yearly_for_period(&self, period: &BudgetPeriod) -> Money {
+    fn calculate_yearly_for_period(&self, period: &BudgetPeriod, rounding: TargetRounding) -> i64 {
+        let amount = self.amount.cents();
         match period {
-            BudgetPeriod::Monthly { .. } => Money::from_cents(self.amount.cents() / 12),
-            BudgetPeriod::Weekly { .. } => {
-                Money::from_cents((self.amount.cents() as f64 / 52.0).round() as i64)
-            }
-            BudgetPeriod::BiWeekly { .. } => {
-                Money::from_cents((self.amount.cents() as f64 / 26.0).round() as i64)
-            }
-            BudgetPeriod::Custom { start, end } => {
-                let days = (*end - *start).num_days() as f64 + 1.0;
-                Money::from_cents((self.amount.cents() as f64 * days / 365.0).round() as i64)
-            }
+            BudgetPeriod::Monthly { .. } => rounding.divide(amount, 12),
+            BudgetPeriod::Weekly { .. } => rounding.divide(amount, 52),
+            BudgetPeriod::BiWeekly { .. } => rounding.divide(amount, 26),
+            BudgetPeriod::Custom { .. } => rounding.divide(amount * period_days(period), 365),
         }
     }
 
-    fn calculate_custom_for_period(&self, period: &BudgetPeriod, interval_days: u32) -> Money {
-        let period_days = (period.end_date() - period.start_date()).num_days() as f64 + 1.0;
-        let intervals = period_days / interval_days as f64;
-        Money::from_cents((self.amount.cents() as f64 * intervals).round() as i64)
-    }
-
-    fn calculate_by_date_for_period(&self, period: &BudgetPeriod, target_date: NaiveDate) -> Money {
+    fn calculate_custom_for_period(
+        &self,
+        period: &BudgetPeriod,
+        interval_days: u32,
+        rounding: TargetRounding,
+    ) -> i64 {
+        rounding.divide(
+            self.amount.cents() * period_days(period),
+            interval_days as i64,
+        )
+    }
+
+    fn calculate_by_date_for_period(
+        &self,
+        period: &BudgetPeriod,
+        target_date: NaiveDate,
+        rounding: TargetRounding,
+    ) -> i64 {
         let period_start = period.start_date();
         let period_end = period.end_date();
 
         if target_date < period_start {
-            return Money::zero();
+            return 0;
         }
 
         if target_date <= period_end {
-            return self.amount;
+            return self.amount.cents();
         }
 
         let months_remaining = self.months_between(period_start, target_date);
         if months_remaining <= 0 {
-            return self.amount;
+            return self.amount.cents();
         }
 
-        Money::from_cents((self.amount.cents() as f64 / months_remaining as f64).ceil() as i64)
+        rounding.divide(self.amount.cents(), months_remaining as i64)
     }
 
     fn months_between(&self, start: NaiveDate, end: NaiveDate) -> i32 {
@@ -238,24 +295,68 @@ impl BudgetTarget {
         years * 12 + months
     }
 
+    /// Fraction of the goal already saved, from 0.0 to 1.0
+    ///
+    /// `ByDate` goals measure `already_saved` against the full amount;
+    /// recurring targets measure it against what `period` calls for. A goal
+    /// of nothing counts as met. Overdue goals still report what was saved,
+    /// so pair this with `is_overdue`.
+    pub fn progress(&self, period: &BudgetPeriod, already_saved: Money) -> f64 {
+        let goal = match self.cadence {
+            TargetCadence::ByDate { .. } => self.amount,
+            _ => self.calculate_for_period(period),
+        };
+        if !goal.is_positive() {
+            return 1.0;
+        }
+
+        (already_saved.cents() as f64 / goal.cents() as f64).clamp(0.0, 1.0)
+    }
+
+    /// Whole months between `period` and a `ByDate` goal's date
+    ///
+    /// Zero when the goal falls due within `period` or is already past;
+    /// `None` for recurring targets.
+    pub fn months_remaining(&self, period: &BudgetPeriod) -> Option<u32> {
+        match self.cadence {
+            TargetCadence::ByDate { target_date } => {
+                Some(self.months_between(period.start_date(), target_date).max(0) as u32)
+            }
+            _ => None,
+        }
+    }
+
+    /// Whether a `ByDate` goal's date is before `period` starts
+    pub fn is_overdue(&self, period: &BudgetPeriod) -> bool {
+        match self.cadence {
+            TargetCadence::ByDate { target_date } => target_date < period.start_date(),
+            _ => false,
+        }
+    }
+
     pub fn set_amount(&mut self, amount: Money) {
         self.amount = amount;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     pub fn set_cadence(&mut self, cadence: TargetCadence) {
         self.cadence = cadence;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
+    }
+
+    pub fn set_notes(&mut self, notes: impl Into<String>) {
+        self.notes = notes.into();
+        self.updated_at = clock::now();
     }
 
     pub fn activate(&mut self) {
         self.active = true;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     pub fn deactivate(&mut self) {
         self.active = false;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     pub fn validate(&self) -> Result<(), TargetValidationError> {
@@ -277,6 +378,11 @@ impl BudgetTarget {
     }
 }
 
+/// Number of days in a period, counting both ends
+fn period_days(period: &BudgetPeriod) -> i64 {
+    (period.end_date() - period.start_date()).num_days() + 1
+}
+
 impl fmt::Display for BudgetTarget {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{} {}", self.amount, self.cadence)
@@ -466,6 +572,107 @@ mod tests {
         assert_eq!(suggested.cents(), expected);
     }
 
+    // ============================================
+    // Rounding Policy Tests
+    // ============================================
+
+    #[test]
+    fn test_rounding_divide() {
+        assert_eq!(TargetRounding::Nearest.divide(5, 2), 3);
+        assert_eq!(TargetRounding::Nearest.divide(7, 3), 2);
+        assert_eq!(TargetRounding::Up.divide(7, 3), 3);
+        assert_eq!(TargetRounding::Down.divide(8, 3), 2);
+        // Exact divisions are never nudged
+        for rounding in [
+            TargetRounding::Nearest,
+            TargetRounding::Up,
+            TargetRounding::Down,
+        ] {
+            assert_eq!(rounding.divide(43300 * 100, 433), 10000);
+        }
+        assert_eq!(TargetRounding::default(), TargetRounding::Up);
+    }
+
+    #[test]
+    fn test_rounding_policy_per_cadence() {
+        let category_id = test_category_id();
+        let january = BudgetPeriod::monthly(2025, 1); // 31 days
+        let week = BudgetPeriod::weekly(2025, 1);
+        let fortnight = BudgetPeriod::bi_weekly(NaiveDate::from_ymd_opt(2025, 1, 1).unwrap());
+        let april = NaiveDate::from_ymd_opt(2025, 4, 15).unwrap();
+
+        // (target, period, [nearest, up, down]) in cents
+        let cases = [
+            // 10000 * 31 / 7 = 44285.71
+            (
+                BudgetTarget::weekly(category_id, Money::from_cents(10000)),
+                &january,
+                [44286, 44286, 44285],
+            ),
+            // 10000 / 4.33 = 2309.47
+            (
+                BudgetTarget::monthly(category_id, Money::from_cents(10000)),
+                &week,
+                [2309, 2310, 2309],
+            ),
+            // 10001 / 2 = 5000.5
+            (
+                BudgetTarget::monthly(category_id, Money::from_cents(10001)),
+                &fortnight,
+                [5001, 5001, 5000],
+            ),
+            // 10000 / 12 = 833.33
+            (
+                BudgetTarget::yearly(category_id, Money::from_cents(10000)),
+                &january,
+                [833, 834, 833],
+            ),
+            // 10000 * 31 / 14 = 22142.86
+            (
+                BudgetTarget::new(
+                    category_id,
+                    Money::from_cents(10000),
+                    TargetCadence::custom(14),
+                ),
+                &january,
+                [22143, 22143, 22142],
+            ),
+            // 10000 over 3 months = 3333.33
+            (
+                BudgetTarget::new(
+                    category_id,
+                    Money::from_cents(10000),
+                    TargetCadence::by_date(april),
+                ),
+                &january,
+                [3333, 3334, 3333],
+            ),
+        ];
+
+        for (target, period, expected) in cases {
+            let policies = [
+                TargetRounding::Nearest,
+                TargetRounding::Up,
+                TargetRounding::Down,
+            ];
+            for (rounding, cents) in policies.into_iter().zip(expected) {
+                assert_eq!(
+                    target
+                        .calculate_for_period_rounded(period, rounding)
+                        .cents(),
+                    cents,
+                    "{} rounded {}",
+                    target.cadence,
+                    rounding
+                );
+            }
+        }
+
+        // The default policy rounds up
+        let yearly = BudgetTarget::yearly(category_id, Money::from_cents(10000));
+        assert_eq!(yearly.calculate_for_period(&january).cents(), 834);
+    }
+
     // ============================================
     // Custom Interval Tests
     // ============================================
@@ -644,6 +851,44 @@ mod tests {
         assert_eq!(suggested.cents(), 100000); // Full amount needed
     }
 
+    #[test]
+    fn test_by_date_progress() {
+        let target = BudgetTarget::new(
+            test_category_id(),
+            Money::from_cents(100000),
+            TargetCadence::by_date(NaiveDate::from_ymd_opt(2025, 7, 1).unwrap()),
+        );
+        let period = BudgetPeriod::monthly(2025, 1);
+
+        assert_eq!(target.progress(&period, Money::from_cents(25000)), 0.25);
+        assert_eq!(target.progress(&period, Money::from_cents(150000)), 1.0);
+        assert_eq!(target.progress(&period, Money::from_cents(-5000)), 0.0);
+        assert_eq!(target.months_remaining(&period), Some(6));
+        assert!(!target.is_overdue(&period));
+    }
+
+    #[test]
+    fn test_by_date_progress_past_due() {
+        let target = BudgetTarget::new(
+            test_category_id(),
+            Money::from_cents(100000),
+            TargetCadence::by_date(NaiveDate::from_ymd_opt(2024, 12, 15).unwrap()),
+        );
+        let period = BudgetPeriod::monthly(2025, 1);
+
+        // No division by a zero suggestion once the date has passed
+        assert_eq!(target.progress(&period, Money::from_cents(40000)), 0.4);
+        assert_eq!(target.months_remaining(&period), Some(0));
+        assert!(target.is_overdue(&period));
+
+        let zero_goal = BudgetTarget::new(
+            test_category_id(),
+            Money::zero(),
+            TargetCadence::by_date(NaiveDate::from_ymd_opt(2024, 12, 15).unwrap()),
+        );
+        assert_eq!(zero_goal.progress(&period, Money::zero()), 1.0);
+    }
+
     // ============================================
     // Inactive Target Tests
     // ============================================
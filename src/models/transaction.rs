@@ -10,6 +10,8 @@ use std::fmt;
 use super::ids::{AccountId, CategoryId, PayeeId, TransactionId};
 use super::money::Money;
 
+use crate::config::clock;
+
 /// Status of a transaction
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
 #[serde(rename_all = "lowercase")]
@@ -40,11 +42,71 @@ impl fmt::Display for TransactionStatus {
     }
 }
 
-/// A split portion of a transaction assigned to a specific category
+/// A triage marker, independent of the transaction's status
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+#[serde(rename_all = "lowercase")]
+pub enum TransactionFlag {
+    /// Not flagged
+    #[default]
+    None,
+    /// Needs a look before it's cleared, such as a freshly imported transaction
+    Review,
+    /// Marked as worth keeping an eye on
+    Starred,
+}
+
+impl TransactionFlag {
+    /// Check if the transaction is unflagged
+    pub fn is_none(&self) -> bool {
+        matches!(self, Self::None)
+    }
+
+    /// The next flag when cycling None -> Review -> Starred -> None
+    pub fn next(self) -> Self {
+        match self {
+            Self::None => Self::Review,
+            Self::Review => Self::Starred,
+            Self::Starred => Self::None,
+        }
+    }
+
+    /// One-character marker for register rows
+    pub fn marker(&self) -> &'static str {
+        match self {
+            Self::None => " ",
+            Self::Review => "⚑",
+            Self::Starred => "★",
+        }
+    }
+
+    /// Parse a flag name (none, review, starred/star)
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "none" => Some(Self::None),
+            "review" => Some(Self::Review),
+            "starred" | "star" => Some(Self::Starred),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for TransactionFlag {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::None => write!(f, "None"),
+            Self::Review => write!(f, "Review"),
+            Self::Starred => write!(f, "Starred"),
+        }
+    }
+}
+
+/// A split portion of a transaction assigned to a category, or transferred
+/// to another account
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Split {
-    /// The category for this split portion
-    pub category_id: CategoryId,
+    /// The category for this split portion (None for a transfer split)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub category_id: Option<CategoryId>,
 
     /// The amount for this split (same sign as parent transaction)
     pub amount: Money,
@@ -52,26 +114,54 @@ pub struct Split {
     /// Optional memo for this split
     #[serde(default)]
     pub memo: String,
+
+    /// Account this portion is transferred to, instead of a category
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transfer_account_id: Option<AccountId>,
+
+    /// The transaction created in the other account for a transfer split
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub transfer_transaction_id: Option<TransactionId>,
 }
 
 impl Split {
     /// Create a new split
     pub fn new(category_id: CategoryId, amount: Money) -> Self {
         Self {
-            category_id,
+            category_id: Some(category_id),
             amount,
             memo: String::new(),
+            transfer_account_id: None,
+            transfer_transaction_id: None,
         }
     }
 
     /// Create a new split with a memo
     pub fn with_memo(category_id: CategoryId, amount: Money, memo: impl Into<String>) -> Self {
         Self {
-            category_id,
-            amount,
             memo: memo.into(),
+            ..Self::new(category_id, amount)
+        }
+    }
+
+    /// Create a split that transfers its amount to another account
+    ///
+    /// The amount has the parent's sign: -$20 on a purchase moves $20 into
+    /// `account_id`, as with cash back.
+    pub fn transfer(account_id: AccountId, amount: Money) -> Self {
+        Self {
+            category_id: None,
+            amount,
+            memo: String::new(),
+            transfer_account_id: Some(account_id),
+            transfer_transaction_id: None,
         }
     }
+
+    /// Check if this split is a transfer to another account
+    pub fn is_transfer(&self) -> bool {
+        self.transfer_account_id.is_some()
+    }
 }
 
 /// A financial transaction
@@ -117,6 +207,22 @@ pub struct Transaction {
     /// Import ID for duplicate detection during CSV import
     pub import_id: Option<String>,
 
+    /// Whether this is the account's opening "Starting Balance" transaction
+    #[serde(default)]
+    pub is_starting_balance: bool,
+
+    /// Free-form labels that span categories (normalized, sorted, unique)
+    #[serde(default)]
+    pub tags: Vec<String>,
+
+    /// Where an inflow came from, such as "salary" or "interest" (lowercase)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub income_source: Option<String>,
+
+    /// Triage marker, such as Review for imported transactions
+    #[serde(default, skip_serializing_if = "TransactionFlag::is_none")]
+    pub flag: TransactionFlag,
+
     /// When the transaction was created
     pub created_at: DateTime<Utc>,
 
@@ -127,7 +233,7 @@ pub struct Transaction {
 impl Transaction {
     /// Create a new transaction
     pub fn new(account_id: AccountId, date: NaiveDate, amount: Money) -> Self {
-        let now = Utc::now();
+        let now = clock::now();
         Self {
             id: TransactionId::new(),
             account_id,
@@ -141,6 +247,10 @@ impl Transaction {
             status: TransactionStatus::Pending,
             transfer_transaction_id: None,
             import_id: None,
+            is_starting_balance: false,
+            tags: Vec::new(),
+            income_source: None,
+            flag: TransactionFlag::None,
             created_at: now,
             updated_at: now,
         }
@@ -162,6 +272,24 @@ impl Transaction {
         txn
     }
 
+    /// Create an account's opening "Starting Balance" transaction
+    ///
+    /// The transaction is uncategorized, so it flows into Available to Budget,
+    /// and reconciled, so it is locked against ordinary edits.
+    pub fn starting_balance(account_id: AccountId, date: NaiveDate, amount: Money) -> Self {
+        let mut txn = Self::with_details(
+            account_id,
+            date,
+            amount,
+            "Starting Balance",
+            None,
+            "Initial account balance",
+        );
+        txn.status = TransactionStatus::Reconciled;
+        txn.is_starting_balance = true;
+        txn
+    }
+
     /// Check if this is a split transaction
     pub fn is_split(&self) -> bool {
         !self.splits.is_empty()
@@ -190,7 +318,7 @@ impl Transaction {
     /// Set the status
     pub fn set_status(&mut self, status: TransactionStatus) {
         self.status = status;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Clear the transaction (mark as cleared)
@@ -208,14 +336,14 @@ impl Transaction {
         self.splits.push(split);
         // When splits are added, category_id should be cleared
         self.category_id = None;
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Clear all splits and set a single category
     pub fn set_category(&mut self, category_id: CategoryId) {
         self.splits.clear();
         self.category_id = Some(category_id);
-        self.updated_at = Utc::now();
+        self.updated_at = clock::now();
     }
 
     /// Get the total of all splits (should equal transaction amount)
@@ -223,6 +351,58 @@ impl Transaction {
         self.splits.iter().map(|s| s.amount).sum()
     }
 
+    /// Amount this transaction moves to a linked transfer transaction
+    ///
+    /// Either the whole amount for a plain transfer, or the amount of the
+    /// transfer split that created `linked_id`. None if they aren't linked.
+    pub fn transfer_amount_to(&self, linked_id: TransactionId) -> Option<Money> {
+        if self.transfer_transaction_id == Some(linked_id) {
+            return Some(self.amount);
+        }
+        self.splits
+            .iter()
+            .find(|s| s.transfer_transaction_id == Some(linked_id))
+            .map(|s| s.amount)
+    }
+
+    /// Check if the transaction carries a tag (compared after normalization)
+    pub fn has_tag(&self, tag: &str) -> bool {
+        normalize_tag(tag).is_some_and(|tag| self.tags.contains(&tag))
+    }
+
+    /// Add tags, normalizing and skipping ones already present
+    ///
+    /// Returns true if any tag was added.
+    pub fn add_tags<S: AsRef<str>>(&mut self, tags: &[S]) -> bool {
+        let before = self.tags.len();
+        self.tags
+            .extend(tags.iter().filter_map(|t| normalize_tag(t.as_ref())));
+        self.tags.sort();
+        self.tags.dedup();
+
+        let changed = self.tags.len() != before;
+        if changed {
+            self.updated_at = clock::now();
+        }
+        changed
+    }
+
+    /// Remove tags, returning true if any were present
+    pub fn remove_tags<S: AsRef<str>>(&mut self, tags: &[S]) -> bool {
+        let remove: Vec<String> = tags
+            .iter()
+            .filter_map(|t| normalize_tag(t.as_ref()))
+            .collect();
+        let before = self.tags.len();
+        self.tags.retain(|t| !remove.contains(t));
+
+        let changed = self.tags.len() != before;
+        if changed {
+            self.updated_at = clock::now();
+        }
+        changed
+    }
+
     /// Validate the transaction
     pub fn validate(&self) -> Result<(), TransactionValidationError> {
         // If split, splits total must equal transaction amount
@@ -241,6 +421,15 @@ impl Transaction {
             return Err(TransactionValidationError::CategoryAndSplits);
         }
 
+        // Each split goes to exactly one category or account
+        if self
+            .splits
+            .iter()
+            .any(|s| s.category_id.is_some() == s.is_transfer())
+        {
+            return Err(TransactionValidationError::SplitTarget);
+        }
+
         // Transfers shouldn't have categories
         if self.is_transfer() && (self.category_id.is_some() || !self.splits.is_empty()) {
             return Err(TransactionValidationError::TransferWithCategory);
@@ -260,6 +449,32 @@ impl Transaction {
     }
 }
 
+/// Normalize a tag: trimmed, lowercased, without a leading `#`
+///
+/// Returns None for tags that are empty after normalization.
+pub fn normalize_tag(tag: &str) -> Option<String> {
+    let tag = tag.trim().trim_start_matches('#').trim().to_lowercase();
+    if tag.is_empty() {
+        None
+    } else {
+        Some(tag)
+    }
+}
+
+/// Split user input like `"#vacation2025, tax-deductible"` into tags
+///
+/// Tags are separated by commas or whitespace and returned normalized,
+/// sorted, and unique.
+pub fn parse_tags(input: &str) -> Vec<String> {
+    let mut tags: Vec<String> = input
+        .split(|c: char| c == ',' || c.is_whitespace())
+        .filter_map(normalize_tag)
+        .collect();
+    tags.sort();
+    tags.dedup();
+    tags
+}
+
 impl fmt::Display for Transaction {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
@@ -281,6 +496,7 @@ pub enum TransactionValidationError {
     },
     CategoryAndSplits,
     TransferWithCategory,
+    SplitTarget,
 }
 
 impl fmt::Display for TransactionValidationError {
@@ -300,6 +516,12 @@ impl fmt::Display for TransactionValidationError {
             Self::TransferWithCategory => {
                 write!(f, "Transfer transactions should not have a category")
             }
+            Self::SplitTarget => {
+                write!(
+                    f,
+                    "Each split needs either a category or a transfer account"
+                )
+            }
         }
     }
 }
@@ -362,6 +584,18 @@ mod tests {
         assert!(txn.is_locked());
     }
 
+    #[test]
+    fn test_starting_balance_transaction() {
+        let account_id = test_account_id();
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let txn = Transaction::starting_balance(account_id, date, Money::from_cents(100000));
+
+        assert!(txn.is_starting_balance);
+        assert!(txn.is_locked());
+        assert_eq!(txn.category_id, None);
+        assert_eq!(txn.payee_name, "Starting Balance");
+    }
+
     #[test]
     fn test_split_transaction() {
         let account_id = test_account_id();
@@ -412,6 +646,21 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_transfer_split_validation() {
+        let account_id = test_account_id();
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let mut txn = Transaction::new(account_id, date, Money::from_cents(-10000));
+
+        txn.add_split(Split::new(test_category_id(), Money::from_cents(-8000)));
+        txn.add_split(Split::transfer(test_account_id(), Money::from_cents(-2000)));
+        assert!(txn.validate().is_ok());
+
+        // A split can't go to both a category and an account
+        txn.splits[1].category_id = Some(test_category_id());
+        assert_eq!(txn.validate(), Err(TransactionValidationError::SplitTarget));
+    }
+
     #[test]
     fn test_import_id_generation() {
         let account_id = test_account_id();
@@ -447,6 +696,51 @@ mod tests {
         assert_eq!(txn.payee_name, deserialized.payee_name);
     }
 
+    #[test]
+    fn test_flag_serialization() {
+        let mut txn = Transaction::new(
+            test_account_id(),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            Money::from_cents(-5000),
+        );
+
+        // Unflagged transactions keep their old shape
+        let json = serde_json::to_string(&txn).unwrap();
+        assert!(!json.contains("flag"));
+
+        txn.flag = TransactionFlag::Review;
+        let json = serde_json::to_string(&txn).unwrap();
+        assert!(json.contains(r#""flag":"review""#));
+        let deserialized: Transaction = serde_json::from_str(&json).unwrap();
+        assert_eq!(deserialized.flag, TransactionFlag::Review);
+
+        assert_eq!(TransactionFlag::Starred.next(), TransactionFlag::None);
+        assert_eq!(
+            TransactionFlag::parse("Star"),
+            Some(TransactionFlag::Starred)
+        );
+    }
+
+    #[test]
+    fn test_tags_are_normalized() {
+        let mut txn = Transaction::new(
+            test_account_id(),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            Money::from_cents(-5000),
+        );
+
+        assert!(txn.add_tags(&[" #Vacation2025 ", "tax-deductible", "VACATION2025", "#"]));
+        assert_eq!(txn.tags, vec!["tax-deductible", "vacation2025"]);
+        assert!(txn.has_tag("#Tax-Deductible"));
+        assert!(!txn.add_tags(&["vacation2025"]));
+
+        assert!(txn.remove_tags(&["Vacation2025"]));
+        assert_eq!(txn.tags, vec!["tax-deductible"]);
+        assert!(!txn.remove_tags(&["missing"]));
+
+        assert_eq!(parse_tags("b, #a  A,,c"), vec!["a", "b", "c"]);
+    }
+
     #[test]
     fn test_display() {
         let account_id = test_account_id();
@@ -0,0 +1,181 @@
+//! Exchange rates for converting between currencies
+//!
+//! Rate lookups go through the [`RateProvider`] trait so the source can be
+//! swapped out. [`StaticRates`] is a fixed table built from the rates saved
+//! in settings; lookups take a date so a provider backed by daily rates can
+//! be added later.
+
+use std::collections::BTreeMap;
+
+use chrono::NaiveDate;
+use serde::{Deserialize, Serialize};
+
+use crate::error::{EnvelopeError, EnvelopeResult};
+use crate::models::Money;
+
+/// A saved exchange rate: one unit of `from` is worth `rate` units of `to`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ExchangeRate {
+    /// Source currency code (ISO 4217, e.g., "EUR")
+    pub from: String,
+    /// Target currency code
+    pub to: String,
+    /// Units of `to` per unit of `from`
+    pub rate: f64,
+}
+
+impl ExchangeRate {
+    /// Parse a rate written as `FROM/TO=RATE` (e.g., `EUR/USD=1.08`)
+    pub fn parse(s: &str) -> EnvelopeResult<Self> {
+        let invalid = || {
+            EnvelopeError::Validation(format!(
+                "Invalid exchange rate '{}'. Use FROM/TO=RATE (e.g., EUR/USD=1.08)",
+                s
+            ))
+        };
+
+        let (pair, rate) = s.split_once('=').ok_or_else(invalid)?;
+        let (from, to) = pair.split_once('/').ok_or_else(invalid)?;
+        let rate: f64 = rate.trim().parse().map_err(|_| invalid())?;
+
+        let from = normalize_currency(from).ok_or_else(invalid)?;
+        let to = normalize_currency(to).ok_or_else(invalid)?;
+        if !(rate.is_finite() && rate > 0.0) || from == to {
+            return Err(invalid());
+        }
+
+        Ok(Self { from, to, rate })
+    }
+}
+
+/// Normalize a currency code: trimmed, uppercased, three ASCII letters
+pub fn normalize_currency(code: &str) -> Option<String> {
+    let code = code.trim().to_uppercase();
+    if code.len() == 3 && code.chars().all(|c| c.is_ascii_alphabetic()) {
+        Some(code)
+    } else {
+        None
+    }
+}
+
+/// Symbol written before amounts in a currency, e.g. `€` for EUR
+///
+/// Codes without a well-known symbol are written as the code and a space.
+pub fn currency_symbol(code: &str) -> String {
+    match code.to_uppercase().as_str() {
+        "USD" | "AUD" | "CAD" | "NZD" | "MXN" => "$".to_string(),
+        "EUR" => "€".to_string(),
+        "GBP" => "£".to_string(),
+        "JPY" | "CNY" => "¥".to_string(),
+        "INR" => "₹".to_string(),
+        "KRW" => "₩".to_string(),
+        "CHF" => "CHF ".to_string(),
+        other => format!("{} ", other),
+    }
+}
+
+/// A source of exchange rates
+pub trait RateProvider {
+    /// Units of `to` per unit of `from` on `date`
+    ///
+    /// Returns [`EnvelopeError::MissingExchangeRate`] if the pair is unknown.
+    fn rate(&self, from: &str, to: &str, date: NaiveDate) -> EnvelopeResult<f64>;
+}
+
+/// Convert an amount between currencies, rounding to the nearest cent
+pub fn convert(
+    amount: Money,
+    from: &str,
+    to: &str,
+    date: NaiveDate,
+    rates: &dyn RateProvider,
+) -> EnvelopeResult<Money> {
+    if from.eq_ignore_ascii_case(to) {
+        return Ok(amount);
+    }
+    let rate = rates.rate(from, to, date)?;
+    Ok(Money::from_cents(
+        (amount.cents() as f64 * rate).round() as i64
+    ))
+}
+
+/// A fixed rate table that ignores the date
+///
+/// Each saved pair also answers the reverse lookup with the inverse rate.
+#[derive(Debug, Clone, Default)]
+pub struct StaticRates {
+    rates: BTreeMap<(String, String), f64>,
+}
+
+impl StaticRates {
+    /// Create an empty rate table
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Build a table from saved rates
+    pub fn from_rates(rates: &[ExchangeRate]) -> Self {
+        let mut table = Self::new();
+        for rate in rates {
+            table.insert(&rate.from, &rate.to, rate.rate);
+        }
+        table
+    }
+
+    /// Add or replace the rate for a pair
+    pub fn insert(&mut self, from: &str, to: &str, rate: f64) {
+        self.rates
+            .insert((from.to_uppercase(), to.to_uppercase()), rate);
+    }
+}
+
+impl RateProvider for StaticRates {
+    fn rate(&self, from: &str, to: &str, _date: NaiveDate) -> EnvelopeResult<f64> {
+        let from = from.to_uppercase();
+        let to = to.to_uppercase();
+        if from == to {
+            return Ok(1.0);
+        }
+
+        if let Some(rate) = self.rates.get(&(from.clone(), to.clone())) {
+            return Ok(*rate);
+        }
+        if let Some(rate) = self.rates.get(&(to.clone(), from.clone())) {
+            return Ok(1.0 / rate);
+        }
+
+        Err(EnvelopeError::MissingExchangeRate { from, to })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_currency_symbol() {
+        assert_eq!(currency_symbol("eur"), "€");
+        assert_eq!(currency_symbol("GBP"), "£");
+        assert_eq!(currency_symbol("SEK"), "SEK ");
+    }
+
+    #[test]
+    fn test_static_rates() {
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let rates = StaticRates::from_rates(&[ExchangeRate::parse("eur/usd=1.25").unwrap()]);
+
+        let usd = convert(Money::from_cents(10000), "EUR", "USD", date, &rates).unwrap();
+        assert_eq!(usd.cents(), 12500);
+
+        // Reverse lookups use the inverse rate
+        let eur = convert(Money::from_cents(12500), "USD", "EUR", date, &rates).unwrap();
+        assert_eq!(eur.cents(), 10000);
+
+        // Missing pairs name both currencies
+        let err = convert(Money::from_cents(100), "GBP", "USD", date, &rates).unwrap_err();
+        assert!(err.to_string().contains("GBP to USD"));
+
+        assert!(ExchangeRate::parse("EUR=1.1").is_err());
+        assert!(ExchangeRate::parse("EUR/USD=-1").is_err());
+    }
+}
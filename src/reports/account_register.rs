@@ -3,26 +3,32 @@
 //! Generates a detailed transaction register for an account with filtering options.
 
 use crate::error::EnvelopeResult;
-use crate::models::{AccountId, CategoryId, Money, Transaction, TransactionStatus};
+use crate::models::{AccountId, CategoryId, Money, Transaction, TransactionId, TransactionStatus};
+use crate::reports::markdown::{Align, MarkdownTable};
 use crate::services::{AccountService, CategoryService};
 use crate::storage::Storage;
 use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::io::Write;
 
 /// A single entry in the register report
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct RegisterEntry {
+    /// Transaction ID
+    pub transaction_id: TransactionId,
     /// Transaction date
     pub date: NaiveDate,
     /// Payee name
     pub payee: String,
-    /// Category name (or "Split" or "Transfer" or "Uncategorized")
+    /// Category name (or "Split", "Transfer", "Available to Budget" or "Uncategorized")
     pub category: String,
     /// Memo
     pub memo: String,
     /// Transaction amount
     pub amount: Money,
-    /// Running balance after this transaction
+    /// Account balance after this transaction, counting every transaction
+    /// in the account up to it, not just the ones the filter shows
     pub running_balance: Money,
     /// Transaction status
     pub status: TransactionStatus,
@@ -33,7 +39,7 @@ pub struct RegisterEntry {
 }
 
 /// Filter options for the register report
-#[derive(Debug, Clone, Default)]
+#[derive(Debug, Clone, Default, Serialize)]
 pub struct RegisterFilter {
     /// Filter by start date
     pub start_date: Option<NaiveDate>,
@@ -51,6 +57,8 @@ pub struct RegisterFilter {
     pub max_amount: Option<Money>,
     /// Only show uncategorized transactions
     pub uncategorized_only: bool,
+    /// Only show flagged (review or starred) transactions
+    pub flagged_only: bool,
 }
 
 impl RegisterFilter {
@@ -71,7 +79,7 @@ impl RegisterFilter {
         // Category filter
         if let Some(cat_id) = self.category_id {
             let matches_category = txn.category_id == Some(cat_id)
-                || txn.splits.iter().any(|s| s.category_id == cat_id);
+                || txn.splits.iter().any(|s| s.category_id == Some(cat_id));
             if !matches_category {
                 return false;
             }
@@ -110,17 +118,25 @@ impl RegisterFilter {
 
         // Uncategorized filter
         if self.uncategorized_only
-            && (txn.category_id.is_some() || !txn.splits.is_empty() || txn.is_transfer())
+            && (txn.category_id.is_some()
+                || !txn.splits.is_empty()
+                || txn.is_transfer()
+                || txn.is_starting_balance)
         {
             return false;
         }
 
+        // Flagged filter
+        if self.flagged_only && txn.flag.is_none() {
+            return false;
+        }
+
         true
     }
 }
 
 /// Account Register Report
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AccountRegisterReport {
     /// Account ID
     pub account_id: AccountId,
@@ -170,8 +186,8 @@ impl AccountRegisterReport {
                 .then_with(|| a.created_at.cmp(&b.created_at))
         });
 
-        // Calculate starting balance (account starting balance + all transactions before filter start)
-        let mut starting_balance = account.starting_balance;
+        // Calculate starting balance (untracked account starting balance + all transactions before filter start)
+        let mut starting_balance = account.untracked_starting_balance(&transactions);
         if let Some(start_date) = filter.start_date {
             for txn in &transactions {
                 if txn.date < start_date {
@@ -180,20 +196,22 @@ impl AccountRegisterReport {
             }
         }
 
-        // Build register entries
+        // Build register entries; the balance runs over every transaction in
+        // date order so filtered-out ones still count toward it
         let mut entries = Vec::new();
-        let mut running_balance = starting_balance;
+        let mut running_balance = account.untracked_starting_balance(&transactions);
+        let mut ending_balance = starting_balance;
         let mut total_inflows = Money::zero();
         let mut total_outflows = Money::zero();
 
         for txn in &transactions {
+            running_balance += txn.amount;
+
             // Apply filter
             if !filter.matches(txn) {
                 continue;
             }
-
-            // Update running balance
-            running_balance += txn.amount;
+            ending_balance = running_balance;
 
             // Track totals
             if txn.amount.is_positive() {
@@ -205,6 +223,8 @@ impl AccountRegisterReport {
             // Determine category display
             let category = if txn.is_transfer() {
                 "Transfer".to_string()
+            } else if txn.is_starting_balance {
+                "Available to Budget".to_string()
             } else if txn.is_split() {
                 "Split".to_string()
             } else if let Some(cat_id) = txn.category_id {
@@ -217,6 +237,7 @@ impl AccountRegisterReport {
             };
 
             entries.push(RegisterEntry {
+                transaction_id: txn.id,
                 date: txn.date,
                 payee: txn.payee_name.clone(),
                 category,
@@ -233,7 +254,7 @@ impl AccountRegisterReport {
             account_id,
             account_name: account.name.clone(),
             starting_balance,
-            ending_balance: running_balance,
+            ending_balance,
             entries,
             total_inflows,
             total_outflows,
@@ -241,6 +262,16 @@ impl AccountRegisterReport {
         })
     }
 
+    /// Running balance after each transaction in the report, by ID
+    ///
+    /// For attaching balances to transactions listed in another order.
+    pub fn running_balances(&self) -> HashMap<TransactionId, Money> {
+        self.entries
+            .iter()
+            .map(|e| (e.transaction_id, e.running_balance))
+            .collect()
+    }
+
     /// Format the report for terminal display
     pub fn format_terminal(&self) -> String {
         let mut output = String::new();
@@ -314,6 +345,52 @@ impl AccountRegisterReport {
         output
     }
 
+    /// Format the report as a Markdown table
+    pub fn format_markdown(&self) -> String {
+        let mut output = format!("## Account Register: {}\n\n", self.account_name);
+        if let Some(start) = self.filter.start_date {
+            output.push_str(&format!("- From: {}\n", start));
+        }
+        if let Some(end) = self.filter.end_date {
+            output.push_str(&format!("- To: {}\n", end));
+        }
+        output.push_str(&format!("- Starting Balance: {}\n", self.starting_balance));
+        output.push_str(&format!("- Ending Balance: {}\n\n", self.ending_balance));
+
+        let mut table = MarkdownTable::new(&[
+            ("Date", Align::Left),
+            ("Payee", Align::Left),
+            ("Category", Align::Left),
+            ("Amount", Align::Right),
+            ("Balance", Align::Right),
+            ("Clr", Align::Left),
+        ]);
+        for entry in &self.entries {
+            let status_char = match entry.status {
+                TransactionStatus::Pending => "",
+                TransactionStatus::Cleared => "C",
+                TransactionStatus::Reconciled => "R",
+            };
+            table.row(vec![
+                entry.date.to_string(),
+                entry.payee.clone(),
+                entry.category.clone(),
+                entry.amount.to_string(),
+                entry.running_balance.to_string(),
+                status_char.to_string(),
+            ]);
+        }
+
+        output.push_str(&table.render());
+        output.push_str(&format!(
+            "\nTotal Inflows: {} | Total Outflows: {} | Transactions: {}\n",
+            self.total_inflows,
+            self.total_outflows.abs(),
+            self.entries.len()
+        ));
+        output
+    }
+
     /// Export the report to CSV format
     pub fn export_csv<W: Write>(&self, writer: &mut W) -> EnvelopeResult<()> {
         // Write header
@@ -414,7 +491,7 @@ fn escape_csv_field(s: &str) -> String {
 mod tests {
     use super::*;
     use crate::config::paths::EnvelopePaths;
-    use crate::models::{Account, AccountType, Category, CategoryGroup};
+    use crate::models::{Account, AccountType, Category, CategoryGroup, TransactionFlag};
     use tempfile::TempDir;
 
     fn create_test_storage() -> (TempDir, Storage) {
@@ -479,13 +556,16 @@ mod tests {
         let account = Account::new("Checking", AccountType::Checking);
         storage.accounts.upsert(account.clone()).unwrap();
 
-        // Add transactions on different dates
+        // Add transactions on different dates, flagging the last one
         for day in 1..10 {
-            let txn = Transaction::new(
+            let mut txn = Transaction::new(
                 account.id,
                 NaiveDate::from_ymd_opt(2025, 1, day).unwrap(),
                 Money::from_cents(-1000),
             );
+            if day == 9 {
+                txn.flag = TransactionFlag::Review;
+            }
             storage.transactions.upsert(txn).unwrap();
         }
 
@@ -499,6 +579,70 @@ mod tests {
         let report = AccountRegisterReport::generate(&storage, account.id, filter).unwrap();
 
         assert_eq!(report.entries.len(), 5); // Days 3, 4, 5, 6, 7
+
+        let filter = RegisterFilter {
+            flagged_only: true,
+            ..Default::default()
+        };
+        let report = AccountRegisterReport::generate(&storage, account.id, filter).unwrap();
+        assert_eq!(report.entries.len(), 1);
+    }
+
+    #[test]
+    fn test_running_balance_ends_at_account_balance() {
+        let (_temp_dir, storage) = create_test_storage();
+
+        let account = Account::with_starting_balance(
+            "Checking",
+            AccountType::Checking,
+            Money::from_cents(100000),
+        );
+        storage.accounts.upsert(account.clone()).unwrap();
+
+        let group = CategoryGroup::new("Test");
+        storage.categories.upsert_group(group.clone()).unwrap();
+        let cat = Category::new("Groceries", group.id);
+        storage.categories.upsert_category(cat.clone()).unwrap();
+
+        // Inserted out of date order
+        for (day, cents, category) in [
+            (20, -2500, Some(cat.id)),
+            (5, 50000, None),
+            (12, -7500, Some(cat.id)),
+        ] {
+            let mut txn = Transaction::new(
+                account.id,
+                NaiveDate::from_ymd_opt(2025, 1, day).unwrap(),
+                Money::from_cents(cents),
+            );
+            txn.category_id = category;
+            storage.transactions.upsert(txn).unwrap();
+        }
+
+        let report =
+            AccountRegisterReport::generate(&storage, account.id, RegisterFilter::default())
+                .unwrap();
+        let balances: Vec<i64> = report
+            .entries
+            .iter()
+            .map(|e| e.running_balance.cents())
+            .collect();
+        assert_eq!(balances, [150000, 142500, 140000]);
+
+        let current = AccountService::new(&storage)
+            .calculate_balance(account.id)
+            .unwrap();
+        assert_eq!(report.entries.last().unwrap().running_balance, current);
+        assert_eq!(report.ending_balance, current);
+
+        // Hidden transactions still count toward the balances shown
+        let filter = RegisterFilter {
+            category_id: Some(cat.id),
+            ..Default::default()
+        };
+        let report = AccountRegisterReport::generate(&storage, account.id, filter).unwrap();
+        assert_eq!(report.entries[0].running_balance.cents(), 142500);
+        assert_eq!(report.ending_balance, current);
     }
 
     #[test]
@@ -1,16 +1,21 @@
 //! Budget Overview Report
 //!
 //! Generates a comprehensive budget overview showing all categories
-//! with budgeted, activity (spending), and available amounts.
+//! with budgeted, activity (spending), and available amounts, plus a
+//! budget vs. actual variance view.
 
+use crate::display::{MoneyColumn, NegativeStyle};
 use crate::error::EnvelopeResult;
-use crate::models::{BudgetPeriod, CategoryGroupId, CategoryId, Money};
+use crate::models::{BudgetPeriod, CategoryBudgetSummary, CategoryGroupId, CategoryId, Money};
+use crate::reports::markdown::{Align, MarkdownTable};
 use crate::services::{BudgetService, CategoryService};
 use crate::storage::Storage;
+use serde::Serialize;
+use std::collections::HashMap;
 use std::io::Write;
 
 /// A row in the budget report for a single category
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct CategoryReportRow {
     /// Category ID
     pub category_id: CategoryId,
@@ -26,6 +31,10 @@ pub struct CategoryReportRow {
     pub activity: Money,
     /// Available balance (budgeted + carryover + activity)
     pub available: Money,
+    /// Percent of the budgeted amount spent (infinite if spent unbudgeted)
+    pub percent_used: f64,
+    /// Budgeted minus spent (negative when over budget)
+    pub variance: Money,
 }
 
 impl CategoryReportRow {
@@ -33,10 +42,30 @@ impl CategoryReportRow {
     pub fn is_overspent(&self) -> bool {
         self.available.is_negative()
     }
+
+    /// Check if more than the budgeted amount was spent this period
+    pub fn is_over_budget(&self) -> bool {
+        self.percent_used > 100.0
+    }
+}
+
+/// Percent of `budgeted` used by `activity` (negative activity is spending)
+///
+/// With nothing budgeted, any spending counts as infinitely over budget
+/// rather than dividing by zero.
+fn percent_used(budgeted: Money, activity: Money) -> f64 {
+    let spent = -activity.cents();
+    if budgeted.cents() > 0 {
+        spent as f64 / budgeted.cents() as f64 * 100.0
+    } else if spent > 0 {
+        f64::INFINITY
+    } else {
+        0.0
+    }
 }
 
 /// A row in the budget report for a category group with totals
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct GroupReportRow {
     /// Group ID
     pub group_id: CategoryGroupId,
@@ -52,6 +81,10 @@ pub struct GroupReportRow {
     pub total_activity: Money,
     /// Total available for this group
     pub total_available: Money,
+    /// Percent of the group's budget spent
+    pub percent_used: f64,
+    /// Group budgeted minus spent
+    pub variance: Money,
 }
 
 impl GroupReportRow {
@@ -65,6 +98,8 @@ impl GroupReportRow {
             total_carryover: Money::zero(),
             total_activity: Money::zero(),
             total_available: Money::zero(),
+            percent_used: 0.0,
+            variance: Money::zero(),
         }
     }
 
@@ -74,6 +109,8 @@ impl GroupReportRow {
         self.total_carryover += category.carryover;
         self.total_activity += category.activity;
         self.total_available += category.available;
+        self.variance += category.variance;
+        self.percent_used = percent_used(self.total_budgeted, self.total_activity);
         self.categories.push(category);
     }
 
@@ -84,7 +121,7 @@ impl GroupReportRow {
 }
 
 /// Budget Overview Report
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct BudgetOverviewReport {
     /// The budget period for this report
     pub period: BudgetPeriod,
@@ -112,6 +149,12 @@ impl BudgetOverviewReport {
         let groups = category_service.list_groups()?;
         let categories = category_service.list_categories()?;
 
+        let mut summaries: HashMap<CategoryId, CategoryBudgetSummary> = budget_service
+            .get_category_summaries(period)?
+            .into_iter()
+            .map(|s| (s.category_id, s))
+            .collect();
+
         let mut report_groups: Vec<GroupReportRow> = Vec::new();
         let mut grand_total_budgeted = Money::zero();
         let mut grand_total_carryover = Money::zero();
@@ -124,7 +167,9 @@ impl BudgetOverviewReport {
 
             // Find categories in this group
             for category in categories.iter().filter(|c| c.group_id == group.id) {
-                let summary = budget_service.get_category_summary(category.id, period)?;
+                let summary = summaries
+                    .remove(&category.id)
+                    .unwrap_or_else(|| CategoryBudgetSummary::empty(category.id));
 
                 let category_row = CategoryReportRow {
                     category_id: category.id,
@@ -134,6 +179,8 @@ impl BudgetOverviewReport {
                     carryover: summary.carryover,
                     activity: summary.activity,
                     available: summary.available,
+                    percent_used: percent_used(summary.budgeted, summary.activity),
+                    variance: summary.budgeted + summary.activity,
                 };
 
                 group_row.add_category(category_row);
@@ -163,24 +210,51 @@ impl BudgetOverviewReport {
     }
 
     /// Format the report for terminal display
-    pub fn format_terminal(&self) -> String {
+    ///
+    /// Each amount column is as wide as its widest amount, so decimal
+    /// points line up however large the numbers get.
+    pub fn format_terminal(&self, style: NegativeStyle) -> String {
         let mut output = String::new();
 
+        // Measure every amount that lands in each column
+        let budgeted = MoneyColumn::new(
+            self.column_amounts(|c| c.budgeted, |g| g.total_budgeted)
+                .chain([self.grand_total_budgeted]),
+            12,
+            style,
+        );
+        let activity = MoneyColumn::new(
+            self.column_amounts(|c| c.activity, |g| g.total_activity)
+                .chain([self.grand_total_activity]),
+            12,
+            style,
+        );
+        let available = MoneyColumn::new(
+            self.column_amounts(|c| c.available, |g| g.total_available)
+                .chain([self.grand_total_available]),
+            12,
+            style,
+        );
+        let rule_width = (35 + budgeted.width() + activity.width() + available.width()).max(80);
+
         // Header
         output.push_str(&format!("Budget Overview - {}\n", self.period));
-        output.push_str(&"=".repeat(80));
+        output.push_str(&"=".repeat(rule_width));
         output.push('\n');
         output.push_str(&format!(
             "Available to Budget: {}\n\n",
-            self.available_to_budget
+            style.format(self.available_to_budget).trim_end()
         ));
 
         // Column headers
         output.push_str(&format!(
-            "{:<30} {:>12} {:>12} {:>12}\n",
-            "Category", "Budgeted", "Activity", "Available"
+            "{:<30} {} {} {}\n",
+            "Category",
+            budgeted.pad("Budgeted"),
+            activity.pad("Activity"),
+            available.pad("Available")
         ));
-        output.push_str(&"-".repeat(80));
+        output.push_str(&"-".repeat(rule_width));
         output.push('\n');
 
         // Groups and categories
@@ -189,34 +263,38 @@ impl BudgetOverviewReport {
             output.push_str(&format!("\n{}\n", group.group_name.to_uppercase()));
 
             for category in &group.categories {
-                let available_display = if category.is_overspent() {
-                    format!("{} *", category.available)
-                } else {
-                    category.available.to_string()
-                };
+                // The marker sits after the column so decimals stay aligned
+                let marker = if category.is_overspent() { " *" } else { "" };
 
                 output.push_str(&format!(
-                    "  {:<28} {:>12} {:>12} {:>12}\n",
-                    category.category_name, category.budgeted, category.activity, available_display
+                    "  {:<28} {} {} {}{}\n",
+                    category.category_name,
+                    budgeted.format(category.budgeted),
+                    activity.format(category.activity),
+                    available.format(category.available),
+                    marker
                 ));
             }
 
             // Group total
             output.push_str(&format!(
-                "  {:<28} {:>12} {:>12} {:>12}\n",
-                "Group Total:", group.total_budgeted, group.total_activity, group.total_available
+                "  {:<28} {} {} {}\n",
+                "Group Total:",
+                budgeted.format(group.total_budgeted),
+                activity.format(group.total_activity),
+                available.format(group.total_available)
             ));
         }
 
         // Grand totals
-        output.push_str(&"-".repeat(80));
+        output.push_str(&"-".repeat(rule_width));
         output.push('\n');
         output.push_str(&format!(
-            "{:<30} {:>12} {:>12} {:>12}\n",
+            "{:<30} {} {} {}\n",
             "GRAND TOTAL",
-            self.grand_total_budgeted,
-            self.grand_total_activity,
-            self.grand_total_available
+            budgeted.format(self.grand_total_budgeted),
+            activity.format(self.grand_total_activity),
+            available.format(self.grand_total_available)
         ));
 
         output.push_str("\n* = Overspent\n");
@@ -224,6 +302,71 @@ impl BudgetOverviewReport {
         output
     }
 
+    /// Format the report as a Markdown table
+    ///
+    /// Group names are bold rows above their categories.
+    pub fn format_markdown(&self) -> String {
+        let mut output = format!("## Budget Overview - {}\n\n", self.period);
+        output.push_str(&format!(
+            "Available to Budget: {}\n\n",
+            self.available_to_budget
+        ));
+
+        let mut table = MarkdownTable::new(&[
+            ("Category", Align::Left),
+            ("Budgeted", Align::Right),
+            ("Activity", Align::Right),
+            ("Available", Align::Right),
+        ]);
+        for group in &self.groups {
+            table.bold_row(vec![group.group_name.clone()]);
+
+            for category in &group.categories {
+                let available_display = if category.is_overspent() {
+                    format!("{} (overspent)", category.available)
+                } else {
+                    category.available.to_string()
+                };
+                table.row(vec![
+                    category.category_name.clone(),
+                    category.budgeted.to_string(),
+                    category.activity.to_string(),
+                    available_display,
+                ]);
+            }
+
+            table.row(vec![
+                "Group Total".to_string(),
+                group.total_budgeted.to_string(),
+                group.total_activity.to_string(),
+                group.total_available.to_string(),
+            ]);
+        }
+        table.bold_row(vec![
+            "Grand Total".to_string(),
+            self.grand_total_budgeted.to_string(),
+            self.grand_total_activity.to_string(),
+            self.grand_total_available.to_string(),
+        ]);
+
+        output.push_str(&table.render());
+        output
+    }
+
+    /// One amount from every category row and group total
+    fn column_amounts<'r>(
+        &'r self,
+        category: fn(&CategoryReportRow) -> Money,
+        group: fn(&GroupReportRow) -> Money,
+    ) -> impl Iterator<Item = Money> + 'r {
+        self.groups.iter().flat_map(move |g| {
+            g.categories
+                .iter()
+                .map(category)
+                .chain(std::iter::once(group(g)))
+        })
+    }
+
     /// Export the report to CSV format
     pub fn export_csv<W: Write>(&self, writer: &mut W) -> EnvelopeResult<()> {
         // Write header
@@ -279,6 +422,161 @@ impl BudgetOverviewReport {
         Ok(())
     }
 
+    /// Format the budget vs. actual variance view for terminal display
+    ///
+    /// Categories over 100% of budget are shown in red when `color` is set.
+    pub fn format_variance_terminal(&self, color: bool) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!("Budget vs. Actual - {}\n", self.period));
+        output.push_str(&"=".repeat(80));
+        output.push('\n');
+        output.push_str(&format!(
+            "{:<30} {:>12} {:>12} {:>12} {:>8}\n",
+            "Category", "Budgeted", "Spent", "Variance", "% Used"
+        ));
+        output.push_str(&"-".repeat(80));
+        output.push('\n');
+
+        for group in &self.groups {
+            output.push_str(&format!("\n{}\n", group.group_name.to_uppercase()));
+
+            for category in &group.categories {
+                let line = format!(
+                    "  {:<28} {:>12} {:>12} {:>12} {:>8}",
+                    category.category_name,
+                    category.budgeted,
+                    -category.activity,
+                    category.variance,
+                    format_percent_used(category.percent_used)
+                );
+                output.push_str(&highlight_over(line, category.is_over_budget(), color));
+            }
+
+            let line = format!(
+                "  {:<28} {:>12} {:>12} {:>12} {:>8}",
+                "Group Total:",
+                group.total_budgeted,
+                -group.total_activity,
+                group.variance,
+                format_percent_used(group.percent_used)
+            );
+            output.push_str(&highlight_over(line, group.percent_used > 100.0, color));
+        }
+
+        let total_percent = percent_used(self.grand_total_budgeted, self.grand_total_activity);
+        output.push_str(&"-".repeat(80));
+        output.push('\n');
+        output.push_str(&format!(
+            "{:<30} {:>12} {:>12} {:>12} {:>8}\n",
+            "GRAND TOTAL",
+            self.grand_total_budgeted,
+            -self.grand_total_activity,
+            self.grand_total_budgeted + self.grand_total_activity,
+            format_percent_used(total_percent)
+        ));
+
+        let over = self
+            .groups
+            .iter()
+            .flat_map(|g| &g.categories)
+            .filter(|c| c.is_over_budget())
+            .count();
+        if over > 0 {
+            output.push_str(&format!("\n{} category(ies) over budget\n", over));
+        }
+
+        output
+    }
+
+    /// Format the variance view as a Markdown table
+    pub fn format_variance_markdown(&self) -> String {
+        let mut output = format!("## Budget vs. Actual - {}\n\n", self.period);
+
+        let mut table = MarkdownTable::new(&[
+            ("Category", Align::Left),
+            ("Budgeted", Align::Right),
+            ("Spent", Align::Right),
+            ("Variance", Align::Right),
+            ("% Used", Align::Right),
+        ]);
+        for group in &self.groups {
+            table.bold_row(vec![group.group_name.clone()]);
+
+            for category in &group.categories {
+                table.row(vec![
+                    category.category_name.clone(),
+                    category.budgeted.to_string(),
+                    (-category.activity).to_string(),
+                    category.variance.to_string(),
+                    format_percent_used(category.percent_used),
+                ]);
+            }
+
+            table.row(vec![
+                "Group Total".to_string(),
+                group.total_budgeted.to_string(),
+                (-group.total_activity).to_string(),
+                group.variance.to_string(),
+                format_percent_used(group.percent_used),
+            ]);
+        }
+
+        let total_percent = percent_used(self.grand_total_budgeted, self.grand_total_activity);
+        table.bold_row(vec![
+            "Grand Total".to_string(),
+            self.grand_total_budgeted.to_string(),
+            (-self.grand_total_activity).to_string(),
+            (self.grand_total_budgeted + self.grand_total_activity).to_string(),
+            format_percent_used(total_percent),
+        ]);
+
+        output.push_str(&table.render());
+        output
+    }
+
+    /// Export the variance view to CSV format
+    ///
+    /// Percent used is left empty for spending against a zero budget.
+    pub fn export_variance_csv<W: Write>(&self, writer: &mut W) -> EnvelopeResult<()> {
+        writeln!(
+            writer,
+            "Period,Group,Category,Budgeted,Spent,Variance,Percent Used"
+        )
+        .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
+
+        for group in &self.groups {
+            for category in &group.categories {
+                writeln!(
+                    writer,
+                    "{},{},{},{:.2},{:.2},{:.2},{}",
+                    self.period,
+                    group.group_name,
+                    category.category_name,
+                    category.budgeted.cents() as f64 / 100.0,
+                    -category.activity.cents() as f64 / 100.0,
+                    category.variance.cents() as f64 / 100.0,
+                    csv_percent(category.percent_used),
+                )
+                .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
+            }
+
+            writeln!(
+                writer,
+                "{},{},TOTAL,{:.2},{:.2},{:.2},{}",
+                self.period,
+                group.group_name,
+                group.total_budgeted.cents() as f64 / 100.0,
+                -group.total_activity.cents() as f64 / 100.0,
+                group.variance.cents() as f64 / 100.0,
+                csv_percent(group.percent_used),
+            )
+            .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
     /// Get count of overspent categories
     pub fn overspent_count(&self) -> usize {
         self.groups
@@ -298,6 +596,33 @@ impl BudgetOverviewReport {
     }
 }
 
+/// Format a percent used, showing spending against a zero budget as infinite
+fn format_percent_used(percent: f64) -> String {
+    if percent.is_infinite() {
+        "∞".to_string()
+    } else {
+        format!("{:.0}%", percent)
+    }
+}
+
+/// Format a percent used for CSV, empty when infinite
+fn csv_percent(percent: f64) -> String {
+    if percent.is_infinite() {
+        String::new()
+    } else {
+        format!("{:.1}", percent)
+    }
+}
+
+/// Terminate a report line, in red if it is over budget and color is on
+fn highlight_over(line: String, over: bool, color: bool) -> String {
+    if over && color {
+        format!("\x1b[31m{}\x1b[0m\n", line)
+    } else {
+        format!("{}\n", line)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -370,6 +695,49 @@ mod tests {
         assert_eq!(report.grand_total_budgeted.cents(), 70000);
     }
 
+    #[test]
+    fn test_variance() {
+        let (_temp_dir, storage) = create_test_storage();
+        let period = setup_test_data(&storage);
+
+        // Spend against a category with no budget
+        let group = storage.categories.get_all_groups().unwrap()[0].clone();
+        let unbudgeted = Category::new("Gifts", group.id);
+        storage
+            .categories
+            .upsert_category(unbudgeted.clone())
+            .unwrap();
+        let account = storage.accounts.get_all().unwrap()[0].clone();
+        let mut txn = Transaction::new(
+            account.id,
+            NaiveDate::from_ymd_opt(2025, 1, 20).unwrap(),
+            Money::from_cents(-1500),
+        );
+        txn.category_id = Some(unbudgeted.id);
+        storage.transactions.upsert(txn).unwrap();
+
+        let report = BudgetOverviewReport::generate(&storage, &period).unwrap();
+        let rows: Vec<&CategoryReportRow> =
+            report.groups.iter().flat_map(|g| &g.categories).collect();
+        let row = |name: &str| *rows.iter().find(|c| c.category_name == name).unwrap();
+
+        let groceries = row("Groceries");
+        assert_eq!(groceries.variance.cents(), 47000);
+        assert!((groceries.percent_used - 6.0).abs() < 1e-9);
+
+        assert_eq!(row("Dining Out").percent_used, 0.0);
+
+        let gifts = row("Gifts");
+        assert!(gifts.percent_used.is_infinite());
+        assert!(gifts.is_over_budget());
+        assert_eq!(gifts.variance.cents(), -1500);
+
+        let output = report.format_variance_terminal(true);
+        assert!(output.contains("∞"));
+        assert!(output.contains("\x1b[31m  Gifts"));
+        assert!(!report.format_variance_terminal(false).contains("\x1b["));
+    }
+
     #[test]
     fn test_csv_export() {
         let (_temp_dir, storage) = create_test_storage();
@@ -392,10 +760,26 @@ mod tests {
         let period = setup_test_data(&storage);
 
         let report = BudgetOverviewReport::generate(&storage, &period).unwrap();
-        let output = report.format_terminal();
+        let output = report.format_terminal(NegativeStyle::Minus);
 
         assert!(output.contains("Budget Overview"));
         assert!(output.contains("Groceries"));
         assert!(output.contains("GRAND TOTAL"));
     }
+
+    #[test]
+    fn test_markdown_format() {
+        let (_temp_dir, storage) = create_test_storage();
+        let period = setup_test_data(&storage);
+
+        let report = BudgetOverviewReport::generate(&storage, &period).unwrap();
+        let output = report.format_markdown();
+
+        assert!(output.starts_with("## Budget Overview - 2025-01"));
+        assert!(output.contains("| Category | Budgeted | Activity | Available |"));
+        assert!(output.contains("| --- | ---: | ---: | ---: |"));
+        assert!(output.contains("| **Test Group** |  |  |  |"));
+        assert!(output.contains("| Groceries | $500.00 | -$30.00 | $470.00 |"));
+        assert!(output.contains("| **Grand Total** | **$700.00** |"));
+    }
 }
@@ -0,0 +1,254 @@
+//! Cashflow Report
+//!
+//! Totals a period's income and expenses and the net between them. Transfers
+//! between accounts move money without earning or spending it, so they are
+//! left out of both sides.
+
+use crate::error::EnvelopeResult;
+use crate::models::{BudgetPeriod, Money, Transaction};
+use crate::reports::markdown::{Align, MarkdownTable};
+use crate::storage::Storage;
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::io::Write;
+
+/// Income vs. expense summary for a period
+#[derive(Debug, Clone, Serialize)]
+pub struct CashflowReport {
+    /// The period reported on
+    pub period: BudgetPeriod,
+    /// First day of the period
+    pub start_date: NaiveDate,
+    /// Last day of the period
+    pub end_date: NaiveDate,
+    /// Money coming in
+    pub income: Money,
+    /// Money going out, as a positive amount
+    pub expenses: Money,
+    /// Income minus expenses
+    pub net: Money,
+    /// Number of transactions counted as income
+    pub income_count: usize,
+    /// Number of transactions counted as expenses
+    pub expense_count: usize,
+}
+
+impl CashflowReport {
+    /// Generate a cashflow report for a period
+    ///
+    /// Transfers (`transfer_transaction_id` set) and opening balances are
+    /// skipped, as are the transfer parts of split transactions.
+    pub fn generate(storage: &Storage, period: &BudgetPeriod) -> EnvelopeResult<Self> {
+        let start_date = period.start_date();
+        let end_date = period.end_date();
+
+        let mut income = Money::zero();
+        let mut expenses = Money::zero();
+        let mut income_count = 0;
+        let mut expense_count = 0;
+
+        for txn in storage
+            .transactions
+            .get_by_date_range(start_date, end_date)?
+        {
+            if txn.transfer_transaction_id.is_some() || txn.is_starting_balance {
+                continue;
+            }
+
+            let amount = amount_excluding_transfers(&txn);
+            if amount.is_positive() {
+                income += amount;
+                income_count += 1;
+            } else if amount.is_negative() {
+                expenses += -amount;
+                expense_count += 1;
+            }
+        }
+
+        Ok(Self {
+            period: period.clone(),
+            start_date,
+            end_date,
+            income,
+            expenses,
+            net: income - expenses,
+            income_count,
+            expense_count,
+        })
+    }
+
+    /// Format the report for terminal display
+    pub fn format_terminal(&self) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!(
+            "Cashflow Report: {} ({} to {})\n",
+            self.period, self.start_date, self.end_date
+        ));
+        output.push_str(&"=".repeat(50));
+        output.push('\n');
+
+        output.push_str(&format!(
+            "{:<12} {:>15} {:>12}\n",
+            "", "Amount", "Transactions"
+        ));
+        output.push_str(&format!(
+            "{:<12} {:>15} {:>12}\n",
+            "Income",
+            self.income.to_string(),
+            self.income_count
+        ));
+        output.push_str(&format!(
+            "{:<12} {:>15} {:>12}\n",
+            "Expenses",
+            self.expenses.to_string(),
+            self.expense_count
+        ));
+        output.push_str(&"-".repeat(50));
+        output.push('\n');
+        output.push_str(&format!("{:<12} {:>15}\n", "Net", self.net.to_string()));
+
+        output
+    }
+
+    /// Format the report as a Markdown table
+    pub fn format_markdown(&self) -> String {
+        let mut output = format!(
+            "## Cashflow Report: {}\n\n- From: {}\n- To: {}\n\n",
+            self.period, self.start_date, self.end_date
+        );
+
+        let mut table = MarkdownTable::new(&[
+            ("", Align::Left),
+            ("Amount", Align::Right),
+            ("Transactions", Align::Right),
+        ]);
+        table.row(vec![
+            "Income".to_string(),
+            self.income.to_string(),
+            self.income_count.to_string(),
+        ]);
+        table.row(vec![
+            "Expenses".to_string(),
+            self.expenses.to_string(),
+            self.expense_count.to_string(),
+        ]);
+        table.bold_row(vec!["Net".to_string(), self.net.to_string()]);
+
+        output.push_str(&table.render());
+        output
+    }
+
+    /// Export the report to CSV format
+    pub fn export_csv<W: Write>(&self, writer: &mut W) -> EnvelopeResult<()> {
+        writeln!(
+            writer,
+            "Period,Start Date,End Date,Income,Expenses,Net,Income Count,Expense Count"
+        )
+        .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
+
+        writeln!(
+            writer,
+            "{},{},{},{:.2},{:.2},{:.2},{},{}",
+            self.period,
+            self.start_date,
+            self.end_date,
+            self.income.cents() as f64 / 100.0,
+            self.expenses.cents() as f64 / 100.0,
+            self.net.cents() as f64 / 100.0,
+            self.income_count,
+            self.expense_count
+        )
+        .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
+
+        Ok(())
+    }
+}
+
+/// A transaction's amount less any split parts sent to other accounts
+fn amount_excluding_transfers(txn: &Transaction) -> Money {
+    let transferred: Money = txn
+        .splits
+        .iter()
+        .filter(|s| s.is_transfer())
+        .map(|s| s.amount)
+        .sum();
+    txn.amount - transferred
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::paths::EnvelopePaths;
+    use crate::models::{Account, AccountType, Split};
+    use crate::services::TransferService;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (TempDir, Storage) {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let mut storage = Storage::new(paths).unwrap();
+        storage.load_all().unwrap();
+        (temp_dir, storage)
+    }
+
+    #[test]
+    fn test_cashflow_excludes_transfers() {
+        let (_temp_dir, storage) = create_test_storage();
+        let date = |day| NaiveDate::from_ymd_opt(2025, 1, day).unwrap();
+
+        let checking = Account::new("Checking", AccountType::Checking);
+        let savings = Account::new("Savings", AccountType::Savings);
+        storage.accounts.upsert(checking.clone()).unwrap();
+        storage.accounts.upsert(savings.clone()).unwrap();
+
+        for (day, cents) in [(1, 300000), (5, -120000), (20, -4550)] {
+            storage
+                .transactions
+                .upsert(Transaction::new(
+                    checking.id,
+                    date(day),
+                    Money::from_cents(cents),
+                ))
+                .unwrap();
+        }
+
+        // Outside the period
+        storage
+            .transactions
+            .upsert(Transaction::new(
+                checking.id,
+                NaiveDate::from_ymd_opt(2025, 2, 1).unwrap(),
+                Money::from_cents(-9900),
+            ))
+            .unwrap();
+
+        // Moving money to savings is neither income nor spending
+        TransferService::new(&storage)
+            .create_transfer(
+                checking.id,
+                savings.id,
+                Money::from_cents(50000),
+                date(10),
+                None,
+            )
+            .unwrap();
+
+        // Only the spent part of a split with a transfer counts
+        let mut split = Transaction::new(checking.id, date(15), Money::from_cents(-10000));
+        split.add_split(Split::new(
+            crate::models::CategoryId::new(),
+            Money::from_cents(-4000),
+        ));
+        split.add_split(Split::transfer(savings.id, Money::from_cents(-6000)));
+        storage.transactions.upsert(split).unwrap();
+
+        let report = CashflowReport::generate(&storage, &BudgetPeriod::monthly(2025, 1)).unwrap();
+
+        assert_eq!(report.income.cents(), 300000);
+        assert_eq!(report.income_count, 1);
+        assert_eq!(report.expenses.cents(), 128550);
+        assert_eq!(report.expense_count, 3);
+        assert_eq!(report.net.cents(), 171450);
+    }
+}
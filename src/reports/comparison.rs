@@ -0,0 +1,388 @@
+//! Period Comparison Report
+//!
+//! Shows each category's budgeted and activity amounts for two budget
+//! periods side by side, with the change from the first to the second.
+
+use crate::error::{EnvelopeError, EnvelopeResult};
+use crate::models::{BudgetPeriod, CategoryId, Money};
+use crate::reports::markdown::{Align, MarkdownTable};
+use crate::services::BudgetService;
+use crate::storage::Storage;
+use serde::Serialize;
+use std::io::Write;
+
+/// One category's amounts in both periods
+#[derive(Debug, Clone, Serialize)]
+pub struct ComparisonRow {
+    /// Category ID
+    pub category_id: CategoryId,
+    /// Category name
+    pub category_name: String,
+    /// Amount budgeted in the first period
+    pub budgeted_a: Money,
+    /// Activity in the first period (negative is spending)
+    pub activity_a: Money,
+    /// Amount budgeted in the second period
+    pub budgeted_b: Money,
+    /// Activity in the second period (negative is spending)
+    pub activity_b: Money,
+}
+
+impl ComparisonRow {
+    /// A row with zeros on both sides, named from storage
+    fn empty(storage: &Storage, category_id: CategoryId) -> EnvelopeResult<Self> {
+        let category_name = storage
+            .categories
+            .get_category(category_id)?
+            .map(|c| c.name)
+            .unwrap_or_else(|| "(deleted category)".to_string());
+
+        Ok(Self {
+            category_id,
+            category_name,
+            budgeted_a: Money::zero(),
+            activity_a: Money::zero(),
+            budgeted_b: Money::zero(),
+            activity_b: Money::zero(),
+        })
+    }
+
+    /// Change in the budgeted amount from the first period to the second
+    pub fn budgeted_delta(&self) -> Money {
+        self.budgeted_b - self.budgeted_a
+    }
+
+    /// Change in activity from the first period to the second
+    ///
+    /// Negative when more was spent in the second period.
+    pub fn activity_delta(&self) -> Money {
+        self.activity_b - self.activity_a
+    }
+}
+
+/// Period Comparison Report
+#[derive(Debug, Clone, Serialize)]
+pub struct PeriodComparisonReport {
+    /// The first (usually earlier) period
+    pub period_a: BudgetPeriod,
+    /// The second period
+    pub period_b: BudgetPeriod,
+    /// Categories with anything budgeted or spent in either period
+    pub rows: Vec<ComparisonRow>,
+}
+
+impl PeriodComparisonReport {
+    /// Generate a comparison of two budget periods
+    ///
+    /// A category that only has amounts in one period still gets a row,
+    /// with zeros for the other.
+    pub fn generate(
+        storage: &Storage,
+        period_a: &BudgetPeriod,
+        period_b: &BudgetPeriod,
+    ) -> EnvelopeResult<Self> {
+        let budget_service = BudgetService::new(storage);
+        let overview_a = budget_service.get_budget_overview(period_a)?;
+        let overview_b = budget_service.get_budget_overview(period_b)?;
+
+        // Join by category, keeping the first overview's order
+        let mut rows: Vec<ComparisonRow> = Vec::new();
+        for a in &overview_a.categories {
+            let mut row = ComparisonRow::empty(storage, a.category_id)?;
+            row.budgeted_a = a.budgeted;
+            row.activity_a = a.activity;
+            rows.push(row);
+        }
+        for b in &overview_b.categories {
+            let index = match rows.iter().position(|r| r.category_id == b.category_id) {
+                Some(index) => index,
+                None => {
+                    rows.push(ComparisonRow::empty(storage, b.category_id)?);
+                    rows.len() - 1
+                }
+            };
+            rows[index].budgeted_b = b.budgeted;
+            rows[index].activity_b = b.activity;
+        }
+
+        rows.retain(|r| {
+            [r.budgeted_a, r.activity_a, r.budgeted_b, r.activity_b]
+                .iter()
+                .any(|m| !m.is_zero())
+        });
+
+        Ok(Self {
+            period_a: period_a.clone(),
+            period_b: period_b.clone(),
+            rows,
+        })
+    }
+
+    /// Total budgeted in each period
+    pub fn total_budgeted(&self) -> (Money, Money) {
+        (
+            self.rows.iter().map(|r| r.budgeted_a).sum(),
+            self.rows.iter().map(|r| r.budgeted_b).sum(),
+        )
+    }
+
+    /// Total activity in each period
+    pub fn total_activity(&self) -> (Money, Money) {
+        (
+            self.rows.iter().map(|r| r.activity_a).sum(),
+            self.rows.iter().map(|r| r.activity_b).sum(),
+        )
+    }
+
+    /// Format the report for terminal display
+    ///
+    /// Activity changes are shown in red when spending went up and green
+    /// when it went down, if `color` is set.
+    pub fn format_terminal(&self, color: bool) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!(
+            "Period Comparison - {} vs {}\n",
+            self.period_a, self.period_b
+        ));
+        output.push_str(&"=".repeat(100));
+        output.push('\n');
+
+        if self.rows.is_empty() {
+            output.push_str("Nothing budgeted or spent in either period.\n");
+            return output;
+        }
+
+        output.push_str(&format!(
+            "{:<24} {:>12} {:>12} {:>12} {:>12} {:>12} {:>12}\n",
+            "Category",
+            format!("Bud {}", self.period_a),
+            format!("Bud {}", self.period_b),
+            "Bud Change",
+            format!("Act {}", self.period_a),
+            format!("Act {}", self.period_b),
+            "Act Change"
+        ));
+        output.push_str(&"-".repeat(100));
+        output.push('\n');
+
+        for row in &self.rows {
+            output.push_str(&format!(
+                "{:<24} {:>12} {:>12} {:>12} {:>12} {:>12} {}\n",
+                truncate(&row.category_name, 24),
+                row.budgeted_a,
+                row.budgeted_b,
+                row.budgeted_delta(),
+                row.activity_a,
+                row.activity_b,
+                colorize_delta(row.activity_delta(), color)
+            ));
+        }
+
+        let (budgeted_a, budgeted_b) = self.total_budgeted();
+        let (activity_a, activity_b) = self.total_activity();
+        output.push_str(&"-".repeat(100));
+        output.push('\n');
+        output.push_str(&format!(
+            "{:<24} {:>12} {:>12} {:>12} {:>12} {:>12} {}\n",
+            "TOTAL",
+            budgeted_a,
+            budgeted_b,
+            budgeted_b - budgeted_a,
+            activity_a,
+            activity_b,
+            colorize_delta(activity_b - activity_a, color)
+        ));
+
+        output
+    }
+
+    /// Format the report as a Markdown table
+    pub fn format_markdown(&self) -> String {
+        let mut output = format!(
+            "## Period Comparison - {} vs {}\n\n",
+            self.period_a, self.period_b
+        );
+
+        if self.rows.is_empty() {
+            output.push_str("Nothing budgeted or spent in either period.\n");
+            return output;
+        }
+
+        let budgeted_a_header = format!("Bud {}", self.period_a);
+        let budgeted_b_header = format!("Bud {}", self.period_b);
+        let activity_a_header = format!("Act {}", self.period_a);
+        let activity_b_header = format!("Act {}", self.period_b);
+        let mut table = MarkdownTable::new(&[
+            ("Category", Align::Left),
+            (&budgeted_a_header, Align::Right),
+            (&budgeted_b_header, Align::Right),
+            ("Bud Change", Align::Right),
+            (&activity_a_header, Align::Right),
+            (&activity_b_header, Align::Right),
+            ("Act Change", Align::Right),
+        ]);
+        for row in &self.rows {
+            table.row(vec![
+                row.category_name.clone(),
+                row.budgeted_a.to_string(),
+                row.budgeted_b.to_string(),
+                row.budgeted_delta().to_string(),
+                row.activity_a.to_string(),
+                row.activity_b.to_string(),
+                row.activity_delta().to_string(),
+            ]);
+        }
+
+        let (budgeted_a, budgeted_b) = self.total_budgeted();
+        let (activity_a, activity_b) = self.total_activity();
+        table.bold_row(vec![
+            "Total".to_string(),
+            budgeted_a.to_string(),
+            budgeted_b.to_string(),
+            (budgeted_b - budgeted_a).to_string(),
+            activity_a.to_string(),
+            activity_b.to_string(),
+            (activity_b - activity_a).to_string(),
+        ]);
+
+        output.push_str(&table.render());
+        output
+    }
+
+    /// Export the report to CSV format
+    pub fn export_csv<W: Write>(&self, writer: &mut W) -> EnvelopeResult<()> {
+        writeln!(
+            writer,
+            "Category ID,Category,Budgeted {a},Budgeted {b},Budgeted Change,Activity {a},Activity {b},Activity Change",
+            a = self.period_a,
+            b = self.period_b
+        )
+        .map_err(|e| EnvelopeError::Export(e.to_string()))?;
+
+        for row in &self.rows {
+            writeln!(
+                writer,
+                "{},\"{}\",{:.2},{:.2},{:.2},{:.2},{:.2},{:.2}",
+                row.category_id,
+                row.category_name.replace('"', "\"\""),
+                row.budgeted_a.cents() as f64 / 100.0,
+                row.budgeted_b.cents() as f64 / 100.0,
+                row.budgeted_delta().cents() as f64 / 100.0,
+                row.activity_a.cents() as f64 / 100.0,
+                row.activity_b.cents() as f64 / 100.0,
+                row.activity_delta().cents() as f64 / 100.0,
+            )
+            .map_err(|e| EnvelopeError::Export(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Right-align an activity change, red if spending rose and green if it fell
+fn colorize_delta(delta: Money, color: bool) -> String {
+    let text = format!("{:>12}", delta);
+    if !color || delta.is_zero() {
+        text
+    } else if delta.is_negative() {
+        format!("\x1b[31m{}\x1b[0m", text)
+    } else {
+        format!("\x1b[32m{}\x1b[0m", text)
+    }
+}
+
+/// Truncate a string to a maximum number of characters
+fn truncate(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
+        s.to_string()
+    } else {
+        let truncated: String = s.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", truncated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::paths::EnvelopePaths;
+    use crate::models::{Account, AccountType, Category, CategoryGroup, Transaction};
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (TempDir, Storage) {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let mut storage = Storage::new(paths).unwrap();
+        storage.load_all().unwrap();
+        (temp_dir, storage)
+    }
+
+    #[test]
+    fn test_period_comparison() {
+        let (_temp_dir, storage) = create_test_storage();
+
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+
+        let group = CategoryGroup::new("Needs");
+        storage.categories.upsert_group(group.clone()).unwrap();
+        let groceries = Category::new("Groceries", group.id);
+        let gifts = Category::new("Gifts", group.id);
+        let unused = Category::new("Unused", group.id);
+        for category in [&groceries, &gifts, &unused] {
+            storage
+                .categories
+                .upsert_category(category.clone())
+                .unwrap();
+        }
+
+        let jan = BudgetPeriod::monthly(2025, 1);
+        let feb = BudgetPeriod::monthly(2025, 2);
+        let budget_service = BudgetService::new(&storage);
+        budget_service
+            .assign_to_category(groceries.id, &jan, Money::from_cents(40000))
+            .unwrap();
+        budget_service
+            .assign_to_category(groceries.id, &feb, Money::from_cents(45000))
+            .unwrap();
+
+        for (day, month, cents, category_id) in [
+            (10, 1, -30000, groceries.id),
+            (10, 2, -42000, groceries.id),
+            (14, 2, -5000, gifts.id),
+        ] {
+            let mut txn = Transaction::new(
+                account.id,
+                NaiveDate::from_ymd_opt(2025, month, day).unwrap(),
+                Money::from_cents(cents),
+            );
+            txn.category_id = Some(category_id);
+            storage.transactions.upsert(txn).unwrap();
+        }
+
+        let report = PeriodComparisonReport::generate(&storage, &jan, &feb).unwrap();
+        assert_eq!(report.rows.len(), 2);
+
+        let row = report
+            .rows
+            .iter()
+            .find(|r| r.category_id == groceries.id)
+            .unwrap();
+        assert_eq!(row.budgeted_delta(), Money::from_cents(5000));
+        assert_eq!(row.activity_delta(), Money::from_cents(-12000));
+
+        // Only spent in February, so January shows zeros
+        let row = report
+            .rows
+            .iter()
+            .find(|r| r.category_id == gifts.id)
+            .unwrap();
+        assert_eq!(row.activity_a, Money::zero());
+        assert_eq!(row.activity_b, Money::from_cents(-5000));
+
+        let output = report.format_terminal(true);
+        assert!(output.contains("\x1b[31m"));
+        assert!(!report.format_terminal(false).contains("\x1b["));
+    }
+}
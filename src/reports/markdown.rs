@@ -0,0 +1,106 @@
+//! Markdown table output
+//!
+//! Builds GitHub-flavored Markdown tables so reports can be pasted into
+//! notes. Amount columns are right-aligned with the `---:` syntax.
+
+/// Alignment of a Markdown table column
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Align {
+    /// Text columns
+    Left,
+    /// Amount and count columns
+    Right,
+}
+
+/// A GitHub-flavored Markdown table
+#[derive(Debug, Clone)]
+pub struct MarkdownTable {
+    headers: Vec<(String, Align)>,
+    rows: Vec<Vec<String>>,
+}
+
+impl MarkdownTable {
+    /// Create a table with the given column headers and alignments
+    pub fn new(columns: &[(&str, Align)]) -> Self {
+        Self {
+            headers: columns
+                .iter()
+                .map(|(name, align)| (name.to_string(), *align))
+                .collect(),
+            rows: Vec::new(),
+        }
+    }
+
+    /// Add a row of cells
+    ///
+    /// Missing cells are left empty and extra cells are dropped.
+    pub fn row(&mut self, cells: Vec<String>) {
+        let mut cells: Vec<String> = cells.iter().map(|c| escape(c)).collect();
+        cells.resize(self.headers.len(), String::new());
+        self.rows.push(cells);
+    }
+
+    /// Add a row with every non-empty cell in bold, for headers and totals
+    pub fn bold_row(&mut self, cells: Vec<String>) {
+        self.row(cells);
+        if let Some(last) = self.rows.last_mut() {
+            for cell in last.iter_mut().filter(|c| !c.is_empty()) {
+                *cell = format!("**{}**", cell);
+            }
+        }
+    }
+
+    /// Render the table, one line per row
+    pub fn render(&self) -> String {
+        let mut output = String::new();
+
+        let names: Vec<String> = self.headers.iter().map(|(name, _)| escape(name)).collect();
+        output.push_str(&format_line(&names));
+
+        let separators: Vec<String> = self
+            .headers
+            .iter()
+            .map(|(_, align)| match align {
+                Align::Left => "---".to_string(),
+                Align::Right => "---:".to_string(),
+            })
+            .collect();
+        output.push_str(&format_line(&separators));
+
+        for row in &self.rows {
+            output.push_str(&format_line(row));
+        }
+
+        output
+    }
+}
+
+/// Join cells into a `| a | b |` line
+fn format_line(cells: &[String]) -> String {
+    format!("| {} |\n", cells.join(" | "))
+}
+
+/// Escape characters that would break a table cell
+fn escape(cell: &str) -> String {
+    cell.replace('|', "\\|").replace('\n', " ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_markdown_table() {
+        let mut table = MarkdownTable::new(&[("Category", Align::Left), ("Amount", Align::Right)]);
+        table.bold_row(vec!["Needs".to_string()]);
+        table.row(vec!["Rent | Utilities".to_string(), "$1500.00".to_string()]);
+
+        assert_eq!(
+            table.render(),
+            "| Category | Amount |\n\
+             | --- | ---: |\n\
+             | **Needs** |  |\n\
+             | Rent \\| Utilities | $1500.00 |\n"
+        );
+    }
+}
@@ -1,14 +1,26 @@
 //! Reports module for EnvelopeCLI
 //!
 //! Provides various financial reports including budget overview,
-//! spending analysis, account registers, and net worth summaries.
+//! spending analysis, income vs. expense cashflow, period comparisons,
+//! account registers, net worth summaries, and archived monthly summaries,
+//! with terminal, CSV, and Markdown output.
 
 pub mod account_register;
 pub mod budget_overview;
+pub mod cashflow;
+pub mod comparison;
+pub mod markdown;
+pub mod monthly;
 pub mod net_worth;
 pub mod spending;
+pub mod tags;
 
 pub use account_register::{AccountRegisterReport, RegisterEntry, RegisterFilter};
 pub use budget_overview::{BudgetOverviewReport, CategoryReportRow, GroupReportRow};
+pub use cashflow::CashflowReport;
+pub use comparison::{ComparisonRow, PeriodComparisonReport};
+pub use markdown::{Align, MarkdownTable};
+pub use monthly::{MonthlyReportFormat, MonthlySummary};
 pub use net_worth::{NetWorthReport, NetWorthSummary};
-pub use spending::{SpendingByCategory, SpendingReport};
+pub use spending::{SpendingByCategory, SpendingByPayee, SpendingReport};
+pub use tags::{TagReport, TagTotal};
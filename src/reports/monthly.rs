@@ -0,0 +1,429 @@
+//! Monthly Summary Report
+//!
+//! Bundles the budget overview and spending by category for one period
+//! into a single document, written to a dated file in the reports
+//! directory so each closed month can be archived.
+
+use std::fmt;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+use serde_json::json;
+
+use crate::error::{EnvelopeError, EnvelopeResult};
+use crate::models::BudgetPeriod;
+use crate::reports::{BudgetOverviewReport, SpendingReport};
+use crate::storage::Storage;
+
+/// File format of a written monthly summary
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum MonthlyReportFormat {
+    /// GitHub-flavored Markdown
+    #[default]
+    #[serde(rename = "md")]
+    Markdown,
+    /// A standalone HTML page
+    Html,
+    /// Machine-readable JSON with amounts in cents
+    Json,
+}
+
+impl MonthlyReportFormat {
+    /// Parse a format name: md, html, or json
+    pub fn parse(s: &str) -> Option<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "md" | "markdown" => Some(Self::Markdown),
+            "html" => Some(Self::Html),
+            "json" => Some(Self::Json),
+            _ => None,
+        }
+    }
+
+    /// File extension for this format
+    pub fn extension(self) -> &'static str {
+        match self {
+            Self::Markdown => "md",
+            Self::Html => "html",
+            Self::Json => "json",
+        }
+    }
+}
+
+impl fmt::Display for MonthlyReportFormat {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.extension())
+    }
+}
+
+/// Budget overview plus spending by category for one period
+#[derive(Debug, Clone)]
+pub struct MonthlySummary {
+    /// The period summarized
+    pub period: BudgetPeriod,
+    /// Budgeted, activity, and available per category
+    pub budget: BudgetOverviewReport,
+    /// Spending per category over the period's dates
+    pub spending: SpendingReport,
+}
+
+impl MonthlySummary {
+    /// Generate the summary for a period
+    pub fn generate(storage: &Storage, period: &BudgetPeriod) -> EnvelopeResult<Self> {
+        Ok(Self {
+            period: period.clone(),
+            budget: BudgetOverviewReport::generate(storage, period)?,
+            spending: SpendingReport::generate(storage, period.start_date(), period.end_date())?,
+        })
+    }
+
+    /// Name of the file a period's summary is written to, e.g. `2025-01-summary.md`
+    ///
+    /// The name depends only on the period and format, so writing the same
+    /// period again replaces the earlier file.
+    pub fn file_name(period: &BudgetPeriod, format: MonthlyReportFormat) -> String {
+        // Bi-weekly and custom periods display as ranges; name them by start date
+        let stem = match period {
+            BudgetPeriod::Monthly { .. } | BudgetPeriod::Weekly { .. } => period.to_string(),
+            _ => period.start_date().format("%Y-%m-%d").to_string(),
+        };
+        format!("{}-summary.{}", stem, format.extension())
+    }
+
+    /// Render the summary in a format
+    pub fn render(&self, format: MonthlyReportFormat) -> String {
+        match format {
+            MonthlyReportFormat::Markdown => self.format_markdown(),
+            MonthlyReportFormat::Html => self.format_html(),
+            MonthlyReportFormat::Json => self.format_json(),
+        }
+    }
+
+    /// Format the summary as Markdown, one section per report
+    pub fn format_markdown(&self) -> String {
+        format!(
+            "# Monthly Summary - {}\n\n{}\n{}",
+            self.period,
+            self.budget.format_markdown(),
+            self.spending.format_markdown()
+        )
+    }
+
+    /// Format the summary as a standalone HTML page
+    pub fn format_html(&self) -> String {
+        let title = format!("Monthly Summary - {}", self.period);
+        let mut output = String::new();
+        output.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+        output.push_str(&format!("<title>{}</title>\n", escape_html(&title)));
+        output.push_str(
+            "<style>\n\
+             body { font-family: sans-serif; margin: 2em; }\n\
+             table { border-collapse: collapse; margin-bottom: 2em; }\n\
+             th, td { padding: 0.25em 0.75em; border-bottom: 1px solid #ddd; }\n\
+             td.amount, th.amount { text-align: right; }\n\
+             tr.bold td { font-weight: bold; }\n\
+             </style>\n",
+        );
+        output.push_str("</head>\n<body>\n");
+        output.push_str(&format!("<h1>{}</h1>\n", escape_html(&title)));
+
+        // Budget overview
+        output.push_str("<h2>Budget Overview</h2>\n");
+        output.push_str(&format!(
+            "<p>Available to Budget: {}</p>\n",
+            self.budget.available_to_budget
+        ));
+        let mut rows = Vec::new();
+        for group in &self.budget.groups {
+            rows.push((true, vec![group.group_name.clone()]));
+            for category in &group.categories {
+                rows.push((
+                    false,
+                    vec![
+                        category.category_name.clone(),
+                        category.budgeted.to_string(),
+                        category.activity.to_string(),
+                        category.available.to_string(),
+                    ],
+                ));
+            }
+            rows.push((
+                false,
+                vec![
+                    "Group Total".to_string(),
+                    group.total_budgeted.to_string(),
+                    group.total_activity.to_string(),
+                    group.total_available.to_string(),
+                ],
+            ));
+        }
+        rows.push((
+            true,
+            vec![
+                "Grand Total".to_string(),
+                self.budget.grand_total_budgeted.to_string(),
+                self.budget.grand_total_activity.to_string(),
+                self.budget.grand_total_available.to_string(),
+            ],
+        ));
+        output.push_str(&html_table(
+            &["Category", "Budgeted", "Activity", "Available"],
+            &rows,
+        ));
+
+        // Spending by category
+        output.push_str("<h2>Spending by Category</h2>\n");
+        output.push_str(&format!(
+            "<p>Total Spending: {} &middot; Total Income: {} &middot; Transactions: {}</p>\n",
+            self.spending.total_spending.abs(),
+            self.spending.total_income,
+            self.spending.total_transactions
+        ));
+        let mut rows = Vec::new();
+        for group in &self.spending.groups {
+            for category in &group.categories {
+                rows.push((
+                    false,
+                    vec![
+                        format!("{} / {}", group.group_name, category.category_name),
+                        category.total_spending.abs().to_string(),
+                        category.transaction_count.to_string(),
+                        format!("{:.1}%", category.percentage),
+                    ],
+                ));
+            }
+        }
+        if self.spending.has_uncategorized() {
+            rows.push((
+                false,
+                vec![
+                    "(Uncategorized)".to_string(),
+                    self.spending.uncategorized_spending.abs().to_string(),
+                    self.spending.uncategorized_count.to_string(),
+                    format!("{:.1}%", self.spending.uncategorized_percentage),
+                ],
+            ));
+        }
+        rows.push((
+            true,
+            vec![
+                "Total Spending".to_string(),
+                self.spending.total_spending.abs().to_string(),
+                self.spending.total_transactions.to_string(),
+            ],
+        ));
+        output.push_str(&html_table(&["Category", "Amount", "Count", "%"], &rows));
+
+        output.push_str("</body>\n</html>\n");
+        output
+    }
+
+    /// Format the summary as pretty-printed JSON with amounts in cents
+    pub fn format_json(&self) -> String {
+        let groups: Vec<_> = self
+            .budget
+            .groups
+            .iter()
+            .map(|group| {
+                let categories: Vec<_> = group
+                    .categories
+                    .iter()
+                    .map(|c| {
+                        json!({
+                            "name": c.category_name,
+                            "budgeted": c.budgeted.cents(),
+                            "activity": c.activity.cents(),
+                            "available": c.available.cents(),
+                        })
+                    })
+                    .collect();
+                json!({
+                    "name": group.group_name,
+                    "budgeted": group.total_budgeted.cents(),
+                    "activity": group.total_activity.cents(),
+                    "available": group.total_available.cents(),
+                    "categories": categories,
+                })
+            })
+            .collect();
+
+        let spending: Vec<_> = self
+            .spending
+            .groups
+            .iter()
+            .flat_map(|group| {
+                group.categories.iter().map(move |c| {
+                    json!({
+                        "group": group.group_name,
+                        "category": c.category_name,
+                        "amount": c.total_spending.abs().cents(),
+                        "count": c.transaction_count,
+                        "percentage": c.percentage,
+                    })
+                })
+            })
+            .collect();
+
+        let value = json!({
+            "period": self.period.to_string(),
+            "start_date": self.period.start_date().to_string(),
+            "end_date": self.period.end_date().to_string(),
+            "budget": {
+                "available_to_budget": self.budget.available_to_budget.cents(),
+                "budgeted": self.budget.grand_total_budgeted.cents(),
+                "activity": self.budget.grand_total_activity.cents(),
+                "available": self.budget.grand_total_available.cents(),
+                "groups": groups,
+            },
+            "spending": {
+                "total_spending": self.spending.total_spending.abs().cents(),
+                "total_income": self.spending.total_income.cents(),
+                "transactions": self.spending.total_transactions,
+                "uncategorized": self.spending.uncategorized_spending.abs().cents(),
+                "categories": spending,
+            },
+        });
+
+        // A json! value always serializes
+        let mut output = serde_json::to_string_pretty(&value).unwrap_or_default();
+        output.push('\n');
+        output
+    }
+
+    /// Write the summary into `dir`, replacing any earlier file for the period
+    ///
+    /// Returns the path written.
+    pub fn write_to(&self, dir: &Path, format: MonthlyReportFormat) -> EnvelopeResult<PathBuf> {
+        fs::create_dir_all(dir).map_err(|e| {
+            EnvelopeError::Export(format!(
+                "Failed to create reports directory {}: {}",
+                dir.display(),
+                e
+            ))
+        })?;
+
+        let path = dir.join(Self::file_name(&self.period, format));
+        fs::write(&path, self.render(format)).map_err(|e| {
+            EnvelopeError::Export(format!("Failed to write {}: {}", path.display(), e))
+        })?;
+        Ok(path)
+    }
+}
+
+/// Render a table; bold rows are group headers and totals
+fn html_table(headers: &[&str], rows: &[(bool, Vec<String>)]) -> String {
+    let mut output = String::from("<table>\n<tr>");
+    for (i, header) in headers.iter().enumerate() {
+        let class = if i == 0 { "" } else { " class=\"amount\"" };
+        output.push_str(&format!("<th{}>{}</th>", class, escape_html(header)));
+    }
+    output.push_str("</tr>\n");
+
+    for (bold, cells) in rows {
+        output.push_str(if *bold { "<tr class=\"bold\">" } else { "<tr>" });
+        for i in 0..headers.len() {
+            let class = if i == 0 { "" } else { " class=\"amount\"" };
+            let cell = cells.get(i).map(String::as_str).unwrap_or("");
+            output.push_str(&format!("<td{}>{}</td>", class, escape_html(cell)));
+        }
+        output.push_str("</tr>\n");
+    }
+
+    output.push_str("</table>\n");
+    output
+}
+
+/// Escape text for an HTML element body
+fn escape_html(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::paths::EnvelopePaths;
+    use crate::models::{Account, AccountType, Category, CategoryGroup, Money, Transaction};
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (TempDir, Storage) {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let mut storage = Storage::new(paths).unwrap();
+        storage.load_all().unwrap();
+        (temp_dir, storage)
+    }
+
+    fn january_summary(storage: &Storage) -> MonthlySummary {
+        let group = CategoryGroup::new("Needs");
+        storage.categories.upsert_group(group.clone()).unwrap();
+        let category = Category::new("Food & Drink", group.id);
+        storage
+            .categories
+            .upsert_category(category.clone())
+            .unwrap();
+
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+
+        let mut txn = Transaction::new(
+            account.id,
+            NaiveDate::from_ymd_opt(2025, 1, 10).unwrap(),
+            Money::from_cents(-5000),
+        );
+        txn.category_id = Some(category.id);
+        storage.transactions.upsert(txn).unwrap();
+
+        MonthlySummary::generate(storage, &BudgetPeriod::monthly(2025, 1)).unwrap()
+    }
+
+    #[test]
+    fn test_formats_include_both_sections() {
+        let (_temp_dir, storage) = create_test_storage();
+        let summary = january_summary(&storage);
+
+        let markdown = summary.format_markdown();
+        assert!(markdown.contains("Budget Overview - 2025-01"));
+        assert!(markdown.contains("Spending Report"));
+
+        let html = summary.format_html();
+        assert!(html.contains("Food &amp; Drink"));
+        assert!(!html.contains("Food & Drink"));
+
+        let json: serde_json::Value = serde_json::from_str(&summary.format_json()).unwrap();
+        assert_eq!(json["period"], "2025-01");
+        assert_eq!(json["spending"]["total_spending"], 5000);
+        assert_eq!(json["budget"]["activity"], -5000);
+    }
+
+    #[test]
+    fn test_write_replaces_same_period() {
+        let (temp_dir, storage) = create_test_storage();
+        let summary = january_summary(&storage);
+        let dir = temp_dir.path().join("reports");
+
+        let first = summary.write_to(&dir, MonthlyReportFormat::Json).unwrap();
+        let second = summary.write_to(&dir, MonthlyReportFormat::Json).unwrap();
+
+        assert_eq!(first, second);
+        assert_eq!(first.file_name().unwrap(), "2025-01-summary.json");
+        assert_eq!(fs::read_dir(&dir).unwrap().count(), 1);
+    }
+
+    #[test]
+    fn test_parse_format() {
+        assert_eq!(
+            MonthlyReportFormat::parse("markdown"),
+            Some(MonthlyReportFormat::Markdown)
+        );
+        assert_eq!(
+            MonthlyReportFormat::parse("HTML"),
+            Some(MonthlyReportFormat::Html)
+        );
+        assert_eq!(MonthlyReportFormat::parse("pdf"), None);
+    }
+}
@@ -1,15 +1,20 @@
 //! Net Worth Report
 //!
 //! Generates a summary of all account balances showing total net worth.
+//! Balances in other currencies are converted to the base currency.
 
+use crate::config::clock;
 use crate::error::EnvelopeResult;
-use crate::models::{AccountId, AccountType, Money};
+use crate::models::{AccountId, AccountType, BudgetPeriod, Money};
+use crate::rates::{convert, RateProvider};
+use crate::reports::markdown::{Align, MarkdownTable};
 use crate::services::AccountService;
 use crate::storage::Storage;
+use serde::Serialize;
 use std::io::Write;
 
 /// Summary of a single account's balance
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AccountBalance {
     /// Account ID
     pub account_id: AccountId,
@@ -19,16 +24,20 @@ pub struct AccountBalance {
     pub account_type: AccountType,
     /// Whether this is an on-budget account
     pub on_budget: bool,
-    /// Current balance
+    /// Account currency code
+    pub currency: String,
+    /// Current balance in the account's own currency
+    pub native_balance: Money,
+    /// Current balance in the base currency
     pub balance: Money,
-    /// Cleared balance
+    /// Cleared balance in the base currency
     pub cleared_balance: Money,
     /// Number of uncleared transactions
     pub uncleared_count: usize,
 }
 
 /// Net worth summary grouped by account type
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AccountTypeGroup {
     /// Account type
     pub account_type: AccountType,
@@ -60,7 +69,7 @@ impl AccountTypeGroup {
 }
 
 /// Net Worth Summary
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct NetWorthSummary {
     /// Total assets (positive accounts: checking, savings, cash, investment)
     pub total_assets: Money,
@@ -75,8 +84,12 @@ pub struct NetWorthSummary {
 }
 
 /// Net Worth Report
-#[derive(Debug, Clone)]
+///
+/// All totals are in the base currency.
+#[derive(Debug, Clone, Serialize)]
 pub struct NetWorthReport {
+    /// Currency the balances are converted into
+    pub base_currency: String,
     /// Account groups by type
     pub groups: Vec<AccountTypeGroup>,
     /// Net worth summary
@@ -87,9 +100,19 @@ pub struct NetWorthReport {
 
 impl NetWorthReport {
     /// Generate a net worth report
-    pub fn generate(storage: &Storage, include_archived: bool) -> EnvelopeResult<Self> {
+    ///
+    /// Balances of accounts in other currencies are converted to
+    /// `base_currency` at today's rate. A missing rate is an error naming
+    /// the currency pair, never an assumed 1:1.
+    pub fn generate(
+        storage: &Storage,
+        include_archived: bool,
+        base_currency: &str,
+        rates: &dyn RateProvider,
+    ) -> EnvelopeResult<Self> {
         let account_service = AccountService::new(storage);
         let summaries = account_service.list_with_balances(include_archived)?;
+        let today = clock::today();
 
         // Group accounts by type
         let mut groups: std::collections::HashMap<AccountType, AccountTypeGroup> =
@@ -101,13 +124,34 @@ impl NetWorthReport {
         let mut off_budget_total = Money::zero();
 
         for account_summary in summaries {
+            let currency = account_summary
+                .account
+                .currency_or(base_currency)
+                .to_string();
+            let balance = convert(
+                account_summary.balance,
+                &currency,
+                base_currency,
+                today,
+                rates,
+            )?;
+            let cleared_balance = convert(
+                account_summary.cleared_balance,
+                &currency,
+                base_currency,
+                today,
+                rates,
+            )?;
+
             let account_balance = AccountBalance {
                 account_id: account_summary.account.id,
                 account_name: account_summary.account.name.clone(),
                 account_type: account_summary.account.account_type,
                 on_budget: account_summary.account.on_budget,
-                balance: account_summary.balance,
-                cleared_balance: account_summary.cleared_balance,
+                currency,
+                native_balance: account_summary.balance,
+                balance,
+                cleared_balance,
                 uncleared_count: account_summary.uncleared_count,
             };
 
@@ -119,15 +163,15 @@ impl NetWorthReport {
 
             // Track totals
             if is_liability_account(account_summary.account.account_type) {
-                total_liabilities += account_summary.balance;
+                total_liabilities += balance;
             } else {
-                total_assets += account_summary.balance;
+                total_assets += balance;
             }
 
             if account_summary.account.on_budget {
-                on_budget_total += account_summary.balance;
+                on_budget_total += balance;
             } else {
-                off_budget_total += account_summary.balance;
+                off_budget_total += balance;
             }
         }
 
@@ -144,12 +188,172 @@ impl NetWorthReport {
         };
 
         Ok(Self {
+            base_currency: base_currency.to_string(),
             groups,
             summary,
             include_archived,
         })
     }
 
+    /// Net worth at the end of each period
+    ///
+    /// Each account's balance is the sum of its transactions dated on or
+    /// before the period's `end_date()`, so later activity doesn't leak into
+    /// earlier periods. Foreign balances are converted at the rate for that
+    /// date (today's rate for periods that haven't ended yet).
+    pub fn trend(
+        storage: &Storage,
+        periods: &[BudgetPeriod],
+        include_archived: bool,
+        base_currency: &str,
+        rates: &dyn RateProvider,
+    ) -> EnvelopeResult<Vec<(BudgetPeriod, NetWorthSummary)>> {
+        let account_service = AccountService::new(storage);
+        let accounts = account_service.list(include_archived)?;
+        let today = clock::today();
+
+        let mut summaries: Vec<NetWorthSummary> = periods
+            .iter()
+            .map(|_| NetWorthSummary {
+                total_assets: Money::zero(),
+                total_liabilities: Money::zero(),
+                net_worth: Money::zero(),
+                on_budget_total: Money::zero(),
+                off_budget_total: Money::zero(),
+            })
+            .collect();
+
+        for account in accounts {
+            let transactions = storage.transactions.get_by_account(account.id)?;
+            let opening = account.untracked_starting_balance(&transactions);
+            let currency = account.currency_or(base_currency).to_string();
+
+            for (period, summary) in periods.iter().zip(summaries.iter_mut()) {
+                let end = period.end_date();
+                let native: Money = opening
+                    + transactions
+                        .iter()
+                        .filter(|t| t.date <= end)
+                        .map(|t| t.amount)
+                        .sum::<Money>();
+                let balance = convert(native, &currency, base_currency, end.min(today), rates)?;
+
+                if is_liability_account(account.account_type) {
+                    summary.total_liabilities += balance;
+                } else {
+                    summary.total_assets += balance;
+                }
+                if account.on_budget {
+                    summary.on_budget_total += balance;
+                } else {
+                    summary.off_budget_total += balance;
+                }
+            }
+        }
+
+        Ok(periods
+            .iter()
+            .cloned()
+            .zip(summaries.into_iter().map(|mut summary| {
+                summary.net_worth = summary.total_assets + summary.total_liabilities;
+                summary
+            }))
+            .collect())
+    }
+
+    /// Format a net worth trend for terminal display
+    ///
+    /// The change column compares each period with the one above it.
+    pub fn format_trend_terminal(trend: &[(BudgetPeriod, NetWorthSummary)]) -> String {
+        let mut output = String::new();
+
+        output.push_str("Net Worth Trend\n");
+        output.push_str(&"=".repeat(70));
+        output.push('\n');
+        output.push_str(&format!(
+            "{:<12} {:>14} {:>14} {:>14} {:>12}\n",
+            "Period", "Assets", "Liabilities", "Net Worth", "Change"
+        ));
+        output.push_str(&"-".repeat(70));
+        output.push('\n');
+
+        let mut previous: Option<Money> = None;
+        for (period, summary) in trend {
+            let change = previous
+                .map(|p| (summary.net_worth - p).to_string())
+                .unwrap_or_default();
+            output.push_str(&format!(
+                "{:<12} {:>14} {:>14} {:>14} {:>12}\n",
+                period.to_string(),
+                summary.total_assets,
+                summary.total_liabilities.abs(),
+                summary.net_worth,
+                change
+            ));
+            previous = Some(summary.net_worth);
+        }
+
+        output
+    }
+
+    /// Format a net worth trend as a Markdown table
+    pub fn format_trend_markdown(trend: &[(BudgetPeriod, NetWorthSummary)]) -> String {
+        let mut output = String::from("## Net Worth Trend\n\n");
+
+        let mut table = MarkdownTable::new(&[
+            ("Period", Align::Left),
+            ("Assets", Align::Right),
+            ("Liabilities", Align::Right),
+            ("Net Worth", Align::Right),
+            ("Change", Align::Right),
+        ]);
+        let mut previous: Option<Money> = None;
+        for (period, summary) in trend {
+            table.row(vec![
+                period.to_string(),
+                summary.total_assets.to_string(),
+                summary.total_liabilities.abs().to_string(),
+                summary.net_worth.to_string(),
+                previous
+                    .map(|p| (summary.net_worth - p).to_string())
+                    .unwrap_or_default(),
+            ]);
+            previous = Some(summary.net_worth);
+        }
+
+        output.push_str(&table.render());
+        output
+    }
+
+    /// Export a net worth trend to CSV, one row per period
+    pub fn export_trend_csv<W: Write>(
+        trend: &[(BudgetPeriod, NetWorthSummary)],
+        writer: &mut W,
+    ) -> EnvelopeResult<()> {
+        writeln!(
+            writer,
+            "Period,End Date,Total Assets,Total Liabilities,Net Worth,On Budget,Off Budget"
+        )
+        .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
+
+        for (period, summary) in trend {
+            writeln!(
+                writer,
+                "{},{},{:.2},{:.2},{:.2},{:.2},{:.2}",
+                period,
+                period.end_date(),
+                summary.total_assets.cents() as f64 / 100.0,
+                summary.total_liabilities.cents() as f64 / 100.0,
+                summary.net_worth.cents() as f64 / 100.0,
+                summary.on_budget_total.cents() as f64 / 100.0,
+                summary.off_budget_total.cents() as f64 / 100.0,
+            )
+            .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
     /// Format the report for terminal display
     pub fn format_terminal(&self) -> String {
         let mut output = String::new();
@@ -211,6 +415,13 @@ impl NetWorthReport {
                     account.cleared_balance,
                     account.uncleared_count
                 ));
+                if account.currency != self.base_currency {
+                    output.push_str(&format!(
+                        "  {:<28} {:>12}\n",
+                        "",
+                        format_native(account.native_balance, &account.currency)
+                    ));
+                }
             }
 
             // Group total
@@ -224,7 +435,75 @@ impl NetWorthReport {
         output.push_str(&"-".repeat(70));
         output.push('\n');
         output.push_str("B = On-Budget account\n");
+        if self.has_foreign_accounts() {
+            output.push_str(&format!(
+                "Amounts in {}; native balances shown below converted accounts\n",
+                self.base_currency
+            ));
+        }
+
+        output
+    }
+
+    /// Format the report as a Markdown table
+    ///
+    /// Account types are bold rows above their accounts. Converted accounts
+    /// show their native balance after the converted one.
+    pub fn format_markdown(&self) -> String {
+        let mut output = String::from("## Net Worth Report\n\n");
+        output.push_str(&format!("- Total Assets: {}\n", self.summary.total_assets));
+        output.push_str(&format!(
+            "- Total Liabilities: {}\n",
+            self.summary.total_liabilities.abs()
+        ));
+        output.push_str(&format!("- **Net Worth: {}**\n", self.summary.net_worth));
+        output.push_str(&format!("- On-Budget: {}\n", self.summary.on_budget_total));
+        output.push_str(&format!(
+            "- Off-Budget: {}\n\n",
+            self.summary.off_budget_total
+        ));
+
+        let mut table = MarkdownTable::new(&[
+            ("Account", Align::Left),
+            ("On Budget", Align::Left),
+            ("Balance", Align::Right),
+            ("Cleared", Align::Right),
+            ("Uncleared", Align::Right),
+        ]);
+        for group in &self.groups {
+            table.bold_row(vec![format!("{:?}", group.account_type)]);
 
+            for account in &group.accounts {
+                let balance = if account.currency != self.base_currency {
+                    format!(
+                        "{} ({})",
+                        account.balance,
+                        format_native(account.native_balance, &account.currency)
+                    )
+                } else {
+                    account.balance.to_string()
+                };
+                table.row(vec![
+                    account.account_name.clone(),
+                    if account.on_budget { "Yes" } else { "No" }.to_string(),
+                    balance,
+                    account.cleared_balance.to_string(),
+                    account.uncleared_count.to_string(),
+                ]);
+            }
+
+            table.row(vec![
+                "Subtotal".to_string(),
+                String::new(),
+                group.total_balance.to_string(),
+                group.total_cleared.to_string(),
+            ]);
+        }
+
+        output.push_str(&table.render());
+        if self.has_foreign_accounts() {
+            output.push_str(&format!("\nAmounts in {}.\n", self.base_currency));
+        }
         output
     }
 
@@ -233,7 +512,7 @@ impl NetWorthReport {
         // Write header
         writeln!(
             writer,
-            "Account Type,Account Name,On Budget,Balance,Cleared Balance,Uncleared Count"
+            "Account Type,Account Name,On Budget,Balance,Cleared Balance,Uncleared Count,Currency,Native Balance"
         )
         .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
 
@@ -242,13 +521,15 @@ impl NetWorthReport {
             for account in &group.accounts {
                 writeln!(
                     writer,
-                    "{:?},{},{},{:.2},{:.2},{}",
+                    "{:?},{},{},{:.2},{:.2},{},{},{:.2}",
                     group.account_type,
                     account.account_name,
                     account.on_budget,
                     account.balance.cents() as f64 / 100.0,
                     account.cleared_balance.cents() as f64 / 100.0,
-                    account.uncleared_count
+                    account.uncleared_count,
+                    account.currency,
+                    account.native_balance.cents() as f64 / 100.0,
                 )
                 .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
             }
@@ -282,6 +563,19 @@ impl NetWorthReport {
     pub fn account_count(&self) -> usize {
         self.groups.iter().map(|g| g.accounts.len()).sum()
     }
+
+    /// Check if any account is in a currency other than the base currency
+    pub fn has_foreign_accounts(&self) -> bool {
+        self.groups
+            .iter()
+            .flat_map(|g| &g.accounts)
+            .any(|a| a.currency != self.base_currency)
+    }
+}
+
+/// Format an amount in its own currency, e.g. "1234.50 EUR"
+fn format_native(amount: Money, currency: &str) -> String {
+    format!("{:.2} {}", amount.cents() as f64 / 100.0, currency)
 }
 
 /// Check if an account type is a liability
@@ -306,7 +600,9 @@ fn account_type_sort_order(account_type: AccountType) -> i32 {
 mod tests {
     use super::*;
     use crate::config::paths::EnvelopePaths;
-    use crate::models::Account;
+    use crate::models::{Account, Transaction};
+    use crate::rates::StaticRates;
+    use chrono::NaiveDate;
     use tempfile::TempDir;
 
     fn create_test_storage() -> (TempDir, Storage) {
@@ -345,7 +641,7 @@ mod tests {
         storage.accounts.save().unwrap();
 
         // Generate report
-        let report = NetWorthReport::generate(&storage, false).unwrap();
+        let report = NetWorthReport::generate(&storage, false, "USD", &StaticRates::new()).unwrap();
 
         assert_eq!(report.account_count(), 3);
         assert_eq!(report.summary.total_assets.cents(), 1500000);
@@ -353,6 +649,70 @@ mod tests {
         assert_eq!(report.summary.net_worth.cents(), 1450000);
     }
 
+    #[test]
+    fn test_created_credit_card_counts_as_liability() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = AccountService::new(&storage);
+
+        service
+            .create(
+                "Checking",
+                AccountType::Checking,
+                Money::from_cents(200000),
+                true,
+            )
+            .unwrap();
+        service
+            .create("Visa", AccountType::Credit, Money::from_cents(-50000), true)
+            .unwrap();
+
+        // Starting balances recorded as transactions aren't counted twice
+        let report = NetWorthReport::generate(&storage, false, "USD", &StaticRates::new()).unwrap();
+        assert_eq!(report.summary.total_assets.cents(), 200000);
+        assert_eq!(report.summary.total_liabilities.cents(), -50000);
+        assert_eq!(report.summary.net_worth.cents(), 150000);
+    }
+
+    #[test]
+    fn test_converts_foreign_accounts() {
+        let (_temp_dir, storage) = create_test_storage();
+
+        let checking = Account::with_starting_balance(
+            "Checking",
+            AccountType::Checking,
+            Money::from_cents(100000),
+        );
+        storage.accounts.upsert(checking).unwrap();
+
+        let mut savings = Account::with_starting_balance(
+            "Euro Savings",
+            AccountType::Savings,
+            Money::from_cents(200000),
+        );
+        savings.currency = Some("EUR".to_string());
+        storage.accounts.upsert(savings).unwrap();
+
+        // Missing rates are reported, not assumed 1:1
+        let err =
+            NetWorthReport::generate(&storage, false, "USD", &StaticRates::new()).unwrap_err();
+        assert!(err.to_string().contains("EUR to USD"));
+
+        let mut rates = StaticRates::new();
+        rates.insert("EUR", "USD", 1.1);
+        let report = NetWorthReport::generate(&storage, false, "USD", &rates).unwrap();
+
+        assert_eq!(report.summary.net_worth.cents(), 320000);
+        let euro = report
+            .groups
+            .iter()
+            .flat_map(|g| &g.accounts)
+            .find(|a| a.account_name == "Euro Savings")
+            .unwrap();
+        assert_eq!(euro.native_balance.cents(), 200000);
+        assert_eq!(euro.balance.cents(), 220000);
+        assert!(report.format_terminal().contains("2000.00 EUR"));
+    }
+
     #[test]
     fn test_csv_export() {
         let (_temp_dir, storage) = create_test_storage();
@@ -365,7 +725,7 @@ mod tests {
         storage.accounts.upsert(checking).unwrap();
         storage.accounts.save().unwrap();
 
-        let report = NetWorthReport::generate(&storage, false).unwrap();
+        let report = NetWorthReport::generate(&storage, false, "USD", &StaticRates::new()).unwrap();
 
         let mut csv_output = Vec::new();
         report.export_csv(&mut csv_output).unwrap();
@@ -375,4 +735,38 @@ mod tests {
         assert!(csv_string.contains("Checking"));
         assert!(csv_string.contains("Net Worth"));
     }
+
+    #[test]
+    fn test_trend_uses_balances_as_of_period_end() {
+        let (_temp_dir, storage) = create_test_storage();
+
+        let checking = Account::new("Checking", AccountType::Checking);
+        let card = Account::new("Visa", AccountType::Credit);
+        let date = |m| NaiveDate::from_ymd_opt(2025, m, 15).unwrap();
+        storage.accounts.upsert(checking.clone()).unwrap();
+        storage.accounts.upsert(card.clone()).unwrap();
+        for txn in [
+            Transaction::new(checking.id, date(1), Money::from_cents(100000)),
+            Transaction::new(checking.id, date(2), Money::from_cents(50000)),
+            Transaction::new(card.id, date(2), Money::from_cents(-20000)),
+            Transaction::new(checking.id, date(4), Money::from_cents(-30000)),
+        ] {
+            storage.transactions.upsert(txn).unwrap();
+        }
+
+        let periods: Vec<_> = (1..=3).map(|m| BudgetPeriod::monthly(2025, m)).collect();
+        let trend =
+            NetWorthReport::trend(&storage, &periods, false, "USD", &StaticRates::new()).unwrap();
+
+        let net_worth: Vec<_> = trend.iter().map(|(_, s)| s.net_worth.cents()).collect();
+        assert_eq!(net_worth, vec![100000, 130000, 130000]);
+        assert_eq!(trend[1].0, BudgetPeriod::monthly(2025, 2));
+        assert_eq!(trend[1].1.total_assets.cents(), 150000);
+        assert_eq!(trend[1].1.total_liabilities.cents(), -20000);
+
+        let mut csv = Vec::new();
+        NetWorthReport::export_trend_csv(&trend, &mut csv).unwrap();
+        let csv = String::from_utf8(csv).unwrap();
+        assert!(csv.contains("2025-02,2025-02-28,1500.00,-200.00,1300.00"));
+    }
 }
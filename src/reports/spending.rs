@@ -1,17 +1,20 @@
 //! Spending Report
 //!
-//! Generates spending analysis by category for a given date range.
+//! Generates spending analysis by category, or by payee, for a given date
+//! range.
 
 use crate::error::EnvelopeResult;
 use crate::models::{CategoryGroupId, CategoryId, Money};
+use crate::reports::markdown::{Align, MarkdownTable};
 use crate::services::CategoryService;
 use crate::storage::Storage;
 use chrono::NaiveDate;
+use serde::Serialize;
 use std::collections::HashMap;
 use std::io::Write;
 
 /// Spending breakdown by category
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SpendingByCategory {
     /// Category ID
     pub category_id: CategoryId,
@@ -30,7 +33,7 @@ pub struct SpendingByCategory {
 }
 
 /// Spending by group summary
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SpendingByGroup {
     /// Group ID
     pub group_id: CategoryGroupId,
@@ -46,8 +49,23 @@ pub struct SpendingByGroup {
     pub percentage: f64,
 }
 
+/// Spending breakdown by payee
+#[derive(Debug, Clone, Serialize)]
+pub struct SpendingByPayee {
+    /// Payee name ("(No payee)" for transactions without one)
+    pub payee_name: String,
+    /// Total spending (negative value)
+    pub total_spending: Money,
+    /// Number of transactions
+    pub transaction_count: usize,
+    /// Average spending per transaction (negative value)
+    pub average: Money,
+    /// Percentage of total spending
+    pub percentage: f64,
+}
+
 /// Spending Report
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct SpendingReport {
     /// Start date of the report
     pub start_date: NaiveDate,
@@ -61,10 +79,14 @@ pub struct SpendingReport {
     pub total_income: Money,
     /// Total transaction count
     pub total_transactions: usize,
-    /// Uncategorized spending
+    /// Uncategorized spending (no category, or a category that no longer exists)
     pub uncategorized_spending: Money,
     /// Uncategorized transaction count
     pub uncategorized_count: usize,
+    /// Percentage of total spending that is uncategorized
+    pub uncategorized_percentage: f64,
+    /// Spending by payee, most spending first
+    pub payees: Vec<SpendingByPayee>,
 }
 
 impl SpendingReport {
@@ -84,7 +106,7 @@ impl SpendingReport {
             .get_by_date_range(start_date, end_date)?;
 
         // Build category lookup
-        let _category_map: HashMap<CategoryId, _> =
+        let category_map: HashMap<CategoryId, _> =
             categories.iter().map(|c| (c.id, c.clone())).collect();
 
         let _group_map: HashMap<CategoryGroupId, _> =
@@ -97,14 +119,29 @@ impl SpendingReport {
         let mut total_income = Money::zero();
         let mut total_spending = Money::zero();
 
+        // Keyed by lowercased payee name: ((first date, display name), spending,
+        // count). The display name is the earliest spelling, ties broken by
+        // name, so it doesn't depend on storage order.
+        let mut payee_spending: HashMap<String, ((NaiveDate, String), Money, usize)> =
+            HashMap::new();
+
         for txn in &transactions {
+            // Opening balances are neither income nor spending
+            if txn.is_starting_balance {
+                continue;
+            }
+
+            let spending_before = total_spending;
             if txn.amount.is_positive() {
                 total_income += txn.amount;
             } else if txn.is_split() {
-                // Handle split transactions
+                // Handle split transactions; transfer splits aren't spending
                 for split in &txn.splits {
+                    let Some(cat_id) = split.category_id else {
+                        continue;
+                    };
                     let entry = category_spending
-                        .entry(split.category_id)
+                        .entry(cat_id)
                         .or_insert((Money::zero(), 0));
                     entry.0 += split.amount;
                     entry.1 += 1;
@@ -123,10 +160,45 @@ impl SpendingReport {
                 uncategorized_count += 1;
                 total_spending += txn.amount;
             }
+
+            // A split counts once for its payee, with all of its spending
+            let txn_spending = total_spending - spending_before;
+            if !txn_spending.is_zero() {
+                let name = match txn.payee_name.trim() {
+                    "" => "(No payee)",
+                    name => name,
+                };
+                let (first, spending, count) = payee_spending
+                    .entry(name.to_lowercase())
+                    .or_insert_with(|| ((txn.date, name.to_string()), Money::zero(), 0));
+                if (txn.date, name) < (first.0, first.1.as_str()) {
+                    *first = (txn.date, name.to_string());
+                }
+                *spending += txn_spending;
+                *count += 1;
+            }
         }
 
+        // Spending against categories that no longer exist would otherwise drop
+        // out of the breakdown while still counting toward the total
+        category_spending.retain(|cat_id, (spending, count)| {
+            if category_map.contains_key(cat_id) {
+                true
+            } else {
+                uncategorized_spending += *spending;
+                uncategorized_count += *count;
+                false
+            }
+        });
+
         // Calculate total absolute spending for percentages
         let total_abs_spending = total_spending.abs();
+        let uncategorized_percentage = if total_abs_spending.is_zero() {
+            0.0
+        } else {
+            (uncategorized_spending.abs().cents() as f64 / total_abs_spending.cents() as f64)
+                * 100.0
+        };
 
         // Build report by group
         let mut report_groups: Vec<SpendingByGroup> = Vec::new();
@@ -192,6 +264,26 @@ impl SpendingReport {
         // Sort groups by spending
         report_groups.sort_by(|a, b| a.total_spending.cmp(&b.total_spending));
 
+        let mut payees: Vec<SpendingByPayee> = payee_spending
+            .into_values()
+            .map(|((_, payee_name), spending, count)| SpendingByPayee {
+                payee_name,
+                total_spending: spending,
+                transaction_count: count,
+                average: Money::from_cents(spending.cents() / count as i64),
+                percentage: if total_abs_spending.is_zero() {
+                    0.0
+                } else {
+                    (spending.abs().cents() as f64 / total_abs_spending.cents() as f64) * 100.0
+                },
+            })
+            .collect();
+        payees.sort_by(|a, b| {
+            a.total_spending
+                .cmp(&b.total_spending)
+                .then_with(|| a.payee_name.cmp(&b.payee_name))
+        });
+
         Ok(Self {
             start_date,
             end_date,
@@ -201,9 +293,16 @@ impl SpendingReport {
             total_transactions: transactions.len(),
             uncategorized_spending,
             uncategorized_count,
+            uncategorized_percentage,
+            payees,
         })
     }
 
+    /// Whether any spending in the period is uncategorized
+    pub fn has_uncategorized(&self) -> bool {
+        !self.uncategorized_spending.is_zero()
+    }
+
     /// Format the report for terminal display
     pub fn format_terminal(&self) -> String {
         let mut output = String::new();
@@ -218,9 +317,18 @@ impl SpendingReport {
         output.push_str(&format!("Total Spending: {}\n", self.total_spending.abs()));
         output.push_str(&format!("Total Income: {}\n", self.total_income));
         output.push_str(&format!(
-            "Total Transactions: {}\n\n",
+            "Total Transactions: {}\n",
             self.total_transactions
         ));
+        if self.has_uncategorized() {
+            output.push_str(&format!(
+                "⚠️  Uncategorized: {} in {} transaction(s) ({:.1}%) - categorize these for an accurate breakdown\n",
+                self.uncategorized_spending.abs(),
+                self.uncategorized_count,
+                self.uncategorized_percentage
+            ));
+        }
+        output.push('\n');
 
         // Column headers
         output.push_str(&format!(
@@ -259,12 +367,13 @@ impl SpendingReport {
         }
 
         // Uncategorized
-        if !self.uncategorized_spending.is_zero() {
+        if self.has_uncategorized() {
             output.push_str(&format!(
-                "\n{:<35} {:>12} {:>8}\n",
-                "UNCATEGORIZED",
+                "\n{:<35} {:>12} {:>8} {:>7.1}%\n",
+                "(Uncategorized)",
                 self.uncategorized_spending.abs(),
-                self.uncategorized_count
+                self.uncategorized_count,
+                self.uncategorized_percentage
             ));
         }
 
@@ -281,6 +390,73 @@ impl SpendingReport {
         output
     }
 
+    /// Format the report as a Markdown table
+    ///
+    /// Group names are bold rows above their categories.
+    pub fn format_markdown(&self) -> String {
+        let mut output = format!(
+            "## Spending Report: {} to {}\n\n",
+            self.start_date, self.end_date
+        );
+        output.push_str(&format!(
+            "- Total Spending: {}\n",
+            self.total_spending.abs()
+        ));
+        output.push_str(&format!("- Total Income: {}\n", self.total_income));
+        output.push_str(&format!(
+            "- Total Transactions: {}\n\n",
+            self.total_transactions
+        ));
+
+        let mut table = MarkdownTable::new(&[
+            ("Category", Align::Left),
+            ("Amount", Align::Right),
+            ("Count", Align::Right),
+            ("%", Align::Right),
+        ]);
+        for group in &self.groups {
+            table.bold_row(vec![
+                group.group_name.clone(),
+                String::new(),
+                String::new(),
+                format!("{:.1}%", group.percentage),
+            ]);
+
+            for category in &group.categories {
+                table.row(vec![
+                    category.category_name.clone(),
+                    category.total_spending.abs().to_string(),
+                    category.transaction_count.to_string(),
+                    format!("{:.1}%", category.percentage),
+                ]);
+            }
+
+            table.row(vec![
+                "Group Total".to_string(),
+                group.total_spending.abs().to_string(),
+                group.transaction_count.to_string(),
+            ]);
+        }
+
+        if self.has_uncategorized() {
+            table.row(vec![
+                "(Uncategorized)".to_string(),
+                self.uncategorized_spending.abs().to_string(),
+                self.uncategorized_count.to_string(),
+                format!("{:.1}%", self.uncategorized_percentage),
+            ]);
+        }
+
+        table.bold_row(vec![
+            "Total Spending".to_string(),
+            self.total_spending.abs().to_string(),
+            self.total_transactions.to_string(),
+        ]);
+
+        output.push_str(&table.render());
+        output
+    }
+
     /// Export the report to CSV format
     pub fn export_csv<W: Write>(&self, writer: &mut W) -> EnvelopeResult<()> {
         // Write header
@@ -309,14 +485,15 @@ impl SpendingReport {
         }
 
         // Uncategorized
-        if !self.uncategorized_spending.is_zero() {
+        if self.has_uncategorized() {
             writeln!(
                 writer,
-                "{},{},UNCATEGORIZED,,{:.2},{},",
+                "{},{},,(Uncategorized),{:.2},{},{:.2}",
                 self.start_date,
                 self.end_date,
                 self.uncategorized_spending.abs().cents() as f64 / 100.0,
-                self.uncategorized_count
+                self.uncategorized_count,
+                self.uncategorized_percentage
             )
             .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
         }
@@ -335,6 +512,108 @@ impl SpendingReport {
         Ok(())
     }
 
+    /// Format the payee breakdown for terminal display
+    pub fn format_payees_terminal(&self) -> String {
+        let mut output = String::new();
+
+        output.push_str(&format!(
+            "Spending by Payee: {} to {}\n",
+            self.start_date, self.end_date
+        ));
+        output.push_str(&"=".repeat(80));
+        output.push('\n');
+        output.push_str(&format!(
+            "{:<35} {:>12} {:>8} {:>12} {:>8}\n",
+            "Payee", "Amount", "Count", "Average", "%"
+        ));
+        output.push_str(&"-".repeat(80));
+        output.push('\n');
+
+        for payee in &self.payees {
+            output.push_str(&format!(
+                "{:<35} {:>12} {:>8} {:>12} {:>7.1}%\n",
+                payee.payee_name,
+                payee.total_spending.abs(),
+                payee.transaction_count,
+                payee.average.abs(),
+                payee.percentage
+            ));
+        }
+
+        output.push_str(&"-".repeat(80));
+        output.push('\n');
+        output.push_str(&format!(
+            "{:<35} {:>12}\n",
+            "TOTAL SPENDING",
+            self.total_spending.abs()
+        ));
+
+        output
+    }
+
+    /// Format the payee breakdown as a Markdown table
+    pub fn format_payees_markdown(&self) -> String {
+        let mut output = format!(
+            "## Spending by Payee: {} to {}\n\n",
+            self.start_date, self.end_date
+        );
+
+        let mut table = MarkdownTable::new(&[
+            ("Payee", Align::Left),
+            ("Amount", Align::Right),
+            ("Count", Align::Right),
+            ("Average", Align::Right),
+            ("%", Align::Right),
+        ]);
+        for payee in &self.payees {
+            table.row(vec![
+                payee.payee_name.clone(),
+                payee.total_spending.abs().to_string(),
+                payee.transaction_count.to_string(),
+                payee.average.abs().to_string(),
+                format!("{:.1}%", payee.percentage),
+            ]);
+        }
+        table.bold_row(vec![
+            "Total Spending".to_string(),
+            self.total_spending.abs().to_string(),
+        ]);
+
+        output.push_str(&table.render());
+        output
+    }
+
+    /// Export the payee breakdown to CSV format
+    pub fn export_payees_csv<W: Write>(&self, writer: &mut W) -> EnvelopeResult<()> {
+        writeln!(
+            writer,
+            "Start Date,End Date,Payee,Amount,Transaction Count,Average,Percentage"
+        )
+        .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
+
+        for payee in &self.payees {
+            writeln!(
+                writer,
+                "{},{},{},{:.2},{},{:.2},{:.2}",
+                self.start_date,
+                self.end_date,
+                payee.payee_name,
+                payee.total_spending.abs().cents() as f64 / 100.0,
+                payee.transaction_count,
+                payee.average.abs().cents() as f64 / 100.0,
+                payee.percentage
+            )
+            .map_err(|e| crate::error::EnvelopeError::Export(e.to_string()))?;
+        }
+
+        Ok(())
+    }
+
+    /// Get top spending payees
+    pub fn top_payees(&self, limit: usize) -> Vec<&SpendingByPayee> {
+        self.payees.iter().take(limit).collect()
+    }
+
     /// Get top spending categories
     pub fn top_categories(&self, limit: usize) -> Vec<&SpendingByCategory> {
         let mut all_categories: Vec<_> = self.groups.iter().flat_map(|g| &g.categories).collect();
@@ -350,7 +629,7 @@ impl SpendingReport {
 mod tests {
     use super::*;
     use crate::config::paths::EnvelopePaths;
-    use crate::models::{Account, AccountType, Category, CategoryGroup, Transaction};
+    use crate::models::{Account, AccountType, Category, CategoryGroup, Split, Transaction};
     use tempfile::TempDir;
 
     fn create_test_storage() -> (TempDir, Storage) {
@@ -461,4 +740,121 @@ mod tests {
         // Should be sorted by spending (highest spending first)
         assert!(top[0].total_spending.cents() <= top[1].total_spending.cents());
     }
+
+    #[test]
+    fn test_uncategorized_spending_bucket() {
+        let (_temp_dir, storage) = create_test_storage();
+
+        let group = CategoryGroup::new("Test Group");
+        storage.categories.upsert_group(group.clone()).unwrap();
+        let cat = Category::new("Groceries", group.id);
+        storage.categories.upsert_category(cat.clone()).unwrap();
+
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+
+        let mut categorized = Transaction::new(account.id, date, Money::from_cents(-6000));
+        categorized.category_id = Some(cat.id);
+        storage.transactions.upsert(categorized).unwrap();
+
+        // No category at all
+        let uncategorized = Transaction::new(account.id, date, Money::from_cents(-3000));
+        storage.transactions.upsert(uncategorized).unwrap();
+
+        // Category that no longer exists
+        let mut orphaned = Transaction::new(account.id, date, Money::from_cents(-1000));
+        orphaned.category_id = Some(CategoryId::new());
+        storage.transactions.upsert(orphaned).unwrap();
+
+        let report = SpendingReport::generate(
+            &storage,
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+        )
+        .unwrap();
+
+        assert_eq!(report.uncategorized_spending.cents(), -4000);
+        assert_eq!(report.uncategorized_count, 2);
+        assert!((report.uncategorized_percentage - 40.0).abs() < 0.01);
+
+        // Breakdown plus uncategorized bucket equals total outflows
+        let categorized_total: i64 = report.groups.iter().map(|g| g.total_spending.cents()).sum();
+        assert_eq!(
+            categorized_total + report.uncategorized_spending.cents(),
+            report.total_spending.cents()
+        );
+
+        assert!(report.format_terminal().contains("(Uncategorized)"));
+    }
+
+    #[test]
+    fn test_spending_by_payee() {
+        let (_temp_dir, storage) = create_test_storage();
+
+        let group = CategoryGroup::new("Test Group");
+        storage.categories.upsert_group(group.clone()).unwrap();
+        let groceries = Category::new("Groceries", group.id);
+        let household = Category::new("Household", group.id);
+        storage
+            .categories
+            .upsert_category(groceries.clone())
+            .unwrap();
+        storage
+            .categories
+            .upsert_category(household.clone())
+            .unwrap();
+
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+        let spend = |payee: &str, cents: i64| {
+            let mut txn = Transaction::new(account.id, date, Money::from_cents(cents));
+            txn.payee_name = payee.to_string();
+            txn.category_id = Some(groceries.id);
+            txn
+        };
+
+        storage.transactions.upsert(spend("Costco", -4000)).unwrap();
+        storage.transactions.upsert(spend("costco", -2000)).unwrap();
+        storage.transactions.upsert(spend("Bakery", -1500)).unwrap();
+        storage
+            .transactions
+            .upsert(spend("Employer", 100000))
+            .unwrap();
+
+        // A split counts once, with its whole outflow, for the payee
+        let mut split = spend("Target", -9000);
+        split.add_split(Split::new(groceries.id, Money::from_cents(-5000)));
+        split.add_split(Split::new(household.id, Money::from_cents(-4000)));
+        storage.transactions.upsert(split).unwrap();
+
+        let report = SpendingReport::generate(
+            &storage,
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+        )
+        .unwrap();
+
+        let names: Vec<_> = report
+            .payees
+            .iter()
+            .map(|p| p.payee_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["Target", "Costco", "Bakery"]);
+
+        let target = &report.payees[0];
+        assert_eq!(target.total_spending.cents(), -9000);
+        assert_eq!(target.transaction_count, 1);
+
+        let costco = &report.payees[1];
+        assert_eq!(costco.total_spending.cents(), -6000);
+        assert_eq!(costco.transaction_count, 2);
+        assert_eq!(costco.average.cents(), -3000);
+
+        let payee_total: i64 = report.payees.iter().map(|p| p.total_spending.cents()).sum();
+        assert_eq!(payee_total, report.total_spending.cents());
+    }
 }
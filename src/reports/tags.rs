@@ -0,0 +1,352 @@
+//! Tag Report
+//!
+//! Totals tagged transactions for a date range, either across every tag or
+//! for a single tag with its matching transactions listed.
+
+use crate::error::{EnvelopeError, EnvelopeResult};
+use crate::models::{normalize_tag, Money, Transaction};
+use crate::reports::markdown::{Align, MarkdownTable};
+use crate::storage::Storage;
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::io::Write;
+
+/// Total for a single tag
+#[derive(Debug, Clone, Serialize)]
+pub struct TagTotal {
+    /// Tag name (normalized)
+    pub tag: String,
+    /// Net amount of the tagged transactions (negative for spending)
+    pub total: Money,
+    /// Number of tagged transactions
+    pub transaction_count: usize,
+}
+
+/// Tag Report
+#[derive(Debug, Clone, Serialize)]
+pub struct TagReport {
+    /// Start date of the report
+    pub start_date: NaiveDate,
+    /// End date of the report
+    pub end_date: NaiveDate,
+    /// The tag reported on, if limited to one
+    pub tag: Option<String>,
+    /// Totals per tag, most spending first
+    pub totals: Vec<TagTotal>,
+    /// Matching transactions, date ascending (only for a single tag)
+    pub transactions: Vec<Transaction>,
+}
+
+impl TagReport {
+    /// Generate a tag report for a date range
+    ///
+    /// With `tag` set, only that tag is totaled and its transactions are
+    /// included. A transaction with several tags counts toward each of them.
+    pub fn generate(
+        storage: &Storage,
+        tag: Option<&str>,
+        start_date: NaiveDate,
+        end_date: NaiveDate,
+    ) -> EnvelopeResult<Self> {
+        let tag = match tag {
+            Some(t) => Some(
+                normalize_tag(t)
+                    .ok_or_else(|| EnvelopeError::Validation("Tag cannot be empty".into()))?,
+            ),
+            None => None,
+        };
+
+        let mut transactions: Vec<Transaction> = storage
+            .transactions
+            .get_by_date_range(start_date, end_date)?
+            .into_iter()
+            .filter(|t| !t.is_starting_balance && !t.tags.is_empty())
+            .collect();
+
+        if let Some(tag) = &tag {
+            transactions.retain(|t| t.tags.contains(tag));
+        }
+
+        let mut by_tag: BTreeMap<&str, (Money, usize)> = BTreeMap::new();
+        for txn in &transactions {
+            for t in &txn.tags {
+                if tag.as_ref().is_some_and(|wanted| wanted != t) {
+                    continue;
+                }
+                let entry = by_tag.entry(t).or_insert((Money::zero(), 0));
+                entry.0 += txn.amount;
+                entry.1 += 1;
+            }
+        }
+
+        let mut totals: Vec<TagTotal> = by_tag
+            .into_iter()
+            .map(|(tag, (total, transaction_count))| TagTotal {
+                tag: tag.to_string(),
+                total,
+                transaction_count,
+            })
+            .collect();
+        totals.sort_by(|a, b| a.total.cmp(&b.total));
+
+        if tag.is_some() {
+            transactions.sort_by(|a, b| a.date.cmp(&b.date));
+        } else {
+            transactions.clear();
+        }
+
+        Ok(Self {
+            start_date,
+            end_date,
+            tag,
+            totals,
+            transactions,
+        })
+    }
+
+    /// Net total across the reported tags' transactions
+    ///
+    /// For a single tag this is the tag's total. Across all tags a
+    /// transaction with several tags is counted once per tag.
+    pub fn grand_total(&self) -> Money {
+        self.totals.iter().map(|t| t.total).sum()
+    }
+
+    /// Format the report for terminal display
+    pub fn format_terminal(&self) -> String {
+        let mut output = String::new();
+
+        // Header
+        match &self.tag {
+            Some(tag) => output.push_str(&format!(
+                "Tag Report: #{} ({} to {})\n",
+                tag, self.start_date, self.end_date
+            )),
+            None => output.push_str(&format!(
+                "Tag Report: {} to {}\n",
+                self.start_date, self.end_date
+            )),
+        }
+        output.push_str(&"=".repeat(70));
+        output.push('\n');
+
+        if self.totals.is_empty() {
+            output.push_str("No tagged transactions in this period.\n");
+            return output;
+        }
+
+        if self.tag.is_some() {
+            output.push_str(&format!(
+                "{:<12} {:<30} {:>12}\n",
+                "Date", "Payee", "Amount"
+            ));
+            output.push_str(&"-".repeat(70));
+            output.push('\n');
+
+            for txn in &self.transactions {
+                output.push_str(&format!(
+                    "{:<12} {:<30} {:>12}\n",
+                    txn.date.format("%Y-%m-%d"),
+                    truncate(&txn.payee_name, 30),
+                    txn.amount
+                ));
+            }
+        } else {
+            output.push_str(&format!("{:<35} {:>12} {:>8}\n", "Tag", "Amount", "Count"));
+            output.push_str(&"-".repeat(70));
+            output.push('\n');
+
+            for total in &self.totals {
+                output.push_str(&format!(
+                    "{:<35} {:>12} {:>8}\n",
+                    format!("#{}", total.tag),
+                    total.total,
+                    total.transaction_count
+                ));
+            }
+        }
+
+        output.push_str(&"-".repeat(70));
+        output.push('\n');
+        let count: usize = self.totals.iter().map(|t| t.transaction_count).sum();
+        output.push_str(&format!(
+            "{:<35} {:>12} {:>8}\n",
+            "TOTAL",
+            self.grand_total(),
+            count
+        ));
+
+        output
+    }
+
+    /// Format the report as a Markdown table
+    ///
+    /// A single-tag report lists its transactions instead of per-tag totals.
+    pub fn format_markdown(&self) -> String {
+        let mut output = match &self.tag {
+            Some(tag) => format!(
+                "## Tag Report: #{} ({} to {})\n\n",
+                tag, self.start_date, self.end_date
+            ),
+            None => format!(
+                "## Tag Report: {} to {}\n\n",
+                self.start_date, self.end_date
+            ),
+        };
+
+        if self.totals.is_empty() {
+            output.push_str("No tagged transactions in this period.\n");
+            return output;
+        }
+
+        let count: usize = self.totals.iter().map(|t| t.transaction_count).sum();
+        let table = if self.tag.is_some() {
+            let mut table = MarkdownTable::new(&[
+                ("Date", Align::Left),
+                ("Payee", Align::Left),
+                ("Amount", Align::Right),
+            ]);
+            for txn in &self.transactions {
+                table.row(vec![
+                    txn.date.format("%Y-%m-%d").to_string(),
+                    txn.payee_name.clone(),
+                    txn.amount.to_string(),
+                ]);
+            }
+            table.bold_row(vec![
+                "Total".to_string(),
+                String::new(),
+                self.grand_total().to_string(),
+            ]);
+            table
+        } else {
+            let mut table = MarkdownTable::new(&[
+                ("Tag", Align::Left),
+                ("Amount", Align::Right),
+                ("Count", Align::Right),
+            ]);
+            for total in &self.totals {
+                table.row(vec![
+                    format!("#{}", total.tag),
+                    total.total.to_string(),
+                    total.transaction_count.to_string(),
+                ]);
+            }
+            table.bold_row(vec![
+                "Total".to_string(),
+                self.grand_total().to_string(),
+                count.to_string(),
+            ]);
+            table
+        };
+
+        output.push_str(&table.render());
+        output
+    }
+
+    /// Export the report to CSV format
+    pub fn export_csv<W: Write>(&self, writer: &mut W) -> EnvelopeResult<()> {
+        if self.tag.is_some() {
+            writeln!(writer, "Tag,Date,Payee,Amount,Memo")
+                .map_err(|e| EnvelopeError::Export(e.to_string()))?;
+
+            let tag = self.tag.as_deref().unwrap_or_default();
+            for txn in &self.transactions {
+                writeln!(
+                    writer,
+                    "{},{},\"{}\",{:.2},\"{}\"",
+                    tag,
+                    txn.date,
+                    txn.payee_name.replace('"', "\"\""),
+                    txn.amount.cents() as f64 / 100.0,
+                    txn.memo.replace('"', "\"\"")
+                )
+                .map_err(|e| EnvelopeError::Export(e.to_string()))?;
+            }
+        } else {
+            writeln!(writer, "Start Date,End Date,Tag,Amount,Transaction Count")
+                .map_err(|e| EnvelopeError::Export(e.to_string()))?;
+
+            for total in &self.totals {
+                writeln!(
+                    writer,
+                    "{},{},{},{:.2},{}",
+                    self.start_date,
+                    self.end_date,
+                    total.tag,
+                    total.total.cents() as f64 / 100.0,
+                    total.transaction_count
+                )
+                .map_err(|e| EnvelopeError::Export(e.to_string()))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+/// Truncate a string to a maximum number of characters
+fn truncate(s: &str, max_len: usize) -> String {
+    if s.chars().count() <= max_len {
+        s.to_string()
+    } else {
+        let truncated: String = s.chars().take(max_len.saturating_sub(3)).collect();
+        format!("{}...", truncated)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::paths::EnvelopePaths;
+    use crate::models::{Account, AccountType};
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (TempDir, Storage) {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let mut storage = Storage::new(paths).unwrap();
+        storage.load_all().unwrap();
+        (temp_dir, storage)
+    }
+
+    #[test]
+    fn test_tag_report() {
+        let (_temp_dir, storage) = create_test_storage();
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+
+        let entries = [
+            (2024, 3, -10000, vec!["tax-deductible"]),
+            (2024, 6, -2500, vec!["tax-deductible", "vacation2024"]),
+            (2024, 7, -4000, vec!["vacation2024"]),
+            (2025, 1, -9900, vec!["tax-deductible"]),
+            (2024, 8, -100, vec![]),
+        ];
+        for (year, month, cents, tags) in entries {
+            let mut txn = Transaction::new(
+                account.id,
+                NaiveDate::from_ymd_opt(year, month, 1).unwrap(),
+                Money::from_cents(cents),
+            );
+            txn.add_tags(&tags);
+            storage.transactions.upsert(txn).unwrap();
+        }
+
+        let start = NaiveDate::from_ymd_opt(2024, 1, 1).unwrap();
+        let end = NaiveDate::from_ymd_opt(2024, 12, 31).unwrap();
+
+        let report = TagReport::generate(&storage, Some("#Tax-Deductible"), start, end).unwrap();
+        assert_eq!(report.tag.as_deref(), Some("tax-deductible"));
+        assert_eq!(report.transactions.len(), 2);
+        assert_eq!(report.grand_total(), Money::from_cents(-12500));
+
+        let report = TagReport::generate(&storage, None, start, end).unwrap();
+        assert_eq!(report.totals.len(), 2);
+        assert_eq!(report.totals[0].tag, "tax-deductible");
+        assert_eq!(report.totals[1].tag, "vacation2024");
+        assert_eq!(report.totals[1].total, Money::from_cents(-6500));
+        assert!(report.transactions.is_empty());
+    }
+}
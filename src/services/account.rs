@@ -3,18 +3,31 @@
 //! Provides business logic for account management including CRUD operations,
 //! balance calculation, and validation.
 
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::BTreeMap;
+
 use crate::audit::EntityType;
+use crate::config::clock;
 use crate::error::{EnvelopeError, EnvelopeResult};
-use crate::models::{Account, AccountId, AccountType, Money, TransactionStatus};
+use crate::models::{
+    Account, AccountId, AccountType, CategoryId, Money, Transaction, TransactionId,
+    TransactionStatus,
+};
+use crate::rates::normalize_currency;
+use crate::services::ScheduledService;
 use crate::storage::Storage;
 
+/// Days of daily balances shown in account details
+pub const BALANCE_HISTORY_DAYS: i64 = 30;
+
 /// Service for account management
 pub struct AccountService<'a> {
     storage: &'a Storage,
 }
 
 /// Summary of an account with computed fields
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize)]
 pub struct AccountSummary {
     pub account: Account,
     /// Current balance (starting balance + all transactions)
@@ -23,6 +36,29 @@ pub struct AccountSummary {
     pub cleared_balance: Money,
     /// Number of uncleared transactions
     pub uncleared_count: usize,
+    /// Number of transactions in the register
+    pub transaction_count: usize,
+}
+
+impl AccountSummary {
+    /// Total of the transactions that haven't cleared yet
+    pub fn uncleared_balance(&self) -> Money {
+        self.balance - self.cleared_balance
+    }
+}
+
+/// What merging one account into another moves, or would move
+#[derive(Debug, Clone)]
+pub struct AccountMergeSummary {
+    /// Transactions reassigned to the target account
+    pub transactions_moved: usize,
+    /// How many of those are reconciled (moved without other changes)
+    pub reconciled_moved: usize,
+    /// Transfers between the two accounts, unlinked since both sides now
+    /// belong to the same account
+    pub transfers_unlinked: usize,
+    /// The target account's combined starting balance
+    pub starting_balance: Money,
 }
 
 impl<'a> AccountService<'a> {
@@ -55,9 +91,17 @@ impl<'a> AccountService<'a> {
             });
         }
 
-        // Create the account
+        // Create the account, placed after the existing ones
         let mut account = Account::with_starting_balance(name, account_type, starting_balance);
         account.on_budget = on_budget;
+        account.sort_order = self
+            .storage
+            .accounts
+            .get_all()?
+            .iter()
+            .map(|a| a.sort_order)
+            .max()
+            .map_or(0, |max| max + 1);
 
         // Validate
         account
@@ -76,6 +120,113 @@ impl<'a> AccountService<'a> {
             &account,
         )?;
 
+        // Record the opening balance in the register
+        if !starting_balance.is_zero() {
+            let txn = Transaction::starting_balance(account.id, clock::today(), starting_balance);
+            self.storage.transactions.upsert(txn.clone())?;
+            self.storage.transactions.save()?;
+            self.storage.log_create(
+                EntityType::Transaction,
+                txn.id.to_string(),
+                Some(format!("{} {}", txn.date, txn.payee_name)),
+                &txn,
+            )?;
+        }
+
+        Ok(account)
+    }
+
+    /// Set an account's starting balance
+    ///
+    /// Creates, updates, or removes the account's "Starting Balance"
+    /// transaction so the register, balances, and exports agree. When no date
+    /// is given, an existing starting balance keeps its date; a new one is
+    /// dated on or before the account's earliest transaction.
+    pub fn set_starting_balance(
+        &self,
+        id: AccountId,
+        amount: Money,
+        date: Option<NaiveDate>,
+    ) -> EnvelopeResult<Account> {
+        let mut account = self
+            .storage
+            .accounts
+            .get(id)?
+            .ok_or_else(|| EnvelopeError::account_not_found(id.to_string()))?;
+
+        let transactions = self.storage.transactions.get_by_account(id)?;
+        let existing = transactions.iter().find(|t| t.is_starting_balance).cloned();
+
+        match existing {
+            Some(txn) if amount.is_zero() => {
+                self.storage.transactions.delete(txn.id)?;
+                self.storage.log_delete(
+                    EntityType::Transaction,
+                    txn.id.to_string(),
+                    Some(format!("{} {}", txn.date, txn.payee_name)),
+                    &txn,
+                )?;
+            }
+            Some(mut txn) => {
+                let before = txn.clone();
+                txn.amount = amount;
+                if let Some(date) = date {
+                    txn.date = date;
+                }
+                txn.updated_at = clock::now();
+                self.storage.transactions.upsert(txn.clone())?;
+                self.storage.log_update(
+                    EntityType::Transaction,
+                    txn.id.to_string(),
+                    Some(format!("{} {}", txn.date, txn.payee_name)),
+                    &before,
+                    &txn,
+                    Some(format!(
+                        "starting balance: {} -> {}",
+                        before.amount, txn.amount
+                    )),
+                )?;
+            }
+            None if amount.is_zero() => {}
+            None => {
+                let today = clock::today();
+                let date = date.unwrap_or_else(|| {
+                    transactions
+                        .iter()
+                        .map(|t| t.date)
+                        .min()
+                        .map_or(today, |earliest| earliest.min(today))
+                });
+                let txn = Transaction::starting_balance(id, date, amount);
+                self.storage.transactions.upsert(txn.clone())?;
+                self.storage.log_create(
+                    EntityType::Transaction,
+                    txn.id.to_string(),
+                    Some(format!("{} {}", txn.date, txn.payee_name)),
+                    &txn,
+                )?;
+            }
+        }
+        self.storage.transactions.save()?;
+
+        let before = account.clone();
+        account.starting_balance = amount;
+        account.updated_at = clock::now();
+        self.storage.accounts.upsert(account.clone())?;
+        self.storage.accounts.save()?;
+
+        self.storage.log_update(
+            EntityType::Account,
+            account.id.to_string(),
+            Some(account.name.clone()),
+            &before,
+            &account,
+            Some(format!(
+                "starting_balance: {} -> {}",
+                before.starting_balance, account.starting_balance
+            )),
+        )?;
+
         Ok(account)
     }
 
@@ -104,6 +255,18 @@ impl<'a> AccountService<'a> {
         Ok(None)
     }
 
+    /// Find active accounts whose name contains `query` (case-insensitive)
+    ///
+    /// Used to offer candidates when [`Self::find`] has no exact match.
+    pub fn search(&self, query: &str) -> EnvelopeResult<Vec<Account>> {
+        let query = query.trim().to_lowercase();
+        Ok(self
+            .list(false)?
+            .into_iter()
+            .filter(|a| a.name.to_lowercase().contains(&query))
+            .collect())
+    }
+
     /// Get all accounts
     pub fn list(&self, include_archived: bool) -> EnvelopeResult<Vec<Account>> {
         if include_archived {
@@ -133,8 +296,9 @@ impl<'a> AccountService<'a> {
     pub fn get_summary(&self, account: &Account) -> EnvelopeResult<AccountSummary> {
         let transactions = self.storage.transactions.get_by_account(account.id)?;
 
-        let mut balance = account.starting_balance;
-        let mut cleared_balance = account.starting_balance;
+        let opening = account.untracked_starting_balance(&transactions);
+        let mut balance = opening;
+        let mut cleared_balance = opening;
         let mut uncleared_count = 0;
 
         for txn in &transactions {
@@ -155,6 +319,7 @@ impl<'a> AccountService<'a> {
             balance,
             cleared_balance,
             uncleared_count,
+            transaction_count: transactions.len(),
         })
     }
 
@@ -169,7 +334,7 @@ impl<'a> AccountService<'a> {
         let transactions = self.storage.transactions.get_by_account(account_id)?;
         let transaction_total: Money = transactions.iter().map(|t| t.amount).sum();
 
-        Ok(account.starting_balance + transaction_total)
+        Ok(account.untracked_starting_balance(&transactions) + transaction_total)
     }
 
     /// Calculate the cleared balance for an account
@@ -192,7 +357,55 @@ impl<'a> AccountService<'a> {
             .map(|t| t.amount)
             .sum();
 
-        Ok(account.starting_balance + cleared_total)
+        Ok(account.untracked_starting_balance(&transactions) + cleared_total)
+    }
+
+    /// Calculate an account's balance at the end of `date`
+    pub fn balance_as_of(&self, account_id: AccountId, date: NaiveDate) -> EnvelopeResult<Money> {
+        let account = self
+            .storage
+            .accounts
+            .get(account_id)?
+            .ok_or_else(|| EnvelopeError::account_not_found(account_id.to_string()))?;
+
+        let transactions = self.storage.transactions.get_by_account(account_id)?;
+        let transaction_total: Money = transactions
+            .iter()
+            .filter(|t| t.date <= date)
+            .map(|t| t.amount)
+            .sum();
+
+        Ok(account.untracked_starting_balance(&transactions) + transaction_total)
+    }
+
+    /// End-of-day balances for every date from `start` to `end`, inclusive
+    ///
+    /// Starts from the balance the day before `start` and replays each day's
+    /// transactions, so days without activity repeat the previous balance.
+    pub fn daily_balances(
+        &self,
+        account_id: AccountId,
+        start: NaiveDate,
+        end: NaiveDate,
+    ) -> EnvelopeResult<Vec<(NaiveDate, Money)>> {
+        let mut balance = match start.pred_opt() {
+            Some(day_before) => self.balance_as_of(account_id, day_before)?,
+            None => Money::zero(),
+        };
+
+        let mut by_date: BTreeMap<NaiveDate, Money> = BTreeMap::new();
+        for txn in self.storage.transactions.get_by_account(account_id)? {
+            if txn.date >= start && txn.date <= end {
+                *by_date.entry(txn.date).or_default() += txn.amount;
+            }
+        }
+
+        let mut balances = Vec::new();
+        for date in start.iter_days().take_while(|d| *d <= end) {
+            balance += by_date.get(&date).copied().unwrap_or_default();
+            balances.push((date, balance));
+        }
+        Ok(balances)
     }
 
     /// Update an account
@@ -225,7 +438,7 @@ impl<'a> AccountService<'a> {
             account.name = new_name.to_string();
         }
 
-        account.updated_at = chrono::Utc::now();
+        account.updated_at = clock::now();
 
         // Validate
         account
@@ -255,7 +468,141 @@ impl<'a> AccountService<'a> {
         Ok(account)
     }
 
+    /// Set or clear the category prefilled on new transactions in this account
+    pub fn set_default_category(
+        &self,
+        id: AccountId,
+        category_id: Option<CategoryId>,
+    ) -> EnvelopeResult<Account> {
+        let mut account = self
+            .storage
+            .accounts
+            .get(id)?
+            .ok_or_else(|| EnvelopeError::account_not_found(id.to_string()))?;
+
+        if let Some(cat_id) = category_id {
+            self.storage
+                .categories
+                .get_category(cat_id)?
+                .ok_or_else(|| EnvelopeError::category_not_found(cat_id.to_string()))?;
+        }
+
+        let before = account.clone();
+        account.default_category_id = category_id;
+        account.updated_at = clock::now();
+
+        // Save
+        self.storage.accounts.upsert(account.clone())?;
+        self.storage.accounts.save()?;
+
+        // Audit log
+        self.storage.log_update(
+            EntityType::Account,
+            account.id.to_string(),
+            Some(account.name.clone()),
+            &before,
+            &account,
+            Some(format!(
+                "default_category: {:?} -> {:?}",
+                before.default_category_id, account.default_category_id
+            )),
+        )?;
+
+        Ok(account)
+    }
+
+    /// Set the account's currency, or clear it to use the base currency
+    ///
+    /// Existing amounts are kept as they are; they're taken to already be in
+    /// the new currency.
+    pub fn set_currency(&self, id: AccountId, currency: Option<&str>) -> EnvelopeResult<Account> {
+        let mut account = self
+            .storage
+            .accounts
+            .get(id)?
+            .ok_or_else(|| EnvelopeError::account_not_found(id.to_string()))?;
+
+        let currency = currency
+            .map(|code| {
+                normalize_currency(code).ok_or_else(|| {
+                    EnvelopeError::Validation(format!(
+                        "Invalid currency code '{}'. Use a 3-letter code like EUR",
+                        code
+                    ))
+                })
+            })
+            .transpose()?;
+
+        let before = account.clone();
+        account.currency = currency;
+        account.updated_at = clock::now();
+
+        self.storage.accounts.upsert(account.clone())?;
+        self.storage.accounts.save()?;
+
+        self.storage.log_update(
+            EntityType::Account,
+            account.id.to_string(),
+            Some(account.name.clone()),
+            &before,
+            &account,
+            Some(format!(
+                "currency: {:?} -> {:?}",
+                before.currency, account.currency
+            )),
+        )?;
+
+        Ok(account)
+    }
+
+    /// Move an account one place up or down among the active accounts
+    ///
+    /// Returns false if it is already at that end of the list. Every active
+    /// account is renumbered, so accounts that still share the default order
+    /// get distinct positions.
+    pub fn move_account(&self, id: AccountId, up: bool) -> EnvelopeResult<bool> {
+        let mut order: Vec<AccountId> = self
+            .storage
+            .accounts
+            .get_active()?
+            .iter()
+            .map(|a| a.id)
+            .collect();
+
+        let pos = order
+            .iter()
+            .position(|&a| a == id)
+            .ok_or_else(|| EnvelopeError::account_not_found(id.to_string()))?;
+        let target = if up {
+            pos.checked_sub(1)
+        } else {
+            Some(pos + 1).filter(|&t| t < order.len())
+        };
+        let Some(target) = target else {
+            return Ok(false);
+        };
+
+        order.swap(pos, target);
+        self.reorder(&order)?;
+        Ok(true)
+    }
+
+    /// Reorder accounts
+    pub fn reorder(&self, order: &[AccountId]) -> EnvelopeResult<()> {
+        for (i, &id) in order.iter().enumerate() {
+            if let Some(mut account) = self.storage.accounts.get(id)? {
+                account.sort_order = i as i32;
+                account.updated_at = clock::now();
+                self.storage.accounts.upsert(account)?;
+            }
+        }
+        self.storage.accounts.save()?;
+        Ok(())
+    }
+
     /// Archive an account (soft delete)
+    ///
+    /// Its schedules are stopped, since they could no longer post.
     pub fn archive(&self, id: AccountId) -> EnvelopeResult<Account> {
         let mut account = self
             .storage
@@ -286,6 +633,8 @@ impl<'a> AccountService<'a> {
             Some("archived: false -> true".to_string()),
         )?;
 
+        ScheduledService::new(self.storage).deactivate_for_account(account.id)?;
+
         Ok(account)
     }
 
@@ -357,6 +706,244 @@ impl<'a> AccountService<'a> {
             .filter(|a| a.account_type == account_type)
             .count())
     }
+
+    /// Find account names shared by more than one account (case-insensitive)
+    ///
+    /// Returns one entry per duplicated name, listing every matching account.
+    /// Creation and renaming reject duplicates, but older data or restored
+    /// exports may still contain them.
+    pub fn find_duplicate_names(&self) -> EnvelopeResult<Vec<Vec<Account>>> {
+        let mut by_name: BTreeMap<String, Vec<Account>> = BTreeMap::new();
+        for account in self.storage.accounts.get_all()? {
+            by_name
+                .entry(account.name.to_lowercase())
+                .or_default()
+                .push(account);
+        }
+
+        Ok(by_name
+            .into_values()
+            .filter(|accounts| accounts.len() > 1)
+            .collect())
+    }
+
+    /// Preview merging `source` into `target` without changing anything
+    pub fn merge_preview(
+        &self,
+        source: AccountId,
+        target: AccountId,
+    ) -> EnvelopeResult<AccountMergeSummary> {
+        let (source, target) = self.merge_accounts(source, target)?;
+        let moving = self.storage.transactions.get_by_account(source.id)?;
+
+        Ok(AccountMergeSummary {
+            transactions_moved: moving.iter().filter(|t| !t.is_starting_balance).count(),
+            reconciled_moved: moving
+                .iter()
+                .filter(|t| !t.is_starting_balance && t.is_locked())
+                .count(),
+            transfers_unlinked: self.transfers_between(&moving, target.id)?.len(),
+            starting_balance: source.starting_balance + target.starting_balance,
+        })
+    }
+
+    /// Merge `source` into `target`, then delete `source`
+    ///
+    /// Every transaction moves to the target account as is, including
+    /// reconciled ones. Transfers between the two accounts lose their link,
+    /// since a transfer can't stay within one account; the pair still nets
+    /// to zero. The starting balances are added together on the target's
+    /// starting balance transaction, and the source's schedules move to the
+    /// target.
+    pub fn merge(
+        &self,
+        source: AccountId,
+        target: AccountId,
+    ) -> EnvelopeResult<AccountMergeSummary> {
+        let (source, target) = self.merge_accounts(source, target)?;
+        let moving = self.storage.transactions.get_by_account(source.id)?;
+        let unlinked = self.transfers_between(&moving, target.id)?;
+        let mut summary = AccountMergeSummary {
+            transactions_moved: 0,
+            reconciled_moved: 0,
+            transfers_unlinked: unlinked.len(),
+            starting_balance: source.starting_balance + target.starting_balance,
+        };
+
+        for mut txn in moving {
+            // The target's starting balance absorbs this one below
+            if txn.is_starting_balance {
+                self.storage.transactions.delete(txn.id)?;
+                self.storage.log_delete(
+                    EntityType::Transaction,
+                    txn.id.to_string(),
+                    Some(format!("{} {}", txn.date, txn.payee_name)),
+                    &txn,
+                )?;
+                continue;
+            }
+
+            let before = txn.clone();
+            txn.account_id = target.id;
+            if unlinked.contains(&txn.id) {
+                txn.transfer_transaction_id = None;
+            }
+            self.storage.transactions.upsert(txn.clone())?;
+            self.storage.log_update(
+                EntityType::Transaction,
+                txn.id.to_string(),
+                Some(format!("{} {}", txn.date, txn.payee_name)),
+                &before,
+                &txn,
+                Some(format!("account: {} -> {}", source.name, target.name)),
+            )?;
+
+            summary.transactions_moved += 1;
+            if txn.is_locked() {
+                summary.reconciled_moved += 1;
+            }
+        }
+
+        // The other side of each unlinked transfer was already in the target
+        for txn in self.storage.transactions.get_by_account(target.id)? {
+            let Some(linked_id) = txn.transfer_transaction_id else {
+                continue;
+            };
+            if !unlinked.contains(&linked_id) {
+                continue;
+            }
+            let before = txn.clone();
+            let mut txn = txn;
+            txn.transfer_transaction_id = None;
+            self.storage.transactions.upsert(txn.clone())?;
+            self.storage.log_update(
+                EntityType::Transaction,
+                txn.id.to_string(),
+                Some(format!("{} {}", txn.date, txn.payee_name)),
+                &before,
+                &txn,
+                Some("transfer unlinked by account merge".to_string()),
+            )?;
+        }
+
+        // Transfer splits elsewhere now go to the target account
+        for mut txn in self.storage.transactions.get_all()? {
+            if !txn
+                .splits
+                .iter()
+                .any(|s| s.transfer_account_id == Some(source.id))
+            {
+                continue;
+            }
+            let before = txn.clone();
+            for split in &mut txn.splits {
+                if split.transfer_account_id == Some(source.id) {
+                    split.transfer_account_id = Some(target.id);
+                }
+            }
+            self.storage.transactions.upsert(txn.clone())?;
+            self.storage.log_update(
+                EntityType::Transaction,
+                txn.id.to_string(),
+                Some(format!("{} {}", txn.date, txn.payee_name)),
+                &before,
+                &txn,
+                Some(format!(
+                    "split transfer account: {} -> {}",
+                    source.name, target.name
+                )),
+            )?;
+        }
+        self.storage.transactions.save()?;
+
+        self.set_starting_balance(target.id, summary.starting_balance, None)?;
+        ScheduledService::new(self.storage).retarget_account(source.id, target.id)?;
+
+        self.storage.accounts.delete(source.id)?;
+        self.storage.accounts.save()?;
+        self.storage.log_delete(
+            EntityType::Account,
+            source.id.to_string(),
+            Some(source.name.clone()),
+            &source,
+        )?;
+
+        Ok(summary)
+    }
+
+    /// Look up and check the two accounts of a merge
+    fn merge_accounts(
+        &self,
+        source: AccountId,
+        target: AccountId,
+    ) -> EnvelopeResult<(Account, Account)> {
+        if source == target {
+            return Err(EnvelopeError::Validation(
+                "Cannot merge an account into itself".into(),
+            ));
+        }
+
+        let source = self
+            .storage
+            .accounts
+            .get(source)?
+            .ok_or_else(|| EnvelopeError::account_not_found(source.to_string()))?;
+        let target = self
+            .storage
+            .accounts
+            .get(target)?
+            .ok_or_else(|| EnvelopeError::account_not_found(target.to_string()))?;
+
+        if source.currency != target.currency {
+            return Err(EnvelopeError::Validation(format!(
+                "Cannot merge '{}' into '{}': they use different currencies",
+                source.name, target.name
+            )));
+        }
+
+        // A transfer split between the two would become a split that
+        // transfers into its own account
+        for (from, to) in [(&source, &target), (&target, &source)] {
+            let splits_between = self
+                .storage
+                .transactions
+                .get_by_account(from.id)?
+                .into_iter()
+                .find(|t| {
+                    t.splits
+                        .iter()
+                        .any(|s| s.transfer_account_id == Some(to.id))
+                });
+            if let Some(txn) = splits_between {
+                return Err(EnvelopeError::Validation(format!(
+                    "Transaction {} in '{}' splits a transfer to '{}'; clear its splits before merging",
+                    txn.id, from.name, to.name
+                )));
+            }
+        }
+
+        Ok((source, target))
+    }
+
+    /// IDs of the moving transactions that are transfers with `target`
+    fn transfers_between(
+        &self,
+        moving: &[Transaction],
+        target: AccountId,
+    ) -> EnvelopeResult<Vec<TransactionId>> {
+        let mut ids = Vec::new();
+        for txn in moving {
+            let Some(linked_id) = txn.transfer_transaction_id else {
+                continue;
+            };
+            if let Some(linked) = self.storage.transactions.get(linked_id)? {
+                if linked.account_id == target {
+                    ids.push(txn.id);
+                }
+            }
+        }
+        Ok(ids)
+    }
 }
 
 #[cfg(test)]
@@ -425,6 +1012,25 @@ mod tests {
         assert_eq!(found.id, created.id);
     }
 
+    #[test]
+    fn test_search_accounts() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = AccountService::new(&storage);
+
+        for name in ["Chase Checking", "Chase Sapphire", "Ally Savings"] {
+            service
+                .create(name, AccountType::Checking, Money::zero(), true)
+                .unwrap();
+        }
+
+        let matches = service.search("chase").unwrap();
+        assert_eq!(matches.len(), 2);
+        assert!(matches.iter().all(|a| a.name.starts_with("Chase")));
+
+        assert_eq!(service.search("savings").unwrap().len(), 1);
+        assert!(service.search("Amex").unwrap().is_empty());
+    }
+
     #[test]
     fn test_list_accounts() {
         let (_temp_dir, storage) = create_test_storage();
@@ -462,6 +1068,38 @@ mod tests {
         assert_eq!(all.len(), 1);
     }
 
+    #[test]
+    fn test_move_account() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = AccountService::new(&storage);
+
+        let savings = service
+            .create("Savings", AccountType::Savings, Money::zero(), true)
+            .unwrap();
+        let checking = service
+            .create("Checking", AccountType::Checking, Money::zero(), true)
+            .unwrap();
+
+        // New accounts go to the end, regardless of name
+        let names = |storage: &Storage| -> Vec<String> {
+            storage
+                .accounts
+                .get_active()
+                .unwrap()
+                .into_iter()
+                .map(|a| a.name)
+                .collect()
+        };
+        assert_eq!(names(&storage), ["Savings", "Checking"]);
+
+        assert!(service.move_account(checking.id, true).unwrap());
+        assert_eq!(names(&storage), ["Checking", "Savings"]);
+
+        // Already at the top
+        assert!(!service.move_account(checking.id, true).unwrap());
+        assert!(!service.move_account(savings.id, false).unwrap());
+    }
+
     #[test]
     fn test_update_account() {
         let (_temp_dir, storage) = create_test_storage();
@@ -475,6 +1113,26 @@ mod tests {
         assert_eq!(updated.name, "New Name");
     }
 
+    #[test]
+    fn test_set_currency() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = AccountService::new(&storage);
+
+        let account = service
+            .create("Euro Savings", AccountType::Savings, Money::zero(), true)
+            .unwrap();
+        assert!(account.currency.is_none());
+
+        let updated = service.set_currency(account.id, Some("eur")).unwrap();
+        assert_eq!(updated.currency.as_deref(), Some("EUR"));
+        assert_eq!(updated.currency_or("USD"), "EUR");
+
+        assert!(service.set_currency(account.id, Some("euro")).is_err());
+
+        let cleared = service.set_currency(account.id, None).unwrap();
+        assert_eq!(cleared.currency_or("USD"), "USD");
+    }
+
     #[test]
     fn test_balance_calculation() {
         let (_temp_dir, storage) = create_test_storage();
@@ -490,8 +1148,6 @@ mod tests {
             .unwrap();
 
         // Add some transactions
-        use crate::models::Transaction;
-        use chrono::NaiveDate;
 
         let txn1 = Transaction::new(
             account.id,
@@ -515,5 +1171,249 @@ mod tests {
         // Cleared balance = 100000 + 20000 = 120000 (pending txn not counted)
         let cleared = service.calculate_cleared_balance(account.id).unwrap();
         assert_eq!(cleared.cents(), 120000);
+
+        // The summary counts the starting balance entry too
+        let summary = service.get_summary(&account).unwrap();
+        assert_eq!(summary.transaction_count, 3);
+        assert_eq!(summary.uncleared_balance().cents(), -5000);
+    }
+
+    #[test]
+    fn test_credit_card_negative_starting_balance() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = AccountService::new(&storage);
+
+        let card = service
+            .create("Visa", AccountType::Credit, Money::from_cents(-50000), true)
+            .unwrap();
+        assert_eq!(card.starting_balance.cents(), -50000);
+
+        // The opening debt is counted once, however the balance is asked for
+        let today = clock::today();
+        assert_eq!(service.calculate_balance(card.id).unwrap().cents(), -50000);
+        assert_eq!(
+            service.balance_as_of(card.id, today).unwrap().cents(),
+            -50000
+        );
+        let summary = service.get_summary(&card).unwrap();
+        assert_eq!(summary.balance.cents(), -50000);
+        assert_eq!(summary.transaction_count, 1);
+
+        assert!(AccountType::Credit
+            .starting_balance_warning(card.starting_balance)
+            .is_none());
+    }
+
+    #[test]
+    fn test_starting_balance_transaction() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = AccountService::new(&storage);
+
+        let account = service
+            .create(
+                "Test",
+                AccountType::Checking,
+                Money::from_cents(100000),
+                true,
+            )
+            .unwrap();
+
+        // Starting balance appears once in the register and is not double counted
+        let txns = storage.transactions.get_by_account(account.id).unwrap();
+        assert_eq!(txns.len(), 1);
+        assert!(txns[0].is_starting_balance);
+        assert_eq!(
+            service.calculate_balance(account.id).unwrap().cents(),
+            100000
+        );
+
+        // Updating replaces the existing starting balance transaction
+        let date = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+        let updated = service
+            .set_starting_balance(account.id, Money::from_cents(25000), Some(date))
+            .unwrap();
+        assert_eq!(updated.starting_balance.cents(), 25000);
+        let txns = storage.transactions.get_by_account(account.id).unwrap();
+        assert_eq!(txns.len(), 1);
+        assert_eq!(txns[0].date, date);
+        assert_eq!(
+            service.calculate_balance(account.id).unwrap().cents(),
+            25000
+        );
+
+        // Zero removes it
+        service
+            .set_starting_balance(account.id, Money::zero(), None)
+            .unwrap();
+        assert!(storage
+            .transactions
+            .get_by_account(account.id)
+            .unwrap()
+            .is_empty());
+        assert!(service.calculate_balance(account.id).unwrap().is_zero());
+    }
+
+    #[test]
+    fn test_legacy_starting_balance_without_transaction() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = AccountService::new(&storage);
+
+        let account =
+            Account::with_starting_balance("Legacy", AccountType::Savings, Money::from_cents(5000));
+        storage.accounts.upsert(account.clone()).unwrap();
+
+        assert_eq!(service.calculate_balance(account.id).unwrap().cents(), 5000);
+    }
+
+    #[test]
+    fn test_find_duplicate_names() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = AccountService::new(&storage);
+
+        service
+            .create("Checking", AccountType::Checking, Money::zero(), true)
+            .unwrap();
+        service
+            .create("Savings", AccountType::Savings, Money::zero(), true)
+            .unwrap();
+        assert!(service.find_duplicate_names().unwrap().is_empty());
+
+        // Bypass the service, as legacy data or a restore might
+        storage
+            .accounts
+            .upsert(Account::new("CHECKING", AccountType::Checking))
+            .unwrap();
+
+        let duplicates = service.find_duplicate_names().unwrap();
+        assert_eq!(duplicates.len(), 1);
+        assert_eq!(duplicates[0].len(), 2);
+    }
+
+    #[test]
+    fn test_rename_to_existing_name_case_insensitive() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = AccountService::new(&storage);
+
+        service
+            .create("Checking", AccountType::Checking, Money::zero(), true)
+            .unwrap();
+        let savings = service
+            .create("Savings", AccountType::Savings, Money::zero(), true)
+            .unwrap();
+
+        let result = service.update(savings.id, Some("checking"));
+        assert!(matches!(result, Err(EnvelopeError::Duplicate { .. })));
+    }
+
+    #[test]
+    fn test_merge_accounts() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = AccountService::new(&storage);
+
+        let checking = service
+            .create(
+                "Checking",
+                AccountType::Checking,
+                Money::from_cents(100000),
+                true,
+            )
+            .unwrap();
+        let duplicate = service
+            .create(
+                "Checking 2",
+                AccountType::Checking,
+                Money::from_cents(5000),
+                true,
+            )
+            .unwrap();
+        let date = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+
+        let mut reconciled = Transaction::new(duplicate.id, date, Money::from_cents(-2500));
+        reconciled.set_status(TransactionStatus::Reconciled);
+        storage.transactions.upsert(reconciled.clone()).unwrap();
+        let transfer = crate::services::TransferService::new(&storage)
+            .create_transfer(
+                duplicate.id,
+                checking.id,
+                Money::from_cents(1000),
+                date,
+                None,
+            )
+            .unwrap();
+
+        let preview = service.merge_preview(duplicate.id, checking.id).unwrap();
+        assert_eq!(preview.transactions_moved, 2);
+        assert_eq!(preview.reconciled_moved, 1);
+        assert_eq!(preview.transfers_unlinked, 1);
+
+        let expected_balance = service.calculate_balance(checking.id).unwrap()
+            + service.calculate_balance(duplicate.id).unwrap();
+        service.merge(duplicate.id, checking.id).unwrap();
+
+        assert!(service.get(duplicate.id).unwrap().is_none());
+        assert_eq!(
+            service.calculate_balance(checking.id).unwrap(),
+            expected_balance
+        );
+
+        // Reconciled transaction moved as is
+        let moved = storage.transactions.get(reconciled.id).unwrap().unwrap();
+        assert_eq!(moved.account_id, checking.id);
+        assert!(moved.is_locked());
+
+        // Both sides of the transfer are now plain transactions
+        for id in [transfer.from_transaction.id, transfer.to_transaction.id] {
+            let txn = storage.transactions.get(id).unwrap().unwrap();
+            assert_eq!(txn.account_id, checking.id);
+            assert!(!txn.is_transfer());
+        }
+
+        // One starting balance transaction carrying both amounts
+        let txns = storage.transactions.get_by_account(checking.id).unwrap();
+        let starting: Vec<_> = txns.iter().filter(|t| t.is_starting_balance).collect();
+        assert_eq!(starting.len(), 1);
+        assert_eq!(starting[0].amount.cents(), 105000);
+
+        assert!(service.merge(checking.id, checking.id).is_err());
+    }
+
+    #[test]
+    fn test_daily_balances() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = AccountService::new(&storage);
+
+        let account = Account::with_starting_balance(
+            "Legacy",
+            AccountType::Checking,
+            Money::from_cents(1000),
+        );
+        storage.accounts.upsert(account.clone()).unwrap();
+
+        let day = |d| NaiveDate::from_ymd_opt(2025, 3, d).unwrap();
+        for (date, cents) in [(day(1), 500), (day(3), -200), (day(3), -100)] {
+            storage
+                .transactions
+                .upsert(Transaction::new(account.id, date, Money::from_cents(cents)))
+                .unwrap();
+        }
+
+        assert_eq!(
+            service.balance_as_of(account.id, day(2)).unwrap().cents(),
+            1500
+        );
+
+        let balances: Vec<i64> = service
+            .daily_balances(account.id, day(2), day(5))
+            .unwrap()
+            .iter()
+            .map(|(_, b)| b.cents())
+            .collect();
+        assert_eq!(balances, vec![1500, 1200, 1200, 1200]);
+
+        // No activity in range is a flat line
+        let quiet = service
+            .daily_balances(account.id, day(10), day(12))
+            .unwrap();
+        assert!(quiet.iter().all(|(_, b)| b.cents() == 1200));
     }
 }
@@ -7,15 +7,20 @@ use crate::audit::EntityType;
 use crate::error::{EnvelopeError, EnvelopeResult};
 use crate::models::{
     BudgetAllocation, BudgetPeriod, BudgetTarget, BudgetTargetId, CategoryBudgetSummary,
-    CategoryId, Money, TargetCadence,
+    CategoryId, Money, TargetCadence, TargetRounding,
 };
 use crate::services::CategoryService;
 use crate::storage::Storage;
-use chrono::Datelike;
+use chrono::{Datelike, NaiveDate};
+use std::collections::{HashMap, VecDeque};
+
+/// How many recent outflows the age of money averages over
+const AGE_OF_MONEY_OUTFLOWS: usize = 10;
 
 /// Service for budget management
 pub struct BudgetService<'a> {
     storage: &'a Storage,
+    target_rounding: TargetRounding,
 }
 
 /// Budget overview for a period
@@ -36,7 +41,16 @@ pub struct BudgetOverview {
 impl<'a> BudgetService<'a> {
     /// Create a new budget service
     pub fn new(storage: &'a Storage) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            target_rounding: TargetRounding::default(),
+        }
+    }
+
+    /// Set how target suggestions are rounded
+    pub fn with_target_rounding(mut self, rounding: TargetRounding) -> Self {
+        self.target_rounding = rounding;
+        self
     }
 
     /// Assign funds to a category for a period
@@ -218,6 +232,68 @@ impl<'a> BudgetService<'a> {
         Ok(())
     }
 
+    /// Allocations in a period with a non-zero budgeted amount
+    pub fn budgeted_in_period(
+        &self,
+        period: &BudgetPeriod,
+    ) -> EnvelopeResult<Vec<BudgetAllocation>> {
+        let mut allocations: Vec<BudgetAllocation> = self
+            .storage
+            .budget
+            .get_for_period(period)?
+            .into_iter()
+            .filter(|a| !a.budgeted.is_zero())
+            .collect();
+        allocations.sort_by_key(|a| a.category_id.to_string());
+        Ok(allocations)
+    }
+
+    /// Zero the budgeted amount of every category in a period
+    ///
+    /// Only `budgeted` changes. Carryover from the previous period and the
+    /// period's activity are left as they are, so money rolled over from
+    /// earlier periods stays in each category. Each cleared category gets
+    /// its own audit entry. Returns the updated allocations.
+    pub fn clear_period(&self, period: &BudgetPeriod) -> EnvelopeResult<Vec<BudgetAllocation>> {
+        let allocations = self.budgeted_in_period(period)?;
+        if allocations.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let mut cleared = Vec::with_capacity(allocations.len());
+        for before in allocations {
+            let mut allocation = before.clone();
+            allocation.set_budgeted(Money::zero());
+            self.storage.budget.upsert(allocation.clone())?;
+            cleared.push((before, allocation));
+        }
+        self.storage.budget.save()?;
+
+        for (before, allocation) in &cleared {
+            let category_name = self
+                .storage
+                .categories
+                .get_category(allocation.category_id)?
+                .map(|c| c.name);
+            self.storage.log_update(
+                EntityType::BudgetAllocation,
+                format!("{}:{}", allocation.category_id, period),
+                category_name,
+                before,
+                allocation,
+                Some(format!(
+                    "budgeted: {} -> {} (period cleared)",
+                    before.budgeted, allocation.budgeted
+                )),
+            )?;
+        }
+
+        Ok(cleared
+            .into_iter()
+            .map(|(_, allocation)| allocation)
+            .collect())
+    }
+
     /// Get the allocation for a category in a period
     pub fn get_allocation(
         &self,
@@ -245,6 +321,9 @@ impl<'a> BudgetService<'a> {
     }
 
     /// Calculate activity (spending) for a category in a period
+    ///
+    /// Transfers only move money between accounts, so they never count, even
+    /// if a category was attached to one before that was refused.
     pub fn calculate_category_activity(
         &self,
         category_id: CategoryId,
@@ -258,14 +337,14 @@ impl<'a> BudgetService<'a> {
 
         let activity: Money = transactions
             .iter()
-            .filter(|t| t.date >= period_start && t.date <= period_end)
+            .filter(|t| t.date >= period_start && t.date <= period_end && !t.is_transfer())
             .map(|t| {
                 // Check if this is a split transaction
                 if t.is_split() {
                     // Sum only the splits for this category
                     t.splits
                         .iter()
-                        .filter(|s| s.category_id == category_id)
+                        .filter(|s| s.category_id == Some(category_id))
                         .map(|s| s.amount)
                         .sum()
                 } else {
@@ -277,7 +356,40 @@ impl<'a> BudgetService<'a> {
         Ok(activity)
     }
 
+    /// Calculate activity for every category in a period in a single pass
+    ///
+    /// Gives the same results as [`Self::calculate_category_activity`] for
+    /// each category, but reads the period's transactions only once.
+    /// Categories without activity are absent from the map. Transfers are
+    /// skipped.
+    pub fn calculate_activity_by_category(
+        &self,
+        period: &BudgetPeriod,
+    ) -> EnvelopeResult<HashMap<CategoryId, Money>> {
+        let transactions = self
+            .storage
+            .transactions
+            .get_by_date_range(period.start_date(), period.end_date())?;
+
+        let mut activity: HashMap<CategoryId, Money> = HashMap::new();
+        for txn in transactions.iter().filter(|t| !t.is_transfer()) {
+            if txn.is_split() {
+                for split in &txn.splits {
+                    if let Some(category_id) = split.category_id {
+                        *activity.entry(category_id).or_default() += split.amount;
+                    }
+                }
+            } else if let Some(category_id) = txn.category_id {
+                *activity.entry(category_id).or_default() += txn.amount;
+            }
+        }
+
+        Ok(activity)
+    }
+
     /// Calculate total income for a period (sum of all positive transactions)
+    ///
+    /// Transfers in are moves between accounts, not income, and are skipped.
     pub fn calculate_income_for_period(&self, period: &BudgetPeriod) -> EnvelopeResult<Money> {
         let period_start = period.start_date();
         let period_end = period.end_date();
@@ -289,26 +401,171 @@ impl<'a> BudgetService<'a> {
 
         let income: Money = transactions
             .iter()
-            .filter(|t| t.amount.is_positive())
+            .filter(|t| t.amount.is_positive() && !t.is_transfer())
             .map(|t| t.amount)
             .sum();
 
         Ok(income)
     }
 
+    /// Age of money: the average age in days of the dollars being spent
+    ///
+    /// Inflows to on-budget accounts are matched to outflows first in, first
+    /// out. Each outflow's age is the amount-weighted number of days since
+    /// the inflows it used up, and the result averages the last ten outflows
+    /// up to `as_of`. Transfers are left out of both sides, so moving money
+    /// between accounts doesn't make it look newer. `None` until ten outflows
+    /// have been paid from known inflows.
+    pub fn age_of_money(&self, as_of: NaiveDate) -> EnvelopeResult<Option<u32>> {
+        let accounts: Vec<_> = self
+            .storage
+            .accounts
+            .get_all()?
+            .into_iter()
+            .filter(|a| a.on_budget)
+            .collect();
+
+        // (date, cents) of every inflow and outflow, oldest first
+        let mut flows = Vec::new();
+        for account in &accounts {
+            let mut transactions = self.storage.transactions.get_by_account(account.id)?;
+            let starting = account.untracked_starting_balance(&transactions);
+            if starting.is_positive() {
+                flows.push((account.created_at.date_naive(), starting.cents()));
+            }
+
+            transactions.sort_by_key(|t| t.created_at);
+            for txn in transactions {
+                if txn.date > as_of || txn.is_transfer() {
+                    continue;
+                }
+                // Money sent to another account by a split isn't spending either
+                let transferred: Money = txn
+                    .splits
+                    .iter()
+                    .filter(|s| s.is_transfer())
+                    .map(|s| s.amount)
+                    .sum();
+                flows.push((txn.date, (txn.amount - transferred).cents()));
+            }
+        }
+        flows.sort_by_key(|(date, _)| *date);
+
+        let mut inflows: VecDeque<(NaiveDate, i64)> = VecDeque::new();
+        let mut ages = Vec::new();
+        for (date, cents) in flows {
+            if cents > 0 {
+                inflows.push_back((date, cents));
+                continue;
+            }
+
+            let mut remaining = -cents;
+            let mut matched = 0i64;
+            let mut weighted_days = 0i64;
+            while remaining > 0 {
+                let Some((inflow_date, available)) = inflows.front_mut() else {
+                    break;
+                };
+                let used = remaining.min(*available);
+                weighted_days += used * (date - *inflow_date).num_days();
+                matched += used;
+                remaining -= used;
+                *available -= used;
+                if *available == 0 {
+                    inflows.pop_front();
+                }
+            }
+
+            // Spending beyond every known inflow has no age to count
+            if matched > 0 {
+                ages.push(weighted_days as f64 / matched as f64);
+            }
+        }
+
+        if ages.len() < AGE_OF_MONEY_OUTFLOWS {
+            return Ok(None);
+        }
+        let recent = &ages[ages.len() - AGE_OF_MONEY_OUTFLOWS..];
+        let average = recent.iter().sum::<f64>() / recent.len() as f64;
+        Ok(Some(average.round() as u32))
+    }
+
     /// Calculate Available to Budget for a period
     ///
-    /// Available to Budget = Total On-Budget Balances - Total Budgeted for current + prior periods
+    /// Available to Budget = On-Budget Balances at the end of the period -
+    /// money still held in categories. A category holds everything budgeted
+    /// to it in this and prior periods, plus its activity up to the end of
+    /// the period, so money budgeted last month and not yet spent is still
+    /// assigned. Overspent categories hold a negative amount, which covering
+    /// them with a budget uses up. Balances and activity both come from the
+    /// active on-budget accounts only.
     pub fn get_available_to_budget(&self, period: &BudgetPeriod) -> EnvelopeResult<Money> {
-        // Get total balance across all on-budget accounts
+        let period_end = period.end_date();
+
         let account_service = crate::services::AccountService::new(self.storage);
-        let total_balance = account_service.total_on_budget_balance()?;
+        let mut total_balance = Money::zero();
+        let mut total_activity = Money::zero();
+        for account in self.storage.accounts.get_active()? {
+            if !account.on_budget {
+                continue;
+            }
+            total_balance += account_service.balance_as_of(account.id, period_end)?;
+            total_activity += self
+                .storage
+                .transactions
+                .get_by_account(account.id)?
+                .iter()
+                .filter(|t| t.date <= period_end && !t.is_transfer())
+                .map(|t| {
+                    if t.is_split() {
+                        t.splits
+                            .iter()
+                            .filter(|s| s.category_id.is_some())
+                            .map(|s| s.amount)
+                            .sum()
+                    } else if t.category_id.is_some() {
+                        t.amount
+                    } else {
+                        Money::zero()
+                    }
+                })
+                .sum::<Money>();
+        }
 
-        // Get total budgeted for this period
-        let allocations = self.storage.budget.get_for_period(period)?;
-        let total_budgeted: Money = allocations.iter().map(|a| a.budgeted).sum();
+        let total_budgeted: Money = self
+            .storage
+            .budget
+            .get_all()?
+            .iter()
+            .filter(|a| &a.period <= period)
+            .map(|a| a.budgeted)
+            .sum();
 
-        Ok(total_balance - total_budgeted)
+        Ok(total_balance - (total_budgeted + total_activity))
+    }
+
+    /// Check whether more has been budgeted than there is money for
+    ///
+    /// True when Available to Budget for the period is negative.
+    pub fn is_overbudgeted(&self, period: &BudgetPeriod) -> EnvelopeResult<bool> {
+        Ok(self.get_available_to_budget(period)?.is_negative())
+    }
+
+    /// Available to Budget if a category's budget for the period were `amount`
+    ///
+    /// Lets callers warn before an assignment budgets money that isn't there.
+    pub fn available_after_assignment(
+        &self,
+        category_id: CategoryId,
+        period: &BudgetPeriod,
+        amount: Money,
+    ) -> EnvelopeResult<Money> {
+        let current = self
+            .storage
+            .budget
+            .get_or_default(category_id, period)?
+            .budgeted;
+        Ok(self.get_available_to_budget(period)? - (amount - current))
     }
 
     /// Get expected income for a period (if set)
@@ -357,22 +614,52 @@ impl<'a> BudgetService<'a> {
         Ok(Some(expected - total_budgeted))
     }
 
-    /// Get a complete budget overview for a period
-    pub fn get_budget_overview(&self, period: &BudgetPeriod) -> EnvelopeResult<BudgetOverview> {
+    /// Get budget summaries for every category in a period
+    ///
+    /// Same results as calling [`Self::get_category_summary`] per category,
+    /// in category order, but reads allocations and transactions only once.
+    pub fn get_category_summaries(
+        &self,
+        period: &BudgetPeriod,
+    ) -> EnvelopeResult<Vec<CategoryBudgetSummary>> {
         let category_service = CategoryService::new(self.storage);
         let categories = category_service.list_categories()?;
 
-        let mut summaries = Vec::with_capacity(categories.len());
+        let mut allocations: HashMap<CategoryId, BudgetAllocation> = self
+            .storage
+            .budget
+            .get_for_period(period)?
+            .into_iter()
+            .map(|a| (a.category_id, a))
+            .collect();
+        let activity = self.calculate_activity_by_category(period)?;
+
+        Ok(categories
+            .iter()
+            .map(|category| {
+                let allocation = allocations
+                    .remove(&category.id)
+                    .unwrap_or_else(|| BudgetAllocation::new(category.id, period.clone()));
+                CategoryBudgetSummary::from_allocation(
+                    &allocation,
+                    activity.get(&category.id).copied().unwrap_or_default(),
+                )
+            })
+            .collect())
+    }
+
+    /// Get a complete budget overview for a period
+    pub fn get_budget_overview(&self, period: &BudgetPeriod) -> EnvelopeResult<BudgetOverview> {
+        let summaries = self.get_category_summaries(period)?;
+
         let mut total_budgeted = Money::zero();
         let mut total_activity = Money::zero();
         let mut total_available = Money::zero();
 
-        for category in &categories {
-            let summary = self.get_category_summary(category.id, period)?;
+        for summary in &summaries {
             total_budgeted += summary.budgeted;
             total_activity += summary.activity;
             total_available += summary.available;
-            summaries.push(summary);
         }
 
         let available_to_budget = self.get_available_to_budget(period)?;
@@ -444,13 +731,13 @@ impl<'a> BudgetService<'a> {
 
         let total_paid: i64 = transactions
             .iter()
-            .filter(|t| t.date <= end_date)
+            .filter(|t| t.date <= end_date && !t.is_transfer())
             .map(|t| {
                 if t.is_split() {
                     // Sum only the splits for this category
                     t.splits
                         .iter()
-                        .filter(|s| s.category_id == category_id)
+                        .filter(|s| s.category_id == Some(category_id))
                         .map(|s| s.amount.cents())
                         .sum::<i64>()
                 } else {
@@ -550,18 +837,11 @@ impl<'a> BudgetService<'a> {
         &self,
         period: &BudgetPeriod,
     ) -> EnvelopeResult<Vec<CategoryBudgetSummary>> {
-        let category_service = CategoryService::new(self.storage);
-        let categories = category_service.list_categories()?;
-
-        let mut overspent = Vec::new();
-        for category in &categories {
-            let summary = self.get_category_summary(category.id, period)?;
-            if summary.is_overspent() {
-                overspent.push(summary);
-            }
-        }
-
-        Ok(overspent)
+        Ok(self
+            .get_category_summaries(period)?
+            .into_iter()
+            .filter(|summary| summary.is_overspent())
+            .collect())
     }
 
     // ==================== Budget Target Methods ====================
@@ -579,13 +859,17 @@ impl<'a> BudgetService<'a> {
             .get_category(category_id)?
             .ok_or_else(|| EnvelopeError::category_not_found(category_id.to_string()))?;
 
-        // Deactivate any existing active target for this category
+        // Deactivate any existing active target for this category, keeping
+        // its notes on the replacement
+        let mut notes = String::new();
         if let Some(mut existing) = self.storage.targets.get_for_category(category_id)? {
+            notes = existing.notes.clone();
             existing.deactivate();
             self.storage.targets.upsert(existing)?;
         }
 
-        let target = BudgetTarget::new(category_id, amount, cadence);
+        let mut target = BudgetTarget::new(category_id, amount, cadence);
+        target.notes = notes;
         target
             .validate()
             .map_err(|e| EnvelopeError::Budget(e.to_string()))?;
@@ -647,6 +931,50 @@ impl<'a> BudgetService<'a> {
         Ok(target)
     }
 
+    /// Set the notes on a category's active target
+    ///
+    /// Empty notes clear them.
+    pub fn set_target_notes(
+        &self,
+        category_id: CategoryId,
+        notes: &str,
+    ) -> EnvelopeResult<BudgetTarget> {
+        let category = self
+            .storage
+            .categories
+            .get_category(category_id)?
+            .ok_or_else(|| EnvelopeError::category_not_found(category_id.to_string()))?;
+
+        let mut target = self
+            .storage
+            .targets
+            .get_for_category(category_id)?
+            .ok_or_else(|| {
+                EnvelopeError::Budget(format!("No target set for '{}'", category.name))
+            })?;
+
+        let before = target.clone();
+        target.set_notes(notes.trim());
+
+        self.storage.targets.upsert(target.clone())?;
+        self.storage.targets.save()?;
+
+        self.storage.log_update(
+            EntityType::BudgetTarget,
+            target.id.to_string(),
+            Some(category.name),
+            &before,
+            &target,
+            Some(if target.notes.is_empty() {
+                "notes cleared".to_string()
+            } else {
+                "notes changed".to_string()
+            }),
+        )?;
+
+        Ok(target)
+    }
+
     /// Get the active target for a category
     pub fn get_target(&self, category_id: CategoryId) -> EnvelopeResult<Option<BudgetTarget>> {
         self.storage.targets.get_for_category(category_id)
@@ -659,7 +987,10 @@ impl<'a> BudgetService<'a> {
         period: &BudgetPeriod,
     ) -> EnvelopeResult<Option<Money>> {
         if let Some(target) = self.storage.targets.get_for_category(category_id)? {
-            Ok(Some(target.calculate_for_period(period)))
+            Ok(Some(target.calculate_for_period_rounded(
+                period,
+                self.target_rounding,
+            )))
         } else {
             Ok(None)
         }
@@ -714,12 +1045,14 @@ impl<'a> BudgetService<'a> {
                 } else {
                     // Spread remaining over remaining months
                     Ok(Some(Money::from_cents(
-                        (remaining as f64 / months as f64).ceil() as i64,
+                        self.target_rounding.divide(remaining, months as i64),
                     )))
                 }
             }
             // For recurring targets, use the standard calculation
-            _ => Ok(Some(target.calculate_for_period(period))),
+            _ => Ok(Some(
+                target.calculate_for_period_rounded(period, self.target_rounding),
+            )),
         }
     }
 
@@ -811,7 +1144,7 @@ impl<'a> BudgetService<'a> {
 mod tests {
     use super::*;
     use crate::config::paths::EnvelopePaths;
-    use crate::models::{Account, AccountType, Category, CategoryGroup, Transaction};
+    use crate::models::{Account, AccountType, Category, CategoryGroup, Split, Transaction};
     use chrono::NaiveDate;
     use tempfile::TempDir;
 
@@ -943,6 +1276,104 @@ mod tests {
         assert_eq!(activity.cents(), -5000);
     }
 
+    #[test]
+    fn test_budget_overview_matches_category_summaries() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (cat1_id, cat2_id, period) = setup_test_data(&storage);
+
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 10).unwrap();
+        let mut txn = Transaction::new(account.id, date, Money::from_cents(-3000));
+        txn.category_id = Some(cat1_id);
+        storage.transactions.upsert(txn).unwrap();
+
+        let mut split = Transaction::new(account.id, date, Money::from_cents(-5000));
+        split.add_split(Split::new(cat1_id, Money::from_cents(-2000)));
+        split.add_split(Split::new(cat2_id, Money::from_cents(-3000)));
+        storage.transactions.upsert(split).unwrap();
+
+        // Outside the period
+        let mut old = Transaction::new(
+            account.id,
+            NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+            Money::from_cents(-9900),
+        );
+        old.category_id = Some(cat2_id);
+        storage.transactions.upsert(old).unwrap();
+
+        let service = BudgetService::new(&storage);
+        service
+            .assign_to_category(cat1_id, &period, Money::from_cents(10000))
+            .unwrap();
+
+        let overview = service.get_budget_overview(&period).unwrap();
+        assert_eq!(overview.categories.len(), 2);
+        for summary in &overview.categories {
+            let expected = service
+                .get_category_summary(summary.category_id, &period)
+                .unwrap();
+            assert_eq!(summary.budgeted, expected.budgeted);
+            assert_eq!(summary.activity, expected.activity);
+            assert_eq!(summary.available, expected.available);
+        }
+        assert_eq!(overview.total_activity.cents(), -8000);
+
+        let activity = service.calculate_activity_by_category(&period).unwrap();
+        assert_eq!(activity.len(), 2);
+        assert_eq!(activity[&cat1_id].cents(), -5000);
+        assert_eq!(activity[&cat2_id].cents(), -3000);
+    }
+
+    /// Compares the single-pass overview against per-category summaries
+    ///
+    /// Run with `cargo test --release -- --ignored --nocapture`.
+    #[test]
+    #[ignore]
+    fn bench_budget_overview() {
+        use std::time::Instant;
+
+        let (_temp_dir, storage) = create_test_storage();
+        let group = CategoryGroup::new("Bench");
+        storage.categories.upsert_group(group.clone()).unwrap();
+        let category_ids: Vec<CategoryId> = (0..50)
+            .map(|i| {
+                let category = Category::new(format!("Category {}", i), group.id);
+                let id = category.id;
+                storage.categories.upsert_category(category).unwrap();
+                id
+            })
+            .collect();
+
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+        for i in 0..5000 {
+            let date = NaiveDate::from_ymd_opt(2025, 1, (i % 28 + 1) as u32).unwrap();
+            let mut txn = Transaction::new(account.id, date, Money::from_cents(-100));
+            txn.category_id = Some(category_ids[i % category_ids.len()]);
+            storage.transactions.upsert(txn).unwrap();
+        }
+
+        let service = BudgetService::new(&storage);
+        let period = BudgetPeriod::monthly(2025, 1);
+
+        let start = Instant::now();
+        for id in &category_ids {
+            service.get_category_summary(*id, &period).unwrap();
+        }
+        let per_category = start.elapsed();
+
+        let start = Instant::now();
+        service.get_budget_overview(&period).unwrap();
+        let single_pass = start.elapsed();
+
+        println!(
+            "per-category: {:?}, single pass: {:?}",
+            per_category, single_pass
+        );
+    }
+
     #[test]
     fn test_available_to_budget() {
         let (_temp_dir, storage) = create_test_storage();
@@ -970,6 +1401,158 @@ mod tests {
 
         let atb = service.get_available_to_budget(&period).unwrap();
         assert_eq!(atb.cents(), 50000); // 100000 - 50000
+
+        // Raising the $500 to $1,200 would leave ATB $200 short
+        let projected = service
+            .available_after_assignment(cat_id, &period, Money::from_cents(120000))
+            .unwrap();
+        assert_eq!(projected.cents(), -20000);
+    }
+
+    #[test]
+    fn test_available_to_budget_counts_prior_periods() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (cat1_id, cat2_id, jan) = setup_test_data(&storage);
+        let feb = jan.next();
+
+        let account = Account::with_starting_balance(
+            "Checking",
+            AccountType::Checking,
+            Money::from_cents(100000),
+        );
+        storage.accounts.upsert(account.clone()).unwrap();
+
+        let service = BudgetService::new(&storage);
+        service
+            .assign_to_category(cat1_id, &jan, Money::from_cents(50000))
+            .unwrap();
+
+        // Spending from the category leaves Available to Budget alone
+        let mut txn = Transaction::new(
+            account.id,
+            jan.start_date() + chrono::Duration::days(9),
+            Money::from_cents(-20000),
+        );
+        txn.category_id = Some(cat1_id);
+        storage.transactions.upsert(txn).unwrap();
+        assert_eq!(
+            service.get_available_to_budget(&jan).unwrap().cents(),
+            50000
+        );
+
+        // The $300 left from January is still assigned in February
+        assert_eq!(
+            service.get_available_to_budget(&feb).unwrap().cents(),
+            50000
+        );
+        assert!(!service.is_overbudgeted(&feb).unwrap());
+
+        service
+            .assign_to_category(cat2_id, &feb, Money::from_cents(60000))
+            .unwrap();
+        assert_eq!(
+            service.get_available_to_budget(&feb).unwrap().cents(),
+            -10000
+        );
+        assert!(service.is_overbudgeted(&feb).unwrap());
+        assert!(!service.is_overbudgeted(&jan).unwrap());
+    }
+
+    #[test]
+    fn test_available_to_budget_ignores_off_budget_activity() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (cat_id, _, period) = setup_test_data(&storage);
+
+        let checking = Account::with_starting_balance(
+            "Checking",
+            AccountType::Checking,
+            Money::from_cents(100000),
+        );
+        let mut brokerage = Account::new("Brokerage", AccountType::Investment);
+        brokerage.on_budget = false;
+        storage.accounts.upsert(checking).unwrap();
+        storage.accounts.upsert(brokerage.clone()).unwrap();
+
+        // A stray category on an off-budget transaction doesn't hold budget money
+        let mut txn =
+            Transaction::new(brokerage.id, period.start_date(), Money::from_cents(-25000));
+        txn.category_id = Some(cat_id);
+        storage.transactions.upsert(txn).unwrap();
+
+        let service = BudgetService::new(&storage);
+        assert_eq!(
+            service.get_available_to_budget(&period).unwrap().cents(),
+            100000
+        );
+    }
+
+    #[test]
+    fn test_off_budget_transfer_is_not_activity_or_income() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (cat_id, _, period) = setup_test_data(&storage);
+
+        let checking = Account::with_starting_balance(
+            "Checking",
+            AccountType::Checking,
+            Money::from_cents(100000),
+        );
+        let mut brokerage = Account::new("Brokerage", AccountType::Investment);
+        brokerage.on_budget = false;
+        storage.accounts.upsert(checking.clone()).unwrap();
+        storage.accounts.upsert(brokerage.clone()).unwrap();
+
+        let date = period.start_date() + chrono::Duration::days(4);
+        let transfers = crate::services::TransferService::new(&storage);
+        transfers
+            .create_transfer(
+                checking.id,
+                brokerage.id,
+                Money::from_cents(30000),
+                date,
+                None,
+            )
+            .unwrap();
+        let back = transfers
+            .create_transfer(
+                brokerage.id,
+                checking.id,
+                Money::from_cents(5000),
+                date,
+                None,
+            )
+            .unwrap();
+
+        // A category can't be put on a transfer leg
+        let service = crate::services::TransactionService::new(&storage);
+        assert!(service
+            .update(
+                back.to_transaction.id,
+                None,
+                None,
+                None,
+                Some(Some(cat_id)),
+                None
+            )
+            .is_err());
+
+        // Even one stored with a category from older data is ignored
+        let mut leg = back.to_transaction.clone();
+        leg.category_id = Some(cat_id);
+        storage.transactions.upsert(leg).unwrap();
+
+        let budget = BudgetService::new(&storage);
+        assert!(budget
+            .calculate_income_for_period(&period)
+            .unwrap()
+            .is_zero());
+        assert!(budget
+            .calculate_category_activity(cat_id, &period)
+            .unwrap()
+            .is_zero());
+        assert!(budget
+            .calculate_activity_by_category(&period)
+            .unwrap()
+            .is_empty());
     }
 
     #[test]
@@ -1042,6 +1625,51 @@ mod tests {
         assert_eq!(feb_alloc.total_budgeted().cents(), 50000);
     }
 
+    #[test]
+    fn test_clear_period_keeps_carryover() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (cat1_id, cat2_id, jan) = setup_test_data(&storage);
+        let feb = jan.next();
+        let service = BudgetService::new(&storage);
+
+        service
+            .assign_to_category(cat1_id, &jan, Money::from_cents(50000))
+            .unwrap();
+        service.apply_rollover(cat1_id, &feb).unwrap();
+        service
+            .assign_to_category(cat1_id, &feb, Money::from_cents(10000))
+            .unwrap();
+        service
+            .assign_to_category(cat2_id, &feb, Money::from_cents(20000))
+            .unwrap();
+
+        let cleared = service.clear_period(&feb).unwrap();
+        assert_eq!(cleared.len(), 2);
+
+        let cat1_alloc = service.get_allocation(cat1_id, &feb).unwrap();
+        assert_eq!(cat1_alloc.budgeted.cents(), 0);
+        assert_eq!(cat1_alloc.carryover.cents(), 50000);
+        assert_eq!(
+            service
+                .get_allocation(cat2_id, &feb)
+                .unwrap()
+                .budgeted
+                .cents(),
+            0
+        );
+
+        // The previous period is untouched
+        assert_eq!(
+            service
+                .get_allocation(cat1_id, &jan)
+                .unwrap()
+                .budgeted
+                .cents(),
+            50000
+        );
+        assert!(service.clear_period(&feb).unwrap().is_empty());
+    }
+
     #[test]
     fn test_apply_rollover_all() {
         let (_temp_dir, storage) = create_test_storage();
@@ -1362,4 +1990,98 @@ mod tests {
             .unwrap();
         assert_eq!(suggested.cents(), 30000);
     }
+
+    #[test]
+    fn test_age_of_money() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = BudgetService::new(&storage);
+        let date = |month, day| NaiveDate::from_ymd_opt(2025, month, day).unwrap();
+
+        let checking = Account::new("Checking", AccountType::Checking);
+        let savings = Account::new("Savings", AccountType::Savings);
+        storage.accounts.upsert(checking.clone()).unwrap();
+        storage.accounts.upsert(savings.clone()).unwrap();
+
+        // $1000 paid in on Jan 1, then $50 spent every day from Jan 11
+        storage
+            .transactions
+            .upsert(Transaction::new(
+                checking.id,
+                date(1, 1),
+                Money::from_cents(100000),
+            ))
+            .unwrap();
+        for day in 11..20 {
+            storage
+                .transactions
+                .upsert(Transaction::new(
+                    checking.id,
+                    date(1, day),
+                    Money::from_cents(-5000),
+                ))
+                .unwrap();
+        }
+
+        // Nine outflows isn't enough history yet
+        assert_eq!(service.age_of_money(date(1, 31)).unwrap(), None);
+
+        storage
+            .transactions
+            .upsert(Transaction::new(
+                checking.id,
+                date(1, 20),
+                Money::from_cents(-5000),
+            ))
+            .unwrap();
+        // Spent 10 through 19 days after it came in
+        assert_eq!(service.age_of_money(date(1, 31)).unwrap(), Some(15));
+        assert_eq!(service.age_of_money(date(1, 19)).unwrap(), None);
+
+        // A transfer out and back in on Jan 25 changes nothing
+        let mut out = Transaction::new(checking.id, date(1, 25), Money::from_cents(-20000));
+        let mut back = Transaction::new(savings.id, date(1, 25), Money::from_cents(20000));
+        out.transfer_transaction_id = Some(back.id);
+        back.transfer_transaction_id = Some(out.id);
+        storage.transactions.upsert(out).unwrap();
+        storage.transactions.upsert(back).unwrap();
+
+        storage
+            .transactions
+            .upsert(Transaction::new(
+                checking.id,
+                date(1, 30),
+                Money::from_cents(-5000),
+            ))
+            .unwrap();
+        // The newest outflow is 29 days old, replacing the oldest (10 days)
+        assert_eq!(service.age_of_money(date(1, 31)).unwrap(), Some(16));
+    }
+
+    #[test]
+    fn test_target_notes() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (cat_id, other_id, _) = setup_test_data(&storage);
+
+        let service = BudgetService::new(&storage);
+
+        // Notes need a target to attach to
+        assert!(service.set_target_notes(other_id, "No target").is_err());
+
+        service
+            .set_target(cat_id, Money::from_cents(30000), TargetCadence::Monthly)
+            .unwrap();
+        let target = service
+            .set_target_notes(cat_id, "  Saving for the new fridge ")
+            .unwrap();
+        assert_eq!(target.notes, "Saving for the new fridge");
+
+        // Changing the amount keeps the notes
+        let target = service
+            .set_target(cat_id, Money::from_cents(40000), TargetCadence::Monthly)
+            .unwrap();
+        assert_eq!(target.notes, "Saving for the new fridge");
+
+        let target = service.set_target_notes(cat_id, "").unwrap();
+        assert!(target.notes.is_empty());
+    }
 }
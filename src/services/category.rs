@@ -3,9 +3,13 @@
 //! Provides business logic for category and category group management
 //! including CRUD operations, reordering, and moving categories between groups.
 
+use std::collections::BTreeMap;
+
 use crate::audit::EntityType;
+use crate::config::clock;
 use crate::error::{EnvelopeError, EnvelopeResult};
 use crate::models::{Category, CategoryGroup, CategoryGroupId, CategoryId};
+use crate::services::ScheduledService;
 use crate::storage::Storage;
 
 /// Service for category management
@@ -20,6 +24,15 @@ pub struct CategoryGroupWithCategories {
     pub categories: Vec<Category>,
 }
 
+/// Category and group names used more than once, one entry per shared name
+#[derive(Debug, Clone, Default)]
+pub struct DuplicateNames {
+    /// Categories sharing a name (ignoring case), across all groups
+    pub categories: Vec<Vec<Category>>,
+    /// Groups sharing a name (ignoring case)
+    pub groups: Vec<Vec<CategoryGroup>>,
+}
+
 impl<'a> CategoryService<'a> {
     /// Create a new category service
     pub fn new(storage: &'a Storage) -> Self {
@@ -152,7 +165,7 @@ impl<'a> CategoryService<'a> {
             group.name = new_name.to_string();
         }
 
-        group.updated_at = chrono::Utc::now();
+        group.updated_at = clock::now();
         group
             .validate()
             .map_err(|e| EnvelopeError::Validation(e.to_string()))?;
@@ -180,8 +193,7 @@ impl<'a> CategoryService<'a> {
     /// If the group has categories, they must be moved or deleted first
     /// unless force_delete_categories is true.
     ///
-    /// Automatically creates a backup before deletion if one hasn't been
-    /// created recently.
+    /// Automatically creates a backup before deletion, which `undo` restores.
     pub fn delete_group(
         &self,
         id: CategoryGroupId,
@@ -213,6 +225,9 @@ impl<'a> CategoryService<'a> {
             .delete_group(id, force_delete_categories)?;
         self.storage.categories.save()?;
 
+        let deleted: Vec<CategoryId> = categories.iter().map(|c| c.id).collect();
+        ScheduledService::new(self.storage).clear_categories(&deleted)?;
+
         // Audit
         self.storage.log_delete(
             EntityType::CategoryGroup,
@@ -229,7 +244,7 @@ impl<'a> CategoryService<'a> {
         for (i, &id) in order.iter().enumerate() {
             if let Some(mut group) = self.storage.categories.get_group(id)? {
                 group.sort_order = i as i32;
-                group.updated_at = chrono::Utc::now();
+                group.updated_at = clock::now();
                 self.storage.categories.upsert_group(group)?;
             }
         }
@@ -328,6 +343,43 @@ impl<'a> CategoryService<'a> {
         self.storage.categories.get_all_categories()
     }
 
+    /// Categories with names close to `name`, closest first
+    ///
+    /// For suggesting what was meant when a name doesn't match exactly. Names
+    /// containing it (or contained in it) come first, then names, or words of
+    /// names, within a few typos. Archived categories are left out.
+    pub fn similar_categories(&self, name: &str, limit: usize) -> EnvelopeResult<Vec<Category>> {
+        let query = name.trim().to_lowercase();
+        if query.is_empty() {
+            return Ok(Vec::new());
+        }
+        let max_distance = (query.chars().count() / 2).max(1);
+
+        let mut scored: Vec<(usize, Category)> = self
+            .storage
+            .categories
+            .get_all_categories()?
+            .into_iter()
+            .filter(|c| !c.archived)
+            .filter_map(|category| {
+                let name = category.name.to_lowercase();
+                let distance = if name.contains(&query) || query.contains(&name) {
+                    0
+                } else {
+                    name.split_whitespace()
+                        .map(|word| edit_distance(word, &query))
+                        .chain([edit_distance(&name, &query)])
+                        .min()
+                        .unwrap_or(usize::MAX)
+                };
+                (distance <= max_distance).then_some((distance, category))
+            })
+            .collect();
+
+        scored.sort_by(|a, b| a.0.cmp(&b.0).then_with(|| a.1.name.cmp(&b.1.name)));
+        Ok(scored.into_iter().take(limit).map(|(_, c)| c).collect())
+    }
+
     /// List categories in a group
     pub fn list_categories_in_group(
         &self,
@@ -379,7 +431,7 @@ impl<'a> CategoryService<'a> {
             category.set_goal(goal_amount);
         }
 
-        category.updated_at = chrono::Utc::now();
+        category.updated_at = clock::now();
         category
             .validate()
             .map_err(|e| EnvelopeError::Validation(e.to_string()))?;
@@ -413,6 +465,94 @@ impl<'a> CategoryService<'a> {
         Ok(category)
     }
 
+    /// Set a category's notes, or clear them with an empty string
+    pub fn set_notes(&self, id: CategoryId, notes: &str) -> EnvelopeResult<Category> {
+        let mut category = self
+            .storage
+            .categories
+            .get_category(id)?
+            .ok_or_else(|| EnvelopeError::category_not_found(id.to_string()))?;
+
+        let before = category.clone();
+        category.notes = notes.trim().to_string();
+        if category.notes == before.notes {
+            return Ok(category);
+        }
+        category.updated_at = clock::now();
+
+        self.storage.categories.upsert_category(category.clone())?;
+        self.storage.categories.save()?;
+
+        self.storage.log_update(
+            EntityType::Category,
+            category.id.to_string(),
+            Some(category.name.clone()),
+            &before,
+            &category,
+            Some(if category.notes.is_empty() {
+                "notes cleared".to_string()
+            } else {
+                "notes changed".to_string()
+            }),
+        )?;
+
+        Ok(category)
+    }
+
+    /// Archive a category
+    ///
+    /// The category leaves the budget view but keeps its allocations, and
+    /// transactions still point at it, so reports of past periods are
+    /// unchanged.
+    pub fn archive_category(&self, id: CategoryId) -> EnvelopeResult<Category> {
+        self.set_category_archived(id, true)
+    }
+
+    /// Unarchive a category
+    pub fn unarchive_category(&self, id: CategoryId) -> EnvelopeResult<Category> {
+        self.set_category_archived(id, false)
+    }
+
+    fn set_category_archived(&self, id: CategoryId, archived: bool) -> EnvelopeResult<Category> {
+        let mut category = self
+            .storage
+            .categories
+            .get_category(id)?
+            .ok_or_else(|| EnvelopeError::category_not_found(id.to_string()))?;
+
+        if category.archived == archived {
+            let state = if archived { "already" } else { "not" };
+            return Err(EnvelopeError::Validation(format!(
+                "Category '{}' is {} archived",
+                category.name, state
+            )));
+        }
+
+        let before = category.clone();
+        if archived {
+            category.archive();
+        } else {
+            category.unarchive();
+        }
+
+        self.storage.categories.upsert_category(category.clone())?;
+        self.storage.categories.save()?;
+
+        self.storage.log_update(
+            EntityType::Category,
+            category.id.to_string(),
+            Some(category.name.clone()),
+            &before,
+            &category,
+            Some(format!(
+                "archived: {} -> {}",
+                before.archived, category.archived
+            )),
+        )?;
+
+        Ok(category)
+    }
+
     /// Move a category to a different group
     pub fn move_category(
         &self,
@@ -472,8 +612,7 @@ impl<'a> CategoryService<'a> {
 
     /// Delete a category
     ///
-    /// Automatically creates a backup before deletion if one hasn't been
-    /// created recently.
+    /// Automatically creates a backup before deletion, which `undo` restores.
     pub fn delete_category(&self, id: CategoryId) -> EnvelopeResult<()> {
         let category = self
             .storage
@@ -489,6 +628,7 @@ impl<'a> CategoryService<'a> {
 
         self.storage.categories.delete_category(id)?;
         self.storage.categories.save()?;
+        ScheduledService::new(self.storage).clear_categories(&[id])?;
 
         // Audit
         self.storage.log_delete(
@@ -511,7 +651,7 @@ impl<'a> CategoryService<'a> {
             if let Some(mut category) = self.storage.categories.get_category(id)? {
                 if category.group_id == group_id {
                     category.sort_order = i as i32;
-                    category.updated_at = chrono::Utc::now();
+                    category.updated_at = clock::now();
                     self.storage.categories.upsert_category(category)?;
                 }
             }
@@ -519,6 +659,86 @@ impl<'a> CategoryService<'a> {
         self.storage.categories.save()?;
         Ok(())
     }
+
+    /// Move a category one place up or down within its group
+    ///
+    /// Returns false if it is already at that end of the group. Archived
+    /// categories are stepped over when moving an active one (and vice
+    /// versa), since the budget view usually hides them.
+    pub fn move_category_in_group(&self, id: CategoryId, up: bool) -> EnvelopeResult<bool> {
+        let category = self
+            .storage
+            .categories
+            .get_category(id)?
+            .ok_or_else(|| EnvelopeError::category_not_found(id.to_string()))?;
+        let siblings = self
+            .storage
+            .categories
+            .get_categories_in_group(category.group_id)?;
+
+        let pos = siblings
+            .iter()
+            .position(|c| c.id == id)
+            .ok_or_else(|| EnvelopeError::category_not_found(id.to_string()))?;
+        let is_neighbour = |&i: &usize| siblings[i].archived == category.archived;
+        let target = if up {
+            (0..pos).rev().find(is_neighbour)
+        } else {
+            (pos + 1..siblings.len()).find(is_neighbour)
+        };
+        let Some(target) = target else {
+            return Ok(false);
+        };
+
+        let mut order: Vec<CategoryId> = siblings.iter().map(|c| c.id).collect();
+        order.swap(pos, target);
+        self.reorder_categories(category.group_id, &order)?;
+        Ok(true)
+    }
+
+    /// Find category and group names used more than once (case-insensitive)
+    ///
+    /// Category names are unique across all groups because the CLI looks
+    /// categories up by name alone.
+    pub fn find_duplicate_names(&self) -> EnvelopeResult<DuplicateNames> {
+        let mut categories: BTreeMap<String, Vec<Category>> = BTreeMap::new();
+        for category in self.storage.categories.get_all_categories()? {
+            categories
+                .entry(category.name.to_lowercase())
+                .or_default()
+                .push(category);
+        }
+
+        let mut groups: BTreeMap<String, Vec<CategoryGroup>> = BTreeMap::new();
+        for group in self.storage.categories.get_all_groups()? {
+            groups
+                .entry(group.name.to_lowercase())
+                .or_default()
+                .push(group);
+        }
+
+        Ok(DuplicateNames {
+            categories: categories.into_values().filter(|c| c.len() > 1).collect(),
+            groups: groups.into_values().filter(|g| g.len() > 1).collect(),
+        })
+    }
+}
+
+/// Number of single-character edits turning `a` into `b` (Levenshtein distance)
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1; b.len() + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current[j + 1] = substitution.min(previous[j + 1] + 1).min(current[j] + 1);
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
 }
 
 #[cfg(test)]
@@ -596,6 +816,55 @@ mod tests {
         assert_eq!(moved.group_id, needs.id);
     }
 
+    #[test]
+    fn test_move_category_in_group() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = CategoryService::new(&storage);
+
+        let bills = service.create_group("Bills").unwrap();
+        let rent = service.create_category("Rent", bills.id).unwrap();
+        let water = service.create_category("Water", bills.id).unwrap();
+        let electric = service.create_category("Electric", bills.id).unwrap();
+        service.archive_category(water.id).unwrap();
+
+        let names = |storage: &Storage| -> Vec<String> {
+            storage
+                .categories
+                .get_categories_in_group(bills.id)
+                .unwrap()
+                .into_iter()
+                .map(|c| c.name)
+                .collect()
+        };
+
+        // Steps over the archived category between them
+        assert!(service.move_category_in_group(electric.id, true).unwrap());
+        assert_eq!(names(&storage), ["Electric", "Water", "Rent"]);
+
+        // Already at the top and bottom
+        assert!(!service.move_category_in_group(electric.id, true).unwrap());
+        assert!(!service.move_category_in_group(rent.id, false).unwrap());
+    }
+
+    #[test]
+    fn test_set_notes() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = CategoryService::new(&storage);
+
+        let group = service.create_group("Bills").unwrap();
+        let category = service.create_category("Gifts", group.id).unwrap();
+        assert!(category.notes.is_empty());
+
+        service
+            .set_notes(category.id, "  Bump this in December ")
+            .unwrap();
+        let category = service.get_category(category.id).unwrap().unwrap();
+        assert_eq!(category.notes, "Bump this in December");
+
+        let category = service.set_notes(category.id, "").unwrap();
+        assert!(category.notes.is_empty());
+    }
+
     #[test]
     fn test_delete_category() {
         let (_temp_dir, storage) = create_test_storage();
@@ -623,4 +892,81 @@ mod tests {
         let found = service.find_category("monthly rent").unwrap().unwrap();
         assert_eq!(found.id, category.id);
     }
+
+    #[test]
+    fn test_similar_categories() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = CategoryService::new(&storage);
+
+        let group = service.create_group("Spending").unwrap();
+        for name in ["Groceries", "Gas", "Dining Out", "Rent", "Old Hobby"] {
+            service.create_category(name, group.id).unwrap();
+        }
+        let hobby = service.find_category("Old Hobby").unwrap().unwrap();
+        service.archive_category(hobby.id).unwrap();
+
+        let names = |query: &str| -> Vec<String> {
+            service
+                .similar_categories(query, 5)
+                .unwrap()
+                .into_iter()
+                .map(|c| c.name)
+                .collect()
+        };
+
+        assert_eq!(names("grocery"), ["Groceries"]);
+        assert_eq!(names("dinning"), ["Dining Out"]);
+        assert_eq!(names("rnt"), ["Rent"]);
+        assert_eq!(names("ga"), ["Gas"]);
+        assert!(names("hobby").is_empty());
+        assert!(names("xyz").is_empty());
+    }
+
+    #[test]
+    fn test_duplicate_category_names_rejected_and_reported() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = CategoryService::new(&storage);
+
+        let bills = service.create_group("Bills").unwrap();
+        let needs = service.create_group("Needs").unwrap();
+        service.create_category("Phone", bills.id).unwrap();
+
+        // Case-insensitive, and across groups
+        let result = service.create_category("PHONE", needs.id);
+        assert!(matches!(result, Err(EnvelopeError::Duplicate { .. })));
+
+        let duplicates = service.find_duplicate_names().unwrap();
+        assert!(duplicates.categories.is_empty());
+        assert!(duplicates.groups.is_empty());
+
+        storage
+            .categories
+            .upsert_category(Category::new("phone", needs.id))
+            .unwrap();
+        let categories = service.find_duplicate_names().unwrap().categories;
+        assert_eq!(categories.len(), 1);
+        assert_eq!(categories[0].len(), 2);
+    }
+
+    #[test]
+    fn test_archive_category() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = CategoryService::new(&storage);
+
+        let group = service.create_group("Bills").unwrap();
+        let category = service.create_category("Old Gym", group.id).unwrap();
+
+        let archived = service.archive_category(category.id).unwrap();
+        assert!(archived.archived);
+        assert!(matches!(
+            service.archive_category(category.id),
+            Err(EnvelopeError::Validation(_))
+        ));
+
+        // Still there for history
+        assert_eq!(service.list_categories().unwrap().len(), 1);
+
+        let restored = service.unarchive_category(category.id).unwrap();
+        assert!(!restored.archived);
+    }
 }
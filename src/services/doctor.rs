@@ -0,0 +1,511 @@
+//! Doctor service
+//!
+//! Scans stored data for integrity problems such as dangling references,
+//! broken transfers, and mismatched splits, and repairs the ones that have
+//! a single safe fix.
+
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+use crate::audit::EntityType;
+use crate::config::clock;
+use crate::error::EnvelopeResult;
+use crate::models::{CategoryId, TransactionId};
+use crate::services::{AccountService, CategoryService};
+use crate::storage::Storage;
+
+/// How serious a finding is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    /// Data is broken and balances or reports may be wrong
+    Error,
+    /// Data is inconsistent but still usable
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Error => write!(f, "Error"),
+            Self::Warning => write!(f, "Warning"),
+        }
+    }
+}
+
+/// A repair that can be applied without user judgement
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Fix {
+    /// Clear a transaction's reference to a category that no longer exists
+    ClearMissingCategory(TransactionId),
+    /// Turn a transfer whose partner is gone into a regular transaction
+    UnlinkTransfer(TransactionId),
+    /// Remove budget allocations for a category that no longer exists
+    RemoveAllocations(CategoryId),
+}
+
+/// A single integrity problem
+#[derive(Debug, Clone)]
+pub struct Finding {
+    pub severity: Severity,
+    pub message: String,
+    /// The safe repair for this finding, if there is one
+    pub fix: Option<Fix>,
+}
+
+impl Finding {
+    fn error(message: impl Into<String>, fix: Option<Fix>) -> Self {
+        Self {
+            severity: Severity::Error,
+            message: message.into(),
+            fix,
+        }
+    }
+
+    fn warning(message: impl Into<String>, fix: Option<Fix>) -> Self {
+        Self {
+            severity: Severity::Warning,
+            message: message.into(),
+            fix,
+        }
+    }
+}
+
+/// Result of a doctor scan
+#[derive(Debug, Clone, Default)]
+pub struct DoctorReport {
+    pub findings: Vec<Finding>,
+}
+
+impl DoctorReport {
+    /// Number of error-severity findings
+    pub fn error_count(&self) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == Severity::Error)
+            .count()
+    }
+
+    /// Number of warning-severity findings
+    pub fn warning_count(&self) -> usize {
+        self.findings
+            .iter()
+            .filter(|f| f.severity == Severity::Warning)
+            .count()
+    }
+
+    /// Number of findings with a safe fix
+    pub fn fixable_count(&self) -> usize {
+        self.findings.iter().filter(|f| f.fix.is_some()).count()
+    }
+
+    /// Check if no problems were found
+    pub fn is_healthy(&self) -> bool {
+        self.findings.is_empty()
+    }
+}
+
+/// Service for data integrity checks
+pub struct DoctorService<'a> {
+    storage: &'a Storage,
+}
+
+impl<'a> DoctorService<'a> {
+    /// Create a new doctor service
+    pub fn new(storage: &'a Storage) -> Self {
+        Self { storage }
+    }
+
+    /// Scan all data and report problems, errors first
+    pub fn check(&self) -> EnvelopeResult<DoctorReport> {
+        let mut findings = Vec::new();
+
+        let account_currencies: HashMap<_, _> = self
+            .storage
+            .accounts
+            .get_all()?
+            .into_iter()
+            .map(|a| (a.id, a.currency))
+            .collect();
+        let categories = self.storage.categories.get_all_categories()?;
+        let category_ids: HashSet<_> = categories.iter().map(|c| c.id).collect();
+        let group_ids: HashSet<_> = self
+            .storage
+            .categories
+            .get_all_groups()?
+            .iter()
+            .map(|g| g.id)
+            .collect();
+        let transactions = self.storage.transactions.get_all()?;
+
+        for category in &categories {
+            if !group_ids.contains(&category.group_id) {
+                findings.push(Finding::error(
+                    format!(
+                        "Category '{}' belongs to a missing group {}",
+                        category.name, category.group_id
+                    ),
+                    None,
+                ));
+            }
+        }
+
+        for txn in &transactions {
+            let label = format!("Transaction {} ({} {})", txn.id, txn.date, txn.amount);
+
+            if !account_currencies.contains_key(&txn.account_id) {
+                findings.push(Finding::error(
+                    format!("{} references missing account {}", label, txn.account_id),
+                    None,
+                ));
+            }
+
+            if let Some(cat_id) = txn.category_id {
+                if !category_ids.contains(&cat_id) {
+                    findings.push(Finding::error(
+                        format!("{} references missing category {}", label, cat_id),
+                        Some(Fix::ClearMissingCategory(txn.id)),
+                    ));
+                }
+            }
+
+            for split in &txn.splits {
+                if let Some(cat_id) = split.category_id {
+                    if !category_ids.contains(&cat_id) {
+                        findings.push(Finding::error(
+                            format!("{} has a split for missing category {}", label, cat_id),
+                            None,
+                        ));
+                    }
+                }
+                if let Some(leg_id) = split.transfer_transaction_id {
+                    if self.storage.transactions.get(leg_id)?.is_none() {
+                        findings.push(Finding::error(
+                            format!(
+                                "{} has a transfer split whose other leg {} is missing",
+                                label, leg_id
+                            ),
+                            None,
+                        ));
+                    }
+                }
+            }
+
+            if txn.is_split() && txn.splits_total() != txn.amount {
+                findings.push(Finding::error(
+                    format!(
+                        "{} splits total {} but the transaction is {}",
+                        label,
+                        txn.splits_total(),
+                        txn.amount
+                    ),
+                    None,
+                ));
+            }
+
+            if let Some(partner_id) = txn.transfer_transaction_id {
+                match self.storage.transactions.get(partner_id)? {
+                    None => findings.push(Finding::error(
+                        format!("{} is a transfer whose other leg is missing", label),
+                        Some(Fix::UnlinkTransfer(txn.id)),
+                    )),
+                    // The partner may be a split whose transfer split made this leg
+                    Some(partner) => match partner.transfer_amount_to(txn.id) {
+                        None => findings.push(Finding::warning(
+                            format!(
+                                "{} links to transfer {} which does not link back",
+                                label, partner_id
+                            ),
+                            None,
+                        )),
+                        // Legs in different currencies only need opposite signs
+                        Some(amount)
+                            if amount != -txn.amount
+                                && (account_currencies.get(&partner.account_id)
+                                    == account_currencies.get(&txn.account_id)
+                                    || amount.is_negative() == txn.amount.is_negative()) =>
+                        {
+                            findings.push(Finding::warning(
+                                format!(
+                                    "{} and its transfer partner {} have mismatched amounts",
+                                    label, partner_id
+                                ),
+                                None,
+                            ));
+                        }
+                        Some(_) => {}
+                    },
+                }
+            }
+        }
+
+        let mut orphaned_allocations: Vec<CategoryId> = Vec::new();
+        for allocation in self.storage.budget.get_all()? {
+            if !category_ids.contains(&allocation.category_id)
+                && !orphaned_allocations.contains(&allocation.category_id)
+            {
+                orphaned_allocations.push(allocation.category_id);
+            }
+        }
+        for cat_id in orphaned_allocations {
+            findings.push(Finding::warning(
+                format!("Budget allocations exist for deleted category {}", cat_id),
+                Some(Fix::RemoveAllocations(cat_id)),
+            ));
+        }
+
+        for accounts in AccountService::new(self.storage).find_duplicate_names()? {
+            findings.push(Finding::warning(
+                format!(
+                    "{} accounts share the name '{}'; name lookups are ambiguous",
+                    accounts.len(),
+                    accounts[0].name
+                ),
+                None,
+            ));
+        }
+
+        let duplicates = CategoryService::new(self.storage).find_duplicate_names()?;
+        for categories in duplicates.categories {
+            findings.push(Finding::warning(
+                format!(
+                    "{} categories share the name '{}'; name lookups are ambiguous",
+                    categories.len(),
+                    categories[0].name
+                ),
+                None,
+            ));
+        }
+        for groups in duplicates.groups {
+            findings.push(Finding::warning(
+                format!(
+                    "{} category groups share the name '{}'",
+                    groups.len(),
+                    groups[0].name
+                ),
+                None,
+            ));
+        }
+
+        findings.sort_by_key(|f| f.severity);
+
+        Ok(DoctorReport { findings })
+    }
+
+    /// Apply every safe fix in the report, returning how many were applied
+    ///
+    /// Takes a backup first, and audits each change like any other edit.
+    pub fn fix(&self, report: &DoctorReport) -> EnvelopeResult<usize> {
+        let fixes: Vec<&Fix> = report
+            .findings
+            .iter()
+            .filter_map(|f| f.fix.as_ref())
+            .collect();
+        if fixes.is_empty() {
+            return Ok(0);
+        }
+        self.storage.backup_before_destructive()?;
+
+        let mut applied = 0;
+        let mut updated = Vec::new();
+        let mut removed = Vec::new();
+
+        for fix in fixes {
+            match fix {
+                Fix::ClearMissingCategory(id) => {
+                    if let Some(before) = self.storage.transactions.get(*id)? {
+                        let mut txn = before.clone();
+                        txn.category_id = None;
+                        txn.updated_at = clock::now();
+                        self.storage.transactions.upsert(txn.clone())?;
+                        updated.push((before, txn, "category: missing -> none"));
+                        applied += 1;
+                    }
+                }
+                Fix::UnlinkTransfer(id) => {
+                    if let Some(before) = self.storage.transactions.get(*id)? {
+                        let mut txn = before.clone();
+                        txn.transfer_transaction_id = None;
+                        txn.updated_at = clock::now();
+                        self.storage.transactions.upsert(txn.clone())?;
+                        updated.push((before, txn, "transfer: missing leg unlinked"));
+                        applied += 1;
+                    }
+                }
+                Fix::RemoveAllocations(cat_id) => {
+                    removed.extend(self.storage.budget.get_for_category(*cat_id)?);
+                    self.storage.budget.delete_for_category(*cat_id)?;
+                    applied += 1;
+                }
+            }
+        }
+
+        if !updated.is_empty() {
+            self.storage.transactions.save()?;
+        }
+        if !removed.is_empty() {
+            self.storage.budget.save()?;
+        }
+
+        for (before, after, summary) in &updated {
+            self.storage.log_update(
+                EntityType::Transaction,
+                after.id.to_string(),
+                Some(format!("{} {}", after.date, after.payee_name)),
+                before,
+                after,
+                Some(summary.to_string()),
+            )?;
+        }
+        for allocation in &removed {
+            self.storage.log_delete(
+                EntityType::BudgetAllocation,
+                format!("{}:{}", allocation.category_id, allocation.period),
+                None,
+                allocation,
+            )?;
+        }
+
+        Ok(applied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::paths::EnvelopePaths;
+    use crate::models::{
+        Account, AccountType, BudgetAllocation, BudgetPeriod, Category, CategoryGroup, Money,
+        Split, Transaction,
+    };
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (TempDir, Storage) {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let mut storage = Storage::new(paths).unwrap();
+        storage.load_all().unwrap();
+        (temp_dir, storage)
+    }
+
+    fn date() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2025, 1, 15).unwrap()
+    }
+
+    #[test]
+    fn test_healthy_data() {
+        let (_temp_dir, storage) = create_test_storage();
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+        storage
+            .transactions
+            .upsert(Transaction::new(
+                account.id,
+                date(),
+                Money::from_cents(-500),
+            ))
+            .unwrap();
+
+        let report = DoctorService::new(&storage).check().unwrap();
+        assert!(report.is_healthy());
+    }
+
+    #[test]
+    fn test_detects_and_fixes_problems() {
+        let (_temp_dir, storage) = create_test_storage();
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+
+        let group = CategoryGroup::new("Needs");
+        storage.categories.upsert_group(group.clone()).unwrap();
+        let groceries = Category::new("Groceries", group.id);
+        storage
+            .categories
+            .upsert_category(groceries.clone())
+            .unwrap();
+
+        let missing_category = CategoryId::new();
+
+        // Dangling category reference (fixable)
+        let mut txn = Transaction::new(account.id, date(), Money::from_cents(-500));
+        txn.category_id = Some(missing_category);
+        storage.transactions.upsert(txn.clone()).unwrap();
+
+        // Transfer with a missing partner (fixable)
+        let mut transfer = Transaction::new(account.id, date(), Money::from_cents(-1000));
+        transfer.transfer_transaction_id = Some(TransactionId::new());
+        storage.transactions.upsert(transfer.clone()).unwrap();
+
+        // Splits that don't add up (not fixable)
+        let mut split = Transaction::new(account.id, date(), Money::from_cents(-1000));
+        split
+            .splits
+            .push(Split::new(groceries.id, Money::from_cents(-400)));
+        storage.transactions.upsert(split).unwrap();
+
+        // Allocation for a deleted category (fixable)
+        let period = BudgetPeriod::monthly(2025, 1);
+        let mut allocation = BudgetAllocation::new(missing_category, period);
+        allocation.budgeted = Money::from_cents(1000);
+        storage.budget.upsert(allocation).unwrap();
+
+        let service = DoctorService::new(&storage);
+        let report = service.check().unwrap();
+        assert_eq!(report.error_count(), 3);
+        assert_eq!(report.warning_count(), 1);
+        assert_eq!(report.fixable_count(), 3);
+        assert_eq!(report.findings[0].severity, Severity::Error);
+
+        assert_eq!(service.fix(&report).unwrap(), 3);
+
+        // The fix is backed up and audited like any other edit
+        assert_eq!(
+            storage
+                .backup_manager(Default::default())
+                .list_backups()
+                .unwrap()
+                .len(),
+            1
+        );
+        let log = storage.read_audit_log(10).unwrap();
+        assert_eq!(log.len(), 3);
+        assert!(log
+            .iter()
+            .any(|e| e.entity_type == EntityType::BudgetAllocation));
+
+        let report = service.check().unwrap();
+        assert_eq!(report.error_count(), 1);
+        assert_eq!(report.fixable_count(), 0);
+        assert!(storage
+            .transactions
+            .get(transfer.id)
+            .unwrap()
+            .unwrap()
+            .transfer_transaction_id
+            .is_none());
+    }
+
+    #[test]
+    fn test_cross_currency_transfer_is_healthy() {
+        let (_temp_dir, storage) = create_test_storage();
+        let checking = Account::new("Checking", AccountType::Checking);
+        let mut savings = Account::new("Euro Savings", AccountType::Savings);
+        savings.currency = Some("EUR".to_string());
+        storage.accounts.upsert(checking.clone()).unwrap();
+        storage.accounts.upsert(savings.clone()).unwrap();
+
+        crate::services::TransferService::new(&storage)
+            .with_exchange_rate(0.92)
+            .create_transfer(
+                checking.id,
+                savings.id,
+                Money::from_cents(10000),
+                date(),
+                None,
+            )
+            .unwrap();
+
+        let report = DoctorService::new(&storage).check().unwrap();
+        assert!(report.is_healthy());
+    }
+}
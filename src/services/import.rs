@@ -1,15 +1,17 @@
-//! CSV Import service
+//! CSV and OFX import service
 //!
-//! Provides functionality for importing transactions from CSV files,
-//! including column mapping, date parsing, duplicate detection, and batch import.
+//! Provides functionality for importing transactions from CSV and OFX/QFX
+//! files, including column mapping, date parsing, duplicate detection, and
+//! batch import.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
 
 use chrono::NaiveDate;
 
-use crate::error::EnvelopeResult;
-use crate::models::{AccountId, CategoryId, Money, TransactionStatus};
-use crate::services::TransactionService;
+use crate::error::{EnvelopeError, EnvelopeResult};
+use crate::models::{AccountId, CategoryId, Money, Split, TransactionFlag, TransactionStatus};
+use crate::services::{CategoryService, TransactionService};
 use crate::storage::Storage;
 use csv::{Reader, StringRecord};
 
@@ -36,8 +38,26 @@ pub struct ColumnMapping {
     pub delimiter: char,
     /// Whether to invert amounts (some banks use positive for debits)
     pub invert_amounts: bool,
+    /// Number of preamble rows to drop before the header (or first data row)
+    pub skip_header_rows: usize,
+    /// Number of trailing rows to drop (e.g., a "total" footer)
+    pub skip_footer_rows: usize,
+    /// Silently skip rows whose date fails to parse instead of reporting errors
+    pub skip_invalid_dates: bool,
+    /// Index of the row ID column (EnvelopeCLI exports)
+    pub id_column: Option<usize>,
+    /// Index of the category name column (EnvelopeCLI exports)
+    pub category_column: Option<usize>,
+    /// Index of the `Split Of` column naming a split row's parent
+    pub split_of_column: Option<usize>,
+    /// Index of a text column (e.g., "Type") whose value marks a refund or
+    /// credit; those rows are always inflows, whatever `invert_amounts` says
+    pub refund_indicator_column: Option<usize>,
 }
 
+/// Values of the refund indicator column that mark a row as an inflow
+const REFUND_INDICATORS: &[&str] = &["credit", "cr", "refund", "return", "payment"];
+
 impl Default for ColumnMapping {
     fn default() -> Self {
         Self {
@@ -51,6 +71,13 @@ impl Default for ColumnMapping {
             has_header: true,
             delimiter: ',',
             invert_amounts: false,
+            skip_header_rows: 0,
+            skip_footer_rows: 0,
+            skip_invalid_dates: false,
+            id_column: None,
+            category_column: None,
+            split_of_column: None,
+            refund_indicator_column: None,
         }
     }
 }
@@ -74,6 +101,13 @@ impl ColumnMapping {
             has_header: true,
             delimiter: ',',
             invert_amounts: false,
+            skip_header_rows: 0,
+            skip_footer_rows: 0,
+            skip_invalid_dates: false,
+            id_column: None,
+            category_column: None,
+            split_of_column: None,
+            refund_indicator_column: None,
         }
     }
 
@@ -90,6 +124,13 @@ impl ColumnMapping {
             has_header: true,
             delimiter: ',',
             invert_amounts: true, // Credit cards often show positive for purchases
+            skip_header_rows: 0,
+            skip_footer_rows: 0,
+            skip_invalid_dates: false,
+            id_column: None,
+            category_column: None,
+            split_of_column: None,
+            refund_indicator_column: None,
         }
     }
 
@@ -111,6 +152,13 @@ impl ColumnMapping {
             has_header: true,
             delimiter: ',',
             invert_amounts: false,
+            skip_header_rows: 0,
+            skip_footer_rows: 0,
+            skip_invalid_dates: false,
+            id_column: None,
+            category_column: None,
+            split_of_column: None,
+            refund_indicator_column: None,
         }
     }
 
@@ -127,6 +175,13 @@ impl ColumnMapping {
             has_header: false,
             delimiter: ',',
             invert_amounts: false,
+            skip_header_rows: 0,
+            skip_footer_rows: 0,
+            skip_invalid_dates: false,
+            id_column: None,
+            category_column: None,
+            split_of_column: None,
+            refund_indicator_column: None,
         }
     }
 
@@ -147,6 +202,119 @@ impl ColumnMapping {
         self.delimiter = delimiter;
         self
     }
+
+    /// Set the number of preamble lines to skip
+    pub fn with_skip_header_rows(mut self, rows: usize) -> Self {
+        self.skip_header_rows = rows;
+        self
+    }
+
+    /// Set the number of footer rows to skip
+    pub fn with_skip_footer_rows(mut self, rows: usize) -> Self {
+        self.skip_footer_rows = rows;
+        self
+    }
+
+    /// Set the column whose value marks a refund or credit
+    pub fn with_refund_indicator_column(mut self, column: usize) -> Self {
+        self.refund_indicator_column = Some(column);
+        self
+    }
+
+    /// Set whether rows with unparseable dates are skipped
+    pub fn with_skip_invalid_dates(mut self, skip: bool) -> Self {
+        self.skip_invalid_dates = skip;
+        self
+    }
+
+    /// Check the mapping against a file's header and first data row
+    ///
+    /// Every mapped column must exist in `headers`. With a `sample` row, the
+    /// date column must hold a date and the amount column(s) a number, so a
+    /// misconfigured mapping fails once up front instead of on every row.
+    pub fn validate_against(
+        &self,
+        headers: &StringRecord,
+        sample: Option<&StringRecord>,
+    ) -> EnvelopeResult<()> {
+        let name = |col: usize| match headers.get(col).map(str::trim) {
+            Some(header) if self.has_header && !header.is_empty() => {
+                format!("column {} ('{}')", col + 1, header)
+            }
+            _ => format!("column {}", col + 1),
+        };
+
+        let columns = [
+            ("date", Some(self.date_column)),
+            ("amount", self.amount_column),
+            ("outflow", self.outflow_column),
+            ("inflow", self.inflow_column),
+            ("payee", self.payee_column),
+            ("memo", self.memo_column),
+            ("ID", self.id_column),
+            ("category", self.category_column),
+            ("Split Of", self.split_of_column),
+            ("refund indicator", self.refund_indicator_column),
+        ];
+        for (role, col) in columns {
+            if let Some(col) = col.filter(|col| *col >= headers.len()) {
+                return Err(EnvelopeError::Import(format!(
+                    "The {} column is set to column {}, but the file only has {} columns",
+                    role,
+                    col + 1,
+                    headers.len()
+                )));
+            }
+        }
+
+        if self.amount_column.is_none()
+            && (self.outflow_column.is_none() || self.inflow_column.is_none())
+        {
+            return Err(EnvelopeError::Import(
+                "No amount column: set an amount column or both outflow and inflow columns".into(),
+            ));
+        }
+
+        let Some(sample) = sample else {
+            return Ok(());
+        };
+
+        let value = |col: usize| sample.get(col).unwrap_or("").trim();
+
+        let date = value(self.date_column);
+        if parse_date(date, &self.date_format).is_err() {
+            return Err(EnvelopeError::Import(format!(
+                "The date column, {}, doesn't hold a date on the first row: '{}'",
+                name(self.date_column),
+                date
+            )));
+        }
+
+        let amount_columns = match self.amount_column {
+            Some(col) => vec![col],
+            None => self
+                .outflow_column
+                .into_iter()
+                .chain(self.inflow_column)
+                .collect(),
+        };
+        for col in amount_columns {
+            let amount = value(col);
+            // Separate outflow/inflow columns leave one side blank
+            if amount.is_empty() && self.amount_column.is_none() {
+                continue;
+            }
+            if parse_amount(amount).is_err() {
+                return Err(EnvelopeError::Import(format!(
+                    "The amount column, {}, doesn't hold a number on the first row: '{}'",
+                    name(col),
+                    amount
+                )));
+            }
+        }
+
+        Ok(())
+    }
 }
 
 /// A parsed row from the CSV before import
@@ -164,6 +332,25 @@ pub struct ParsedTransaction {
     pub row_number: usize,
     /// Generated import ID for duplicate detection
     pub import_id: String,
+    /// Row ID from the file, when it has one
+    pub source_id: String,
+    /// Category name from the file, when it has one
+    pub category: String,
+    /// ID of the transaction this row is a split of, if any
+    pub split_of: String,
+    /// Split rows folded into this transaction
+    pub splits: Vec<ParsedSplit>,
+}
+
+/// A split row folded into its parent transaction
+#[derive(Debug, Clone)]
+pub struct ParsedSplit {
+    /// Category name from the file
+    pub category: String,
+    /// Split amount (same sign as the transaction)
+    pub amount: Money,
+    /// Split memo
+    pub memo: String,
 }
 
 impl ParsedTransaction {
@@ -178,6 +365,9 @@ impl ParsedTransaction {
     }
 }
 
+/// Default days either side of a row's date searched for likely duplicates
+pub const DEFAULT_DUPLICATE_WINDOW_DAYS: u32 = 1;
+
 /// Status of a transaction for import preview
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum ImportStatus {
@@ -198,6 +388,8 @@ pub struct ImportPreviewEntry {
     pub status: ImportStatus,
     /// Matching existing transaction ID (for duplicates)
     pub existing_id: Option<String>,
+    /// Whether the duplicate matched on amount and date only, not import ID
+    pub likely: bool,
 }
 
 /// Result of a completed import
@@ -213,27 +405,78 @@ pub struct ImportResult {
     pub imported_ids: Vec<String>,
     /// Error messages by row
     pub error_messages: HashMap<usize, String>,
+    /// Nothing was written; `imported` counts what would have been
+    pub dry_run: bool,
+}
+
+/// Options for [`ImportService::import_file`]
+#[derive(Debug, Clone, Default)]
+pub struct ImportFileOptions {
+    /// Category for rows that don't name one that exists
+    pub default_category: Option<CategoryId>,
+    /// Mark imported transactions as cleared
+    pub mark_cleared: bool,
+    /// Stop after the preview and report counts without importing
+    pub dry_run: bool,
+    /// Character encoding of the file (UTF-8 if `None`)
+    pub encoding: Option<String>,
 }
 
 /// Service for CSV import
 pub struct ImportService<'a> {
     storage: &'a Storage,
+    duplicate_window_days: u32,
 }
 
 impl<'a> ImportService<'a> {
     /// Create a new import service
     pub fn new(storage: &'a Storage) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            duplicate_window_days: DEFAULT_DUPLICATE_WINDOW_DAYS,
+        }
+    }
+
+    /// Set how many days apart a same-amount transaction may be and still
+    /// be flagged as a likely duplicate
+    pub fn with_duplicate_window(mut self, days: u32) -> Self {
+        self.duplicate_window_days = days;
+        self
+    }
+
+    /// Parse CSV text into transactions
+    ///
+    /// Drops `skip_header_rows` preamble rows before building the reader, so
+    /// junk rows ahead of the real header never reach the CSV parser.
+    pub fn parse_csv_str(
+        &self,
+        data: &str,
+        mapping: &ColumnMapping,
+    ) -> EnvelopeResult<Vec<Result<ParsedTransaction, String>>> {
+        let body = skip_rows(data, mapping.skip_header_rows, mapping.delimiter);
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(mapping.has_header)
+            .delimiter(mapping.delimiter as u8)
+            .flexible(true)
+            .from_reader(body.as_bytes());
+        self.parse_csv_from_reader(&mut reader, mapping)
     }
 
     /// Parse a CSV from a reader into transactions
+    ///
+    /// Footer rows and (optionally) rows with unparseable dates are dropped.
+    /// Preamble rows must already have been consumed by the caller; see
+    /// [`ImportService::parse_csv_str`].
     pub fn parse_csv_from_reader<R: std::io::Read>(
         &self,
         reader: &mut Reader<R>,
         mapping: &ColumnMapping,
     ) -> EnvelopeResult<Vec<Result<ParsedTransaction, String>>> {
+        let records: Vec<_> = reader.records().collect();
+        let keep = records.len().saturating_sub(mapping.skip_footer_rows);
+
         let mut results = Vec::new();
-        for (idx, result) in reader.records().enumerate() {
+        for (idx, result) in records.into_iter().take(keep).enumerate() {
             let record = match result {
                 Ok(record) => record,
                 Err(e) => {
@@ -241,12 +484,152 @@ impl<'a> ImportService<'a> {
                     continue;
                 }
             };
+            if mapping.skip_invalid_dates && !self.has_valid_date(&record, mapping) {
+                continue;
+            }
             let result = self.parse_record(&record, idx, mapping);
             results.push(result);
         }
+
+        if mapping.split_of_column.is_some() {
+            results = merge_split_rows(results);
+        }
         Ok(results)
     }
 
+    /// Parse OFX or QFX text into transactions
+    ///
+    /// Reads each `<STMTTRN>` record from both SGML (OFX 1.x, unclosed
+    /// elements) and XML (OFX 2.x) files. The record's `FITID` becomes the
+    /// import ID, so re-importing the same download is caught exactly;
+    /// records without one fall back to the generated hash.
+    pub fn parse_ofx_str(
+        &self,
+        data: &str,
+    ) -> EnvelopeResult<Vec<Result<ParsedTransaction, String>>> {
+        let upper = data.to_ascii_uppercase();
+        if !upper.contains("<OFX>") {
+            return Err(EnvelopeError::Import(
+                "File is not OFX: no <OFX> element found".to_string(),
+            ));
+        }
+
+        let mut results = Vec::new();
+        let mut rest = 0;
+        while let Some(offset) = upper[rest..].find("<STMTTRN>") {
+            let start = rest + offset + "<STMTTRN>".len();
+            let end = upper[start..]
+                .find("</STMTTRN>")
+                .map(|pos| start + pos)
+                .unwrap_or(data.len());
+            results.push(parse_ofx_record(
+                &data[start..end],
+                &upper[start..end],
+                results.len(),
+            ));
+            rest = end;
+        }
+
+        Ok(results)
+    }
+
+    /// Parse OFX or QFX from a reader into transactions
+    ///
+    /// The data must be UTF-8; decode other encodings with
+    /// [`decode_csv_bytes`] and use [`ImportService::parse_ofx_str`].
+    pub fn parse_ofx_from_reader<R: std::io::Read>(
+        &self,
+        mut reader: R,
+    ) -> EnvelopeResult<Vec<Result<ParsedTransaction, String>>> {
+        let mut data = String::new();
+        reader
+            .read_to_string(&mut data)
+            .map_err(|e| EnvelopeError::Import(format!("Failed to read OFX data: {}", e)))?;
+        self.parse_ofx_str(&data)
+    }
+
+    /// Check a mapping against CSV text before parsing it
+    ///
+    /// Uses the header (or first row) and the first data row that would be
+    /// imported. See [`ColumnMapping::validate_against`].
+    pub fn validate_mapping(&self, data: &str, mapping: &ColumnMapping) -> EnvelopeResult<()> {
+        let body = skip_rows(data, mapping.skip_header_rows, mapping.delimiter);
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(mapping.delimiter as u8)
+            .flexible(true)
+            .from_reader(body.as_bytes());
+        let mut records = reader.records().filter_map(Result::ok);
+
+        let Some(first) = records.next() else {
+            return Ok(());
+        };
+        let sample = if mapping.has_header {
+            records.find(|r| !mapping.skip_invalid_dates || self.has_valid_date(r, mapping))
+        } else if !mapping.skip_invalid_dates || self.has_valid_date(&first, mapping) {
+            Some(first.clone())
+        } else {
+            records.find(|r| self.has_valid_date(r, mapping))
+        };
+
+        mapping.validate_against(&first, sample.as_ref())
+    }
+
+    /// Count the preamble rows before the header row, or before the first
+    /// data row when the file has no header
+    ///
+    /// Rows are CSV records as the reader sees them, so any line ending works
+    /// and blank lines don't count.
+    ///
+    /// Uses [`looks_like_data_row`](Self::looks_like_data_row) to find the
+    /// first data row. The line above it counts as the header only if
+    /// [`looks_like_header_row`](Self::looks_like_header_row) agrees; otherwise
+    /// everything above the data is preamble. Returns 0 when no data row is
+    /// found.
+    pub fn detect_preamble_rows(&self, data: &str, delimiter: char) -> usize {
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(delimiter as u8)
+            .flexible(true)
+            .from_reader(data.as_bytes());
+
+        let mut previous: Option<StringRecord> = None;
+        for (idx, record) in reader.records().enumerate() {
+            let Ok(record) = record else {
+                continue;
+            };
+            if self.looks_like_data_row(&record) {
+                let has_header = previous
+                    .as_ref()
+                    .is_some_and(|prev| self.looks_like_header_row(prev, &record));
+                return idx - usize::from(has_header);
+            }
+            previous = Some(record);
+        }
+        0
+    }
+
+    /// Whether a record looks like the header for the given data row
+    ///
+    /// A header has as many columns as the data, and none of its cells are
+    /// dates or numbers.
+    fn looks_like_header_row(&self, record: &StringRecord, data_row: &StringRecord) -> bool {
+        record.len() == data_row.len()
+            && !self.looks_like_data_row(record)
+            && record
+                .iter()
+                .map(str::trim)
+                .all(|cell| parse_amount(cell).is_err())
+    }
+
+    /// Whether the record's date column parses as a date
+    fn has_valid_date(&self, record: &StringRecord, mapping: &ColumnMapping) -> bool {
+        record
+            .get(mapping.date_column)
+            .map(|s| parse_date(s.trim(), &mapping.date_format).is_ok())
+            .unwrap_or(false)
+    }
+
     /// Parse a single CSV record
     fn parse_record(
         &self,
@@ -260,7 +643,7 @@ impl<'a> ImportService<'a> {
             .ok_or_else(|| "Missing date column".to_string())?
             .trim();
 
-        let date = self.parse_date(date_str, &mapping.date_format)?;
+        let date = parse_date(date_str, &mapping.date_format)?;
 
         // Parse amount
         let amount = self.parse_amount_from_record(record, mapping)?;
@@ -279,6 +662,13 @@ impl<'a> ImportService<'a> {
             .map(|s| s.trim().to_string())
             .unwrap_or_default();
 
+        // Columns only present in EnvelopeCLI exports
+        let text = |col: Option<usize>| {
+            col.and_then(|col| record.get(col))
+                .map(|s| s.trim().to_string())
+                .unwrap_or_default()
+        };
+
         // Generate import ID
         let import_id = ParsedTransaction::generate_import_id(date, amount, &payee);
 
@@ -289,6 +679,10 @@ impl<'a> ImportService<'a> {
             memo,
             row_number,
             import_id,
+            source_id: text(mapping.id_column),
+            category: text(mapping.category_column),
+            split_of: text(mapping.split_of_column),
+            splits: Vec::new(),
         })
     }
 
@@ -305,7 +699,7 @@ impl<'a> ImportService<'a> {
                 .ok_or_else(|| "Missing amount column".to_string())?
                 .trim();
 
-            self.parse_amount_string(amount_str)?
+            parse_amount(amount_str)?
         } else {
             // Separate inflow/outflow columns
             let outflow_col = mapping
@@ -321,18 +715,31 @@ impl<'a> ImportService<'a> {
             let outflow = if outflow_str.is_empty() {
                 Money::zero()
             } else {
-                -self.parse_amount_string(outflow_str)?.abs()
+                -parse_amount(outflow_str)?.abs()
             };
 
             let inflow = if inflow_str.is_empty() {
                 Money::zero()
             } else {
-                self.parse_amount_string(inflow_str)?.abs()
+                parse_amount(inflow_str)?.abs()
             };
 
             outflow + inflow
         };
 
+        // A refund or credit is money back, even on an inverted card export
+        // that would otherwise flip it into a charge
+        let is_refund = mapping
+            .refund_indicator_column
+            .and_then(|col| record.get(col))
+            .is_some_and(|value| {
+                let value = value.trim().to_lowercase();
+                REFUND_INDICATORS.contains(&value.as_str())
+            });
+        if is_refund {
+            return Ok(amount.abs());
+        }
+
         if mapping.invert_amounts {
             Ok(-amount)
         } else {
@@ -340,28 +747,6 @@ impl<'a> ImportService<'a> {
         }
     }
 
-    /// Parse a date string using multiple format attempts
-    fn parse_date(&self, s: &str, primary_format: &str) -> Result<NaiveDate, String> {
-        // Try primary format first
-        if let Ok(date) = NaiveDate::parse_from_str(s, primary_format) {
-            return Ok(date);
-        }
-
-        // Try common alternative formats
-        let formats = [
-            "%Y-%m-%d", "%m/%d/%Y", "%m/%d/%y", "%d/%m/%Y", "%d/%m/%y", "%Y/%m/%d", "%m-%d-%Y",
-            "%d-%m-%Y",
-        ];
-
-        for format in formats {
-            if let Ok(date) = NaiveDate::parse_from_str(s, format) {
-                return Ok(date);
-            }
-        }
-
-        Err(format!("Could not parse date: '{}'", s))
-    }
-
     /// Check if a record looks like data (not headers)
     /// Returns true if first column parses as a date
     fn looks_like_data_row(&self, record: &StringRecord) -> bool {
@@ -378,6 +763,59 @@ impl<'a> ImportService<'a> {
         false
     }
 
+    /// Guess the delimiter of raw CSV text
+    ///
+    /// Picks whichever of comma, semicolon, tab, or pipe splits the most of
+    /// the first rows into the same number of columns (more than one).
+    /// Falls back to a comma.
+    pub fn detect_delimiter(&self, data: &str) -> char {
+        let mut best = (',', 0);
+        for delimiter in [',', ';', '\t', '|'] {
+            let mut reader = csv::ReaderBuilder::new()
+                .has_headers(false)
+                .delimiter(delimiter as u8)
+                .flexible(true)
+                .from_reader(data.as_bytes());
+
+            let mut widths: HashMap<usize, usize> = HashMap::new();
+            for record in reader.byte_records().take(20).filter_map(Result::ok) {
+                if record.len() > 1 {
+                    *widths.entry(record.len()).or_default() += 1;
+                }
+            }
+            let score = widths.into_values().max().unwrap_or(0);
+            if score > best.1 {
+                best = (delimiter, score);
+            }
+        }
+        best.0
+    }
+
+    /// Detect a column mapping for raw CSV text
+    ///
+    /// Detects the delimiter, skips any preamble, then detects columns from
+    /// the first remaining row.
+    pub fn detect_mapping(&self, data: &str) -> EnvelopeResult<ColumnMapping> {
+        let delimiter = self.detect_delimiter(data);
+        let preamble = self.detect_preamble_rows(data, delimiter);
+        let mut reader = csv::ReaderBuilder::new()
+            .has_headers(false)
+            .delimiter(delimiter as u8)
+            .flexible(true)
+            .from_reader(skip_rows(data, preamble, delimiter).as_bytes());
+
+        let first = match reader.records().next() {
+            Some(record) => record
+                .map_err(|e| EnvelopeError::Import(format!("Failed to read CSV headers: {}", e)))?,
+            None => StringRecord::new(),
+        };
+
+        let mut mapping = self.detect_mapping_from_headers(&first);
+        mapping.delimiter = delimiter;
+        mapping.skip_header_rows = preamble;
+        Ok(mapping)
+    }
+
     /// Detect column mapping from CSV header record
     pub fn detect_mapping_from_headers(&self, headers: &StringRecord) -> ColumnMapping {
         // First, check if this looks like a data row (no headers)
@@ -399,11 +837,28 @@ impl<'a> ImportService<'a> {
 
         let mut mapping = ColumnMapping::new();
 
+        // A `Split Of` column marks an EnvelopeCLI transactions export, whose
+        // IDs and categories are used to rebuild split transactions
+        let is_envelope_export = headers
+            .iter()
+            .any(|h| h.trim().eq_ignore_ascii_case("split of"));
+
         for (idx, header) in headers.iter().enumerate() {
             let h = header.to_lowercase();
             let h = h.trim();
 
-            if h.contains("date") || h.contains("posted") {
+            if is_envelope_export && h == "id" {
+                mapping.id_column = Some(idx);
+            } else if is_envelope_export && h == "category" {
+                mapping.category_column = Some(idx);
+            } else if is_envelope_export && h == "split of" {
+                mapping.split_of_column = Some(idx);
+            } else if matches!(
+                h,
+                "type" | "transaction type" | "debit/credit" | "credit/debit"
+            ) {
+                mapping.refund_indicator_column = Some(idx);
+            } else if h.contains("date") || h.contains("posted") {
                 mapping.date_column = idx;
             } else if h.contains("amount") {
                 mapping.amount_column = Some(idx);
@@ -430,29 +885,13 @@ impl<'a> ImportService<'a> {
         mapping
     }
 
-    /// Parse an amount string, handling various formats
-    fn parse_amount_string(&self, s: &str) -> Result<Money, String> {
-        // Remove currency symbols, commas, spaces
-        let cleaned: String = s
-            .chars()
-            .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '-' || *c == '(' || *c == ')')
-            .collect();
-
-        // Handle parentheses as negative (accounting format)
-        let (is_negative, value) = if cleaned.starts_with('(') && cleaned.ends_with(')') {
-            (true, &cleaned[1..cleaned.len() - 1])
-        } else if let Some(stripped) = cleaned.strip_prefix('-') {
-            (true, stripped)
-        } else {
-            (false, cleaned.as_str())
-        };
-
-        Money::parse(value)
-            .map(|m| if is_negative { -m } else { m })
-            .map_err(|e| format!("Could not parse amount '{}': {}", s, e))
-    }
-
     /// Generate an import preview, checking for duplicates
+    ///
+    /// A row whose import ID matches an existing transaction is a definite
+    /// duplicate. Failing that, an existing transaction with the same amount
+    /// dated within the duplicate window marks it a likely duplicate, which
+    /// catches re-imports where the bank reworded the description. Each
+    /// existing transaction is matched at most once, closest date first.
     pub fn generate_preview(
         &self,
         parsed: &[Result<ParsedTransaction, String>],
@@ -471,22 +910,44 @@ impl<'a> ImportService<'a> {
             })
             .collect();
 
+        // Transactions claimed by an exact match can't also match loosely
+        let mut matched: HashSet<String> = parsed
+            .iter()
+            .flatten()
+            .filter_map(|txn| existing_import_ids.get(&txn.import_id).cloned())
+            .collect();
+        let window = i64::from(self.duplicate_window_days);
+
         for result in parsed {
             match result {
                 Ok(txn) => {
-                    let status = if let Some(_existing_id) = existing_import_ids.get(&txn.import_id)
-                    {
-                        ImportStatus::Duplicate
-                    } else {
-                        ImportStatus::New
-                    };
-
-                    let existing_id = existing_import_ids.get(&txn.import_id).cloned();
+                    let (status, existing_id, likely) =
+                        if let Some(existing_id) = existing_import_ids.get(&txn.import_id) {
+                            (ImportStatus::Duplicate, Some(existing_id.clone()), false)
+                        } else {
+                            let nearest = existing_txns
+                                .iter()
+                                .filter(|t| t.amount == txn.amount)
+                                .filter(|t| !matched.contains(&t.id.to_string()))
+                                .map(|t| (t, (t.date - txn.date).num_days().abs()))
+                                .filter(|(_, days)| *days <= window)
+                                .min_by_key(|(_, days)| *days);
+
+                            match nearest {
+                                Some((existing, _)) => {
+                                    let existing_id = existing.id.to_string();
+                                    matched.insert(existing_id.clone());
+                                    (ImportStatus::Duplicate, Some(existing_id), true)
+                                }
+                                None => (ImportStatus::New, None, false),
+                            }
+                        };
 
                     preview.push(ImportPreviewEntry {
                         transaction: txn.clone(),
                         status,
                         existing_id,
+                        likely,
                     });
                 }
                 Err(e) => {
@@ -498,9 +959,14 @@ impl<'a> ImportService<'a> {
                             memo: String::new(),
                             row_number: 0,
                             import_id: String::new(),
+                            source_id: String::new(),
+                            category: String::new(),
+                            split_of: String::new(),
+                            splits: Vec::new(),
                         },
                         status: ImportStatus::Error(e.clone()),
                         existing_id: None,
+                        likely: false,
                     });
                 }
             }
@@ -509,6 +975,49 @@ impl<'a> ImportService<'a> {
         Ok(preview)
     }
 
+    /// Look up the category and splits named in an imported row
+    ///
+    /// A category name that doesn't match falls back to the default, but every
+    /// split must name an existing category and the splits must add up to the
+    /// transaction amount.
+    fn resolve_categories(
+        &self,
+        txn: &ParsedTransaction,
+        default_category_id: Option<CategoryId>,
+    ) -> EnvelopeResult<(Option<CategoryId>, Vec<Split>)> {
+        let category_service = CategoryService::new(self.storage);
+
+        if txn.splits.is_empty() {
+            let category_id = match txn.category.as_str() {
+                "" => None,
+                name => category_service.find_category(name)?.map(|c| c.id),
+            };
+            return Ok((category_id.or(default_category_id), Vec::new()));
+        }
+
+        let mut splits = Vec::with_capacity(txn.splits.len());
+        for split in &txn.splits {
+            let category = category_service
+                .find_category(&split.category)?
+                .ok_or_else(|| EnvelopeError::category_not_found(&split.category))?;
+            splits.push(Split::with_memo(
+                category.id,
+                split.amount,
+                split.memo.clone(),
+            ));
+        }
+
+        let total: Money = splits.iter().map(|s| s.amount).sum();
+        if total != txn.amount {
+            return Err(EnvelopeError::Validation(format!(
+                "Splits total {} but the transaction is {}",
+                total, txn.amount
+            )));
+        }
+
+        Ok((None, splits))
+    }
+
     /// Import transactions from a preview
     pub fn import_from_preview(
         &self,
@@ -525,29 +1034,43 @@ impl<'a> ImportService<'a> {
             errors: 0,
             imported_ids: Vec::new(),
             error_messages: HashMap::new(),
+            dry_run: false,
         };
 
         for entry in preview {
             match &entry.status {
                 ImportStatus::New => {
-                    let input = crate::services::CreateTransactionInput {
-                        account_id,
-                        date: entry.transaction.date,
-                        amount: entry.transaction.amount,
-                        payee_name: Some(entry.transaction.payee.clone()),
-                        category_id: default_category_id,
-                        memo: Some(entry.transaction.memo.clone()),
-                        status: if mark_cleared {
-                            Some(TransactionStatus::Cleared)
-                        } else {
-                            None
-                        },
-                    };
-
-                    match txn_service.create(input) {
+                    let created = self
+                        .resolve_categories(&entry.transaction, default_category_id)
+                        .and_then(|(category_id, splits)| {
+                            let input = crate::services::CreateTransactionInput {
+                                account_id,
+                                date: entry.transaction.date,
+                                amount: entry.transaction.amount,
+                                payee_name: Some(entry.transaction.payee.clone()),
+                                category_id,
+                                memo: Some(entry.transaction.memo.clone()),
+                                status: if mark_cleared {
+                                    Some(TransactionStatus::Cleared)
+                                } else {
+                                    None
+                                },
+                            };
+
+                            let txn = txn_service.create(input)?;
+                            if splits.is_empty() {
+                                Ok(txn)
+                            } else {
+                                txn_service.set_splits(txn.id, splits)
+                            }
+                        });
+
+                    match created {
                         Ok(mut txn) => {
                             // Set the import ID for duplicate detection
                             txn.import_id = Some(entry.transaction.import_id.clone());
+                            // Flag for review so imports can be swept before clearing
+                            txn.flag = TransactionFlag::Review;
                             self.storage.transactions.upsert(txn.clone())?;
                             result.imported += 1;
                             result.imported_ids.push(txn.id.to_string());
@@ -577,6 +1100,319 @@ impl<'a> ImportService<'a> {
 
         Ok(result)
     }
+
+    /// Import a CSV, OFX, or QFX file in one step
+    ///
+    /// The format and CSV column mapping are detected from the file, so no
+    /// [`ColumnMapping`] is needed. With `dry_run`, the result counts what
+    /// the preview would import, skip, and reject, and nothing is written.
+    pub fn import_file(
+        &self,
+        path: &Path,
+        account_id: AccountId,
+        options: &ImportFileOptions,
+    ) -> EnvelopeResult<ImportResult> {
+        let bytes = std::fs::read(path).map_err(|e| {
+            EnvelopeError::Import(format!("Failed to read {}: {}", path.display(), e))
+        })?;
+        let data = decode_csv_bytes(&bytes, options.encoding.as_deref())?;
+
+        let parsed = if is_ofx(path, &data) {
+            self.parse_ofx_str(&data)?
+        } else {
+            let mapping = self.detect_mapping(&data)?;
+            self.validate_mapping(&data, &mapping)?;
+            self.parse_csv_str(&data, &mapping)?
+        };
+
+        let preview = self.generate_preview(&parsed, account_id)?;
+        if !options.dry_run {
+            return self.import_from_preview(
+                &preview,
+                account_id,
+                options.default_category,
+                options.mark_cleared,
+            );
+        }
+
+        let mut result = ImportResult {
+            imported: 0,
+            duplicates_skipped: 0,
+            errors: 0,
+            imported_ids: Vec::new(),
+            error_messages: HashMap::new(),
+            dry_run: true,
+        };
+        for entry in &preview {
+            match &entry.status {
+                ImportStatus::New => result.imported += 1,
+                ImportStatus::Duplicate => result.duplicates_skipped += 1,
+                ImportStatus::Error(e) => {
+                    result.errors += 1;
+                    result
+                        .error_messages
+                        .insert(entry.transaction.row_number, e.clone());
+                }
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Whether a file is OFX or QFX rather than CSV
+///
+/// Decided by a `.ofx`, `.qfx`, or `.csv` extension, and otherwise by an
+/// OFX header or `<OFX>` element near the start of the data.
+pub fn is_ofx(path: &Path, data: &str) -> bool {
+    let extension = path
+        .extension()
+        .and_then(|e| e.to_str())
+        .map(|e| e.to_ascii_lowercase());
+    match extension.as_deref() {
+        Some("ofx" | "qfx") => true,
+        Some("csv") => false,
+        _ => {
+            let head = data
+                .chars()
+                .take(1024)
+                .collect::<String>()
+                .to_ascii_uppercase();
+            head.contains("OFXHEADER") || head.contains("<OFX>")
+        }
+    }
+}
+
+/// Fold split rows into the transactions they belong to
+///
+/// A split row names its parent in `split_of`. When the parent row is in the
+/// file its amount is kept; when it isn't (a flattened export) the parent is
+/// rebuilt from its splits, summing their amounts.
+fn merge_split_rows(
+    rows: Vec<Result<ParsedTransaction, String>>,
+) -> Vec<Result<ParsedTransaction, String>> {
+    let mut merged: Vec<Result<ParsedTransaction, String>> = Vec::with_capacity(rows.len());
+    let mut parents: HashMap<String, usize> = HashMap::new();
+    let mut rebuilt = Vec::new();
+
+    for row in rows {
+        let txn = match row {
+            Ok(txn) if !txn.split_of.is_empty() => txn,
+            other => {
+                if let Ok(txn) = &other {
+                    if !txn.source_id.is_empty() {
+                        parents.insert(txn.source_id.clone(), merged.len());
+                    }
+                }
+                merged.push(other);
+                continue;
+            }
+        };
+
+        let idx = *parents.entry(txn.split_of.clone()).or_insert_with(|| {
+            rebuilt.push(merged.len());
+            merged.push(Ok(ParsedTransaction {
+                amount: Money::zero(),
+                memo: String::new(),
+                source_id: txn.split_of.clone(),
+                category: String::new(),
+                split_of: String::new(),
+                ..txn.clone()
+            }));
+            merged.len() - 1
+        });
+
+        if let Ok(parent) = &mut merged[idx] {
+            parent.splits.push(ParsedSplit {
+                category: txn.category,
+                amount: txn.amount,
+                memo: txn.memo,
+            });
+        }
+    }
+
+    for idx in rebuilt {
+        if let Ok(parent) = &mut merged[idx] {
+            parent.amount = parent.splits.iter().map(|s| s.amount).sum();
+            parent.import_id =
+                ParsedTransaction::generate_import_id(parent.date, parent.amount, &parent.payee);
+        }
+    }
+
+    merged
+}
+
+/// Parse the body of one `<STMTTRN>` record
+///
+/// `upper` is the ASCII-uppercased copy of `record`, used to find tags
+/// regardless of case while values keep their original spelling.
+fn parse_ofx_record(record: &str, upper: &str, index: usize) -> Result<ParsedTransaction, String> {
+    let field = |tag: &str| ofx_field(record, upper, tag);
+
+    let posted =
+        field("DTPOSTED").ok_or_else(|| format!("Transaction {}: missing DTPOSTED", index + 1))?;
+    let date = parse_ofx_date(&posted).ok_or_else(|| {
+        format!(
+            "Transaction {}: could not parse date '{}'",
+            index + 1,
+            posted
+        )
+    })?;
+
+    let amount_str =
+        field("TRNAMT").ok_or_else(|| format!("Transaction {}: missing TRNAMT", index + 1))?;
+    let amount =
+        parse_amount(&amount_str).map_err(|e| format!("Transaction {}: {}", index + 1, e))?;
+
+    let memo = field("MEMO").unwrap_or_default();
+    let payee = field("NAME").unwrap_or_else(|| memo.clone());
+    let fitid = field("FITID").unwrap_or_default();
+
+    let import_id = if fitid.is_empty() {
+        ParsedTransaction::generate_import_id(date, amount, &payee)
+    } else {
+        fitid.clone()
+    };
+
+    Ok(ParsedTransaction {
+        date,
+        amount,
+        payee,
+        memo,
+        row_number: index,
+        import_id,
+        source_id: fitid,
+        category: String::new(),
+        split_of: String::new(),
+        splits: Vec::new(),
+    })
+}
+
+/// Value of the first `<TAG>` element in an OFX record
+///
+/// The value runs to the next tag or line break, which covers both SGML
+/// elements without closing tags and XML `<TAG>value</TAG>` pairs.
+fn ofx_field(record: &str, upper: &str, tag: &str) -> Option<String> {
+    let open = format!("<{}>", tag);
+    let start = upper.find(&open)? + open.len();
+    let end = record[start..]
+        .find(['<', '\r', '\n'])
+        .map(|pos| start + pos)
+        .unwrap_or(record.len());
+
+    let value = record[start..end]
+        .trim()
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&amp;", "&");
+    (!value.is_empty()).then_some(value)
+}
+
+/// Parse an OFX date such as `20250115`, `20250115120000`, or
+/// `20250115120000.000[-5:EST]`; only the calendar date is kept
+fn parse_ofx_date(s: &str) -> Option<NaiveDate> {
+    let digits = s.get(..8)?;
+    NaiveDate::parse_from_str(digits, "%Y%m%d").ok()
+}
+
+/// Parse a date string using multiple format attempts
+fn parse_date(s: &str, primary_format: &str) -> Result<NaiveDate, String> {
+    // Try primary format first
+    if let Ok(date) = NaiveDate::parse_from_str(s, primary_format) {
+        return Ok(date);
+    }
+
+    // Try common alternative formats
+    let formats = [
+        "%Y-%m-%d", "%m/%d/%Y", "%m/%d/%y", "%d/%m/%Y", "%d/%m/%y", "%Y/%m/%d", "%m-%d-%Y",
+        "%d-%m-%Y",
+    ];
+
+    for format in formats {
+        if let Ok(date) = NaiveDate::parse_from_str(s, format) {
+            return Ok(date);
+        }
+    }
+
+    Err(format!("Could not parse date: '{}'", s))
+}
+
+/// Parse an amount string, handling various formats
+fn parse_amount(s: &str) -> Result<Money, String> {
+    // Remove currency symbols, commas, spaces
+    let cleaned: String = s
+        .chars()
+        .filter(|c| c.is_ascii_digit() || *c == '.' || *c == '-' || *c == '(' || *c == ')')
+        .collect();
+
+    // Handle parentheses as negative (accounting format)
+    let (is_negative, value) = if cleaned.starts_with('(') && cleaned.ends_with(')') {
+        (true, &cleaned[1..cleaned.len() - 1])
+    } else if let Some(stripped) = cleaned.strip_prefix('-') {
+        (true, stripped)
+    } else {
+        (false, cleaned.as_str())
+    };
+
+    Money::parse(value)
+        .map(|m| if is_negative { -m } else { m })
+        .map_err(|e| format!("Could not parse amount '{}': {}", s, e))
+}
+
+/// Decode raw CSV bytes into UTF-8 text
+///
+/// A UTF-8 byte order mark is stripped when present. Without an `encoding`
+/// label the data must be valid UTF-8; otherwise the bytes are transcoded
+/// from the named encoding (e.g., `latin1`, `windows-1252`).
+pub fn decode_csv_bytes(bytes: &[u8], encoding: Option<&str>) -> EnvelopeResult<String> {
+    if let Some((bom_encoding, bom_len)) = encoding_rs::Encoding::for_bom(bytes) {
+        if bom_encoding == encoding_rs::UTF_8 {
+            return String::from_utf8(bytes[bom_len..].to_vec())
+                .map_err(|e| EnvelopeError::Import(format!("Invalid UTF-8 in CSV file: {}", e)));
+        }
+    }
+
+    match encoding {
+        Some(label) => {
+            let encoding =
+                encoding_rs::Encoding::for_label(label.trim().as_bytes()).ok_or_else(|| {
+                    EnvelopeError::Validation(format!("Unknown encoding: '{}'", label))
+                })?;
+            let (text, _, _) = encoding.decode(bytes);
+            Ok(text.into_owned())
+        }
+        None => String::from_utf8(bytes.to_vec()).map_err(|_| {
+            EnvelopeError::Import(
+                "CSV file is not valid UTF-8; pass --encoding (e.g., latin1)".to_string(),
+            )
+        }),
+    }
+}
+
+/// Drop the first `n` CSV records of `data`
+///
+/// Records are read with the same reader settings used for parsing, so line
+/// endings and quoted newlines are handled the same way.
+fn skip_rows(data: &str, n: usize, delimiter: char) -> &str {
+    if n == 0 {
+        return data;
+    }
+
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(false)
+        .delimiter(delimiter as u8)
+        .flexible(true)
+        .from_reader(data.as_bytes());
+    let mut record = csv::ByteRecord::new();
+    for _ in 0..n {
+        if !matches!(reader.read_byte_record(&mut record), Ok(true)) {
+            return "";
+        }
+    }
+
+    let offset = reader.position().byte() as usize;
+    data.get(offset..).unwrap_or("")
 }
 
 #[cfg(test)]
@@ -648,6 +1484,45 @@ mod tests {
         assert_eq!(txn2.amount.cents(), 10000);
     }
 
+    #[test]
+    fn test_credit_card_refund_indicator() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = ImportService::new(&storage);
+
+        // Amex-style export: charges positive, but refunds and payments
+        // appear with either sign and are marked in the Type column
+        let csv_data = "Date,Description,Amount,Type\n\
+                        01/05/2025,AMAZON.COM,45.99,DEBIT\n\
+                        01/07/2025,AMAZON.COM REFUND,45.99,CREDIT\n\
+                        01/09/2025,AUTOPAY PAYMENT,-300.00,Payment\n\
+                        01/10/2025,STARBUCKS,5.25,";
+        let parse = |mapping: &ColumnMapping| -> Vec<i64> {
+            let mut reader = csv::Reader::from_reader(csv_data.as_bytes());
+            service
+                .parse_csv_from_reader(&mut reader, mapping)
+                .unwrap()
+                .into_iter()
+                .map(|r| r.unwrap().amount.cents())
+                .collect()
+        };
+
+        let inverted = ColumnMapping {
+            memo_column: None,
+            ..ColumnMapping::credit_card()
+        };
+        // Without the indicator the refund is flipped into a charge
+        assert_eq!(parse(&inverted), [-4599, -4599, 30000, -525]);
+
+        let mapping = inverted.with_refund_indicator_column(3);
+        assert_eq!(parse(&mapping), [-4599, 4599, 30000, -525]);
+
+        // A Type header is picked up as the indicator column
+        let headers = StringRecord::from(vec!["Date", "Description", "Amount", "Type"]);
+        let detected = service.detect_mapping_from_headers(&headers);
+        assert_eq!(detected.refund_indicator_column, Some(3));
+        assert_eq!(detected.amount_column, Some(2));
+    }
+
     #[test]
     fn test_parse_various_date_formats() {
         let (_temp_dir, storage) = create_test_storage();
@@ -709,6 +1584,112 @@ mod tests {
         assert_eq!(preview2[0].status, ImportStatus::Duplicate);
     }
 
+    #[test]
+    fn test_parse_sgml_ofx() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = ImportService::new(&storage);
+
+        let ofx_data = "OFXHEADER:100\nDATA:OFXSGML\nVERSION:102\n\n<OFX>\n<BANKMSGSRSV1><STMTTRNRS><STMTRS>\n<BANKTRANLIST>\n<STMTTRN>\n<TRNTYPE>DEBIT\n<DTPOSTED>20250115120000.000[-5:EST]\n<TRNAMT>-50.00\n<FITID>2025011501\n<NAME>Corner Store &amp; Deli\n<MEMO>POS purchase\n</STMTTRN>\n<STMTTRN>\n<TRNTYPE>CREDIT\n<DTPOSTED>20250116\n<TRNAMT>1500.00\n<MEMO>Payroll deposit\n</STMTTRN>\n</BANKTRANLIST>\n</STMTRS></STMTTRNRS></BANKMSGSRSV1>\n</OFX>\n";
+
+        let results = service.parse_ofx_from_reader(ofx_data.as_bytes()).unwrap();
+        assert_eq!(results.len(), 2);
+
+        let txn1 = results[0].as_ref().unwrap();
+        assert_eq!(txn1.date, NaiveDate::from_ymd_opt(2025, 1, 15).unwrap());
+        assert_eq!(txn1.amount.cents(), -5000);
+        assert_eq!(txn1.payee, "Corner Store & Deli");
+        assert_eq!(txn1.memo, "POS purchase");
+        assert_eq!(txn1.import_id, "2025011501");
+
+        // No NAME or FITID: the memo is the payee and the ID is hashed
+        let txn2 = results[1].as_ref().unwrap();
+        assert_eq!(txn2.amount.cents(), 150000);
+        assert_eq!(txn2.payee, "Payroll deposit");
+        assert!(txn2.import_id.starts_with("imp-"));
+    }
+
+    #[test]
+    fn test_parse_xml_ofx_and_errors() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = ImportService::new(&storage);
+
+        let ofx_data = r#"<?xml version="1.0"?><?OFX OFXHEADER="200"?><OFX><BANKTRANLIST><STMTTRN><DTPOSTED>20250120</DTPOSTED><TRNAMT>-12.34</TRNAMT><FITID>A1</FITID><NAME>Coffee</NAME></STMTTRN><STMTTRN><TRNAMT>-1.00</TRNAMT></STMTTRN></BANKTRANLIST></OFX>"#;
+
+        let results = service.parse_ofx_str(ofx_data).unwrap();
+        assert_eq!(results.len(), 2);
+
+        let txn = results[0].as_ref().unwrap();
+        assert_eq!(txn.amount.cents(), -1234);
+        assert_eq!(txn.payee, "Coffee");
+        assert_eq!(txn.import_id, "A1");
+        assert!(results[1].as_ref().unwrap_err().contains("DTPOSTED"));
+
+        assert!(service.parse_ofx_str("Date,Amount\n").is_err());
+    }
+
+    #[test]
+    fn test_ofx_reimport_is_exact_duplicate() {
+        let (_temp_dir, storage) = create_test_storage();
+        let account_id = setup_test_account(&storage);
+        let service = ImportService::new(&storage);
+
+        let first = "<OFX><STMTTRN><DTPOSTED>20250115<TRNAMT>-50.00<FITID>X9<NAME>Test Store</STMTTRN></OFX>";
+        let parsed = service.parse_ofx_str(first).unwrap();
+        let preview = service.generate_preview(&parsed, account_id).unwrap();
+        service
+            .import_from_preview(&preview, account_id, None, false)
+            .unwrap();
+
+        // Same FITID with a reworded name still matches by ID
+        let second = "<OFX><STMTTRN><DTPOSTED>20250115<TRNAMT>-50.00<FITID>X9<NAME>TEST STORE #12</STMTTRN></OFX>";
+        let parsed = service.parse_ofx_str(second).unwrap();
+        let preview = service.generate_preview(&parsed, account_id).unwrap();
+        assert_eq!(preview[0].status, ImportStatus::Duplicate);
+        assert!(!preview[0].likely);
+    }
+
+    #[test]
+    fn test_likely_duplicate_within_window() {
+        let (_temp_dir, storage) = create_test_storage();
+        let account_id = setup_test_account(&storage);
+        let service = ImportService::new(&storage);
+        let mapping = ColumnMapping::new();
+
+        let csv_data = "Date,Amount,Description\n2025-01-15,-50.00,TEST STORE #123";
+        let mut reader = csv::Reader::from_reader(csv_data.as_bytes());
+        let parsed = service
+            .parse_csv_from_reader(&mut reader, &mapping)
+            .unwrap();
+        let preview = service.generate_preview(&parsed, account_id).unwrap();
+        service
+            .import_from_preview(&preview, account_id, None, false)
+            .unwrap();
+        let existing_id = storage.transactions.get_all().unwrap()[0].id.to_string();
+
+        // Reworded description a day later, plus a second row of the same
+        // amount that can't claim the same existing transaction
+        let csv_data = "Date,Amount,Description\n\
+                        2025-01-16,-50.00,Test Store\n\
+                        2025-01-16,-50.00,Test Store";
+        let mut reader = csv::Reader::from_reader(csv_data.as_bytes());
+        let parsed = service
+            .parse_csv_from_reader(&mut reader, &mapping)
+            .unwrap();
+        let preview = service.generate_preview(&parsed, account_id).unwrap();
+        assert_eq!(preview[0].status, ImportStatus::Duplicate);
+        assert!(preview[0].likely);
+        assert_eq!(
+            preview[0].existing_id.as_deref(),
+            Some(existing_id.as_str())
+        );
+        assert_eq!(preview[1].status, ImportStatus::New);
+
+        // Outside a zero-day window the reworded row is new
+        let strict = ImportService::new(&storage).with_duplicate_window(0);
+        let preview = strict.generate_preview(&parsed, account_id).unwrap();
+        assert_eq!(preview[0].status, ImportStatus::New);
+    }
+
     #[test]
     fn test_detect_mapping() {
         let (_temp_dir, storage) = create_test_storage();
@@ -729,6 +1710,43 @@ mod tests {
         assert!(mapping.amount_column.is_none());
     }
 
+    #[test]
+    fn test_validate_mapping() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = ImportService::new(&storage);
+        let data = "Date,Description,Amount\n2025-01-15,Coffee Shop,-4.50\n";
+
+        let mapping = ColumnMapping {
+            amount_column: Some(2),
+            payee_column: Some(1),
+            ..ColumnMapping::new()
+        };
+        assert!(service.validate_mapping(data, &mapping).is_ok());
+
+        // Amount pointed at the description column
+        let wrong = ColumnMapping {
+            amount_column: Some(1),
+            ..mapping.clone()
+        };
+        let err = service.validate_mapping(data, &wrong).unwrap_err();
+        assert!(err.to_string().contains("'Description'"));
+
+        // Date pointed at a text column
+        let wrong = ColumnMapping {
+            date_column: 1,
+            ..mapping.clone()
+        };
+        assert!(service.validate_mapping(data, &wrong).is_err());
+
+        // Column beyond the end of the file
+        let wrong = ColumnMapping {
+            memo_column: Some(7),
+            ..mapping
+        };
+        let err = service.validate_mapping(data, &wrong).unwrap_err();
+        assert!(err.to_string().contains("only has 3 columns"));
+    }
+
     #[test]
     fn test_import_result() {
         let (_temp_dir, storage) = create_test_storage();
@@ -752,5 +1770,237 @@ mod tests {
         assert_eq!(result.duplicates_skipped, 0);
         assert_eq!(result.errors, 0);
         assert_eq!(result.imported_ids.len(), 2);
+
+        // Imported transactions start flagged for review
+        let txns = storage.transactions.get_by_account(account_id).unwrap();
+        assert!(txns.iter().all(|t| t.flag == TransactionFlag::Review));
+    }
+
+    #[test]
+    fn test_import_file_detects_mapping() {
+        let (temp_dir, storage) = create_test_storage();
+        let account_id = setup_test_account(&storage);
+        let service = ImportService::new(&storage);
+
+        let path = temp_dir.path().join("statement.csv");
+        std::fs::write(
+            &path,
+            "Account: 1234\nPosted Date,Description,Amount\n01/15/2025,Store 1,-50.00\n01/16/2025,Store 2,-25.00\n",
+        )
+        .unwrap();
+
+        // A dry run counts rows but writes nothing
+        let options = ImportFileOptions {
+            dry_run: true,
+            ..Default::default()
+        };
+        let result = service.import_file(&path, account_id, &options).unwrap();
+        assert!(result.dry_run);
+        assert_eq!(result.imported, 2);
+        assert!(storage
+            .transactions
+            .get_by_account(account_id)
+            .unwrap()
+            .is_empty());
+
+        let options = ImportFileOptions {
+            mark_cleared: true,
+            ..Default::default()
+        };
+        let result = service.import_file(&path, account_id, &options).unwrap();
+        assert_eq!(result.imported, 2);
+        let imported = storage.transactions.get_by_account(account_id).unwrap();
+        assert!(imported
+            .iter()
+            .all(|t| t.status == TransactionStatus::Cleared));
+
+        let result = service.import_file(&path, account_id, &options).unwrap();
+        assert_eq!(result.imported, 0);
+        assert_eq!(result.duplicates_skipped, 2);
+    }
+
+    #[test]
+    fn test_skip_preamble_and_footer() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = ImportService::new(&storage);
+
+        let csv_data = "Account: Checking ****1234\n\
+                        Statement period: January 2025\n\
+                        Date,Amount,Description\n\
+                        2025-01-15,-50.00,Test Store\n\
+                        2025-01-16,100.00,Paycheck\n\
+                        Total,50.00,";
+
+        let preamble = service.detect_preamble_rows(csv_data, ',');
+        assert_eq!(preamble, 2);
+
+        let mapping = ColumnMapping::new()
+            .with_skip_header_rows(preamble)
+            .with_skip_footer_rows(1);
+        let results = service.parse_csv_str(csv_data, &mapping).unwrap();
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap().payee, "Test Store");
+        assert_eq!(results[1].as_ref().unwrap().amount.cents(), 10000);
+    }
+
+    #[test]
+    fn test_headerless_file_with_preamble() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = ImportService::new(&storage);
+
+        let csv_data = "Account: Checking ****1234\n\
+                        Statement period: January 2025\n\
+                        2025-01-15,Test Store,50.00,,950.00\n\
+                        2025-01-16,Paycheck,,100.00,1050.00";
+
+        let preamble = service.detect_preamble_rows(csv_data, ',');
+        assert_eq!(preamble, 2);
+
+        let mapping = service.detect_mapping(csv_data).unwrap();
+        assert!(!mapping.has_header);
+        assert_eq!(mapping.skip_header_rows, 2);
+
+        let results = service.parse_csv_str(csv_data, &mapping).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap().payee, "Test Store");
+        assert_eq!(results[0].as_ref().unwrap().amount.cents(), -5000);
+        assert_eq!(results[1].as_ref().unwrap().amount.cents(), 10000);
+    }
+
+    #[test]
+    fn test_detect_mapping_crlf_semicolon_file() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = ImportService::new(&storage);
+
+        let csv_data = "Account: Checking ****1234\r\n\
+                        \r\n\
+                        Date;Amount;Description\r\n\
+                        2025-01-15;-50.00;\"Test; Store\"\r\n\
+                        2025-01-16;100.00;Paycheck\r\n";
+
+        assert_eq!(service.detect_delimiter(csv_data), ';');
+
+        let mapping = service.detect_mapping(csv_data).unwrap();
+        assert_eq!(mapping.delimiter, ';');
+        assert!(mapping.has_header);
+        assert_eq!(mapping.skip_header_rows, 1);
+
+        let results = service.parse_csv_str(csv_data, &mapping).unwrap();
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].as_ref().unwrap().payee, "Test; Store");
+        assert_eq!(results[0].as_ref().unwrap().amount.cents(), -5000);
+        assert_eq!(results[1].as_ref().unwrap().amount.cents(), 10000);
+    }
+
+    #[test]
+    fn test_skip_invalid_dates() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = ImportService::new(&storage);
+
+        let csv_data = "Date,Amount,Description\n2025-01-15,-50.00,Store\nTotal,-50.00,";
+
+        let mapping = ColumnMapping::new();
+        let results = service.parse_csv_str(csv_data, &mapping).unwrap();
+        assert_eq!(results.len(), 2);
+        assert!(results[1].is_err());
+
+        let mapping = ColumnMapping::new().with_skip_invalid_dates(true);
+        let results = service.parse_csv_str(csv_data, &mapping).unwrap();
+        assert_eq!(results.len(), 1);
+        assert!(results[0].is_ok());
+    }
+
+    #[test]
+    fn test_import_rebuilds_exported_splits() {
+        use crate::export::{export_transactions_csv_with_splits, SplitRows};
+        use crate::models::{Category, CategoryGroup, Transaction};
+
+        // A fresh budget per mode, so one pass doesn't export the other's import
+        for mode in [SplitRows::Child, SplitRows::Flatten] {
+            let (_temp_dir, storage) = create_test_storage();
+            let service = ImportService::new(&storage);
+            let source = setup_test_account(&storage);
+
+            let group = CategoryGroup::new("Everyday");
+            storage.categories.upsert_group(group.clone()).unwrap();
+            let groceries = Category::new("Groceries", group.id);
+            let household = Category::new("Household", group.id);
+            storage
+                .categories
+                .upsert_category(groceries.clone())
+                .unwrap();
+            storage
+                .categories
+                .upsert_category(household.clone())
+                .unwrap();
+
+            let mut txn = Transaction::new(
+                source,
+                NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+                Money::from_cents(-8000),
+            );
+            txn.payee_name = "Market".to_string();
+            txn.add_split(Split::new(groceries.id, Money::from_cents(-5000)));
+            txn.add_split(Split::with_memo(
+                household.id,
+                Money::from_cents(-3000),
+                "Soap",
+            ));
+            storage.transactions.upsert(txn).unwrap();
+
+            let mut output = Vec::new();
+            export_transactions_csv_with_splits(&storage, &mut output, mode).unwrap();
+            let data = String::from_utf8(output).unwrap();
+
+            let target = Account::new(format!("{:?}", mode), AccountType::Checking);
+            let target_id = target.id;
+            storage.accounts.upsert(target).unwrap();
+
+            let mapping = service.detect_mapping(&data).unwrap();
+            let parsed = service.parse_csv_str(&data, &mapping).unwrap();
+            assert_eq!(parsed.len(), 1, "{:?}", mode);
+
+            let preview = service.generate_preview(&parsed, target_id).unwrap();
+            let result = service
+                .import_from_preview(&preview, target_id, None, false)
+                .unwrap();
+            assert_eq!(result.imported, 1, "{:?}", mode);
+
+            let imported = &storage.transactions.get_by_account(target_id).unwrap()[0];
+            assert_eq!(imported.amount.cents(), -8000);
+            assert_eq!(imported.splits.len(), 2);
+            assert_eq!(imported.splits[0].category_id, Some(groceries.id));
+            assert_eq!(imported.splits[1].category_id, Some(household.id));
+            assert_eq!(imported.splits[1].memo, "Soap");
+        }
+    }
+
+    #[test]
+    fn test_import_latin1_payee() {
+        let (_temp_dir, storage) = create_test_storage();
+        let account_id = setup_test_account(&storage);
+        let service = ImportService::new(&storage);
+
+        let bytes = b"Date,Amount,Description\n2025-01-15,-12.50,Caf\xe9 Ren\xe9e\n";
+        assert!(decode_csv_bytes(bytes, None).is_err());
+
+        let data = decode_csv_bytes(bytes, Some("latin1")).unwrap();
+        let mapping = service.detect_mapping(&data).unwrap();
+        let parsed = service.parse_csv_str(&data, &mapping).unwrap();
+        let preview = service.generate_preview(&parsed, account_id).unwrap();
+        service
+            .import_from_preview(&preview, account_id, None, false)
+            .unwrap();
+
+        let imported = &storage.transactions.get_by_account(account_id).unwrap()[0];
+        assert_eq!(imported.payee_name, "Café Renée");
+    }
+
+    #[test]
+    fn test_decode_strips_utf8_bom() {
+        let data = decode_csv_bytes(b"\xef\xbb\xbfDate,Amount\n", None).unwrap();
+        assert_eq!(data, "Date,Amount\n");
+        assert!(decode_csv_bytes(b"Date", Some("klingon")).is_err());
     }
 }
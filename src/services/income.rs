@@ -5,13 +5,26 @@
 use crate::audit::EntityType;
 use crate::error::{EnvelopeError, EnvelopeResult};
 use crate::models::{BudgetPeriod, IncomeExpectation, Money};
+use crate::services::BudgetService;
 use crate::storage::Storage;
+use std::collections::BTreeMap;
 
 /// Service for income expectation management
 pub struct IncomeService<'a> {
     storage: &'a Storage,
 }
 
+/// Income received from one source in a period
+#[derive(Debug, Clone)]
+pub struct IncomeSourceTotal {
+    /// The source, or None for income without one
+    pub source: Option<String>,
+    /// Total received
+    pub amount: Money,
+    /// Number of transactions
+    pub count: usize,
+}
+
 impl<'a> IncomeService<'a> {
     /// Create a new income service
     pub fn new(storage: &'a Storage) -> Self {
@@ -100,6 +113,8 @@ impl<'a> IncomeService<'a> {
 
     /// Delete income expectation for a period
     pub fn delete_expected_income(&self, period: &BudgetPeriod) -> EnvelopeResult<bool> {
+        self.storage.check_writable()?;
+
         if let Some(removed) = self.storage.income.delete_for_period(period) {
             self.storage.income.save()?;
 
@@ -121,6 +136,65 @@ impl<'a> IncomeService<'a> {
     pub fn get_all_expectations(&self) -> EnvelopeResult<Vec<IncomeExpectation>> {
         self.storage.income.get_all()
     }
+
+    /// Get expected income for a period that hasn't arrived yet
+    ///
+    /// The expectation minus income already received in the period, never
+    /// negative. Zero when no expectation is set.
+    pub fn get_upcoming_income(&self, period: &BudgetPeriod) -> EnvelopeResult<Money> {
+        let Some(expected) = self.get_expected_income(period) else {
+            return Ok(Money::zero());
+        };
+
+        let received = BudgetService::new(self.storage).calculate_income_for_period(period)?;
+        Ok((expected - received).max(Money::zero()))
+    }
+
+    /// Total income in a period grouped by income source
+    ///
+    /// Counts inflows other than transfers and starting balances. Sources
+    /// are ordered largest first, with income that has no source last.
+    pub fn by_source(&self, period: &BudgetPeriod) -> EnvelopeResult<Vec<IncomeSourceTotal>> {
+        let transactions = self
+            .storage
+            .transactions
+            .get_by_date_range(period.start_date(), period.end_date())?;
+
+        let mut totals: BTreeMap<Option<String>, IncomeSourceTotal> = BTreeMap::new();
+        for txn in transactions {
+            if !txn.amount.is_positive() || txn.is_transfer() || txn.is_starting_balance {
+                continue;
+            }
+            let total =
+                totals
+                    .entry(txn.income_source.clone())
+                    .or_insert_with(|| IncomeSourceTotal {
+                        source: txn.income_source.clone(),
+                        amount: Money::zero(),
+                        count: 0,
+                    });
+            total.amount += txn.amount;
+            total.count += 1;
+        }
+
+        let mut totals: Vec<IncomeSourceTotal> = totals.into_values().collect();
+        totals.sort_by(|a, b| {
+            a.source
+                .is_none()
+                .cmp(&b.source.is_none())
+                .then(b.amount.cmp(&a.amount))
+        });
+        Ok(totals)
+    }
+
+    /// Project Available to Budget with income still expected this period
+    ///
+    /// An opt-in figure for budgeting money that is known to be coming.
+    /// Available to Budget itself stays cash-based.
+    pub fn project_available_to_budget(&self, period: &BudgetPeriod) -> EnvelopeResult<Money> {
+        let available = BudgetService::new(self.storage).get_available_to_budget(period)?;
+        Ok(available + self.get_upcoming_income(period)?)
+    }
 }
 
 #[cfg(test)]
@@ -225,4 +299,120 @@ mod tests {
         let result = service.set_expected_income(&period, Money::from_cents(-100), None);
         assert!(result.is_err());
     }
+
+    #[test]
+    fn test_project_available_to_budget() {
+        use crate::models::{Account, AccountType, Transaction};
+        use chrono::NaiveDate;
+
+        let (_temp_dir, storage) = create_test_storage();
+        let service = IncomeService::new(&storage);
+        let period = BudgetPeriod::monthly(2025, 1);
+
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+        let paycheck = Transaction::new(
+            account.id,
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            Money::from_cents(100000),
+        );
+        storage.transactions.upsert(paycheck).unwrap();
+
+        // Without an expectation the projection is the cash figure
+        assert_eq!(
+            service.project_available_to_budget(&period).unwrap(),
+            Money::from_cents(100000)
+        );
+
+        // $3,000 expected, $1,000 already received
+        service
+            .set_expected_income(&period, Money::from_cents(300000), None)
+            .unwrap();
+        assert_eq!(
+            service.get_upcoming_income(&period).unwrap(),
+            Money::from_cents(200000)
+        );
+        assert_eq!(
+            service.project_available_to_budget(&period).unwrap(),
+            Money::from_cents(300000)
+        );
+
+        // Receiving more than expected never projects less than cash
+        service
+            .set_expected_income(&period, Money::from_cents(50000), None)
+            .unwrap();
+        assert!(service.get_upcoming_income(&period).unwrap().is_zero());
+    }
+
+    #[test]
+    fn test_income_by_source() {
+        use crate::models::{Account, AccountType, Transaction};
+        use crate::services::{TransactionService, TransferService};
+        use chrono::NaiveDate;
+
+        let (_temp_dir, storage) = create_test_storage();
+        let service = IncomeService::new(&storage);
+        let transaction_service = TransactionService::new(&storage);
+        let date = NaiveDate::from_ymd_opt(2024, 3, 15).unwrap();
+
+        let checking = Account::new("Checking", AccountType::Checking);
+        let savings = Account::new("Savings", AccountType::Savings);
+        storage.accounts.upsert(checking.clone()).unwrap();
+        storage.accounts.upsert(savings.clone()).unwrap();
+
+        for (cents, source) in [
+            (300000, Some("Salary")),
+            (300000, Some("salary")),
+            (1500, Some("interest")),
+            (2000, None),
+            (-5000, None),
+        ] {
+            let txn = Transaction::new(checking.id, date, Money::from_cents(cents));
+            storage.transactions.upsert(txn.clone()).unwrap();
+            if source.is_some() {
+                transaction_service
+                    .set_income_source(txn.id, source)
+                    .unwrap();
+            }
+        }
+
+        // A transfer in isn't income, and can't be given a source
+        let transfer = TransferService::new(&storage)
+            .create_transfer(
+                checking.id,
+                savings.id,
+                Money::from_cents(10000),
+                date,
+                None,
+            )
+            .unwrap();
+        assert!(transaction_service
+            .set_income_source(transfer.to_transaction.id, Some("salary"))
+            .is_err());
+
+        let year = BudgetPeriod::custom(
+            NaiveDate::from_ymd_opt(2024, 1, 1).unwrap(),
+            NaiveDate::from_ymd_opt(2024, 12, 31).unwrap(),
+        );
+        let totals = service.by_source(&year).unwrap();
+        let summary: Vec<(Option<&str>, i64, usize)> = totals
+            .iter()
+            .map(|t| (t.source.as_deref(), t.amount.cents(), t.count))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                (Some("salary"), 600000, 2),
+                (Some("interest"), 1500, 1),
+                (None, 2000, 1),
+            ]
+        );
+
+        assert_eq!(
+            BudgetService::new(&storage)
+                .calculate_income_for_period(&year)
+                .unwrap(),
+            Money::from_cents(603500)
+        );
+    }
 }
@@ -6,25 +6,31 @@
 pub mod account;
 pub mod budget;
 pub mod category;
+pub mod doctor;
 pub mod import;
 pub mod income;
 pub mod payee;
 pub mod period;
 pub mod reconciliation;
+pub mod scheduled;
 pub mod transaction;
 pub mod transfer;
 
 pub use account::AccountService;
 pub use budget::BudgetService;
-pub use category::CategoryService;
+pub use category::{CategoryService, DuplicateNames};
+pub use doctor::{DoctorReport, DoctorService, Finding, Severity};
 pub use import::{
-    ColumnMapping, ImportPreviewEntry, ImportResult, ImportService, ImportStatus, ParsedTransaction,
+    decode_csv_bytes, is_ofx, ColumnMapping, ImportFileOptions, ImportPreviewEntry, ImportResult,
+    ImportService, ImportStatus, ParsedSplit, ParsedTransaction,
 };
-pub use income::IncomeService;
-pub use payee::PayeeService;
+pub use income::{IncomeService, IncomeSourceTotal};
+pub use payee::{suggestion_score, PayeeService, PayeeUsage};
 pub use period::PeriodService;
 pub use reconciliation::{
-    ReconciliationResult, ReconciliationService, ReconciliationSession, ReconciliationSummary,
+    ReconciliationRecord, ReconciliationResult, ReconciliationService, ReconciliationSession,
+    ReconciliationSummary,
 };
+pub use scheduled::{CreateScheduledInput, MaterializeResult, ScheduledService};
 pub use transaction::{CreateTransactionInput, TransactionFilter, TransactionService};
 pub use transfer::TransferService;
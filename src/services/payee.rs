@@ -3,9 +3,14 @@
 //! Provides business logic for payee management including auto-suggestion,
 //! category learning, and fuzzy matching.
 
+use std::collections::HashMap;
+
+use chrono::NaiveDate;
+
 use crate::audit::EntityType;
+use crate::config::clock;
 use crate::error::{EnvelopeError, EnvelopeResult};
-use crate::models::{CategoryId, Payee, PayeeId};
+use crate::models::{CategoryId, Payee, PayeeId, Transaction};
 use crate::storage::Storage;
 
 /// Service for payee management
@@ -13,6 +18,51 @@ pub struct PayeeService<'a> {
     storage: &'a Storage,
 }
 
+/// How often and how recently a payee has been used
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct PayeeUsage {
+    /// Number of transactions with this payee
+    pub count: usize,
+    /// Date of the most recent transaction with this payee
+    pub last_used: Option<NaiveDate>,
+}
+
+/// Score used to rank payee suggestions, higher first
+///
+/// Blends how often a payee is used (with diminishing returns) with how
+/// recently: the recency boost is 2.0 for a payee used today and halves every
+/// 30 days.
+pub fn suggestion_score(usage: PayeeUsage, today: NaiveDate) -> f64 {
+    let frequency = (1.0 + usage.count as f64).ln();
+    let recency = usage.last_used.map_or(0.0, |last| {
+        let days = (today - last).num_days().max(0) as f64;
+        2.0 * 0.5_f64.powf(days / 30.0)
+    });
+    frequency + recency
+}
+
+/// How well a payee name matches what was typed, or None for no match
+///
+/// A prefix beats the start of a later word, which beats a match anywhere,
+/// which beats the letters merely appearing in order.
+fn match_rank(name: &str, query: &str) -> Option<u8> {
+    if query.is_empty() || name.starts_with(query) {
+        return Some(3);
+    }
+    if name
+        .split(|c: char| !c.is_alphanumeric())
+        .any(|word| word.starts_with(query))
+    {
+        return Some(2);
+    }
+    if name.contains(query) {
+        return Some(1);
+    }
+
+    let mut chars = name.chars();
+    query.chars().all(|q| chars.any(|c| c == q)).then_some(0)
+}
+
 impl<'a> PayeeService<'a> {
     /// Create a new payee service
     pub fn new(storage: &'a Storage) -> Self {
@@ -153,6 +203,58 @@ impl<'a> PayeeService<'a> {
         self.storage.payees.search(partial, 10)
     }
 
+    /// Suggest payees for what has been typed so far, for autocomplete
+    ///
+    /// Payees whose name starts with `prefix` come first, then those with a
+    /// later word starting with it, then looser matches. Within each group
+    /// payees are ranked by [`suggestion_score`], then by name. An empty
+    /// prefix suggests the most used payees.
+    pub fn suggest_payees(&self, prefix: &str, limit: usize) -> EnvelopeResult<Vec<Payee>> {
+        let query = Payee::normalize_name(prefix);
+        let today = clock::today();
+        let usage = self.usage()?;
+
+        let mut ranked: Vec<(u8, f64, Payee)> = self
+            .storage
+            .payees
+            .get_all()?
+            .into_iter()
+            .filter_map(|payee| {
+                let name = Payee::normalize_name(&payee.name);
+                let rank = match_rank(&name, &query)?;
+                let score = suggestion_score(usage.get(&name).copied().unwrap_or_default(), today);
+                Some((rank, score, payee))
+            })
+            .collect();
+
+        ranked.sort_by(|a, b| {
+            b.0.cmp(&a.0)
+                .then(b.1.total_cmp(&a.1))
+                .then_with(|| a.2.name.to_lowercase().cmp(&b.2.name.to_lowercase()))
+        });
+        Ok(ranked
+            .into_iter()
+            .take(limit)
+            .map(|(_, _, payee)| payee)
+            .collect())
+    }
+
+    /// Usage of every payee name (normalized) across all transactions
+    fn usage(&self) -> EnvelopeResult<HashMap<String, PayeeUsage>> {
+        let mut usage: HashMap<String, PayeeUsage> = HashMap::new();
+        for txn in self.storage.transactions.get_all()? {
+            if txn.payee_name.trim().is_empty() {
+                continue;
+            }
+            let entry = usage
+                .entry(Payee::normalize_name(&txn.payee_name))
+                .or_default();
+            entry.count += 1;
+            entry.last_used = entry.last_used.max(Some(txn.date));
+        }
+        Ok(usage)
+    }
+
     /// Get the suggested category for a payee
     pub fn get_suggested_category(&self, payee_name: &str) -> EnvelopeResult<Option<CategoryId>> {
         if let Some(payee) = self.storage.payees.get_by_name(payee_name)? {
@@ -271,6 +373,10 @@ impl<'a> PayeeService<'a> {
     }
 
     /// Rename a payee
+    ///
+    /// Transactions store the payee's name alongside its ID, so every
+    /// transaction linked to the payee is rewritten to the new name too.
+    /// Renaming onto another payee's name is rejected; use [`Self::merge`].
     pub fn rename(&self, id: PayeeId, new_name: &str) -> EnvelopeResult<Payee> {
         let new_name = new_name.trim();
         if new_name.is_empty() {
@@ -297,7 +403,7 @@ impl<'a> PayeeService<'a> {
 
         let before = payee.clone();
         payee.name = new_name.to_string();
-        payee.updated_at = chrono::Utc::now();
+        payee.updated_at = clock::now();
 
         // Validate
         payee
@@ -318,9 +424,139 @@ impl<'a> PayeeService<'a> {
             Some(format!("name: '{}' -> '{}'", before.name, payee.name)),
         )?;
 
+        for (before, txn) in self.relink_transactions(&[id], &payee)? {
+            self.storage.log_update(
+                EntityType::Transaction,
+                txn.id.to_string(),
+                Some(format!("{} {}", txn.date, txn.payee_name)),
+                &before,
+                &txn,
+                Some(format!(
+                    "payee: '{}' -> '{}'",
+                    before.payee_name, txn.payee_name
+                )),
+            )?;
+        }
+
         Ok(payee)
     }
 
+    /// Merge the `sources` payees into `target`, then delete the sources
+    ///
+    /// Transactions move to the target payee and take its name. The target
+    /// keeps its default category, falling back to the first source's, and
+    /// adds up category usage so suggestions survive. The whole merge is one
+    /// audit entry on the target. Returns the number of transactions moved.
+    pub fn merge(&self, sources: Vec<PayeeId>, target: PayeeId) -> EnvelopeResult<usize> {
+        if sources.is_empty() {
+            return Err(EnvelopeError::Validation("No payees given to merge".into()));
+        }
+        if sources.contains(&target) {
+            return Err(EnvelopeError::Validation(
+                "Cannot merge a payee into itself".into(),
+            ));
+        }
+
+        let mut target = self
+            .storage
+            .payees
+            .get(target)?
+            .ok_or_else(|| EnvelopeError::payee_not_found(target.to_string()))?;
+
+        let mut merged: Vec<Payee> = Vec::with_capacity(sources.len());
+        for id in sources {
+            if merged.iter().any(|p| p.id == id) {
+                continue;
+            }
+            let source = self
+                .storage
+                .payees
+                .get(id)?
+                .ok_or_else(|| EnvelopeError::payee_not_found(id.to_string()))?;
+            merged.push(source);
+        }
+
+        let before = target.clone();
+        for source in &merged {
+            for (category_id, count) in &source.category_frequency {
+                *target.category_frequency.entry(*category_id).or_insert(0) += count;
+            }
+            if target.default_category_id.is_none() {
+                target.default_category_id = source.default_category_id;
+            }
+            target.manual |= source.manual;
+        }
+        target.updated_at = clock::now();
+
+        for source in &merged {
+            self.storage.payees.delete(source.id)?;
+        }
+        self.storage.payees.upsert(target.clone())?;
+        self.storage.payees.save()?;
+
+        let source_ids: Vec<PayeeId> = merged.iter().map(|p| p.id).collect();
+        let moved = self.relink_transactions(&source_ids, &target)?.len();
+
+        let names: Vec<String> = merged.iter().map(|p| format!("'{}'", p.name)).collect();
+        self.storage.log_update(
+            EntityType::Payee,
+            target.id.to_string(),
+            Some(target.name.clone()),
+            &before,
+            &target,
+            Some(format!(
+                "merged {} ({} transaction(s) moved)",
+                names.join(", "),
+                moved
+            )),
+        )?;
+
+        Ok(moved)
+    }
+
+    /// Count the transactions linked to a payee
+    pub fn transaction_count(&self, id: PayeeId) -> EnvelopeResult<usize> {
+        Ok(self
+            .storage
+            .transactions
+            .get_all()?
+            .iter()
+            .filter(|t| t.payee_id == Some(id))
+            .count())
+    }
+
+    /// Point every transaction linked to one of `from` at `payee`
+    ///
+    /// Returns each changed transaction before and after, for audit logging.
+    fn relink_transactions(
+        &self,
+        from: &[PayeeId],
+        payee: &Payee,
+    ) -> EnvelopeResult<Vec<(Transaction, Transaction)>> {
+        let mut relinked = Vec::new();
+        for mut txn in self.storage.transactions.get_all()? {
+            if !txn.payee_id.is_some_and(|id| from.contains(&id)) {
+                continue;
+            }
+            if txn.payee_id == Some(payee.id) && txn.payee_name == payee.name {
+                continue;
+            }
+
+            let before = txn.clone();
+            txn.payee_id = Some(payee.id);
+            txn.payee_name = payee.name.clone();
+            txn.updated_at = clock::now();
+            self.storage.transactions.upsert(txn.clone())?;
+            relinked.push((before, txn));
+        }
+
+        if !relinked.is_empty() {
+            self.storage.transactions.save()?;
+        }
+
+        Ok(relinked)
+    }
+
     /// Count payees
     pub fn count(&self) -> EnvelopeResult<usize> {
         self.storage.payees.count()
@@ -331,7 +567,8 @@ impl<'a> PayeeService<'a> {
 mod tests {
     use super::*;
     use crate::config::paths::EnvelopePaths;
-    use crate::models::{Category, CategoryGroup};
+    use crate::models::{AccountId, Category, CategoryGroup, Money};
+    use chrono::NaiveDate;
     use tempfile::TempDir;
 
     fn create_test_storage() -> (TempDir, Storage) {
@@ -402,6 +639,107 @@ mod tests {
         assert_eq!(results[0].name, "Grocery Store");
     }
 
+    #[test]
+    fn test_suggestion_score_ranking() {
+        let today = NaiveDate::from_ymd_opt(2025, 6, 30).unwrap();
+        let days_ago = |n| Some(today - chrono::Duration::days(n));
+
+        let old_favorite = PayeeUsage {
+            count: 20,
+            last_used: days_ago(365),
+        };
+        let recent_regular = PayeeUsage {
+            count: 3,
+            last_used: days_ago(1),
+        };
+        let one_off_today = PayeeUsage {
+            count: 1,
+            last_used: days_ago(0),
+        };
+        let unused = PayeeUsage::default();
+
+        let mut scored = [
+            ("old favorite", suggestion_score(old_favorite, today)),
+            ("unused", suggestion_score(unused, today)),
+            ("one-off today", suggestion_score(one_off_today, today)),
+            ("recent regular", suggestion_score(recent_regular, today)),
+        ];
+        scored.sort_by(|a, b| b.1.total_cmp(&a.1));
+        let order: Vec<&str> = scored.iter().map(|(name, _)| *name).collect();
+
+        assert_eq!(
+            order,
+            ["recent regular", "old favorite", "one-off today", "unused"]
+        );
+        assert_eq!(suggestion_score(unused, today), 0.0);
+    }
+
+    #[test]
+    fn test_suggest_payees() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = PayeeService::new(&storage);
+        let today = NaiveDate::from_ymd_opt(2025, 6, 30).unwrap();
+        clock::set_clock(clock::FixedClock::on(today));
+
+        service.create("Wallace Hardware").unwrap();
+        service.create("Walmart").unwrap();
+        service.create("Sam's Wallpaper").unwrap();
+        service.create("Kowalski Deli").unwrap();
+        service.create("Target").unwrap();
+
+        let account_id = AccountId::new();
+        let uses = [
+            ("Walmart", 2),
+            ("Walmart", 9),
+            ("Walmart", 20),
+            ("Wallace Hardware", 200),
+            ("Target", 1),
+        ];
+        for (payee, days_ago) in uses {
+            let txn = Transaction::with_details(
+                account_id,
+                today - chrono::Duration::days(days_ago),
+                Money::from_cents(-1000),
+                payee,
+                None,
+                "",
+            );
+            storage.transactions.upsert(txn).unwrap();
+        }
+
+        let names = |prefix: &str| -> Vec<String> {
+            service
+                .suggest_payees(prefix, 10)
+                .unwrap()
+                .into_iter()
+                .map(|p| p.name)
+                .collect()
+        };
+
+        // Prefix matches first, ranked by usage, then word starts, then anywhere
+        assert_eq!(
+            names("wal"),
+            [
+                "Walmart",
+                "Wallace Hardware",
+                "Sam's Wallpaper",
+                "Kowalski Deli"
+            ]
+        );
+        assert_eq!(names("WAL")[0], "Walmart");
+        assert_eq!(names("tgt"), ["Target"]);
+        assert!(names("xyz").is_empty());
+
+        // Nothing typed: a recent one-off outranks an old, rarely used payee
+        let all = service.suggest_payees("", 3).unwrap();
+        assert_eq!(all.len(), 3);
+        assert_eq!(all[0].name, "Walmart");
+        assert_eq!(all[1].name, "Target");
+        assert_eq!(all[2].name, "Wallace Hardware");
+
+        clock::reset_clock();
+    }
+
     #[test]
     fn test_category_learning() {
         let (_temp_dir, storage) = create_test_storage();
@@ -461,4 +799,90 @@ mod tests {
         assert!(service.get_by_name("Old Name").unwrap().is_none());
         assert!(service.get_by_name("New Name").unwrap().is_some());
     }
+
+    fn add_transaction(storage: &Storage, payee: &Payee) -> Transaction {
+        let mut txn = Transaction::new(
+            AccountId::new(),
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            Money::from_cents(-4200),
+        );
+        txn.payee_id = Some(payee.id);
+        txn.payee_name = payee.name.clone();
+        storage.transactions.upsert(txn.clone()).unwrap();
+        txn
+    }
+
+    #[test]
+    fn test_rename_relinks_transactions() {
+        let (_temp_dir, storage) = create_test_storage();
+        let service = PayeeService::new(&storage);
+
+        let payee = service.create("WALMART #1234").unwrap();
+        let txn = add_transaction(&storage, &payee);
+        service.create("Walmart").unwrap();
+
+        // Colliding with another payee needs a merge
+        assert!(matches!(
+            service.rename(payee.id, "walmart"),
+            Err(EnvelopeError::Duplicate { .. })
+        ));
+
+        service.rename(payee.id, "Walmart Supercenter").unwrap();
+        let txn = storage.transactions.get(txn.id).unwrap().unwrap();
+        assert_eq!(txn.payee_name, "Walmart Supercenter");
+        assert_eq!(txn.payee_id, Some(payee.id));
+    }
+
+    #[test]
+    fn test_merge_payees() {
+        let (_temp_dir, storage) = create_test_storage();
+        let category_id = setup_test_category(&storage);
+        let service = PayeeService::new(&storage);
+
+        let source = service
+            .create_with_category("WALMART #1234", category_id)
+            .unwrap();
+        let target = service.create("Walmart").unwrap();
+        let txn = add_transaction(&storage, &source);
+
+        assert_eq!(service.merge(vec![source.id], target.id).unwrap(), 1);
+
+        let txn = storage.transactions.get(txn.id).unwrap().unwrap();
+        assert_eq!(txn.payee_id, Some(target.id));
+        assert_eq!(txn.payee_name, "Walmart");
+        assert!(service.get(source.id).unwrap().is_none());
+        let target = service.get(target.id).unwrap().unwrap();
+        assert_eq!(target.default_category_id, Some(category_id));
+    }
+
+    #[test]
+    fn test_merge_sums_category_usage() {
+        let (_temp_dir, storage) = create_test_storage();
+        let category_id = setup_test_category(&storage);
+        let service = PayeeService::new(&storage);
+
+        let target = service.create("Walmart").unwrap();
+        let first = service.create("WALMART #1234").unwrap();
+        let second = service.create("WAL-MART STORE").unwrap();
+        for payee in [&target, &first, &first, &second] {
+            service
+                .record_category_usage(payee.id, category_id)
+                .unwrap();
+        }
+        add_transaction(&storage, &first);
+        add_transaction(&storage, &second);
+
+        let moved = service.merge(vec![first.id, second.id], target.id).unwrap();
+        assert_eq!(moved, 2);
+
+        let target = service.get(target.id).unwrap().unwrap();
+        assert_eq!(target.category_frequency.get(&category_id), Some(&4));
+        assert_eq!(service.count().unwrap(), 1);
+        assert_eq!(service.transaction_count(target.id).unwrap(), 2);
+
+        assert!(matches!(
+            service.merge(vec![target.id], target.id),
+            Err(EnvelopeError::Validation(_))
+        ));
+    }
 }
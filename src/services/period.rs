@@ -3,10 +3,11 @@
 //! Provides period management including navigation, validation, and
 //! period-specific operations.
 
+use crate::config::clock;
 use crate::config::settings::{BudgetPeriodType, Settings};
 use crate::error::{EnvelopeError, EnvelopeResult};
 use crate::models::BudgetPeriod;
-use chrono::{Datelike, Duration, Local, NaiveDate};
+use chrono::{Datelike, Duration, NaiveDate};
 
 /// Service for budget period management
 pub struct PeriodService<'a> {
@@ -21,7 +22,7 @@ impl<'a> PeriodService<'a> {
 
     /// Get the current period based on user preferences
     pub fn current_period(&self) -> BudgetPeriod {
-        let today = Local::now().date_naive();
+        let today = clock::today();
         self.period_for_date(today)
     }
 
@@ -136,7 +137,7 @@ impl<'a> PeriodService<'a> {
                 let rest = stripped.trim();
                 let year = if rest.is_empty() {
                     // Use current year, or previous year if month is in the future
-                    let today = Local::now().date_naive();
+                    let today = clock::today();
                     if month > today.month() {
                         today.year() - 1
                     } else {
@@ -243,7 +244,7 @@ mod tests {
         let service = PeriodService::new(&settings);
 
         let period = service.current_period();
-        let today = Local::now().date_naive();
+        let today = clock::today();
 
         // Should be a monthly period containing today
         assert!(period.contains(today));
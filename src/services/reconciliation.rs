@@ -4,11 +4,13 @@
 //! starting reconciliation, calculating differences, completing reconciliation,
 //! and creating adjustment transactions.
 
-use chrono::NaiveDate;
+use chrono::{DateTime, NaiveDate, Utc};
 
-use crate::audit::EntityType;
+use crate::audit::{EntityType, Operation};
 use crate::error::{EnvelopeError, EnvelopeResult};
-use crate::models::{AccountId, CategoryId, Money, Transaction, TransactionId, TransactionStatus};
+use crate::models::{
+    Account, AccountId, CategoryId, Money, Transaction, TransactionId, TransactionStatus,
+};
 use crate::storage::Storage;
 
 /// Service for reconciliation operations
@@ -57,6 +59,17 @@ pub struct ReconciliationResult {
     pub adjustment_amount: Option<Money>,
 }
 
+/// A past reconciliation, reconstructed from the audit log
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReconciliationRecord {
+    /// When the reconciliation was completed
+    pub completed_at: DateTime<Utc>,
+    /// Statement date
+    pub statement_date: NaiveDate,
+    /// Statement ending balance
+    pub statement_balance: Money,
+}
+
 impl<'a> ReconciliationService<'a> {
     /// Create a new reconciliation service
     pub fn new(storage: &'a Storage) -> Self {
@@ -330,48 +343,128 @@ impl<'a> ReconciliationService<'a> {
         Ok(txn)
     }
 
+    /// Absorb a small difference into a single transaction
+    ///
+    /// For when the bank applied a fee or rounding the transaction doesn't
+    /// show. The transaction's amount is changed by whatever difference
+    /// would remain once it's cleared, and it is marked cleared, so the
+    /// reconciliation balances without a separate adjustment transaction.
+    /// The change must be no larger than `max_adjustment`.
+    pub fn adjust_to_match(
+        &self,
+        session: &ReconciliationSession,
+        transaction_id: TransactionId,
+        max_adjustment: Money,
+    ) -> EnvelopeResult<Transaction> {
+        let mut txn = self
+            .storage
+            .transactions
+            .get(transaction_id)?
+            .ok_or_else(|| EnvelopeError::transaction_not_found(transaction_id.to_string()))?;
+
+        if txn.account_id != session.account_id {
+            return Err(EnvelopeError::Reconciliation(
+                "Transaction belongs to a different account".into(),
+            ));
+        }
+        if txn.status == TransactionStatus::Reconciled {
+            return Err(EnvelopeError::Locked(format!(
+                "Transaction {} is already reconciled",
+                txn.id
+            )));
+        }
+        if txn.is_split() || txn.is_transfer() || txn.is_starting_balance {
+            return Err(EnvelopeError::Reconciliation(
+                "Split, transfer, and starting balance transactions can't absorb a difference"
+                    .into(),
+            ));
+        }
+
+        // A pending transaction counts toward the cleared balance once cleared
+        let mut adjustment = self.get_difference(session)?;
+        if txn.status == TransactionStatus::Pending {
+            adjustment -= txn.amount;
+        }
+
+        if adjustment.is_zero() {
+            return Err(EnvelopeError::Reconciliation(
+                "Nothing to adjust: clearing this transaction already balances".into(),
+            ));
+        }
+        if adjustment.abs() > max_adjustment {
+            return Err(EnvelopeError::Reconciliation(format!(
+                "Adjustment of {} exceeds the maximum of {}",
+                adjustment, max_adjustment
+            )));
+        }
+
+        let before = txn.clone();
+        txn.amount += adjustment;
+        txn.set_status(TransactionStatus::Cleared);
+
+        txn.validate()
+            .map_err(|e| EnvelopeError::Validation(e.to_string()))?;
+
+        self.storage.transactions.upsert(txn.clone())?;
+        self.storage.transactions.save()?;
+
+        self.storage.log_update(
+            EntityType::Transaction,
+            txn.id.to_string(),
+            Some(format!("{} {}", txn.date, txn.payee_name)),
+            &before,
+            &txn,
+            Some(format!(
+                "amount: {} -> {} (absorbed {} during reconciliation)",
+                before.amount, txn.amount, adjustment
+            )),
+        )?;
+
+        Ok(txn)
+    }
+
     /// Internal method to complete reconciliation
+    ///
+    /// All-or-nothing: if saving fails, every transaction and the account are
+    /// put back as they were and nothing is written to the audit log.
     fn complete_internal(
         &self,
         session: &ReconciliationSession,
         transactions_to_reconcile: &[Transaction],
     ) -> EnvelopeResult<ReconciliationResult> {
-        let mut count = 0;
+        let before_account = self
+            .storage
+            .accounts
+            .get(session.account_id)?
+            .ok_or_else(|| EnvelopeError::account_not_found(session.account_id.to_string()))?;
+        let mut account = before_account.clone();
+        account.reconcile(session.statement_date, session.statement_balance);
 
-        for txn in transactions_to_reconcile {
-            let mut updated_txn = txn.clone();
-            let before = txn.clone();
-            updated_txn.set_status(TransactionStatus::Reconciled);
+        let reconciled: Vec<Transaction> = transactions_to_reconcile
+            .iter()
+            .map(|txn| {
+                let mut updated_txn = txn.clone();
+                updated_txn.set_status(TransactionStatus::Reconciled);
+                updated_txn
+            })
+            .collect();
 
-            self.storage.transactions.upsert(updated_txn.clone())?;
+        if let Err(e) = self.save_completion(&reconciled, &account) {
+            self.restore_completion(transactions_to_reconcile, &before_account);
+            return Err(e);
+        }
 
+        for (before, updated_txn) in transactions_to_reconcile.iter().zip(&reconciled) {
             self.storage.log_update(
                 EntityType::Transaction,
                 updated_txn.id.to_string(),
                 Some(format!("{} {}", updated_txn.date, updated_txn.payee_name)),
-                &before,
-                &updated_txn,
+                before,
+                updated_txn,
                 Some("status: Cleared -> Reconciled (reconciliation complete)".to_string()),
             )?;
-
-            count += 1;
         }
 
-        self.storage.transactions.save()?;
-
-        // Update account's reconciliation info
-        let mut account = self
-            .storage
-            .accounts
-            .get(session.account_id)?
-            .ok_or_else(|| EnvelopeError::account_not_found(session.account_id.to_string()))?;
-
-        let before_account = account.clone();
-        account.reconcile(session.statement_date, session.statement_balance);
-
-        self.storage.accounts.upsert(account.clone())?;
-        self.storage.accounts.save()?;
-
         self.storage.log_update(
             EntityType::Account,
             account.id.to_string(),
@@ -385,12 +478,72 @@ impl<'a> ReconciliationService<'a> {
         )?;
 
         Ok(ReconciliationResult {
-            transactions_reconciled: count,
+            transactions_reconciled: reconciled.len(),
             adjustment_created: false,
             adjustment_amount: None,
         })
     }
 
+    /// Write the reconciled transactions and account to storage
+    fn save_completion(&self, reconciled: &[Transaction], account: &Account) -> EnvelopeResult<()> {
+        for txn in reconciled {
+            self.storage.transactions.upsert(txn.clone())?;
+        }
+        self.storage.transactions.save()?;
+
+        self.storage.accounts.upsert(account.clone())?;
+        self.storage.accounts.save()
+    }
+
+    /// Undo a partially saved completion
+    ///
+    /// Best effort: the original error is what gets reported.
+    fn restore_completion(&self, originals: &[Transaction], account: &Account) {
+        for txn in originals {
+            let _ = self.storage.transactions.upsert(txn.clone());
+        }
+        let _ = self.storage.transactions.save();
+        let _ = self.storage.accounts.upsert(account.clone());
+        let _ = self.storage.accounts.save();
+    }
+
+    /// Past reconciliations of an account, newest first
+    ///
+    /// Rebuilt from the account's audit entries, so it goes back as far as the
+    /// audit log does.
+    pub fn history(&self, account_id: AccountId) -> EnvelopeResult<Vec<ReconciliationRecord>> {
+        let entries = self
+            .storage
+            .audit()
+            .read_for_entity(&account_id.to_string())?;
+
+        let mut records: Vec<ReconciliationRecord> = entries
+            .into_iter()
+            .filter(|e| e.entity_type == EntityType::Account && e.operation == Operation::Update)
+            .filter_map(|e| {
+                let after: Account = serde_json::from_value(e.after?).ok()?;
+                let before: Option<Account> = e.before.and_then(|b| serde_json::from_value(b).ok());
+                let unchanged = before.is_some_and(|b| {
+                    b.last_reconciled_date == after.last_reconciled_date
+                        && b.last_reconciled_balance == after.last_reconciled_balance
+                });
+                let logged = e.diff_summary.is_some_and(|d| d.starts_with("reconciled:"));
+                if unchanged && !logged {
+                    return None;
+                }
+
+                Some(ReconciliationRecord {
+                    completed_at: e.timestamp,
+                    statement_date: after.last_reconciled_date?,
+                    statement_balance: after.last_reconciled_balance?,
+                })
+            })
+            .collect();
+
+        records.reverse();
+        Ok(records)
+    }
+
     /// Calculate the reconciled balance for an account
     /// (starting balance + all reconciled transactions)
     fn calculate_reconciled_balance(&self, account_id: AccountId) -> EnvelopeResult<Money> {
@@ -407,7 +560,7 @@ impl<'a> ReconciliationService<'a> {
             .map(|t| t.amount)
             .sum();
 
-        Ok(account.starting_balance + reconciled_total)
+        Ok(account.untracked_starting_balance(&transactions) + reconciled_total)
     }
 }
 
@@ -415,7 +568,7 @@ impl<'a> ReconciliationService<'a> {
 mod tests {
     use super::*;
     use crate::config::paths::EnvelopePaths;
-    use crate::models::{Account, AccountType};
+    use crate::models::{Account, AccountType, Category, CategoryGroup};
     use tempfile::TempDir;
 
     fn create_test_storage() -> (TempDir, Storage) {
@@ -530,6 +683,91 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_history() {
+        let (_temp_dir, storage) = create_test_storage();
+        let account = create_test_account(&storage);
+        let service = ReconciliationService::new(&storage);
+
+        assert!(service.history(account.id).unwrap().is_empty());
+
+        let january = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+        let session = service
+            .start(account.id, january, Money::from_cents(100000))
+            .unwrap();
+        service.complete(&session).unwrap();
+
+        // An unrelated account edit isn't a reconciliation
+        let before = storage.accounts.get(account.id).unwrap().unwrap();
+        let mut renamed = before.clone();
+        renamed.name = "Renamed Checking".to_string();
+        storage.accounts.upsert(renamed.clone()).unwrap();
+        storage
+            .log_update(
+                EntityType::Account,
+                account.id.to_string(),
+                Some(renamed.name.clone()),
+                &before,
+                &renamed,
+                None,
+            )
+            .unwrap();
+
+        let mut txn = Transaction::new(
+            account.id,
+            NaiveDate::from_ymd_opt(2025, 2, 10).unwrap(),
+            Money::from_cents(-5000),
+        );
+        txn.set_status(TransactionStatus::Cleared);
+        storage.transactions.upsert(txn).unwrap();
+
+        let february = NaiveDate::from_ymd_opt(2025, 2, 28).unwrap();
+        let session = service
+            .start(account.id, february, Money::from_cents(95000))
+            .unwrap();
+        service.complete(&session).unwrap();
+
+        let history = service.history(account.id).unwrap();
+        assert_eq!(history.len(), 2);
+        assert_eq!(history[0].statement_date, february);
+        assert_eq!(history[0].statement_balance.cents(), 95000);
+        assert_eq!(history[1].statement_date, january);
+        assert_eq!(history[1].statement_balance.cents(), 100000);
+    }
+
+    #[test]
+    fn test_complete_is_all_or_nothing() {
+        let (_temp_dir, storage) = create_test_storage();
+        let account = create_test_account(&storage);
+        let service = ReconciliationService::new(&storage);
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let mut txn = Transaction::new(account.id, date, Money::from_cents(-5000));
+        txn.set_status(TransactionStatus::Cleared);
+        storage.transactions.upsert(txn.clone()).unwrap();
+        storage.transactions.save().unwrap();
+
+        let session = service
+            .start(
+                account.id,
+                NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+                Money::from_cents(95000),
+            )
+            .unwrap();
+
+        // Make the transactions file unwritable by putting a directory there
+        let transactions_file = storage.paths().transactions_file();
+        std::fs::remove_file(&transactions_file).unwrap();
+        std::fs::create_dir(&transactions_file).unwrap();
+
+        assert!(service.complete(&session).is_err());
+
+        let unchanged = storage.transactions.get(txn.id).unwrap().unwrap();
+        assert_eq!(unchanged.status, TransactionStatus::Cleared);
+        let account = storage.accounts.get(account.id).unwrap().unwrap();
+        assert_eq!(account.last_reconciled_date, None);
+    }
+
     #[test]
     fn test_complete_with_adjustment() {
         let (_temp_dir, storage) = create_test_storage();
@@ -556,6 +794,45 @@ mod tests {
         assert_eq!(result.adjustment_amount.unwrap().cents(), -1000);
     }
 
+    #[test]
+    fn test_complete_with_adjustment_in_category() {
+        let (_temp_dir, storage) = create_test_storage();
+        let account = create_test_account(&storage);
+        let service = ReconciliationService::new(&storage);
+
+        let group = CategoryGroup::new("Fees");
+        storage.categories.upsert_group(group.clone()).unwrap();
+        let category = Category::new("Bank Fees", group.id);
+        storage
+            .categories
+            .upsert_category(category.clone())
+            .unwrap();
+
+        let session = service
+            .start(
+                account.id,
+                NaiveDate::from_ymd_opt(2025, 1, 31).unwrap(),
+                Money::from_cents(99650),
+            )
+            .unwrap();
+
+        let result = service
+            .complete_with_adjustment(&session, Some(category.id))
+            .unwrap();
+        assert!(result.adjustment_created);
+
+        let adjustment = storage
+            .transactions
+            .get_by_account(account.id)
+            .unwrap()
+            .into_iter()
+            .find(|t| t.payee_name == "Reconciliation Adjustment")
+            .unwrap();
+        assert_eq!(adjustment.amount.cents(), -350);
+        assert_eq!(adjustment.category_id, Some(category.id));
+        assert_eq!(adjustment.status, TransactionStatus::Reconciled);
+    }
+
     #[test]
     fn test_cannot_complete_without_zero_difference() {
         let (_temp_dir, storage) = create_test_storage();
@@ -592,4 +869,33 @@ mod tests {
         let uncleared = service.unclear_transaction(txn.id).unwrap();
         assert_eq!(uncleared.status, TransactionStatus::Pending);
     }
+
+    #[test]
+    fn test_adjust_to_match() {
+        let (_temp_dir, storage) = create_test_storage();
+        let account = create_test_account(&storage);
+        let service = ReconciliationService::new(&storage);
+
+        // The bank charged $50.50; the register says $50.00
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let txn = Transaction::new(account.id, date, Money::from_cents(-5000));
+        storage.transactions.upsert(txn.clone()).unwrap();
+        storage.transactions.save().unwrap();
+
+        let statement_date = NaiveDate::from_ymd_opt(2025, 1, 31).unwrap();
+        let session = service
+            .start(account.id, statement_date, Money::from_cents(94950))
+            .unwrap();
+
+        // Larger than the allowed maximum
+        let result = service.adjust_to_match(&session, txn.id, Money::from_cents(25));
+        assert!(matches!(result, Err(EnvelopeError::Reconciliation(_))));
+
+        let adjusted = service
+            .adjust_to_match(&session, txn.id, Money::from_cents(100))
+            .unwrap();
+        assert_eq!(adjusted.amount.cents(), -5050);
+        assert_eq!(adjusted.status, TransactionStatus::Cleared);
+        assert!(service.get_summary(&session).unwrap().can_complete);
+    }
 }
@@ -0,0 +1,448 @@
+//! Scheduled transaction service
+//!
+//! Provides business logic for recurring transactions: creating schedules
+//! and turning due occurrences into real transactions.
+
+use chrono::NaiveDate;
+
+use crate::audit::EntityType;
+use crate::config::clock;
+use crate::error::{EnvelopeError, EnvelopeResult};
+use crate::models::{
+    AccountId, CategoryId, Money, ScheduledId, ScheduledTransaction, TargetCadence, Transaction,
+};
+use crate::services::{CreateTransactionInput, TransactionService};
+use crate::storage::Storage;
+
+/// Service for scheduled transaction management
+pub struct ScheduledService<'a> {
+    storage: &'a Storage,
+}
+
+/// Input for creating a scheduled transaction
+#[derive(Debug, Clone)]
+pub struct CreateScheduledInput {
+    pub account_id: AccountId,
+    pub amount: Money,
+    pub payee_name: Option<String>,
+    pub category_id: Option<CategoryId>,
+    pub memo: Option<String>,
+    pub cadence: TargetCadence,
+    pub start_date: NaiveDate,
+    pub end_date: Option<NaiveDate>,
+}
+
+/// What [`ScheduledService::materialize_due`] did
+#[derive(Debug, Default)]
+pub struct MaterializeResult {
+    /// Transactions created, in schedule order
+    pub created: Vec<Transaction>,
+    /// Schedules that stopped on an error, with the error
+    pub failed: Vec<(ScheduledTransaction, EnvelopeError)>,
+}
+
+impl<'a> ScheduledService<'a> {
+    /// Create a new scheduled transaction service
+    pub fn new(storage: &'a Storage) -> Self {
+        Self { storage }
+    }
+
+    /// Create a scheduled transaction
+    pub fn create(&self, input: CreateScheduledInput) -> EnvelopeResult<ScheduledTransaction> {
+        let account = self
+            .storage
+            .accounts
+            .get(input.account_id)?
+            .ok_or_else(|| EnvelopeError::account_not_found(input.account_id.to_string()))?;
+
+        if account.archived {
+            return Err(EnvelopeError::Validation(
+                "Cannot schedule transactions on an archived account".into(),
+            ));
+        }
+
+        if let Some(cat_id) = input.category_id {
+            self.storage
+                .categories
+                .get_category(cat_id)?
+                .ok_or_else(|| EnvelopeError::category_not_found(cat_id.to_string()))?;
+        }
+
+        let mut scheduled = ScheduledTransaction::new(
+            input.account_id,
+            input.amount,
+            input.cadence,
+            input.start_date,
+        );
+        if let Some(payee_name) = input.payee_name {
+            scheduled.payee_name = payee_name.trim().to_string();
+        }
+        scheduled.category_id = input.category_id;
+        if let Some(memo) = input.memo {
+            scheduled.memo = memo;
+        }
+        scheduled.end_date = input.end_date;
+
+        scheduled
+            .validate()
+            .map_err(|e| EnvelopeError::Validation(e.to_string()))?;
+
+        self.storage.scheduled.upsert(scheduled.clone())?;
+        self.storage.scheduled.save()?;
+
+        self.storage.log_create(
+            EntityType::ScheduledTransaction,
+            scheduled.id.to_string(),
+            Some(scheduled.to_string()),
+            &scheduled,
+        )?;
+
+        Ok(scheduled)
+    }
+
+    /// Get all scheduled transactions, soonest first
+    pub fn list(&self) -> EnvelopeResult<Vec<ScheduledTransaction>> {
+        self.storage.scheduled.get_all()
+    }
+
+    /// Find a scheduled transaction by ID or unique payee name
+    pub fn find(&self, identifier: &str) -> EnvelopeResult<Option<ScheduledTransaction>> {
+        if let Ok(id) = identifier.parse::<ScheduledId>() {
+            if let Some(scheduled) = self.storage.scheduled.get(id)? {
+                return Ok(Some(scheduled));
+            }
+        }
+
+        let matches: Vec<_> = self
+            .list()?
+            .into_iter()
+            .filter(|s| s.payee_name.eq_ignore_ascii_case(identifier))
+            .collect();
+        match matches.len() {
+            0 => Ok(None),
+            1 => Ok(matches.into_iter().next()),
+            _ => Err(EnvelopeError::Validation(format!(
+                "'{}' matches {} schedules; use the ID instead",
+                identifier,
+                matches.len()
+            ))),
+        }
+    }
+
+    /// Delete a scheduled transaction
+    ///
+    /// Transactions it already created are kept.
+    pub fn delete(&self, id: ScheduledId) -> EnvelopeResult<ScheduledTransaction> {
+        let scheduled = self
+            .storage
+            .scheduled
+            .get(id)?
+            .ok_or_else(|| EnvelopeError::NotFound {
+                entity_type: "Scheduled transaction",
+                identifier: id.to_string(),
+            })?;
+
+        self.storage.scheduled.delete(id)?;
+        self.storage.scheduled.save()?;
+
+        self.storage.log_delete(
+            EntityType::ScheduledTransaction,
+            scheduled.id.to_string(),
+            Some(scheduled.to_string()),
+            &scheduled,
+        )?;
+
+        Ok(scheduled)
+    }
+
+    /// Create a transaction for every occurrence due on or before `as_of`
+    ///
+    /// A schedule that fell behind catches up one transaction per missed
+    /// occurrence. Each schedule's `next_date` moves past `as_of`, so running
+    /// this twice for the same day creates nothing the second time.
+    ///
+    /// A schedule that can't post (say its account was archived) stops at the
+    /// failing occurrence and is reported in the result; the others still run,
+    /// and the occurrences it did post are kept.
+    pub fn materialize_due(&self, as_of: NaiveDate) -> EnvelopeResult<MaterializeResult> {
+        let transaction_service = TransactionService::new(self.storage);
+        let mut result = MaterializeResult::default();
+
+        for scheduled in self.list()? {
+            if !scheduled.is_due(as_of) {
+                continue;
+            }
+
+            let before = scheduled.clone();
+            let mut updated = scheduled;
+            while updated.is_due(as_of) {
+                match transaction_service.create(CreateTransactionInput {
+                    account_id: updated.account_id,
+                    date: updated.next_date,
+                    amount: updated.amount,
+                    payee_name: Some(updated.payee_name.clone()),
+                    category_id: updated.category_id,
+                    memo: Some(updated.memo.clone()),
+                    status: None,
+                }) {
+                    Ok(txn) => {
+                        result.created.push(txn);
+                        updated.advance();
+                    }
+                    Err(e) => {
+                        result.failed.push((updated.clone(), e));
+                        break;
+                    }
+                }
+            }
+
+            if updated.next_date == before.next_date && updated.active == before.active {
+                continue;
+            }
+
+            self.storage.scheduled.upsert(updated.clone())?;
+            self.storage.scheduled.save()?;
+
+            self.storage.log_update(
+                EntityType::ScheduledTransaction,
+                updated.id.to_string(),
+                Some(updated.to_string()),
+                &before,
+                &updated,
+                Some(format!(
+                    "next date {} -> {}",
+                    before.next_date, updated.next_date
+                )),
+            )?;
+        }
+
+        Ok(result)
+    }
+
+    /// Move an account's schedules to another account, as when merging them
+    pub fn retarget_account(&self, from: AccountId, to: AccountId) -> EnvelopeResult<usize> {
+        self.update_where(
+            |s| s.account_id == from,
+            |s| s.account_id = to,
+            "account retargeted by merge",
+        )
+    }
+
+    /// Stop an account's active schedules, as when it is archived
+    pub fn deactivate_for_account(&self, account_id: AccountId) -> EnvelopeResult<usize> {
+        self.update_where(
+            |s| s.active && s.account_id == account_id,
+            |s| s.active = false,
+            "stopped: account archived",
+        )
+    }
+
+    /// Remove deleted categories from the schedules that use them
+    ///
+    /// The schedules keep running and post uncategorized transactions.
+    pub fn clear_categories(&self, category_ids: &[CategoryId]) -> EnvelopeResult<usize> {
+        self.update_where(
+            |s| s.category_id.is_some_and(|id| category_ids.contains(&id)),
+            |s| s.category_id = None,
+            "category deleted",
+        )
+    }
+
+    /// Apply `change` to every schedule matching `filter`, returning how many changed
+    fn update_where(
+        &self,
+        filter: impl Fn(&ScheduledTransaction) -> bool,
+        change: impl Fn(&mut ScheduledTransaction),
+        summary: &str,
+    ) -> EnvelopeResult<usize> {
+        let matching: Vec<_> = self.list()?.into_iter().filter(|s| filter(s)).collect();
+        if matching.is_empty() {
+            return Ok(0);
+        }
+
+        let mut changed = Vec::with_capacity(matching.len());
+        for before in matching {
+            let mut scheduled = before.clone();
+            change(&mut scheduled);
+            scheduled.updated_at = clock::now();
+            self.storage.scheduled.upsert(scheduled.clone())?;
+            changed.push((before, scheduled));
+        }
+        self.storage.scheduled.save()?;
+
+        for (before, scheduled) in &changed {
+            self.storage.log_update(
+                EntityType::ScheduledTransaction,
+                scheduled.id.to_string(),
+                Some(scheduled.to_string()),
+                before,
+                scheduled,
+                Some(summary.to_string()),
+            )?;
+        }
+
+        Ok(changed.len())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::paths::EnvelopePaths;
+    use crate::models::{Account, AccountType};
+    use tempfile::TempDir;
+
+    fn create_test_storage() -> (TempDir, Storage) {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let mut storage = Storage::new(paths).unwrap();
+        storage.load_all().unwrap();
+        (temp_dir, storage)
+    }
+
+    fn date(y: i32, m: u32, d: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(y, m, d).unwrap()
+    }
+
+    #[test]
+    fn test_materialize_due_catches_up() {
+        let (_temp_dir, storage) = create_test_storage();
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+
+        let service = ScheduledService::new(&storage);
+        let scheduled = service
+            .create(CreateScheduledInput {
+                account_id: account.id,
+                amount: Money::from_cents(-150000),
+                payee_name: Some("Landlord".into()),
+                category_id: None,
+                memo: None,
+                cadence: TargetCadence::Monthly,
+                start_date: date(2025, 1, 1),
+                end_date: None,
+            })
+            .unwrap();
+
+        let created = service.materialize_due(date(2025, 3, 15)).unwrap().created;
+        let dates: Vec<_> = created.iter().map(|t| t.date).collect();
+        assert_eq!(
+            dates,
+            vec![date(2025, 1, 1), date(2025, 2, 1), date(2025, 3, 1)]
+        );
+        assert!(created.iter().all(|t| t.payee_name == "Landlord"));
+
+        let updated = storage.scheduled.get(scheduled.id).unwrap().unwrap();
+        assert_eq!(updated.next_date, date(2025, 4, 1));
+
+        // Nothing new is due the second time
+        assert!(service
+            .materialize_due(date(2025, 3, 15))
+            .unwrap()
+            .created
+            .is_empty());
+        assert_eq!(storage.transactions.count().unwrap(), 3);
+    }
+
+    #[test]
+    fn test_create_rejects_archived_account() {
+        let (_temp_dir, storage) = create_test_storage();
+        let mut account = Account::new("Old", AccountType::Checking);
+        account.archived = true;
+        storage.accounts.upsert(account.clone()).unwrap();
+
+        let result = ScheduledService::new(&storage).create(CreateScheduledInput {
+            account_id: account.id,
+            amount: Money::from_cents(-1000),
+            payee_name: None,
+            category_id: None,
+            memo: None,
+            cadence: TargetCadence::Weekly,
+            start_date: date(2025, 1, 1),
+            end_date: None,
+        });
+        assert!(matches!(result, Err(EnvelopeError::Validation(_))));
+    }
+
+    fn rent_input(account_id: AccountId, category_id: Option<CategoryId>) -> CreateScheduledInput {
+        CreateScheduledInput {
+            account_id,
+            amount: Money::from_cents(-150000),
+            payee_name: Some("Landlord".into()),
+            category_id,
+            memo: None,
+            cadence: TargetCadence::Monthly,
+            start_date: date(2025, 1, 1),
+            end_date: None,
+        }
+    }
+
+    #[test]
+    fn test_materialize_due_keeps_going_past_a_failing_schedule() {
+        let (_temp_dir, storage) = create_test_storage();
+        let checking = Account::new("Checking", AccountType::Checking);
+        let old = Account::new("Old", AccountType::Checking);
+        storage.accounts.upsert(checking.clone()).unwrap();
+        storage.accounts.upsert(old.clone()).unwrap();
+
+        let service = ScheduledService::new(&storage);
+        let broken = service.create(rent_input(old.id, None)).unwrap();
+        let working = service.create(rent_input(checking.id, None)).unwrap();
+
+        // Archived behind the service's back, so the schedule is still active
+        let mut archived = old.clone();
+        archived.archived = true;
+        storage.accounts.upsert(archived).unwrap();
+
+        let result = service.materialize_due(date(2025, 2, 15)).unwrap();
+        assert_eq!(result.created.len(), 2);
+        assert_eq!(result.failed.len(), 1);
+        assert_eq!(result.failed[0].0.id, broken.id);
+
+        let working = storage.scheduled.get(working.id).unwrap().unwrap();
+        assert_eq!(working.next_date, date(2025, 3, 1));
+        let broken = storage.scheduled.get(broken.id).unwrap().unwrap();
+        assert_eq!(broken.next_date, date(2025, 1, 1));
+    }
+
+    #[test]
+    fn test_orphaning_changes_update_schedules() {
+        use crate::models::{Category, CategoryGroup};
+        use crate::services::{AccountService, CategoryService};
+
+        let (_temp_dir, storage) = create_test_storage();
+        let checking = Account::new("Checking", AccountType::Checking);
+        let savings = Account::new("Savings", AccountType::Savings);
+        let old = Account::new("Old", AccountType::Checking);
+        for account in [&checking, &savings, &old] {
+            storage.accounts.upsert(account.clone()).unwrap();
+        }
+        let group = CategoryGroup::new("Bills");
+        storage.categories.upsert_group(group.clone()).unwrap();
+        let rent = Category::new("Rent", group.id);
+        storage.categories.upsert_category(rent.clone()).unwrap();
+
+        let service = ScheduledService::new(&storage);
+        let on_savings = service
+            .create(rent_input(savings.id, Some(rent.id)))
+            .unwrap();
+        let on_old = service.create(rent_input(old.id, None)).unwrap();
+
+        AccountService::new(&storage)
+            .merge(savings.id, checking.id)
+            .unwrap();
+        AccountService::new(&storage).archive(old.id).unwrap();
+        CategoryService::new(&storage)
+            .delete_category(rent.id)
+            .unwrap();
+
+        let on_savings = storage.scheduled.get(on_savings.id).unwrap().unwrap();
+        assert_eq!(on_savings.account_id, checking.id);
+        assert!(on_savings.category_id.is_none());
+        assert!(!storage.scheduled.get(on_old.id).unwrap().unwrap().active);
+
+        let result = service.materialize_due(date(2025, 1, 15)).unwrap();
+        assert_eq!(result.created.len(), 1);
+        assert!(result.failed.is_empty());
+    }
+}
@@ -3,12 +3,14 @@
 //! Provides business logic for transaction management including CRUD operations,
 //! status management, and integration with budget calculations.
 
-use chrono::{NaiveDate, Utc};
+use chrono::NaiveDate;
 
 use crate::audit::EntityType;
+use crate::config::clock;
 use crate::error::{EnvelopeError, EnvelopeResult};
 use crate::models::{
-    AccountId, CategoryId, Money, Split, Transaction, TransactionId, TransactionStatus,
+    AccountId, CategoryId, Money, Payee, Split, Transaction, TransactionFlag, TransactionId,
+    TransactionStatus,
 };
 use crate::storage::Storage;
 
@@ -30,6 +32,20 @@ pub struct TransactionFilter {
     pub end_date: Option<NaiveDate>,
     /// Filter by status
     pub status: Option<TransactionStatus>,
+    /// Filter by tag
+    pub tag: Option<String>,
+    /// Payee contains this text, ignoring case
+    pub payee_contains: Option<String>,
+    /// Memo, or any split's memo, contains this text, ignoring case
+    pub memo_contains: Option<String>,
+    /// Smallest amount to include, compared by size so a $43 charge matches 40
+    pub amount_min: Option<Money>,
+    /// Largest amount to include, compared by size
+    pub amount_max: Option<Money>,
+    /// Only include transactions with this flag
+    pub flag: Option<TransactionFlag>,
+    /// Only include transactions from non-archived accounts
+    pub active_accounts_only: bool,
     /// Maximum number of transactions to return
     pub limit: Option<usize>,
 }
@@ -65,6 +81,48 @@ impl TransactionFilter {
         self
     }
 
+    /// Filter by tag
+    pub fn tag(mut self, tag: impl Into<String>) -> Self {
+        self.tag = Some(tag.into());
+        self
+    }
+
+    /// Filter by text in the payee
+    pub fn payee_contains(mut self, text: impl Into<String>) -> Self {
+        self.payee_contains = Some(text.into());
+        self
+    }
+
+    /// Filter by text in the memo or a split's memo
+    pub fn memo_contains(mut self, text: impl Into<String>) -> Self {
+        self.memo_contains = Some(text.into());
+        self
+    }
+
+    /// Filter by minimum amount, ignoring sign
+    pub fn amount_min(mut self, amount: Money) -> Self {
+        self.amount_min = Some(amount);
+        self
+    }
+
+    /// Filter by maximum amount, ignoring sign
+    pub fn amount_max(mut self, amount: Money) -> Self {
+        self.amount_max = Some(amount);
+        self
+    }
+
+    /// Filter by flag
+    pub fn flag(mut self, flag: TransactionFlag) -> Self {
+        self.flag = Some(flag);
+        self
+    }
+
+    /// Exclude transactions from archived accounts
+    pub fn active_accounts_only(mut self) -> Self {
+        self.active_accounts_only = true;
+        self
+    }
+
     /// Limit results
     pub fn limit(mut self, limit: usize) -> Self {
         self.limit = Some(limit);
@@ -158,17 +216,70 @@ impl<'a> TransactionService<'a> {
         Ok(txn)
     }
 
+    /// Resolve the category to prefill on a new transaction
+    ///
+    /// The account's default wins over the global one. A default whose
+    /// category has since been deleted is skipped rather than treated as an
+    /// error.
+    pub fn default_category(
+        &self,
+        account_id: AccountId,
+        global_default: Option<CategoryId>,
+    ) -> EnvelopeResult<Option<CategoryId>> {
+        let account_default = self
+            .storage
+            .accounts
+            .get(account_id)?
+            .and_then(|a| a.default_category_id);
+
+        for cat_id in [account_default, global_default].into_iter().flatten() {
+            if self.storage.categories.get_category(cat_id)?.is_some() {
+                return Ok(Some(cat_id));
+            }
+        }
+
+        Ok(None)
+    }
+
     /// Get a transaction by ID
     pub fn get(&self, id: TransactionId) -> EnvelopeResult<Option<Transaction>> {
         self.storage.transactions.get(id)
     }
 
     /// Find a transaction by ID string
+    ///
+    /// Accepts the full ID or the short `txn-1a2b3c4d` form shown in lists.
+    /// A short form matching more than one transaction is an error.
     pub fn find(&self, identifier: &str) -> EnvelopeResult<Option<Transaction>> {
         if let Ok(id) = identifier.parse::<TransactionId>() {
             return self.storage.transactions.get(id);
         }
-        Ok(None)
+
+        let prefix = identifier
+            .trim()
+            .strip_prefix("txn-")
+            .unwrap_or(identifier.trim())
+            .to_lowercase();
+        if prefix.is_empty() || !prefix.chars().all(|c| c.is_ascii_hexdigit() || c == '-') {
+            return Ok(None);
+        }
+
+        let mut matches: Vec<Transaction> = self
+            .storage
+            .transactions
+            .get_all()?
+            .into_iter()
+            .filter(|t| t.id.as_uuid().to_string().starts_with(&prefix))
+            .collect();
+
+        match matches.len() {
+            0 => Ok(None),
+            1 => Ok(matches.pop()),
+            n => Err(EnvelopeError::Validation(format!(
+                "'{}' matches {} transactions; use more of the ID",
+                identifier, n
+            ))),
+        }
     }
 
     /// List all transactions with optional filtering
@@ -193,6 +304,41 @@ impl<'a> TransactionService<'a> {
         if let Some(status) = filter.status {
             transactions.retain(|t| t.status == status);
         }
+        if let Some(tag) = &filter.tag {
+            transactions.retain(|t| t.has_tag(tag));
+        }
+        if let Some(flag) = filter.flag {
+            transactions.retain(|t| t.flag == flag);
+        }
+        if let Some(text) = &filter.payee_contains {
+            let text = text.to_lowercase();
+            transactions.retain(|t| t.payee_name.to_lowercase().contains(&text));
+        }
+        if let Some(text) = &filter.memo_contains {
+            let text = text.to_lowercase();
+            transactions.retain(|t| {
+                t.memo.to_lowercase().contains(&text)
+                    || t.splits
+                        .iter()
+                        .any(|s| s.memo.to_lowercase().contains(&text))
+            });
+        }
+        if let Some(min) = filter.amount_min {
+            transactions.retain(|t| t.amount.abs() >= min.abs());
+        }
+        if let Some(max) = filter.amount_max {
+            transactions.retain(|t| t.amount.abs() <= max.abs());
+        }
+        if filter.active_accounts_only {
+            let active: std::collections::HashSet<_> = self
+                .storage
+                .accounts
+                .get_active()?
+                .into_iter()
+                .map(|a| a.id)
+                .collect();
+            transactions.retain(|t| active.contains(&t.account_id));
+        }
 
         // Apply limit
         if let Some(limit) = filter.limit {
@@ -228,6 +374,10 @@ impl<'a> TransactionService<'a> {
             .get(id)?
             .ok_or_else(|| EnvelopeError::transaction_not_found(id.to_string()))?;
 
+        if txn.is_starting_balance {
+            return Err(starting_balance_locked(id));
+        }
+
         // Check if locked
         if txn.is_locked() {
             return Err(EnvelopeError::Locked(format!(
@@ -236,6 +386,10 @@ impl<'a> TransactionService<'a> {
             )));
         }
 
+        if amount.is_some_and(|a| a != txn.amount) && self.split_parent_of(&txn)?.is_some() {
+            return Err(split_transfer_leg(id));
+        }
+
         let before = txn.clone();
 
         // Apply updates
@@ -280,12 +434,25 @@ impl<'a> TransactionService<'a> {
             txn.memo = new_memo;
         }
 
-        txn.updated_at = Utc::now();
+        txn.updated_at = clock::now();
 
         // Validate
         txn.validate()
             .map_err(|e| EnvelopeError::Validation(e.to_string()))?;
 
+        // Keep transfer split legs in step with their transaction
+        if txn.splits.is_empty() {
+            self.remove_split_legs(&before)?;
+        } else if txn.date != before.date {
+            for leg_id in txn.splits.iter().filter_map(|s| s.transfer_transaction_id) {
+                if let Some(mut leg) = self.storage.transactions.get(leg_id)? {
+                    leg.date = txn.date;
+                    leg.updated_at = clock::now();
+                    self.storage.transactions.upsert(leg)?;
+                }
+            }
+        }
+
         // Save
         self.storage.transactions.upsert(txn.clone())?;
         self.storage.transactions.save()?;
@@ -342,6 +509,10 @@ impl<'a> TransactionService<'a> {
             .get(id)?
             .ok_or_else(|| EnvelopeError::transaction_not_found(id.to_string()))?;
 
+        if txn.is_starting_balance {
+            return Err(starting_balance_locked(id));
+        }
+
         // Check if locked
         if txn.is_locked() {
             return Err(EnvelopeError::Locked(format!(
@@ -350,6 +521,13 @@ impl<'a> TransactionService<'a> {
             )));
         }
 
+        if self.split_parent_of(&txn)?.is_some() {
+            return Err(split_transfer_leg(id));
+        }
+
+        // Transfer splits take their legs in the other accounts with them
+        self.remove_split_legs(&txn)?;
+
         // If this is a transfer, we need to handle the linked transaction
         if let Some(linked_id) = txn.transfer_transaction_id {
             // Delete the linked transaction too
@@ -400,6 +578,10 @@ impl<'a> TransactionService<'a> {
             .get(id)?
             .ok_or_else(|| EnvelopeError::transaction_not_found(id.to_string()))?;
 
+        if txn.is_starting_balance {
+            return Err(starting_balance_locked(id));
+        }
+
         // Can't change status of reconciled transaction without unlocking first
         if txn.is_locked() && status != TransactionStatus::Reconciled {
             return Err(EnvelopeError::Locked(format!(
@@ -449,6 +631,10 @@ impl<'a> TransactionService<'a> {
             .get(id)?
             .ok_or_else(|| EnvelopeError::transaction_not_found(id.to_string()))?;
 
+        if txn.is_starting_balance {
+            return Err(starting_balance_locked(id));
+        }
+
         if !txn.is_locked() {
             return Err(EnvelopeError::Validation(format!(
                 "Transaction {} is not locked",
@@ -476,6 +662,146 @@ impl<'a> TransactionService<'a> {
         Ok(txn)
     }
 
+    /// Add tags to a transaction
+    ///
+    /// Tags don't affect balances, so they can be changed on reconciled
+    /// transactions too.
+    pub fn add_tags(&self, id: TransactionId, tags: &[String]) -> EnvelopeResult<Transaction> {
+        self.update_tags(id, |txn| txn.add_tags(tags), "added tags")
+    }
+
+    /// Remove tags from a transaction
+    pub fn remove_tags(&self, id: TransactionId, tags: &[String]) -> EnvelopeResult<Transaction> {
+        self.update_tags(id, |txn| txn.remove_tags(tags), "removed tags")
+    }
+
+    /// Apply a tag change, saving and auditing only if something changed
+    fn update_tags(
+        &self,
+        id: TransactionId,
+        change: impl FnOnce(&mut Transaction) -> bool,
+        description: &str,
+    ) -> EnvelopeResult<Transaction> {
+        let mut txn = self
+            .storage
+            .transactions
+            .get(id)?
+            .ok_or_else(|| EnvelopeError::transaction_not_found(id.to_string()))?;
+
+        let before = txn.clone();
+        if !change(&mut txn) {
+            return Ok(txn);
+        }
+
+        // Save
+        self.storage.transactions.upsert(txn.clone())?;
+        self.storage.transactions.save()?;
+
+        // Audit log
+        self.storage.log_update(
+            EntityType::Transaction,
+            txn.id.to_string(),
+            Some(format!("{} {}", txn.date, txn.payee_name)),
+            &before,
+            &txn,
+            Some(format!("{}: {}", description, txn.tags.join(", "))),
+        )?;
+
+        Ok(txn)
+    }
+
+    /// Flag a transaction for review, star it, or clear its flag
+    ///
+    /// Flags are for triage and don't affect balances, so reconciled
+    /// transactions can be flagged too.
+    pub fn set_flag(
+        &self,
+        id: TransactionId,
+        flag: TransactionFlag,
+    ) -> EnvelopeResult<Transaction> {
+        let mut txn = self
+            .storage
+            .transactions
+            .get(id)?
+            .ok_or_else(|| EnvelopeError::transaction_not_found(id.to_string()))?;
+
+        if txn.flag == flag {
+            return Ok(txn);
+        }
+
+        let before = txn.clone();
+        txn.flag = flag;
+        txn.updated_at = clock::now();
+
+        // Save
+        self.storage.transactions.upsert(txn.clone())?;
+        self.storage.transactions.save()?;
+
+        // Audit log
+        self.storage.log_update(
+            EntityType::Transaction,
+            txn.id.to_string(),
+            Some(format!("{} {}", txn.date, txn.payee_name)),
+            &before,
+            &txn,
+            Some(format!("flag: {} -> {}", before.flag, txn.flag)),
+        )?;
+
+        Ok(txn)
+    }
+
+    /// Set or clear where an inflow came from
+    ///
+    /// Sources are stored lowercase so "Salary" and "salary" report together.
+    /// Like tags, a source doesn't affect balances, so reconciled transactions
+    /// can have theirs changed. Only inflows that aren't transfers take one.
+    pub fn set_income_source(
+        &self,
+        id: TransactionId,
+        source: Option<&str>,
+    ) -> EnvelopeResult<Transaction> {
+        let mut txn = self
+            .storage
+            .transactions
+            .get(id)?
+            .ok_or_else(|| EnvelopeError::transaction_not_found(id.to_string()))?;
+
+        let source = source
+            .map(|s| s.trim().to_lowercase())
+            .filter(|s| !s.is_empty());
+        if source.is_some() && (!txn.amount.is_positive() || txn.is_transfer()) {
+            return Err(EnvelopeError::Validation(
+                "Only inflows that aren't transfers can have an income source".into(),
+            ));
+        }
+        if txn.income_source == source {
+            return Ok(txn);
+        }
+
+        let before = txn.clone();
+        txn.income_source = source;
+        txn.updated_at = clock::now();
+
+        // Save
+        self.storage.transactions.upsert(txn.clone())?;
+        self.storage.transactions.save()?;
+
+        // Audit log
+        self.storage.log_update(
+            EntityType::Transaction,
+            txn.id.to_string(),
+            Some(format!("{} {}", txn.date, txn.payee_name)),
+            &before,
+            &txn,
+            Some(match &txn.income_source {
+                Some(source) => format!("income source: {}", source),
+                None => "income source cleared".to_string(),
+            }),
+        )?;
+
+        Ok(txn)
+    }
+
     /// Add a split to a transaction
     ///
     /// Note: This validates that splits total equals the transaction amount.
@@ -559,26 +885,31 @@ impl<'a> TransactionService<'a> {
             )));
         }
 
-        // Verify all categories exist
+        // Verify all categories and transfer accounts exist
         for split in &splits {
-            self.storage
-                .categories
-                .get_category(split.category_id)?
-                .ok_or_else(|| EnvelopeError::category_not_found(split.category_id.to_string()))?;
+            self.verify_split_target(&txn, split)?;
         }
 
         let before = txn.clone();
 
         // Replace splits
         txn.splits = splits;
+        for split in &mut txn.splits {
+            split.transfer_transaction_id = None;
+        }
         txn.category_id = None; // Clear single category when using splits
-        txn.updated_at = Utc::now();
+        txn.updated_at = clock::now();
 
         // Validate
         txn.validate()
             .map_err(|e| EnvelopeError::Validation(e.to_string()))?;
 
+        // Replace the transfer legs of the old splits with new ones
+        self.remove_split_legs(&before)?;
+        let legs = self.create_split_legs(&mut txn)?;
+
         // Save
+        self.save_split_legs(legs)?;
         self.storage.transactions.upsert(txn.clone())?;
         self.storage.transactions.save()?;
 
@@ -614,9 +945,11 @@ impl<'a> TransactionService<'a> {
             return Ok(txn);
         }
 
+        self.remove_split_legs(&txn)?;
+
         let before = txn.clone();
         txn.splits.clear();
-        txn.updated_at = Utc::now();
+        txn.updated_at = clock::now();
 
         // Save
         self.storage.transactions.upsert(txn.clone())?;
@@ -635,6 +968,75 @@ impl<'a> TransactionService<'a> {
         Ok(txn)
     }
 
+    /// Get uncategorized transactions from a payee across all accounts
+    ///
+    /// Payee names match case-insensitively. Transfers, splits, starting
+    /// balances, and reconciled transactions are left out, since they can't
+    /// be recategorized.
+    pub fn uncategorized_for_payee(&self, payee_name: &str) -> EnvelopeResult<Vec<Transaction>> {
+        let payee_name = Payee::normalize_name(payee_name);
+        let mut transactions: Vec<Transaction> = self
+            .storage
+            .transactions
+            .get_all()?
+            .into_iter()
+            .filter(|t| {
+                t.category_id.is_none()
+                    && !t.is_split()
+                    && !t.is_transfer()
+                    && !t.is_starting_balance
+                    && !t.is_locked()
+                    && Payee::normalize_name(&t.payee_name) == payee_name
+            })
+            .collect();
+        transactions.sort_by(|a, b| a.date.cmp(&b.date));
+        Ok(transactions)
+    }
+
+    /// Categorize all uncategorized transactions from a payee
+    ///
+    /// Returns the number of transactions categorized.
+    pub fn categorize_payee(
+        &self,
+        payee_name: &str,
+        category_id: CategoryId,
+    ) -> EnvelopeResult<usize> {
+        self.storage
+            .categories
+            .get_category(category_id)?
+            .ok_or_else(|| EnvelopeError::category_not_found(category_id.to_string()))?;
+
+        let transactions = self.uncategorized_for_payee(payee_name)?;
+        for txn in &transactions {
+            let before = txn.clone();
+            let mut txn = txn.clone();
+            txn.category_id = Some(category_id);
+            txn.updated_at = clock::now();
+
+            self.storage.transactions.upsert(txn.clone())?;
+            self.storage.log_update(
+                EntityType::Transaction,
+                txn.id.to_string(),
+                Some(format!("{} {}", txn.date, txn.payee_name)),
+                &before,
+                &txn,
+                Some(format!(
+                    "category: None -> {:?} (by payee)",
+                    txn.category_id
+                )),
+            )?;
+        }
+        self.storage.transactions.save()?;
+
+        if let Some(txn) = transactions.first() {
+            let mut txn = txn.clone();
+            txn.category_id = Some(category_id);
+            self.learn_from_transaction(&txn)?;
+        }
+
+        Ok(transactions.len())
+    }
+
     /// Learn from a transaction - update payee's category frequency
     pub fn learn_from_transaction(&self, txn: &Transaction) -> EnvelopeResult<()> {
         if let (Some(payee_id), Some(category_id)) = (txn.payee_id, txn.category_id) {
@@ -678,6 +1080,151 @@ impl<'a> TransactionService<'a> {
             .filter(|t| t.status == TransactionStatus::Cleared)
             .collect())
     }
+
+    /// The split transaction that created `txn` as its transfer leg, if any
+    fn split_parent_of(&self, txn: &Transaction) -> EnvelopeResult<Option<Transaction>> {
+        let Some(linked_id) = txn.transfer_transaction_id else {
+            return Ok(None);
+        };
+        Ok(self.storage.transactions.get(linked_id)?.filter(|parent| {
+            parent
+                .splits
+                .iter()
+                .any(|s| s.transfer_transaction_id == Some(txn.id))
+        }))
+    }
+
+    /// Check that a split's category or transfer account can be used
+    fn verify_split_target(&self, txn: &Transaction, split: &Split) -> EnvelopeResult<()> {
+        if let Some(category_id) = split.category_id {
+            self.storage
+                .categories
+                .get_category(category_id)?
+                .ok_or_else(|| EnvelopeError::category_not_found(category_id.to_string()))?;
+        }
+
+        if let Some(account_id) = split.transfer_account_id {
+            let account = self
+                .storage
+                .accounts
+                .get(account_id)?
+                .ok_or_else(|| EnvelopeError::account_not_found(account_id.to_string()))?;
+
+            if account_id == txn.account_id {
+                return Err(EnvelopeError::Validation(
+                    "A split cannot transfer to the transaction's own account".into(),
+                ));
+            }
+            if account.archived {
+                return Err(EnvelopeError::Validation(format!(
+                    "Account '{}' is archived and cannot be used for transfers",
+                    account.name
+                )));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Create the other side of each transfer split and link it to its split
+    ///
+    /// The legs are saved by the caller along with the transaction.
+    ///
+    /// Split legs carry the split's amount unchanged, so they can't go to an
+    /// account in another currency.
+    fn create_split_legs(&self, txn: &mut Transaction) -> EnvelopeResult<Vec<Transaction>> {
+        let source = self.storage.accounts.get(txn.account_id)?;
+        let source_name = source.as_ref().map(|a| a.name.clone()).unwrap_or_default();
+        let source_currency = source.and_then(|a| a.currency);
+
+        let mut legs = Vec::new();
+        for split in txn.splits.iter_mut() {
+            let Some(account_id) = split.transfer_account_id else {
+                continue;
+            };
+            if let Some(target) = self.storage.accounts.get(account_id)? {
+                if target.currency != source_currency {
+                    return Err(EnvelopeError::Validation(format!(
+                        "Cannot split a transfer into '{}': it uses a different currency. Use 'envelope transfer --rate' instead",
+                        target.name
+                    )));
+                }
+            }
+
+            let mut leg = Transaction::new(account_id, txn.date, -split.amount);
+            leg.payee_name = if split.amount.is_negative() {
+                format!("Transfer from {}", source_name)
+            } else {
+                format!("Transfer to {}", source_name)
+            };
+            leg.memo = split.memo.clone();
+            leg.transfer_transaction_id = Some(txn.id);
+            split.transfer_transaction_id = Some(leg.id);
+            legs.push(leg);
+        }
+
+        Ok(legs)
+    }
+
+    /// Delete the transfer legs created by a transaction's splits
+    ///
+    /// Nothing is deleted if any leg is reconciled.
+    fn remove_split_legs(&self, txn: &Transaction) -> EnvelopeResult<()> {
+        let mut legs = Vec::new();
+        for leg_id in txn.splits.iter().filter_map(|s| s.transfer_transaction_id) {
+            if let Some(leg) = self.storage.transactions.get(leg_id)? {
+                if leg.is_locked() {
+                    return Err(EnvelopeError::Locked(format!(
+                        "Transfer split transaction {} is reconciled and cannot be changed. Unlock it first.",
+                        leg.id
+                    )));
+                }
+                legs.push(leg);
+            }
+        }
+
+        for leg in legs {
+            self.storage.transactions.delete(leg.id)?;
+            self.storage.log_delete(
+                EntityType::Transaction,
+                leg.id.to_string(),
+                Some(format!("{} {} (split transfer)", leg.date, leg.payee_name)),
+                &leg,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    /// Save newly created split legs and record them in the audit log
+    fn save_split_legs(&self, legs: Vec<Transaction>) -> EnvelopeResult<()> {
+        for leg in legs {
+            self.storage.transactions.upsert(leg.clone())?;
+            self.storage.log_create(
+                EntityType::Transaction,
+                leg.id.to_string(),
+                Some(format!("{} {} (split transfer)", leg.date, leg.payee_name)),
+                &leg,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+/// Error for attempts to change a starting balance transaction directly
+fn starting_balance_locked(id: TransactionId) -> EnvelopeError {
+    EnvelopeError::Locked(format!(
+        "Transaction {} is an account starting balance. Change it with `envelope account edit --starting-balance`.",
+        id
+    ))
+}
+
+/// Error for attempts to change a transfer leg created by a split directly
+pub(crate) fn split_transfer_leg(id: TransactionId) -> EnvelopeError {
+    EnvelopeError::Validation(format!(
+        "Transaction {} was created by a transfer split. Edit or delete the split transaction instead.",
+        id
+    ))
 }
 
 #[cfg(test)]
@@ -738,6 +1285,32 @@ mod tests {
         assert_eq!(txn.status, TransactionStatus::Pending);
     }
 
+    #[test]
+    fn test_find_by_short_id() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (account_id, _) = setup_test_data(&storage);
+        let service = TransactionService::new(&storage);
+
+        let txn = Transaction::new(
+            account_id,
+            NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            Money::from_cents(-5000),
+        );
+        storage.transactions.upsert(txn.clone()).unwrap();
+
+        let short = txn.id.to_string();
+        assert_eq!(service.find(&short).unwrap().unwrap().id, txn.id);
+        assert_eq!(
+            service
+                .find(&txn.id.as_uuid().to_string())
+                .unwrap()
+                .unwrap()
+                .id,
+            txn.id
+        );
+        assert!(service.find("txn-zzzz").unwrap().is_none());
+    }
+
     #[test]
     fn test_list_transactions() {
         let (_temp_dir, storage) = create_test_storage();
@@ -772,6 +1345,79 @@ mod tests {
         assert_eq!(limited.len(), 2);
     }
 
+    #[test]
+    fn test_search_filters() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (account_id, category_id) = setup_test_data(&storage);
+        let service = TransactionService::new(&storage);
+
+        let savings = Account::new("Savings", AccountType::Savings);
+        let savings_id = savings.id;
+        storage.accounts.upsert(savings).unwrap();
+
+        for (account, cents, payee) in [
+            (account_id, -4300, "AMAZON Marketplace"),
+            (savings_id, -9900, "Amazon Prime"),
+            (account_id, -4250, "Corner Store"),
+        ] {
+            service
+                .create(CreateTransactionInput {
+                    account_id: account,
+                    date: NaiveDate::from_ymd_opt(2025, 4, 10).unwrap(),
+                    amount: Money::from_cents(cents),
+                    payee_name: Some(payee.to_string()),
+                    category_id: Some(category_id),
+                    memo: None,
+                    status: None,
+                })
+                .unwrap();
+        }
+
+        // Payee text ignores case and spans accounts
+        let amazon = service
+            .list(TransactionFilter::new().payee_contains("amazon"))
+            .unwrap();
+        assert_eq!(amazon.len(), 2);
+
+        // Amounts compare by size
+        let around_43 = service
+            .list(
+                TransactionFilter::new()
+                    .amount_min(Money::from_cents(4000))
+                    .amount_max(Money::from_cents(4500)),
+            )
+            .unwrap();
+        assert_eq!(around_43.len(), 2);
+
+        let both = service
+            .list(
+                TransactionFilter::new()
+                    .payee_contains("Amazon")
+                    .amount_max(Money::from_cents(5000)),
+            )
+            .unwrap();
+        assert_eq!(both.len(), 1);
+        assert_eq!(both[0].amount.cents(), -4300);
+
+        // Split memos are searched too
+        let txn = both[0].clone();
+        service
+            .set_splits(
+                txn.id,
+                vec![Split::with_memo(
+                    category_id,
+                    Money::from_cents(-4300),
+                    "USB cable".to_string(),
+                )],
+            )
+            .unwrap();
+        let cable = service
+            .list(TransactionFilter::new().memo_contains("usb"))
+            .unwrap();
+        assert_eq!(cable.len(), 1);
+        assert_eq!(cable[0].id, txn.id);
+    }
+
     #[test]
     fn test_update_transaction() {
         let (_temp_dir, storage) = create_test_storage();
@@ -830,6 +1476,30 @@ mod tests {
         assert_eq!(service.count().unwrap(), 0);
     }
 
+    #[test]
+    fn test_starting_balance_is_immutable() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (account_id, _category_id) = setup_test_data(&storage);
+        let service = TransactionService::new(&storage);
+
+        let txn = Transaction::starting_balance(
+            account_id,
+            NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+            Money::from_cents(100000),
+        );
+        storage.transactions.upsert(txn.clone()).unwrap();
+
+        assert!(matches!(
+            service.delete(txn.id),
+            Err(EnvelopeError::Locked(_))
+        ));
+        assert!(matches!(
+            service.unlock(txn.id),
+            Err(EnvelopeError::Locked(_))
+        ));
+        assert_eq!(service.count().unwrap(), 1);
+    }
+
     #[test]
     fn test_status_transitions() {
         let (_temp_dir, storage) = create_test_storage();
@@ -964,4 +1634,266 @@ mod tests {
         assert_eq!(final_txn.splits.len(), 2);
         assert!(final_txn.validate().is_ok());
     }
+
+    #[test]
+    fn test_transfer_split() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (account_id, category_id) = setup_test_data(&storage);
+        let service = TransactionService::new(&storage);
+
+        let cash = Account::new("Cash", AccountType::Cash);
+        let cash_id = cash.id;
+        storage.accounts.upsert(cash).unwrap();
+
+        let input = CreateTransactionInput {
+            account_id,
+            date: NaiveDate::from_ymd_opt(2025, 1, 15).unwrap(),
+            amount: Money::from_cents(-10000),
+            payee_name: Some("Grocery Store".to_string()),
+            category_id: None,
+            memo: None,
+            status: None,
+        };
+        let txn = service.create(input).unwrap();
+
+        let splits = vec![
+            Split::new(category_id, Money::from_cents(-8000)),
+            Split::transfer(cash_id, Money::from_cents(-2000)),
+        ];
+        let txn = service.set_splits(txn.id, splits).unwrap();
+
+        // The cash back shows up as an inflow in the other account
+        let leg_id = txn.splits[1].transfer_transaction_id.unwrap();
+        let leg = service.get(leg_id).unwrap().unwrap();
+        assert_eq!(leg.account_id, cash_id);
+        assert_eq!(leg.amount.cents(), 2000);
+        assert_eq!(leg.transfer_transaction_id, Some(txn.id));
+        assert_eq!(leg.payee_name, "Transfer from Checking");
+
+        // The leg can only change through its split transaction
+        assert!(matches!(
+            service.delete(leg_id),
+            Err(EnvelopeError::Validation(_))
+        ));
+
+        // Replacing the splits replaces the leg
+        let txn = service
+            .set_splits(
+                txn.id,
+                vec![
+                    Split::new(category_id, Money::from_cents(-7000)),
+                    Split::transfer(cash_id, Money::from_cents(-3000)),
+                ],
+            )
+            .unwrap();
+        assert!(service.get(leg_id).unwrap().is_none());
+        let leg_id = txn.splits[1].transfer_transaction_id.unwrap();
+        assert_eq!(service.get(leg_id).unwrap().unwrap().amount.cents(), 3000);
+
+        // Deleting the split transaction removes the leg too
+        service.delete(txn.id).unwrap();
+        assert!(service.get(leg_id).unwrap().is_none());
+        assert_eq!(service.count().unwrap(), 0);
+    }
+
+    #[test]
+    fn test_tags_and_tag_filter() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (account_id, category_id) = setup_test_data(&storage);
+        let service = TransactionService::new(&storage);
+
+        let mut ids = Vec::new();
+        for i in 1..=2 {
+            let txn = service
+                .create(CreateTransactionInput {
+                    account_id,
+                    date: NaiveDate::from_ymd_opt(2025, 1, i).unwrap(),
+                    amount: Money::from_cents(-1000),
+                    payee_name: None,
+                    category_id: Some(category_id),
+                    memo: None,
+                    status: None,
+                })
+                .unwrap();
+            ids.push(txn.id);
+        }
+
+        // Reconciled transactions can still be tagged
+        service
+            .set_status(ids[0], TransactionStatus::Reconciled)
+            .unwrap();
+        let tagged = service
+            .add_tags(ids[0], &["#Tax-Deductible".to_string()])
+            .unwrap();
+        assert_eq!(tagged.tags, vec!["tax-deductible"]);
+
+        let matches = service
+            .list(TransactionFilter::new().tag("tax-deductible"))
+            .unwrap();
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].id, ids[0]);
+
+        service
+            .remove_tags(ids[0], &["tax-deductible".to_string()])
+            .unwrap();
+        assert!(service
+            .list(TransactionFilter::new().tag("tax-deductible"))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_set_flag_and_flag_filter() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (account_id, category_id) = setup_test_data(&storage);
+        let service = TransactionService::new(&storage);
+
+        let txn = service
+            .create(CreateTransactionInput {
+                account_id,
+                date: NaiveDate::from_ymd_opt(2025, 1, 1).unwrap(),
+                amount: Money::from_cents(-1000),
+                payee_name: None,
+                category_id: Some(category_id),
+                memo: None,
+                status: None,
+            })
+            .unwrap();
+
+        // Reconciled transactions can still be flagged
+        service
+            .set_status(txn.id, TransactionStatus::Reconciled)
+            .unwrap();
+        let flagged = service.set_flag(txn.id, TransactionFlag::Review).unwrap();
+        assert_eq!(flagged.flag, TransactionFlag::Review);
+
+        let review = service
+            .list(TransactionFilter::new().flag(TransactionFlag::Review))
+            .unwrap();
+        assert_eq!(review.len(), 1);
+
+        let entries = storage.read_audit_history(&txn.id.to_string()).unwrap();
+        assert_eq!(
+            entries.last().unwrap().diff_summary.as_deref(),
+            Some("flag: None -> Review")
+        );
+
+        service.set_flag(txn.id, TransactionFlag::None).unwrap();
+        assert!(service
+            .list(TransactionFilter::new().flag(TransactionFlag::Review))
+            .unwrap()
+            .is_empty());
+    }
+
+    #[test]
+    fn test_default_category_resolution() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (account_id, category_id) = setup_test_data(&storage);
+        let service = TransactionService::new(&storage);
+
+        assert_eq!(service.default_category(account_id, None).unwrap(), None);
+        assert_eq!(
+            service
+                .default_category(account_id, Some(category_id))
+                .unwrap(),
+            Some(category_id)
+        );
+
+        // The account's default overrides the global one
+        let group_id = storage.categories.get_all_groups().unwrap()[0].id;
+        let dining = Category::new("Dining", group_id);
+        storage.categories.upsert_category(dining.clone()).unwrap();
+        crate::services::AccountService::new(&storage)
+            .set_default_category(account_id, Some(dining.id))
+            .unwrap();
+        assert_eq!(
+            service
+                .default_category(account_id, Some(category_id))
+                .unwrap(),
+            Some(dining.id)
+        );
+
+        // Deleted categories fall back instead of failing
+        storage.categories.delete_category(dining.id).unwrap();
+        assert_eq!(
+            service
+                .default_category(account_id, Some(category_id))
+                .unwrap(),
+            Some(category_id)
+        );
+        storage.categories.delete_category(category_id).unwrap();
+        assert_eq!(
+            service
+                .default_category(account_id, Some(category_id))
+                .unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_categorize_payee() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (account_id, category_id) = setup_test_data(&storage);
+        let service = TransactionService::new(&storage);
+
+        let savings = Account::new("Savings", AccountType::Savings);
+        storage.accounts.upsert(savings.clone()).unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let add = |account_id, payee: &str, category_id| {
+            service
+                .create(CreateTransactionInput {
+                    account_id,
+                    date,
+                    amount: Money::from_cents(-500),
+                    payee_name: Some(payee.to_string()),
+                    category_id,
+                    memo: None,
+                    status: None,
+                })
+                .unwrap()
+        };
+
+        add(account_id, "Corner Cafe", None);
+        add(savings.id, "corner cafe", None);
+        add(account_id, "Corner Cafe", Some(category_id));
+        add(account_id, "Bookshop", None);
+        let locked = add(account_id, "Corner Cafe", None);
+        service
+            .set_status(locked.id, TransactionStatus::Reconciled)
+            .unwrap();
+
+        assert_eq!(
+            service
+                .uncategorized_for_payee("CORNER CAFE")
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            service
+                .categorize_payee("Corner Cafe", category_id)
+                .unwrap(),
+            2
+        );
+        assert!(service
+            .uncategorized_for_payee("Corner Cafe")
+            .unwrap()
+            .is_empty());
+
+        // Locked and other payees' transactions are untouched
+        assert_eq!(
+            storage
+                .transactions
+                .get(locked.id)
+                .unwrap()
+                .unwrap()
+                .category_id,
+            None
+        );
+        assert_eq!(
+            service.uncategorized_for_payee("Bookshop").unwrap().len(),
+            1
+        );
+    }
 }
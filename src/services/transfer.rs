@@ -4,9 +4,11 @@
 //! Transfers create linked transaction pairs - an outflow from the source
 //! account and an inflow to the destination account.
 
-use chrono::{NaiveDate, Utc};
+use chrono::NaiveDate;
 
+use super::transaction::split_transfer_leg;
 use crate::audit::EntityType;
+use crate::config::clock;
 use crate::error::{EnvelopeError, EnvelopeResult};
 use crate::models::{Account, AccountId, Money, Transaction, TransactionId};
 
@@ -17,6 +19,7 @@ use crate::storage::Storage;
 /// Service for managing transfers between accounts
 pub struct TransferService<'a> {
     storage: &'a Storage,
+    exchange_rate: Option<f64>,
 }
 
 /// Result of creating a transfer
@@ -31,7 +34,55 @@ pub struct TransferResult {
 impl<'a> TransferService<'a> {
     /// Create a new transfer service
     pub fn new(storage: &'a Storage) -> Self {
-        Self { storage }
+        Self {
+            storage,
+            exchange_rate: None,
+        }
+    }
+
+    /// Set the rate for transfers between accounts in different currencies
+    ///
+    /// The rate is units of the destination currency per unit of the source
+    /// currency. Without one, such transfers are rejected.
+    pub fn with_exchange_rate(mut self, rate: f64) -> Self {
+        self.exchange_rate = Some(rate);
+        self
+    }
+
+    /// Amount the destination account receives for `amount` sent
+    fn received_amount(
+        &self,
+        from: &Account,
+        to: &Account,
+        amount: Money,
+    ) -> EnvelopeResult<Money> {
+        if from.currency == to.currency {
+            return Ok(amount);
+        }
+
+        let rate = self.exchange_rate.ok_or_else(|| {
+            EnvelopeError::Validation(format!(
+                "'{}' ({}) and '{}' ({}) use different currencies; give an exchange rate to transfer between them",
+                from.name,
+                from.currency.as_deref().unwrap_or("base currency"),
+                to.name,
+                to.currency.as_deref().unwrap_or("base currency"),
+            ))
+        })?;
+        if !(rate.is_finite() && rate > 0.0) {
+            return Err(EnvelopeError::Validation(format!(
+                "Exchange rate must be a positive number, got {}",
+                rate
+            )));
+        }
+
+        let received = Money::from_cents((amount.cents() as f64 * rate).round() as i64);
+        if received.is_zero() {
+            return Err(EnvelopeError::Validation(
+                "Transfer amount rounds to zero in the destination currency".into(),
+            ));
+        }
+        Ok(received)
     }
 
     /// Create a transfer between two accounts
@@ -39,6 +90,14 @@ impl<'a> TransferService<'a> {
     /// This creates two linked transactions:
     /// - An outflow (negative amount) from the source account
     /// - An inflow (positive amount) to the destination account
+    ///
+    /// Each leg is in its own account's currency. Between accounts in
+    /// different currencies the inflow is converted with the rate from
+    /// [`TransferService::with_exchange_rate`], which is required.
+    ///
+    /// Neither leg is categorized, and validation refuses one that is, so a
+    /// transfer never shows up as category activity or income. That holds for
+    /// transfers to and from off-budget accounts too.
     pub fn create_transfer(
         &self,
         from_account_id: AccountId,
@@ -69,6 +128,7 @@ impl<'a> TransferService<'a> {
         // Verify both accounts exist and are not archived
         let from_account = self.get_active_account(from_account_id)?;
         let to_account = self.get_active_account(to_account_id)?;
+        let received = self.received_amount(&from_account, &to_account, amount)?;
 
         // Create the outflow transaction (from source)
         let mut from_txn = Transaction::new(from_account_id, date, -amount);
@@ -78,7 +138,7 @@ impl<'a> TransferService<'a> {
         }
 
         // Create the inflow transaction (to destination)
-        let mut to_txn = Transaction::new(to_account_id, date, amount);
+        let mut to_txn = Transaction::new(to_account_id, date, received);
         to_txn.payee_name = format!("Transfer from {}", from_account.name);
         if let Some(ref m) = memo {
             to_txn.memo = m.clone();
@@ -183,6 +243,10 @@ impl<'a> TransferService<'a> {
             .get(linked_id)?
             .ok_or_else(|| EnvelopeError::transaction_not_found(linked_id.to_string()))?;
 
+        if linked_txn.is_split() {
+            return Err(split_transfer_leg(transaction_id));
+        }
+
         if linked_txn.is_locked() {
             return Err(EnvelopeError::Locked(format!(
                 "Linked transaction {} is reconciled and cannot be edited",
@@ -193,20 +257,28 @@ impl<'a> TransferService<'a> {
         let txn_before = txn.clone();
         let linked_before = linked_txn.clone();
 
-        // Determine which transaction is the outflow (negative) and which is the inflow (positive)
+        // Legs of a transfer between currencies differ in size; keep their ratio
         let amount = new_amount.abs();
+        let linked_amount = if linked_txn.amount.abs() == txn.amount.abs() {
+            amount
+        } else {
+            let ratio = linked_txn.amount.cents().abs() as f64 / txn.amount.cents().abs() as f64;
+            Money::from_cents((amount.cents() as f64 * ratio).round() as i64)
+        };
+
+        // Determine which transaction is the outflow (negative) and which is the inflow (positive)
         if txn.amount.is_negative() {
             // txn is the outflow
             txn.amount = -amount;
-            linked_txn.amount = amount;
+            linked_txn.amount = linked_amount;
         } else {
             // txn is the inflow
             txn.amount = amount;
-            linked_txn.amount = -amount;
+            linked_txn.amount = -linked_amount;
         }
 
-        txn.updated_at = Utc::now();
-        linked_txn.updated_at = Utc::now();
+        txn.updated_at = clock::now();
+        linked_txn.updated_at = clock::now();
 
         // Validate both
         txn.validate()
@@ -296,6 +368,10 @@ impl<'a> TransferService<'a> {
             .get(linked_id)?
             .ok_or_else(|| EnvelopeError::transaction_not_found(linked_id.to_string()))?;
 
+        if linked_txn.is_split() {
+            return Err(split_transfer_leg(transaction_id));
+        }
+
         if linked_txn.is_locked() {
             return Err(EnvelopeError::Locked(format!(
                 "Linked transaction {} is reconciled and cannot be edited",
@@ -308,8 +384,8 @@ impl<'a> TransferService<'a> {
 
         txn.date = new_date;
         linked_txn.date = new_date;
-        txn.updated_at = Utc::now();
-        linked_txn.updated_at = Utc::now();
+        txn.updated_at = clock::now();
+        linked_txn.updated_at = clock::now();
 
         // Save both
         self.storage.transactions.upsert(txn.clone())?;
@@ -383,6 +459,10 @@ impl<'a> TransferService<'a> {
             .get(linked_id)?
             .ok_or_else(|| EnvelopeError::transaction_not_found(linked_id.to_string()))?;
 
+        if linked_txn.is_split() {
+            return Err(split_transfer_leg(transaction_id));
+        }
+
         if linked_txn.is_locked() {
             return Err(EnvelopeError::Locked(format!(
                 "Linked transaction {} is reconciled and cannot be deleted",
@@ -543,6 +623,45 @@ mod tests {
         assert!(matches!(result, Err(EnvelopeError::Validation(_))));
     }
 
+    #[test]
+    fn test_cross_currency_transfer_needs_rate() {
+        let (_temp_dir, storage) = create_test_storage();
+        let (checking_id, savings_id) = setup_test_accounts(&storage);
+        let mut savings = storage.accounts.get(savings_id).unwrap().unwrap();
+        savings.currency = Some("EUR".to_string());
+        storage.accounts.upsert(savings).unwrap();
+
+        let date = NaiveDate::from_ymd_opt(2025, 1, 15).unwrap();
+        let result = TransferService::new(&storage).create_transfer(
+            checking_id,
+            savings_id,
+            Money::from_cents(10000),
+            date,
+            None,
+        );
+        assert!(matches!(result, Err(EnvelopeError::Validation(_))));
+
+        // 100.00 USD at 0.92 EUR per USD
+        let service = TransferService::new(&storage).with_exchange_rate(0.92);
+        let result = service
+            .create_transfer(
+                checking_id,
+                savings_id,
+                Money::from_cents(10000),
+                date,
+                None,
+            )
+            .unwrap();
+        assert_eq!(result.from_transaction.amount.cents(), -10000);
+        assert_eq!(result.to_transaction.amount.cents(), 9200);
+
+        // Editing the amount keeps the rate between the legs
+        let updated = service
+            .update_transfer_amount(result.from_transaction.id, Money::from_cents(20000))
+            .unwrap();
+        assert_eq!(updated.to_transaction.amount.cents(), 18400);
+    }
+
     #[test]
     fn test_update_transfer_amount() {
         let (_temp_dir, storage) = create_test_storage();
@@ -6,9 +6,8 @@ use std::io::{self, Write};
 
 use crate::config::{paths::EnvelopePaths, settings::Settings};
 use crate::error::{EnvelopeError, EnvelopeResult};
-use crate::models::{Account, Money, TransactionStatus};
+use crate::models::{Account, Money};
 use crate::services::account::AccountService;
-use crate::services::transaction::{CreateTransactionInput, TransactionService};
 use crate::storage::Storage;
 
 use super::steps::{
@@ -124,21 +123,6 @@ impl SetupWizard {
             account_result.account.on_budget,
         )?;
 
-        // Create starting balance transaction if non-zero
-        if !account_result.starting_balance.is_zero() {
-            let txn_service = TransactionService::new(storage);
-            let input = CreateTransactionInput {
-                account_id: saved_account.id,
-                date: chrono::Local::now().naive_local().date(),
-                amount: account_result.starting_balance,
-                payee_name: Some("Starting Balance".to_string()),
-                category_id: None,
-                memo: Some("Initial account balance".to_string()),
-                status: Some(TransactionStatus::Cleared),
-            };
-            txn_service.create(input)?;
-        }
-
         // Update settings
         settings.budget_period_type = period_result.period_type;
         settings.setup_completed = true;
@@ -10,6 +10,7 @@ use crate::error::EnvelopeError;
 use crate::models::{Account, AccountId};
 
 use super::file_io::{read_json, write_json_atomic};
+use super::guard::WriteGuard;
 
 /// Serializable account data structure
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
@@ -20,6 +21,7 @@ struct AccountData {
 /// Repository for account persistence
 pub struct AccountRepository {
     path: PathBuf,
+    guard: WriteGuard,
     data: RwLock<HashMap<AccountId, Account>>,
 }
 
@@ -28,10 +30,17 @@ impl AccountRepository {
     pub fn new(path: PathBuf) -> Self {
         Self {
             path,
+            guard: WriteGuard::default(),
             data: RwLock::new(HashMap::new()),
         }
     }
 
+    /// Share a read-only guard with the rest of the storage
+    pub fn with_guard(mut self, guard: WriteGuard) -> Self {
+        self.guard = guard;
+        self
+    }
+
     /// Load accounts from disk
     pub fn load(&self) -> Result<(), EnvelopeError> {
         let file_data: AccountData = read_json(&self.path)?;
@@ -51,6 +60,8 @@ impl AccountRepository {
 
     /// Save accounts to disk
     pub fn save(&self) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let data = self
             .data
             .read()
@@ -107,6 +118,8 @@ impl AccountRepository {
 
     /// Insert or update an account
     pub fn upsert(&self, account: Account) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let mut data = self
             .data
             .write()
@@ -118,6 +131,8 @@ impl AccountRepository {
 
     /// Delete an account
     pub fn delete(&self, id: AccountId) -> Result<bool, EnvelopeError> {
+        self.guard.check()?;
+
         let mut data = self
             .data
             .write()
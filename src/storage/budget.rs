@@ -10,6 +10,7 @@ use crate::error::EnvelopeError;
 use crate::models::{BudgetAllocation, BudgetPeriod, CategoryId};
 
 use super::file_io::{read_json, write_json_atomic};
+use super::guard::WriteGuard;
 
 /// Serializable budget data (extends CategoryData)
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
@@ -37,6 +38,7 @@ impl AllocationKey {
 /// Repository for budget allocation persistence
 pub struct BudgetRepository {
     path: PathBuf,
+    guard: WriteGuard,
     allocations: RwLock<HashMap<AllocationKey, BudgetAllocation>>,
 }
 
@@ -45,10 +47,17 @@ impl BudgetRepository {
     pub fn new(path: PathBuf) -> Self {
         Self {
             path,
+            guard: WriteGuard::default(),
             allocations: RwLock::new(HashMap::new()),
         }
     }
 
+    /// Share a read-only guard with the rest of the storage
+    pub fn with_guard(mut self, guard: WriteGuard) -> Self {
+        self.guard = guard;
+        self
+    }
+
     /// Load allocations from disk
     pub fn load(&self) -> Result<(), EnvelopeError> {
         let file_data: BudgetData = read_json(&self.path)?;
@@ -69,6 +78,8 @@ impl BudgetRepository {
 
     /// Save allocations to disk
     pub fn save(&self) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let allocations = self
             .allocations
             .read()
@@ -150,6 +161,8 @@ impl BudgetRepository {
 
     /// Insert or update an allocation
     pub fn upsert(&self, allocation: BudgetAllocation) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let mut allocations = self
             .allocations
             .write()
@@ -166,6 +179,8 @@ impl BudgetRepository {
         category_id: CategoryId,
         period: &BudgetPeriod,
     ) -> Result<bool, EnvelopeError> {
+        self.guard.check()?;
+
         let mut allocations = self
             .allocations
             .write()
@@ -177,6 +192,8 @@ impl BudgetRepository {
 
     /// Delete all allocations for a category
     pub fn delete_for_category(&self, category_id: CategoryId) -> Result<usize, EnvelopeError> {
+        self.guard.check()?;
+
         let mut allocations = self
             .allocations
             .write()
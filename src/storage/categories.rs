@@ -10,6 +10,7 @@ use crate::error::EnvelopeError;
 use crate::models::{Category, CategoryGroup, CategoryGroupId, CategoryId};
 
 use super::file_io::{read_json, write_json_atomic};
+use super::guard::WriteGuard;
 
 /// Serializable category data structure
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
@@ -21,6 +22,7 @@ pub struct CategoryData {
 /// Repository for category and group persistence
 pub struct CategoryRepository {
     path: PathBuf,
+    guard: WriteGuard,
     groups: RwLock<HashMap<CategoryGroupId, CategoryGroup>>,
     categories: RwLock<HashMap<CategoryId, Category>>,
 }
@@ -30,11 +32,18 @@ impl CategoryRepository {
     pub fn new(path: PathBuf) -> Self {
         Self {
             path,
+            guard: WriteGuard::default(),
             groups: RwLock::new(HashMap::new()),
             categories: RwLock::new(HashMap::new()),
         }
     }
 
+    /// Share a read-only guard with the rest of the storage
+    pub fn with_guard(mut self, guard: WriteGuard) -> Self {
+        self.guard = guard;
+        self
+    }
+
     /// Load categories from disk
     pub fn load(&self) -> Result<(), EnvelopeError> {
         let file_data: CategoryData = read_json(&self.path)?;
@@ -64,6 +73,8 @@ impl CategoryRepository {
 
     /// Save categories to disk
     pub fn save(&self) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let groups = self
             .groups
             .read()
@@ -127,6 +138,8 @@ impl CategoryRepository {
 
     /// Insert or update a group
     pub fn upsert_group(&self, group: CategoryGroup) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let mut groups = self
             .groups
             .write()
@@ -142,6 +155,8 @@ impl CategoryRepository {
         id: CategoryGroupId,
         delete_categories: bool,
     ) -> Result<bool, EnvelopeError> {
+        self.guard.check()?;
+
         let mut groups = self
             .groups
             .write()
@@ -216,6 +231,8 @@ impl CategoryRepository {
 
     /// Insert or update a category
     pub fn upsert_category(&self, category: Category) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let mut categories = self
             .categories
             .write()
@@ -227,6 +244,8 @@ impl CategoryRepository {
 
     /// Delete a category
     pub fn delete_category(&self, id: CategoryId) -> Result<bool, EnvelopeError> {
+        self.guard.check()?;
+
         let mut categories = self
             .categories
             .write()
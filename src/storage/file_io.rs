@@ -22,12 +22,7 @@ where
         return Ok(T::default());
     }
 
-    let file = File::open(path)
-        .map_err(|e| EnvelopeError::Storage(format!("Failed to open {}: {}", path.display(), e)))?;
-
-    let reader = BufReader::new(file);
-    serde_json::from_reader(reader)
-        .map_err(|e| EnvelopeError::Storage(format!("Failed to parse {}: {}", path.display(), e)))
+    parse_json_file(path)
 }
 
 /// Read JSON from a file, returning an error if file doesn't exist
@@ -45,12 +40,32 @@ where
         )));
     }
 
-    let file = File::open(path)
+    parse_json_file(path)
+}
+
+/// Read and parse an existing JSON file
+///
+/// Parse failures are reported as [`EnvelopeError::Corrupted`] with the
+/// byte offset of the problem, so callers can offer to restore the file.
+fn parse_json_file<T: DeserializeOwned>(path: &Path) -> Result<T, EnvelopeError> {
+    let contents = fs::read_to_string(path)
         .map_err(|e| EnvelopeError::Storage(format!("Failed to open {}: {}", path.display(), e)))?;
 
-    let reader = BufReader::new(file);
-    serde_json::from_reader(reader)
-        .map_err(|e| EnvelopeError::Storage(format!("Failed to parse {}: {}", path.display(), e)))
+    serde_json::from_str(&contents).map_err(|e| EnvelopeError::Corrupted {
+        path: path.to_path_buf(),
+        offset: byte_offset(&contents, e.line(), e.column()),
+        message: e.to_string(),
+    })
+}
+
+/// Convert serde_json's 1-based line and column into a byte offset
+fn byte_offset(contents: &str, line: usize, column: usize) -> usize {
+    let line_start: usize = contents
+        .split_inclusive('\n')
+        .take(line.saturating_sub(1))
+        .map(str::len)
+        .sum();
+    (line_start + column.saturating_sub(1)).min(contents.len())
 }
 
 /// Write JSON to a file atomically (write to temp, then rename)
@@ -143,6 +158,21 @@ mod tests {
         assert_eq!(data, TestData::default());
     }
 
+    #[test]
+    fn test_read_corrupted_reports_file_and_offset() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("truncated.json");
+        fs::write(&path, "{\n  \"name\": \"test\",\n  \"value\": ").unwrap();
+
+        let err = read_json::<TestData, _>(&path).unwrap_err();
+        assert_eq!(err.corrupted_path(), Some(path.as_path()));
+        match err {
+            // Truncated on the third line, which starts at byte 20
+            EnvelopeError::Corrupted { offset, .. } => assert!((20..=31).contains(&offset)),
+            other => panic!("unexpected error: {:?}", other),
+        }
+    }
+
     #[test]
     fn test_write_and_read() {
         let temp_dir = TempDir::new().unwrap();
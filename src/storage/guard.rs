@@ -0,0 +1,36 @@
+//! Read-only switch shared by the repositories
+//!
+//! One guard is created by [`super::Storage`] and handed to every
+//! repository, so turning on read-only mode blocks writes everywhere at once.
+
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crate::error::EnvelopeError;
+
+/// Shared flag that rejects writes while read-only mode is on
+#[derive(Debug, Clone, Default)]
+pub struct WriteGuard {
+    read_only: Arc<AtomicBool>,
+}
+
+impl WriteGuard {
+    /// Turn read-only mode on or off
+    pub fn set_read_only(&self, read_only: bool) {
+        self.read_only.store(read_only, Ordering::Relaxed);
+    }
+
+    /// Check whether read-only mode is on
+    pub fn is_read_only(&self) -> bool {
+        self.read_only.load(Ordering::Relaxed)
+    }
+
+    /// Fail with [`EnvelopeError::ReadOnly`] if writes are blocked
+    pub fn check(&self) -> Result<(), EnvelopeError> {
+        if self.is_read_only() {
+            Err(EnvelopeError::ReadOnly)
+        } else {
+            Ok(())
+        }
+    }
+}
@@ -10,6 +10,7 @@ use crate::error::EnvelopeError;
 use crate::models::{BudgetPeriod, IncomeExpectation, IncomeId};
 
 use super::file_io::{read_json, write_json_atomic};
+use super::guard::WriteGuard;
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 struct IncomeData {
@@ -20,6 +21,7 @@ struct IncomeData {
 /// Repository for income expectations
 pub struct IncomeRepository {
     path: PathBuf,
+    guard: WriteGuard,
     expectations: RwLock<HashMap<BudgetPeriod, IncomeExpectation>>,
 }
 
@@ -28,10 +30,17 @@ impl IncomeRepository {
     pub fn new(path: PathBuf) -> Self {
         Self {
             path,
+            guard: WriteGuard::default(),
             expectations: RwLock::new(HashMap::new()),
         }
     }
 
+    /// Share a read-only guard with the rest of the storage
+    pub fn with_guard(mut self, guard: WriteGuard) -> Self {
+        self.guard = guard;
+        self
+    }
+
     /// Load expectations from disk
     pub fn load(&self) -> Result<(), EnvelopeError> {
         let file_data: IncomeData = read_json(&self.path)?;
@@ -51,6 +60,8 @@ impl IncomeRepository {
 
     /// Save expectations to disk
     pub fn save(&self) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let expectations = self
             .expectations
             .read()
@@ -82,6 +93,8 @@ impl IncomeRepository {
 
     /// Upsert an income expectation (insert or update)
     pub fn upsert(&self, expectation: IncomeExpectation) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let mut expectations = self
             .expectations
             .write()
@@ -93,6 +106,10 @@ impl IncomeRepository {
 
     /// Delete income expectation for a period
     pub fn delete_for_period(&self, period: &BudgetPeriod) -> Option<IncomeExpectation> {
+        if self.guard.is_read_only() {
+            return None;
+        }
+
         let mut expectations = self.expectations.write().ok()?;
         expectations.remove(period)
     }
@@ -8,9 +8,11 @@ pub mod accounts;
 pub mod budget;
 pub mod categories;
 pub mod file_io;
+pub mod guard;
 pub mod income;
 pub mod init;
 pub mod payees;
+pub mod scheduled;
 pub mod targets;
 pub mod transactions;
 
@@ -18,13 +20,18 @@ pub use accounts::AccountRepository;
 pub use budget::BudgetRepository;
 pub use categories::CategoryRepository;
 pub use file_io::{read_json, write_json_atomic};
+pub use guard::WriteGuard;
 pub use income::IncomeRepository;
 pub use init::initialize_storage;
 pub use payees::PayeeRepository;
+pub use scheduled::ScheduledRepository;
 pub use targets::TargetRepository;
 pub use transactions::TransactionRepository;
 
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use serde::{Deserialize, Serialize};
 
 use crate::audit::{AuditEntry, AuditLogger, EntityType};
 use crate::backup::{BackupManager, RestoreManager, RestoreResult};
@@ -43,7 +50,25 @@ pub struct Storage {
     pub payees: PayeeRepository,
     pub targets: TargetRepository,
     pub income: IncomeRepository,
+    pub scheduled: ScheduledRepository,
     audit: AuditLogger,
+    guard: WriteGuard,
+    backup_retention: BackupRetention,
+    /// Whether a destructive operation is under way, so its audit entries
+    /// extend the undo marker
+    destructive_open: AtomicBool,
+}
+
+/// Filename, in the backup directory, of the marker [`Storage::undo_last`] reads
+const UNDO_MARKER_FILE: &str = "undo.json";
+
+/// What undo needs to know about the latest destructive operation
+#[derive(Debug, Serialize, Deserialize)]
+struct UndoMarker {
+    /// Filename of the backup taken just before the operation
+    backup: String,
+    /// Length of the audit log once the operation finished
+    audit_entries: usize,
 }
 
 impl Storage {
@@ -53,20 +78,53 @@ impl Storage {
         paths.ensure_directories()?;
 
         let audit = AuditLogger::new(paths.audit_log());
+        let guard = WriteGuard::default();
 
         Ok(Self {
-            accounts: AccountRepository::new(paths.accounts_file()),
-            transactions: TransactionRepository::new(paths.transactions_file()),
-            categories: CategoryRepository::new(paths.budget_file()),
-            budget: BudgetRepository::new(paths.allocations_file()),
-            payees: PayeeRepository::new(paths.payees_file()),
-            targets: TargetRepository::new(paths.targets_file()),
-            income: IncomeRepository::new(paths.income_file()),
+            accounts: AccountRepository::new(paths.accounts_file()).with_guard(guard.clone()),
+            transactions: TransactionRepository::new(paths.transactions_file())
+                .with_guard(guard.clone()),
+            categories: CategoryRepository::new(paths.budget_file()).with_guard(guard.clone()),
+            budget: BudgetRepository::new(paths.allocations_file()).with_guard(guard.clone()),
+            payees: PayeeRepository::new(paths.payees_file()).with_guard(guard.clone()),
+            targets: TargetRepository::new(paths.targets_file()).with_guard(guard.clone()),
+            income: IncomeRepository::new(paths.income_file()).with_guard(guard.clone()),
+            scheduled: ScheduledRepository::new(paths.scheduled_file()).with_guard(guard.clone()),
             audit,
+            guard,
+            backup_retention: BackupRetention::default(),
+            destructive_open: AtomicBool::new(false),
             paths,
         })
     }
 
+    /// Use the configured retention policy for the backups storage takes itself
+    ///
+    /// Applies to [`Storage::create_backup`], the backups taken before
+    /// destructive changes, and undo.
+    pub fn with_backup_retention(mut self, retention: BackupRetention) -> Self {
+        self.backup_retention = retention;
+        self
+    }
+
+    /// Turn read-only mode on or off
+    ///
+    /// While on, every repository rejects saves, upserts, and deletes with
+    /// [`EnvelopeError::ReadOnly`]. Reading, backups, and exports still work.
+    pub fn set_read_only(&self, read_only: bool) {
+        self.guard.set_read_only(read_only);
+    }
+
+    /// Check whether read-only mode is on
+    pub fn is_read_only(&self) -> bool {
+        self.guard.is_read_only()
+    }
+
+    /// Fail with [`EnvelopeError::ReadOnly`] in read-only mode
+    pub fn check_writable(&self) -> EnvelopeResult<()> {
+        self.guard.check()
+    }
+
     /// Get the paths configuration
     pub fn paths(&self) -> &EnvelopePaths {
         &self.paths
@@ -79,7 +137,17 @@ impl Storage {
 
     /// Log an audit entry
     pub fn log_audit(&self, entry: &AuditEntry) -> EnvelopeResult<()> {
-        self.audit.log(entry)
+        self.guard.check()?;
+        self.audit.log(entry)?;
+
+        // Entries logged by the destructive operation itself are part of what undo reverts
+        if self.destructive_open.load(Ordering::SeqCst) {
+            if let Some(mut marker) = self.read_undo_marker()? {
+                marker.audit_entries = self.audit.entry_count()?;
+                self.write_undo_marker(&marker)?;
+            }
+        }
+        Ok(())
     }
 
     /// Log a create operation
@@ -91,7 +159,7 @@ impl Storage {
         entity: &T,
     ) -> EnvelopeResult<()> {
         let entry = AuditEntry::create(entity_type, entity_id, entity_name, entity);
-        self.audit.log(&entry)
+        self.log_audit(&entry)
     }
 
     /// Log an update operation
@@ -112,7 +180,7 @@ impl Storage {
             after,
             diff_summary,
         );
-        self.audit.log(&entry)
+        self.log_audit(&entry)
     }
 
     /// Log a delete operation
@@ -124,7 +192,7 @@ impl Storage {
         entity: &T,
     ) -> EnvelopeResult<()> {
         let entry = AuditEntry::delete(entity_type, entity_id, entity_name, entity);
-        self.audit.log(&entry)
+        self.log_audit(&entry)
     }
 
     /// Read recent audit entries
@@ -132,8 +200,18 @@ impl Storage {
         self.audit.read_recent(count)
     }
 
+    /// Read the audit history of a single entity, oldest first
+    pub fn read_audit_history(&self, entity_id: &str) -> EnvelopeResult<Vec<AuditEntry>> {
+        self.audit.read_for_entity(entity_id)
+    }
+
     /// Load all data from disk
     pub fn load_all(&mut self) -> Result<(), EnvelopeError> {
+        self.reload_all()
+    }
+
+    /// Reload every repository from disk, discarding in-memory state
+    fn reload_all(&self) -> Result<(), EnvelopeError> {
         self.accounts.load()?;
         self.transactions.load()?;
         self.categories.load()?;
@@ -141,6 +219,7 @@ impl Storage {
         self.payees.load()?;
         self.targets.load()?;
         self.income.load()?;
+        self.scheduled.load()?;
         Ok(())
     }
 
@@ -153,6 +232,7 @@ impl Storage {
         self.payees.save()?;
         self.targets.save()?;
         self.income.save()?;
+        self.scheduled.save()?;
         Ok(())
     }
 
@@ -163,11 +243,10 @@ impl Storage {
 
     /// Create a backup of all data
     ///
-    /// Creates a backup using the default retention policy.
+    /// Creates a backup using the storage's retention policy.
     /// Returns the path to the created backup file.
     pub fn create_backup(&self) -> EnvelopeResult<PathBuf> {
-        let retention = BackupRetention::default();
-        let manager = BackupManager::new(self.paths.clone(), retention);
+        let manager = self.backup_manager(self.backup_retention.clone());
         manager.create_backup()
     }
 
@@ -185,6 +264,8 @@ impl Storage {
     /// WARNING: This will overwrite all current data.
     /// It's recommended to create a backup before restoring.
     pub fn restore_from_backup(&mut self, backup_path: &Path) -> EnvelopeResult<RestoreResult> {
+        self.guard.check()?;
+
         let restore_manager = RestoreManager::new(self.paths.clone());
         let result = restore_manager.restore_from_file(backup_path)?;
 
@@ -199,34 +280,114 @@ impl Storage {
         BackupManager::new(self.paths.clone(), retention)
     }
 
-    /// Create a backup before a destructive operation if needed
+    /// Create a backup before a destructive operation
     ///
-    /// This creates a backup only if:
-    /// - No backup exists yet, OR
-    /// - The most recent backup is older than 60 seconds
-    ///
-    /// This prevents creating too many backups when multiple destructive
-    /// operations happen in quick succession.
+    /// Each destructive operation gets its own backup, marked as
+    /// pre-destructive, and becomes the one [`Storage::undo_last`] reverts.
+    /// Calls made while an operation is already under way (see
+    /// [`Storage::end_destructive`]) are part of that operation and take no
+    /// new backup.
     ///
     /// Returns Ok(Some(path)) if a backup was created, Ok(None) if skipped.
     pub fn backup_before_destructive(&self) -> EnvelopeResult<Option<PathBuf>> {
-        let retention = BackupRetention::default();
-        let manager = BackupManager::new(self.paths.clone(), retention);
+        if self.destructive_open.load(Ordering::SeqCst) {
+            return Ok(None);
+        }
 
-        // Check if we need to create a backup
-        if let Some(latest) = manager.get_latest_backup()? {
-            let age = chrono::Utc::now().signed_duration_since(latest.created_at);
+        let manager = self.backup_manager(self.backup_retention.clone());
+        let path = manager.create_pre_destructive_backup()?;
 
-            // Skip if last backup was less than 60 seconds ago
-            if age.num_seconds() < 60 {
-                return Ok(None);
-            }
-        }
+        let backup = path
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.write_undo_marker(&UndoMarker {
+            backup,
+            audit_entries: self.audit.entry_count()?,
+        })?;
+        self.destructive_open.store(true, Ordering::SeqCst);
 
-        // Create backup
-        let path = manager.create_backup()?;
         Ok(Some(path))
     }
+
+    /// Mark the current destructive operation as finished
+    ///
+    /// A command-line run is a single operation, so only long-lived callers
+    /// such as the TUI need this. Changes made afterwards are ordinary edits
+    /// that [`Storage::undo_last`] will not discard.
+    pub fn end_destructive(&self) {
+        self.destructive_open.store(false, Ordering::SeqCst);
+    }
+
+    /// Roll back the most recent destructive operation
+    ///
+    /// Restores the backup taken by [`Storage::backup_before_destructive`],
+    /// and refuses if anything has been audited since the operation finished,
+    /// since rolling back would silently discard those edits too. The current
+    /// data is backed up first, so `backup restore latest` redoes the change.
+    /// Each operation can be undone once.
+    pub fn undo_last(&self) -> EnvelopeResult<RestoreResult> {
+        self.guard.check()?;
+
+        let marker = self
+            .read_undo_marker()?
+            .ok_or_else(|| EnvelopeError::Validation("Nothing to undo".into()))?;
+
+        let manager = self.backup_manager(self.backup_retention.clone());
+        let backup = manager.get_backup(&marker.backup)?.ok_or_else(|| {
+            EnvelopeError::Validation(format!(
+                "Nothing to undo: the backup taken before the last destructive change ({}) no longer exists",
+                marker.backup
+            ))
+        })?;
+
+        let later_changes = self
+            .audit
+            .entry_count()?
+            .saturating_sub(marker.audit_entries);
+        if later_changes > 0 {
+            return Err(EnvelopeError::Validation(format!(
+                "Cannot undo: {} change(s) were made after the last destructive change and would be lost. Use 'envelope backup restore {}' to roll back anyway",
+                later_changes, marker.backup
+            )));
+        }
+
+        manager.create_backup()?;
+
+        let restore_manager = RestoreManager::new(self.paths.clone());
+        let result = restore_manager.restore_from_file(&backup.path)?;
+
+        self.clear_undo_marker()?;
+        self.end_destructive();
+        self.reload_all()?;
+
+        Ok(result)
+    }
+
+    /// Path of the undo marker
+    fn undo_marker_path(&self) -> PathBuf {
+        self.paths.backup_dir().join(UNDO_MARKER_FILE)
+    }
+
+    /// Read the undo marker, if a destructive operation can be undone
+    fn read_undo_marker(&self) -> EnvelopeResult<Option<UndoMarker>> {
+        read_json(self.undo_marker_path())
+    }
+
+    /// Record the latest destructive operation for undo
+    fn write_undo_marker(&self, marker: &UndoMarker) -> EnvelopeResult<()> {
+        write_json_atomic(self.undo_marker_path(), marker)
+    }
+
+    /// Forget the latest destructive operation once it has been undone
+    fn clear_undo_marker(&self) -> EnvelopeResult<()> {
+        let path = self.undo_marker_path();
+        if path.exists() {
+            std::fs::remove_file(&path)
+                .map_err(|e| EnvelopeError::Io(format!("Failed to remove undo marker: {}", e)))?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -244,4 +405,152 @@ mod tests {
         assert!(temp_dir.path().join("backups").exists());
         assert!(!storage.is_initialized());
     }
+
+    #[test]
+    fn test_read_only_blocks_writes() {
+        use crate::models::{Account, AccountType};
+
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let storage = Storage::new(paths).unwrap();
+
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+        storage.accounts.save().unwrap();
+
+        storage.set_read_only(true);
+        let mut renamed = account.clone();
+        renamed.name = "Renamed".to_string();
+        assert!(matches!(
+            storage.accounts.upsert(renamed),
+            Err(EnvelopeError::ReadOnly)
+        ));
+        assert!(matches!(
+            storage.accounts.delete(account.id),
+            Err(EnvelopeError::ReadOnly)
+        ));
+        assert!(matches!(
+            storage.accounts.save(),
+            Err(EnvelopeError::ReadOnly)
+        ));
+
+        // Reads still work
+        assert_eq!(
+            storage.accounts.get(account.id).unwrap().unwrap().name,
+            "Checking"
+        );
+    }
+    #[test]
+    fn test_backups_use_configured_retention() {
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let storage = Storage::new(paths)
+            .unwrap()
+            .with_backup_retention(BackupRetention {
+                compress: true,
+                ..BackupRetention::default()
+            });
+
+        let routine = storage.create_backup().unwrap();
+        let destructive = storage.backup_before_destructive().unwrap().unwrap();
+        storage.end_destructive();
+
+        for path in [routine, destructive] {
+            assert_eq!(path.extension().unwrap(), "gz", "{}", path.display());
+        }
+    }
+
+    #[test]
+    fn test_undo_last() {
+        use crate::models::{Account, AccountType};
+
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let storage = Storage::new(paths).unwrap();
+        assert!(storage.undo_last().is_err());
+
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+        storage.accounts.save().unwrap();
+
+        storage.backup_before_destructive().unwrap().unwrap();
+        storage.accounts.delete(account.id).unwrap();
+        storage.accounts.save().unwrap();
+        assert!(storage.accounts.get(account.id).unwrap().is_none());
+
+        let result = storage.undo_last().unwrap();
+        assert!(result.accounts_restored);
+        assert!(storage.accounts.get(account.id).unwrap().is_some());
+
+        // Each destructive change is undone once
+        assert!(matches!(
+            storage.undo_last(),
+            Err(EnvelopeError::Validation(_))
+        ));
+    }
+
+    #[test]
+    fn test_undo_refuses_after_later_edits() {
+        use crate::models::{Account, AccountType};
+
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let storage = Storage::new(paths).unwrap();
+
+        let account = Account::new("Checking", AccountType::Checking);
+        storage.accounts.upsert(account.clone()).unwrap();
+        storage.accounts.save().unwrap();
+
+        // The destructive change's own audit entries don't block undo
+        storage.backup_before_destructive().unwrap().unwrap();
+        storage.accounts.delete(account.id).unwrap();
+        storage.accounts.save().unwrap();
+        storage
+            .log_delete(EntityType::Account, account.id.to_string(), None, &account)
+            .unwrap();
+        storage.end_destructive();
+
+        // An ordinary edit afterwards does
+        let savings = Account::new("Savings", AccountType::Savings);
+        storage.accounts.upsert(savings.clone()).unwrap();
+        storage.accounts.save().unwrap();
+        storage
+            .log_create(EntityType::Account, savings.id.to_string(), None, &savings)
+            .unwrap();
+
+        assert!(matches!(
+            storage.undo_last(),
+            Err(EnvelopeError::Validation(msg)) if msg.contains("1 change(s)")
+        ));
+        assert!(storage.accounts.get(savings.id).unwrap().is_some());
+    }
+
+    #[test]
+    fn test_each_destructive_change_gets_its_own_backup() {
+        use crate::models::{Account, AccountType};
+
+        let temp_dir = TempDir::new().unwrap();
+        let paths = EnvelopePaths::with_base_dir(temp_dir.path().to_path_buf());
+        let storage = Storage::new(paths).unwrap();
+
+        let checking = Account::new("Checking", AccountType::Checking);
+        let savings = Account::new("Savings", AccountType::Savings);
+        storage.accounts.upsert(checking.clone()).unwrap();
+        storage.accounts.upsert(savings.clone()).unwrap();
+        storage.accounts.save().unwrap();
+
+        for account in [&checking, &savings] {
+            assert!(storage.backup_before_destructive().unwrap().is_some());
+            // Within one operation, no second backup
+            assert!(storage.backup_before_destructive().unwrap().is_none());
+            storage.accounts.delete(account.id).unwrap();
+            storage.accounts.save().unwrap();
+            storage.end_destructive();
+        }
+
+        // Undo only reverts the second delete
+        storage.undo_last().unwrap();
+        assert!(storage.accounts.get(checking.id).unwrap().is_none());
+        assert!(storage.accounts.get(savings.id).unwrap().is_some());
+    }
 }
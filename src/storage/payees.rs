@@ -10,6 +10,7 @@ use crate::error::EnvelopeError;
 use crate::models::{Payee, PayeeId};
 
 use super::file_io::{read_json, write_json_atomic};
+use super::guard::WriteGuard;
 
 /// Serializable payee data structure
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
@@ -20,6 +21,7 @@ struct PayeeData {
 /// Repository for payee persistence
 pub struct PayeeRepository {
     path: PathBuf,
+    guard: WriteGuard,
     data: RwLock<HashMap<PayeeId, Payee>>,
     /// Index: normalized name -> payee_id
     by_name: RwLock<HashMap<String, PayeeId>>,
@@ -30,11 +32,18 @@ impl PayeeRepository {
     pub fn new(path: PathBuf) -> Self {
         Self {
             path,
+            guard: WriteGuard::default(),
             data: RwLock::new(HashMap::new()),
             by_name: RwLock::new(HashMap::new()),
         }
     }
 
+    /// Share a read-only guard with the rest of the storage
+    pub fn with_guard(mut self, guard: WriteGuard) -> Self {
+        self.guard = guard;
+        self
+    }
+
     /// Load payees from disk
     pub fn load(&self) -> Result<(), EnvelopeError> {
         let file_data: PayeeData = read_json(&self.path)?;
@@ -62,6 +71,8 @@ impl PayeeRepository {
 
     /// Save payees to disk
     pub fn save(&self) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let data = self
             .data
             .read()
@@ -145,6 +156,8 @@ impl PayeeRepository {
 
     /// Insert or update a payee
     pub fn upsert(&self, payee: Payee) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let mut data = self
             .data
             .write()
@@ -170,6 +183,8 @@ impl PayeeRepository {
 
     /// Delete a payee
     pub fn delete(&self, id: PayeeId) -> Result<bool, EnvelopeError> {
+        self.guard.check()?;
+
         let mut data = self
             .data
             .write()
@@ -0,0 +1,177 @@
+//! Scheduled transaction repository
+//!
+//! Handles persistence of scheduled transactions to JSON files.
+
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::RwLock;
+
+use crate::error::EnvelopeError;
+use crate::models::{AccountId, ScheduledId, ScheduledTransaction};
+
+use super::file_io::{read_json, write_json_atomic};
+use super::guard::WriteGuard;
+
+#[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
+struct ScheduledData {
+    #[serde(default)]
+    scheduled: Vec<ScheduledTransaction>,
+}
+
+/// Repository for scheduled transactions
+pub struct ScheduledRepository {
+    path: PathBuf,
+    guard: WriteGuard,
+    scheduled: RwLock<HashMap<ScheduledId, ScheduledTransaction>>,
+}
+
+impl ScheduledRepository {
+    /// Create a new repository
+    pub fn new(path: PathBuf) -> Self {
+        Self {
+            path,
+            guard: WriteGuard::default(),
+            scheduled: RwLock::new(HashMap::new()),
+        }
+    }
+
+    /// Share a read-only guard with the rest of the storage
+    pub fn with_guard(mut self, guard: WriteGuard) -> Self {
+        self.guard = guard;
+        self
+    }
+
+    /// Load scheduled transactions from disk
+    pub fn load(&self) -> Result<(), EnvelopeError> {
+        let file_data: ScheduledData = read_json(&self.path)?;
+
+        let mut scheduled = self
+            .scheduled
+            .write()
+            .map_err(|e| EnvelopeError::Storage(format!("Failed to acquire write lock: {}", e)))?;
+
+        scheduled.clear();
+        for item in file_data.scheduled {
+            scheduled.insert(item.id, item);
+        }
+
+        Ok(())
+    }
+
+    /// Save scheduled transactions to disk
+    pub fn save(&self) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
+        let scheduled = self
+            .scheduled
+            .read()
+            .map_err(|e| EnvelopeError::Storage(format!("Failed to acquire read lock: {}", e)))?;
+
+        let mut list: Vec<_> = scheduled.values().cloned().collect();
+        list.sort_by(|a, b| a.created_at.cmp(&b.created_at));
+
+        let file_data = ScheduledData { scheduled: list };
+
+        write_json_atomic(&self.path, &file_data)
+    }
+
+    /// Get a scheduled transaction by ID
+    pub fn get(&self, id: ScheduledId) -> Result<Option<ScheduledTransaction>, EnvelopeError> {
+        let scheduled = self
+            .scheduled
+            .read()
+            .map_err(|e| EnvelopeError::Storage(format!("Failed to acquire read lock: {}", e)))?;
+
+        Ok(scheduled.get(&id).cloned())
+    }
+
+    /// Get all scheduled transactions, soonest first
+    pub fn get_all(&self) -> Result<Vec<ScheduledTransaction>, EnvelopeError> {
+        let scheduled = self
+            .scheduled
+            .read()
+            .map_err(|e| EnvelopeError::Storage(format!("Failed to acquire read lock: {}", e)))?;
+
+        let mut list: Vec<_> = scheduled.values().cloned().collect();
+        list.sort_by(|a, b| {
+            a.next_date
+                .cmp(&b.next_date)
+                .then(a.created_at.cmp(&b.created_at))
+        });
+        Ok(list)
+    }
+
+    /// Get the active scheduled transactions for an account, soonest first
+    pub fn get_active_by_account(
+        &self,
+        account_id: AccountId,
+    ) -> Result<Vec<ScheduledTransaction>, EnvelopeError> {
+        Ok(self
+            .get_all()?
+            .into_iter()
+            .filter(|s| s.active && s.account_id == account_id)
+            .collect())
+    }
+
+    /// Insert or update a scheduled transaction
+    pub fn upsert(&self, item: ScheduledTransaction) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
+        let mut scheduled = self
+            .scheduled
+            .write()
+            .map_err(|e| EnvelopeError::Storage(format!("Failed to acquire write lock: {}", e)))?;
+
+        scheduled.insert(item.id, item);
+        Ok(())
+    }
+
+    /// Delete a scheduled transaction
+    pub fn delete(&self, id: ScheduledId) -> Result<bool, EnvelopeError> {
+        self.guard.check()?;
+
+        let mut scheduled = self
+            .scheduled
+            .write()
+            .map_err(|e| EnvelopeError::Storage(format!("Failed to acquire write lock: {}", e)))?;
+
+        Ok(scheduled.remove(&id).is_some())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::{Money, TargetCadence};
+    use chrono::NaiveDate;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_save_and_load() {
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("scheduled.json");
+        let account_id = AccountId::new();
+        let start = NaiveDate::from_ymd_opt(2025, 1, 1).unwrap();
+
+        {
+            let repo = ScheduledRepository::new(path.clone());
+            let item = ScheduledTransaction::new(
+                account_id,
+                Money::from_cents(-150000),
+                TargetCadence::Monthly,
+                start,
+            );
+            repo.upsert(item).unwrap();
+            repo.save().unwrap();
+        }
+
+        {
+            let repo = ScheduledRepository::new(path);
+            repo.load().unwrap();
+            let items = repo.get_active_by_account(account_id).unwrap();
+            assert_eq!(items.len(), 1);
+            assert_eq!(items[0].next_date, start);
+            assert_eq!(items[0].cadence, TargetCadence::Monthly);
+        }
+    }
+}
@@ -8,6 +8,7 @@ use crate::error::EnvelopeError;
 use crate::models::{BudgetTarget, BudgetTargetId, CategoryId};
 
 use super::file_io::{read_json, write_json_atomic};
+use super::guard::WriteGuard;
 
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
 struct TargetData {
@@ -17,6 +18,7 @@ struct TargetData {
 
 pub struct TargetRepository {
     path: PathBuf,
+    guard: WriteGuard,
     targets: RwLock<HashMap<BudgetTargetId, BudgetTarget>>,
 }
 
@@ -24,10 +26,17 @@ impl TargetRepository {
     pub fn new(path: PathBuf) -> Self {
         Self {
             path,
+            guard: WriteGuard::default(),
             targets: RwLock::new(HashMap::new()),
         }
     }
 
+    /// Share a read-only guard with the rest of the storage
+    pub fn with_guard(mut self, guard: WriteGuard) -> Self {
+        self.guard = guard;
+        self
+    }
+
     pub fn load(&self) -> Result<(), EnvelopeError> {
         let file_data: TargetData = read_json(&self.path)?;
 
@@ -45,6 +54,8 @@ impl TargetRepository {
     }
 
     pub fn save(&self) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let targets = self
             .targets
             .read()
@@ -96,6 +107,8 @@ impl TargetRepository {
     }
 
     pub fn upsert(&self, target: BudgetTarget) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let mut targets = self
             .targets
             .write()
@@ -106,6 +119,8 @@ impl TargetRepository {
     }
 
     pub fn delete(&self, id: BudgetTargetId) -> Result<bool, EnvelopeError> {
+        self.guard.check()?;
+
         let mut targets = self
             .targets
             .write()
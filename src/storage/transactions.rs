@@ -12,6 +12,7 @@ use crate::error::EnvelopeError;
 use crate::models::{AccountId, CategoryId, Transaction, TransactionId};
 
 use super::file_io::{read_json, write_json_atomic};
+use super::guard::WriteGuard;
 
 /// Serializable transaction data structure
 #[derive(Debug, Clone, Default, serde::Serialize, serde::Deserialize)]
@@ -22,6 +23,7 @@ struct TransactionData {
 /// Repository for transaction persistence with indexing
 pub struct TransactionRepository {
     path: PathBuf,
+    guard: WriteGuard,
     data: RwLock<HashMap<TransactionId, Transaction>>,
     /// Index: account_id -> transaction_ids
     by_account: RwLock<HashMap<AccountId, Vec<TransactionId>>>,
@@ -34,12 +36,19 @@ impl TransactionRepository {
     pub fn new(path: PathBuf) -> Self {
         Self {
             path,
+            guard: WriteGuard::default(),
             data: RwLock::new(HashMap::new()),
             by_account: RwLock::new(HashMap::new()),
             by_category: RwLock::new(HashMap::new()),
         }
     }
 
+    /// Share a read-only guard with the rest of the storage
+    pub fn with_guard(mut self, guard: WriteGuard) -> Self {
+        self.guard = guard;
+        self
+    }
+
     /// Load transactions from disk and build indexes
     pub fn load(&self) -> Result<(), EnvelopeError> {
         let file_data: TransactionData = read_json(&self.path)?;
@@ -72,8 +81,8 @@ impl TransactionRepository {
             if let Some(cat_id) = txn.category_id {
                 by_category.entry(cat_id).or_default().push(id);
             }
-            for split in &txn.splits {
-                by_category.entry(split.category_id).or_default().push(id);
+            for cat_id in txn.splits.iter().filter_map(|s| s.category_id) {
+                by_category.entry(cat_id).or_default().push(id);
             }
 
             data.insert(id, txn);
@@ -84,6 +93,8 @@ impl TransactionRepository {
 
     /// Save transactions to disk
     pub fn save(&self) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let data = self
             .data
             .read()
@@ -118,6 +129,24 @@ impl TransactionRepository {
         Ok(transactions)
     }
 
+    /// Visit every transaction, oldest first, without cloning them
+    ///
+    /// The read lock is held for the whole walk, so `f` must not write to
+    /// this repository.
+    pub fn for_each<F>(&self, mut f: F) -> Result<(), EnvelopeError>
+    where
+        F: FnMut(&Transaction) -> Result<(), EnvelopeError>,
+    {
+        let data = self
+            .data
+            .read()
+            .map_err(|e| EnvelopeError::Storage(format!("Failed to acquire read lock: {}", e)))?;
+
+        let mut transactions: Vec<_> = data.values().collect();
+        transactions.sort_by(|a, b| a.date.cmp(&b.date).then(a.created_at.cmp(&b.created_at)));
+        transactions.into_iter().try_for_each(&mut f)
+    }
+
     /// Get transactions for an account
     pub fn get_by_account(&self, account_id: AccountId) -> Result<Vec<Transaction>, EnvelopeError> {
         let data = self
@@ -176,6 +205,8 @@ impl TransactionRepository {
 
     /// Insert or update a transaction
     pub fn upsert(&self, txn: Transaction) -> Result<(), EnvelopeError> {
+        self.guard.check()?;
+
         let mut data = self
             .data
             .write()
@@ -199,8 +230,8 @@ impl TransactionRepository {
                     ids.retain(|&id| id != txn.id);
                 }
             }
-            for split in &old.splits {
-                if let Some(ids) = by_category.get_mut(&split.category_id) {
+            for cat_id in old.splits.iter().filter_map(|s| s.category_id) {
+                if let Some(ids) = by_category.get_mut(&cat_id) {
                     ids.retain(|&id| id != txn.id);
                 }
             }
@@ -211,11 +242,8 @@ impl TransactionRepository {
         if let Some(cat_id) = txn.category_id {
             by_category.entry(cat_id).or_default().push(txn.id);
         }
-        for split in &txn.splits {
-            by_category
-                .entry(split.category_id)
-                .or_default()
-                .push(txn.id);
+        for cat_id in txn.splits.iter().filter_map(|s| s.category_id) {
+            by_category.entry(cat_id).or_default().push(txn.id);
         }
 
         data.insert(txn.id, txn);
@@ -224,6 +252,8 @@ impl TransactionRepository {
 
     /// Delete a transaction
     pub fn delete(&self, id: TransactionId) -> Result<bool, EnvelopeError> {
+        self.guard.check()?;
+
         let mut data = self
             .data
             .write()
@@ -247,8 +277,8 @@ impl TransactionRepository {
                     ids.retain(|&tid| tid != id);
                 }
             }
-            for split in &txn.splits {
-                if let Some(ids) = by_category.get_mut(&split.category_id) {
+            for cat_id in txn.splits.iter().filter_map(|s| s.category_id) {
+                if let Some(ids) = by_category.get_mut(&cat_id) {
                     ids.retain(|&tid| tid != id);
                 }
             }
@@ -2,23 +2,33 @@
 //!
 //! The App struct holds all state needed for rendering and handling events.
 
+use std::collections::HashSet;
+
 use crate::config::paths::EnvelopePaths;
-use crate::config::settings::Settings;
-use crate::models::{AccountId, BudgetPeriod, CategoryGroupId, CategoryId, TransactionId};
+use crate::config::settings::{Settings, TuiState};
+use crate::error::EnvelopeResult;
+use crate::models::{
+    AccountId, BudgetPeriod, Category, CategoryGroupId, CategoryId, Money, ScheduledTransaction,
+    Transaction, TransactionId,
+};
+use crate::services::{TransactionFilter, TransactionService};
 use crate::storage::Storage;
 
 use super::dialogs::account::AccountFormState;
 use super::dialogs::adjustment::AdjustmentDialogState;
-use super::dialogs::budget::BudgetDialogState;
+use super::dialogs::budget::{BudgetDialogState, InlineBudgetEdit};
 use super::dialogs::bulk_categorize::BulkCategorizeState;
 use super::dialogs::category::CategoryFormState;
+use super::dialogs::go_to::GoToState;
 use super::dialogs::group::GroupFormState;
 use super::dialogs::income::IncomeFormState;
-use super::dialogs::move_funds::MoveFundsState;
+use super::dialogs::move_category::MoveCategoryState;
+use super::dialogs::move_funds::{FundMove, MoveFundsState};
 use super::dialogs::reconcile_start::ReconcileStartState;
 use super::dialogs::transaction::TransactionFormState;
 use super::dialogs::unlock_confirm::UnlockConfirmState;
 use super::views::reconcile::ReconciliationState;
+use super::widgets::{NotificationQueue, TypeAhead};
 
 /// Which view is currently active
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -31,6 +41,33 @@ pub enum ActiveView {
     Reconcile,
 }
 
+impl ActiveView {
+    /// Parse a view saved in settings
+    ///
+    /// Reconciliation isn't restored, since it starts from a statement
+    /// balance entered in the session.
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "accounts" => Some(Self::Accounts),
+            "register" => Some(Self::Register),
+            "budget" => Some(Self::Budget),
+            "reports" => Some(Self::Reports),
+            _ => None,
+        }
+    }
+
+    /// The name saved in settings
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Accounts => "accounts",
+            Self::Register => "register",
+            Self::Budget => "budget",
+            Self::Reports => "reports",
+            Self::Reconcile => "reconcile",
+        }
+    }
+}
+
 /// What to display in the budget header
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum BudgetHeaderDisplay {
@@ -97,6 +134,45 @@ impl BudgetHeaderDisplay {
     }
 }
 
+/// What the register is sorted by
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum RegisterSortKey {
+    #[default]
+    Date,
+    Payee,
+    Amount,
+}
+
+impl RegisterSortKey {
+    /// Cycle to the next sort key
+    pub fn next(self) -> Self {
+        match self {
+            Self::Date => Self::Payee,
+            Self::Payee => Self::Amount,
+            Self::Amount => Self::Date,
+        }
+    }
+
+    /// Parse a sort key saved in settings
+    pub fn parse(s: &str) -> Option<Self> {
+        match s {
+            "date" => Some(Self::Date),
+            "payee" => Some(Self::Payee),
+            "amount" => Some(Self::Amount),
+            _ => None,
+        }
+    }
+
+    /// The name saved in settings and shown in the status bar
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Date => "date",
+            Self::Payee => "payee",
+            Self::Amount => "amount",
+        }
+    }
+}
+
 /// Which panel currently has focus
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
 pub enum FocusedPanel {
@@ -125,10 +201,12 @@ pub enum ActiveDialog {
     EditAccount(AccountId),
     AddCategory,
     EditCategory(CategoryId),
+    MoveCategory(CategoryId),
     AddGroup,
     EditGroup(CategoryGroupId),
     MoveFunds,
     CommandPalette,
+    GoTo,
     Help,
     Confirm(String),
     BulkCategorize,
@@ -192,6 +270,12 @@ pub struct App<'a> {
     /// Show archived accounts
     pub show_archived: bool,
 
+    /// Reports view compares the current period with the one before
+    pub reports_compare: bool,
+
+    /// Type-ahead query for jumping to an account in the sidebar
+    pub sidebar_type_ahead: TypeAhead,
+
     /// Multi-selection mode (for bulk operations)
     pub multi_select_mode: bool,
 
@@ -204,6 +288,12 @@ pub struct App<'a> {
     /// Status message to display
     pub status_message: Option<String>,
 
+    /// Toast notifications shown over the top right of the screen
+    pub notifications: NotificationQueue,
+
+    /// The last fund move, kept so it can be undone
+    pub last_fund_move: Option<FundMove>,
+
     /// Command palette input
     pub command_input: String,
 
@@ -213,6 +303,12 @@ pub struct App<'a> {
     /// Selected command index in palette
     pub selected_command_index: usize,
 
+    /// Go-to dialog state
+    pub go_to_state: GoToState,
+
+    /// Move category dialog state
+    pub move_category_state: MoveCategoryState,
+
     /// Transaction form state
     pub transaction_form: TransactionFormState,
 
@@ -246,21 +342,53 @@ pub struct App<'a> {
     /// Income form dialog state
     pub income_form: IncomeFormState,
 
+    /// Budgeted cell being edited in place in the budget view
+    pub inline_budget_edit: Option<InlineBudgetEdit>,
+
+    /// Category and amount fund-to-target already warned would overbudget
+    pub fund_to_target_warning: Option<(CategoryId, Money)>,
+
     /// Pending 'g' keypress for Vim-style gg (go to top)
     pub pending_g: bool,
+
+    /// What the register is sorted by
+    pub register_sort: RegisterSortKey,
+
+    /// Whether the register sorts latest or largest first
+    pub register_sort_descending: bool,
+
+    /// Category groups collapsed in the budget view
+    pub collapsed_groups: HashSet<CategoryGroupId>,
 }
 
 impl<'a> App<'a> {
     /// Create a new App instance
     pub fn new(storage: &'a Storage, settings: &'a Settings, paths: &'a EnvelopePaths) -> Self {
-        // Initialize selected_account to first account (default view is Accounts)
-        let selected_account = storage
-            .accounts
-            .get_active()
-            .ok()
-            .and_then(|accounts| accounts.first().map(|a| a.id));
-
-        Self {
+        // Restore the last session's view state, dropping anything stale
+        let state = &settings.tui_state;
+
+        // Reselect the last account, or the first one if it's gone
+        let accounts = storage.accounts.get_active().unwrap_or_default();
+        let selected_account_index = state
+            .selected_account
+            .and_then(|id| accounts.iter().position(|a| a.id == id))
+            .unwrap_or(0);
+        let selected_account = accounts.get(selected_account_index).map(|a| a.id);
+        let current_period = state
+            .period
+            .as_deref()
+            .and_then(|p| BudgetPeriod::parse(p).ok())
+            .unwrap_or_else(BudgetPeriod::current_month);
+
+        let register_sort = RegisterSortKey::parse(&state.register_sort).unwrap_or_default();
+        let collapsed_groups = state
+            .collapsed_groups
+            .iter()
+            .copied()
+            .filter(|id| matches!(storage.categories.get_group(*id), Ok(Some(_))))
+            .collect();
+
+        let mut app = Self {
             storage,
             settings,
             paths,
@@ -270,21 +398,27 @@ impl<'a> App<'a> {
             input_mode: InputMode::default(),
             active_dialog: ActiveDialog::default(),
             selected_account,
-            selected_account_index: 0,
+            selected_account_index,
             selected_transaction: None,
             selected_transaction_index: 0,
             selected_category: None,
             selected_category_index: 0,
-            current_period: BudgetPeriod::current_month(),
+            current_period,
             budget_header_display: BudgetHeaderDisplay::default(),
             show_archived: false,
+            reports_compare: false,
+            sidebar_type_ahead: TypeAhead::default(),
             multi_select_mode: false,
             selected_transactions: Vec::new(),
             scroll_offset: 0,
             status_message: None,
+            notifications: NotificationQueue::new(),
+            last_fund_move: None,
             command_input: String::new(),
             command_results: Vec::new(),
             selected_command_index: 0,
+            go_to_state: GoToState::new(),
+            move_category_state: MoveCategoryState::new(),
             transaction_form: TransactionFormState::new(),
             move_funds_state: MoveFundsState::new(),
             bulk_categorize_state: BulkCategorizeState::new(),
@@ -296,8 +430,20 @@ impl<'a> App<'a> {
             group_form: GroupFormState::new(),
             budget_dialog_state: BudgetDialogState::new(),
             income_form: IncomeFormState::new(),
+            inline_budget_edit: None,
+            fund_to_target_warning: None,
             pending_g: false,
+            register_sort,
+            register_sort_descending: state.register_sort_descending,
+            collapsed_groups,
+        };
+
+        // The accounts view is the default and keeps the restored account
+        let view = state.active_view.as_deref().and_then(ActiveView::parse);
+        if let Some(view) = view.filter(|v| *v != ActiveView::Accounts) {
+            app.switch_view(view);
         }
+        app
     }
 
     /// Request to quit the application
@@ -332,32 +478,12 @@ impl<'a> App<'a> {
             ActiveView::Register => {
                 self.selected_transaction_index = 0;
                 // Initialize selected_transaction to first transaction (sorted by date desc)
-                if let Some(account_id) = self.selected_account {
-                    let mut txns = self
-                        .storage
-                        .transactions
-                        .get_by_account(account_id)
-                        .unwrap_or_default();
-                    txns.sort_by(|a, b| b.date.cmp(&a.date));
-                    self.selected_transaction = txns.first().map(|t| t.id);
-                }
+                self.selected_transaction = self.register_transactions().first().map(|t| t.id);
             }
             ActiveView::Budget => {
                 self.selected_category_index = 0;
                 // Initialize selected_category to first category (in visual order)
-                let groups = self.storage.categories.get_all_groups().unwrap_or_default();
-                let all_categories = self
-                    .storage
-                    .categories
-                    .get_all_categories()
-                    .unwrap_or_default();
-                // Find first category in visual order (first category in first group)
-                for group in &groups {
-                    if let Some(cat) = all_categories.iter().find(|c| c.group_id == group.id) {
-                        self.selected_category = Some(cat.id);
-                        break;
-                    }
-                }
+                self.selected_category = self.budget_categories().first().map(|c| c.id);
             }
             ActiveView::Reports => {}
             ActiveView::Reconcile => {
@@ -369,6 +495,187 @@ impl<'a> App<'a> {
         }
     }
 
+    /// Transactions shown in the register, in the chosen sort order
+    ///
+    /// With no account selected ("All Accounts"), this spans every
+    /// non-archived account. Ties keep their date-descending order.
+    pub fn register_transactions(&self) -> Vec<Transaction> {
+        let mut transactions = if let Some(account_id) = self.selected_account {
+            self.storage
+                .transactions
+                .get_by_account(account_id)
+                .unwrap_or_default()
+        } else {
+            let filter = TransactionFilter::new().active_accounts_only();
+            TransactionService::new(self.storage)
+                .list(filter)
+                .unwrap_or_default()
+        };
+
+        transactions.sort_by(|a, b| {
+            let ordering = match self.register_sort {
+                RegisterSortKey::Date => a.date.cmp(&b.date),
+                RegisterSortKey::Payee => a
+                    .payee_name
+                    .to_lowercase()
+                    .cmp(&b.payee_name.to_lowercase()),
+                RegisterSortKey::Amount => a.amount.cmp(&b.amount),
+            };
+            if self.register_sort_descending {
+                ordering.reverse()
+            } else {
+                ordering
+            }
+        });
+        transactions
+    }
+
+    /// Scheduled transactions still to come for the register, soonest first
+    ///
+    /// Shown greyed out above the real transactions; they can't be selected.
+    pub fn register_upcoming(&self) -> Vec<ScheduledTransaction> {
+        if let Some(account_id) = self.selected_account {
+            self.storage
+                .scheduled
+                .get_active_by_account(account_id)
+                .unwrap_or_default()
+        } else {
+            let archived: HashSet<_> = self
+                .storage
+                .accounts
+                .get_all()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|a| a.archived)
+                .map(|a| a.id)
+                .collect();
+            self.storage
+                .scheduled
+                .get_all()
+                .unwrap_or_default()
+                .into_iter()
+                .filter(|s| s.active && !archived.contains(&s.account_id))
+                .collect()
+        }
+    }
+
+    /// Cycle the register to the next sort key
+    pub fn cycle_register_sort(&mut self) {
+        self.register_sort = self.register_sort.next();
+        self.reset_register_selection();
+    }
+
+    /// Flip the register between ascending and descending order
+    pub fn toggle_register_sort_direction(&mut self) {
+        self.register_sort_descending = !self.register_sort_descending;
+        self.reset_register_selection();
+    }
+
+    /// Select the first transaction after the register order changes
+    fn reset_register_selection(&mut self) {
+        self.selected_transaction_index = 0;
+        self.selected_transaction = self.register_transactions().first().map(|t| t.id);
+        self.set_status(format!(
+            "Sorted by {} ({})",
+            self.register_sort.as_str(),
+            if self.register_sort_descending {
+                "descending"
+            } else {
+                "ascending"
+            }
+        ));
+    }
+
+    /// Categories the budget view lets you select, in display order
+    ///
+    /// Categories in collapsed groups are left out, and so are archived
+    /// ones unless archived items are shown.
+    pub fn budget_categories(&self) -> Vec<Category> {
+        let groups = self.storage.categories.get_all_groups().unwrap_or_default();
+        let all_categories = self
+            .storage
+            .categories
+            .get_all_categories()
+            .unwrap_or_default();
+
+        groups
+            .iter()
+            .filter(|group| !self.collapsed_groups.contains(&group.id))
+            .flat_map(|group| {
+                all_categories
+                    .iter()
+                    .filter(|c| c.group_id == group.id && (self.show_archived || !c.archived))
+                    .cloned()
+                    .collect::<Vec<_>>()
+            })
+            .collect()
+    }
+
+    /// Collapse or expand a category group in the budget view
+    pub fn toggle_group_collapsed(&mut self, group_id: CategoryGroupId) {
+        if !self.collapsed_groups.remove(&group_id) {
+            self.collapsed_groups.insert(group_id);
+        }
+        self.clamp_category_selection();
+    }
+
+    /// Expand every collapsed group in the budget view
+    pub fn expand_all_groups(&mut self) {
+        self.collapsed_groups.clear();
+        self.clamp_category_selection();
+    }
+
+    /// Keep the budget selection on a visible category
+    fn clamp_category_selection(&mut self) {
+        let categories = self.budget_categories();
+        if let Some(index) = self
+            .selected_category
+            .and_then(|id| categories.iter().position(|c| c.id == id))
+        {
+            self.selected_category_index = index;
+        } else {
+            self.selected_category_index = self
+                .selected_category_index
+                .min(categories.len().saturating_sub(1));
+            self.selected_category = categories.get(self.selected_category_index).map(|c| c.id);
+        }
+    }
+
+    /// Save the view, selection, period, register sort, and collapsed groups
+    /// for the next launch
+    pub fn save_view_state(&self) -> EnvelopeResult<()> {
+        if self.storage.is_read_only() {
+            return Ok(());
+        }
+
+        let mut collapsed_groups: Vec<CategoryGroupId> =
+            self.collapsed_groups.iter().copied().collect();
+        collapsed_groups.sort_by_key(|id| id.to_string());
+
+        // Reconciliation needs a statement balance, so resume in the register
+        let view = match self.active_view {
+            ActiveView::Reconcile => ActiveView::Register,
+            view => view,
+        };
+
+        let state = TuiState {
+            register_sort: self.register_sort.as_str().to_string(),
+            register_sort_descending: self.register_sort_descending,
+            collapsed_groups,
+            active_view: Some(view.as_str().to_string()),
+            selected_account: self.selected_account,
+            period: Some(self.current_period.to_string()),
+        };
+        if state == self.settings.tui_state {
+            return Ok(());
+        }
+
+        // Reload so changes saved elsewhere during the session are kept
+        let mut settings = Settings::load_or_create(self.paths)?;
+        settings.tui_state = state;
+        settings.save(self.paths)
+    }
+
     /// Toggle focus between sidebar and main panel
     pub fn toggle_panel_focus(&mut self) {
         self.focused_panel = match self.focused_panel {
@@ -393,31 +700,12 @@ impl<'a> App<'a> {
             }
             ActiveView::Register => {
                 if self.selected_transaction.is_none() {
-                    if let Some(account_id) = self.selected_account {
-                        let mut txns = self
-                            .storage
-                            .transactions
-                            .get_by_account(account_id)
-                            .unwrap_or_default();
-                        txns.sort_by(|a, b| b.date.cmp(&a.date));
-                        self.selected_transaction = txns.first().map(|t| t.id);
-                    }
+                    self.selected_transaction = self.register_transactions().first().map(|t| t.id);
                 }
             }
             ActiveView::Budget => {
                 if self.selected_category.is_none() {
-                    let groups = self.storage.categories.get_all_groups().unwrap_or_default();
-                    let all_categories = self
-                        .storage
-                        .categories
-                        .get_all_categories()
-                        .unwrap_or_default();
-                    for group in &groups {
-                        if let Some(cat) = all_categories.iter().find(|c| c.group_id == group.id) {
-                            self.selected_category = Some(cat.id);
-                            break;
-                        }
-                    }
+                    self.selected_category = self.budget_categories().first().map(|c| c.id);
                 }
             }
             _ => {}
@@ -426,15 +714,44 @@ impl<'a> App<'a> {
 
     /// Open a dialog
     pub fn open_dialog(&mut self, dialog: ActiveDialog) {
+        // Starting balances are edited through their account, not the register
+        if let ActiveDialog::EditTransaction(txn_id) = &dialog {
+            if let Ok(Some(txn)) = self.storage.transactions.get(*txn_id) {
+                if txn.is_starting_balance {
+                    self.set_status("Edit the starting balance from the account (Edit Account)");
+                    return;
+                }
+            }
+        }
+
         self.active_dialog = dialog.clone();
         match &dialog {
             ActiveDialog::CommandPalette => {
                 self.command_input.clear();
                 self.input_mode = InputMode::Command;
             }
+            ActiveDialog::GoTo => {
+                self.go_to_state.init(self.storage);
+                self.input_mode = InputMode::Editing;
+            }
+            ActiveDialog::MoveCategory(category_id) => {
+                self.move_category_state.init(self.storage, *category_id);
+                self.input_mode = InputMode::Editing;
+            }
             ActiveDialog::AddTransaction => {
                 // Reset form for new transaction
                 self.transaction_form = TransactionFormState::new();
+                if let Some(account_id) = self.selected_account {
+                    let default_category = TransactionService::new(self.storage)
+                        .default_category(account_id, self.settings.default_category_id)
+                        .ok()
+                        .flatten()
+                        .and_then(|id| self.storage.categories.get_category(id).ok().flatten());
+                    if let Some(category) = default_category {
+                        self.transaction_form
+                            .set_category(category.id, &category.name);
+                    }
+                }
                 self.transaction_form
                     .set_focus(super::dialogs::transaction::TransactionField::Date);
                 self.input_mode = InputMode::Editing;
@@ -519,7 +836,8 @@ impl<'a> App<'a> {
                 // Initialize unified budget dialog for selected category
                 if let Some(category_id) = self.selected_category {
                     if let Ok(Some(category)) = self.storage.categories.get_category(category_id) {
-                        let budget_service = crate::services::BudgetService::new(self.storage);
+                        let budget_service = crate::services::BudgetService::new(self.storage)
+                            .with_target_rounding(self.settings.target_rounding);
                         let summary = budget_service
                             .get_category_summary(category_id, &self.current_period)
                             .unwrap_or_else(|_| {
@@ -23,6 +23,7 @@ pub enum CommandAction {
     ViewBudget,
     ViewReports,
     ViewRegister,
+    GoTo,
 
     // Account operations
     AddAccount,
@@ -34,6 +35,7 @@ pub enum CommandAction {
     EditTransaction,
     DeleteTransaction,
     ClearTransaction,
+    Reconcile,
 
     // Budget operations
     MoveFunds,
@@ -49,6 +51,8 @@ pub enum CommandAction {
     AddGroup,
     EditCategory,
     DeleteCategory,
+    ArchiveCategory,
+    MoveCategory,
     EditGroup,
     DeleteGroup,
 
@@ -56,12 +60,60 @@ pub enum CommandAction {
     Help,
     Quit,
     Refresh,
+    Undo,
     ToggleArchived,
 
     // Target operations
     AutoFillTargets,
 }
 
+/// Cheat-sheet sections, in display order
+pub const SECTIONS: &[&str] = &[
+    "Navigation",
+    "Accounts",
+    "Transactions",
+    "Budget",
+    "Income",
+    "Categories",
+    "Targets",
+    "General",
+];
+
+impl CommandAction {
+    /// Cheat-sheet section this action belongs to
+    ///
+    /// Deliberately exhaustive so new actions must be placed in a section.
+    pub fn section(&self) -> &'static str {
+        match self {
+            Self::ViewAccounts
+            | Self::ViewBudget
+            | Self::ViewReports
+            | Self::ViewRegister
+            | Self::GoTo => "Navigation",
+            Self::AddAccount | Self::EditAccount | Self::ArchiveAccount | Self::ToggleArchived => {
+                "Accounts"
+            }
+            Self::AddTransaction
+            | Self::EditTransaction
+            | Self::DeleteTransaction
+            | Self::ClearTransaction
+            | Self::Reconcile => "Transactions",
+            Self::MoveFunds | Self::AssignBudget | Self::NextPeriod | Self::PrevPeriod => "Budget",
+            Self::SetIncome => "Income",
+            Self::AddCategory
+            | Self::AddGroup
+            | Self::EditCategory
+            | Self::DeleteCategory
+            | Self::ArchiveCategory
+            | Self::MoveCategory
+            | Self::EditGroup
+            | Self::DeleteGroup => "Categories",
+            Self::AutoFillTargets => "Targets",
+            Self::Help | Self::Quit | Self::Refresh | Self::Undo => "General",
+        }
+    }
+}
+
 /// All available commands
 pub static COMMANDS: &[Command] = &[
     // Navigation commands
@@ -89,6 +141,12 @@ pub static COMMANDS: &[Command] = &[
         shortcut: Some("Enter"),
         action: CommandAction::ViewRegister,
     },
+    Command {
+        name: "go-to",
+        description: "Jump to an account, category, or payee",
+        shortcut: Some("Ctrl+F"),
+        action: CommandAction::GoTo,
+    },
     // Transaction commands
     Command {
         name: "add-transaction",
@@ -114,6 +172,12 @@ pub static COMMANDS: &[Command] = &[
         shortcut: Some("c"),
         action: CommandAction::ClearTransaction,
     },
+    Command {
+        name: "reconcile",
+        description: "Reconcile selected account with a statement",
+        shortcut: Some("r"),
+        action: CommandAction::Reconcile,
+    },
     // Budget commands
     Command {
         name: "move-funds",
@@ -167,7 +231,7 @@ pub static COMMANDS: &[Command] = &[
     },
     Command {
         name: "toggle-archived",
-        description: "Show/hide archived accounts",
+        description: "Show/hide archived accounts and categories",
         shortcut: Some("A"),
         action: CommandAction::ToggleArchived,
     },
@@ -196,6 +260,18 @@ pub static COMMANDS: &[Command] = &[
         shortcut: None,
         action: CommandAction::DeleteCategory,
     },
+    Command {
+        name: "archive-category",
+        description: "Archive/unarchive selected category",
+        shortcut: None,
+        action: CommandAction::ArchiveCategory,
+    },
+    Command {
+        name: "move-category",
+        description: "Move selected category to another group",
+        shortcut: Some("M"),
+        action: CommandAction::MoveCategory,
+    },
     Command {
         name: "edit-group",
         description: "Edit selected category group",
@@ -227,6 +303,12 @@ pub static COMMANDS: &[Command] = &[
         shortcut: None,
         action: CommandAction::Refresh,
     },
+    Command {
+        name: "undo",
+        description: "Undo the last destructive change",
+        shortcut: None,
+        action: CommandAction::Undo,
+    },
     // Target commands
     Command {
         name: "auto-fill-targets",
@@ -241,6 +323,24 @@ pub fn find_command(name: &str) -> Option<&'static Command> {
     COMMANDS.iter().find(|cmd| cmd.name == name)
 }
 
+/// All commands grouped by section, in [`SECTIONS`] order
+pub fn commands_by_section() -> Vec<(&'static str, Vec<&'static Command>)> {
+    SECTIONS
+        .iter()
+        .filter_map(|section| {
+            let commands: Vec<&'static Command> = COMMANDS
+                .iter()
+                .filter(|cmd| cmd.action.section() == *section)
+                .collect();
+            if commands.is_empty() {
+                None
+            } else {
+                Some((*section, commands))
+            }
+        })
+        .collect()
+}
+
 /// Filter commands by search query
 pub fn filter_commands(query: &str) -> Vec<&'static Command> {
     if query.is_empty() {
@@ -256,3 +356,25 @@ pub fn filter_commands(query: &str) -> Vec<&'static Command> {
             .collect()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::collections::HashSet;
+
+    #[test]
+    fn test_command_names_unique() {
+        let mut names = HashSet::new();
+        for cmd in COMMANDS {
+            assert!(names.insert(cmd.name), "duplicate command: {}", cmd.name);
+        }
+    }
+
+    #[test]
+    fn test_commands_by_section_covers_all() {
+        let grouped = commands_by_section();
+        let total: usize = grouped.iter().map(|(_, cmds)| cmds.len()).sum();
+        assert_eq!(total, COMMANDS.len());
+        assert_eq!(grouped[0].0, "Navigation");
+    }
+}
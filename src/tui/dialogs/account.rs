@@ -11,7 +11,9 @@ use ratatui::{
     Frame,
 };
 
+use crate::config::clock;
 use crate::models::{Account, AccountType, Money};
+use crate::services::AccountService;
 use crate::tui::app::App;
 use crate::tui::layout::centered_rect;
 use crate::tui::widgets::input::TextInput;
@@ -615,7 +617,16 @@ fn save_account(app: &mut App) -> Result<(), String> {
         // Update existing account
         if let Some(account_id) = editing_id {
             if let Ok(Some(mut existing)) = app.storage.accounts.get(account_id) {
-                existing.name = app.account_form.name_input.value().trim().to_string();
+                let new_name = app.account_form.name_input.value().trim().to_string();
+                if app
+                    .storage
+                    .accounts
+                    .name_exists(&new_name, Some(account_id))
+                    .map_err(|e| e.to_string())?
+                {
+                    return Err(format!("An account named '{}' already exists", new_name));
+                }
+                existing.name = new_name;
                 existing.account_type = app.account_form.selected_account_type();
                 existing.on_budget = app.account_form.on_budget;
 
@@ -631,9 +642,9 @@ fn save_account(app: &mut App) -> Result<(), String> {
                 if existing.account_type.is_liability() && new_balance.cents() > 0 {
                     new_balance = Money::from_cents(-new_balance.cents());
                 }
-                existing.starting_balance = new_balance;
+                let balance_changed = new_balance != existing.starting_balance;
 
-                existing.updated_at = chrono::Utc::now();
+                existing.updated_at = clock::now();
 
                 let account_name = existing.name.clone();
                 app.storage
@@ -642,6 +653,13 @@ fn save_account(app: &mut App) -> Result<(), String> {
                     .map_err(|e| e.to_string())?;
 
                 app.storage.accounts.save().map_err(|e| e.to_string())?;
+
+                // Keep the register's Starting Balance transaction in sync
+                if balance_changed {
+                    AccountService::new(app.storage)
+                        .set_starting_balance(account_id, new_balance, None)
+                        .map_err(|e| e.to_string())?;
+                }
                 app.close_dialog();
                 app.set_status(format!("Account '{}' updated", account_name));
             }
@@ -649,20 +667,30 @@ fn save_account(app: &mut App) -> Result<(), String> {
     } else {
         // Build new account
         let account = app.account_form.build_account()?;
-        let account_name = account.name.clone();
 
-        // Save to storage
-        app.storage
-            .accounts
-            .upsert(account)
+        // Save through the service so the starting balance is recorded in the register
+        let account = AccountService::new(app.storage)
+            .create(
+                &account.name,
+                account.account_type,
+                account.starting_balance,
+                account.on_budget,
+            )
             .map_err(|e| e.to_string())?;
-
-        // Save to disk
-        app.storage.accounts.save().map_err(|e| e.to_string())?;
+        let account_name = account.name.clone();
 
         // Close dialog
         app.close_dialog();
-        app.set_status(format!("Account '{}' created", account_name));
+        match account
+            .account_type
+            .starting_balance_warning(account.starting_balance)
+        {
+            Some(warning) => app.set_status(format!(
+                "Account '{}' created. Warning: {}",
+                account_name, warning
+            )),
+            None => app.set_status(format!("Account '{}' created", account_name)),
+        }
     }
 
     Ok(())
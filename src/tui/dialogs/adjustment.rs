@@ -11,6 +11,7 @@ use ratatui::{
 };
 
 use crate::models::{CategoryId, Money};
+use crate::services::ReconciliationService;
 use crate::tui::app::App;
 use crate::tui::layout::centered_rect_fixed;
 
@@ -195,7 +196,7 @@ pub fn render(frame: &mut Frame, app: &App) {
     // Instructions
     let instructions = Paragraph::new(Line::from(vec![
         Span::styled("[Enter]", Style::default().fg(Color::Green)),
-        Span::raw(" Create  "),
+        Span::raw(" Create & reconcile  "),
         Span::styled("[Tab]", Style::default().fg(Color::Cyan)),
         Span::raw(" Select category  "),
         Span::styled("[Esc]", Style::default().fg(Color::Yellow)),
@@ -229,14 +230,43 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyCode) -> bool {
         }
         KeyCode::Char(c) if !state.selecting_category => {
             state.category_input.push(c);
+            state.selected_category = None;
             state.selecting_category = true;
             state.selected_index = 0;
             true
         }
         KeyCode::Backspace if !state.selecting_category => {
             state.category_input.pop();
+            state.selected_category = None;
             true
         }
         _ => false,
     }
 }
+
+/// Complete the reconciliation in progress with an adjustment transaction
+pub fn submit(app: &mut App) {
+    app.close_dialog();
+
+    let Some(session) = app.reconciliation_state.session() else {
+        return;
+    };
+
+    let service = ReconciliationService::new(app.storage);
+    match service.complete_with_adjustment(&session, app.adjustment_dialog_state.selected_category)
+    {
+        Ok(result) => {
+            app.reconciliation_state
+                .init_for_account(session.account_id);
+            let adjustment = result
+                .adjustment_amount
+                .map(|amount| format!(" with a {} adjustment", amount))
+                .unwrap_or_default();
+            app.set_status(format!(
+                "Reconciled {} transaction(s) at {}{}",
+                result.transactions_reconciled, session.statement_balance, adjustment
+            ));
+        }
+        Err(e) => app.set_status(e.to_string()),
+    }
+}
@@ -5,6 +5,7 @@
 //! - Target settings (recurring budget goals with cadence)
 
 use chrono::NaiveDate;
+use crossterm::event::{KeyCode, KeyEvent};
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
@@ -13,10 +14,13 @@ use ratatui::{
     Frame,
 };
 
-use crate::models::{BudgetTarget, CategoryId, Money, TargetCadence};
+use crate::config::clock;
+use crate::error::EnvelopeResult;
+use crate::models::{BudgetPeriod, BudgetTarget, CategoryId, Money, TargetCadence};
 use crate::services::BudgetService;
-use crate::tui::app::App;
+use crate::tui::app::{App, InputMode};
 use crate::tui::layout::centered_rect_fixed;
+use crate::tui::widgets::TextInput;
 
 /// Which tab is currently active
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -34,6 +38,7 @@ pub enum TargetField {
     Cadence,
     CustomDays,
     TargetDate,
+    Notes,
 }
 
 /// Cadence options for budget targets
@@ -83,6 +88,8 @@ pub struct BudgetDialogState {
     pub suggested_amount: Option<Money>,
     pub period_amount_input: String,
     pub period_cursor: usize,
+    /// Amount already warned about and the negative ATB it would leave
+    pub over_budget_warning: Option<(Money, Money)>,
 
     // Target tab fields
     pub has_existing_target: bool,
@@ -93,6 +100,8 @@ pub struct BudgetDialogState {
     pub custom_days_cursor: usize,
     pub target_date_input: String,
     pub target_date_cursor: usize,
+    pub target_notes_input: String,
+    pub target_notes_cursor: usize,
     pub target_field: TargetField,
 }
 
@@ -125,6 +134,7 @@ impl BudgetDialogState {
             self.period_amount_input = format!("{:.2}", cents as f64 / 100.0);
         }
         self.period_cursor = self.period_amount_input.len();
+        self.over_budget_warning = None;
 
         // Target tab initialization
         if let Some(target) = existing_target {
@@ -152,6 +162,9 @@ impl BudgetDialogState {
                     self.target_date_cursor = self.target_date_input.len();
                 }
             }
+
+            self.target_notes_input = target.notes.clone();
+            self.target_notes_cursor = self.target_notes_input.len();
         } else {
             self.has_existing_target = false;
             self.target_amount_input = String::new();
@@ -159,9 +172,11 @@ impl BudgetDialogState {
             self.cadence = CadenceOption::Monthly;
             self.custom_days_input = "30".to_string();
             self.custom_days_cursor = 2;
-            let default_date = chrono::Local::now().date_naive() + chrono::Duration::days(180);
+            let default_date = clock::today() + chrono::Duration::days(180);
             self.target_date_input = default_date.format("%Y-%m-%d").to_string();
             self.target_date_cursor = self.target_date_input.len();
+            self.target_notes_input = String::new();
+            self.target_notes_cursor = 0;
         }
 
         self.target_field = TargetField::Amount;
@@ -244,23 +259,25 @@ impl BudgetDialogState {
             TargetField::Cadence => match self.cadence {
                 CadenceOption::Custom => TargetField::CustomDays,
                 CadenceOption::ByDate => TargetField::TargetDate,
-                _ => TargetField::Amount,
+                _ => TargetField::Notes,
             },
-            TargetField::CustomDays => TargetField::Amount,
-            TargetField::TargetDate => TargetField::Amount,
+            TargetField::CustomDays => TargetField::Notes,
+            TargetField::TargetDate => TargetField::Notes,
+            TargetField::Notes => TargetField::Amount,
         };
     }
 
     pub fn target_prev_field(&mut self) {
         self.target_field = match self.target_field {
-            TargetField::Amount => match self.cadence {
+            TargetField::Amount => TargetField::Notes,
+            TargetField::Cadence => TargetField::Amount,
+            TargetField::CustomDays => TargetField::Cadence,
+            TargetField::TargetDate => TargetField::Cadence,
+            TargetField::Notes => match self.cadence {
                 CadenceOption::Custom => TargetField::CustomDays,
                 CadenceOption::ByDate => TargetField::TargetDate,
                 _ => TargetField::Cadence,
             },
-            TargetField::Cadence => TargetField::Amount,
-            TargetField::CustomDays => TargetField::Cadence,
-            TargetField::TargetDate => TargetField::Cadence,
         };
     }
 
@@ -307,6 +324,11 @@ impl BudgetDialogState {
                     self.error_message = None;
                 }
             }
+            TargetField::Notes => {
+                self.target_notes_input.insert(self.target_notes_cursor, c);
+                self.target_notes_cursor += c.len_utf8();
+                self.error_message = None;
+            }
             TargetField::Cadence => {}
         }
     }
@@ -334,6 +356,16 @@ impl BudgetDialogState {
                     self.error_message = None;
                 }
             }
+            TargetField::Notes => {
+                if let Some(c) = self.target_notes_input[..self.target_notes_cursor]
+                    .chars()
+                    .next_back()
+                {
+                    self.target_notes_cursor -= c.len_utf8();
+                    self.target_notes_input.remove(self.target_notes_cursor);
+                    self.error_message = None;
+                }
+            }
             TargetField::Cadence => {}
         }
     }
@@ -355,6 +387,14 @@ impl BudgetDialogState {
                     self.target_date_cursor -= 1;
                 }
             }
+            TargetField::Notes => {
+                if let Some(c) = self.target_notes_input[..self.target_notes_cursor]
+                    .chars()
+                    .next_back()
+                {
+                    self.target_notes_cursor -= c.len_utf8();
+                }
+            }
             TargetField::Cadence => self.prev_cadence(),
         }
     }
@@ -376,6 +416,14 @@ impl BudgetDialogState {
                     self.target_date_cursor += 1;
                 }
             }
+            TargetField::Notes => {
+                if let Some(c) = self.target_notes_input[self.target_notes_cursor..]
+                    .chars()
+                    .next()
+                {
+                    self.target_notes_cursor += c.len_utf8();
+                }
+            }
             TargetField::Cadence => self.next_cadence(),
         }
     }
@@ -394,6 +442,10 @@ impl BudgetDialogState {
                 self.target_date_input.clear();
                 self.target_date_cursor = 0;
             }
+            TargetField::Notes => {
+                self.target_notes_input.clear();
+                self.target_notes_cursor = 0;
+            }
             TargetField::Cadence => {}
         }
         self.error_message = None;
@@ -443,6 +495,12 @@ impl BudgetDialogState {
     pub fn set_error(&mut self, msg: impl Into<String>) {
         self.error_message = Some(msg.into());
     }
+
+    /// The over-budget warning, if it is for the amount currently entered
+    pub fn active_over_budget_warning(&self) -> Option<Money> {
+        let (amount, projected) = self.over_budget_warning?;
+        (self.parse_period_amount().ok() == Some(amount)).then_some(projected)
+    }
 }
 
 /// Render the unified budget dialog
@@ -459,21 +517,38 @@ pub fn render(frame: &mut Frame, app: &App) {
             }
         }
         BudgetTab::Target => match state.cadence {
-            CadenceOption::Custom | CadenceOption::ByDate => 15,
-            _ => 13,
+            CadenceOption::Custom | CadenceOption::ByDate => 16,
+            _ => 14,
         },
     };
 
     let area = centered_rect_fixed(55, height, frame.area());
     frame.render_widget(Clear, area);
 
-    let block = Block::default()
-        .title(format!(" Budget: {} ", state.category_name))
-        .title_style(
+    // Archived categories can be budgeted once revealed, but look it
+    let archived = state
+        .category_id
+        .and_then(|id| app.storage.categories.get_category(id).ok().flatten())
+        .is_some_and(|c| c.archived);
+    let (title, title_style) = if archived {
+        (
+            format!(" Budget: {} (archived) ", state.category_name),
+            Style::default()
+                .fg(Color::DarkGray)
+                .add_modifier(Modifier::DIM),
+        )
+    } else {
+        (
+            format!(" Budget: {} ", state.category_name),
             Style::default()
                 .fg(Color::Cyan)
                 .add_modifier(Modifier::BOLD),
         )
+    };
+
+    let block = Block::default()
+        .title(title)
+        .title_style(title_style)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan));
 
@@ -624,13 +699,19 @@ fn render_period_tab(frame: &mut Frame, area: Rect, app: &App) {
     frame.render_widget(Paragraph::new(input_line), chunks[row]);
     row += 1;
 
-    // Error message
+    // Error message, or the warning that this overspends Available to Budget
     if let Some(ref error) = state.error_message {
         let error_line = Line::from(Span::styled(
             error.as_str(),
             Style::default().fg(Color::Red),
         ));
         frame.render_widget(Paragraph::new(error_line), chunks[row]);
+    } else if let Some(projected) = state.active_over_budget_warning() {
+        let warning_line = Line::from(Span::styled(
+            format!("ATB would be {}. Enter again to save anyway.", projected),
+            Style::default().fg(Color::Yellow),
+        ));
+        frame.render_widget(Paragraph::new(warning_line), chunks[row]);
     }
     row += 1;
 
@@ -666,6 +747,7 @@ fn render_target_tab(frame: &mut Frame, area: Rect, app: &App) {
         constraints.push(Constraint::Length(1)); // Extra field (days or date)
     }
 
+    constraints.push(Constraint::Length(1)); // Notes
     constraints.push(Constraint::Length(1)); // Spacer
     constraints.push(Constraint::Length(1)); // Error
     constraints.push(Constraint::Length(1)); // Instructions
@@ -730,6 +812,18 @@ fn render_target_tab(frame: &mut Frame, area: Rect, app: &App) {
         row += 1;
     }
 
+    // Notes field
+    render_labeled_input(
+        frame,
+        chunks[row],
+        "Notes",
+        "",
+        &state.target_notes_input,
+        state.target_notes_cursor,
+        state.target_field == TargetField::Notes,
+    );
+    row += 1;
+
     row += 1; // Spacer
 
     // Error message
@@ -830,9 +924,9 @@ fn render_labeled_input(
             Style::default().fg(Color::Black).bg(Color::Cyan),
         ));
 
-        if after.len() > 1 {
+        if after.len() > cursor_char.len_utf8() {
             spans.push(Span::styled(
-                after[1..].to_string(),
+                after[cursor_char.len_utf8()..].to_string(),
                 Style::default().fg(Color::White),
             ));
         }
@@ -963,13 +1057,21 @@ fn handle_target_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
     use crossterm::event::{KeyCode, KeyModifiers};
 
     match key.code {
-        // Field navigation: j/k or up/down arrows
-        KeyCode::Down | KeyCode::Char('j') => {
+        // Field navigation: j/k or up/down arrows (j/k type into notes)
+        KeyCode::Down => {
+            app.budget_dialog_state.target_next_field();
+            true
+        }
+        KeyCode::Char('j') if app.budget_dialog_state.target_field != TargetField::Notes => {
             app.budget_dialog_state.target_next_field();
             true
         }
 
-        KeyCode::Up | KeyCode::Char('k') => {
+        KeyCode::Up => {
+            app.budget_dialog_state.target_prev_field();
+            true
+        }
+        KeyCode::Char('k') if app.budget_dialog_state.target_field != TargetField::Notes => {
             app.budget_dialog_state.target_prev_field();
             true
         }
@@ -1014,6 +1116,234 @@ fn handle_target_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
     }
 }
 
+/// The negative Available to Budget that budgeting `amount` would leave
+///
+/// Every way of budgeting from the budget view checks this first, so none of
+/// them overbudgets without a warning. Lowering a budget never warns.
+fn over_budget_projection(
+    budget_service: &BudgetService,
+    category_id: CategoryId,
+    period: &BudgetPeriod,
+    amount: Money,
+) -> EnvelopeResult<Option<Money>> {
+    let current = budget_service
+        .get_category_summary(category_id, period)?
+        .budgeted;
+    if amount <= current {
+        return Ok(None);
+    }
+    let projected = budget_service.available_after_assignment(category_id, period, amount)?;
+    Ok(projected.is_negative().then_some(projected))
+}
+
+/// Set the category's period budget to its target's suggested amount
+///
+/// A quicker path than opening the dialog for categories funded to target
+/// every period. Categories without a target are left alone. Like the
+/// dialog, it warns once before overbudgeting; pressing it again for the
+/// same amount funds anyway.
+pub fn fund_to_target(app: &mut App, category_id: CategoryId) {
+    let budget_service =
+        BudgetService::new(app.storage).with_target_rounding(app.settings.target_rounding);
+
+    let name = match app.storage.categories.get_category(category_id) {
+        Ok(Some(category)) => category.name,
+        _ => return,
+    };
+
+    let suggested =
+        match budget_service.get_suggested_budget_with_progress(category_id, &app.current_period) {
+            Ok(Some(amount)) => amount,
+            Ok(None) => {
+                app.set_status(format!("'{}' has no target", name));
+                return;
+            }
+            Err(e) => {
+                app.set_status(format!("Failed to compute suggestion: {}", e));
+                return;
+            }
+        };
+
+    if app.fund_to_target_warning != Some((category_id, suggested)) {
+        match over_budget_projection(&budget_service, category_id, &app.current_period, suggested) {
+            Ok(Some(projected)) => {
+                app.fund_to_target_warning = Some((category_id, suggested));
+                app.set_status(format!(
+                    "Funding '{}' to {} would leave ATB at {}. Press f again to fund anyway.",
+                    name, suggested, projected
+                ));
+                return;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                app.set_status(format!("Failed to check Available to Budget: {}", e));
+                return;
+            }
+        }
+    }
+    app.fund_to_target_warning = None;
+
+    match budget_service.assign_to_category(category_id, &app.current_period, suggested) {
+        Ok(_) => app.set_status(format!("Budget for '{}' set to {}", name, suggested)),
+        Err(e) => app.set_status(format!("Failed to set budget: {}", e)),
+    }
+}
+
+/// Budgeted cell being edited in place in the budget view
+#[derive(Debug, Clone)]
+pub struct InlineBudgetEdit {
+    /// Category whose budgeted amount is being edited
+    pub category_id: CategoryId,
+    /// Amount typed so far
+    pub input: TextInput,
+    /// Amount already warned about for leaving Available to Budget negative
+    pub over_budget_warning: Option<Money>,
+}
+
+/// Turn the selected category's budgeted cell into an editable field
+pub fn start_inline_edit(app: &mut App, category_id: CategoryId) {
+    let budgeted = BudgetService::new(app.storage)
+        .get_category_summary(category_id, &app.current_period)
+        .map(|summary| summary.budgeted)
+        .unwrap_or_default();
+    let content = if budgeted.is_zero() {
+        String::new()
+    } else {
+        format!("{:.2}", budgeted.cents() as f64 / 100.0)
+    };
+
+    app.inline_budget_edit = Some(InlineBudgetEdit {
+        category_id,
+        input: TextInput::new().focused(true).content(content),
+        over_budget_warning: None,
+    });
+    app.input_mode = InputMode::Editing;
+}
+
+/// Leave inline editing without saving
+fn end_inline_edit(app: &mut App) {
+    app.inline_budget_edit = None;
+    app.input_mode = InputMode::Normal;
+}
+
+/// Save the inline edit, returning false (with a status message) on failure
+///
+/// An unchanged amount isn't saved, so tabbing through categories doesn't
+/// write an audit entry for each one. An amount that would leave Available
+/// to Budget negative is held back once with a warning, as in the dialog.
+fn commit_inline_edit(app: &mut App) -> bool {
+    let Some(edit) = &app.inline_budget_edit else {
+        return true;
+    };
+    let category_id = edit.category_id;
+    let warned = edit.over_budget_warning;
+
+    let amount = if edit.input.value().trim().is_empty() {
+        Money::zero()
+    } else {
+        match Money::parse(edit.input.value()) {
+            Ok(amount) => amount,
+            Err(_) => {
+                app.set_status("Invalid amount format");
+                return false;
+            }
+        }
+    };
+
+    let budget_service = BudgetService::new(app.storage);
+    let current = budget_service
+        .get_category_summary(category_id, &app.current_period)
+        .map(|summary| summary.budgeted)
+        .unwrap_or_default();
+    if amount == current {
+        return true;
+    }
+
+    if warned != Some(amount) {
+        match over_budget_projection(&budget_service, category_id, &app.current_period, amount) {
+            Ok(Some(projected)) => {
+                if let Some(edit) = app.inline_budget_edit.as_mut() {
+                    edit.over_budget_warning = Some(amount);
+                }
+                app.set_status(format!(
+                    "ATB would be {}. Save again to budget anyway.",
+                    projected
+                ));
+                return false;
+            }
+            Ok(None) => {}
+            Err(e) => {
+                app.set_status(format!("Failed to check Available to Budget: {}", e));
+                return false;
+            }
+        }
+    }
+
+    match budget_service.assign_to_category(category_id, &app.current_period, amount) {
+        Ok(_) => {
+            let name = app
+                .storage
+                .categories
+                .get_category(category_id)
+                .ok()
+                .flatten()
+                .map(|c| c.name)
+                .unwrap_or_default();
+            app.set_status(format!("Budget for '{}' set to {}", name, amount));
+            true
+        }
+        Err(e) => {
+            app.set_status(format!("Failed to set budget: {}", e));
+            false
+        }
+    }
+}
+
+/// Handle keys while a budgeted cell is being edited inline
+///
+/// Enter saves and Esc cancels. Tab/Down and Shift+Tab/Up save and move
+/// straight on to editing the next or previous category.
+pub fn handle_inline_edit_key(app: &mut App, key: KeyEvent) {
+    let Some(edit) = app.inline_budget_edit.as_mut() else {
+        return;
+    };
+
+    match key.code {
+        KeyCode::Esc => end_inline_edit(app),
+        KeyCode::Enter => {
+            if commit_inline_edit(app) {
+                end_inline_edit(app);
+            }
+        }
+        KeyCode::Tab | KeyCode::Down | KeyCode::BackTab | KeyCode::Up => {
+            if !commit_inline_edit(app) {
+                return;
+            }
+            let categories = app.budget_categories();
+            if matches!(key.code, KeyCode::Tab | KeyCode::Down) {
+                app.move_down(categories.len());
+            } else {
+                app.move_up();
+            }
+            match categories.get(app.selected_category_index) {
+                Some(category) => {
+                    app.selected_category = Some(category.id);
+                    start_inline_edit(app, category.id);
+                }
+                None => end_inline_edit(app),
+            }
+        }
+        KeyCode::Char(c) if c.is_ascii_digit() || c == '.' || c == '-' => edit.input.insert(c),
+        KeyCode::Backspace => edit.input.backspace(),
+        KeyCode::Delete => edit.input.delete(),
+        KeyCode::Left => edit.input.move_left(),
+        KeyCode::Right => edit.input.move_right(),
+        KeyCode::Home => edit.input.move_start(),
+        KeyCode::End => edit.input.move_end(),
+        _ => {}
+    }
+}
+
 fn save_period_budget(app: &mut App) -> Result<(), String> {
     let state = &app.budget_dialog_state;
 
@@ -1021,6 +1351,19 @@ fn save_period_budget(app: &mut App) -> Result<(), String> {
     let amount = state.parse_period_amount()?;
 
     let budget_service = BudgetService::new(app.storage);
+
+    // Warn once before budgeting money that isn't there; a second Enter on
+    // the same amount saves anyway
+    if state.active_over_budget_warning().is_none() {
+        if let Some(projected) =
+            over_budget_projection(&budget_service, category_id, &app.current_period, amount)
+                .map_err(|e| e.to_string())?
+        {
+            app.budget_dialog_state.over_budget_warning = Some((amount, projected));
+            return Ok(());
+        }
+    }
+
     budget_service
         .assign_to_category(category_id, &app.current_period, amount)
         .map_err(|e| e.to_string())?;
@@ -1041,9 +1384,14 @@ fn save_target(app: &mut App) -> Result<(), String> {
     let cadence = state.build_cadence()?;
 
     let budget_service = BudgetService::new(app.storage);
-    budget_service
+    let target = budget_service
         .set_target(category_id, amount, cadence)
         .map_err(|e| e.to_string())?;
+    if target.notes != state.target_notes_input.trim() {
+        budget_service
+            .set_target_notes(category_id, &state.target_notes_input)
+            .map_err(|e| e.to_string())?;
+    }
 
     let cat_name = state.category_name.clone();
     app.budget_dialog_state.reset();
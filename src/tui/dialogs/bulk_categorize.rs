@@ -1,6 +1,7 @@
 //! Bulk categorize dialog
 //!
-//! Apply category to multiple selected transactions
+//! Apply category to multiple selected transactions, or to every
+//! uncategorized transaction from the first selected transaction's payee
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -10,10 +11,12 @@ use ratatui::{
     Frame,
 };
 
+use crate::config::clock;
 use crate::models::CategoryId;
-use crate::services::CategoryService;
+use crate::services::{CategoryService, TransactionService};
 use crate::tui::app::App;
 use crate::tui::layout::centered_rect;
+use crate::tui::widgets::fuzzy_filter;
 
 /// State for the bulk categorize dialog
 #[derive(Debug, Clone, Default)]
@@ -30,6 +33,9 @@ pub struct BulkCategorizeState {
     pub error_message: Option<String>,
     /// Success message
     pub success_message: Option<String>,
+    /// Apply to all uncategorized transactions from the payee instead of
+    /// the selected transactions
+    pub by_payee: bool,
 }
 
 impl BulkCategorizeState {
@@ -92,14 +98,25 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     // Clear the background
     frame.render_widget(Clear, area);
 
-    let count = app.selected_transactions.len();
-
-    let block = Block::default()
-        .title(format!(
-            " Categorize {} Transaction{} ",
+    let title = match payee_scope(app) {
+        Some((payee, count)) if app.bulk_categorize_state.by_payee => format!(
+            " Categorize {} Uncategorized '{}' Transaction{} ",
             count,
+            payee,
             if count == 1 { "" } else { "s" }
-        ))
+        ),
+        _ => {
+            let count = app.selected_transactions.len();
+            format!(
+                " Categorize {} Transaction{} ",
+                count,
+                if count == 1 { "" } else { "s" }
+            )
+        }
+    };
+
+    let block = Block::default()
+        .title(title)
         .title_style(
             Style::default()
                 .fg(Color::Cyan)
@@ -137,11 +154,11 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     let all_categories = category_service.list_categories().unwrap_or_default();
 
     // Filter categories by search
-    let search = app.bulk_categorize_state.search_input.to_lowercase();
-    let filtered_categories: Vec<_> = all_categories
-        .iter()
-        .filter(|c| search.is_empty() || c.name.to_lowercase().contains(&search))
-        .collect();
+    let filtered_categories = fuzzy_filter(
+        &all_categories,
+        &app.bulk_categorize_state.search_input,
+        |c| c.name.as_str(),
+    );
 
     // Search input
     render_search_field(
@@ -182,12 +199,34 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         Span::raw(" Select  "),
         Span::styled("[Enter]", Style::default().fg(Color::Green)),
         Span::raw(" Apply  "),
+        Span::styled("[Tab]", Style::default().fg(Color::Yellow)),
+        Span::raw(if app.bulk_categorize_state.by_payee {
+            " Selected only  "
+        } else {
+            " All from payee  "
+        }),
         Span::styled("[Esc]", Style::default().fg(Color::Red)),
         Span::raw(" Cancel"),
     ]);
     frame.render_widget(Paragraph::new(hints), chunks[6]);
 }
 
+/// The payee of the first selected transaction, with how many of its
+/// transactions are uncategorized across all accounts
+fn payee_scope(app: &App) -> Option<(String, usize)> {
+    let txn_id = app.selected_transactions.first()?;
+    let txn = app.storage.transactions.get(*txn_id).ok()??;
+    if txn.payee_name.trim().is_empty() {
+        return None;
+    }
+
+    let count = TransactionService::new(app.storage)
+        .uncategorized_for_payee(&txn.payee_name)
+        .map(|t| t.len())
+        .unwrap_or(0);
+    Some((txn.payee_name, count))
+}
+
 /// Render the search field
 fn render_search_field(
     frame: &mut Frame,
@@ -285,11 +324,11 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
     // Get filtered categories for index bounds
     let category_service = CategoryService::new(app.storage);
     let all_categories = category_service.list_categories().unwrap_or_default();
-    let search = app.bulk_categorize_state.search_input.to_lowercase();
-    let filtered: Vec<_> = all_categories
-        .iter()
-        .filter(|c| search.is_empty() || c.name.to_lowercase().contains(&search))
-        .collect();
+    let filtered = fuzzy_filter(
+        &all_categories,
+        &app.bulk_categorize_state.search_input,
+        |c| c.name.as_str(),
+    );
     let cat_count = filtered.len();
 
     match key.code {
@@ -307,7 +346,11 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
                     .category_list_index
                     .min(cat_count.saturating_sub(1));
                 if let Some(cat) = filtered.get(idx) {
-                    execute_bulk_categorize(app, cat.id);
+                    if app.bulk_categorize_state.by_payee {
+                        execute_categorize_payee(app, cat.id);
+                    } else {
+                        execute_bulk_categorize(app, cat.id);
+                    }
                 }
             } else {
                 app.bulk_categorize_state.set_error("No category selected");
@@ -315,6 +358,21 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
             return true;
         }
 
+        KeyCode::Tab => {
+            // Switch between the selection and all uncategorized from the payee
+            if app.bulk_categorize_state.by_payee {
+                app.bulk_categorize_state.by_payee = false;
+                app.bulk_categorize_state.clear_error();
+            } else if payee_scope(app).is_some() {
+                app.bulk_categorize_state.by_payee = true;
+                app.bulk_categorize_state.clear_error();
+            } else {
+                app.bulk_categorize_state
+                    .set_error("Selected transaction has no payee");
+            }
+            return true;
+        }
+
         KeyCode::Up | KeyCode::Char('k') => {
             if app.bulk_categorize_state.category_list_index > 0 {
                 app.bulk_categorize_state.category_list_index -= 1;
@@ -368,14 +426,14 @@ fn execute_bulk_categorize(app: &mut App, category_id: CategoryId) {
     for txn_id in &transaction_ids {
         match app.storage.transactions.get(*txn_id) {
             Ok(Some(mut txn)) => {
-                // Skip transfers (they shouldn't be categorized)
-                if txn.is_transfer() {
+                // Skip transfers and starting balances (they shouldn't be categorized)
+                if txn.is_transfer() || txn.is_starting_balance {
                     continue;
                 }
 
                 // Update category
                 txn.category_id = Some(category_id);
-                txn.updated_at = chrono::Utc::now();
+                txn.updated_at = clock::now();
 
                 if app.storage.transactions.upsert(txn).is_ok() {
                     success_count += 1;
@@ -427,3 +485,49 @@ fn execute_bulk_categorize(app: &mut App, category_id: CategoryId) {
     app.bulk_categorize_state.reset();
     app.close_dialog();
 }
+
+/// Categorize every uncategorized transaction from the selected payee
+fn execute_categorize_payee(app: &mut App, category_id: CategoryId) {
+    let Some((payee, count)) = payee_scope(app) else {
+        app.bulk_categorize_state
+            .set_error("Selected transaction has no payee");
+        return;
+    };
+
+    if count == 0 {
+        app.bulk_categorize_state
+            .set_error(format!("No uncategorized transactions from '{}'", payee));
+        return;
+    }
+
+    let service = TransactionService::new(app.storage);
+    let categorized = match service.categorize_payee(&payee, category_id) {
+        Ok(categorized) => categorized,
+        Err(e) => {
+            app.bulk_categorize_state
+                .set_error(format!("Failed to categorize: {}", e));
+            return;
+        }
+    };
+
+    let category_name = CategoryService::new(app.storage)
+        .get_category(category_id)
+        .ok()
+        .flatten()
+        .map(|c| c.name)
+        .unwrap_or_else(|| "Unknown".into());
+
+    app.selected_transactions.clear();
+    app.multi_select_mode = false;
+
+    app.set_status(format!(
+        "Categorized {} '{}' transaction{} as '{}'",
+        categorized,
+        payee,
+        if categorized == 1 { "" } else { "s" },
+        category_name
+    ));
+
+    app.bulk_categorize_state.reset();
+    app.close_dialog();
+}
@@ -1,7 +1,7 @@
 //! Category entry dialog
 //!
 //! Modal dialog for adding new budget categories with form validation,
-//! group selection, and save/cancel functionality.
+//! group selection, notes, and save/cancel functionality.
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
@@ -23,6 +23,7 @@ pub enum CategoryField {
     #[default]
     Name,
     Group,
+    Notes,
 }
 
 /// State for the category form dialog
@@ -37,6 +38,9 @@ pub struct CategoryFormState {
     /// Available groups (cached)
     pub groups: Vec<(CategoryGroupId, String)>,
 
+    /// Notes input
+    pub notes_input: TextInput,
+
     /// Currently focused field
     pub focused_field: CategoryField,
 
@@ -62,6 +66,7 @@ impl CategoryFormState {
                 .placeholder("Category name (e.g., Groceries, Rent)"),
             selected_group_index: 0,
             groups: Vec::new(),
+            notes_input: notes_input(),
             focused_field: CategoryField::Name,
             error_message: None,
             editing_id: None,
@@ -75,6 +80,7 @@ impl CategoryFormState {
         self.name_input = TextInput::new()
             .label("Name")
             .placeholder("Category name (e.g., Groceries, Rent)");
+        self.notes_input = notes_input();
         self.focused_field = CategoryField::Name;
         self.error_message = None;
         self.editing_id = None;
@@ -90,6 +96,7 @@ impl CategoryFormState {
             .label("Name")
             .placeholder("Category name (e.g., Groceries, Rent)")
             .content(&category.name);
+        self.notes_input = notes_input().content(&category.notes);
 
         // Find and select the current group
         self.selected_group_index = self
@@ -111,15 +118,17 @@ impl CategoryFormState {
     pub fn next_field(&mut self) {
         self.focused_field = match self.focused_field {
             CategoryField::Name => CategoryField::Group,
-            CategoryField::Group => CategoryField::Name,
+            CategoryField::Group => CategoryField::Notes,
+            CategoryField::Notes => CategoryField::Name,
         };
     }
 
     /// Move to previous field
     pub fn prev_field(&mut self) {
         self.focused_field = match self.focused_field {
-            CategoryField::Name => CategoryField::Group,
+            CategoryField::Name => CategoryField::Notes,
             CategoryField::Group => CategoryField::Name,
+            CategoryField::Notes => CategoryField::Group,
         };
     }
 
@@ -191,9 +200,16 @@ impl CategoryFormState {
     }
 }
 
+/// An empty notes input
+fn notes_input() -> TextInput {
+    TextInput::new()
+        .label("Notes")
+        .placeholder("Optional (e.g., bump this in December)")
+}
+
 /// Render the category dialog
 pub fn render(frame: &mut Frame, app: &mut App) {
-    let area = centered_rect(50, 30, frame.area());
+    let area = centered_rect(50, 35, frame.area());
 
     // Clear the background
     frame.render_widget(Clear, area);
@@ -233,6 +249,8 @@ pub fn render(frame: &mut Frame, app: &mut App) {
             Constraint::Length(1), // Spacer
             Constraint::Length(1), // Group label
             Constraint::Length(1), // Spacer
+            Constraint::Length(1), // Notes label
+            Constraint::Length(1), // Spacer
             Constraint::Length(1), // Error
             Constraint::Length(1), // Buttons
             Constraint::Min(0),    // Remaining
@@ -243,6 +261,9 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     let name_value = app.category_form.name_input.value().to_string();
     let name_cursor = app.category_form.name_input.cursor;
     let name_placeholder = app.category_form.name_input.placeholder.clone();
+    let notes_value = app.category_form.notes_input.value().to_string();
+    let notes_cursor = app.category_form.notes_input.cursor;
+    let notes_placeholder = app.category_form.notes_input.placeholder.clone();
     let focused_field = app.category_form.focused_field;
     let error_message = app.category_form.error_message.clone();
     let group_name = app
@@ -271,13 +292,24 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         focused_field == CategoryField::Group,
     );
 
+    // Render notes field
+    render_text_field(
+        frame,
+        chunks[4],
+        "Notes",
+        &notes_value,
+        focused_field == CategoryField::Notes,
+        notes_cursor,
+        &notes_placeholder,
+    );
+
     // Render error message if any
     if let Some(ref error) = error_message {
         let error_line = Line::from(Span::styled(
             error.as_str(),
             Style::default().fg(Color::Red),
         ));
-        frame.render_widget(Paragraph::new(error_line), chunks[4]);
+        frame.render_widget(Paragraph::new(error_line), chunks[6]);
     }
 
     // Render buttons/hints
@@ -289,7 +321,7 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         Span::styled("[Esc]", Style::default().fg(Color::Red)),
         Span::raw(" Cancel"),
     ]);
-    frame.render_widget(Paragraph::new(hints), chunks[5]);
+    frame.render_widget(Paragraph::new(hints), chunks[7]);
 }
 
 /// Render a text field
@@ -380,14 +412,12 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
             return true;
         }
 
-        KeyCode::Tab | KeyCode::Down if app.category_form.focused_field == CategoryField::Name => {
+        KeyCode::Tab | KeyCode::Down => {
             app.category_form.next_field();
             return true;
         }
 
-        KeyCode::BackTab | KeyCode::Up
-            if app.category_form.focused_field == CategoryField::Group =>
-        {
+        KeyCode::BackTab | KeyCode::Up => {
             app.category_form.prev_field();
             return true;
         }
@@ -405,53 +435,61 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
 
     // Field-specific handling
     match app.category_form.focused_field {
-        CategoryField::Name => handle_name_input(app, key),
+        CategoryField::Name | CategoryField::Notes => handle_text_input(app, key),
         CategoryField::Group => handle_group_selector(app, key),
     }
 }
 
-/// Handle input for the name field
-fn handle_name_input(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
+/// Handle input for the focused text field (name or notes)
+fn handle_text_input(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
     use crossterm::event::KeyCode;
 
     let form = &mut app.category_form;
+    if matches!(
+        key.code,
+        KeyCode::Backspace | KeyCode::Delete | KeyCode::Char(_)
+    ) {
+        form.clear_error();
+    }
+    let input = if form.focused_field == CategoryField::Notes {
+        &mut form.notes_input
+    } else {
+        &mut form.name_input
+    };
 
     match key.code {
         KeyCode::Backspace => {
-            form.clear_error();
-            form.name_input.backspace();
+            input.backspace();
             true
         }
 
         KeyCode::Delete => {
-            form.clear_error();
-            form.name_input.delete();
+            input.delete();
             true
         }
 
         KeyCode::Left => {
-            form.name_input.move_left();
+            input.move_left();
             true
         }
 
         KeyCode::Right => {
-            form.name_input.move_right();
+            input.move_right();
             true
         }
 
         KeyCode::Home => {
-            form.name_input.move_start();
+            input.move_start();
             true
         }
 
         KeyCode::End => {
-            form.name_input.move_end();
+            input.move_end();
             true
         }
 
         KeyCode::Char(c) => {
-            form.clear_error();
-            form.name_input.insert(c);
+            input.insert(c);
             true
         }
 
@@ -486,6 +524,7 @@ fn save_category(app: &mut App) -> Result<(), String> {
     app.category_form.validate()?;
 
     let name = app.category_form.name_input.value().trim().to_string();
+    let notes = app.category_form.notes_input.value().trim().to_string();
     let group_id = app
         .category_form
         .selected_group_id()
@@ -508,14 +547,23 @@ fn save_category(app: &mut App) -> Result<(), String> {
             }
         }
 
+        category_service
+            .set_notes(category_id, &notes)
+            .map_err(|e| e.to_string())?;
+
         // Close dialog
         app.close_dialog();
         app.set_status(format!("Category '{}' updated", name));
     } else {
         // Create new category
-        category_service
+        let category = category_service
             .create_category(&name, group_id)
             .map_err(|e| e.to_string())?;
+        if !notes.is_empty() {
+            category_service
+                .set_notes(category.id, &notes)
+                .map_err(|e| e.to_string())?;
+        }
 
         // Close dialog
         app.close_dialog();
@@ -0,0 +1,217 @@
+//! Go-to dialog
+//!
+//! A fuzzy picker over every account, category, and payee. Choosing one
+//! switches to the view that shows it and selects it there.
+
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::models::{AccountId, CategoryId, PayeeId};
+use crate::storage::Storage;
+use crate::tui::app::{ActiveView, App, FocusedPanel};
+use crate::tui::layout::centered_rect_fixed;
+use crate::tui::widgets::{FuzzyPicker, PickerAction};
+
+/// What a go-to entry points at
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GoToTarget {
+    Account(AccountId),
+    Category(CategoryId),
+    Payee(PayeeId),
+}
+
+impl GoToTarget {
+    /// Icon shown before the entry's name
+    fn icon(&self) -> &'static str {
+        match self {
+            Self::Account(_) => "▣",
+            Self::Category(_) => "●",
+            Self::Payee(_) => "◇",
+        }
+    }
+}
+
+/// A named entity that can be jumped to
+#[derive(Debug, Clone)]
+pub struct GoToEntry {
+    pub target: GoToTarget,
+    pub name: String,
+}
+
+/// State for the go-to dialog
+#[derive(Debug, Clone, Default)]
+pub struct GoToState {
+    /// Picker over the entries' names
+    pub picker: FuzzyPicker,
+    /// Active accounts, then categories, then payees
+    pub entries: Vec<GoToEntry>,
+}
+
+impl GoToState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reset the picker and load every entry from storage
+    pub fn init(&mut self, storage: &Storage) {
+        self.picker.reset();
+        self.entries.clear();
+
+        let accounts = storage.accounts.get_active().unwrap_or_default();
+        self.entries.extend(accounts.into_iter().map(|a| GoToEntry {
+            target: GoToTarget::Account(a.id),
+            name: a.name,
+        }));
+
+        let categories = storage.categories.get_all_categories().unwrap_or_default();
+        self.entries
+            .extend(categories.into_iter().map(|c| GoToEntry {
+                target: GoToTarget::Category(c.id),
+                name: c.name,
+            }));
+
+        let payees = storage.payees.get_all().unwrap_or_default();
+        self.entries.extend(payees.into_iter().map(|p| GoToEntry {
+            target: GoToTarget::Payee(p.id),
+            name: p.name,
+        }));
+    }
+}
+
+/// Render the go-to dialog
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let area = centered_rect_fixed(60, 20, frame.area());
+    frame.render_widget(Clear, area);
+
+    let block = Block::default()
+        .title(" Go To ")
+        .title_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    frame.render_widget(block, area);
+
+    let inner = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(3),
+    };
+
+    let state = &app.go_to_state;
+    let labels: Vec<String> = state
+        .picker
+        .filter(&state.entries, |e| e.name.as_str())
+        .into_iter()
+        .map(|e| format!("{} {}", e.target.icon(), e.name))
+        .collect();
+    state.picker.render(frame, inner, &labels);
+
+    let hints = Line::from(vec![
+        Span::styled("▣", Style::default().fg(Color::Cyan)),
+        Span::raw(" account  "),
+        Span::styled("●", Style::default().fg(Color::Cyan)),
+        Span::raw(" category  "),
+        Span::styled("◇", Style::default().fg(Color::Cyan)),
+        Span::raw(" payee  "),
+        Span::styled("Enter", Style::default().fg(Color::Yellow)),
+        Span::raw(" go  "),
+        Span::styled("Esc", Style::default().fg(Color::Yellow)),
+        Span::raw(" close"),
+    ]);
+    let hints_area = Rect {
+        y: area.y + area.height.saturating_sub(2),
+        height: 1,
+        ..inner
+    };
+    frame.render_widget(Paragraph::new(hints), hints_area);
+}
+
+/// Handle key input for the go-to dialog
+pub fn handle_key(app: &mut App, key: KeyEvent) {
+    let state = &mut app.go_to_state;
+    let matches: Vec<GoToEntry> = state
+        .picker
+        .filter(&state.entries, |e| e.name.as_str())
+        .into_iter()
+        .cloned()
+        .collect();
+
+    match state.picker.handle_key(key, matches.len()) {
+        PickerAction::Select(index) => {
+            app.close_dialog();
+            jump_to(app, &matches[index]);
+        }
+        PickerAction::Cancel => app.close_dialog(),
+        PickerAction::Handled | PickerAction::Ignored => {}
+    }
+}
+
+/// Switch to the view showing the entry and select it
+fn jump_to(app: &mut App, entry: &GoToEntry) {
+    match entry.target {
+        GoToTarget::Account(account_id) => {
+            let accounts = app.storage.accounts.get_active().unwrap_or_default();
+            let Some(index) = accounts.iter().position(|a| a.id == account_id) else {
+                return;
+            };
+            app.selected_account_index = index;
+            app.selected_account = Some(account_id);
+            app.switch_view(ActiveView::Register);
+        }
+        GoToTarget::Category(category_id) => {
+            // A category in a collapsed group can't be selected until it's shown
+            if let Ok(Some(category)) = app.storage.categories.get_category(category_id) {
+                app.collapsed_groups.remove(&category.group_id);
+            }
+            app.switch_view(ActiveView::Budget);
+            if let Some(index) = app
+                .budget_categories()
+                .iter()
+                .position(|c| c.id == category_id)
+            {
+                app.selected_category_index = index;
+                app.selected_category = Some(category_id);
+            }
+        }
+        GoToTarget::Payee(payee_id) => {
+            // Payees span accounts, so show them in the All Accounts register
+            let account_count = app
+                .storage
+                .accounts
+                .get_active()
+                .map(|a| a.len())
+                .unwrap_or(0);
+            app.selected_account_index = account_count;
+            app.selected_account = None;
+            app.switch_view(ActiveView::Register);
+
+            // Select the payee's latest transaction
+            let transactions = app.register_transactions();
+            let latest = transactions
+                .iter()
+                .enumerate()
+                .filter(|(_, t)| {
+                    t.payee_id == Some(payee_id) || t.payee_name.eq_ignore_ascii_case(&entry.name)
+                })
+                .max_by_key(|(_, t)| t.date);
+            match latest {
+                Some((index, txn)) => {
+                    app.selected_transaction_index = index;
+                    app.selected_transaction = Some(txn.id);
+                }
+                None => app.set_status(format!("No transactions for '{}'", entry.name)),
+            }
+        }
+    }
+    app.focused_panel = FocusedPanel::Main;
+}
@@ -1,8 +1,10 @@
 //! Help dialog
 //!
-//! Shows contextual keyboard shortcuts
+//! Shows contextual keyboard shortcuts alongside a cheat-sheet of every
+//! palette command, generated from `COMMANDS`
 
 use ratatui::{
+    layout::{Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, Paragraph, Wrap},
@@ -10,11 +12,12 @@ use ratatui::{
 };
 
 use crate::tui::app::{ActiveView, App};
+use crate::tui::commands::commands_by_section;
 use crate::tui::layout::centered_rect;
 
 /// Render the help dialog
 pub fn render(frame: &mut Frame, app: &mut App) {
-    let area = centered_rect(60, 70, frame.area());
+    let area = centered_rect(85, 85, frame.area());
 
     // Clear the background
     frame.render_widget(Clear, area);
@@ -29,14 +32,68 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         .borders(Borders::ALL)
         .border_style(Style::default().fg(Color::Cyan));
 
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(inner);
+
     // Build help text based on current view
     let help_lines = get_help_lines(app);
+    let paragraph = Paragraph::new(help_lines).wrap(Wrap { trim: false });
+    frame.render_widget(paragraph, columns[0]);
 
-    let paragraph = Paragraph::new(help_lines)
-        .block(block)
+    let cheat_sheet = Paragraph::new(get_command_lines())
+        .block(
+            Block::default()
+                .borders(Borders::LEFT)
+                .border_style(Style::default().fg(Color::DarkGray)),
+        )
         .wrap(Wrap { trim: false });
+    frame.render_widget(cheat_sheet, columns[1]);
+}
 
-    frame.render_widget(paragraph, area);
+/// Get cheat-sheet lines for every palette command
+fn get_command_lines() -> Vec<Line<'static>> {
+    let mut lines = vec![section_line("Commands (: to run)"), Line::from("")];
+
+    for (section, commands) in commands_by_section() {
+        lines.push(Line::from(vec![Span::styled(
+            format!(" {}", section),
+            Style::default()
+                .fg(Color::Magenta)
+                .add_modifier(Modifier::BOLD),
+        )]));
+        for cmd in commands {
+            lines.push(Line::from(vec![
+                Span::styled(
+                    format!("{:>8}", cmd.shortcut.unwrap_or("")),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::raw("  "),
+                Span::styled(
+                    format!("{:<20}", cmd.name),
+                    Style::default().fg(Color::Green),
+                ),
+                Span::styled(cmd.description, Style::default().fg(Color::White)),
+            ]));
+        }
+        lines.push(Line::from(""));
+    }
+
+    lines
+}
+
+/// Create a section heading line
+fn section_line(title: &'static str) -> Line<'static> {
+    Line::from(vec![Span::styled(
+        title,
+        Style::default()
+            .add_modifier(Modifier::BOLD)
+            .fg(Color::Yellow),
+    )])
 }
 
 /// Get help lines for the current context
@@ -52,6 +109,7 @@ fn get_help_lines(app: &App) -> Vec<Line<'static>> {
         key_line("q", "Quit application"),
         key_line("?", "Show/hide help"),
         key_line(":", "Open command palette"),
+        key_line("Ctrl+F", "Go to account, category, or payee"),
         key_line("Tab", "Switch panel focus"),
         key_line("h/l", "Move focus left/right"),
         key_line("j/k", "Move selection up/down"),
@@ -72,7 +130,9 @@ fn get_help_lines(app: &App) -> Vec<Line<'static>> {
             lines.push(key_line("1", "Switch to Accounts view"));
             lines.push(key_line("2", "Switch to Budget view"));
             lines.push(key_line("3", "Switch to Reports view"));
-            lines.push(key_line("A", "Toggle archived accounts"));
+            lines.push(key_line("A", "Toggle archived accounts and categories"));
+            lines.push(key_line("f", "Find account by typing its name"));
+            lines.push(key_line("J/K", "Move account down/up"));
         }
         ActiveView::Register => {
             lines.push(Line::from(vec![Span::styled(
@@ -85,9 +145,13 @@ fn get_help_lines(app: &App) -> Vec<Line<'static>> {
             lines.push(key_line("a/n", "Add new transaction"));
             lines.push(key_line("e/Enter", "Edit transaction"));
             lines.push(key_line("c", "Toggle cleared status"));
+            lines.push(key_line("f", "Cycle flag (review, starred)"));
             lines.push(key_line("Ctrl+d", "Delete transaction"));
+            lines.push(key_line("r", "Start reconciliation"));
             lines.push(key_line("g", "Go to top"));
             lines.push(key_line("G", "Go to bottom"));
+            lines.push(key_line("s", "Sort by date, payee, or amount"));
+            lines.push(key_line("S", "Reverse sort direction"));
             lines.push(Line::from(""));
             lines.push(Line::from(vec![Span::styled(
                 "Multi-Select Mode",
@@ -111,7 +175,14 @@ fn get_help_lines(app: &App) -> Vec<Line<'static>> {
             lines.push(key_line("[/H", "Previous period"));
             lines.push(key_line("]/L", "Next period"));
             lines.push(key_line("m", "Move funds between categories"));
+            lines.push(key_line("u", "Undo the last fund move"));
+            lines.push(key_line("J/K", "Move category down/up in its group"));
+            lines.push(key_line("M", "Move category to another group"));
+            lines.push(key_line("f", "Budget the target's suggested amount"));
+            lines.push(key_line("z", "Collapse/expand the category's group"));
+            lines.push(key_line("Z", "Expand all groups"));
             lines.push(key_line("Enter", "Edit budget amount"));
+            lines.push(key_line("=", "Edit budgeted in place (Tab for next)"));
         }
         ActiveView::Reports => {
             lines.push(Line::from(vec![Span::styled(
@@ -121,7 +192,9 @@ fn get_help_lines(app: &App) -> Vec<Line<'static>> {
                     .fg(Color::Yellow),
             )]));
             lines.push(Line::from(""));
-            lines.push(Line::from("Reports view coming soon!"));
+            lines.push(key_line("[/H", "Previous period"));
+            lines.push(key_line("]/L", "Next period"));
+            lines.push(key_line("c", "Compare with previous period"));
         }
         ActiveView::Reconcile => {
             lines.push(Line::from(vec![Span::styled(
@@ -133,7 +206,11 @@ fn get_help_lines(app: &App) -> Vec<Line<'static>> {
             lines.push(Line::from(""));
             lines.push(key_line("Tab", "Switch between header and transactions"));
             lines.push(key_line("Space", "Toggle cleared status"));
-            lines.push(key_line("Enter", "Start reconciliation / Complete"));
+            lines.push(key_line("a", "Adjust transaction to match statement"));
+            lines.push(key_line(
+                "Enter",
+                "Start reconciliation / Reconcile all cleared, adjusting any difference",
+            ));
             lines.push(key_line("j/k", "Navigate transactions"));
             lines.push(key_line("Esc", "Cancel reconciliation"));
         }
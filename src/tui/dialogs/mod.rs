@@ -9,9 +9,11 @@ pub mod bulk_categorize;
 pub mod category;
 pub mod command_palette;
 pub mod confirm;
+pub mod go_to;
 pub mod group;
 pub mod help;
 pub mod income;
+pub mod move_category;
 pub mod move_funds;
 pub mod reconcile_start;
 pub mod transaction;
@@ -0,0 +1,142 @@
+//! Move category dialog
+//!
+//! A fuzzy picker over the category groups. Choosing one moves the selected
+//! category to the end of that group.
+
+use crossterm::event::KeyEvent;
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::models::{CategoryGroup, CategoryId};
+use crate::services::CategoryService;
+use crate::storage::Storage;
+use crate::tui::app::App;
+use crate::tui::layout::centered_rect_fixed;
+use crate::tui::widgets::{FuzzyPicker, PickerAction};
+
+/// State for the move category dialog
+#[derive(Debug, Clone, Default)]
+pub struct MoveCategoryState {
+    /// Picker over the groups' names
+    pub picker: FuzzyPicker,
+    /// Groups the category can move to, excluding its current one
+    pub groups: Vec<CategoryGroup>,
+    /// Name of the category being moved
+    pub category_name: String,
+}
+
+impl MoveCategoryState {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reset the picker and load the other groups from storage
+    pub fn init(&mut self, storage: &Storage, category_id: CategoryId) {
+        self.picker.reset();
+        self.groups.clear();
+        self.category_name.clear();
+
+        let Ok(Some(category)) = storage.categories.get_category(category_id) else {
+            return;
+        };
+        self.groups = storage
+            .categories
+            .get_all_groups()
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|g| g.id != category.group_id)
+            .collect();
+        self.category_name = category.name;
+    }
+}
+
+/// Render the move category dialog
+pub fn render(frame: &mut Frame, app: &mut App) {
+    let area = centered_rect_fixed(50, 16, frame.area());
+    frame.render_widget(Clear, area);
+
+    let state = &app.move_category_state;
+    let block = Block::default()
+        .title(format!(" Move '{}' to Group ", state.category_name))
+        .title_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::Cyan));
+    frame.render_widget(block, area);
+
+    let inner = Rect {
+        x: area.x + 1,
+        y: area.y + 1,
+        width: area.width.saturating_sub(2),
+        height: area.height.saturating_sub(3),
+    };
+
+    let labels: Vec<String> = state
+        .picker
+        .filter(&state.groups, |g| g.name.as_str())
+        .into_iter()
+        .map(|g| g.name.clone())
+        .collect();
+    state.picker.render(frame, inner, &labels);
+
+    let hints = Line::from(vec![
+        Span::styled("Enter", Style::default().fg(Color::Yellow)),
+        Span::raw(" move  "),
+        Span::styled("Esc", Style::default().fg(Color::Yellow)),
+        Span::raw(" cancel"),
+    ]);
+    let hints_area = Rect {
+        y: area.y + area.height.saturating_sub(2),
+        height: 1,
+        ..inner
+    };
+    frame.render_widget(Paragraph::new(hints), hints_area);
+}
+
+/// Handle key input for the move category dialog
+pub fn handle_key(app: &mut App, key: KeyEvent, category_id: CategoryId) {
+    let state = &mut app.move_category_state;
+    let matches: Vec<CategoryGroup> = state
+        .picker
+        .filter(&state.groups, |g| g.name.as_str())
+        .into_iter()
+        .cloned()
+        .collect();
+
+    match state.picker.handle_key(key, matches.len()) {
+        PickerAction::Select(index) => {
+            app.close_dialog();
+            move_to(app, category_id, &matches[index]);
+        }
+        PickerAction::Cancel => app.close_dialog(),
+        PickerAction::Handled | PickerAction::Ignored => {}
+    }
+}
+
+/// Move the category and keep it selected in its new position
+fn move_to(app: &mut App, category_id: CategoryId, group: &CategoryGroup) {
+    match CategoryService::new(app.storage).move_category(category_id, group.id) {
+        Ok(category) => {
+            // The category can't stay selected inside a collapsed group
+            app.collapsed_groups.remove(&group.id);
+            if let Some(index) = app
+                .budget_categories()
+                .iter()
+                .position(|c| c.id == category_id)
+            {
+                app.selected_category_index = index;
+                app.selected_category = Some(category_id);
+            }
+            app.set_status(format!("Moved '{}' to '{}'", category.name, group.name));
+        }
+        Err(e) => app.set_status(format!("Failed to move category: {}", e)),
+    }
+}
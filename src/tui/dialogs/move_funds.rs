@@ -1,19 +1,38 @@
 //! Move funds dialog
 //!
-//! Transfer budget between categories
+//! Transfer budget between categories. Both category fields are fuzzy
+//! pickers: type to filter, arrows to move, Enter to choose.
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    widgets::{Block, Borders, Clear, Paragraph},
     Frame,
 };
 
-use crate::models::{CategoryId, Money};
+use crate::models::{BudgetPeriod, Category, CategoryId, Money};
 use crate::services::{BudgetService, CategoryService};
 use crate::tui::app::App;
 use crate::tui::layout::centered_rect;
+use crate::tui::widgets::{FuzzyPicker, Notification, PickerAction};
+
+/// A completed fund move, kept so it can be undone
+#[derive(Debug, Clone)]
+pub struct FundMove {
+    /// Category the funds came from
+    pub from_id: CategoryId,
+    /// Category the funds went to
+    pub to_id: CategoryId,
+    /// Period both allocations belong to
+    pub period: BudgetPeriod,
+    /// Amount moved
+    pub amount: Money,
+    /// Name of the source category, for messages
+    pub from_name: String,
+    /// Name of the destination category, for messages
+    pub to_name: String,
+}
 
 /// Which field is focused in the move funds dialog
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -51,10 +70,10 @@ pub struct MoveFundsState {
     pub from_category: Option<CategoryId>,
     /// Selected destination category
     pub to_category: Option<CategoryId>,
-    /// Index in the from category list
-    pub from_list_index: usize,
-    /// Index in the to category list
-    pub to_list_index: usize,
+    /// Picker for the source category
+    pub from_picker: FuzzyPicker,
+    /// Picker for the destination category
+    pub to_picker: FuzzyPicker,
     /// Amount to move (as string for editing)
     pub amount_input: String,
     /// Amount cursor position
@@ -85,6 +104,15 @@ impl MoveFundsState {
         self.focused_field = self.focused_field.prev();
     }
 
+    /// The picker for the focused field, if it is a category field
+    pub fn active_picker_mut(&mut self) -> Option<&mut FuzzyPicker> {
+        match self.focused_field {
+            MoveFundsField::FromCategory => Some(&mut self.from_picker),
+            MoveFundsField::ToCategory => Some(&mut self.to_picker),
+            MoveFundsField::Amount => None,
+        }
+    }
+
     /// Clear error message
     pub fn clear_error(&mut self) {
         self.error_message = None;
@@ -193,9 +221,9 @@ pub fn render(frame: &mut Frame, app: &mut App) {
             Constraint::Length(1), // Title
             Constraint::Length(1), // Spacer
             Constraint::Length(1), // From label
-            Constraint::Length(6), // From list
+            Constraint::Length(7), // From picker
             Constraint::Length(1), // To label
-            Constraint::Length(6), // To list
+            Constraint::Length(7), // To picker
             Constraint::Length(1), // Amount label
             Constraint::Length(1), // Amount input
             Constraint::Length(1), // Spacer
@@ -225,7 +253,7 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         &categories,
         "From:",
         app.move_funds_state.from_category,
-        app.move_funds_state.from_list_index,
+        &app.move_funds_state.from_picker,
         from_focused,
         chunks[2],
         chunks[3],
@@ -238,7 +266,7 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         &categories,
         "To:",
         app.move_funds_state.to_category,
-        app.move_funds_state.to_list_index,
+        &app.move_funds_state.to_picker,
         to_focused,
         chunks[4],
         chunks[5],
@@ -274,8 +302,10 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     let hints = Line::from(vec![
         Span::styled("[Tab]", Style::default().fg(Color::White)),
         Span::raw(" Next  "),
+        Span::styled("[Type]", Style::default().fg(Color::White)),
+        Span::raw(" Filter  "),
         Span::styled("[Enter]", Style::default().fg(Color::Green)),
-        Span::raw(" Move  "),
+        Span::raw(" Select/Move  "),
         Span::styled("[Esc]", Style::default().fg(Color::Red)),
         Span::raw(" Cancel"),
     ]);
@@ -286,10 +316,10 @@ pub fn render(frame: &mut Frame, app: &mut App) {
 #[allow(clippy::too_many_arguments)]
 fn render_category_field(
     frame: &mut Frame,
-    categories: &[crate::models::Category],
+    categories: &[Category],
     label: &str,
     selected: Option<CategoryId>,
-    list_index: usize,
+    picker: &FuzzyPicker,
     focused: bool,
     label_area: Rect,
     list_area: Rect,
@@ -319,32 +349,14 @@ fn render_category_field(
     ]);
     frame.render_widget(Paragraph::new(label_line), label_area);
 
-    // Render category list if focused
+    // Render the picker if focused
     if focused {
-        let items: Vec<ListItem> = categories
-            .iter()
-            .map(|cat| {
-                let style = if Some(cat.id) == selected {
-                    Style::default().fg(Color::Green)
-                } else {
-                    Style::default().fg(Color::White)
-                };
-                ListItem::new(Line::from(Span::styled(format!("  {}", cat.name), style)))
-            })
+        let labels: Vec<String> = picker
+            .filter(categories, |c| c.name.as_str())
+            .into_iter()
+            .map(|c| c.name.clone())
             .collect();
-
-        let list = List::new(items)
-            .highlight_style(
-                Style::default()
-                    .bg(Color::DarkGray)
-                    .add_modifier(Modifier::BOLD),
-            )
-            .highlight_symbol("▶ ");
-
-        let mut state = ListState::default();
-        state.select(Some(list_index.min(categories.len().saturating_sub(1))));
-
-        frame.render_stateful_widget(list, list_area, &mut state);
+        picker.render(frame, list_area, &labels);
     } else {
         // Show hint when not focused
         let hint = Paragraph::new("  (Tab to this field to select)")
@@ -415,17 +427,7 @@ fn render_amount_field(
 pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
     use crossterm::event::{KeyCode, KeyModifiers};
 
-    let category_service = CategoryService::new(app.storage);
-    let categories = category_service.list_categories().unwrap_or_default();
-    let cat_count = categories.len();
-
     match key.code {
-        KeyCode::Esc => {
-            app.move_funds_state.reset();
-            app.close_dialog();
-            return true;
-        }
-
         KeyCode::Tab => {
             if key.modifiers.contains(KeyModifiers::SHIFT) {
                 app.move_funds_state.prev_field();
@@ -440,93 +442,72 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
             return true;
         }
 
-        KeyCode::Enter => {
-            // If in a category list, select the category
-            match app.move_funds_state.focused_field {
-                MoveFundsField::FromCategory => {
-                    if let Some(cat) = categories.get(app.move_funds_state.from_list_index) {
-                        app.move_funds_state.from_category = Some(cat.id);
-                        app.move_funds_state.next_field();
-                    }
-                    return true;
-                }
-                MoveFundsField::ToCategory => {
-                    if let Some(cat) = categories.get(app.move_funds_state.to_list_index) {
-                        app.move_funds_state.to_category = Some(cat.id);
-                        app.move_funds_state.next_field();
-                    }
-                    return true;
-                }
-                MoveFundsField::Amount => {
-                    // Try to execute the move
-                    execute_move(app);
-                    return true;
+        _ => {}
+    }
+
+    // Category fields are handled by their picker
+    if app.move_funds_state.focused_field != MoveFundsField::Amount {
+        let category_service = CategoryService::new(app.storage);
+        let categories = category_service.list_categories().unwrap_or_default();
+
+        let Some(picker) = app.move_funds_state.active_picker_mut() else {
+            return false;
+        };
+        let matches = picker.filter(&categories, |c| c.name.as_str());
+
+        match picker.handle_key(key, matches.len()) {
+            PickerAction::Select(index) => {
+                let id = matches[index].id;
+                match app.move_funds_state.focused_field {
+                    MoveFundsField::FromCategory => app.move_funds_state.from_category = Some(id),
+                    _ => app.move_funds_state.to_category = Some(id),
                 }
+                app.move_funds_state.clear_error();
+                app.move_funds_state.next_field();
+                return true;
+            }
+            PickerAction::Cancel => {
+                app.move_funds_state.reset();
+                app.close_dialog();
+                return true;
             }
+            PickerAction::Handled => return true,
+            PickerAction::Ignored => return false,
         }
+    }
 
-        KeyCode::Up | KeyCode::Char('k') => {
-            match app.move_funds_state.focused_field {
-                MoveFundsField::FromCategory => {
-                    if app.move_funds_state.from_list_index > 0 {
-                        app.move_funds_state.from_list_index -= 1;
-                    }
-                }
-                MoveFundsField::ToCategory => {
-                    if app.move_funds_state.to_list_index > 0 {
-                        app.move_funds_state.to_list_index -= 1;
-                    }
-                }
-                _ => {}
-            }
+    match key.code {
+        KeyCode::Esc => {
+            app.move_funds_state.reset();
+            app.close_dialog();
             return true;
         }
 
-        KeyCode::Down | KeyCode::Char('j') => {
-            match app.move_funds_state.focused_field {
-                MoveFundsField::FromCategory => {
-                    if app.move_funds_state.from_list_index < cat_count.saturating_sub(1) {
-                        app.move_funds_state.from_list_index += 1;
-                    }
-                }
-                MoveFundsField::ToCategory => {
-                    if app.move_funds_state.to_list_index < cat_count.saturating_sub(1) {
-                        app.move_funds_state.to_list_index += 1;
-                    }
-                }
-                _ => {}
-            }
+        KeyCode::Enter => {
+            execute_move(app);
             return true;
         }
 
         KeyCode::Char(c) => {
-            if app.move_funds_state.focused_field == MoveFundsField::Amount {
-                app.move_funds_state.clear_error();
-                app.move_funds_state.insert_char(c);
-                return true;
-            }
+            app.move_funds_state.clear_error();
+            app.move_funds_state.insert_char(c);
+            return true;
         }
 
         KeyCode::Backspace => {
-            if app.move_funds_state.focused_field == MoveFundsField::Amount {
-                app.move_funds_state.clear_error();
-                app.move_funds_state.backspace();
-                return true;
-            }
+            app.move_funds_state.clear_error();
+            app.move_funds_state.backspace();
+            return true;
         }
 
         KeyCode::Left => {
-            if app.move_funds_state.focused_field == MoveFundsField::Amount {
-                app.move_funds_state.move_left();
-                return true;
-            }
+            app.move_funds_state.move_left();
+            return true;
         }
 
         KeyCode::Right => {
-            if app.move_funds_state.focused_field == MoveFundsField::Amount {
-                app.move_funds_state.move_right();
-                return true;
-            }
+            app.move_funds_state.move_right();
+            return true;
         }
 
         _ => {}
@@ -565,10 +546,21 @@ fn execute_move(app: &mut App) {
                 .map(|c| c.name)
                 .unwrap_or_else(|| "Unknown".into());
 
-            app.set_status(format!(
-                "Moved {} from '{}' to '{}'",
-                amount, from_name, to_name
-            ));
+            app.notifications.push(
+                Notification::success(format!(
+                    "Moved {} from '{}' to '{}'. Press u to undo.",
+                    amount, from_name, to_name
+                ))
+                .with_duration(5),
+            );
+            app.last_fund_move = Some(FundMove {
+                from_id,
+                to_id,
+                period: app.current_period.clone(),
+                amount,
+                from_name,
+                to_name,
+            });
             app.move_funds_state.reset();
             app.close_dialog();
         }
@@ -577,3 +569,35 @@ fn execute_move(app: &mut App) {
         }
     }
 }
+
+/// Undo the last fund move by moving the amount back
+///
+/// Both allocations are restored through the budget service, so the undo
+/// shows up in the audit log like any other move.
+pub fn undo_last_move(app: &mut App) {
+    let Some(last) = app.last_fund_move.take() else {
+        app.set_status("No fund move to undo");
+        return;
+    };
+
+    let budget_service = BudgetService::new(app.storage);
+    match budget_service.move_between_categories(
+        last.to_id,
+        last.from_id,
+        &last.period,
+        last.amount,
+    ) {
+        Ok(()) => {
+            app.notifications.push(Notification::info(format!(
+                "Undid move: {} back to '{}'",
+                last.amount, last.from_name
+            )));
+        }
+        Err(e) => {
+            app.notifications.push(Notification::error(format!(
+                "Could not undo move from '{}': {}",
+                last.to_name, e
+            )));
+        }
+    }
+}
@@ -2,6 +2,7 @@
 //!
 //! Dialog to enter statement date and balance to begin reconciliation.
 
+use chrono::NaiveDate;
 use ratatui::{
     layout::{Constraint, Direction, Layout},
     style::{Color, Modifier, Style},
@@ -10,7 +11,9 @@ use ratatui::{
     Frame,
 };
 
-use crate::tui::app::App;
+use crate::config::clock;
+use crate::models::Money;
+use crate::tui::app::{ActiveView, App};
 use crate::tui::layout::centered_rect_fixed;
 
 /// State for the reconcile start dialog
@@ -27,7 +30,7 @@ pub struct ReconcileStartState {
 impl ReconcileStartState {
     pub fn new() -> Self {
         Self {
-            date_input: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            date_input: clock::today().format("%Y-%m-%d").to_string(),
             balance_input: String::new(),
             active_field: 0,
         }
@@ -128,6 +131,31 @@ pub fn render(frame: &mut Frame, app: &App) {
     frame.render_widget(instructions, chunks[5]);
 }
 
+/// Start reconciling the selected account against the entered statement
+///
+/// Leaves the dialog open with a status message if an input doesn't parse.
+pub fn submit(app: &mut App) {
+    let state = &app.reconcile_start_state;
+    let Ok(balance) = Money::parse(&state.balance_input) else {
+        app.set_status("Invalid balance format. Use format like 1234.56");
+        return;
+    };
+    if NaiveDate::parse_from_str(&state.date_input, "%Y-%m-%d").is_err() {
+        app.set_status("Invalid date format. Use YYYY-MM-DD");
+        return;
+    }
+    let (date_input, balance_input) = (state.date_input.clone(), state.balance_input.clone());
+
+    app.close_dialog();
+    app.switch_view(ActiveView::Reconcile);
+
+    let reconciliation = &mut app.reconciliation_state;
+    reconciliation.statement_date = date_input;
+    reconciliation.statement_balance = balance_input;
+    reconciliation.active_field = 1;
+    reconciliation.load(app.storage, balance);
+}
+
 /// Handle key input for the reconcile start dialog
 pub fn handle_key(app: &mut App, key: crossterm::event::KeyCode) -> bool {
     use crossterm::event::KeyCode;
@@ -3,20 +3,22 @@
 //! Modal dialog for adding or editing transactions with form fields,
 //! tab navigation, validation, and save/cancel functionality.
 
-use chrono::{Local, NaiveDate};
+use chrono::{Duration, Months, NaiveDate};
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
     Frame,
 };
 
-use crate::models::{CategoryId, Money, Transaction, TransactionStatus};
-use crate::services::CategoryService;
+use crate::config::clock;
+use crate::models::{parse_tags, CategoryId, Money, Split, Transaction, TransactionStatus};
+use crate::services::{CategoryService, PayeeService, TransactionService};
 use crate::tui::app::{ActiveDialog, App};
 use crate::tui::layout::centered_rect;
 use crate::tui::widgets::input::TextInput;
+use crate::tui::widgets::{follow_selection, fuzzy_filter, render_scrollbar};
 
 /// Which field is currently focused in the transaction form
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
@@ -28,6 +30,7 @@ pub enum TransactionField {
     Outflow,
     Inflow,
     Memo,
+    Tags,
 }
 
 impl TransactionField {
@@ -39,23 +42,66 @@ impl TransactionField {
             Self::Category => Self::Outflow,
             Self::Outflow => Self::Inflow,
             Self::Inflow => Self::Memo,
-            Self::Memo => Self::Date,
+            Self::Memo => Self::Tags,
+            Self::Tags => Self::Date,
         }
     }
 
     /// Get the previous field (for Shift+Tab navigation)
     pub fn prev(self) -> Self {
         match self {
-            Self::Date => Self::Memo,
+            Self::Date => Self::Tags,
             Self::Payee => Self::Date,
             Self::Category => Self::Payee,
             Self::Outflow => Self::Category,
             Self::Inflow => Self::Outflow,
             Self::Memo => Self::Inflow,
+            Self::Tags => Self::Memo,
         }
     }
 }
 
+/// One category and amount row in split mode
+#[derive(Debug, Clone)]
+pub struct SplitRow {
+    /// Category search input
+    pub category_input: TextInput,
+
+    /// Selected category ID
+    pub category_id: Option<CategoryId>,
+
+    /// Amount input, entered without a sign like outflow/inflow
+    pub amount_input: TextInput,
+
+    /// Memo carried over from an existing split
+    pub memo: String,
+}
+
+impl SplitRow {
+    /// Create an empty split row
+    pub fn new() -> Self {
+        Self {
+            category_input: TextInput::new().placeholder("Type to search..."),
+            category_id: None,
+            amount_input: TextInput::new().placeholder("0.00"),
+            memo: String::new(),
+        }
+    }
+
+    /// Whether nothing has been entered in this row
+    fn is_blank(&self) -> bool {
+        self.category_id.is_none()
+            && self.category_input.value().trim().is_empty()
+            && self.amount_input.value().trim().is_empty()
+    }
+}
+
+impl Default for SplitRow {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 /// State for the transaction form dialog
 #[derive(Debug, Clone)]
 pub struct TransactionFormState {
@@ -68,6 +114,15 @@ pub struct TransactionFormState {
     /// Payee input
     pub payee_input: TextInput,
 
+    /// Payee suggestions for the current payee input, best first
+    pub payee_suggestions: Vec<String>,
+
+    /// Payee input the suggestions were computed for
+    pub payee_suggestions_for: Option<String>,
+
+    /// Highlighted payee suggestion, once the user moves into the list
+    pub payee_list_index: Option<usize>,
+
     /// Category search input
     pub category_input: TextInput,
 
@@ -77,6 +132,9 @@ pub struct TransactionFormState {
     /// Category selection index (for dropdown)
     pub category_list_index: usize,
 
+    /// First visible row of the category dropdown
+    pub category_scroll_offset: usize,
+
     /// Show category dropdown
     pub show_category_dropdown: bool,
 
@@ -89,6 +147,24 @@ pub struct TransactionFormState {
     /// Memo input
     pub memo_input: TextInput,
 
+    /// Tags input (comma or space separated)
+    pub tags_input: TextInput,
+
+    /// Whether the category field is split across several categories
+    pub split_mode: bool,
+
+    /// Split rows shown in place of the category in split mode
+    pub split_rows: Vec<SplitRow>,
+
+    /// Focused split row
+    pub split_index: usize,
+
+    /// Whether the focused split row's amount (rather than category) has focus
+    pub split_amount_focused: bool,
+
+    /// Transfer splits of an existing transaction, kept as they are
+    pub transfer_splits: Vec<Split>,
+
     /// Whether this is an edit (vs new transaction)
     pub is_edit: bool,
 
@@ -105,7 +181,7 @@ impl Default for TransactionFormState {
 impl TransactionFormState {
     /// Create a new form state with default values
     pub fn new() -> Self {
-        let today = Local::now().date_naive();
+        let today = clock::today();
         Self {
             focused_field: TransactionField::Date,
             date_input: TextInput::new()
@@ -115,15 +191,27 @@ impl TransactionFormState {
             payee_input: TextInput::new()
                 .label("Payee")
                 .placeholder("Enter payee name"),
+            payee_suggestions: Vec::new(),
+            payee_suggestions_for: None,
+            payee_list_index: None,
             category_input: TextInput::new()
                 .label("Category")
                 .placeholder("Type to search..."),
             selected_category: None,
             category_list_index: 0,
+            category_scroll_offset: 0,
             show_category_dropdown: false,
             outflow_input: TextInput::new().label("Outflow").placeholder("(expense)"),
             inflow_input: TextInput::new().label("Inflow").placeholder("(income)"),
             memo_input: TextInput::new().label("Memo").placeholder("Optional note"),
+            tags_input: TextInput::new()
+                .label("Tags")
+                .placeholder("e.g. vacation2025, tax-deductible"),
+            split_mode: false,
+            split_rows: Vec::new(),
+            split_index: 0,
+            split_amount_focused: false,
+            transfer_splits: Vec::new(),
             is_edit: false,
             error_message: None,
         }
@@ -155,6 +243,9 @@ impl TransactionFormState {
         }
 
         state.memo_input = TextInput::new().label("Memo").content(&txn.memo);
+        if !txn.tags.is_empty() {
+            state.tags_input = TextInput::new().label("Tags").content(txn.tags.join(", "));
+        }
 
         // Set category
         if let Some(cat_id) = txn.category_id {
@@ -164,20 +255,287 @@ impl TransactionFormState {
             }
         }
 
+        // Split transactions open in split mode with their rows filled in
+        if txn.is_split() {
+            state.split_mode = true;
+            for split in &txn.splits {
+                let Some(cat_id) = split.category_id else {
+                    state.transfer_splits.push(split.clone());
+                    continue;
+                };
+                let mut row = SplitRow::new();
+                row.category_id = Some(cat_id);
+                if let Some((_, name)) = categories.iter().find(|(id, _)| *id == cat_id) {
+                    row.category_input = TextInput::new().content(name);
+                }
+                row.amount_input = TextInput::new()
+                    .placeholder("0.00")
+                    .content(format!("{:.2}", split.amount.cents().abs() as f64 / 100.0));
+                row.memo = split.memo.clone();
+                state.split_rows.push(row);
+            }
+            if state.split_rows.is_empty() {
+                state.split_rows.push(SplitRow::new());
+            }
+        }
+
         state
     }
 
+    /// Select a category, showing its name in the category input
+    ///
+    /// In split mode this sets the focused split row's category.
+    pub fn set_category(&mut self, id: CategoryId, name: &str) {
+        if self.split_mode {
+            if let Some(row) = self.split_rows.get_mut(self.split_index) {
+                row.category_id = Some(id);
+                row.category_input = TextInput::new().content(name);
+            }
+            return;
+        }
+        self.selected_category = Some(id);
+        self.category_input = TextInput::new().label("Category").content(name);
+    }
+
+    /// Whether the payee suggestion dropdown is showing
+    ///
+    /// Hidden once the payee matches the only suggestion.
+    pub fn is_suggesting_payee(&self) -> bool {
+        self.focused_field == TransactionField::Payee
+            && match self.payee_suggestions.as_slice() {
+                [] => false,
+                [only] => !only.eq_ignore_ascii_case(self.payee_input.value().trim()),
+                _ => true,
+            }
+    }
+
+    /// Category chosen for the focused category input
+    pub fn active_category(&self) -> Option<CategoryId> {
+        if self.split_mode {
+            self.split_rows
+                .get(self.split_index)
+                .and_then(|row| row.category_id)
+        } else {
+            self.selected_category
+        }
+    }
+
+    /// Search text of the focused category input
+    pub fn category_search(&self) -> &str {
+        if self.split_mode {
+            self.split_rows
+                .get(self.split_index)
+                .map(|row| row.category_input.value())
+                .unwrap_or("")
+        } else {
+            self.category_input.value()
+        }
+    }
+
+    /// Whether the category dropdown is taking input (a category search in progress)
+    pub fn is_searching_category(&self) -> bool {
+        self.focused_field == TransactionField::Category
+            && !(self.split_mode && self.split_amount_focused)
+            && self.active_category().is_none()
+    }
+
+    /// Clear the focused category so it can be searched again
+    pub fn clear_active_category(&mut self) {
+        if self.split_mode {
+            if let Some(row) = self.split_rows.get_mut(self.split_index) {
+                row.category_id = None;
+                row.category_input.clear();
+            }
+        } else {
+            self.selected_category = None;
+            self.category_input.clear();
+        }
+    }
+
+    /// Switch between a single category and split rows
+    ///
+    /// Entering split mode starts from the current category and amount;
+    /// leaving it keeps the category only when there was a single row.
+    pub fn toggle_split_mode(&mut self) {
+        self.split_mode = !self.split_mode;
+        self.split_index = 0;
+        self.split_amount_focused = false;
+
+        if self.split_mode {
+            if self.split_rows.is_empty() {
+                let mut first = SplitRow::new();
+                first.category_id = self.selected_category;
+                if self.selected_category.is_some() {
+                    first.category_input = TextInput::new().content(self.category_input.value());
+                }
+                if let Some(amount) = self.amount() {
+                    first.amount_input = TextInput::new()
+                        .placeholder("0.00")
+                        .content(format!("{:.2}", amount.cents().abs() as f64 / 100.0));
+                }
+                self.split_rows.push(first);
+                self.split_rows.push(SplitRow::new());
+            }
+        } else {
+            let rows: Vec<&SplitRow> = self.split_rows.iter().filter(|r| !r.is_blank()).collect();
+            match rows.as_slice() {
+                [row] if row.category_id.is_some() => {
+                    self.selected_category = row.category_id;
+                    self.category_input = TextInput::new()
+                        .label("Category")
+                        .content(row.category_input.value());
+                }
+                _ => {
+                    self.selected_category = None;
+                    self.category_input.clear();
+                }
+            }
+            self.split_rows.clear();
+            self.transfer_splits.clear();
+        }
+
+        self.set_focus(TransactionField::Category);
+    }
+
+    /// Add a split row after the focused one and focus it
+    pub fn add_split_row(&mut self) {
+        let index = (self.split_index + 1).min(self.split_rows.len());
+        let mut row = SplitRow::new();
+        if let Some(remaining) = self.remaining_to_assign().filter(|m| m.is_positive()) {
+            row.amount_input = TextInput::new()
+                .placeholder("0.00")
+                .content(format!("{:.2}", remaining.cents() as f64 / 100.0));
+        }
+        self.split_rows.insert(index, row);
+        self.split_index = index;
+        self.split_amount_focused = false;
+        self.update_focus();
+    }
+
+    /// Remove the focused split row, always leaving at least one
+    pub fn remove_split_row(&mut self) {
+        if self.split_rows.len() > 1 {
+            self.split_rows.remove(self.split_index);
+            self.split_index = self.split_index.min(self.split_rows.len() - 1);
+        } else {
+            self.split_rows = vec![SplitRow::new()];
+            self.split_index = 0;
+        }
+        self.split_amount_focused = false;
+        self.update_focus();
+    }
+
+    /// Transaction amount from the outflow/inflow inputs, if valid
+    pub fn amount(&self) -> Option<Money> {
+        let outflow_str = self.outflow_input.value().trim();
+        let inflow_str = self.inflow_input.value().trim();
+        match (outflow_str.is_empty(), inflow_str.is_empty()) {
+            (false, true) => Money::parse_expression(outflow_str).ok().map(|m| -m),
+            (true, false) => Money::parse_expression(inflow_str).ok(),
+            _ => None,
+        }
+    }
+
+    /// Amount still to assign to splits (unsigned), or None if the amount isn't valid
+    pub fn remaining_to_assign(&self) -> Option<Money> {
+        let total = self.amount()?.abs();
+        let assigned: Money = self
+            .split_rows
+            .iter()
+            .filter_map(|row| Money::parse_expression(row.amount_input.value().trim()).ok())
+            .sum();
+        let transfers: Money = self.transfer_splits.iter().map(|s| s.amount.abs()).sum();
+        Some(total - assigned - transfers)
+    }
+
+    /// Build the splits from the split rows, signed like the transaction
+    pub fn build_splits(&self, amount: Money) -> Result<Vec<Split>, String> {
+        let mut splits = Vec::new();
+        for (i, row) in self.split_rows.iter().enumerate() {
+            if row.is_blank() {
+                continue;
+            }
+            let category_id = row
+                .category_id
+                .ok_or_else(|| format!("Split {} needs a category", i + 1))?;
+            let value = Money::parse_expression(row.amount_input.value().trim())
+                .map_err(|_| format!("Invalid amount in split {}", i + 1))?;
+            let value = if amount.is_negative() {
+                -value.abs()
+            } else {
+                value.abs()
+            };
+            let mut split = Split::new(category_id, value);
+            split.memo = row.memo.clone();
+            splits.push(split);
+        }
+        splits.extend(self.transfer_splits.iter().cloned());
+
+        if splits.is_empty() {
+            return Err("Add at least one split".to_string());
+        }
+
+        let total: Money = splits.iter().map(|s| s.amount).sum();
+        if total != amount {
+            let remaining = amount.abs() - total.abs();
+            return Err(if remaining.is_negative() {
+                format!("Splits are {} over the transaction amount", remaining.abs())
+            } else {
+                format!("{} left to assign to splits", remaining)
+            });
+        }
+
+        Ok(splits)
+    }
+
     /// Move to the next field
+    ///
+    /// In split mode Tab walks each row's category and amount before
+    /// leaving the splits.
     pub fn next_field(&mut self) {
         self.show_category_dropdown = false;
+        if self.split_mode && self.focused_field == TransactionField::Category {
+            if !self.split_amount_focused {
+                self.split_amount_focused = true;
+                self.update_focus();
+                return;
+            }
+            if self.split_index + 1 < self.split_rows.len() {
+                self.split_index += 1;
+                self.split_amount_focused = false;
+                self.update_focus();
+                return;
+            }
+        }
         self.focused_field = self.focused_field.next();
+        if self.split_mode && self.focused_field == TransactionField::Category {
+            self.split_index = 0;
+            self.split_amount_focused = false;
+        }
         self.update_focus();
     }
 
     /// Move to the previous field
     pub fn prev_field(&mut self) {
         self.show_category_dropdown = false;
+        if self.split_mode && self.focused_field == TransactionField::Category {
+            if self.split_amount_focused {
+                self.split_amount_focused = false;
+                self.update_focus();
+                return;
+            }
+            if self.split_index > 0 {
+                self.split_index -= 1;
+                self.split_amount_focused = true;
+                self.update_focus();
+                return;
+            }
+        }
         self.focused_field = self.focused_field.prev();
+        if self.split_mode && self.focused_field == TransactionField::Category {
+            self.split_index = self.split_rows.len().saturating_sub(1);
+            self.split_amount_focused = true;
+        }
         self.update_focus();
     }
 
@@ -189,6 +547,14 @@ impl TransactionFormState {
         self.outflow_input.focused = self.focused_field == TransactionField::Outflow;
         self.inflow_input.focused = self.focused_field == TransactionField::Inflow;
         self.memo_input.focused = self.focused_field == TransactionField::Memo;
+        self.tags_input.focused = self.focused_field == TransactionField::Tags;
+
+        let splits_focused = self.focused_field == TransactionField::Category;
+        for (i, row) in self.split_rows.iter_mut().enumerate() {
+            let row_focused = splits_focused && i == self.split_index;
+            row.category_input.focused = row_focused && !self.split_amount_focused;
+            row.amount_input.focused = row_focused && self.split_amount_focused;
+        }
 
         // Show dropdown when category is focused
         if self.focused_field == TransactionField::Category {
@@ -202,15 +568,72 @@ impl TransactionFormState {
         self.update_focus();
     }
 
+    /// The date in the date field, or today if it doesn't parse
+    fn date_or_today(&self) -> NaiveDate {
+        NaiveDate::parse_from_str(self.date_input.value().trim(), "%Y-%m-%d")
+            .unwrap_or_else(|_| clock::today())
+    }
+
+    /// Whether the date field holds a date still being typed, such as `2025-03`
+    ///
+    /// `-` is a separator while typing and only steps the date otherwise.
+    pub fn is_typing_date(&self) -> bool {
+        let value = self.date_input.value();
+        !value.is_empty()
+            && value.chars().all(|c| c.is_ascii_digit() || c == '-')
+            && NaiveDate::parse_from_str(value, "%Y-%m-%d").is_err()
+    }
+
+    /// Replace the date field with a date
+    pub fn set_date(&mut self, date: NaiveDate) {
+        self.date_input
+            .set_value(date.format("%Y-%m-%d").to_string());
+    }
+
+    /// Move the date by a number of days
+    ///
+    /// Text that isn't a complete date is treated as today.
+    pub fn step_date_days(&mut self, days: i64) {
+        let date = self.date_or_today();
+        self.set_date(
+            date.checked_add_signed(Duration::days(days))
+                .unwrap_or(date),
+        );
+    }
+
+    /// Move the date by a number of months, clamping to the end of shorter months
+    pub fn step_date_months(&mut self, months: i32) {
+        let date = self.date_or_today();
+        let stepped = if months < 0 {
+            date.checked_sub_months(Months::new(months.unsigned_abs()))
+        } else {
+            date.checked_add_months(Months::new(months as u32))
+        };
+        self.set_date(stepped.unwrap_or(date));
+    }
+
     /// Get the currently focused input
     pub fn focused_input(&mut self) -> &mut TextInput {
         match self.focused_field {
             TransactionField::Date => &mut self.date_input,
             TransactionField::Payee => &mut self.payee_input,
+            TransactionField::Category if self.split_mode => {
+                if self.split_rows.is_empty() {
+                    self.split_rows.push(SplitRow::new());
+                }
+                self.split_index = self.split_index.min(self.split_rows.len() - 1);
+                let row = &mut self.split_rows[self.split_index];
+                if self.split_amount_focused {
+                    &mut row.amount_input
+                } else {
+                    &mut row.category_input
+                }
+            }
             TransactionField::Category => &mut self.category_input,
             TransactionField::Outflow => &mut self.outflow_input,
             TransactionField::Inflow => &mut self.inflow_input,
             TransactionField::Memo => &mut self.memo_input,
+            TransactionField::Tags => &mut self.tags_input,
         }
     }
 
@@ -236,14 +659,20 @@ impl TransactionFormState {
             return Err("Enter either outflow OR inflow, not both".to_string());
         }
 
-        if has_outflow && Money::parse(outflow_str).is_err() {
+        if has_outflow && Money::parse_expression(outflow_str).is_err() {
             return Err("Invalid outflow format".to_string());
         }
 
-        if has_inflow && Money::parse(inflow_str).is_err() {
+        if has_inflow && Money::parse_expression(inflow_str).is_err() {
             return Err("Invalid inflow format".to_string());
         }
 
+        if self.split_mode {
+            if let Some(amount) = self.amount() {
+                self.build_splits(amount)?;
+            }
+        }
+
         Ok(())
     }
 
@@ -263,23 +692,29 @@ impl TransactionFormState {
 
         let amount = if !outflow_str.is_empty() {
             // Outflow = negative amount (expense)
-            let parsed = Money::parse(outflow_str).map_err(|_| "Invalid outflow")?;
+            let parsed = Money::parse_expression(outflow_str).map_err(|_| "Invalid outflow")?;
             -parsed
         } else {
             // Inflow = positive amount (income)
-            Money::parse(inflow_str).map_err(|_| "Invalid inflow")?
+            Money::parse_expression(inflow_str).map_err(|_| "Invalid inflow")?
         };
 
+        let category_id = if self.split_mode {
+            None
+        } else {
+            self.selected_category
+        };
         let mut txn = Transaction::with_details(
             account_id,
             date,
             amount,
             self.payee_input.value(),
-            self.selected_category,
+            category_id,
             self.memo_input.value(),
         );
 
         txn.status = TransactionStatus::Pending;
+        txn.tags = parse_tags(self.tags_input.value());
 
         Ok(txn)
     }
@@ -328,21 +763,45 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         height: area.height.saturating_sub(2),
     };
 
+    // In split mode the category line grows into the split rows
+    let split_mode = app.transaction_form.split_mode;
+    let category_height = if split_mode {
+        1 + app
+            .transaction_form
+            .split_rows
+            .len()
+            .min(MAX_VISIBLE_SPLITS) as u16
+    } else {
+        1
+    };
+
+    // The payee suggestions open under the payee while it has focus
+    let payee_dropdown_height = if app.transaction_form.is_suggesting_payee() {
+        app.transaction_form
+            .payee_suggestions
+            .len()
+            .min(MAX_VISIBLE_PAYEES) as u16
+    } else {
+        0
+    };
+
     // Layout: fields + category dropdown + buttons
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(1), // Date
-            Constraint::Length(1), // Payee
-            Constraint::Length(1), // Category input
-            Constraint::Length(6), // Category dropdown
-            Constraint::Length(1), // Outflow
-            Constraint::Length(1), // Inflow
-            Constraint::Length(1), // Memo
-            Constraint::Length(1), // Spacer
-            Constraint::Length(1), // Error
-            Constraint::Length(1), // Buttons
-            Constraint::Min(0),    // Remaining
+            Constraint::Length(1),                     // Date
+            Constraint::Length(1),                     // Payee
+            Constraint::Length(payee_dropdown_height), // Payee suggestions
+            Constraint::Length(category_height),       // Category input or split rows
+            Constraint::Length(6),                     // Category dropdown
+            Constraint::Length(1),                     // Outflow
+            Constraint::Length(1),                     // Inflow
+            Constraint::Length(1),                     // Memo
+            Constraint::Length(1),                     // Tags
+            Constraint::Length(1),                     // Spacer
+            Constraint::Length(1),                     // Error
+            Constraint::Length(1),                     // Buttons
+            Constraint::Min(0),                        // Remaining
         ])
         .split(inner);
 
@@ -372,6 +831,11 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     let memo_cursor = app.transaction_form.memo_input.cursor;
     let memo_placeholder = app.transaction_form.memo_input.placeholder.clone();
 
+    let tags_value = app.transaction_form.tags_input.value().to_string();
+    let tags_focused = app.transaction_form.focused_field == TransactionField::Tags;
+    let tags_cursor = app.transaction_form.tags_input.cursor;
+    let tags_placeholder = app.transaction_form.tags_input.placeholder.clone();
+
     let error_message = app.transaction_form.error_message.clone();
 
     // Render date field
@@ -396,35 +860,48 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         &payee_placeholder,
     );
 
+    if payee_dropdown_height > 0 {
+        render_payee_dropdown(frame, app, chunks[2]);
+    }
+
     // Render category field (needs app for category lookup)
-    render_category_field(frame, app, chunks[2], chunks[3]);
+    if split_mode {
+        render_split_rows(frame, app, chunks[3]);
+        if app.transaction_form.is_searching_category() {
+            render_category_dropdown(frame, app, chunks[4]);
+        }
+    } else {
+        render_category_field(frame, app, chunks[3], chunks[4]);
+    }
 
     // Render outflow field
     render_field_simple(
         frame,
-        chunks[4],
+        chunks[5],
         "Outflow",
         &outflow_value,
         outflow_focused,
         outflow_cursor,
         &outflow_placeholder,
     );
+    render_expression_total(frame, chunks[5], &outflow_value);
 
     // Render inflow field
     render_field_simple(
         frame,
-        chunks[5],
+        chunks[6],
         "Inflow",
         &inflow_value,
         inflow_focused,
         inflow_cursor,
         &inflow_placeholder,
     );
+    render_expression_total(frame, chunks[6], &inflow_value);
 
     // Render memo field
     render_field_simple(
         frame,
-        chunks[6],
+        chunks[7],
         "Memo",
         &memo_value,
         memo_focused,
@@ -432,27 +909,209 @@ pub fn render(frame: &mut Frame, app: &mut App) {
         &memo_placeholder,
     );
 
+    // Render tags field
+    render_field_simple(
+        frame,
+        chunks[8],
+        "Tags",
+        &tags_value,
+        tags_focused,
+        tags_cursor,
+        &tags_placeholder,
+    );
+
     // Render error message if any
     if let Some(ref error) = error_message {
         let error_line = Line::from(Span::styled(
             error.as_str(),
             Style::default().fg(Color::Red),
         ));
-        frame.render_widget(Paragraph::new(error_line), chunks[8]);
+        frame.render_widget(Paragraph::new(error_line), chunks[10]);
     }
 
     // Render buttons/hints
-    let hints = Line::from(vec![
+    let mut hints = vec![
         Span::styled("[Tab]", Style::default().fg(Color::Yellow)),
         Span::raw(" Next  "),
         Span::styled("[Shift+Tab]", Style::default().fg(Color::Yellow)),
         Span::raw(" Prev  "),
+        Span::styled("[Ctrl+T]", Style::default().fg(Color::Yellow)),
+        Span::raw(if split_mode { " Unsplit  " } else { " Split  " }),
+    ];
+    if split_mode {
+        hints.extend([
+            Span::styled("[Ctrl+N]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Add split  "),
+            Span::styled("[Ctrl+D]", Style::default().fg(Color::Yellow)),
+            Span::raw(" Remove split  "),
+        ]);
+    }
+    hints.extend([
         Span::styled("[Enter]", Style::default().fg(Color::Green)),
         Span::raw(" Save  "),
         Span::styled("[Esc]", Style::default().fg(Color::Red)),
         Span::raw(" Cancel"),
     ]);
-    frame.render_widget(Paragraph::new(hints), chunks[9]);
+    frame.render_widget(Paragraph::new(Line::from(hints)), chunks[11]);
+}
+
+/// Most payee suggestions offered at once
+const MAX_VISIBLE_PAYEES: usize = 5;
+
+/// Render the payee suggestions, ranked by how often and recently each is used
+fn render_payee_dropdown(frame: &mut Frame, app: &App, area: Rect) {
+    let form = &app.transaction_form;
+    let items: Vec<ListItem> = form
+        .payee_suggestions
+        .iter()
+        .map(|name| {
+            ListItem::new(Line::from(Span::styled(
+                format!("{:>12}{}", "", name),
+                Style::default().fg(Color::White),
+            )))
+        })
+        .collect();
+
+    let list = List::new(items).highlight_style(
+        Style::default()
+            .bg(Color::DarkGray)
+            .add_modifier(Modifier::BOLD),
+    );
+
+    let mut state = ListState::default();
+    state.select(form.payee_list_index);
+    frame.render_stateful_widget(list, area, &mut state);
+}
+
+/// Most split rows shown at once; the list scrolls to keep the focused row visible
+const MAX_VISIBLE_SPLITS: usize = 5;
+
+/// Render the split rows with the amount still to assign
+fn render_split_rows(frame: &mut Frame, app: &App, area: Rect) {
+    let form = &app.transaction_form;
+    let focused = form.focused_field == TransactionField::Category;
+
+    let label_style = if focused {
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::BOLD)
+    } else {
+        Style::default().fg(Color::Cyan)
+    };
+
+    // Header: remaining to assign, green once the splits balance
+    let remaining = match form.remaining_to_assign() {
+        Some(m) if m.is_zero() => Span::styled("Balanced", Style::default().fg(Color::Green)),
+        Some(m) if m.is_negative() => {
+            Span::styled(format!("{} over", m.abs()), Style::default().fg(Color::Red))
+        }
+        Some(m) => Span::styled(
+            format!("{} remaining to assign", m),
+            Style::default().fg(Color::Yellow),
+        ),
+        None => Span::styled(
+            "Enter an outflow or inflow",
+            Style::default().fg(Color::DarkGray),
+        ),
+    };
+    let mut header = vec![
+        Span::styled(format!("{:>10}: ", "Splits"), label_style),
+        remaining,
+    ];
+    if !form.transfer_splits.is_empty() {
+        let count = form.transfer_splits.len();
+        header.push(Span::styled(
+            format!(
+                "  (+{} transfer split{})",
+                count,
+                if count == 1 { "" } else { "s" }
+            ),
+            Style::default().fg(Color::DarkGray),
+        ));
+    }
+    let rows_area = Rect {
+        y: area.y + 1,
+        height: area.height.saturating_sub(1),
+        ..area
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(header)),
+        Rect { height: 1, ..area },
+    );
+
+    let visible = rows_area.height as usize;
+    let first = form
+        .split_index
+        .saturating_sub(visible.saturating_sub(1))
+        .min(form.split_rows.len().saturating_sub(visible));
+
+    for (offset, row) in form
+        .split_rows
+        .iter()
+        .enumerate()
+        .skip(first)
+        .take(visible)
+        .map(|(i, row)| (i - first, row))
+    {
+        let row_focused = focused && first + offset == form.split_index;
+        let marker = if row_focused { "▶ " } else { "  " };
+
+        let mut spans = vec![Span::raw(format!("{:>12}", marker))];
+        spans.extend(input_spans(
+            &row.category_input,
+            row.category_input.focused,
+            24,
+        ));
+        spans.push(Span::raw("  "));
+        spans.extend(input_spans(&row.amount_input, row.amount_input.focused, 10));
+
+        let line_area = Rect {
+            y: rows_area.y + offset as u16,
+            height: 1,
+            ..rows_area
+        };
+        frame.render_widget(Paragraph::new(Line::from(spans)), line_area);
+    }
+}
+
+/// Spans for an input padded to a width, with a cursor when focused
+fn input_spans(input: &TextInput, focused: bool, width: usize) -> Vec<Span<'static>> {
+    let value = input.value();
+    let value_style = if focused {
+        Style::default().fg(Color::White)
+    } else {
+        Style::default().fg(Color::Yellow)
+    };
+
+    if !focused {
+        let (text, style) = if value.is_empty() {
+            (
+                input.placeholder.clone(),
+                Style::default().fg(Color::DarkGray),
+            )
+        } else {
+            (value.to_string(), value_style)
+        };
+        return vec![Span::styled(format!("{:<width$}", text), style)];
+    }
+
+    let cursor_pos = input.cursor.min(value.len());
+    let (before, after) = value.split_at(cursor_pos);
+    let cursor_char = after.chars().next().unwrap_or(' ');
+    let rest = after.get(cursor_char.len_utf8()..).unwrap_or("");
+    let used = value.chars().count().max(cursor_pos + 1);
+
+    vec![
+        Span::styled(before.to_string(), value_style),
+        Span::styled(
+            cursor_char.to_string(),
+            Style::default().fg(Color::Black).bg(Color::Cyan),
+        ),
+        Span::styled(
+            format!("{}{}", rest, " ".repeat(width.saturating_sub(used))),
+            value_style,
+        ),
+    ]
 }
 
 /// Render a single form field with extracted values
@@ -516,6 +1175,22 @@ fn render_field_simple(
     frame.render_widget(Paragraph::new(Line::from(spans)), area);
 }
 
+/// Show the running total of an amount typed as a sum, right-aligned
+fn render_expression_total(frame: &mut Frame, area: Rect, value: &str) {
+    if !Money::is_expression(value) {
+        return;
+    }
+
+    let total = match Money::parse_expression(value) {
+        Ok(total) => Span::styled(format!("= {}", total), Style::default().fg(Color::Green)),
+        Err(_) => Span::styled("= ?", Style::default().fg(Color::Red)),
+    };
+    frame.render_widget(
+        Paragraph::new(Line::from(total)).alignment(Alignment::Right),
+        area,
+    );
+}
+
 /// Render the category field with dropdown
 fn render_category_field(frame: &mut Frame, app: &mut App, input_area: Rect, dropdown_area: Rect) {
     let form = &app.transaction_form;
@@ -596,12 +1271,8 @@ fn render_category_dropdown(frame: &mut Frame, app: &mut App, area: Rect) {
     let categories = category_service.list_categories().unwrap_or_default();
 
     // Filter categories based on search input
-    let search = app.transaction_form.category_input.value().to_lowercase();
-    let filtered: Vec<_> = categories
-        .iter()
-        .filter(|c| search.is_empty() || c.name.to_lowercase().contains(&search))
-        .take(5)
-        .collect();
+    let search = app.transaction_form.category_search();
+    let filtered = fuzzy_filter(&categories, search, |c| c.name.as_str());
 
     if filtered.is_empty() {
         let hint = if search.is_empty() {
@@ -632,19 +1303,57 @@ fn render_category_dropdown(frame: &mut Frame, app: &mut App, area: Rect) {
         )
         .highlight_symbol("▶ ");
 
-    let mut state = ListState::default();
-    let idx = app
-        .transaction_form
-        .category_list_index
-        .min(filtered.len().saturating_sub(1));
-    state.select(Some(idx));
+    let total = filtered.len();
+    let visible = area.height as usize;
+    let form = &mut app.transaction_form;
+    form.category_list_index = form.category_list_index.min(total.saturating_sub(1));
+    form.category_scroll_offset = follow_selection(
+        form.category_scroll_offset,
+        form.category_list_index,
+        visible,
+        total,
+    );
+
+    let mut state = ListState::default().with_offset(form.category_scroll_offset);
+    state.select(Some(form.category_list_index));
 
     frame.render_stateful_widget(list, area, &mut state);
+    render_scrollbar(frame, area, form.category_list_index, total, visible);
 }
 
 /// Handle key input for the transaction dialog
 /// Returns true if the key was handled, false otherwise
 pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
+    let handled = handle_form_key(app, key);
+    if app.has_dialog() && app.transaction_form.focused_field == TransactionField::Payee {
+        refresh_payee_suggestions(app);
+    }
+    handled
+}
+
+/// Recompute the payee suggestions when the payee input has changed
+fn refresh_payee_suggestions(app: &mut App) {
+    let form = &app.transaction_form;
+    let query = form.payee_input.value().to_string();
+    if form.payee_suggestions_for.as_deref() == Some(query.as_str()) {
+        return;
+    }
+
+    let suggestions = PayeeService::new(app.storage)
+        .suggest_payees(&query, MAX_VISIBLE_PAYEES)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|p| p.name)
+        .collect();
+
+    let form = &mut app.transaction_form;
+    form.payee_suggestions = suggestions;
+    form.payee_suggestions_for = Some(query);
+    form.payee_list_index = None;
+}
+
+/// Handle a key in the form itself
+fn handle_form_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
     use crossterm::event::{KeyCode, KeyModifiers};
 
     let form = &mut app.transaction_form;
@@ -669,10 +1378,60 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
             return true;
         }
 
+        // Split the category across several rows, or back to one
+        KeyCode::Char('t') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            form.clear_error();
+            form.toggle_split_mode();
+            return true;
+        }
+
+        KeyCode::Char('n') if key.modifiers.contains(KeyModifiers::CONTROL) && form.split_mode => {
+            form.clear_error();
+            form.set_focus(TransactionField::Category);
+            form.add_split_row();
+            return true;
+        }
+
+        KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) && form.split_mode => {
+            form.clear_error();
+            if form.focused_field == TransactionField::Category {
+                form.remove_split_row();
+            }
+            return true;
+        }
+
+        // Step the date rather than typing into it
+        KeyCode::Char(c @ ('+' | '=' | '-' | '[' | ']' | 't'))
+            if form.focused_field == TransactionField::Date
+                && !(c == '-' && form.is_typing_date()) =>
+        {
+            form.clear_error();
+            match c {
+                '+' | '=' => form.step_date_days(1),
+                '-' => form.step_date_days(-1),
+                '[' => form.step_date_months(-1),
+                ']' => form.step_date_months(1),
+                _ => form.set_date(clock::today()),
+            }
+            return true;
+        }
+
         KeyCode::Enter => {
+            // Take the highlighted payee suggestion, if one was picked
+            if form.is_suggesting_payee() {
+                if let Some(name) = form
+                    .payee_list_index
+                    .and_then(|i| form.payee_suggestions.get(i))
+                    .cloned()
+                {
+                    form.payee_input = TextInput::new().label("Payee").content(name);
+                    form.next_field();
+                    return true;
+                }
+            }
+
             // If in category dropdown and category is focused, select the category
-            if form.focused_field == TransactionField::Category && form.selected_category.is_none()
-            {
+            if form.is_searching_category() {
                 select_category_from_dropdown(app);
                 return true;
             }
@@ -685,31 +1444,50 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
         }
 
         KeyCode::Up => {
-            if form.focused_field == TransactionField::Category && form.selected_category.is_none()
-            {
+            if form.is_suggesting_payee() {
+                form.payee_list_index = match form.payee_list_index {
+                    Some(0) | None => None,
+                    Some(i) => Some(i - 1),
+                };
+                return true;
+            }
+            if form.is_searching_category() {
                 if form.category_list_index > 0 {
                     form.category_list_index -= 1;
                 }
                 return true;
             }
+            if form.split_mode && form.focused_field == TransactionField::Category {
+                form.split_index = form.split_index.saturating_sub(1);
+                form.set_focus(TransactionField::Category);
+                return true;
+            }
         }
 
         KeyCode::Down => {
-            if form.focused_field == TransactionField::Category && form.selected_category.is_none()
-            {
+            if form.is_suggesting_payee() {
+                let last = form.payee_suggestions.len() - 1;
+                form.payee_list_index =
+                    Some(form.payee_list_index.map_or(0, |i| (i + 1).min(last)));
+                return true;
+            }
+            if form.is_searching_category() {
                 form.category_list_index += 1;
                 return true;
             }
+            if form.split_mode && form.focused_field == TransactionField::Category {
+                form.split_index = (form.split_index + 1).min(form.split_rows.len() - 1);
+                form.set_focus(TransactionField::Category);
+                return true;
+            }
         }
 
         KeyCode::Backspace => {
             form.clear_error();
 
             // If category is selected and we're in category field, clear it
-            if form.focused_field == TransactionField::Category && form.selected_category.is_some()
-            {
-                form.selected_category = None;
-                form.category_input.clear();
+            if is_on_chosen_category(form) {
+                form.clear_active_category();
                 return true;
             }
 
@@ -748,17 +1526,16 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
             form.clear_error();
 
             // If category is selected and we're typing, clear it first
-            if form.focused_field == TransactionField::Category && form.selected_category.is_some()
-            {
-                form.selected_category = None;
-                form.category_input.clear();
+            if is_on_chosen_category(form) {
+                form.clear_active_category();
             }
 
             form.focused_input().insert(c);
 
             // Reset category list index when typing in category field
-            if form.focused_field == TransactionField::Category {
+            if form.is_searching_category() {
                 form.category_list_index = 0;
+                form.category_scroll_offset = 0;
             }
 
             return true;
@@ -770,25 +1547,28 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyEvent) -> bool {
     false
 }
 
+/// Whether focus is on a category input that already has a category chosen
+fn is_on_chosen_category(form: &TransactionFormState) -> bool {
+    form.focused_field == TransactionField::Category
+        && !(form.split_mode && form.split_amount_focused)
+        && form.active_category().is_some()
+}
+
 /// Select the currently highlighted category from the dropdown
 fn select_category_from_dropdown(app: &mut App) {
     let category_service = CategoryService::new(app.storage);
     let categories = category_service.list_categories().unwrap_or_default();
 
-    let search = app.transaction_form.category_input.value().to_lowercase();
-    let filtered: Vec<_> = categories
-        .iter()
-        .filter(|c| search.is_empty() || c.name.to_lowercase().contains(&search))
-        .take(5)
-        .collect();
+    let filtered = fuzzy_filter(&categories, app.transaction_form.category_search(), |c| {
+        c.name.as_str()
+    });
 
     let idx = app
         .transaction_form
         .category_list_index
         .min(filtered.len().saturating_sub(1));
     if let Some(cat) = filtered.get(idx) {
-        app.transaction_form.selected_category = Some(cat.id);
-        app.transaction_form.category_input = TextInput::new().label("Category").content(&cat.name);
+        app.transaction_form.set_category(cat.id, &cat.name);
         app.transaction_form.next_field(); // Move to next field after selection
     }
 }
@@ -798,43 +1578,83 @@ fn save_transaction(app: &mut App) -> Result<(), String> {
     // Validate form
     app.transaction_form.validate()?;
 
-    // Get account ID
-    let account_id = app.selected_account.ok_or("No account selected")?;
+    // Get account ID (edits keep the transaction's own account, which matters
+    // when the register is showing all accounts)
+    let account_id = match app.active_dialog {
+        ActiveDialog::EditTransaction(txn_id) => app
+            .storage
+            .transactions
+            .get(txn_id)
+            .ok()
+            .flatten()
+            .map(|t| t.account_id),
+        _ => app.selected_account,
+    }
+    .ok_or("No account selected")?;
 
     // Build transaction
     let txn = app.transaction_form.build_transaction(account_id)?;
+    let splits = if app.transaction_form.split_mode {
+        Some(app.transaction_form.build_splits(txn.amount)?)
+    } else {
+        None
+    };
+    let service = TransactionService::new(app.storage);
 
     // Check if edit or new
     let is_edit = matches!(app.active_dialog, ActiveDialog::EditTransaction(_));
 
-    if is_edit {
-        if let ActiveDialog::EditTransaction(txn_id) = app.active_dialog {
-            // Update existing transaction
-            if let Ok(Some(mut existing)) = app.storage.transactions.get(txn_id) {
-                existing.date = txn.date;
-                existing.amount = txn.amount;
-                existing.payee_name = txn.payee_name;
-                existing.category_id = txn.category_id;
-                existing.memo = txn.memo;
-                existing.updated_at = chrono::Utc::now();
-
-                app.storage
-                    .transactions
-                    .upsert(existing)
-                    .map_err(|e| e.to_string())?;
-            }
+    let saved_id = if let ActiveDialog::EditTransaction(txn_id) = app.active_dialog {
+        // Leaving split mode drops the old splits (and their transfer legs)
+        let was_split = app
+            .storage
+            .transactions
+            .get(txn_id)
+            .ok()
+            .flatten()
+            .is_some_and(|t| t.is_split());
+        if was_split && splits.is_none() {
+            service
+                .set_splits(txn_id, Vec::new())
+                .map_err(|e| e.to_string())?;
+        }
+
+        // Update existing transaction
+        if let Ok(Some(mut existing)) = app.storage.transactions.get(txn_id) {
+            existing.date = txn.date;
+            existing.amount = txn.amount;
+            existing.payee_name = txn.payee_name;
+            existing.category_id = txn.category_id;
+            existing.memo = txn.memo;
+            existing.tags = txn.tags;
+            existing.updated_at = clock::now();
+
+            app.storage
+                .transactions
+                .upsert(existing)
+                .map_err(|e| e.to_string())?;
         }
+        txn_id
     } else {
         // Create new transaction
+        let id = txn.id;
         app.storage
             .transactions
             .upsert(txn)
             .map_err(|e| e.to_string())?;
-    }
+        id
+    };
 
     // Save to disk
     app.storage.transactions.save().map_err(|e| e.to_string())?;
 
+    // Splits replace the category (set_splits validates and saves)
+    if let Some(splits) = splits {
+        service
+            .set_splits(saved_id, splits)
+            .map_err(|e| e.to_string())?;
+    }
+
     // Close dialog
     app.close_dialog();
     app.set_status(if is_edit {
@@ -3,22 +3,36 @@
 //! Routes keyboard and mouse events to the appropriate handlers
 //! based on the current application state.
 
+use std::time::Instant;
+
 use anyhow::Result;
 use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
 
 use super::app::{ActiveDialog, ActiveView, App, FocusedPanel, InputMode};
 use super::commands::{CommandAction, COMMANDS};
+use super::dialogs::reconcile_start::ReconcileStartState;
 use super::event::Event;
+use super::widgets::{type_ahead_match, Notification};
+use crate::services::{AccountService, CategoryService, TransactionService};
 
 /// Handle an incoming event
 pub fn handle_event(app: &mut App, event: Event) -> Result<()> {
     match event {
-        Event::Key(key) => handle_key_event(app, key),
+        Event::Key(key) => {
+            let result = handle_key_event(app, key);
+            // Whatever a key did is finished, so later edits aren't part of it
+            app.storage.end_destructive();
+            result
+        }
         Event::Mouse(_mouse) => {
             // Mouse handling can be added later
             Ok(())
         }
-        Event::Tick => Ok(()),
+        Event::Tick => {
+            app.sidebar_type_ahead.expire(Instant::now());
+            app.notifications.remove_expired();
+            Ok(())
+        }
         Event::Resize(_, _) => Ok(()),
     }
 }
@@ -40,6 +54,11 @@ fn handle_key_event(app: &mut App, key: KeyEvent) -> Result<()> {
 
 /// Handle keys in normal mode
 fn handle_normal_key(app: &mut App, key: KeyEvent) -> Result<()> {
+    // An active sidebar type-ahead takes typed characters before any command
+    if app.focused_panel == FocusedPanel::Sidebar && handle_type_ahead_key(app, key) {
+        return Ok(());
+    }
+
     // Global keys (work everywhere)
     match key.code {
         // Quit
@@ -60,6 +79,12 @@ fn handle_normal_key(app: &mut App, key: KeyEvent) -> Result<()> {
             return Ok(());
         }
 
+        // Go to any account, category, or payee
+        KeyCode::Char('f') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.open_dialog(ActiveDialog::GoTo);
+            return Ok(());
+        }
+
         // Panel navigation
         KeyCode::Tab => {
             app.toggle_panel_focus();
@@ -91,43 +116,36 @@ fn handle_normal_key(app: &mut App, key: KeyEvent) -> Result<()> {
 
 /// Handle keys when sidebar is focused
 fn handle_sidebar_key(app: &mut App, key: KeyEvent) -> Result<()> {
-    // Get account count for bounds checking
+    // Get account count for bounds checking (plus the trailing "All Accounts" row)
     let account_count = app
         .storage
         .accounts
         .get_active()
         .map(|a| a.len())
         .unwrap_or(0);
+    let row_count = if account_count > 0 {
+        account_count + 1
+    } else {
+        0
+    };
 
     match key.code {
         // Navigation
         KeyCode::Char('j') | KeyCode::Down => {
-            app.move_down(account_count);
-            // Update selected account
-            if let Ok(accounts) = app.storage.accounts.get_active() {
-                if let Some(account) = accounts.get(app.selected_account_index) {
-                    app.selected_account = Some(account.id);
-                }
-            }
+            app.move_down(row_count);
+            sync_sidebar_selection(app);
         }
         KeyCode::Char('k') | KeyCode::Up => {
             app.move_up();
-            // Update selected account
-            if let Ok(accounts) = app.storage.accounts.get_active() {
-                if let Some(account) = accounts.get(app.selected_account_index) {
-                    app.selected_account = Some(account.id);
-                }
-            }
+            sync_sidebar_selection(app);
         }
 
-        // Select account and view register
+        // Select account (or "All Accounts") and view register
         KeyCode::Enter => {
-            if let Ok(accounts) = app.storage.accounts.get_active() {
-                if let Some(account) = accounts.get(app.selected_account_index) {
-                    app.selected_account = Some(account.id);
-                    app.switch_view(ActiveView::Register);
-                    app.focused_panel = FocusedPanel::Main;
-                }
+            if app.selected_account_index < row_count {
+                sync_sidebar_selection(app);
+                app.switch_view(ActiveView::Register);
+                app.focused_panel = FocusedPanel::Main;
             }
         }
 
@@ -155,12 +173,86 @@ fn handle_sidebar_key(app: &mut App, key: KeyEvent) -> Result<()> {
             }
         }
 
+        // Find account by typing its name
+        KeyCode::Char('f') => {
+            app.sidebar_type_ahead.start(Instant::now());
+        }
+
+        // Move the selected account up or down
+        KeyCode::Char('K') | KeyCode::Char('J') => {
+            let up = key.code == KeyCode::Char('K');
+            if let Ok(accounts) = app.storage.accounts.get_active() {
+                if let Some(account) = accounts.get(app.selected_account_index) {
+                    match AccountService::new(app.storage).move_account(account.id, up) {
+                        Ok(true) => {
+                            if up {
+                                app.selected_account_index -= 1;
+                            } else {
+                                app.selected_account_index += 1;
+                            }
+                            sync_sidebar_selection(app);
+                        }
+                        Ok(false) => {}
+                        Err(e) => app.set_status(format!("Failed to move account: {}", e)),
+                    }
+                }
+            }
+        }
+
         _ => {}
     }
 
     Ok(())
 }
 
+/// Handle a key while the sidebar type-ahead is active
+///
+/// Returns true if the key was consumed. Enter and other non-text keys end
+/// the query and fall through, so Enter still opens the selected account.
+fn handle_type_ahead_key(app: &mut App, key: KeyEvent) -> bool {
+    let now = Instant::now();
+    if !app.sidebar_type_ahead.is_active(now) {
+        app.sidebar_type_ahead.clear();
+        return false;
+    }
+
+    match key.code {
+        KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+            app.sidebar_type_ahead.push(c, now);
+        }
+        KeyCode::Backspace => {
+            app.sidebar_type_ahead.pop(now);
+        }
+        KeyCode::Esc => {
+            app.sidebar_type_ahead.clear();
+            return true;
+        }
+        _ => {
+            app.sidebar_type_ahead.clear();
+            return false;
+        }
+    }
+
+    let accounts = app.storage.accounts.get_active().unwrap_or_default();
+    let names = accounts.iter().map(|a| a.name.as_str());
+    if let Some(index) = type_ahead_match(names, app.sidebar_type_ahead.query()) {
+        app.selected_account_index = index;
+        sync_sidebar_selection(app);
+    }
+
+    true
+}
+
+/// Update the selected account from the sidebar index
+///
+/// The row after the last account is the "All Accounts" pseudo-selection,
+/// represented by `selected_account == None`.
+fn sync_sidebar_selection(app: &mut App) {
+    if let Ok(accounts) = app.storage.accounts.get_active() {
+        app.selected_account = accounts.get(app.selected_account_index).map(|a| a.id);
+    }
+}
+
 /// Handle keys when main panel is focused
 fn handle_main_panel_key(app: &mut App, key: KeyEvent) -> Result<()> {
     match app.active_view {
@@ -220,20 +312,9 @@ fn handle_accounts_view_key(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
-/// Get sorted transactions for an account (matches display order)
+/// Get sorted transactions for the register (matches display order)
 fn get_sorted_transactions(app: &App) -> Vec<crate::models::Transaction> {
-    if let Some(account_id) = app.selected_account {
-        let mut txns = app
-            .storage
-            .transactions
-            .get_by_account(account_id)
-            .unwrap_or_default();
-        // Sort by date descending (matches render order)
-        txns.sort_by(|a, b| b.date.cmp(&a.date));
-        txns
-    } else {
-        Vec::new()
-    }
+    app.register_transactions()
 }
 
 /// Handle keys in the register view
@@ -293,6 +374,22 @@ fn handle_register_view_key(app: &mut App, key: KeyEvent) -> Result<()> {
             app.open_dialog(ActiveDialog::AddTransaction);
         }
 
+        // Reconcile the account against a statement
+        KeyCode::Char('r') => {
+            app.pending_g = false;
+            start_reconciliation(app);
+        }
+
+        // Sort by the next key, or flip the direction
+        KeyCode::Char('s') => {
+            app.pending_g = false;
+            app.cycle_register_sort();
+        }
+        KeyCode::Char('S') => {
+            app.pending_g = false;
+            app.toggle_register_sort_direction();
+        }
+
         // Edit transaction
         KeyCode::Char('e') => {
             app.pending_g = false;
@@ -343,13 +440,27 @@ fn handle_register_view_key(app: &mut App, key: KeyEvent) -> Result<()> {
             }
         }
 
+        // Cycle the review flag (none -> review -> starred)
+        KeyCode::Char('f') => {
+            app.pending_g = false;
+            if let Some(txn_id) = app.selected_transaction {
+                if let Ok(Some(txn)) = app.storage.transactions.get(txn_id) {
+                    let service = TransactionService::new(app.storage);
+                    match service.set_flag(txn_id, txn.flag.next()) {
+                        Ok(updated) => {
+                            app.set_status(format!("Transaction flag: {}", updated.flag));
+                        }
+                        Err(e) => app.set_status(format!("Error: {}", e)),
+                    }
+                }
+            }
+        }
+
         // Delete transaction
         KeyCode::Char('d') if key.modifiers.contains(KeyModifiers::CONTROL) => {
             app.pending_g = false;
             if app.selected_transaction.is_some() {
-                app.open_dialog(ActiveDialog::Confirm(
-                    "Delete this transaction?".to_string(),
-                ));
+                confirm_destructive(app, "Delete this transaction?".to_string())?;
             }
         }
 
@@ -380,11 +491,14 @@ fn handle_register_view_key(app: &mut App, key: KeyEvent) -> Result<()> {
         KeyCode::Char('D') if app.multi_select_mode && !app.selected_transactions.is_empty() => {
             app.pending_g = false;
             let count = app.selected_transactions.len();
-            app.open_dialog(ActiveDialog::Confirm(format!(
-                "Delete {} transaction{}?",
-                count,
-                if count == 1 { "" } else { "s" }
-            )));
+            confirm_destructive(
+                app,
+                format!(
+                    "Delete {} transaction{}?",
+                    count,
+                    if count == 1 { "" } else { "s" }
+                ),
+            )?;
         }
 
         _ => {
@@ -397,23 +511,7 @@ fn handle_register_view_key(app: &mut App, key: KeyEvent) -> Result<()> {
 
 /// Get categories in visual order (grouped by group, same as render)
 fn get_categories_in_visual_order(app: &App) -> Vec<crate::models::Category> {
-    let groups = app.storage.categories.get_all_groups().unwrap_or_default();
-    let all_categories = app
-        .storage
-        .categories
-        .get_all_categories()
-        .unwrap_or_default();
-
-    let mut result = Vec::new();
-    for group in &groups {
-        let group_cats: Vec<_> = all_categories
-            .iter()
-            .filter(|c| c.group_id == group.id)
-            .cloned()
-            .collect();
-        result.extend(group_cats);
-    }
-    result
+    app.budget_categories()
 }
 
 /// Handle keys in the budget view
@@ -503,6 +601,34 @@ fn handle_budget_view_key(app: &mut App, key: KeyEvent) -> Result<()> {
             app.open_dialog(ActiveDialog::AddGroup);
         }
 
+        // Move the selected category up or down within its group
+        KeyCode::Char('K') | KeyCode::Char('J') => {
+            app.pending_g = false;
+            let up = key.code == KeyCode::Char('K');
+            if let Some(cat) = categories.get(app.selected_category_index) {
+                match CategoryService::new(app.storage).move_category_in_group(cat.id, up) {
+                    Ok(true) => {
+                        if let Some(index) =
+                            app.budget_categories().iter().position(|c| c.id == cat.id)
+                        {
+                            app.selected_category_index = index;
+                            app.selected_category = Some(cat.id);
+                        }
+                    }
+                    Ok(false) => {}
+                    Err(e) => app.set_status(format!("Failed to move category: {}", e)),
+                }
+            }
+        }
+
+        // Move category to another group (Shift+M)
+        KeyCode::Char('M') => {
+            app.pending_g = false;
+            if let Some(cat) = categories.get(app.selected_category_index) {
+                app.open_dialog(ActiveDialog::MoveCategory(cat.id));
+            }
+        }
+
         // Edit category group (Shift+E)
         KeyCode::Char('E') => {
             app.pending_g = false;
@@ -530,7 +656,7 @@ fn handle_budget_view_key(app: &mut App, key: KeyEvent) -> Result<()> {
                             group_categories.len()
                         )
                     };
-                    app.open_dialog(ActiveDialog::Confirm(warning));
+                    confirm_destructive(app, warning)?;
                 }
             }
         }
@@ -550,10 +676,7 @@ fn handle_budget_view_key(app: &mut App, key: KeyEvent) -> Result<()> {
             if let Some(cat) = categories.get(app.selected_category_index) {
                 app.selected_category = Some(cat.id);
                 if let Ok(Some(category)) = app.storage.categories.get_category(cat.id) {
-                    app.open_dialog(ActiveDialog::Confirm(format!(
-                        "Delete category '{}'?",
-                        category.name
-                    )));
+                    confirm_destructive(app, format!("Delete category '{}'?", category.name))?;
                 }
             }
         }
@@ -567,12 +690,50 @@ fn handle_budget_view_key(app: &mut App, key: KeyEvent) -> Result<()> {
             }
         }
 
+        // Edit the budgeted amount in place
+        KeyCode::Char('=') => {
+            app.pending_g = false;
+            if let Some(cat) = categories.get(app.selected_category_index) {
+                app.selected_category = Some(cat.id);
+                super::dialogs::budget::start_inline_edit(app, cat.id);
+            }
+        }
+
+        // Budget exactly the target's suggested amount
+        KeyCode::Char('f') => {
+            app.pending_g = false;
+            if let Some(cat) = categories.get(app.selected_category_index) {
+                app.selected_category = Some(cat.id);
+                super::dialogs::budget::fund_to_target(app, cat.id);
+            }
+        }
+
         // Open income dialog
         KeyCode::Char('i') => {
             app.pending_g = false;
             app.open_dialog(ActiveDialog::Income);
         }
 
+        // Collapse or expand the selected category's group
+        KeyCode::Char('z') => {
+            app.pending_g = false;
+            if let Some(cat) = categories.get(app.selected_category_index) {
+                app.toggle_group_collapsed(cat.group_id);
+            }
+        }
+
+        // Expand all groups
+        KeyCode::Char('Z') => {
+            app.pending_g = false;
+            app.expand_all_groups();
+        }
+
+        // Undo the last fund move
+        KeyCode::Char('u') => {
+            app.pending_g = false;
+            super::dialogs::move_funds::undo_last_move(app);
+        }
+
         _ => {
             app.pending_g = false;
         }
@@ -582,8 +743,14 @@ fn handle_budget_view_key(app: &mut App, key: KeyEvent) -> Result<()> {
 }
 
 /// Handle keys in the reports view
-fn handle_reports_view_key(_app: &mut App, _key: KeyEvent) -> Result<()> {
-    // Reports view keys will be added later
+fn handle_reports_view_key(app: &mut App, key: KeyEvent) -> Result<()> {
+    match key.code {
+        // Period navigation
+        KeyCode::Char('[') | KeyCode::Char('H') => app.prev_period(),
+        KeyCode::Char(']') | KeyCode::Char('L') => app.next_period(),
+        KeyCode::Char('c') => app.reports_compare = !app.reports_compare,
+        _ => {}
+    }
     Ok(())
 }
 
@@ -596,6 +763,11 @@ fn handle_reconcile_view_key(app: &mut App, key: KeyEvent) -> Result<()> {
 
 /// Handle keys in editing mode
 fn handle_editing_key(app: &mut App, key: KeyEvent) -> Result<()> {
+    if app.inline_budget_edit.is_some() {
+        super::dialogs::budget::handle_inline_edit_key(app, key);
+        return Ok(());
+    }
+
     match key.code {
         KeyCode::Esc => {
             app.input_mode = InputMode::Normal;
@@ -699,6 +871,9 @@ fn execute_command_action(app: &mut App, action: CommandAction) -> Result<()> {
         CommandAction::ViewRegister => {
             app.switch_view(ActiveView::Register);
         }
+        CommandAction::GoTo => {
+            app.open_dialog(ActiveDialog::GoTo);
+        }
 
         // Account operations
         CommandAction::AddAccount => {
@@ -715,10 +890,7 @@ fn execute_command_action(app: &mut App, action: CommandAction) -> Result<()> {
             // Archive selected account with confirmation
             if let Ok(accounts) = app.storage.accounts.get_active() {
                 if let Some(account) = accounts.get(app.selected_account_index) {
-                    app.open_dialog(ActiveDialog::Confirm(format!(
-                        "Archive account '{}'?",
-                        account.name
-                    )));
+                    confirm_destructive(app, format!("Archive account '{}'?", account.name))?;
                 } else {
                     app.set_status("No account selected".to_string());
                 }
@@ -738,11 +910,14 @@ fn execute_command_action(app: &mut App, action: CommandAction) -> Result<()> {
         }
         CommandAction::DeleteTransaction => {
             if app.selected_transaction.is_some() {
-                app.open_dialog(ActiveDialog::Confirm("Delete transaction?".to_string()));
+                confirm_destructive(app, "Delete transaction?".to_string())?;
             } else {
                 app.set_status("No transaction selected".to_string());
             }
         }
+        CommandAction::Reconcile => {
+            start_reconciliation(app);
+        }
         CommandAction::ClearTransaction => {
             // Toggle cleared status for selected transaction
             if let Some(txn_id) = app.selected_transaction {
@@ -821,15 +996,51 @@ fn execute_command_action(app: &mut App, action: CommandAction) -> Result<()> {
             // Delete selected category with confirmation
             if let Some(category_id) = app.selected_category {
                 if let Ok(Some(category)) = app.storage.categories.get_category(category_id) {
-                    app.open_dialog(ActiveDialog::Confirm(format!(
-                        "Delete category '{}'?",
-                        category.name
-                    )));
+                    confirm_destructive(app, format!("Delete category '{}'?", category.name))?;
+                }
+            } else {
+                app.set_status("No category selected".to_string());
+            }
+        }
+        CommandAction::ArchiveCategory => {
+            // Archive the selected category, or bring it back if it already is
+            if let Some(category_id) = app.selected_category {
+                if let Ok(Some(category)) = app.storage.categories.get_category(category_id) {
+                    use crate::services::CategoryService;
+                    let category_service = CategoryService::new(app.storage);
+                    let result = if category.archived {
+                        category_service.unarchive_category(category_id)
+                    } else {
+                        category_service.archive_category(category_id)
+                    };
+                    match result {
+                        Ok(category) if category.archived => {
+                            app.set_status(format!("Category '{}' archived", category.name));
+                            if !app.show_archived {
+                                app.selected_category = None;
+                                app.selected_category_index = 0;
+                            }
+                        }
+                        Ok(category) => {
+                            app.set_status(format!("Category '{}' unarchived", category.name));
+                        }
+                        Err(e) => {
+                            app.set_status(format!("Failed to archive: {}", e));
+                        }
+                    }
                 }
             } else {
                 app.set_status("No category selected".to_string());
             }
         }
+        CommandAction::MoveCategory => {
+            // Pick a new group for the selected category
+            if let Some(category_id) = app.selected_category {
+                app.open_dialog(ActiveDialog::MoveCategory(category_id));
+            } else {
+                app.set_status("No category selected. Switch to Budget view first.".to_string());
+            }
+        }
         CommandAction::EditGroup => {
             // Edit the group of the currently selected category
             if let Some(category_id) = app.selected_category {
@@ -860,7 +1071,7 @@ fn execute_command_action(app: &mut App, action: CommandAction) -> Result<()> {
                                 categories.len()
                             )
                         };
-                        app.open_dialog(ActiveDialog::Confirm(warning));
+                        confirm_destructive(app, warning)?;
                     }
                 }
             } else {
@@ -878,22 +1089,38 @@ fn execute_command_action(app: &mut App, action: CommandAction) -> Result<()> {
         CommandAction::Refresh => {
             // Reload all data from disk
             if let Err(e) = app.storage.accounts.load() {
-                app.set_status(format!("Failed to refresh accounts: {}", e));
+                app.notifications.push(Notification::error(format!(
+                    "Failed to refresh accounts: {}",
+                    e
+                )));
                 return Ok(());
             }
             if let Err(e) = app.storage.transactions.load() {
-                app.set_status(format!("Failed to refresh transactions: {}", e));
+                app.notifications.push(Notification::error(format!(
+                    "Failed to refresh transactions: {}",
+                    e
+                )));
                 return Ok(());
             }
             if let Err(e) = app.storage.categories.load() {
-                app.set_status(format!("Failed to refresh categories: {}", e));
+                app.notifications.push(Notification::error(format!(
+                    "Failed to refresh categories: {}",
+                    e
+                )));
                 return Ok(());
             }
             if let Err(e) = app.storage.budget.load() {
-                app.set_status(format!("Failed to refresh budget: {}", e));
+                app.notifications.push(Notification::error(format!(
+                    "Failed to refresh budget: {}",
+                    e
+                )));
                 return Ok(());
             }
-            app.set_status("Data refreshed from disk".to_string());
+            app.notifications
+                .push(Notification::success("Data refreshed from disk"));
+        }
+        CommandAction::Undo => {
+            confirm_destructive(app, UNDO_MESSAGE.to_string())?;
         }
         CommandAction::ToggleArchived => {
             app.show_archived = !app.show_archived;
@@ -902,19 +1129,25 @@ fn execute_command_action(app: &mut App, action: CommandAction) -> Result<()> {
         // Target operations
         CommandAction::AutoFillTargets => {
             use crate::services::BudgetService;
-            let budget_service = BudgetService::new(app.storage);
+            let budget_service =
+                BudgetService::new(app.storage).with_target_rounding(app.settings.target_rounding);
             match budget_service.auto_fill_all_targets(&app.current_period) {
                 Ok(allocations) => {
                     if allocations.is_empty() {
-                        app.set_status("No targets to auto-fill".to_string());
+                        app.notifications
+                            .push(Notification::info("No targets to auto-fill"));
                     } else {
                         let count = allocations.len();
                         let plural = if count == 1 { "category" } else { "categories" };
-                        app.set_status(format!("{} {} updated from targets", count, plural));
+                        app.notifications.push(Notification::success(format!(
+                            "{} {} updated from targets",
+                            count, plural
+                        )));
                     }
                 }
                 Err(e) => {
-                    app.set_status(format!("Auto-fill failed: {}", e));
+                    app.notifications
+                        .push(Notification::error(format!("Auto-fill failed: {}", e)));
                 }
             }
         }
@@ -932,6 +1165,9 @@ fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<()> {
         ActiveDialog::CommandPalette => {
             handle_command_key(app, key)?;
         }
+        ActiveDialog::GoTo => {
+            super::dialogs::go_to::handle_key(app, key);
+        }
         ActiveDialog::Confirm(msg) => {
             let msg = msg.clone();
             match key.code {
@@ -956,21 +1192,17 @@ fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<()> {
         ActiveDialog::BulkCategorize => {
             super::dialogs::bulk_categorize::handle_key(app, key);
         }
-        ActiveDialog::ReconcileStart => {
-            match key.code {
-                KeyCode::Esc => {
-                    app.close_dialog();
-                }
-                KeyCode::Enter => {
-                    // Start reconciliation
-                    app.close_dialog();
-                    app.switch_view(ActiveView::Reconcile);
-                }
-                _ => {
-                    super::dialogs::reconcile_start::handle_key(app, key.code);
-                }
+        ActiveDialog::ReconcileStart => match key.code {
+            KeyCode::Esc => {
+                app.close_dialog();
             }
-        }
+            KeyCode::Enter => {
+                super::dialogs::reconcile_start::submit(app);
+            }
+            _ => {
+                super::dialogs::reconcile_start::handle_key(app, key.code);
+            }
+        },
         ActiveDialog::UnlockConfirm(_) => {
             match key.code {
                 KeyCode::Char('y') | KeyCode::Char('Y') => {
@@ -983,20 +1215,17 @@ fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<()> {
                 _ => {}
             }
         }
-        ActiveDialog::Adjustment => {
-            match key.code {
-                KeyCode::Esc => {
-                    app.close_dialog();
-                }
-                KeyCode::Enter => {
-                    // Create adjustment
-                    app.close_dialog();
-                }
-                _ => {
-                    super::dialogs::adjustment::handle_key(app, key.code);
-                }
+        ActiveDialog::Adjustment => match key.code {
+            KeyCode::Esc => {
+                app.close_dialog();
             }
-        }
+            KeyCode::Enter if !app.adjustment_dialog_state.selecting_category => {
+                super::dialogs::adjustment::submit(app);
+            }
+            _ => {
+                super::dialogs::adjustment::handle_key(app, key.code);
+            }
+        },
         ActiveDialog::Budget => {
             super::dialogs::budget::handle_key(app, key);
         }
@@ -1009,6 +1238,10 @@ fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<()> {
         ActiveDialog::AddCategory | ActiveDialog::EditCategory(_) => {
             super::dialogs::category::handle_key(app, key);
         }
+        ActiveDialog::MoveCategory(category_id) => {
+            let category_id = *category_id;
+            super::dialogs::move_category::handle_key(app, key, category_id);
+        }
         ActiveDialog::AddGroup | ActiveDialog::EditGroup(_) => {
             super::dialogs::group::handle_key(app, key);
         }
@@ -1017,8 +1250,77 @@ fn handle_dialog_key(app: &mut App, key: KeyEvent) -> Result<()> {
     Ok(())
 }
 
+/// Open the statement dialog to reconcile the selected account
+fn start_reconciliation(app: &mut App) {
+    if app.selected_account.is_none() {
+        app.set_status("Select an account to reconcile");
+        return;
+    }
+    app.reconcile_start_state = ReconcileStartState::new();
+    app.open_dialog(ActiveDialog::ReconcileStart);
+}
+
+/// Ask before a destructive action, or run it straight away when the
+/// `confirm_destructive` setting is off
+fn confirm_destructive(app: &mut App, message: String) -> Result<()> {
+    if app.settings.confirm_destructive {
+        app.open_dialog(ActiveDialog::Confirm(message));
+        Ok(())
+    } else {
+        execute_confirmed_action(app, &message)
+    }
+}
+
+/// Confirmation message for undoing the last destructive change
+const UNDO_MESSAGE: &str = "Undo the last destructive change?";
+
+/// Roll back the last destructive change
+fn undo_last_change(app: &mut App) {
+    match app.storage.undo_last() {
+        Ok(result) => {
+            // Selections may point at data that no longer exists
+            app.switch_view(app.active_view);
+            app.notifications.push(Notification::success(format!(
+                "Undone: data is back to {}",
+                result.backup_date.format("%Y-%m-%d %H:%M:%S UTC")
+            )));
+        }
+        Err(e) => {
+            app.notifications
+                .push(Notification::error(format!("Undo failed: {}", e)));
+        }
+    }
+}
+
 /// Execute an action after user confirmation
 fn execute_confirmed_action(app: &mut App, message: &str) -> Result<()> {
+    // Undo backs up the current data itself; a pre-destructive backup here
+    // would replace the change it is meant to undo
+    if message == UNDO_MESSAGE {
+        undo_last_change(app);
+        return Ok(());
+    }
+
+    // Every destructive action is backed up first, confirmed or not
+    match app.storage.backup_before_destructive() {
+        Ok(Some(path)) => {
+            let name = path
+                .file_name()
+                .map(|n| n.to_string_lossy().into_owned())
+                .unwrap_or_default();
+            app.notifications
+                .push(Notification::info(format!("Backup created: {}", name)));
+        }
+        Ok(None) => {}
+        Err(e) => {
+            app.notifications.push(Notification::error(format!(
+                "Backup failed, nothing was changed: {}",
+                e
+            )));
+            return Ok(());
+        }
+    }
+
     // Bulk delete transactions
     if message.contains("Delete")
         && message.contains("transaction")
@@ -1028,15 +1330,15 @@ fn execute_confirmed_action(app: &mut App, message: &str) -> Result<()> {
         let mut deleted_count = 0;
         let mut error_count = 0;
 
+        let txn_service = TransactionService::new(app.storage);
         for txn_id in &transaction_ids {
-            if app.storage.transactions.delete(*txn_id).is_err() {
+            if txn_service.delete(*txn_id).is_err() {
                 error_count += 1;
             } else {
                 deleted_count += 1;
             }
         }
 
-        let _ = app.storage.transactions.save();
         app.selected_transactions.clear();
         app.multi_select_mode = false;
 
@@ -1052,10 +1354,9 @@ fn execute_confirmed_action(app: &mut App, message: &str) -> Result<()> {
     // Delete single transaction
     else if message.contains("Delete") && message.contains("transaction") {
         if let Some(txn_id) = app.selected_transaction {
-            if let Err(e) = app.storage.transactions.delete(txn_id) {
+            if let Err(e) = TransactionService::new(app.storage).delete(txn_id) {
                 app.set_status(format!("Failed to delete: {}", e));
             } else {
-                let _ = app.storage.transactions.save();
                 app.selected_transaction = None;
                 app.set_status("Transaction deleted".to_string());
             }
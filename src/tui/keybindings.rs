@@ -55,6 +55,12 @@ pub static KEYBINDINGS: &[Keybinding] = &[
         description: "Command palette",
         context: KeyContext::Global,
     },
+    Keybinding {
+        key: KeyCode::Char('f'),
+        modifiers: KeyModifiers::CONTROL,
+        description: "Go to account, category, or payee",
+        context: KeyContext::Global,
+    },
     Keybinding {
         key: KeyCode::Tab,
         modifiers: KeyModifiers::NONE,
@@ -122,6 +128,24 @@ pub static KEYBINDINGS: &[Keybinding] = &[
         description: "Toggle archived",
         context: KeyContext::Sidebar,
     },
+    Keybinding {
+        key: KeyCode::Char('f'),
+        modifiers: KeyModifiers::NONE,
+        description: "Find account by name",
+        context: KeyContext::Sidebar,
+    },
+    Keybinding {
+        key: KeyCode::Char('K'),
+        modifiers: KeyModifiers::SHIFT,
+        description: "Move account up",
+        context: KeyContext::Sidebar,
+    },
+    Keybinding {
+        key: KeyCode::Char('J'),
+        modifiers: KeyModifiers::SHIFT,
+        description: "Move account down",
+        context: KeyContext::Sidebar,
+    },
     // Register
     Keybinding {
         key: KeyCode::Char('a'),
@@ -141,6 +165,12 @@ pub static KEYBINDINGS: &[Keybinding] = &[
         description: "Clear transaction",
         context: KeyContext::Register,
     },
+    Keybinding {
+        key: KeyCode::Char('f'),
+        modifiers: KeyModifiers::NONE,
+        description: "Cycle flag",
+        context: KeyContext::Register,
+    },
     Keybinding {
         key: KeyCode::Char('d'),
         modifiers: KeyModifiers::CONTROL,
@@ -153,6 +183,12 @@ pub static KEYBINDINGS: &[Keybinding] = &[
         description: "Multi-select mode",
         context: KeyContext::Register,
     },
+    Keybinding {
+        key: KeyCode::Char('r'),
+        modifiers: KeyModifiers::NONE,
+        description: "Start reconciliation",
+        context: KeyContext::Register,
+    },
     Keybinding {
         key: KeyCode::Char(' '),
         modifiers: KeyModifiers::NONE,
@@ -171,6 +207,18 @@ pub static KEYBINDINGS: &[Keybinding] = &[
         description: "Go to bottom",
         context: KeyContext::Register,
     },
+    Keybinding {
+        key: KeyCode::Char('s'),
+        modifiers: KeyModifiers::NONE,
+        description: "Cycle sort key",
+        context: KeyContext::Register,
+    },
+    Keybinding {
+        key: KeyCode::Char('S'),
+        modifiers: KeyModifiers::SHIFT,
+        description: "Reverse sort direction",
+        context: KeyContext::Register,
+    },
     // Budget
     Keybinding {
         key: KeyCode::Char('g'),
@@ -214,6 +262,30 @@ pub static KEYBINDINGS: &[Keybinding] = &[
         description: "Add category group",
         context: KeyContext::Budget,
     },
+    Keybinding {
+        key: KeyCode::Char('='),
+        modifiers: KeyModifiers::NONE,
+        description: "Edit budgeted inline",
+        context: KeyContext::Budget,
+    },
+    Keybinding {
+        key: KeyCode::Char('f'),
+        modifiers: KeyModifiers::NONE,
+        description: "Fund to target",
+        context: KeyContext::Budget,
+    },
+    Keybinding {
+        key: KeyCode::Char('z'),
+        modifiers: KeyModifiers::NONE,
+        description: "Collapse/expand group",
+        context: KeyContext::Budget,
+    },
+    Keybinding {
+        key: KeyCode::Char('Z'),
+        modifiers: KeyModifiers::SHIFT,
+        description: "Expand all groups",
+        context: KeyContext::Budget,
+    },
     // Dialog
     Keybinding {
         key: KeyCode::Esc,
@@ -271,3 +343,14 @@ pub fn format_keybinding(kb: &Keybinding) -> String {
     parts.push(&key_str);
     parts.join("+")
 }
+
+/// The key bound to an action, formatted for display
+///
+/// Looks the action up by its description so hints shown elsewhere stay in
+/// step with the keybinding table.
+pub fn key_for(context: KeyContext, description: &str) -> Option<String> {
+    KEYBINDINGS
+        .iter()
+        .find(|kb| kb.context == context && kb.description == description)
+        .map(format_keybinding)
+}
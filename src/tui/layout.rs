@@ -101,6 +101,8 @@ impl MainPanelLayout {
 
 /// Layout for the budget view
 pub struct BudgetLayout {
+    /// Overbudgeted warning banner (zero height when not shown)
+    pub banner: Rect,
     /// Available to Budget header
     pub atb_header: Rect,
     /// Category table
@@ -109,18 +111,20 @@ pub struct BudgetLayout {
 
 impl BudgetLayout {
     /// Calculate budget view layout
-    pub fn new(area: Rect) -> Self {
+    pub fn new(area: Rect, show_banner: bool) -> Self {
         let chunks = Layout::default()
             .direction(Direction::Vertical)
             .constraints([
-                Constraint::Length(3), // ATB header
-                Constraint::Min(3),    // Categories
+                Constraint::Length(u16::from(show_banner)), // Banner
+                Constraint::Length(3),                      // ATB header
+                Constraint::Min(3),                         // Categories
             ])
             .split(area);
 
         Self {
-            atb_header: chunks[0],
-            categories: chunks[1],
+            banner: chunks[0],
+            atb_header: chunks[1],
+            categories: chunks[2],
         }
     }
 }
@@ -95,8 +95,7 @@ pub fn run_tui(storage: &Storage, settings: &Settings, paths: &EnvelopePaths) ->
                 // Terminal will redraw automatically
             }
             Event::Tick => {
-                // Clear transient status messages after some time
-                // (could add a timestamp check here)
+                handle_event(&mut app, Event::Tick)?;
             }
         }
 
@@ -109,5 +108,11 @@ pub fn run_tui(storage: &Storage, settings: &Settings, paths: &EnvelopePaths) ->
     // Restore terminal
     restore_terminal()?;
 
+    // Reopen with the same sort and collapsed groups next time
+    app.save_view_state()?;
+
+    // Everything is already on disk, so the key isn't needed any more
+    storage.audit().lock();
+
     Ok(())
 }
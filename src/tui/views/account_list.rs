@@ -2,16 +2,24 @@
 //!
 //! Shows detailed account information
 
+use chrono::Duration;
 use ratatui::{
-    layout::Rect,
+    layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Cell, Row, Table, TableState},
+    symbols,
+    text::Span,
+    widgets::{Axis, Block, Borders, Cell, Chart, Dataset, GraphType, Row, Table, TableState},
     Frame,
 };
 
+use crate::config::clock;
+use crate::models::Account;
+use crate::services::account::BALANCE_HISTORY_DAYS;
 use crate::services::AccountService;
 use crate::tui::app::{App, FocusedPanel};
+use crate::tui::keybindings::{key_for, KeyContext};
 use crate::tui::layout::MainPanelLayout;
+use crate::tui::widgets::render_empty_state;
 
 /// Render the accounts view in the main panel
 pub fn render_main(frame: &mut Frame, app: &mut App, area: Rect) {
@@ -20,8 +28,23 @@ pub fn render_main(frame: &mut Frame, app: &mut App, area: Rect) {
     // Render header
     render_header(frame, layout.header);
 
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Min(5), Constraint::Length(10)])
+        .split(layout.content);
+
     // Render account table
-    render_account_table(frame, app, layout.content);
+    render_account_table(frame, app, chunks[0]);
+
+    // Render the selected account's recent balance
+    let selected = AccountService::new(app.storage)
+        .list(app.show_archived)
+        .unwrap_or_default()
+        .into_iter()
+        .nth(app.selected_account_index);
+    if let Some(account) = selected {
+        render_balance_chart(frame, app, &account, chunks[1]);
+    }
 }
 
 /// Render header
@@ -57,6 +80,21 @@ fn render_account_table(frame: &mut Frame, app: &mut App, area: Rect) {
         .list_with_balances(app.show_archived)
         .unwrap_or_default();
 
+    if accounts.is_empty() {
+        let (message, hint) = if app.storage.accounts.count().unwrap_or(0) == 0 {
+            let key = key_for(KeyContext::Sidebar, "Add account").unwrap_or_default();
+            ("No accounts yet", format!("Press '{}' to add one", key))
+        } else {
+            let key = key_for(KeyContext::Sidebar, "Toggle archived").unwrap_or_default();
+            (
+                "No active accounts",
+                format!("Press '{}' in the sidebar to show archived accounts", key),
+            )
+        };
+        render_empty_state(frame, area, block, message, &hint);
+        return;
+    }
+
     // Define column widths
     let widths = [
         ratatui::layout::Constraint::Length(20), // Name
@@ -98,8 +136,9 @@ fn render_account_table(frame: &mut Frame, app: &mut App, area: Rect) {
             Row::new(vec![
                 Cell::from(summary.account.name.clone()),
                 Cell::from(format!("{}", summary.account.account_type)),
-                Cell::from(format!("{}", summary.balance)).style(balance_style),
-                Cell::from(format!("{}", summary.cleared_balance)).style(cleared_style),
+                Cell::from(summary.account.format_amount(summary.balance)).style(balance_style),
+                Cell::from(summary.account.format_amount(summary.cleared_balance))
+                    .style(cleared_style),
                 Cell::from(format!("{}", summary.uncleared_count)),
                 Cell::from(if summary.account.on_budget {
                     "Yes"
@@ -125,3 +164,82 @@ fn render_account_table(frame: &mut Frame, app: &mut App, area: Rect) {
 
     frame.render_stateful_widget(table, area, &mut state);
 }
+
+/// Render a chart of an account's daily balance over the last 30 days
+fn render_balance_chart(frame: &mut Frame, app: &App, account: &Account, area: Rect) {
+    let block = Block::default()
+        .title(format!(
+            " {} - Last {} Days ",
+            account.name, BALANCE_HISTORY_DAYS
+        ))
+        .title_style(Style::default().fg(Color::Cyan))
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::White));
+
+    let today = clock::today();
+    let balances = AccountService::new(app.storage)
+        .daily_balances(
+            account.id,
+            today - Duration::days(BALANCE_HISTORY_DAYS - 1),
+            today,
+        )
+        .unwrap_or_default();
+
+    let points: Vec<(f64, f64)> = balances
+        .iter()
+        .enumerate()
+        .map(|(day, (_, balance))| (day as f64, balance.cents() as f64 / 100.0))
+        .collect();
+    let (Some(low), Some(high)) = (
+        balances.iter().map(|(_, b)| *b).min(),
+        balances.iter().map(|(_, b)| *b).max(),
+    ) else {
+        frame.render_widget(block, area);
+        return;
+    };
+
+    // Pad a flat line so it sits in the middle rather than on an edge
+    let padding = ((high.cents() - low.cents()) as f64 / 100.0 * 0.1).max(1.0);
+    let y_bounds = [
+        low.cents() as f64 / 100.0 - padding,
+        high.cents() as f64 / 100.0 + padding,
+    ];
+
+    let line_color = if balances.last().is_some_and(|(_, b)| b.is_negative()) {
+        Color::Red
+    } else {
+        Color::Green
+    };
+
+    let dataset = Dataset::default()
+        .marker(symbols::Marker::Braille)
+        .graph_type(GraphType::Line)
+        .style(Style::default().fg(line_color))
+        .data(&points);
+
+    let first_date = balances.first().map(|(d, _)| d.format("%m/%d").to_string());
+    let last_date = balances.last().map(|(d, _)| d.format("%m/%d").to_string());
+
+    let chart = Chart::new(vec![dataset])
+        .block(block)
+        .x_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds([0.0, (points.len().max(2) - 1) as f64])
+                .labels(vec![
+                    Span::raw(first_date.unwrap_or_default()),
+                    Span::raw(last_date.unwrap_or_default()),
+                ]),
+        )
+        .y_axis(
+            Axis::default()
+                .style(Style::default().fg(Color::DarkGray))
+                .bounds(y_bounds)
+                .labels(vec![
+                    Span::raw(low.to_string()),
+                    Span::raw(high.to_string()),
+                ]),
+        );
+
+    frame.render_widget(chart, area);
+}
@@ -2,41 +2,67 @@
 //!
 //! Shows budget categories with budgeted, activity, available, and target amounts
 
-use chrono::Datelike;
+use std::collections::HashMap;
+
 use ratatui::{
-    layout::Rect,
+    layout::{Alignment, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
     Frame,
 };
 
-use crate::models::{AccountType, BudgetPeriod, TargetCadence};
+use crate::display::report::format_bar;
+use crate::models::{AccountType, CategoryBudgetSummary, CategoryId, Money, TargetCadence};
 use crate::services::{AccountService, BudgetService, CategoryService};
 use crate::tui::app::{App, BudgetHeaderDisplay, FocusedPanel};
+use crate::tui::keybindings::{key_for, KeyContext};
 use crate::tui::layout::BudgetLayout;
+use crate::tui::widgets::{
+    follow_selection, render_empty_state, render_scrollbar, VERTICAL_BORDERS,
+};
 
 /// Render the budget view
-pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
-    let layout = BudgetLayout::new(area);
+///
+/// `atb` is Available to Budget for the current period.
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect, atb: Money) {
+    let layout = BudgetLayout::new(area, atb.is_negative());
+
+    if atb.is_negative() {
+        render_overbudgeted_banner(frame, atb, layout.banner);
+    }
 
     // Render ATB header
-    render_atb_header(frame, app, layout.atb_header);
+    render_atb_header(frame, app, layout.atb_header, atb);
 
     // Render category table
     render_category_table(frame, app, layout.categories);
 }
 
+/// Render a red warning across the top when more is budgeted than there is
+///
+/// Shown whichever balance the header is toggled to, so it can't be missed.
+fn render_overbudgeted_banner(frame: &mut Frame, atb: Money, area: Rect) {
+    let banner = Paragraph::new(format!(
+        " ⚠ Overbudgeted by {}: budget less or move money out of a category ",
+        atb.abs()
+    ))
+    .alignment(Alignment::Center)
+    .style(
+        Style::default()
+            .fg(Color::White)
+            .bg(Color::Red)
+            .add_modifier(Modifier::BOLD),
+    );
+    frame.render_widget(banner, area);
+}
+
 /// Render Available to Budget header (or account type balance based on toggle)
-fn render_atb_header(frame: &mut Frame, app: &mut App, area: Rect) {
+fn render_atb_header(frame: &mut Frame, app: &mut App, area: Rect, atb: Money) {
     let budget_service = BudgetService::new(app.storage);
 
     let (label, amount, color) = match app.budget_header_display {
         BudgetHeaderDisplay::AvailableToBudget => {
-            let atb = budget_service
-                .get_available_to_budget(&app.current_period)
-                .unwrap_or_default();
-
             let color = if atb.is_negative() {
                 Color::Red
             } else if atb.is_zero() {
@@ -177,6 +203,29 @@ fn render_category_table(frame: &mut Frame, app: &mut App, area: Rect) {
     let groups = category_service.list_groups().unwrap_or_default();
     let categories = category_service.list_categories().unwrap_or_default();
 
+    if categories.is_empty() {
+        let add_category = key_for(KeyContext::Budget, "Add category").unwrap_or_default();
+        let hint = if groups.is_empty() {
+            let add_group = key_for(KeyContext::Budget, "Add category group").unwrap_or_default();
+            format!(
+                "Press '{}' to add a category group, then '{}' to add a category",
+                add_group, add_category
+            )
+        } else {
+            format!("Press '{}' to add one", add_category)
+        };
+        render_empty_state(frame, area, block, "No categories yet", &hint);
+        return;
+    }
+
+    // Summaries for every category in one pass over the transactions
+    let mut summaries: HashMap<CategoryId, CategoryBudgetSummary> = budget_service
+        .get_category_summaries(&app.current_period)
+        .unwrap_or_default()
+        .into_iter()
+        .map(|s| (s.category_id, s))
+        .collect();
+
     // Build rows with group headers
     let mut rows: Vec<Row> = Vec::new();
     let mut row_to_category_index: Vec<Option<usize>> = Vec::new();
@@ -185,9 +234,21 @@ fn render_category_table(frame: &mut Frame, app: &mut App, area: Rect) {
     let mut visual_index = 0usize;
 
     for group in &groups {
-        // Group header row
+        // Categories in this group
+        let group_categories: Vec<_> = categories
+            .iter()
+            .filter(|c| c.group_id == group.id && (app.show_archived || !c.archived))
+            .collect();
+        let collapsed = app.collapsed_groups.contains(&group.id);
+
+        // Group header row, with a count when collapsed
+        let header = if collapsed {
+            format!("▶ {} ({})", group.name, group_categories.len())
+        } else {
+            format!("▼ {}", group.name)
+        };
         rows.push(
-            Row::new(vec![Cell::from(format!("▼ {}", group.name))])
+            Row::new(vec![Cell::from(header)])
                 .style(
                     Style::default()
                         .fg(Color::Cyan)
@@ -197,18 +258,16 @@ fn render_category_table(frame: &mut Frame, app: &mut App, area: Rect) {
         );
         row_to_category_index.push(None);
 
-        // Categories in this group
-        let group_categories: Vec<_> = categories
-            .iter()
-            .filter(|c| c.group_id == group.id)
-            .collect();
+        if collapsed {
+            continue;
+        }
 
         for category in group_categories {
             let cat_index = visual_index;
             visual_index += 1;
-            let summary = budget_service
-                .get_category_summary(category.id, &app.current_period)
-                .unwrap_or_else(|_| crate::models::CategoryBudgetSummary::empty(category.id));
+            let summary = summaries
+                .remove(&category.id)
+                .unwrap_or_else(|| CategoryBudgetSummary::empty(category.id));
 
             // Get target for this category
             let target = budget_service.get_target(category.id).ok().flatten();
@@ -221,65 +280,38 @@ fn render_category_table(frame: &mut Frame, app: &mut App, area: Rect) {
                 Some(t) => {
                     match &t.cadence {
                         TargetCadence::ByDate { target_date } => {
-                            // For ByDate goals: paid is the source of truth, budgeted is fallback
-                            let target_period =
-                                BudgetPeriod::monthly(target_date.year(), target_date.month());
-                            let cumulative_paid = budget_service
-                                .calculate_cumulative_paid(category.id, &target_period)
-                                .unwrap_or_default();
-                            let cumulative_budgeted = budget_service
-                                .calculate_cumulative_budgeted(category.id, &target_period)
-                                .unwrap_or_default();
-
-                            // Use paid amount; fall back to budgeted only if no payments yet
-                            let progress_amount = if cumulative_paid.cents() > 0 {
-                                cumulative_paid.cents()
-                            } else {
-                                cumulative_budgeted.cents().max(0)
-                            };
-
-                            // Preview: what progress would be if all budgeted amount is paid
-                            // Only count unpaid budgeted (avoid double-counting already paid amounts)
-                            let unpaid_budgeted =
-                                (cumulative_budgeted.cents() - cumulative_paid.cents()).max(0);
-                            let preview_amount = cumulative_paid.cents() + unpaid_budgeted;
-
-                            let target_cents = t.amount.cents();
-                            let (progress_pct, preview_pct) = if target_cents > 0 {
-                                let progress = ((progress_amount as f64 / target_cents as f64)
-                                    * 100.0)
-                                    .min(100.0);
-                                let preview =
-                                    ((preview_amount.max(0) as f64 / target_cents as f64) * 100.0)
-                                        .min(100.0);
-                                (progress, preview)
+                            // Progress is what's set aside so far: the cumulative available
+                            let progress = t.progress(&app.current_period, summary.available);
+                            let due = if t.is_overdue(&app.current_period) {
+                                Span::styled(" overdue", Style::default().fg(Color::Red))
                             } else {
-                                (0.0, 0.0)
+                                match t.months_remaining(&app.current_period) {
+                                    Some(0) | None => {
+                                        Span::styled(" due", Style::default().fg(Color::Yellow))
+                                    }
+                                    Some(months) => Span::styled(
+                                        format!(" {}mo", months),
+                                        Style::default().fg(Color::White),
+                                    ),
+                                }
                             };
 
-                            let base_text = format!(
-                                "{} by {} ({:.0}%",
-                                t.amount,
-                                target_date.format("%b %Y"),
-                                progress_pct
-                            );
-
-                            // Show preview only if it differs from current progress
-                            if (preview_pct - progress_pct).abs() > 0.5 {
-                                Line::from(vec![
-                                    Span::styled(base_text, Style::default().fg(Color::Magenta)),
-                                    Span::styled(
-                                        format!(" → {:.0}%", preview_pct),
-                                        Style::default().fg(Color::White),
+                            Line::from(vec![
+                                Span::styled(
+                                    format_bar(progress, 1.0, 8),
+                                    Style::default().fg(Color::Magenta),
+                                ),
+                                Span::styled(
+                                    format!(
+                                        " {:>3.0}% of {} by {}",
+                                        progress * 100.0,
+                                        t.amount,
+                                        target_date.format("%b %Y")
                                     ),
-                                    Span::styled(")", Style::default().fg(Color::Magenta)),
-                                ])
-                            } else {
-                                Line::from(vec![Span::styled(
-                                    format!("{})", base_text),
                                     Style::default().fg(Color::Magenta),
-                                )])
-                            }
+                                ),
+                                due,
+                            ])
                         }
                         _ => Line::from(Span::styled(
                             format!("{} {}", t.amount, t.cadence),
@@ -308,32 +340,41 @@ fn render_category_table(frame: &mut Frame, app: &mut App, area: Rect) {
                 Style::default().fg(Color::Yellow)
             };
 
-            rows.push(Row::new(vec![
+            // The budgeted cell turns into a text field while edited inline
+            let budgeted_cell = match &app.inline_budget_edit {
+                Some(edit) if edit.category_id == category.id => Cell::from(edit.input.to_line()),
+                _ => Cell::from(format!("{}", summary.budgeted)),
+            };
+
+            let row = Row::new(vec![
                 Cell::from(format!("{}{}", target_indicator, category.name)),
-                Cell::from(format!("{}", summary.budgeted)),
+                budgeted_cell,
                 Cell::from(format!("{}", summary.activity)).style(activity_style),
                 Cell::from(format!("{}", summary.available)).style(available_style),
                 Cell::from(target_display),
-            ]));
+            ]);
+
+            // Archived categories only show up when revealed, and dimmed
+            rows.push(if category.archived {
+                row.style(
+                    Style::default()
+                        .fg(Color::DarkGray)
+                        .add_modifier(Modifier::DIM),
+                )
+            } else {
+                row
+            });
             row_to_category_index.push(Some(cat_index));
         }
     }
 
-    if rows.is_empty() {
-        let text = Paragraph::new("No categories. Run 'envelope category create' to add some.")
-            .block(block)
-            .style(Style::default().fg(Color::Yellow));
-        frame.render_widget(text, area);
-        return;
-    }
-
     // Column widths
     let widths = [
         ratatui::layout::Constraint::Min(20), // Category name (with target indicator)
         ratatui::layout::Constraint::Length(14), // Budgeted
         ratatui::layout::Constraint::Length(14), // Activity
         ratatui::layout::Constraint::Length(14), // Available
-        ratatui::layout::Constraint::Length(30), // Target (wider for ByDate progress)
+        ratatui::layout::Constraint::Length(44), // Target (wider for ByDate progress)
     ];
 
     // Header row
@@ -347,6 +388,7 @@ fn render_category_table(frame: &mut Frame, app: &mut App, area: Rect) {
     .style(Style::default().fg(Color::Yellow))
     .height(1);
 
+    let total_rows = rows.len();
     let table = Table::new(rows, widths)
         .header(header)
         .block(block)
@@ -363,8 +405,19 @@ fn render_category_table(frame: &mut Frame, app: &mut App, area: Rect) {
         .position(|&idx| idx == Some(app.selected_category_index))
         .unwrap_or(0);
 
-    let mut state = TableState::default();
+    // Borders and header take three rows
+    let visible = area.height.saturating_sub(3) as usize;
+    app.scroll_offset = follow_selection(app.scroll_offset, selected_row, visible, total_rows);
+
+    let mut state = TableState::default().with_offset(app.scroll_offset);
     state.select(Some(selected_row));
 
     frame.render_stateful_widget(table, area, &mut state);
+    render_scrollbar(
+        frame,
+        area.inner(VERTICAL_BORDERS),
+        selected_row,
+        total_rows,
+        visible,
+    );
 }
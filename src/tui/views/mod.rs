@@ -7,19 +7,34 @@ pub mod account_list;
 pub mod budget;
 pub mod reconcile;
 pub mod register;
+pub mod reports;
 pub mod sidebar;
 pub mod status_bar;
 
-use ratatui::Frame;
+use ratatui::{layout::Rect, Frame};
+
+use crate::config::clock;
+use crate::services::BudgetService;
 
 use super::app::{ActiveDialog, ActiveView, App};
 use super::dialogs;
 use super::layout::AppLayout;
+use super::widgets::NotificationWidget;
+
+/// Most toasts stacked on screen at once
+const MAX_TOASTS: usize = 3;
 
 /// Render the entire application
 pub fn render(frame: &mut Frame, app: &mut App) {
     let layout = AppLayout::new(frame.area());
 
+    // Figures shared across widgets are worked out once per frame
+    let budget_service = BudgetService::new(app.storage);
+    let atb = budget_service
+        .get_available_to_budget(&app.current_period)
+        .unwrap_or_default();
+    let age_of_money = budget_service.age_of_money(clock::today()).ok().flatten();
+
     // Render sidebar
     sidebar::render(frame, app, layout.sidebar);
 
@@ -32,11 +47,10 @@ pub fn render(frame: &mut Frame, app: &mut App) {
             register::render(frame, app, layout.main);
         }
         ActiveView::Budget => {
-            budget::render(frame, app, layout.main);
+            budget::render(frame, app, layout.main, atb);
         }
         ActiveView::Reports => {
-            // Reports view placeholder
-            render_placeholder(frame, layout.main, "Reports");
+            reports::render(frame, app, layout.main);
         }
         ActiveView::Reconcile => {
             reconcile::render(frame, app, layout.main);
@@ -44,12 +58,47 @@ pub fn render(frame: &mut Frame, app: &mut App) {
     }
 
     // Render status bar
-    status_bar::render(frame, app, layout.status_bar);
+    status_bar::render(frame, app, layout.status_bar, atb, age_of_money);
 
     // Render dialog if active
     if app.has_dialog() {
         render_dialog(frame, app);
     }
+
+    // Toasts go on top of everything else
+    render_notifications(frame, app);
+}
+
+/// Render recent toast notifications stacked in the top right corner
+///
+/// The newest toast is on top; older ones fade and drop off as they expire.
+fn render_notifications(frame: &mut Frame, app: &App) {
+    let screen = frame.area();
+    let mut y = screen.y + 1;
+
+    for notification in app.notifications.recent(MAX_TOASTS) {
+        let width = (notification.message.chars().count() as u16 + 4)
+            .min(60)
+            .min(screen.width);
+        // Long messages wrap onto a second line
+        let height = if notification.message.chars().count() as u16 + 4 > width {
+            4
+        } else {
+            3
+        };
+        if y + height > screen.y + screen.height {
+            break;
+        }
+
+        let area = Rect::new(
+            screen.x + screen.width.saturating_sub(width + 1),
+            y,
+            width,
+            height,
+        );
+        frame.render_widget(NotificationWidget::new(notification), area);
+        y += height;
+    }
 }
 
 /// Render active dialog
@@ -61,6 +110,9 @@ fn render_dialog(frame: &mut Frame, app: &mut App) {
         ActiveDialog::CommandPalette => {
             dialogs::command_palette::render(frame, app);
         }
+        ActiveDialog::GoTo => {
+            dialogs::go_to::render(frame, app);
+        }
         ActiveDialog::Confirm(message) => {
             dialogs::confirm::render(frame, message);
         }
@@ -94,26 +146,12 @@ fn render_dialog(frame: &mut Frame, app: &mut App) {
         ActiveDialog::AddCategory | ActiveDialog::EditCategory(_) => {
             dialogs::category::render(frame, app);
         }
+        ActiveDialog::MoveCategory(_) => {
+            dialogs::move_category::render(frame, app);
+        }
         ActiveDialog::AddGroup | ActiveDialog::EditGroup(_) => {
             dialogs::group::render(frame, app);
         }
         ActiveDialog::None => {}
     }
 }
-
-/// Render a placeholder for unimplemented views
-fn render_placeholder(frame: &mut Frame, area: ratatui::layout::Rect, name: &str) {
-    use ratatui::style::{Color, Style};
-    use ratatui::widgets::{Block, Borders, Paragraph};
-
-    let block = Block::default()
-        .title(name)
-        .borders(Borders::ALL)
-        .border_style(Style::default().fg(Color::White));
-
-    let text = Paragraph::new(format!("{} view - Coming soon!", name))
-        .block(block)
-        .style(Style::default().fg(Color::White));
-
-    frame.render_widget(text, area);
-}
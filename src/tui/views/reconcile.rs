@@ -3,17 +3,21 @@
 //! Displays the reconciliation workflow interface showing statement entry,
 //! transaction list, and difference display.
 
+use chrono::NaiveDate;
 use ratatui::{
-    layout::{Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
     widgets::{Block, Borders, Paragraph, Row, Table},
     Frame,
 };
 
+use crate::config::clock;
 use crate::models::{AccountId, Money, Transaction, TransactionId, TransactionStatus};
-use crate::services::ReconciliationService;
-use crate::tui::app::App;
+use crate::services::{ReconciliationService, ReconciliationSession};
+use crate::storage::Storage;
+use crate::tui::app::{ActiveDialog, App};
+use crate::tui::dialogs::adjustment::AdjustmentDialogState;
 
 /// State for the reconciliation view
 #[derive(Debug, Clone, Default)]
@@ -44,7 +48,7 @@ impl ReconciliationState {
     pub fn new() -> Self {
         Self {
             account_id: None,
-            statement_date: chrono::Local::now().format("%Y-%m-%d").to_string(),
+            statement_date: clock::today().format("%Y-%m-%d").to_string(),
             statement_balance: String::new(),
             parsed_balance: None,
             in_transaction_phase: false,
@@ -103,6 +107,50 @@ impl ReconciliationState {
         self.starting_balance + cleared_total
     }
 
+    /// The session described by the entered statement, once a balance is set
+    pub fn session(&self) -> Option<ReconciliationSession> {
+        Some(ReconciliationSession {
+            account_id: self.account_id?,
+            statement_date: NaiveDate::parse_from_str(&self.statement_date, "%Y-%m-%d")
+                .unwrap_or_else(|_| clock::today()),
+            statement_balance: self.parsed_balance?,
+            starting_cleared_balance: self.starting_balance,
+        })
+    }
+
+    /// Enter the transaction phase for `balance`, loading the account's
+    /// uncleared transactions and its reconciled starting balance
+    pub fn load(&mut self, storage: &Storage, balance: Money) {
+        self.parsed_balance = Some(balance);
+        self.in_transaction_phase = true;
+
+        let Some(account_id) = self.account_id else {
+            return;
+        };
+
+        let service = ReconciliationService::new(storage);
+        if let Ok(transactions) = service.get_uncleared_transactions(account_id) {
+            self.transactions = transactions;
+        }
+
+        // Calculate starting balance
+        if let Ok(Some(account)) = storage.accounts.get(account_id) {
+            let account_txns = storage
+                .transactions
+                .get_by_account(account_id)
+                .unwrap_or_default();
+            let reconciled_total: Money = account_txns
+                .iter()
+                .filter(|t| t.status == TransactionStatus::Reconciled)
+                .map(|t| t.amount)
+                .sum();
+            self.starting_balance =
+                account.untracked_starting_balance(&account_txns) + reconciled_total;
+        }
+
+        self.update_difference();
+    }
+
     /// Update difference calculation
     pub fn update_difference(&mut self) {
         if let Some(statement_balance) = self.parsed_balance {
@@ -121,14 +169,16 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(5), // Header with statement info
+            Constraint::Length(4), // Statement, cleared, and difference totals
             Constraint::Min(10),   // Transaction list
-            Constraint::Length(4), // Summary/status bar
+            Constraint::Length(3), // Summary/status bar
         ])
         .split(area);
 
     render_header(frame, app, chunks[0]);
-    render_transactions(frame, app, chunks[1]);
-    render_summary(frame, app, chunks[2]);
+    render_balances(frame, app, chunks[1]);
+    render_transactions(frame, app, chunks[2]);
+    render_summary(frame, app, chunks[3]);
 }
 
 /// Render the header with statement info
@@ -286,6 +336,91 @@ fn render_transactions(frame: &mut Frame, app: &App, area: Rect) {
     frame.render_widget(table, area);
 }
 
+/// Render the statement, cleared, and difference totals
+///
+/// Recomputed from the stored transactions through
+/// [`ReconciliationService::get_summary`] on every frame, so clearing a
+/// transaction shows up here right away.
+fn render_balances(frame: &mut Frame, app: &App, area: Rect) {
+    let state = &app.reconciliation_state;
+
+    let summary = state.session().and_then(|session| {
+        ReconciliationService::new(app.storage)
+            .get_summary(&session)
+            .ok()
+    });
+
+    let Some(summary) = summary else {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::White));
+        let hint = Paragraph::new("Enter the statement balance to start reconciling")
+            .block(block)
+            .style(Style::default().fg(Color::DarkGray))
+            .alignment(Alignment::Center);
+        frame.render_widget(hint, area);
+        return;
+    };
+
+    let (status, status_color) = if summary.can_complete {
+        (" Balanced ".to_string(), Color::Green)
+    } else {
+        (format!(" Off by {} ", summary.difference.abs()), Color::Red)
+    };
+
+    let block = Block::default()
+        .title(status)
+        .title_style(
+            Style::default()
+                .fg(status_color)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(status_color));
+
+    let inner = block.inner(area);
+    frame.render_widget(block, area);
+
+    let columns = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage(33),
+            Constraint::Percentage(34),
+            Constraint::Percentage(33),
+        ])
+        .split(inner);
+
+    let balances = [
+        (
+            "Statement",
+            summary.session.statement_balance,
+            Style::default().fg(Color::White),
+        ),
+        (
+            "Cleared",
+            summary.current_cleared_balance,
+            Style::default().fg(Color::White),
+        ),
+        (
+            "Difference",
+            summary.difference,
+            Style::default().fg(status_color),
+        ),
+    ];
+
+    for ((label, amount, style), column) in balances.into_iter().zip(columns.iter()) {
+        let text = Paragraph::new(vec![
+            Line::from(Span::styled(label, Style::default().fg(Color::Yellow))),
+            Line::from(Span::styled(
+                amount.to_string(),
+                style.add_modifier(Modifier::BOLD),
+            )),
+        ])
+        .alignment(Alignment::Center);
+        frame.render_widget(text, *column);
+    }
+}
+
 /// Render the summary bar
 fn render_summary(frame: &mut Frame, app: &App, area: Rect) {
     let state = &app.reconciliation_state;
@@ -297,16 +432,6 @@ fn render_summary(frame: &mut Frame, app: &App, area: Rect) {
     let inner = block.inner(area);
     frame.render_widget(block, area);
 
-    let cleared_balance = state.calculate_cleared_balance();
-
-    let difference_style = if state.difference.is_zero() {
-        Style::default()
-            .fg(Color::Green)
-            .add_modifier(Modifier::BOLD)
-    } else {
-        Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
-    };
-
     let cleared_count = state
         .transactions
         .iter()
@@ -319,33 +444,6 @@ fn render_summary(frame: &mut Frame, app: &App, area: Rect) {
         .filter(|t| t.status == TransactionStatus::Pending)
         .count();
 
-    let summary_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([
-            Constraint::Percentage(33),
-            Constraint::Percentage(34),
-            Constraint::Percentage(33),
-        ])
-        .split(inner);
-
-    // Cleared balance
-    let cleared_text = Paragraph::new(vec![Line::from(vec![
-        Span::styled("Cleared: ", Style::default().fg(Color::Yellow)),
-        Span::styled(
-            format!("{}", cleared_balance),
-            Style::default().fg(Color::White),
-        ),
-    ])]);
-    frame.render_widget(cleared_text, summary_chunks[0]);
-
-    // Difference
-    let diff_text = Paragraph::new(vec![Line::from(vec![
-        Span::styled("Difference: ", Style::default().fg(Color::Yellow)),
-        Span::styled(format!("{}", state.difference), difference_style),
-    ])]);
-    frame.render_widget(diff_text, summary_chunks[1]);
-
-    // Transaction counts
     let count_text = Paragraph::new(vec![Line::from(vec![
         Span::styled(
             format!("{} cleared  ", cleared_count),
@@ -356,7 +454,7 @@ fn render_summary(frame: &mut Frame, app: &App, area: Rect) {
             Style::default().fg(Color::Yellow),
         ),
     ])]);
-    frame.render_widget(count_text, summary_chunks[2]);
+    frame.render_widget(count_text, inner);
 }
 
 /// Handle key input for reconciliation view
@@ -405,37 +503,72 @@ pub fn handle_key(app: &mut App, key: crossterm::event::KeyCode) -> bool {
             }
             true
         }
+        KeyCode::Char('a') if state.in_transaction_phase => {
+            // Absorb a small fee or rounding difference into the selected transaction
+            let (Some(session), Some(txn_id)) = (state.session(), state.selected_transaction())
+            else {
+                return true;
+            };
+
+            let service = ReconciliationService::new(app.storage);
+            match service.adjust_to_match(&session, txn_id, app.settings.reconcile_max_adjustment) {
+                Ok(updated_txn) => {
+                    let message = format!(
+                        "Adjusted {} to {} to match the statement",
+                        updated_txn.payee_name, updated_txn.amount
+                    );
+                    if let Some(t) = state.transactions.iter_mut().find(|t| t.id == txn_id) {
+                        *t = updated_txn;
+                    }
+                    state.update_difference();
+                    app.set_status(message);
+                }
+                Err(e) => app.set_status(e.to_string()),
+            }
+            true
+        }
+        KeyCode::Enter if state.in_transaction_phase => {
+            // Finish: mark every cleared transaction reconciled in one step
+            let Some(session) = state.session() else {
+                return true;
+            };
+
+            // A leftover difference is settled through an adjustment transaction
+            if !state.difference.is_zero() {
+                let mut dialog = AdjustmentDialogState::new(state.difference);
+                dialog.load_categories(
+                    app.storage
+                        .categories
+                        .get_all_categories()
+                        .unwrap_or_default()
+                        .into_iter()
+                        .map(|c| (c.id, c.name))
+                        .collect(),
+                );
+                app.adjustment_dialog_state = dialog;
+                app.open_dialog(ActiveDialog::Adjustment);
+                return true;
+            }
+
+            let service = ReconciliationService::new(app.storage);
+            match service.complete(&session) {
+                Ok(result) => {
+                    state.init_for_account(session.account_id);
+                    app.set_status(format!(
+                        "Reconciled {} transaction(s) at {}",
+                        result.transactions_reconciled, session.statement_balance
+                    ));
+                }
+                Err(e) => app.set_status(e.to_string()),
+            }
+            true
+        }
         KeyCode::Enter if !state.in_transaction_phase => {
             // Move to transaction phase
             if state.active_field == 1 {
                 // Try to parse the balance
                 if let Ok(balance) = Money::parse(&state.statement_balance) {
-                    state.parsed_balance = Some(balance);
-                    state.in_transaction_phase = true;
-
-                    // Load transactions
-                    if let Some(account_id) = state.account_id {
-                        let service = ReconciliationService::new(app.storage);
-                        if let Ok(transactions) = service.get_uncleared_transactions(account_id) {
-                            state.transactions = transactions;
-                        }
-
-                        // Calculate starting balance
-                        if let Ok(Some(account)) = app.storage.accounts.get(account_id) {
-                            let reconciled_total: Money = app
-                                .storage
-                                .transactions
-                                .get_by_account(account_id)
-                                .unwrap_or_default()
-                                .iter()
-                                .filter(|t| t.status == TransactionStatus::Reconciled)
-                                .map(|t| t.amount)
-                                .sum();
-                            state.starting_balance = account.starting_balance + reconciled_total;
-                        }
-
-                        state.update_difference();
-                    }
+                    state.load(app.storage, balance);
                 } else {
                     app.set_status("Invalid balance format. Use format like 1234.56");
                 }
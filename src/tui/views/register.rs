@@ -5,13 +5,18 @@
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
+    text::{Line, Span},
     widgets::{Block, Borders, Cell, Paragraph, Row, Table, TableState},
     Frame,
 };
 
-use crate::models::TransactionStatus;
+use crate::models::{TransactionFlag, TransactionStatus};
 use crate::tui::app::{App, FocusedPanel};
+use crate::tui::keybindings::{key_for, KeyContext};
 use crate::tui::layout::MainPanelLayout;
+use crate::tui::widgets::{
+    follow_selection, render_empty_state, render_scrollbar, VERTICAL_BORDERS,
+};
 
 /// Render the transaction register
 pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
@@ -35,7 +40,7 @@ fn render_header(frame: &mut Frame, app: &mut App, area: Rect) {
             .map(|a| a.name.clone())
             .unwrap_or_else(|| "Unknown".to_string())
     } else {
-        "No Account Selected".to_string()
+        "All Accounts".to_string()
     };
 
     let title = format!(" {} - Transactions ", account_name);
@@ -52,7 +57,7 @@ fn render_header(frame: &mut Frame, app: &mut App, area: Rect) {
     let hints = if app.multi_select_mode {
         "Multi-select: SPACE to select, C to categorize, D to delete, v to exit"
     } else {
-        "a:Add  e:Edit  c:Clear  v:Multi-select"
+        "a:Add  e:Edit  c:Clear  f:Flag  v:Multi-select"
     };
 
     let paragraph = Paragraph::new(hints)
@@ -71,49 +76,72 @@ fn render_transaction_table(frame: &mut Frame, app: &mut App, area: Rect) {
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color));
 
-    // Get transactions for selected account
-    let transactions = if let Some(account_id) = app.selected_account {
-        let mut txns = app
-            .storage
-            .transactions
-            .get_by_account(account_id)
-            .unwrap_or_default();
-        // Sort by date descending
-        txns.sort_by(|a, b| b.date.cmp(&a.date));
-        txns
-    } else {
-        Vec::new()
-    };
+    // Get transactions for selected account (or every account), date descending
+    let transactions = app.register_transactions();
+    let show_account = app.selected_account.is_none();
 
     if transactions.is_empty() {
-        let text = Paragraph::new("No transactions. Press 'a' to add one.")
-            .block(block)
-            .style(Style::default().fg(Color::Yellow));
-        frame.render_widget(text, area);
+        let (message, hint) = if app.storage.accounts.count().unwrap_or(0) == 0 {
+            let key = key_for(KeyContext::Sidebar, "Add account").unwrap_or_default();
+            (
+                "No accounts yet",
+                format!("Add an account first: press '{}' in the sidebar", key),
+            )
+        } else {
+            let key = key_for(KeyContext::Register, "Add transaction").unwrap_or_default();
+            let message = if app.storage.transactions.count().unwrap_or(0) == 0 {
+                "No transactions yet"
+            } else {
+                "No transactions in this account"
+            };
+            (message, format!("Press '{}' to add one", key))
+        };
+        render_empty_state(frame, area, block, message, &hint);
         return;
     }
 
     // Define column widths
-    let widths = [
+    let mut widths = vec![
         ratatui::layout::Constraint::Length(2),  // Status
+        ratatui::layout::Constraint::Length(1),  // Flag
         ratatui::layout::Constraint::Length(12), // Date
+    ];
+    if show_account {
+        widths.push(ratatui::layout::Constraint::Length(15)); // Account
+    }
+    widths.extend([
         ratatui::layout::Constraint::Length(20), // Payee
         ratatui::layout::Constraint::Length(15), // Category
         ratatui::layout::Constraint::Length(12), // Amount
         ratatui::layout::Constraint::Min(10),    // Memo
-    ];
+    ]);
 
     // Header row
-    let header = Row::new(vec![
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let mut header_cells = vec![
+        Cell::from(""),
         Cell::from(""),
-        Cell::from("Date").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Payee").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Category").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Amount").style(Style::default().add_modifier(Modifier::BOLD)),
-        Cell::from("Memo").style(Style::default().add_modifier(Modifier::BOLD)),
-    ])
-    .style(Style::default().fg(Color::Yellow))
-    .height(1);
+        Cell::from("Date").style(bold),
+    ];
+    if show_account {
+        header_cells.push(Cell::from("Account").style(bold));
+    }
+    header_cells.extend([
+        Cell::from("Payee").style(bold),
+        Cell::from("Category").style(bold),
+        Cell::from("Amount").style(bold),
+        Cell::from("Memo").style(bold),
+    ]);
+    let header = Row::new(header_cells)
+        .style(Style::default().fg(Color::Yellow))
+        .height(1);
+
+    // Get account names for the account column
+    let accounts = if show_account {
+        app.storage.accounts.get_all().unwrap_or_default()
+    } else {
+        Vec::new()
+    };
 
     // Get categories for lookup
     let categories = app
@@ -122,6 +150,47 @@ fn render_transaction_table(frame: &mut Frame, app: &mut App, area: Rect) {
         .get_all_categories()
         .unwrap_or_default();
 
+    // Upcoming scheduled items sit above the transactions, greyed out
+    let upcoming = app.register_upcoming();
+    let upcoming_style = Style::default()
+        .fg(Color::DarkGray)
+        .add_modifier(Modifier::ITALIC);
+    let upcoming_rows: Vec<Row> = upcoming
+        .iter()
+        .map(|scheduled| {
+            let category_name = scheduled
+                .category_id
+                .and_then(|cat_id| categories.iter().find(|c| c.id == cat_id))
+                .map(|c| c.name.clone())
+                .unwrap_or_else(|| "-".to_string());
+
+            let mut cells = vec![
+                Cell::from("⏲"),
+                Cell::from(""),
+                Cell::from(scheduled.next_date.format("%Y-%m-%d").to_string()),
+            ];
+            if show_account {
+                let account_name = accounts
+                    .iter()
+                    .find(|a| a.id == scheduled.account_id)
+                    .map(|a| a.name.as_str())
+                    .unwrap_or("Unknown");
+                cells.push(Cell::from(truncate_string(account_name, 15)));
+            }
+            cells.extend([
+                Cell::from(truncate_string(&scheduled.payee_name, 20)),
+                Cell::from(truncate_string(&category_name, 15)),
+                Cell::from(format!("{}", scheduled.amount)),
+                Cell::from(truncate_string(
+                    &format!("Scheduled: {}", scheduled.cadence),
+                    30,
+                )),
+            ]);
+
+            Row::new(cells).style(upcoming_style)
+        })
+        .collect();
+
     // Data rows
     let rows: Vec<Row> = transactions
         .iter()
@@ -155,6 +224,8 @@ fn render_transaction_table(frame: &mut Frame, app: &mut App, area: Rect) {
                 "Split".to_string()
             } else if txn.is_transfer() {
                 "Transfer".to_string()
+            } else if txn.is_starting_balance {
+                "Available to Budget".to_string()
             } else if let Some(cat_id) = txn.category_id {
                 categories
                     .iter()
@@ -172,17 +243,34 @@ fn render_transaction_table(frame: &mut Frame, app: &mut App, area: Rect) {
                 Style::default().fg(Color::Green)
             };
 
-            Row::new(vec![
+            let mut cells = vec![
                 Cell::from(format!("{}{}", select_indicator, status_indicator))
                     .style(Style::default().fg(status_color)),
+                Cell::from(txn.flag.marker()).style(Style::default().fg(match txn.flag {
+                    TransactionFlag::Review => Color::Magenta,
+                    _ => Color::Yellow,
+                })),
                 Cell::from(txn.date.format("%Y-%m-%d").to_string()),
+            ];
+            if show_account {
+                let account_name = accounts
+                    .iter()
+                    .find(|a| a.id == txn.account_id)
+                    .map(|a| a.name.as_str())
+                    .unwrap_or("Unknown");
+                cells.push(Cell::from(truncate_string(account_name, 15)));
+            }
+            cells.extend([
                 Cell::from(truncate_string(&txn.payee_name, 20)),
                 Cell::from(truncate_string(&category_name, 15)),
                 Cell::from(format!("{}", txn.amount)).style(amount_style),
-                Cell::from(truncate_string(&txn.memo, 30)),
-            ])
+                memo_cell(&txn.memo, &txn.tags),
+            ]);
+
+            Row::new(cells)
         })
         .collect();
+    let rows: Vec<Row> = upcoming_rows.into_iter().chain(rows).collect();
 
     let table = Table::new(rows, widths)
         .header(header)
@@ -194,10 +282,37 @@ fn render_transaction_table(frame: &mut Frame, app: &mut App, area: Rect) {
         )
         .highlight_symbol("▶ ");
 
-    let mut state = TableState::default();
-    state.select(Some(app.selected_transaction_index));
+    // Borders and header take three rows; the selection skips upcoming rows
+    let selected_row = upcoming.len() + app.selected_transaction_index;
+    let total = upcoming.len() + transactions.len();
+    let visible = area.height.saturating_sub(3) as usize;
+    app.scroll_offset = follow_selection(app.scroll_offset, selected_row, visible, total);
+
+    let mut state = TableState::default().with_offset(app.scroll_offset);
+    state.select(Some(selected_row));
 
     frame.render_stateful_widget(table, area, &mut state);
+    render_scrollbar(
+        frame,
+        area.inner(VERTICAL_BORDERS),
+        selected_row,
+        total,
+        visible,
+    );
+}
+
+/// The memo followed by the transaction's tags, as `#tag` in cyan
+fn memo_cell<'a>(memo: &str, tags: &[String]) -> Cell<'a> {
+    let mut spans = vec![Span::raw(truncate_string(memo, 30))];
+    if !tags.is_empty() {
+        let tags: Vec<String> = tags.iter().map(|t| format!("#{}", t)).collect();
+        let separator = if memo.is_empty() { "" } else { " " };
+        spans.push(Span::styled(
+            format!("{}{}", separator, tags.join(" ")),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+    Cell::from(Line::from(spans))
 }
 
 /// Truncate a string to a maximum length
@@ -0,0 +1,267 @@
+//! Reports view
+//!
+//! Shows a spending breakdown by category for the current budget period, or
+//! a comparison of the current period with the one before it
+
+use ratatui::{
+    layout::{Constraint, Rect},
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, Borders, Cell, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::models::Money;
+use crate::reports::{PeriodComparisonReport, SpendingReport};
+use crate::tui::app::{App, FocusedPanel};
+use crate::tui::layout::MainPanelLayout;
+
+/// Render the reports view
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
+    let layout = MainPanelLayout::new(area);
+
+    if app.reports_compare {
+        render_comparison(frame, app, layout.header, layout.content);
+        return;
+    }
+
+    let report = SpendingReport::generate(
+        app.storage,
+        app.current_period.start_date(),
+        app.current_period.end_date(),
+    )
+    .ok();
+
+    render_header(frame, app, report.as_ref(), layout.header);
+    render_spending_table(frame, app, report.as_ref(), layout.content);
+}
+
+/// Render the report header with totals
+fn render_header(frame: &mut Frame, app: &App, report: Option<&SpendingReport>, area: Rect) {
+    let block = Block::default()
+        .title(format!(" Spending - {} ", app.current_period))
+        .title_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::White));
+
+    let mut spans = vec![];
+    if let Some(report) = report {
+        spans.push(Span::styled("Spent: ", Style::default().fg(Color::White)));
+        spans.push(Span::styled(
+            format!("{}", report.total_spending.abs()),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+        spans.push(Span::raw("  │  "));
+        spans.push(Span::styled("Income: ", Style::default().fg(Color::White)));
+        spans.push(Span::styled(
+            format!("{}", report.total_income),
+            Style::default().fg(Color::Green),
+        ));
+
+        if report.has_uncategorized() {
+            spans.push(Span::raw("  │  "));
+            spans.push(Span::styled(
+                format!("⚠ {} uncategorized", report.uncategorized_spending.abs()),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ));
+        }
+    }
+
+    let paragraph = Paragraph::new(Line::from(spans)).block(block);
+    frame.render_widget(paragraph, area);
+}
+
+/// Render spending by group and category
+fn render_spending_table(
+    frame: &mut Frame,
+    app: &App,
+    report: Option<&SpendingReport>,
+    area: Rect,
+) {
+    let is_focused = app.focused_panel == FocusedPanel::Main;
+    let border_color = if is_focused { Color::Cyan } else { Color::Gray };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color));
+
+    let report = match report {
+        Some(report) if report.total_spending.is_negative() => report,
+        _ => {
+            let text = Paragraph::new("No spending in this period.")
+                .block(block)
+                .style(Style::default().fg(Color::Yellow));
+            frame.render_widget(text, area);
+            return;
+        }
+    };
+
+    let widths = [
+        Constraint::Min(20),    // Category
+        Constraint::Length(14), // Amount
+        Constraint::Length(8),  // Count
+        Constraint::Length(8),  // Percentage
+    ];
+
+    let header = Row::new(vec![
+        Cell::from("Category").style(Style::default().add_modifier(Modifier::BOLD)),
+        Cell::from("Amount").style(Style::default().add_modifier(Modifier::BOLD)),
+        Cell::from("Count").style(Style::default().add_modifier(Modifier::BOLD)),
+        Cell::from("%").style(Style::default().add_modifier(Modifier::BOLD)),
+    ])
+    .style(Style::default().fg(Color::Yellow))
+    .height(1);
+
+    let mut rows: Vec<Row> = Vec::new();
+
+    for group in &report.groups {
+        rows.push(
+            Row::new(vec![
+                Cell::from(group.group_name.clone()),
+                Cell::from(format!("{}", group.total_spending.abs())),
+                Cell::from(format!("{}", group.transaction_count)),
+                Cell::from(format!("{:.1}%", group.percentage)),
+            ])
+            .style(
+                Style::default()
+                    .fg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        );
+
+        for category in &group.categories {
+            rows.push(Row::new(vec![
+                Cell::from(format!("  {}", category.category_name)),
+                Cell::from(format!("{}", category.total_spending.abs())),
+                Cell::from(format!("{}", category.transaction_count)),
+                Cell::from(format!("{:.1}%", category.percentage)),
+            ]));
+        }
+    }
+
+    if report.has_uncategorized() {
+        rows.push(
+            Row::new(vec![
+                Cell::from("(Uncategorized) ⚠"),
+                Cell::from(format!("{}", report.uncategorized_spending.abs())),
+                Cell::from(format!("{}", report.uncategorized_count)),
+                Cell::from(format!("{:.1}%", report.uncategorized_percentage)),
+            ])
+            .style(
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        );
+    }
+
+    let table = Table::new(rows, widths).header(header).block(block);
+
+    frame.render_widget(table, area);
+}
+
+/// Render the current period compared with the previous one
+fn render_comparison(frame: &mut Frame, app: &App, header_area: Rect, content_area: Rect) {
+    let previous = app.current_period.prev();
+    let report = PeriodComparisonReport::generate(app.storage, &previous, &app.current_period).ok();
+
+    let header = Block::default()
+        .title(format!(
+            " Compare - {} vs {} ",
+            previous, app.current_period
+        ))
+        .title_style(
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(Color::White));
+
+    let mut spans = vec![];
+    if let Some(report) = &report {
+        let (activity_a, activity_b) = report.total_activity();
+        spans.push(Span::styled("Spent: ", Style::default().fg(Color::White)));
+        spans.push(Span::raw(format!("{} → {}", -activity_a, -activity_b)));
+        spans.push(Span::raw("  │  "));
+        spans.push(Span::styled(
+            format!("{}", activity_b - activity_a),
+            delta_style(activity_b - activity_a),
+        ));
+    }
+    frame.render_widget(Paragraph::new(Line::from(spans)).block(header), header_area);
+
+    let is_focused = app.focused_panel == FocusedPanel::Main;
+    let border_color = if is_focused { Color::Cyan } else { Color::Gray };
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .border_style(Style::default().fg(border_color));
+
+    let report = match report {
+        Some(report) if !report.rows.is_empty() => report,
+        _ => {
+            let text = Paragraph::new("Nothing budgeted or spent in either period.")
+                .block(block)
+                .style(Style::default().fg(Color::Yellow));
+            frame.render_widget(text, content_area);
+            return;
+        }
+    };
+
+    let widths = [
+        Constraint::Min(20),    // Category
+        Constraint::Length(12), // Budgeted A
+        Constraint::Length(12), // Budgeted B
+        Constraint::Length(12), // Activity A
+        Constraint::Length(12), // Activity B
+        Constraint::Length(12), // Change
+    ];
+
+    let bold = Style::default().add_modifier(Modifier::BOLD);
+    let header = Row::new(vec![
+        Cell::from("Category").style(bold),
+        Cell::from(format!("Bud {}", previous)).style(bold),
+        Cell::from(format!("Bud {}", app.current_period)).style(bold),
+        Cell::from(format!("Act {}", previous)).style(bold),
+        Cell::from(format!("Act {}", app.current_period)).style(bold),
+        Cell::from("Change").style(bold),
+    ])
+    .style(Style::default().fg(Color::Yellow))
+    .height(1);
+
+    let rows: Vec<Row> = report
+        .rows
+        .iter()
+        .map(|row| {
+            Row::new(vec![
+                Cell::from(row.category_name.clone()),
+                Cell::from(format!("{}", row.budgeted_a)),
+                Cell::from(format!("{}", row.budgeted_b)),
+                Cell::from(format!("{}", row.activity_a)),
+                Cell::from(format!("{}", row.activity_b)),
+                Cell::from(format!("{}", row.activity_delta()))
+                    .style(delta_style(row.activity_delta())),
+            ])
+        })
+        .collect();
+
+    let table = Table::new(rows, widths).header(header).block(block);
+    frame.render_widget(table, content_area);
+}
+
+/// Red when spending went up, green when it went down
+fn delta_style(activity_delta: Money) -> Style {
+    if activity_delta.is_negative() {
+        Style::default().fg(Color::Red)
+    } else if activity_delta.is_positive() {
+        Style::default().fg(Color::Green)
+    } else {
+        Style::default()
+    }
+}
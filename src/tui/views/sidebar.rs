@@ -2,6 +2,8 @@
 //!
 //! Shows account list and view switcher
 
+use std::time::Instant;
+
 use ratatui::{
     layout::Rect,
     style::{Color, Modifier, Style},
@@ -57,8 +59,15 @@ fn render_accounts(frame: &mut Frame, app: &mut App, area: Rect) {
         Color::White
     };
 
+    // Show the type-ahead query in place of the title while searching
+    let title = if app.sidebar_type_ahead.is_active(Instant::now()) {
+        format!(" Find: {}_ ", app.sidebar_type_ahead.query())
+    } else {
+        " Accounts ".to_string()
+    };
+
     let block = Block::default()
-        .title(" Accounts ")
+        .title(title)
         .borders(Borders::ALL)
         .border_style(Style::default().fg(border_color));
 
@@ -77,7 +86,7 @@ fn render_accounts(frame: &mut Frame, app: &mut App, area: Rect) {
     }
 
     // Build list items
-    let items: Vec<ListItem> = accounts
+    let mut items: Vec<ListItem> = accounts
         .iter()
         .map(|summary| {
             let balance_str = format!("{}", summary.balance);
@@ -102,6 +111,14 @@ fn render_accounts(frame: &mut Frame, app: &mut App, area: Rect) {
         })
         .collect();
 
+    // "All Accounts" pseudo-selection shows every account in the register
+    items.push(ListItem::new(Line::from(Span::styled(
+        "All Accounts",
+        Style::default()
+            .fg(Color::Cyan)
+            .add_modifier(Modifier::ITALIC),
+    ))));
+
     let list = List::new(items)
         .block(block)
         .highlight_style(
@@ -1,6 +1,6 @@
 //! Status bar view
 //!
-//! Shows Available to Budget, current balance, and key hints
+//! Shows Available to Budget, totals for the current view, and key hints
 
 use ratatui::{
     layout::Rect,
@@ -10,28 +10,28 @@ use ratatui::{
     Frame,
 };
 
-use crate::services::{AccountService, BudgetService};
-use crate::tui::app::App;
+use crate::models::Money;
+use crate::services::{AccountService, BudgetService, IncomeService};
+use crate::tui::app::{ActiveView, App};
 
 /// Render the status bar
-pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
-    // Get Available to Budget
-    let budget_service = BudgetService::new(app.storage);
-    let atb = budget_service
-        .get_available_to_budget(&app.current_period)
-        .unwrap_or_default();
-
-    // Get total balance if an account is selected
-    let balance_info = if let Some(account_id) = app.selected_account {
-        let account_service = AccountService::new(app.storage);
-        account_service.calculate_balance(account_id).ok()
-    } else {
-        None
-    };
-
+///
+/// `atb` is Available to Budget for the current period and `age_of_money`
+/// the age of money as of today, both worked out once per frame by the caller.
+pub fn render(frame: &mut Frame, app: &mut App, area: Rect, atb: Money, age_of_money: Option<u32>) {
     // Build status line
     let mut spans = vec![];
 
+    if app.storage.is_read_only() {
+        spans.push(Span::styled(
+            " READ-ONLY ",
+            Style::default()
+                .fg(Color::Black)
+                .bg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        ));
+    }
+
     // Available to Budget
     let atb_color = if atb.is_negative() {
         Color::Red
@@ -47,6 +47,44 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         Style::default().fg(atb_color).add_modifier(Modifier::BOLD),
     ));
 
+    if atb.is_negative() {
+        spans.push(Span::styled(
+            " ⚠ OVERBUDGETED",
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        ));
+    }
+
+    // Opt-in projection with expected income, styled apart from the cash figure
+    if app.settings.show_projected_available {
+        let income_service = IncomeService::new(app.storage);
+        let has_upcoming = income_service
+            .get_upcoming_income(&app.current_period)
+            .is_ok_and(|upcoming| !upcoming.is_zero());
+        if has_upcoming {
+            if let Ok(projected) = income_service.project_available_to_budget(&app.current_period) {
+                spans.push(Span::styled(
+                    " Projected: ",
+                    Style::default().fg(Color::White),
+                ));
+                spans.push(Span::styled(
+                    format!("{}", projected),
+                    Style::default()
+                        .fg(Color::Magenta)
+                        .add_modifier(Modifier::ITALIC),
+                ));
+            }
+        }
+    }
+
+    // Age of money, once there's enough spending to measure it
+    if let Some(days) = age_of_money {
+        spans.push(Span::styled(" Age: ", Style::default().fg(Color::White)));
+        spans.push(Span::styled(
+            format!("{} days", days),
+            Style::default().fg(Color::Cyan),
+        ));
+    }
+
     // Separator
     spans.push(Span::raw(" │ "));
 
@@ -56,19 +94,11 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
         Style::default().fg(Color::Cyan),
     ));
 
-    // Selected account balance
-    if let Some(balance) = balance_info {
-        spans.push(Span::raw(" │ "));
-        spans.push(Span::styled("Bal: ", Style::default().fg(Color::White)));
-        let balance_color = if balance.is_negative() {
-            Color::Red
-        } else {
-            Color::Green
-        };
-        spans.push(Span::styled(
-            format!("{}", balance),
-            Style::default().fg(balance_color),
-        ));
+    // Totals for what the view shows, recomputed every frame
+    match app.active_view {
+        ActiveView::Register => register_totals(app, &mut spans),
+        ActiveView::Budget => budget_totals(app, &mut spans),
+        _ => selected_balance(app, &mut spans),
     }
 
     // Status message if any
@@ -99,3 +129,103 @@ pub fn render(frame: &mut Frame, app: &mut App, area: Rect) {
 
     frame.render_widget(paragraph, area);
 }
+
+/// Style a balance green, or red when negative
+fn balance_style(amount: Money) -> Style {
+    if amount.is_negative() {
+        Style::default().fg(Color::Red)
+    } else {
+        Style::default().fg(Color::Green)
+    }
+}
+
+/// Push a " · label amount" pair
+fn push_total(spans: &mut Vec<Span<'_>>, label: &'static str, amount: Money, style: Style) {
+    spans.push(Span::styled(label, Style::default().fg(Color::White)));
+    spans.push(Span::styled(format!("{}", amount), style));
+}
+
+/// Transaction count and balances for the selected account's register
+fn register_totals(app: &App, spans: &mut Vec<Span<'_>>) {
+    spans.push(Span::raw(" │ "));
+
+    let account_service = AccountService::new(app.storage);
+    let summary = app
+        .selected_account
+        .and_then(|id| account_service.get(id).ok().flatten())
+        .and_then(|account| account_service.get_summary(&account).ok());
+
+    let Some(summary) = summary else {
+        // All Accounts: just the count
+        let count = app.register_transactions().len();
+        spans.push(Span::styled(
+            format!("{} transactions", count),
+            Style::default().fg(Color::White),
+        ));
+        return;
+    };
+
+    spans.push(Span::styled(
+        format!("{} transactions", summary.transaction_count),
+        Style::default().fg(Color::White),
+    ));
+    push_total(
+        spans,
+        " · cleared ",
+        summary.cleared_balance,
+        balance_style(summary.cleared_balance),
+    );
+    push_total(
+        spans,
+        " · uncleared ",
+        summary.uncleared_balance(),
+        Style::default().fg(Color::Yellow),
+    );
+    push_total(
+        spans,
+        " · balance ",
+        summary.balance,
+        balance_style(summary.balance).add_modifier(Modifier::BOLD),
+    );
+}
+
+/// Budgeted, activity, and available totals for the period
+fn budget_totals(app: &App, spans: &mut Vec<Span<'_>>) {
+    let Ok(overview) = BudgetService::new(app.storage).get_budget_overview(&app.current_period)
+    else {
+        return;
+    };
+
+    spans.push(Span::raw(" │ "));
+    push_total(
+        spans,
+        "Budgeted ",
+        overview.total_budgeted,
+        Style::default().fg(Color::Cyan),
+    );
+    push_total(
+        spans,
+        " · Activity ",
+        overview.total_activity,
+        balance_style(overview.total_activity),
+    );
+    push_total(
+        spans,
+        " · Available ",
+        overview.total_available,
+        balance_style(overview.total_available),
+    );
+}
+
+/// Balance of the selected account, if any
+fn selected_balance(app: &App, spans: &mut Vec<Span<'_>>) {
+    let Some(balance) = app
+        .selected_account
+        .and_then(|id| AccountService::new(app.storage).calculate_balance(id).ok())
+    else {
+        return;
+    };
+
+    spans.push(Span::raw(" │ "));
+    push_total(spans, "Bal: ", balance, balance_style(balance));
+}
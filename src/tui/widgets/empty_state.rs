@@ -0,0 +1,34 @@
+//! Empty-state panel
+//!
+//! Shown in place of an empty table, with a hint for getting started.
+
+use ratatui::{
+    layout::{Alignment, Rect},
+    style::{Color, Modifier, Style},
+    text::Line,
+    widgets::{Block, Paragraph},
+    Frame,
+};
+
+/// Render a message and a hint centered inside a bordered block
+pub fn render_empty_state(frame: &mut Frame, area: Rect, block: Block, message: &str, hint: &str) {
+    let inner_height = area.height.saturating_sub(2);
+    let padding = inner_height.saturating_sub(2) / 2;
+
+    let mut lines: Vec<Line> = (0..padding).map(|_| Line::from("")).collect();
+    lines.push(Line::styled(
+        message.to_string(),
+        Style::default()
+            .fg(Color::Yellow)
+            .add_modifier(Modifier::BOLD),
+    ));
+    lines.push(Line::styled(
+        hint.to_string(),
+        Style::default().fg(Color::Gray),
+    ));
+
+    let paragraph = Paragraph::new(lines)
+        .block(block)
+        .alignment(Alignment::Center);
+    frame.render_widget(paragraph, area);
+}
@@ -195,6 +195,31 @@ impl ErrorInfo {
                 ],
                 None,
             ),
+            EnvelopeError::MissingExchangeRate { from, to } => (
+                "Missing Exchange Rate".to_string(),
+                format!("No exchange rate from {} to {} is configured", from, to),
+                vec!["Add a rate with 'envelope config --rate FROM/TO=RATE'".to_string()],
+                None,
+            ),
+            EnvelopeError::ReadOnly => (
+                "Read-Only Mode".to_string(),
+                "The budget was opened with --read-only, so changes can't be saved".to_string(),
+                vec!["Run without --read-only to make changes".to_string()],
+                None,
+            ),
+            EnvelopeError::Corrupted {
+                path,
+                offset,
+                message,
+            } => (
+                "Corrupted Data File".to_string(),
+                format!("{} could not be read", path.display()),
+                vec![
+                    "Restore the file from backup with 'envelope doctor --repair'".to_string(),
+                    "Restore everything with 'envelope backup restore'".to_string(),
+                ],
+                Some(format!("byte {}: {}", offset, message)),
+            ),
         };
 
         Self {
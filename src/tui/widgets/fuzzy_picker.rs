@@ -0,0 +1,255 @@
+//! Fuzzy picker widget
+//!
+//! A type-to-filter list used wherever the user picks one item from many
+//! (categories, accounts, payees). Matching is case-insensitive: prefix
+//! matches rank first, then substring matches, then in-order subsequence
+//! matches, so "grc" still finds "Groceries". The command palette keeps its
+//! own substring filter over names and descriptions.
+
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use ratatui::{
+    layout::Rect,
+    style::{Color, Modifier, Style},
+    text::{Line, Span},
+    widgets::{List, ListItem, ListState, Paragraph},
+    Frame,
+};
+
+/// Score how well `query` matches `candidate`, or None if it doesn't
+///
+/// Higher is better. An empty query matches everything equally.
+pub fn fuzzy_score(query: &str, candidate: &str) -> Option<u32> {
+    if query.is_empty() {
+        return Some(0);
+    }
+
+    let query = query.to_lowercase();
+    let candidate = candidate.to_lowercase();
+
+    if candidate.starts_with(&query) {
+        return Some(300);
+    }
+    if candidate.contains(&query) {
+        return Some(200);
+    }
+
+    // Subsequence match: every query character appears in order
+    let mut chars = candidate.chars();
+    let mut skipped = 0u32;
+    for q in query.chars() {
+        loop {
+            match chars.next() {
+                Some(c) if c == q => break,
+                Some(_) => skipped += 1,
+                None => return None,
+            }
+        }
+    }
+
+    Some(100u32.saturating_sub(skipped))
+}
+
+/// Filter and rank items by how well their key matches the query
+///
+/// Items with equal scores keep their original order.
+pub fn fuzzy_filter<'a, T>(items: &'a [T], query: &str, key: impl Fn(&T) -> &str) -> Vec<&'a T> {
+    let mut scored: Vec<(u32, &T)> = items
+        .iter()
+        .filter_map(|item| fuzzy_score(query, key(item)).map(|score| (score, item)))
+        .collect();
+    scored.sort_by_key(|(score, _)| std::cmp::Reverse(*score));
+    scored.into_iter().map(|(_, item)| item).collect()
+}
+
+/// Outcome of a key press in a picker
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PickerAction {
+    /// The item at this index of the filtered list was chosen
+    Select(usize),
+    /// The user backed out of the picker
+    Cancel,
+    /// The key changed the query or selection
+    Handled,
+    /// The key is not for the picker
+    Ignored,
+}
+
+/// State for a fuzzy picker
+#[derive(Debug, Clone, Default)]
+pub struct FuzzyPicker {
+    /// Current filter text
+    pub query: String,
+    /// Selected index within the filtered list
+    pub selected: usize,
+}
+
+impl FuzzyPicker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Clear the query and selection
+    pub fn reset(&mut self) {
+        *self = Self::default();
+    }
+
+    /// Filter items with the current query
+    pub fn filter<'a, T>(&self, items: &'a [T], key: impl Fn(&T) -> &str) -> Vec<&'a T> {
+        fuzzy_filter(items, &self.query, key)
+    }
+
+    /// Handle a key press given how many items currently match
+    ///
+    /// Typing filters, Up/Down move, Enter selects. Esc clears a non-empty
+    /// query first and cancels on the next press.
+    pub fn handle_key(&mut self, key: KeyEvent, match_count: usize) -> PickerAction {
+        match key.code {
+            KeyCode::Esc => {
+                if self.query.is_empty() {
+                    PickerAction::Cancel
+                } else {
+                    self.reset();
+                    PickerAction::Handled
+                }
+            }
+            KeyCode::Enter => {
+                if match_count == 0 {
+                    PickerAction::Handled
+                } else {
+                    PickerAction::Select(self.selected.min(match_count - 1))
+                }
+            }
+            KeyCode::Up => {
+                self.selected = self.selected.saturating_sub(1);
+                PickerAction::Handled
+            }
+            KeyCode::Down => {
+                if self.selected + 1 < match_count {
+                    self.selected += 1;
+                }
+                PickerAction::Handled
+            }
+            KeyCode::Backspace => {
+                self.query.pop();
+                self.selected = 0;
+                PickerAction::Handled
+            }
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL) => {
+                self.query.push(c);
+                self.selected = 0;
+                PickerAction::Handled
+            }
+            _ => PickerAction::Ignored,
+        }
+    }
+
+    /// Render the query line and the filtered labels
+    ///
+    /// `labels` must already be filtered with [`FuzzyPicker::filter`]. The
+    /// first row of `area` holds the query; the rest holds the list.
+    pub fn render(&self, frame: &mut Frame, area: Rect, labels: &[String]) {
+        if area.height == 0 {
+            return;
+        }
+
+        let query_area = Rect { height: 1, ..area };
+        let list_area = Rect {
+            y: area.y + 1,
+            height: area.height.saturating_sub(1),
+            ..area
+        };
+
+        let query_line = if self.query.is_empty() {
+            Line::from(vec![
+                Span::styled("  / ", Style::default().fg(Color::Cyan)),
+                Span::styled("type to filter", Style::default().fg(Color::DarkGray)),
+            ])
+        } else {
+            Line::from(vec![
+                Span::styled("  / ", Style::default().fg(Color::Cyan)),
+                Span::styled(self.query.clone(), Style::default().fg(Color::White)),
+                Span::styled("_", Style::default().fg(Color::Cyan)),
+            ])
+        };
+        frame.render_widget(Paragraph::new(query_line), query_area);
+
+        if labels.is_empty() {
+            let empty = Paragraph::new("  No matches").style(Style::default().fg(Color::Yellow));
+            frame.render_widget(empty, list_area);
+            return;
+        }
+
+        let items: Vec<ListItem> = labels
+            .iter()
+            .map(|label| ListItem::new(Line::from(format!("  {}", label))))
+            .collect();
+
+        let list = List::new(items)
+            .highlight_style(
+                Style::default()
+                    .bg(Color::DarkGray)
+                    .add_modifier(Modifier::BOLD),
+            )
+            .highlight_symbol("▶ ");
+
+        let mut state = ListState::default();
+        state.select(Some(self.selected.min(labels.len() - 1)));
+        frame.render_stateful_widget(list, list_area, &mut state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode) -> KeyEvent {
+        KeyEvent::new(code, KeyModifiers::NONE)
+    }
+
+    #[test]
+    fn test_fuzzy_score_ranking() {
+        assert_eq!(fuzzy_score("", "Groceries"), Some(0));
+        assert_eq!(fuzzy_score("gro", "Groceries"), Some(300));
+        assert_eq!(fuzzy_score("cer", "Groceries"), Some(200));
+        assert!(fuzzy_score("grc", "Groceries").unwrap() < 200);
+        assert_eq!(fuzzy_score("xyz", "Groceries"), None);
+    }
+
+    #[test]
+    fn test_fuzzy_filter_orders_by_score() {
+        let names = ["Dining Out", "Rent", "Groceries", "Gas"];
+        let matches = fuzzy_filter(&names, "g", |n| n);
+        assert_eq!(matches, vec![&"Groceries", &"Gas", &"Dining Out"]);
+    }
+
+    #[test]
+    fn test_picker_keys() {
+        let mut picker = FuzzyPicker::new();
+
+        assert_eq!(
+            picker.handle_key(key(KeyCode::Char('g')), 3),
+            PickerAction::Handled
+        );
+        assert_eq!(picker.query, "g");
+
+        picker.handle_key(key(KeyCode::Down), 3);
+        picker.handle_key(key(KeyCode::Down), 3);
+        picker.handle_key(key(KeyCode::Down), 3);
+        assert_eq!(picker.selected, 2);
+        assert_eq!(
+            picker.handle_key(key(KeyCode::Enter), 3),
+            PickerAction::Select(2)
+        );
+
+        // Esc clears the query first, then cancels
+        assert_eq!(
+            picker.handle_key(key(KeyCode::Esc), 3),
+            PickerAction::Handled
+        );
+        assert!(picker.query.is_empty());
+        assert_eq!(
+            picker.handle_key(key(KeyCode::Esc), 3),
+            PickerAction::Cancel
+        );
+    }
+}
@@ -107,6 +107,12 @@ impl TextInput {
         self.cursor = self.content.len();
     }
 
+    /// Replace the content, moving the cursor to the end
+    pub fn set_value(&mut self, content: impl Into<String>) {
+        self.content = content.into();
+        self.cursor = self.content.len();
+    }
+
     /// Clear the content
     pub fn clear(&mut self) {
         self.content.clear();
@@ -117,6 +123,24 @@ impl TextInput {
     pub fn value(&self) -> &str {
         &self.content
     }
+
+    /// Build a single line showing the content and cursor, for use inside
+    /// table cells where the widget can't be rendered directly
+    pub fn to_line(&self) -> Line<'static> {
+        let (before, rest) = self.content.split_at(self.cursor.min(self.content.len()));
+        let mut chars = rest.chars();
+        let cursor_char = chars.next().unwrap_or('_');
+        let after: String = chars.collect();
+
+        Line::from(vec![
+            Span::styled(before.to_string(), Style::default().fg(Color::White)),
+            Span::styled(
+                cursor_char.to_string(),
+                Style::default().fg(Color::Black).bg(Color::Cyan),
+            ),
+            Span::styled(after, Style::default().fg(Color::White)),
+        ])
+    }
 }
 
 impl Default for TextInput {
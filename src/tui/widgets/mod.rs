@@ -2,11 +2,19 @@
 //!
 //! Contains custom widgets for common UI elements
 
+pub mod empty_state;
 pub mod error_dialog;
+pub mod fuzzy_picker;
 pub mod input;
 pub mod notification;
+pub mod scrollbar;
+pub mod type_ahead;
 
 // Re-export commonly used widgets
+pub use empty_state::render_empty_state;
 pub use error_dialog::{error_dialog_area, ErrorDialog, ErrorInfo};
+pub use fuzzy_picker::{fuzzy_filter, fuzzy_score, FuzzyPicker, PickerAction};
 pub use input::TextInput;
 pub use notification::{Notification, NotificationQueue, NotificationType, NotificationWidget};
+pub use scrollbar::{follow_selection, render_scrollbar, VERTICAL_BORDERS};
+pub use type_ahead::{type_ahead_match, TypeAhead};
@@ -6,7 +6,7 @@ use ratatui::{
     buffer::Buffer,
     layout::Rect,
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Clear, Paragraph, Widget},
+    widgets::{Block, Borders, Clear, Paragraph, Widget, Wrap},
 };
 
 /// Type of notification
@@ -131,7 +131,12 @@ impl<'a> NotificationWidget<'a> {
 
 impl<'a> Widget for NotificationWidget<'a> {
     fn render(self, area: Rect, buf: &mut Buffer) {
-        let color = self.notification.notification_type.color();
+        // Fade to gray in the last quarter of the display time
+        let color = if self.notification.remaining_fraction() < 0.25 {
+            Color::DarkGray
+        } else {
+            self.notification.notification_type.color()
+        };
         let icon = self.notification.notification_type.icon();
         let title = self.notification.notification_type.title();
 
@@ -146,6 +151,7 @@ impl<'a> Widget for NotificationWidget<'a> {
 
         let paragraph = Paragraph::new(self.notification.message.as_str())
             .style(Style::default().fg(Color::White))
+            .wrap(Wrap { trim: true })
             .block(block);
 
         paragraph.render(area, buf);
@@ -179,6 +185,17 @@ impl NotificationQueue {
         self.notifications.first()
     }
 
+    /// Unexpired notifications, newest first, at most `max` of them
+    ///
+    /// Lets recent results stack on screen instead of replacing each other.
+    pub fn recent(&self, max: usize) -> impl Iterator<Item = &Notification> {
+        self.notifications
+            .iter()
+            .rev()
+            .filter(|n| !n.is_expired())
+            .take(max)
+    }
+
     /// Check if there are any notifications
     pub fn is_empty(&self) -> bool {
         self.notifications.is_empty()
@@ -224,5 +241,10 @@ mod tests {
 
         assert_eq!(queue.len(), 2);
         assert_eq!(queue.current().unwrap().message, "First");
+
+        queue.push(Notification::error("Expired").with_duration(0));
+        queue.push(Notification::warning("Third"));
+        let recent: Vec<&str> = queue.recent(2).map(|n| n.message.as_str()).collect();
+        assert_eq!(recent, vec!["Third", "Second"]);
     }
 }
@@ -0,0 +1,96 @@
+//! Scrollbar helpers
+//!
+//! Keeps a persisted scroll offset following the selection and draws a
+//! vertical scrollbar for lists and tables that overflow their area.
+
+use ratatui::{
+    layout::{Margin, Rect},
+    style::{Color, Style},
+    widgets::{Scrollbar, ScrollbarOrientation, ScrollbarState},
+    Frame,
+};
+
+/// Margin that excludes a block's top and bottom borders from a scrollbar
+pub const VERTICAL_BORDERS: Margin = Margin {
+    vertical: 1,
+    horizontal: 0,
+};
+
+/// Rows kept visible above and below the selection while scrolling
+pub const SCROLL_MARGIN: usize = 2;
+
+/// Compute the scroll offset that keeps `selected` visible
+///
+/// The offset only moves when the selection comes within
+/// [`SCROLL_MARGIN`] rows of the top or bottom edge, so the list doesn't
+/// jump on every key press.
+pub fn follow_selection(offset: usize, selected: usize, visible: usize, total: usize) -> usize {
+    if visible == 0 || total <= visible {
+        return 0;
+    }
+
+    let max_offset = total - visible;
+    let margin = SCROLL_MARGIN.min(visible.saturating_sub(1) / 2);
+
+    let offset = if selected < offset + margin {
+        selected.saturating_sub(margin)
+    } else if selected + margin >= offset + visible {
+        selected + margin + 1 - visible
+    } else {
+        offset
+    };
+
+    offset.min(max_offset)
+}
+
+/// Render a vertical scrollbar along the right edge of `area`
+///
+/// `position` is the selected item and `total` the number of items. Nothing
+/// is drawn when everything fits in `visible` rows. For bordered widgets,
+/// pass the area inside the top and bottom borders.
+pub fn render_scrollbar(
+    frame: &mut Frame,
+    area: Rect,
+    position: usize,
+    total: usize,
+    visible: usize,
+) {
+    if total <= visible {
+        return;
+    }
+
+    let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
+        .begin_symbol(Some("▲"))
+        .end_symbol(Some("▼"))
+        .track_style(Style::default().fg(Color::DarkGray))
+        .thumb_style(Style::default().fg(Color::Cyan));
+
+    let mut state = ScrollbarState::new(total)
+        .position(position)
+        .viewport_content_length(visible);
+
+    frame.render_stateful_widget(scrollbar, area, &mut state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_follow_selection() {
+        // Everything fits
+        assert_eq!(follow_selection(5, 3, 10, 8), 0);
+
+        // Moving within the window keeps the offset
+        assert_eq!(follow_selection(0, 5, 10, 100), 0);
+
+        // Nearing the bottom edge scrolls down
+        assert_eq!(follow_selection(0, 8, 10, 100), 1);
+
+        // Nearing the top edge scrolls up
+        assert_eq!(follow_selection(20, 21, 10, 100), 19);
+
+        // Never scrolls past the end
+        assert_eq!(follow_selection(0, 99, 10, 100), 90);
+    }
+}
@@ -0,0 +1,109 @@
+//! Type-ahead selection
+//!
+//! Collects typed characters into a short-lived query, used to jump to the
+//! first list entry whose name matches. The query lapses after a pause.
+
+use std::time::{Duration, Instant};
+
+/// How long a type-ahead query stays active after the last keystroke
+pub const TYPE_AHEAD_TIMEOUT: Duration = Duration::from_millis(1500);
+
+/// A short-lived type-ahead query
+#[derive(Debug, Clone, Default)]
+pub struct TypeAhead {
+    query: String,
+    last_input: Option<Instant>,
+}
+
+impl TypeAhead {
+    /// Start a new, empty query
+    pub fn start(&mut self, now: Instant) {
+        self.query.clear();
+        self.last_input = Some(now);
+    }
+
+    /// Check if a query is in progress and hasn't timed out
+    pub fn is_active(&self, now: Instant) -> bool {
+        self.last_input
+            .is_some_and(|last| now.duration_since(last) < TYPE_AHEAD_TIMEOUT)
+    }
+
+    /// Append a character, restarting the timeout
+    pub fn push(&mut self, c: char, now: Instant) {
+        self.query.push(c);
+        self.last_input = Some(now);
+    }
+
+    /// Remove the last character, restarting the timeout
+    pub fn pop(&mut self, now: Instant) {
+        self.query.pop();
+        self.last_input = Some(now);
+    }
+
+    /// End the query
+    pub fn clear(&mut self) {
+        self.query.clear();
+        self.last_input = None;
+    }
+
+    /// End the query if it has timed out
+    pub fn expire(&mut self, now: Instant) {
+        if self.last_input.is_some() && !self.is_active(now) {
+            self.clear();
+        }
+    }
+
+    /// The characters typed so far
+    pub fn query(&self) -> &str {
+        &self.query
+    }
+}
+
+/// Index of the first name matching a type-ahead query
+///
+/// Names starting with the query win; otherwise the first name containing
+/// it is used. Matching is case-insensitive.
+pub fn type_ahead_match<'a>(
+    names: impl IntoIterator<Item = &'a str>,
+    query: &str,
+) -> Option<usize> {
+    if query.is_empty() {
+        return None;
+    }
+
+    let query = query.to_lowercase();
+    let names: Vec<String> = names.into_iter().map(str::to_lowercase).collect();
+
+    names
+        .iter()
+        .position(|name| name.starts_with(&query))
+        .or_else(|| names.iter().position(|name| name.contains(&query)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_type_ahead() {
+        let names = ["Ally Savings", "Chase Checking", "Chase Sapphire", "Cash"];
+
+        assert_eq!(type_ahead_match(names, "ch"), Some(1));
+        assert_eq!(type_ahead_match(names, "CHASE S"), Some(2));
+        assert_eq!(type_ahead_match(names, "sav"), Some(0));
+        assert_eq!(type_ahead_match(names, "amex"), None);
+
+        let start = Instant::now();
+        let mut type_ahead = TypeAhead::default();
+        assert!(!type_ahead.is_active(start));
+
+        type_ahead.start(start);
+        type_ahead.push('c', start);
+        assert!(type_ahead.is_active(start + Duration::from_millis(500)));
+
+        let later = start + TYPE_AHEAD_TIMEOUT;
+        assert!(!type_ahead.is_active(later));
+        type_ahead.expire(later);
+        assert_eq!(type_ahead.query(), "");
+    }
+}